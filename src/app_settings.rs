@@ -0,0 +1,274 @@
+//! Obsidian's own settings in `.obsidian/app.json` - only the fields ok reads - and the link
+//! format they give the links ok writes.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::constants::APP_SETTINGS_FILE;
+use crate::constants::CURRENT_FOLDER;
+use crate::constants::FORWARD_SLASH;
+use crate::constants::MARKDOWN_LINK_SPACE;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::OBSIDIAN_FOLDER;
+use crate::constants::PARENT_FOLDER;
+use crate::constants::SPACE;
+use crate::note_template;
+use crate::wikilink::ToWikilink;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppSettings {
+    #[serde(default)]
+    pub attachment_folder_path: String,
+    #[serde(default)]
+    pub new_link_format:        NewLinkFormat,
+    #[serde(default)]
+    pub use_markdown_links:     bool,
+}
+
+impl AppSettings {
+    /// The settings of the vault at `obsidian_path` - Obsidian's defaults when `app.json` is
+    /// missing or can't be read.
+    pub(crate) fn read(obsidian_path: &Path) -> Self {
+        note_template::read_settings(&obsidian_path.join(OBSIDIAN_FOLDER).join(APP_SETTINGS_FILE))
+            .unwrap_or_default()
+    }
+
+    /// Where "Default location for new attachments" puts them.
+    pub(crate) fn attachment_folder(&self) -> AttachmentFolder {
+        let setting = self.attachment_folder_path.trim();
+        if let Some(subfolder) = setting.strip_prefix(CURRENT_FOLDER) {
+            return AttachmentFolder::BesideNote(subfolder.trim_matches(FORWARD_SLASH).to_string());
+        }
+        match setting.trim_matches(FORWARD_SLASH) {
+            "" => AttachmentFolder::VaultRoot,
+            folder => AttachmentFolder::Folder(folder.to_string()),
+        }
+    }
+
+    pub(crate) const fn link_format(&self) -> LinkFormat {
+        LinkFormat {
+            path:     self.new_link_format,
+            markdown: self.use_markdown_links,
+        }
+    }
+}
+
+/// The folder Obsidian puts new attachments in - where a duplicate image is kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum AttachmentFolder {
+    /// `attachmentFolderPath` is empty or `/`.
+    #[default]
+    VaultRoot,
+    /// A folder named from the vault root.
+    Folder(String),
+    /// `./`, or a `./` subfolder: the folder of the note that links to the attachment.
+    BesideNote(String),
+}
+
+impl AttachmentFolder {
+    /// The vault-relative folder for an attachment of a note in the vault-relative
+    /// `note_folder` - empty for the vault root.
+    pub(crate) fn for_note(&self, note_folder: &str) -> String {
+        match self {
+            Self::VaultRoot => String::new(),
+            Self::Folder(folder) => folder.clone(),
+            Self::BesideNote(subfolder) => [note_folder, subfolder]
+                .into_iter()
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join(&FORWARD_SLASH.to_string()),
+        }
+    }
+
+    /// Whether the vault-relative `image_folder` is where Obsidian would have put an image the
+    /// notes in `note_folders` link to - for `BesideNote`, beside any one of them.
+    pub(crate) fn holds<'a>(
+        &self,
+        image_folder: &str,
+        mut note_folders: impl Iterator<Item = &'a str>,
+    ) -> bool {
+        match self {
+            Self::VaultRoot | Self::Folder(_) => self.for_note("") == image_folder,
+            Self::BesideNote(_) => {
+                note_folders.any(|note_folder| self.for_note(note_folder) == image_folder)
+            },
+        }
+    }
+}
+
+/// Obsidian's "New link format": how much of a note's path a new link to it names.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum NewLinkFormat {
+    /// The note's name, with as much of its path as it takes to tell it from another note.
+    #[default]
+    Shortest,
+    /// The path from the folder of the note the link is in.
+    Relative,
+    /// The path from the vault root.
+    Absolute,
+}
+
+/// How Obsidian writes a new link - the default, shortest wikilinks, is how ok has always
+/// written them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct LinkFormat {
+    pub path:     NewLinkFormat,
+    /// "Use [[Wikilinks]]" is off, so links are written `[text](path.md)`.
+    pub markdown: bool,
+}
+
+impl LinkFormat {
+    /// A link showing `display_text`, written in a note in `source_folder` to the note at the
+    /// vault-relative `note_path` (without `.md`). `target` is what a shortest link names - the
+    /// stem, or a path where the link already needs one.
+    pub(crate) fn link(
+        self,
+        target: &str,
+        note_path: &str,
+        display_text: &str,
+        source_folder: &str,
+    ) -> String {
+        let target = match self.path {
+            NewLinkFormat::Shortest => target.to_string(),
+            NewLinkFormat::Relative => relative_path(source_folder, note_path),
+            NewLinkFormat::Absolute => note_path.to_string(),
+        };
+
+        if self.markdown {
+            format!(
+                "[{display_text}]({}{MARKDOWN_SUFFIX})",
+                target.replace(SPACE, MARKDOWN_LINK_SPACE)
+            )
+        } else {
+            target.to_aliased_wikilink(display_text)
+        }
+    }
+}
+
+/// `note_path` as seen from `source_folder`, both vault-relative: `../projects/Plan` from
+/// `daily`, or `Plan` from `projects`.
+fn relative_path(source_folder: &str, note_path: &str) -> String {
+    let from: Vec<&str> = source_folder
+        .split(FORWARD_SLASH)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let to: Vec<&str> = note_path.split(FORWARD_SLASH).collect();
+    // The note's own name is never a shared folder.
+    let shared = from
+        .iter()
+        .zip(&to[..to.len() - 1])
+        .take_while(|(source, target)| source == target)
+        .count();
+
+    let mut segments = vec![PARENT_FOLDER; from.len() - shared];
+    segments.extend(&to[shared..]);
+    segments.join(&FORWARD_SLASH.to_string())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::iter;
+
+    use tempfile::TempDir;
+
+    use super::AppSettings;
+    use super::AttachmentFolder;
+    use super::LinkFormat;
+    use super::NewLinkFormat;
+    use super::relative_path;
+
+    #[test]
+    fn test_link_format_read_from_app_json() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(
+            AppSettings::read(temp_dir.path()).link_format(),
+            LinkFormat::default(),
+            "Obsidian's defaults without app.json"
+        );
+
+        let settings = temp_dir.path().join(".obsidian");
+        fs::create_dir_all(&settings).unwrap();
+        fs::write(
+            settings.join("app.json"),
+            r#"{"newLinkFormat": "relative", "useMarkdownLinks": true}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            AppSettings::read(temp_dir.path()).link_format(),
+            LinkFormat {
+                path:     NewLinkFormat::Relative,
+                markdown: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_attachment_folder_setting() {
+        let attachment_folder = |setting: &str| {
+            AppSettings {
+                attachment_folder_path: setting.to_string(),
+                ..AppSettings::default()
+            }
+            .attachment_folder()
+        };
+
+        assert_eq!(attachment_folder(""), AttachmentFolder::VaultRoot);
+        assert_eq!(attachment_folder("/"), AttachmentFolder::VaultRoot);
+        assert_eq!(
+            attachment_folder("conf/media"),
+            AttachmentFolder::Folder("conf/media".to_string())
+        );
+        assert_eq!(attachment_folder("./").for_note("daily"), "daily");
+        assert_eq!(attachment_folder("./").for_note(""), "");
+        assert_eq!(
+            attachment_folder("./assets").for_note("daily"),
+            "daily/assets"
+        );
+        assert!(attachment_folder("conf/media").holds("conf/media", iter::once("daily")));
+        assert!(attachment_folder("./assets").holds("daily/assets", ["", "daily"].into_iter()));
+        assert!(!attachment_folder("./assets").holds("assets", iter::once("daily")));
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(relative_path("daily", "projects/Plan"), "../projects/Plan");
+        assert_eq!(relative_path("projects", "projects/Plan"), "Plan");
+        assert_eq!(relative_path("", "projects/Plan"), "projects/Plan");
+        assert_eq!(relative_path("a/b", "a/c/Plan"), "../c/Plan");
+        assert_eq!(relative_path("Plan", "Plan"), "../Plan");
+    }
+
+    #[test]
+    fn test_link_follows_obsidian_settings() {
+        let link = |path, markdown| {
+            LinkFormat { path, markdown }.link("Plan", "projects/Plan", "the plan", "daily")
+        };
+
+        assert_eq!(link(NewLinkFormat::Shortest, false), "[[Plan|the plan]]");
+        assert_eq!(
+            link(NewLinkFormat::Absolute, false),
+            "[[projects/Plan|the plan]]"
+        );
+        assert_eq!(
+            link(NewLinkFormat::Relative, false),
+            "[[../projects/Plan|the plan]]"
+        );
+        assert_eq!(link(NewLinkFormat::Shortest, true), "[the plan](Plan.md)");
+        assert_eq!(
+            LinkFormat {
+                path:     NewLinkFormat::Absolute,
+                markdown: true,
+            }
+            .link("Big Plan", "projects/Big Plan", "Big Plan", ""),
+            "[Big Plan](projects/Big%20Plan.md)"
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! Criterion benchmarks for the core pipelines, run by `ok bench` when the `bench` feature is
+//! on. Each vault-backed benchmark runs against generated vaults of every size in
+//! `BENCH_VAULT_SIZES`, so a change can be judged by how it scales as well as how fast it is.
+#![allow(
+    clippy::significant_drop_tightening,
+    reason = "a benchmark group and criterion itself must live until their results are reported"
+)]
+
+use std::error::Error;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::path::PathBuf;
+
+use criterion::BatchSize;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use tempfile::TempDir;
+
+use crate::constants::BENCH_FRONTMATTER;
+use crate::constants::BENCH_GROUP_BACK_POPULATE_MATCHING;
+use crate::constants::BENCH_GROUP_IMAGE_HASHING;
+use crate::constants::BENCH_GROUP_REPORT_TABLES;
+use crate::constants::BENCH_GROUP_WIKILINK_EXTRACTION;
+use crate::constants::BENCH_GROUP_YAML_ROUND_TRIP;
+use crate::constants::BENCH_OUTPUT_FOLDER;
+use crate::constants::BENCH_REPORT_HEADERS;
+use crate::constants::BENCH_USAGE;
+use crate::constants::BENCH_VAULT_SIZES;
+use crate::constants::CACHE_FILE;
+use crate::constants::MARKDOWN_EXTENSION;
+use crate::constants::PNG_EXTENSION;
+use crate::frontmatter::FrontMatter;
+use crate::markdown_file::MarkdownFile;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::OutputFileWriter;
+use crate::sha256_cache::Sha256Cache;
+use crate::validated_config::ValidatedConfig;
+use crate::validated_config::ValidatedConfigBuilder;
+use crate::vault_generator;
+use crate::vault_generator::VaultSpec;
+use crate::wikilink;
+use crate::wikilink::Wikilink;
+use crate::yaml_frontmatter::YamlFrontMatter;
+
+/// A generated vault and the config that scans it. The vault is removed when this is dropped.
+struct BenchVault {
+    _temp_dir:        TempDir,
+    validated_config: ValidatedConfig,
+    note_count:       usize,
+    notes:            Vec<PathBuf>,
+    images:           Vec<PathBuf>,
+}
+
+impl BenchVault {
+    fn generate(note_count: usize) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let temp_dir = TempDir::new()?;
+        let spec = VaultSpec {
+            note_count,
+            ..VaultSpec::default()
+        };
+        vault_generator::generate_vault(temp_dir.path(), &spec)?;
+
+        let mut builder = ValidatedConfigBuilder::default();
+        builder.obsidian_path(temp_dir.path().to_path_buf());
+        builder.output_folder(temp_dir.path().join(BENCH_OUTPUT_FOLDER));
+        let validated_config = builder.build()?;
+
+        let (notes, images) = files_by_extension(temp_dir.path())?;
+        Ok(Self {
+            _temp_dir: temp_dir,
+            validated_config,
+            note_count,
+            notes,
+            images,
+        })
+    }
+
+    fn lines(&self) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+        let mut lines = Vec::new();
+        for note in &self.notes {
+            lines.extend(fs::read_to_string(note)?.lines().map(String::from));
+        }
+        Ok(lines)
+    }
+}
+
+/// Splits the files directly in `folder` into notes and images, each in path order.
+fn files_by_extension(
+    folder: &Path,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>), Box<dyn Error + Send + Sync>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(folder)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    paths.sort();
+    let has_extension = |path: &PathBuf, extension: &str| {
+        path.extension()
+            .is_some_and(|found| found.eq_ignore_ascii_case(extension))
+    };
+    let notes = paths
+        .iter()
+        .filter(|path| has_extension(path, MARKDOWN_EXTENSION))
+        .cloned()
+        .collect();
+    let images = paths
+        .into_iter()
+        .filter(|path| has_extension(path, PNG_EXTENSION))
+        .collect();
+    Ok((notes, images))
+}
+
+/// Runs every benchmark, or only those whose id matches the optional regex filter.
+pub(crate) fn run(args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let filter = match args {
+        [] => None,
+        [filter] => Some(filter.as_str()),
+        _ => return Err(BENCH_USAGE.into()),
+    };
+
+    let vaults = BENCH_VAULT_SIZES
+        .iter()
+        .map(|&note_count| BenchVault::generate(note_count))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut criterion = filter.map_or_else(Criterion::default, |filter| {
+        Criterion::default().with_filter(filter)
+    });
+
+    bench_wikilink_extraction(&mut criterion, &vaults)?;
+    bench_back_populate_matching(&mut criterion, &vaults)?;
+    bench_image_hashing(&mut criterion, &vaults);
+    bench_yaml_round_trip(&mut criterion)?;
+    bench_report_tables(&mut criterion, &vaults)?;
+
+    criterion.final_summary();
+    Ok(())
+}
+
+fn bench_wikilink_extraction(
+    criterion: &mut Criterion,
+    vaults: &[BenchVault],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let vault_lines = vaults
+        .iter()
+        .map(BenchVault::lines)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut group = criterion.benchmark_group(BENCH_GROUP_WIKILINK_EXTRACTION);
+    for (vault, lines) in vaults.iter().zip(&vault_lines) {
+        group.throughput(Throughput::Elements(lines.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(vault.note_count),
+            lines,
+            |bencher, lines| {
+                bencher.iter(|| {
+                    for line in lines {
+                        black_box(wikilink::extract_wikilinks(line));
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+    Ok(())
+}
+
+/// Times the Aho-Corasick scan of every note against the vault's wikilinks, on fresh copies of
+/// the scanned notes so matches found by earlier iterations don't accumulate.
+fn bench_back_populate_matching(
+    criterion: &mut Criterion,
+    vaults: &[BenchVault],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let repositories = vaults
+        .iter()
+        .map(|vault| ObsidianRepository::new(&vault.validated_config))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut group = criterion.benchmark_group(BENCH_GROUP_BACK_POPULATE_MATCHING);
+    for (vault, repository) in vaults.iter().zip(&repositories) {
+        let Some(automaton) = repository.wikilinks_automaton.as_ref() else {
+            continue;
+        };
+        let sorted_wikilinks: Vec<&Wikilink> = repository.wikilinks_sorted.iter().collect();
+        let notes: Vec<MarkdownFile> = repository.markdown_files.iter().cloned().collect();
+
+        group.throughput(Throughput::Elements(vault.note_count as u64));
+        group.bench_function(BenchmarkId::from_parameter(vault.note_count), |bencher| {
+            bencher.iter_batched_ref(
+                || notes.clone(),
+                |notes| {
+                    for note in notes {
+                        note.process_file_for_back_populate_replacements(
+                            &sorted_wikilinks,
+                            &vault.validated_config,
+                            automaton,
+                        );
+                    }
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+    Ok(())
+}
+
+/// Times hashing every image through an empty cache, as a first run would.
+fn bench_image_hashing(criterion: &mut Criterion, vaults: &[BenchVault]) {
+    let mut group = criterion.benchmark_group(BENCH_GROUP_IMAGE_HASHING);
+    for vault in vaults {
+        let cache_path = vault.validated_config.output_folder().join(CACHE_FILE);
+        group.throughput(Throughput::Elements(vault.images.len() as u64));
+        group.bench_function(BenchmarkId::from_parameter(vault.note_count), |bencher| {
+            bencher.iter_batched_ref(
+                || Sha256Cache::load_or_create(cache_path.clone()).0,
+                |cache| {
+                    for image in &vault.images {
+                        black_box(cache.get_or_update(image).ok());
+                    }
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn bench_yaml_round_trip(criterion: &mut Criterion) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // Parsed once up front so a malformed sample fails the run instead of every iteration.
+    FrontMatter::from_yaml_str(BENCH_FRONTMATTER)?;
+    criterion.bench_function(BENCH_GROUP_YAML_ROUND_TRIP, |bencher| {
+        bencher.iter(|| {
+            FrontMatter::from_yaml_str(black_box(BENCH_FRONTMATTER))
+                .and_then(|front_matter| front_matter.to_yaml_str())
+                .ok()
+        });
+    });
+    Ok(())
+}
+
+/// Times formatting a table with one row per note into a buffered section, so nothing reaches
+/// the disk.
+fn bench_report_tables(
+    criterion: &mut Criterion,
+    vaults: &[BenchVault],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let output_file_writers = vaults
+        .iter()
+        .map(|vault| OutputFileWriter::new(vault.validated_config.obsidian_path()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut group = criterion.benchmark_group(BENCH_GROUP_REPORT_TABLES);
+    for (vault, output_file_writer) in vaults.iter().zip(&output_file_writers) {
+        let rows: Vec<Vec<String>> = vault
+            .notes
+            .iter()
+            .enumerate()
+            .map(|(index, note)| {
+                vec![
+                    note.display().to_string(),
+                    index.to_string(),
+                    vault.note_count.to_string(),
+                    note.file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect();
+
+        group.throughput(Throughput::Elements(rows.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(vault.note_count),
+            &rows,
+            |bencher, rows| {
+                bencher.iter(|| {
+                    let section = output_file_writer.buffer();
+                    black_box(
+                        section
+                            .write_markdown_table(&BENCH_REPORT_HEADERS, rows, None)
+                            .ok(),
+                    );
+                });
+            },
+        );
+    }
+    group.finish();
+    Ok(())
+}
@@ -1,20 +1,47 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::error::Error;
+use std::fs;
+use std::path::Path;
 use std::path::PathBuf;
 
+use regex::Regex;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
 use serde::Serializer;
 use serde_yaml::Value;
 
+use crate::app_settings::AppSettings;
+use crate::constants::APPLY_CHANGES_KEY;
+use crate::constants::CACHE_FOLDER;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::DEFAULT_FUTURE_DATE_TOLERANCE_DAYS;
 use crate::constants::DEFAULT_OUTPUT_FOLDER;
+use crate::constants::DEFAULT_PRIVATE_TAG;
+use crate::constants::DEFAULT_REDACTION_PATTERNS;
 use crate::constants::DEFAULT_TIMEZONE;
+use crate::constants::FORMAT_DATE;
+use crate::constants::OPENING_WIKILINK;
+use crate::constants::PROFILES_KEY;
+use crate::constants::STATE_FOLDER_APP;
+use crate::constants::STATE_FOLDER_HASH_LENGTH;
+use crate::constants::TAG_PREFIX;
 use crate::frontmatter::FrontMatter;
+use crate::note_template;
+use crate::note_template::NoteTemplate;
+use crate::query;
+use crate::sha256_cache;
 use crate::support;
 use crate::validated_config::ChangeMode;
+use crate::validated_config::PiiPatternRule;
+use crate::validated_config::RedactionRule;
+use crate::validated_config::RegexReplacementRule;
 use crate::validated_config::ValidatedConfig;
 use crate::validated_config::ValidatedConfigBuilder;
+use crate::validated_config::ValidationError;
+use crate::wikilink::DEFAULT_IGNORED_INVALID_WIKILINK_REASONS;
+use crate::wikilink::InvalidWikilinkReason;
 use crate::yaml_frontmatter::YamlFrontMatter;
 use crate::yaml_frontmatter_struct;
 
@@ -68,6 +95,325 @@ impl From<bool> for ConfiguredChanges {
     }
 }
 
+/// Where `audit_trail` records the changes ok writes to a note.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AuditTrail {
+    /// Append an entry to the note's own `knife_history` property.
+    Frontmatter,
+    /// Append a line per note to one audit note in the output folder.
+    Note,
+}
+
+/// What `resolve_conflict_copies` does with a sync conflict copy of a note.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ConflictResolution {
+    /// Delete copies whose body matches the original; keep the rest.
+    Delete,
+    /// Append the copy's lines the original lacks, then delete the copy.
+    Merge,
+}
+
+/// How `back_populate_grouping` organizes the back populate report.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BackPopulateGrouping {
+    /// One table per matched text.
+    #[default]
+    FoundText,
+    /// One table per note the matches link to, covering every text that links it.
+    TargetNote,
+}
+
+/// How `fix_duplicate_frontmatter_keys` rewrites a frontmatter key written more than once.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DuplicateKeyResolution {
+    /// Keep the first occurrence and drop the rest.
+    KeepFirst,
+    /// Combine list values into one list; other values keep the first occurrence.
+    MergeLists,
+}
+
+/// How `export` writes a wikilink.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ExportLinkStyle {
+    /// The link's alias, or its target, as plain text.
+    #[default]
+    Plain,
+    /// The same text followed by a footnote naming the linked note.
+    Footnotes,
+}
+
+/// How `fix_impossible_dates` repairs a note created after it was last modified. Either way, a
+/// date in the future becomes today.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ImpossibleDatePolicy {
+    /// Move `date_created` back to `date_modified`.
+    CreatedToModified,
+    /// Move `date_modified` forward to `date_created`.
+    ModifiedToCreated,
+}
+
+/// How `link_alias_style` writes the links ok creates or rewrites.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum LinkAliasStyle {
+    /// `[[Target|matched text]]` - the alias keeps the text's case.
+    #[default]
+    KeepCase,
+    /// `[[Target|matched text]]` with the alias lowercased.
+    Lowercase,
+    /// `[[folder/Target|matched text]]` - the target is the note's vault-relative path.
+    PipedPath,
+}
+
+/// A kind of `PersistReason`, as `hold_persist_reasons` names it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PersistReasonKind {
+    AdmonitionsConverted,
+    AliasesGenerated,
+    BackPopulated,
+    BlockIdsAdded,
+    ConflictCopyMerged,
+    CustomReplacementsApplied,
+    DateCreatedFixApplied,
+    DateCreatedUpdated,
+    DateMentionsLinked,
+    DateModifiedUpdated,
+    DatePropertyUpdated,
+    DuplicateFrontmatterKeysFixed,
+    EntityAliasAdded,
+    EntityIndexLinksAdded,
+    FlashcardIdsAdded,
+    FrontmatterCreated,
+    FrontmatterLinkValuesFixed,
+    FrontmatterLinksCanonicalized,
+    HeadingLinksRepaired,
+    HeadingLinksRetargeted,
+    ImageReferencesModified,
+    ImpossibleDatesFixed,
+    JournalDateAligned,
+    LinksCanonicalized,
+    ListsNormalized,
+    NextReviewSet,
+    PeriodicNoteLinked,
+    PhantomLinksResolved,
+    PropertyTypesCoerced,
+    PunctuationNormalized,
+    RegexReplacementsApplied,
+    SectionsSplit,
+    SelfLinksCleaned,
+    TableOfContentsUpdated,
+    WikilinksRepaired,
+}
+
+/// Which part of a note a `replacements` rule may rewrite. Fenced code blocks and inline code
+/// are never touched.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ReplacementScope {
+    /// Every line of the note body.
+    Content,
+    /// Heading lines only, with the same exclusions as `Text`.
+    Headings,
+    /// Prose only - wikilinks, markdown links, HTML comments and math are skipped as well.
+    #[default]
+    Text,
+}
+
+/// One entry of `entity_folders`: the conventions every note under `folder` must follow.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct EntityFolderRule {
+    /// Relative to `obsidian_path`; notes in subfolders count too.
+    pub folder:              PathBuf,
+    /// The note that must link to every note in `folder`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_note:          Option<String>,
+    /// Every note needs an alias starting with this, such as `@`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias_prefix:        Option<String>,
+    /// Properties every note must hold a value for.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_properties: Vec<String>,
+}
+
+/// `export`: notes ok writes as pandoc-ready markdown on each run, with embeds flattened and
+/// Obsidian-only syntax removed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct ExportProfile {
+    /// Names of the notes to export, with or without `[[ ]]`.
+    pub notes:            Vec<String>,
+    /// Relative to `output_folder`; `export` when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder:           Option<String>,
+    #[serde(default)]
+    pub links:            ExportLinkStyle,
+    /// Turns `> [!note] Title` into a quote opening with `**Title**` rather than dropping the
+    /// callout marker.
+    #[serde(default)]
+    pub convert_callouts: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redact:           Option<ExportRedaction>,
+}
+
+/// `export.redact`: what an export withholds, so a published copy can't leak it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct ExportRedaction {
+    /// A line matching any of these is left out; API keys and phone numbers when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patterns:    Option<Vec<String>>,
+    /// Notes with this tag are left out whole, embedded or listed; `private` when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_tag: Option<String>,
+}
+
+impl ExportRedaction {
+    fn compile(&self) -> Result<RedactionRule, ValidationError> {
+        let patterns = self.patterns.as_ref().map_or_else(
+            || {
+                DEFAULT_REDACTION_PATTERNS
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            },
+            Clone::clone,
+        );
+        let regexes = patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|error| {
+                    ValidationError::InvalidRedactionPattern(pattern.clone(), error.to_string())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RedactionRule {
+            regexes,
+            private_tag: self
+                .private_tag
+                .as_deref()
+                .unwrap_or(DEFAULT_PRIVATE_TAG)
+                .trim()
+                .trim_start_matches(TAG_PREFIX)
+                .to_lowercase(),
+        })
+    }
+}
+
+/// `flashcards`: how question and answer pairs are written in the notes ok exports to Anki.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct FlashcardRule {
+    /// Only notes with this tag hold cards; every note does when it isn't set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag:             Option<String>,
+    /// Starts the line holding a card's question; `Q::` when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question_prefix: Option<String>,
+    /// Starts the line after it, holding the answer; `A::` when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer_prefix:   Option<String>,
+}
+
+/// `note_templates`: the templates, by name, that ok fills in for each kind of note it
+/// creates. Names are looked up in the core Templates plugin's folder.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct NoteTemplates {
+    /// Notes `create_missing_link_targets` creates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_target:   Option<String>,
+    /// Weekly and monthly notes `periodic_note_link_property` creates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub periodic_note: Option<String>,
+    /// Notes `split_notes` creates - the section follows the template's body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_note:    Option<String>,
+}
+
+/// One entry of `pii_patterns`: a kind of personal data `detect_pii` counts as well as its own.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct PiiPattern {
+    /// What the report calls a match.
+    pub name:    String,
+    pub pattern: String,
+}
+
+impl PiiPattern {
+    fn compile(&self) -> Result<PiiPatternRule, ValidationError> {
+        let regex = Regex::new(&self.pattern).map_err(|error| {
+            ValidationError::InvalidPiiPattern(self.pattern.clone(), error.to_string())
+        })?;
+
+        Ok(PiiPatternRule {
+            name: self.name.clone(),
+            regex,
+        })
+    }
+}
+
+/// One entry of `replacements`: every match of `pattern` becomes `replacement`, which may refer
+/// to capture groups as `$1` or `${name}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct ReplacementRule {
+    pub pattern:     String,
+    pub replacement: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope:       Option<ReplacementScope>,
+}
+
+impl ReplacementRule {
+    fn compile(&self) -> Result<RegexReplacementRule, ValidationError> {
+        let regex = Regex::new(&self.pattern).map_err(|error| {
+            ValidationError::InvalidReplacementPattern(self.pattern.clone(), error.to_string())
+        })?;
+
+        Ok(RegexReplacementRule {
+            regex,
+            replacement: self.replacement.clone(),
+            scope: self.scope.unwrap_or_default(),
+        })
+    }
+}
+
+/// One entry of `review_after`: a note left unmodified for `days` is due for review. With
+/// `folder` or `tag` set, the rule covers only those notes; the first rule that covers a note
+/// applies.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct ReviewRule {
+    pub days:   u32,
+    /// Relative to `obsidian_path`; notes in subfolders count too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag:    Option<String>,
+}
+
+/// One entry of `saved_reports`: a Dataview query whose matching notes get their own table in
+/// the report on every run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct SavedReport {
+    /// The report section's heading.
+    pub name:    String,
+    /// `FROM`, `WHERE`, `SORT` and `LIMIT` clauses, as in a Dataview `TABLE` query.
+    pub query:   String,
+    /// Columns after the note, such as `file.mtime` or `length(file.inlinks)`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+}
+
+impl SavedReport {
+    fn validate(&self) -> Result<Self, ValidationError> {
+        query::validate_saved_report(&self.query, &self.columns).map_err(|error| {
+            ValidationError::InvalidSavedReport(self.name.clone(), error.to_string())
+        })?;
+        Ok(self.clone())
+    }
+}
+
 yaml_frontmatter_struct! {
     #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
     pub struct Config {
@@ -78,17 +424,151 @@ yaml_frontmatter_struct! {
         )]
         pub configured_changes: ConfiguredChanges,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub ambiguous_match_context_lines: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub audit_trail: Option<AuditTrail>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub back_populate_file_filter: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub back_populate_grouping: Option<BackPopulateGrouping>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub backup_before_apply: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub bibliography: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub block_id_placeholder: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub changelog: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub coerce_property_types: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub convert_admonitions: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub create_missing_link_targets: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub date_link_format: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub date_properties: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub detect_pii: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub do_not_back_populate: Option<Vec<String>>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub entity_folders: Option<Vec<EntityFolderRule>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub exclude_math: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub export: Option<ExportProfile>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub file_limit: Option<usize>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub fix_duplicate_frontmatter_keys: Option<DuplicateKeyResolution>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub fix_entity_notes: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub fix_frontmatter_link_values: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub fix_impossible_dates: Option<ImpossibleDatePolicy>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub fix_self_links: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub flashcards: Option<FlashcardRule>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub frontmatter_link_properties: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub frontmatter_only: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub future_date_tolerance_days: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub generate_aliases: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub hash_all_images: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub hold_persist_reasons: Option<Vec<PersistReasonKind>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub ignore_folders: Option<Vec<PathBuf>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ignore_invalid_wikilink_reasons: Option<Vec<InvalidWikilinkReason>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub journal_folders: Option<Vec<PathBuf>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub link_acronyms: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub link_alias_style: Option<LinkAliasStyle>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub link_date_mentions: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub lint_markdown: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub lint_max_line_length: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub list_block_ids: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub match_decisions: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub normalize_link_aliases: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub normalize_lists: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub normalize_punctuation: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub note_templates: Option<NoteTemplates>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub notify: Option<String>,
         pub obsidian_path: String,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub only_modify_owner: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub operational_timezone: Option<String>,
         pub output_folder: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub periodic_note_link_property: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub pii_patterns: Option<Vec<PiiPattern>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub profiles: Option<BTreeMap<String, Value>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub purge_trash_after_days: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub rename_unsafe_paths: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub repair_heading_links: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub repair_invalid_wikilinks: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub report_deep_links: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub report_rare_words: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub report_row_limit: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub replacements: Option<Vec<ReplacementRule>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub resolve_conflict_copies: Option<ConflictResolution>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub review_after: Option<Vec<ReviewRule>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub saved_reports: Option<Vec<SavedReport>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub scan_secrets: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub secret_patterns: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub set_next_review: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub similar_title_threshold: Option<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub skip_recently_modified_minutes: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub split_notes: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub state_folder: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub table_of_contents_min_headings: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub vault_index: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub write_patch: Option<bool>,
         #[serde(skip)]
         pub file_path: PathBuf,
     }
@@ -97,31 +577,287 @@ yaml_frontmatter_struct! {
 impl Config {
     pub(crate) const fn change_mode(&self) -> ChangeMode { self.configured_changes.resolve() }
 
+    /// Turns `apply_changes` off after a run applied its changes - in profile `profile` too,
+    /// when that profile is what turned it on.
+    pub(crate) fn turn_off_apply_changes(&mut self, profile: Option<&str>) {
+        self.configured_changes = ConfiguredChanges::DryRun;
+        if let Some(Value::Mapping(settings)) =
+            profile.and_then(|name| self.profiles.as_mut()?.get_mut(name))
+            && let Some(apply_changes) = settings.get_mut(APPLY_CHANGES_KEY)
+        {
+            *apply_changes = Value::Bool(false);
+        }
+    }
+
+    fn pii_patterns(&self) -> Result<Vec<PiiPatternRule>, ValidationError> {
+        self.pii_patterns
+            .iter()
+            .flatten()
+            .map(PiiPattern::compile)
+            .collect()
+    }
+
+    fn replacement_rules(&self) -> Result<Vec<RegexReplacementRule>, ValidationError> {
+        self.replacements
+            .iter()
+            .flatten()
+            .map(ReplacementRule::compile)
+            .collect()
+    }
+
+    fn saved_reports(&self) -> Result<Vec<SavedReport>, ValidationError> {
+        self.saved_reports
+            .iter()
+            .flatten()
+            .map(SavedReport::validate)
+            .collect()
+    }
+
+    fn secret_patterns(&self) -> Result<Vec<Regex>, ValidationError> {
+        self.secret_patterns
+            .iter()
+            .flatten()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|error| {
+                    ValidationError::InvalidSecretPattern(pattern.clone(), error.to_string())
+                })
+            })
+            .collect()
+    }
+
+    /// `obsidian_path` with environment variables and `~` expanded.
+    pub(crate) fn expanded_obsidian_path(&self) -> Result<PathBuf, ValidationError> {
+        expand_path(&self.obsidian_path)
+    }
+
+    /// The export profile with its folder expanded.
+    fn expanded_export(&self) -> Result<Option<ExportProfile>, ValidationError> {
+        let mut export = self.export.clone();
+        if let Some(folder) = export.as_mut().and_then(|export| export.folder.as_mut()) {
+            *folder = expand_path(&*folder)?.to_string_lossy().into_owned();
+        }
+        Ok(export)
+    }
+
+    fn export_redaction(&self) -> Result<Option<RedactionRule>, ValidationError> {
+        self.export
+            .as_ref()
+            .and_then(|profile| profile.redact.as_ref())
+            .map(ExportRedaction::compile)
+            .transpose()
+    }
+
+    /// The config with profile `name`'s settings merged over the base ones. Nested settings such
+    /// as `export` merge key by key; anything else the profile sets replaces the base value.
+    pub(crate) fn with_profile(&self, name: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let profiles = self.profiles.as_ref();
+        let Some(profile) = profiles.and_then(|profiles| profiles.get(name)) else {
+            let names: Vec<&str> = profiles
+                .into_iter()
+                .flat_map(BTreeMap::keys)
+                .map(String::as_str)
+                .collect();
+            return Err(ValidationError::UnknownProfile(name.to_string(), names.join(", ")).into());
+        };
+
+        let mut merged = serde_yaml::to_value(self)?;
+        if let Value::Mapping(mapping) = &mut merged {
+            mapping.remove(PROFILES_KEY);
+        }
+        merge_yaml(&mut merged, profile.clone());
+        Ok(serde_yaml::from_value(merged)?)
+    }
+
+    #[allow(clippy::too_many_lines, reason = "one builder call per config option")]
     pub(crate) fn validate(&self) -> Result<ValidatedConfig, Box<dyn Error + Send + Sync>> {
+        let obsidian_path = self.expanded_obsidian_path()?;
+        let note_templates = self.note_templates.clone().unwrap_or_default();
+        let app_settings = AppSettings::read(&obsidian_path);
+        let load_template = |name: Option<&str>| {
+            name.filter(|_| obsidian_path.exists())
+                .map(|name| NoteTemplate::load(&obsidian_path, name))
+                .transpose()
+        };
+        let in_vault = |path: &Option<String>| {
+            path.as_deref()
+                .map(|path| expand_path(path).map(|path| obsidian_path.join(path)))
+                .transpose()
+        };
+
         ValidatedConfigBuilder::default()
             .change_mode(self.change_mode())
+            .ambiguous_match_context_lines(self.ambiguous_match_context_lines)
+            .attachment_folder(app_settings.attachment_folder())
+            .audit_trail(self.audit_trail)
             .back_populate_file_filter(self.back_populate_file_filter.clone())
+            .back_populate_grouping(self.back_populate_grouping.unwrap_or_default())
+            .backup_before_apply(self.backup_before_apply.clone())
+            .bibliography(in_vault(&self.bibliography)?)
+            .block_id_placeholder(self.block_id_placeholder.clone())
+            .changelog(in_vault(&self.changelog)?)
+            .coerce_property_types(self.coerce_property_types.unwrap_or_default())
+            .convert_admonitions(self.convert_admonitions.unwrap_or_default())
+            .create_missing_link_targets(self.create_missing_link_targets.unwrap_or_default())
+            .date_link_format(
+                self.date_link_format
+                    .as_deref()
+                    .map_or_else(|| FORMAT_DATE.to_string(), chrono_date_link_format),
+            )
+            .date_properties(self.date_properties.clone().unwrap_or_default())
+            .detect_pii(self.detect_pii.unwrap_or_default())
             .do_not_back_populate(self.do_not_back_populate.clone())
+            .entity_folders(self.entity_folders.clone().unwrap_or_default())
+            .exclude_math(self.exclude_math.unwrap_or(true))
+            .export(self.expanded_export()?)
+            .export_redaction(self.export_redaction()?)
             .file_limit(self.file_limit)
-            .ignore_folders(self.ignore_folders.clone())
-            .obsidian_path(support::expand_tilde(&self.obsidian_path))
+            .fix_duplicate_frontmatter_keys(self.fix_duplicate_frontmatter_keys)
+            .fix_entity_notes(self.fix_entity_notes.unwrap_or_default())
+            .fix_frontmatter_link_values(self.fix_frontmatter_link_values.unwrap_or_default())
+            .fix_impossible_dates(self.fix_impossible_dates)
+            .fix_self_links(self.fix_self_links.unwrap_or_default())
+            .flashcards(self.flashcards.clone())
+            .frontmatter_link_properties(
+                self.frontmatter_link_properties.clone().unwrap_or_default(),
+            )
+            .frontmatter_only(self.frontmatter_only.unwrap_or_default())
+            .future_date_tolerance_days(
+                self.future_date_tolerance_days
+                    .unwrap_or(DEFAULT_FUTURE_DATE_TOLERANCE_DAYS),
+            )
+            .generate_aliases(self.generate_aliases.unwrap_or_default())
+            .hash_all_images(self.hash_all_images.unwrap_or_default())
+            .hold_persist_reasons(self.hold_persist_reasons.clone().unwrap_or_default())
+            .ignore_folders(
+                self.ignore_folders
+                    .as_ref()
+                    .map(|folders| folders.iter().map(expand_path).collect())
+                    .transpose()?,
+            )
+            .ignored_invalid_wikilink_reasons(
+                self.ignore_invalid_wikilink_reasons
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_IGNORED_INVALID_WIKILINK_REASONS.to_vec()),
+            )
+            .journal_folders(self.journal_folders.clone().unwrap_or_default())
+            .link_acronyms(self.link_acronyms.unwrap_or_default())
+            .link_alias_style(self.link_alias_style.unwrap_or_default())
+            .link_date_mentions(self.link_date_mentions.unwrap_or_default())
+            .link_format(app_settings.link_format())
+            .lint_markdown(self.lint_markdown.unwrap_or_default())
+            .lint_max_line_length(self.lint_max_line_length)
+            .list_block_ids(self.list_block_ids.unwrap_or_default())
+            .match_decisions(in_vault(&self.match_decisions)?)
+            .normalize_link_aliases(self.normalize_link_aliases.unwrap_or_default())
+            .normalize_lists(self.normalize_lists.unwrap_or_default())
+            .normalize_punctuation(self.normalize_punctuation.unwrap_or_default())
+            .link_target_template(load_template(note_templates.link_target.as_deref())?)
+            .periodic_note_template(load_template(note_templates.periodic_note.as_deref())?)
+            .split_note_template(load_template(note_templates.split_note.as_deref())?)
+            .notify(self.notify.clone())
+            .obsidian_path(obsidian_path.clone())
+            .only_modify_owner(self.only_modify_owner.clone())
             .operational_timezone(
                 self.operational_timezone
                     .clone()
                     .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string()),
             )
             .output_folder(
-                support::expand_tilde(&self.obsidian_path).join(
+                obsidian_path.join(expand_path(
                     self.output_folder
                         .as_deref()
                         .unwrap_or(DEFAULT_OUTPUT_FOLDER),
-                ),
+                )?),
+            )
+            .periodic_note_link_property(self.periodic_note_link_property.clone())
+            .pii_patterns(self.pii_patterns()?)
+            .purge_trash_after_days(self.purge_trash_after_days)
+            .rename_unsafe_paths(self.rename_unsafe_paths.unwrap_or_default())
+            .repair_heading_links(self.repair_heading_links.unwrap_or_default())
+            .repair_invalid_wikilinks(self.repair_invalid_wikilinks.unwrap_or_default())
+            .report_deep_links(self.report_deep_links.unwrap_or_default())
+            .report_rare_words(self.report_rare_words.unwrap_or_default())
+            .report_row_limit(self.report_row_limit)
+            .replacement_rules(self.replacement_rules()?)
+            .resolve_conflict_copies(self.resolve_conflict_copies)
+            .review_after(self.review_after.clone().unwrap_or_default())
+            .saved_reports(self.saved_reports()?)
+            .scan_secrets(self.scan_secrets.unwrap_or_default())
+            .secret_patterns(self.secret_patterns()?)
+            .set_next_review(self.set_next_review.unwrap_or_default())
+            .similar_title_threshold(self.similar_title_threshold)
+            .skip_recently_modified_minutes(self.skip_recently_modified_minutes)
+            .split_notes(self.split_notes.clone().unwrap_or_default())
+            .state_folder(
+                self.state_folder
+                    .as_deref()
+                    .map_or_else(|| Ok(default_state_folder(&obsidian_path)), expand_path)?,
             )
+            .table_of_contents_min_headings(self.table_of_contents_min_headings)
+            .template_folders(note_template::template_folders(&obsidian_path))
+            .vault_index(self.vault_index.unwrap_or_default())
+            .write_patch(self.write_patch.unwrap_or_default())
             .build()
             .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)
     }
 }
 
+/// `<platform cache folder>/obsidian_knife/<vault name>-<hash of the vault path>`, so state
+/// stays out of the vault and out of reach of sync services. Falls back to `CACHE_FOLDER` inside
+/// the vault when the platform has no cache folder.
+fn default_state_folder(obsidian_path: &Path) -> PathBuf {
+    let vault_path =
+        fs::canonicalize(obsidian_path).unwrap_or_else(|_| obsidian_path.to_path_buf());
+    let vault_name = vault_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path_hash = sha256_cache::hash_contents(vault_path.as_os_str().as_encoded_bytes());
+    let folder_name = format!(
+        "{vault_name}-{}",
+        &path_hash[..STATE_FOLDER_HASH_LENGTH.min(path_hash.len())]
+    );
+
+    support::platform_cache_folder().map_or_else(
+        || obsidian_path.join(CACHE_FOLDER),
+        |cache_folder| cache_folder.join(STATE_FOLDER_APP).join(folder_name),
+    )
+}
+
+/// `date_link_format` is written the way Obsidian writes date formats (`YYYY-MM-DD ddd`), and
+/// may keep the brackets of the link it describes (`[[DD.MM.YYYY]]`).
+fn chrono_date_link_format(moment: &str) -> String {
+    note_template::chrono_format(
+        moment
+            .trim()
+            .trim_start_matches(OPENING_WIKILINK)
+            .trim_end_matches(CLOSING_WIKILINK),
+    )
+}
+
+/// `support::expand_path` with a failed expansion as a `ValidationError`.
+pub(crate) fn expand_path<P: AsRef<Path>>(path: P) -> Result<PathBuf, ValidationError> {
+    let path = path.as_ref();
+    support::expand_path(path)
+        .map_err(|variable| ValidationError::UnexpandablePath(path.display().to_string(), variable))
+}
+
+fn merge_yaml(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    },
+                }
+            }
+        },
+        (base, overlay) => *base = overlay,
+    }
+}
+
 impl TryFrom<&FrontMatter> for Config {
     type Error = Box<dyn Error + Send + Sync>;
 
@@ -146,12 +882,19 @@ mod tests {
 
     use super::Config;
     use super::ConfiguredChanges;
+    use super::ReplacementRule;
+    use super::ReplacementScope;
+    use super::SavedReport;
+    use super::chrono_date_link_format;
     use crate::constants::DEFAULT_TIMEZONE;
     use crate::constants::OBSIDIAN_FOLDER;
     use crate::frontmatter::FrontMatter;
+    use crate::support;
     use crate::test_support as test_utils;
     use crate::test_support::TestFileBuilder;
     use crate::validated_config::ChangeMode;
+    use crate::validated_config::ValidationError;
+    use crate::wikilink::InvalidWikilinkReason;
     use crate::yaml_frontmatter::YamlFrontMatter;
 
     fn create_test_environment() -> (TempDir, PathBuf) {
@@ -285,6 +1028,13 @@ invalid: yaml: content:
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_chrono_date_link_format() {
+        assert_eq!(chrono_date_link_format("YYYY-MM-DD"), "%Y-%m-%d");
+        assert_eq!(chrono_date_link_format("[[DD.MM.YYYY]]"), "%d.%m.%Y");
+        assert_eq!(chrono_date_link_format("YYYY-MM-DD ddd"), "%Y-%m-%d %a");
+    }
+
     #[test]
     fn test_config_with_output_folder() {
         let yaml = r"
@@ -306,6 +1056,132 @@ apply_changes: false";
         assert_eq!(config.output_folder, None);
     }
 
+    #[test]
+    fn test_config_ignore_invalid_wikilink_reasons() {
+        let (_temp_dir, config_path) = create_test_environment();
+        let markdown_file = test_utils::get_test_markdown_file(config_path);
+        let mut config = Config::try_from(&markdown_file.front_matter.unwrap()).unwrap();
+
+        let validated_config = config.validate().unwrap();
+        assert!(validated_config.is_ignored_invalid_wikilink_reason(InvalidWikilinkReason::Tag));
+        assert!(
+            !validated_config
+                .is_ignored_invalid_wikilink_reason(InvalidWikilinkReason::UnmatchedOpening)
+        );
+
+        let yaml = r"
+obsidian_path: ~/Documents/brain
+ignore_invalid_wikilink_reasons:
+  - unmatched_opening";
+        let parsed: Config = from_str(yaml).unwrap();
+        config.ignore_invalid_wikilink_reasons = parsed.ignore_invalid_wikilink_reasons;
+
+        let validated_config = config.validate().unwrap();
+        assert!(
+            !validated_config.is_ignored_invalid_wikilink_reason(InvalidWikilinkReason::Tag),
+            "an explicit list replaces the defaults"
+        );
+        assert!(
+            validated_config
+                .is_ignored_invalid_wikilink_reason(InvalidWikilinkReason::UnmatchedOpening)
+        );
+    }
+
+    #[test]
+    fn test_config_replacements() {
+        let (_temp_dir, config_path) = create_test_environment();
+        let markdown_file = test_utils::get_test_markdown_file(config_path);
+        let mut config = Config::try_from(&markdown_file.front_matter.unwrap()).unwrap();
+
+        let yaml = r#"
+obsidian_path: ~/Documents/brain
+replacements:
+  - pattern: '(\d+)F'
+    replacement: "${1}°F"
+  - pattern: "TODO"
+    replacement: "To do"
+    scope: headings"#;
+        let parsed: Config = from_str(yaml).unwrap();
+        config.replacements = parsed.replacements;
+
+        let validated_config = config.validate().unwrap();
+        let rules = validated_config.replacement_rules();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(
+            rules[0].scope,
+            ReplacementScope::Text,
+            "text is the default scope"
+        );
+        assert_eq!(rules[1].scope, ReplacementScope::Headings);
+
+        config.replacements = Some(vec![ReplacementRule {
+            pattern:     "(unclosed".to_string(),
+            replacement: String::new(),
+            scope:       None,
+        }]);
+        let err = config.validate().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ValidationError>().unwrap(),
+            ValidationError::InvalidReplacementPattern(pattern, _) if pattern == "(unclosed"
+        ));
+    }
+
+    #[test]
+    fn test_config_saved_reports() {
+        let (_temp_dir, config_path) = create_test_environment();
+        let markdown_file = test_utils::get_test_markdown_file(config_path);
+        let mut config = Config::try_from(&markdown_file.front_matter.unwrap()).unwrap();
+
+        let yaml = r"
+obsidian_path: ~/Documents/brain
+saved_reports:
+  - name: stale inbox
+    query: 'FROM #inbox WHERE file.ctime < date(today) - dur(14 days) SORT file.ctime'
+    columns: [file.ctime, length(file.inlinks)]";
+        let parsed: Config = from_str(yaml).unwrap();
+        config.saved_reports = parsed.saved_reports;
+
+        let validated_config = config.validate().unwrap();
+        assert_eq!(validated_config.saved_reports().len(), 1);
+        assert_eq!(validated_config.saved_reports()[0].columns.len(), 2);
+
+        config.saved_reports = Some(vec![SavedReport {
+            name:    "broken".to_string(),
+            query:   "FROM #inbox WHERE file.size > 0".to_string(),
+            columns: Vec::new(),
+        }]);
+        let err = config.validate().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ValidationError>().unwrap(),
+            ValidationError::InvalidSavedReport(name, _) if name == "broken"
+        ));
+    }
+
+    #[test]
+    fn test_config_state_folder() {
+        let (temp_dir, config_path) = create_test_environment();
+        let markdown_file = test_utils::get_test_markdown_file(config_path);
+        let mut config = Config::try_from(&markdown_file.front_matter.unwrap()).unwrap();
+
+        let validated_config = config.validate().unwrap();
+        let default_state_folder = validated_config.state_folder();
+        if support::platform_cache_folder().is_some() {
+            assert!(!default_state_folder.starts_with(validated_config.obsidian_path()));
+            assert!(
+                default_state_folder
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .starts_with("vault-")
+            );
+        }
+
+        let explicit = temp_dir.path().join("state");
+        config.state_folder = Some(explicit.to_string_lossy().to_string());
+        let validated_config = config.validate().unwrap();
+        assert_eq!(validated_config.state_folder(), explicit);
+    }
+
     #[test]
     fn test_process_config_with_valid_setup() {
         let (_temp_dir, config_path) = create_test_environment();
@@ -317,4 +1193,35 @@ apply_changes: false";
         assert_eq!(validated_config.change_mode(), ChangeMode::DryRun);
         assert!(validated_config.obsidian_path().exists());
     }
+
+    #[test]
+    fn test_profile_deep_merges_over_base_config() {
+        let config: Config = from_str(
+            "obsidian_path: /vault\napply_changes: false\nfile_limit: 5\nexport:\n  notes: [A]\n  folder: out\nprofiles:\n  publish:\n    apply_changes: true\n    export:\n      folder: site",
+        )
+        .unwrap();
+
+        let publish = config.with_profile("publish").unwrap();
+        assert_eq!(publish.configured_changes, ConfiguredChanges::Apply);
+        assert_eq!(publish.file_limit, Some(5));
+        let export = publish.export.unwrap();
+        assert_eq!(export.notes, vec!["A".to_string()]);
+        assert_eq!(export.folder.as_deref(), Some("site"));
+        assert!(publish.profiles.is_none());
+
+        let error = config.with_profile("draft").unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ValidationError>(),
+            Some(ValidationError::UnknownProfile(name, names)) if name == "draft" && names == "publish"
+        ));
+
+        let mut reset = config;
+        reset.configured_changes = ConfiguredChanges::Apply;
+        reset.turn_off_apply_changes(Some("publish"));
+        assert_eq!(reset.configured_changes, ConfiguredChanges::DryRun);
+        assert_eq!(
+            reset.with_profile("publish").unwrap().configured_changes,
+            ConfiguredChanges::DryRun
+        );
+    }
 }
@@ -0,0 +1,310 @@
+//! `ok init`: looks at an existing vault and writes a starter config for it, with each setting
+//! explained where it's set.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono_tz::Tz;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::app_settings::AppSettings;
+use crate::constants::DAILY_NOTES_SETTINGS_FILE;
+use crate::constants::DEFAULT_DAILY_NOTE_FORMAT;
+use crate::constants::DEFAULT_OUTPUT_FOLDER;
+use crate::constants::DEFAULT_TIMEZONE;
+use crate::constants::HOME_ENVIRONMENT_VARIABLE;
+use crate::constants::INIT_USAGE;
+use crate::constants::LOCALTIME_PATH;
+use crate::constants::NEWLINE;
+use crate::constants::OBSIDIAN_FOLDER;
+use crate::constants::STARTER_CONFIG_COMMENT_COLUMN;
+use crate::constants::STARTER_CONFIG_FILE;
+use crate::constants::STARTER_CONFIG_NOTE;
+use crate::constants::TILDE_SLASH;
+use crate::constants::TZ_ENVIRONMENT_VARIABLE;
+use crate::constants::YAML_CLOSING_DELIMITER;
+use crate::constants::YAML_OPENING_DELIMITER;
+use crate::constants::ZONEINFO_FOLDER;
+use crate::note_template;
+
+#[derive(Debug, Error)]
+pub(crate) enum ConfigInitError {
+    #[error("{INIT_USAGE}")]
+    Usage,
+    #[error("{} is not a folder - point init at your vault", .0.display())]
+    VaultNotFound(PathBuf),
+    #[error("{} already exists - init only writes new config files", .0.display())]
+    ConfigExists(PathBuf),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// The core Daily notes plugin's settings - only the fields ok reads.
+#[derive(Debug, Default, Deserialize)]
+struct DailyNotesSettings {
+    #[serde(default)]
+    format: String,
+    #[serde(default)]
+    folder: String,
+}
+
+/// What `init` found in the vault and the system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VaultSettings {
+    /// Where Obsidian puts new attachments, as its settings name it.
+    pub attachment_folder: Option<String>,
+    pub template_folders:  Vec<PathBuf>,
+    /// The daily note name format, when the Daily notes plugin has been set up.
+    pub daily_note_format: Option<String>,
+    pub daily_note_folder: Option<String>,
+    pub timezone:          String,
+}
+
+impl VaultSettings {
+    pub(crate) fn detect(obsidian_path: &Path) -> Self {
+        let settings_path = obsidian_path.join(OBSIDIAN_FOLDER);
+        let app = AppSettings::read(obsidian_path);
+        let daily_notes = note_template::read_settings::<DailyNotesSettings>(
+            &settings_path.join(DAILY_NOTES_SETTINGS_FILE),
+        );
+        let non_empty = |text: &str| {
+            let text = text.trim();
+            (!text.is_empty()).then(|| text.to_string())
+        };
+
+        Self {
+            attachment_folder: non_empty(&app.attachment_folder_path),
+            template_folders:  note_template::template_folders(obsidian_path),
+            daily_note_format: daily_notes.as_ref().map(|settings| {
+                non_empty(&settings.format).unwrap_or_else(|| DEFAULT_DAILY_NOTE_FORMAT.to_string())
+            }),
+            daily_note_folder: daily_notes.and_then(|settings| non_empty(&settings.folder)),
+            timezone:          system_timezone(),
+        }
+    }
+}
+
+/// Reads `<vault> [<config_file.md>]`. The config goes in the default output folder, which ok
+/// never scans, unless a path is given.
+pub(crate) fn from_args(args: &[String]) -> Result<(PathBuf, PathBuf), ConfigInitError> {
+    let (vault, config_path) = match args {
+        [vault] => (
+            PathBuf::from(vault),
+            PathBuf::from(vault)
+                .join(DEFAULT_OUTPUT_FOLDER)
+                .join(STARTER_CONFIG_FILE),
+        ),
+        [vault, config_path] => (PathBuf::from(vault), PathBuf::from(config_path)),
+        _ => return Err(ConfigInitError::Usage),
+    };
+    Ok((vault, config_path))
+}
+
+/// Writes a starter config for the vault at `obsidian_path` to `config_path` and returns what
+/// it found. An existing file is never overwritten.
+pub(crate) fn write_starter_config(
+    obsidian_path: &Path,
+    config_path: &Path,
+) -> Result<VaultSettings, ConfigInitError> {
+    if !obsidian_path.is_dir() {
+        return Err(ConfigInitError::VaultNotFound(obsidian_path.to_path_buf()));
+    }
+    if config_path.exists() {
+        return Err(ConfigInitError::ConfigExists(config_path.to_path_buf()));
+    }
+
+    let settings = VaultSettings::detect(obsidian_path);
+    let obsidian_path = fs::canonicalize(obsidian_path)?;
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(config_path, starter_config(&obsidian_path, &settings))?;
+    Ok(settings)
+}
+
+/// `TZ` when set, or the zone `/etc/localtime` links to; `DEFAULT_TIMEZONE` when neither names
+/// a zone ok knows.
+fn system_timezone() -> String {
+    env::var(TZ_ENVIRONMENT_VARIABLE)
+        .ok()
+        .map(|zone| zone.trim_start_matches(':').to_string())
+        .filter(|zone| zone.parse::<Tz>().is_ok())
+        .or_else(|| {
+            fs::read_link(LOCALTIME_PATH)
+                .ok()
+                .and_then(|target| {
+                    target
+                        .to_str()
+                        .and_then(|target| target.split_once(ZONEINFO_FOLDER))
+                        .map(|(_, zone)| zone.to_string())
+                })
+                .filter(|zone| zone.parse::<Tz>().is_ok())
+        })
+        .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string())
+}
+
+/// The vault path with the home folder written as `~`, so the config reads the same wherever
+/// the home folder is.
+fn display_vault_path(obsidian_path: &Path) -> String {
+    env::var_os(HOME_ENVIRONMENT_VARIABLE)
+        .and_then(|home| obsidian_path.strip_prefix(home).ok().map(Path::to_path_buf))
+        .map_or_else(
+            || obsidian_path.display().to_string(),
+            |relative| format!("{TILDE_SLASH}{}", relative.display()),
+        )
+}
+
+fn setting(config: &mut String, line: &str, comment: &str) {
+    let _ = write!(
+        config,
+        "{line:<STARTER_CONFIG_COMMENT_COLUMN$}# {comment}{NEWLINE}"
+    );
+}
+
+fn starter_config(obsidian_path: &Path, settings: &VaultSettings) -> String {
+    let mut config = String::from(YAML_OPENING_DELIMITER);
+    setting(
+        &mut config,
+        &format!("obsidian_path: {}", display_vault_path(obsidian_path)),
+        "the vault this config runs against",
+    );
+    setting(
+        &mut config,
+        "apply_changes: false",
+        "a dry run only reports - set to true to let ok change files",
+    );
+    setting(
+        &mut config,
+        &format!("output_folder: {DEFAULT_OUTPUT_FOLDER}"),
+        "where the report goes, relative to obsidian_path - never scanned",
+    );
+    setting(
+        &mut config,
+        &format!("operational_timezone: {}", settings.timezone),
+        "from this computer's clock - used to judge dates",
+    );
+
+    if !settings.template_folders.is_empty() {
+        setting(
+            &mut config,
+            "ignore_folders:",
+            "template folders found in Obsidian's settings - ok leaves them alone anyway",
+        );
+        for folder in &settings.template_folders {
+            let _ = write!(config, "  - {}{NEWLINE}", folder.display());
+        }
+    }
+
+    match settings.daily_note_format.as_deref() {
+        Some(DEFAULT_DAILY_NOTE_FORMAT) => setting(
+            &mut config,
+            "# periodic_note_link_property: up",
+            &format!(
+                "daily notes{} are named {DEFAULT_DAILY_NOTE_FORMAT} - uncomment to link them up \
+                 to weekly and monthly notes",
+                settings
+                    .daily_note_folder
+                    .as_ref()
+                    .map(|folder| format!(" in {folder}"))
+                    .unwrap_or_default()
+            ),
+        ),
+        Some(format) => setting(
+            &mut config,
+            &format!("# date_link_format: {format}"),
+            "daily notes use their own date format - uncomment so ok's date links match them",
+        ),
+        None => {},
+    }
+    if let Some(folder) = &settings.attachment_folder {
+        let _ = write!(
+            config,
+            "# new attachments go to {folder} - ok finds images wherever they are{NEWLINE}"
+        );
+    }
+
+    config.push_str(YAML_CLOSING_DELIMITER);
+    config.push_str(STARTER_CONFIG_NOTE);
+    config
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+
+    use super::write_starter_config;
+    use crate::config::Config;
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::constants::FORMAT_DATE;
+    use crate::constants::OBSIDIAN_FOLDER;
+    use crate::markdown_file::MarkdownFile;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_starter_config_describes_vault_and_validates() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path().join("vault");
+        let settings = vault.join(OBSIDIAN_FOLDER);
+        fs::create_dir_all(&settings).unwrap();
+        fs::create_dir_all(vault.join("Templates")).unwrap();
+        fs::write(
+            settings.join("templates.json"),
+            r#"{"folder": "Templates"}"#,
+        )
+        .unwrap();
+        fs::write(
+            settings.join("app.json"),
+            r#"{"attachmentFolderPath": "Attachments"}"#,
+        )
+        .unwrap();
+        fs::write(
+            settings.join("daily-notes.json"),
+            r#"{"folder": "Journal"}"#,
+        )
+        .unwrap();
+
+        let config_path = temp_dir.path().join("ok.md");
+        let found = write_starter_config(&vault, &config_path).unwrap();
+        assert_eq!(found.attachment_folder.as_deref(), Some("Attachments"));
+        assert_eq!(found.template_folders, vec![PathBuf::from("Templates")]);
+        assert_eq!(found.daily_note_format.as_deref(), Some("YYYY-MM-DD"));
+        assert_eq!(found.daily_note_folder.as_deref(), Some("Journal"));
+
+        let written = fs::read_to_string(&config_path).unwrap();
+        assert!(written.contains("# periodic_note_link_property: up"));
+        assert!(written.contains("in Journal"));
+
+        let markdown_file = MarkdownFile::new(
+            config_path.clone(),
+            DEFAULT_TIMEZONE,
+            FORMAT_DATE,
+            None,
+            &[],
+        )
+        .unwrap();
+        let config = Config::try_from(markdown_file.front_matter.as_ref().unwrap()).unwrap();
+        let validated_config = config.validate().unwrap();
+        assert_eq!(validated_config.change_mode(), ChangeMode::DryRun);
+        assert_eq!(
+            validated_config.obsidian_path().canonicalize().unwrap(),
+            vault.canonicalize().unwrap()
+        );
+
+        assert!(
+            write_starter_config(&vault, &config_path).is_err(),
+            "an existing config is never overwritten"
+        );
+    }
+}
@@ -1,35 +1,182 @@
+// backup
+pub(crate) const BACKED_UP: &str = "backed up before applying changes -";
+/// The `backup_before_apply` value that writes a tar archive instead of running a command.
+pub(crate) const BACKUP_ARCHIVE: &str = "archive";
+pub(crate) const BACKUP_ARCHIVE_EXTENSION: &str = "tar";
+pub(crate) const BACKUP_COMMAND_FAILED: &str = "backup command failed, so no changes were applied:";
+pub(crate) const BACKUP_FILE_LIST_EXTENSION: &str = "txt";
+pub(crate) const BACKUP_FILES_PLACEHOLDER: &str = "{files}";
+/// Under `output_folder`.
+pub(crate) const BACKUP_FOLDER: &str = "backups";
+pub(crate) const BACKUP_RUN_ID_FORMAT: &str = "%Y%m%d-%H%M%S";
+pub(crate) const BACKUP_RUN_ID_PLACEHOLDER: &str = "{run_id}";
+#[cfg(not(windows))]
+pub(crate) const BACKUP_SHELL: [&str; 2] = ["sh", "-c"];
+#[cfg(windows)]
+pub(crate) const BACKUP_SHELL: [&str; 2] = ["cmd", "/C"];
+
 // cache
+pub(crate) const CACHE_CORRUPTED_REBUILT: &str =
+    "image hash cache could not be read and has been rebuilt:";
 pub(crate) const CACHE_FILE: &str = "obsidian_knife_cache.json";
 pub(crate) const CACHE_FOLDER: &str = ".ok";
+pub(crate) const CACHE_OUTDATED_REBUILT: &str =
+    "image hash cache was written in an older layout and has been rebuilt:";
+/// Bumped when the cache file's layout changes - a cache written with another version is
+/// rebuilt rather than read.
+pub(crate) const CACHE_VERSION: u32 = 1;
 pub(crate) const HEX_DIGITS_PER_BYTE: usize = 2;
 pub(crate) const SHA256_BUFFER_SIZE: usize = 1024;
+/// Folder under the platform cache folder that holds every vault's state folder.
+pub(crate) const STATE_FOLDER_APP: &str = "obsidian_knife";
+/// Hex digits of the vault path's hash in its state folder name - enough to keep two vaults
+/// with the same name apart.
+pub(crate) const STATE_FOLDER_HASH_LENGTH: usize = 12;
+pub(crate) const VAULT_HEALTH_HISTORY_FILE: &str = "vault_health_history.json";
 
 // cli invocation
 /// Index of the config-file argument in `std::env::args()`.
 pub(crate) const CONFIG_ARG_INDEX: usize = 1;
+/// `--daemon --interval <interval>` after the config-file argument.
+pub(crate) const DAEMON_ARG_COUNT: usize = 3;
+pub(crate) const DAEMON_ARGS_START: usize = 2;
+pub(crate) const DAEMON_FLAG: &str = "--daemon";
+pub(crate) const EXPLAIN_CONFIG_FLAG: &str = "--explain-config";
 /// Program name plus a single config-file argument.
 pub(crate) const EXPECTED_ARG_COUNT: usize = 2;
+pub(crate) const FLAG_PREFIX: &str = "--";
 /// Exit code used when regex compilation fails at startup.
 pub(crate) const INVALID_REGEX_EXIT_CODE: i32 = 1;
+pub(crate) const INTERVAL_FLAG: &str = "--interval";
+pub(crate) const PROFILE_FLAG: &str = "--profile";
+pub(crate) const REBUILD_CACHE_FLAG: &str = "--rebuild-cache";
+pub(crate) const SANDBOX_FLAG: &str = "--sandbox";
+pub(crate) const VERIFY_IDEMPOTENT_FLAG: &str = "--verify-idempotent";
 
 // config
-/// the `DEFAULT_MEDIA_PATH` could be a configuration parameter as it's really specific to my repo
-pub(crate) const DEFAULT_MEDIA_PATH: &str = "conf/media";
+/// Words the rare words report never lists, kept in the output folder.
+pub(crate) const ACCEPTED_WORDS_FILE: &str = "dictionary.md";
+/// The config key `ConfiguredChanges` is read from.
+pub(crate) const APPLY_CHANGES_KEY: &str = "apply_changes";
+pub(crate) const AUDIT_NOTE_FILE: &str = "obsidian knife audit.md";
+/// `date_link_format` is checked by writing this date and reading it back - a day past 12
+/// can't pass for a month.
+pub(crate) const DATE_LINK_FORMAT_CHECK_DATE: (i32, u32, u32) = (2024, 12, 31);
+/// Characters that would end or split a date wikilink.
+pub(crate) const DATE_LINK_FORMAT_FORBIDDEN: [char; 5] = ['[', ']', '|', '#', '^'];
+pub(crate) const DEFAULT_FLASHCARD_ANSWER_PREFIX: &str = "A::";
+pub(crate) const DEFAULT_FLASHCARD_QUESTION_PREFIX: &str = "Q::";
+/// A frontmatter date this many days past today is still plausible - timezones and clock skew.
+pub(crate) const DEFAULT_FUTURE_DATE_TOLERANCE_DAYS: u32 = 1;
+/// `export` writes here, under `output_folder`, when its `folder` isn't set.
+pub(crate) const DEFAULT_EXPORT_FOLDER: &str = "export";
 pub(crate) const DEFAULT_OUTPUT_FOLDER: &str = "obsidian_knife";
+/// `export.redact` leaves out notes with this tag when its `private_tag` isn't set.
+pub(crate) const DEFAULT_PRIVATE_TAG: &str = "private";
+/// `export.redact` leaves out lines matching these when its `patterns` aren't set: key and
+/// password assignments, the key formats of common providers, and phone numbers.
+pub(crate) const DEFAULT_REDACTION_PATTERNS: [&str; 4] = [
+    r"(?i)\b(?:api[_-]?key|secret|token|password|passwd)\b\s*[:=]",
+    r"\b(?:AKIA[0-9A-Z]{16}|gh[pousr]_[0-9A-Za-z]{36}|(?:sk|pk|rk)_(?:live|test)_[0-9A-Za-z]{16,})\b",
+    r"\bsk-[0-9A-Za-z_-]{20,}",
+    PHONE_NUMBER_PATTERN,
+];
 pub(crate) const DEFAULT_TIMEZONE: &str = "America/New_York";
 pub(crate) const ERROR_NOT_FOUND: &str = "file not found: ";
 pub(crate) const ERROR_READING: &str = "error reading config file ";
+pub(crate) const FLASHCARDS_FILE: &str = "obsidian knife flashcards.tsv";
+pub(crate) const FIX_IT_TASKS_FILE: &str = "obsidian knife fix-it tasks.md";
+pub(crate) const MAX_SIMILAR_TITLE_THRESHOLD: u8 = 100;
 pub(crate) const MIN_FILE_LIMIT: usize = 1;
+pub(crate) const MIN_LINT_MAX_LINE_LENGTH: usize = 1;
+pub(crate) const MIN_REPORT_ROW_LIMIT: usize = 1;
+pub(crate) const MIN_REVIEW_AFTER_DAYS: u32 = 1;
+pub(crate) const MIN_SIMILAR_TITLE_THRESHOLD: u8 = 1;
+pub(crate) const MIN_SKIP_RECENTLY_MODIFIED_MINUTES: u32 = 1;
+/// A table of contents with fewer entries isn't worth the space it takes.
+pub(crate) const MIN_TABLE_OF_CONTENTS_HEADINGS: usize = 2;
+pub(crate) const NOTIFY_MAILTO_SCHEME: &str = "mailto:";
+pub(crate) const NOTIFY_SCHEMES: [&str; 3] = ["http://", "https://", NOTIFY_MAILTO_SCHEME];
 pub(crate) const OUTPUT_MARKDOWN_FILE: &str = "obsidian knife output.md";
+/// Overflow files are numbered after this: `obsidian knife overflow 1.md`.
+pub(crate) const OVERFLOW_MARKDOWN_FILE_PREFIX: &str = "obsidian knife overflow ";
+pub(crate) const PATCH_FILE: &str = "obsidian knife changes.patch.json";
+/// The config key holding named profiles, each merged over the base settings by `--profile`.
+pub(crate) const PROFILES_KEY: &str = "profiles";
+/// The frontmatter dates every note is checked for - `date_properties` adds to these.
+pub(crate) const RESERVED_DATE_PROPERTIES: [&str; 3] =
+    ["date_created", "date_created_fix", "date_modified"];
+pub(crate) const VAULT_INDEX_FILE: &str = "obsidian knife index.sqlite";
+
+// config init
+pub(crate) const APP_SETTINGS_FILE: &str = "app.json";
+pub(crate) const DAILY_NOTES_SETTINGS_FILE: &str = "daily-notes.json";
+/// The Daily notes plugin's format when none is set, and the only one ok recognizes.
+pub(crate) const DEFAULT_DAILY_NOTE_FORMAT: &str = "YYYY-MM-DD";
+/// Index of the subcommand in `std::env::args()`; its own arguments follow it.
+pub(crate) const INIT_ARGS_START: usize = 2;
+pub(crate) const INIT_COMMAND: &str = "init";
+pub(crate) const INIT_USAGE: &str = "usage: obsidian_knife init <obsidian_folder> [config_file.md]";
+pub(crate) const INIT_WROTE_CONFIG: &str = "wrote starter config:";
+pub(crate) const LOCALTIME_PATH: &str = "/etc/localtime";
+/// Settings are padded to this width so their comments line up.
+pub(crate) const STARTER_CONFIG_COMMENT_COLUMN: usize = 47;
+pub(crate) const STARTER_CONFIG_FILE: &str = "obsidian_knife_config.md";
+pub(crate) const STARTER_CONFIG_NOTE: &str = "# obsidian knife config\nWritten by `ok init`. Run \
+                                              `ok <this file> --explain-config` to see every \
+                                              option, or read the README.\n";
+pub(crate) const TZ_ENVIRONMENT_VARIABLE: &str = "TZ";
+pub(crate) const ZONEINFO_FOLDER: &str = "zoneinfo/";
+
+// daemon
+pub(crate) const DAEMON_NEXT_RUN: &str = "next run at";
+/// A daemon rewrites its lock this often while it waits, so a long interval never leaves the
+/// lock older than `RUN_LOCK_STALE_AFTER_HOURS`.
+pub(crate) const DAEMON_LOCK_REFRESH_SECONDS: u64 = 60 * 60;
+/// Seconds in each unit an interval can be given in - `90s`, `30m`, `6h` or `1d`.
+pub(crate) const INTERVAL_UNITS: [(char, u64); 4] =
+    [('s', 1), ('m', 60), ('h', 60 * 60), ('d', 24 * 60 * 60)];
 
 // date handling
 pub(crate) const FORMAT_DATE: &str = "%Y-%m-%d";
+/// `March 2 2024` - a date mention's month, day and year, its ordinal and comma dropped.
+pub(crate) const FORMAT_DATE_MENTION: &str = "%B %d %Y";
+pub(crate) const FORMAT_MONTH: &str = "%Y-%m";
+pub(crate) const FORMAT_WEEK: &str = "%G-W%V";
 pub(crate) const NOON_HOUR: u32 = 12;
 
+// explain config
+pub(crate) const EXPLAIN_CONFIG_HEADERS: [&str; 4] = ["key", "type", "default", "value"];
+pub(crate) const EXPLAIN_CONFIG_LIST_OF: &str = "list of";
+pub(crate) const EXPLAIN_CONFIG_MAP: &str = "map";
+/// Struct fields read from a key of another name.
+pub(crate) const EXPLAIN_CONFIG_RENAMED_FIELDS: [(&str, &str); 1] =
+    [("configured_changes", APPLY_CHANGES_KEY)];
+/// Struct fields that aren't read from the config at all.
+pub(crate) const EXPLAIN_CONFIG_SKIPPED_FIELDS: [&str; 1] = ["file_path"];
+pub(crate) const EXPLAIN_CONFIG_TYPE_NAMES: [(&str, &str); 7] = [
+    ("bool", "true/false"),
+    ("ConfiguredChanges", "true/false"),
+    ("PathBuf", "path"),
+    ("String", "text"),
+    ("u8", "number"),
+    ("u32", "number"),
+    ("usize", "number"),
+];
+pub(crate) const NOT_SET: &str = "not set";
+
 // files and extensions
+/// Hidden sibling an atomic write goes through before it is renamed over the real file.
+pub(crate) const ATOMIC_WRITE_TEMP_PREFIX: &str = ".";
+pub(crate) const ATOMIC_WRITE_TEMP_SUFFIX: &str = ".ok-tmp";
+/// Hidden sibling a file a patch replaces or deletes is moved to until every change is made.
+pub(crate) const PATCH_ASIDE_SUFFIX: &str = ".ok-aside";
+pub(crate) const BASE_EXTENSION: &str = "base";
+pub(crate) const CANVAS_EXTENSION: &str = "canvas";
 pub(crate) const DS_STORE: &str = ".DS_Store";
 pub(crate) const EMPTY_FILE_SIZE_BYTES: u64 = 0;
 pub(crate) const GIF_EXTENSION: &str = "gif";
+pub(crate) const HIDDEN_FILE_PREFIX: char = '.';
 pub(crate) const IMAGE_EXTENSIONS: [&str; 8] = [
     GIF_EXTENSION,
     JPEG_EXTENSION,
@@ -51,7 +198,12 @@ pub(crate) const TIFF_EXTENSION: &str = "tiff";
 pub(crate) const WEBP_EXTENSION: &str = "webp";
 
 // folders
+/// `attachmentFolderPath` starts with this to put attachments beside the note.
+pub(crate) const CURRENT_FOLDER: &str = "./";
 pub(crate) const OBSIDIAN_FOLDER: &str = ".obsidian";
+pub(crate) const PARENT_FOLDER: &str = "..";
+pub(crate) const PLUGINS_FOLDER: &str = "plugins";
+pub(crate) const TRASH_FOLDER: &str = ".trash";
 
 // frontmatter delimiters
 pub(crate) const FRONTMATTER_DELIMITER_LINE_COUNT: usize = 2;
@@ -60,6 +212,14 @@ pub(crate) const YAML_CLOSING_DELIMITER_EOF: &str = "\n---";
 pub(crate) const YAML_CLOSING_DELIMITER_NEWLINE: &str = "\n---\n";
 pub(crate) const YAML_OPENING_DELIMITER: &str = "---\n";
 
+// idempotency
+pub(crate) const IDEMPOTENT: &str =
+    "idempotent - a second run over a copy of the vault found nothing left to change";
+/// Start of the sandbox folder name that holds the vault copy while it is verified.
+pub(crate) const IDEMPOTENCY_SANDBOX_PREFIX: &str = "obsidian_knife_idempotency_";
+pub(crate) const NOT_IDEMPOTENT: &str =
+    "not idempotent - a second run over a copy of the vault still changed:";
+
 // macos file dates
 #[cfg(target_os = "macos")]
 pub(crate) const FAILED_TO_SET_CREATION_DATE_WITH_SETFILE: &str =
@@ -95,6 +255,8 @@ pub(crate) const MARKDOWN_TABLE_TRAILING_SEPARATOR: &str = "\n---";
 // matching
 pub(crate) const BACKSLASH: char = '\\';
 pub(crate) const BACKSLASH_PARITY_DIVISOR: usize = 2;
+pub(crate) const BLOCK_REFERENCE_PREFIX: char = '^';
+pub(crate) const CARRIAGE_RETURN: char = '\r';
 pub(crate) const CLOSING_BRACKET: char = ']';
 pub(crate) const CLOSING_PAREN: char = ')';
 pub(crate) const CLOSING_WIKILINK: &str = "]]";
@@ -105,6 +267,8 @@ pub(crate) const ESCAPED_PIPE: &str = r"\|";
 pub(crate) const ESCAPED_PIPE_CAPACITY_MULTIPLIER: usize = 2;
 pub(crate) const FORWARD_SLASH: char = '/';
 pub(crate) const HASH: char = '#';
+/// A space in the path of a markdown link.
+pub(crate) const MARKDOWN_LINK_SPACE: &str = "%20";
 pub(crate) const NEWLINE: char = '\n';
 pub(crate) const OPENING_BRACKET: char = '[';
 pub(crate) const OPENING_PAREN: char = '(';
@@ -112,14 +276,92 @@ pub(crate) const OPENING_WIKILINK: &str = "[[";
 pub(crate) const PIPE: char = '|';
 pub(crate) const SPACE: char = ' ';
 
+// note templates
+/// Moment.js date tokens the core Templates plugin's formats use, longest first, as `chrono`
+/// specifiers.
+pub(crate) const MOMENT_TOKENS: [(&str, &str); 25] = [
+    ("YYYY", "%Y"),
+    ("GGGG", "%G"),
+    ("gggg", "%G"),
+    ("MMMM", "%B"),
+    ("dddd", "%A"),
+    ("DDDD", "%j"),
+    ("MMM", "%b"),
+    ("ddd", "%a"),
+    ("YY", "%y"),
+    ("MM", "%m"),
+    ("DD", "%d"),
+    ("WW", "%V"),
+    ("ww", "%V"),
+    ("HH", "%H"),
+    ("hh", "%I"),
+    ("mm", "%M"),
+    ("ss", "%S"),
+    ("M", "%-m"),
+    ("D", "%-d"),
+    ("H", "%-H"),
+    ("h", "%-I"),
+    ("m", "%-M"),
+    ("s", "%-S"),
+    ("A", "%p"),
+    ("a", "%P"),
+];
+pub(crate) const MOMENT_LITERAL_CLOSE: char = ']';
+pub(crate) const MOMENT_LITERAL_OPEN: char = '[';
+pub(crate) const STRFTIME_ESCAPED_PERCENT: &str = "%%";
+pub(crate) const STRFTIME_PERCENT: char = '%';
+/// The core Templates plugin's defaults when `templates.json` doesn't set a format.
+pub(crate) const TEMPLATE_DEFAULT_DATE_FORMAT: &str = "YYYY-MM-DD";
+pub(crate) const TEMPLATE_DEFAULT_TIME_FORMAT: &str = "HH:mm";
+pub(crate) const TEMPLATE_VARIABLE_DATE: &str = "date";
+pub(crate) const TEMPLATE_VARIABLE_FOLDER: &str = "folder";
+pub(crate) const TEMPLATE_VARIABLE_TIME: &str = "time";
+pub(crate) const TEMPLATE_VARIABLE_TITLE: &str = "title";
+/// The core Templates plugin's settings, in the vault's `.obsidian` folder.
+pub(crate) const TEMPLATES_SETTINGS_FILE: &str = "templates.json";
+/// The Templater plugin's settings, in its folder under `.obsidian/plugins`.
+pub(crate) const TEMPLATER_PLUGIN_FOLDER: &str = "templater-obsidian";
+pub(crate) const TEMPLATER_SETTINGS_FILE: &str = "data.json";
+
+// notification
+/// `curl` reads the summary from stdin and fails on an HTTP error status.
+pub(crate) const CURL_ARGS: [&str; 8] = [
+    "--silent",
+    "--show-error",
+    "--fail",
+    "--header",
+    "Content-Type: application/json",
+    "--data-binary",
+    "@-",
+    "--",
+];
+pub(crate) const CURL_EXECUTABLE: &str = "curl";
+pub(crate) const NOTIFICATION_NOT_SENT: &str = "run summary notification not sent:";
+pub(crate) const NOTIFICATION_SUBJECT: &str = "Subject: obsidian knife run summary";
+pub(crate) const NOTIFICATION_TO: &str = "To: ";
+pub(crate) const SENDMAIL_EXECUTABLE: &str = "sendmail";
+/// `sendmail -t` takes the recipient from the message's `To:` header.
+pub(crate) const SENDMAIL_READ_RECIPIENTS_FLAG: &str = "-t";
+
 // paths
 pub(crate) const HOME_ENVIRONMENT_VARIABLE: &str = "HOME";
+#[cfg(windows)]
+pub(crate) const LOCAL_APP_DATA_ENVIRONMENT_VARIABLE: &str = "LOCALAPPDATA";
+#[cfg(target_os = "macos")]
+pub(crate) const MACOS_CACHE_FOLDER: &str = "Library/Caches";
 pub(crate) const TILDE: &str = "~";
 pub(crate) const TILDE_SLASH: &str = "~/";
+#[cfg(not(any(target_os = "macos", windows)))]
+pub(crate) const XDG_CACHE_HOME_ENVIRONMENT_VARIABLE: &str = "XDG_CACHE_HOME";
+#[cfg(not(any(target_os = "macos", windows)))]
+pub(crate) const XDG_DEFAULT_CACHE_FOLDER: &str = ".cache";
 
 // processing
 #[cfg(debug_assertions)]
 pub(crate) const DEV: &str = "dev";
+pub(crate) const BASE_FILE_COLLECTION_LOCK_POISONED: &str = "base file collection lock poisoned";
+pub(crate) const CANVAS_FILE_COLLECTION_LOCK_POISONED: &str =
+    "canvas file collection lock poisoned";
 pub(crate) const ERROR_DETAILS: &str = "error details:";
 pub(crate) const ERROR_OCCURRED: &str = "error occurred";
 pub(crate) const ERROR_SOURCE: &str = "error source:";
@@ -134,7 +376,10 @@ pub(crate) const MARKDOWN_FILE_COLLECTION_LOCK_POISONED: &str =
 pub(crate) const MILLISECONDS: &str = "ms";
 pub(crate) const MODE_APPLY_CHANGES: &str = "apply changes is on - changes will be applied";
 pub(crate) const MODE_APPLY_CHANGES_OFF: &str = "apply changes is off - no changes will be applied";
+pub(crate) const TEMPLATE_FOLDERS_IGNORED: &str =
+    "template folders are left alone so their placeholders survive:";
 pub(crate) const OBSIDIAN_KNIFE: &str = "obsidian knife - aka \"ok\"";
+pub(crate) const OTHER_FILE_COLLECTION_LOCK_POISONED: &str = "other file collection lock poisoned";
 pub(crate) const OUTPUT_FILE_LOCK_POISONED: &str = "output file lock poisoned";
 pub(crate) const PERSIST_REQUIRES_FRONTMATTER: &str =
     "frontmatter is required to persist a markdown file";
@@ -142,44 +387,231 @@ pub(crate) const PERSIST_REQUIRES_RAW_DATE_MODIFIED: &str =
     "raw_date_modified must be set for persist";
 #[cfg(not(debug_assertions))]
 pub(crate) const RELEASE: &str = "release";
+pub(crate) const SKIPPED_CHANGED_ON_DISK: &str =
+    "not written - changed on disk since ok read it, run again to pick up the edit:";
 pub(crate) const TOTAL_TIME: &str = "total time";
-pub(crate) const USAGE: &str = "usage: obsidian_knife <obsidian_folder/config_file.md>";
+pub(crate) const USAGE: &str = "usage: obsidian_knife <obsidian_folder/config_file.md> [--profile <name>] [--rebuild-cache] \
+     [--daemon --interval <interval> | --explain-config | --sandbox | --verify-idempotent]";
+
+// query
+pub(crate) const QUERY_ARGS_START: usize = 2;
+pub(crate) const QUERY_COMMAND: &str = "query";
 
 // regex
+/// `@article{key,` - the entry type and the citation key opening a BibTeX entry.
+pub(crate) const BIBTEX_ENTRY_PATTERN: &str = r"^\s*@(\w+)\s*[{(]\s*([^,\s]+)\s*,";
+/// BibTeX entries that aren't references.
+pub(crate) const BIBTEX_NON_REFERENCE_TYPES: [&str; 3] = ["comment", "preamble", "string"];
+/// A `^block-id` ending a line, with the whitespace before it, or alone on its line - the id
+/// is captured.
+pub(crate) const BLOCK_ID_PATTERN: &str = r"(?:^|\s+)\^([A-Za-z0-9-]+)\s*$";
+/// `> [!type]+ Title` - the quote markers, the callout type and the title.
+pub(crate) const CALLOUT_HEADER_PATTERN: &str = r"^(\s*(?:>\s*)+)\[!([^\]]+)\][+-]?\s*(.*)$";
 pub(crate) const CASE_INSENSITIVE_WORD_PATTERN_PREFIX: &str = r"(?i)\b";
 pub(crate) const CASE_INSENSITIVE_WORD_PATTERN_SUFFIX: &str = r"\b";
+/// `[@key]`, `[see @key, p. 3; @other]` or the `[@key]` inside `[[@key]]`.
+pub(crate) const CITATION_GROUP_PATTERN: &str = r"\[[^\[\]]*@[^\[\]]*\]";
+/// A pandoc citation key in a citation group - `@` starts it only after a space, `;`, `[` or
+/// the `-` that suppresses the author, so email addresses don't count.
+pub(crate) const CITATION_KEY_PATTERN: &str =
+    r"(?:^|[\s;\[-])@([\p{L}\p{N}_][\p{L}\p{N}_:.#$%&+?<>~/-]*)";
+/// A citation key can't end with these - they belong to the text after it.
+pub(crate) const CITATION_KEY_TRAILING_PUNCTUATION: [char; 13] = [
+    ':', '.', '#', '$', '%', '&', '+', '?', '<', '>', '~', '/', '-',
+];
+/// `2024-03-02`, or a month and day before the year - `March 2, 2024`, `Mar. 2nd, 2024`.
+pub(crate) const DATE_MENTION_PATTERN: &str = r"\b(?:(\d{4}-\d{2}-\d{2})|((?:Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)[a-z]*)\.? (\d{1,2})(?:st|nd|rd|th)?, (\d{4}))\b";
+/// `$NAME` or `${NAME}` in a config path.
+pub(crate) const ENVIRONMENT_VARIABLE_PATTERN: &str =
+    r"\$(?:\{([A-Za-z_][A-Za-z0-9_]*)\}|([A-Za-z_][A-Za-z0-9_]*))";
 pub(crate) const EMAIL_PATTERN: &str = r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}";
+/// The `^card-` block id ok gives a flashcard, ending its answer line.
+pub(crate) const FLASHCARD_ID_PATTERN: &str = r"\s+\^(card-[0-9a-f]+)\s*$";
+/// A run of the characters keys and tokens are written with, long enough to be one.
+pub(crate) const HIGH_ENTROPY_TOKEN_PATTERN: &str = r"[A-Za-z0-9+/=_-]{20,}";
 pub(crate) const IMAGE_EXTENSIONS_SEPARATOR: &str = "|";
+/// A list item's indentation, then its `-`, `*` or `+` marker, or its number and `.` or `)`,
+/// followed by whitespace or the end of the line.
+pub(crate) const LIST_ITEM_PATTERN: &str = r"^([ \t]*)(?:([-*+])|(\d{1,9})([.)]))(?:[ \t]|$)";
 pub(crate) const MARKDOWN_LINK_PATTERN: &str = r"\[.*?\]\(.*?\)";
+/// `![[Note]]`, `![[Note#Heading]]` or `![[Note#^block-id]]`, with an optional `|alias`.
+/// A US social security number or a UK national insurance number.
+pub(crate) const NATIONAL_ID_PATTERN: &str =
+    r"\b(?:\d{3}-\d{2}-\d{4}|[A-CEGHJ-PR-TW-Z]{2} ?\d{2} ?\d{2} ?\d{2} ?[A-D])\b";
+pub(crate) const NOTE_EMBED_PATTERN: &str = r"!\[\[([^\]|#]*)(?:#([^\]|]*))?(?:\|[^\]]*)?\]\]";
+/// `555-123-4567`, `(555) 123 4567` or `+1 555.123.4567`.
+pub(crate) const PHONE_NUMBER_PATTERN: &str =
+    r"(?:\+\d{1,3}[ .-]?)?\(?\b\d{3}\)?[ .-]?\d{3}[ .-]\d{4}\b";
 pub(crate) const RAW_HTTP_PATTERN: &str = r"https?://[^\s]+";
+/// The credentials `scan_secrets` recognizes by their format, and what each is called.
+pub(crate) const SECRET_DETECTOR_PATTERNS: [(&str, &str); 6] = [
+    ("AWS access key", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+    (
+        "GitHub token",
+        r"\b(?:gh[pousr]_[0-9A-Za-z]{36}|github_pat_[0-9A-Za-z_]{22,})\b",
+    ),
+    ("Google API key", r"\bAIza[0-9A-Za-z_-]{35}"),
+    ("private key", r"-----BEGIN (?:[A-Z]+ )?PRIVATE KEY-----"),
+    ("Slack token", r"\bxox[abprs]-[0-9A-Za-z-]{10,}"),
+    (
+        "API key",
+        r"\b(?:(?:sk|rk)_(?:live|test)_[0-9A-Za-z]{16,}|sk-[0-9A-Za-z_-]{20,})",
+    ),
+];
 pub(crate) const TAG_PATTERN: &str = r"(?:^|\s)(#[a-zA-Z0-9_-]+)";
+pub(crate) const TAG_PREFIX: char = '#';
+/// `{{title}}`, `{{date}}`, `{{time}}` or `{{folder}}`, with an optional `:format`.
+pub(crate) const TEMPLATE_VARIABLE_PATTERN: &str = r"\{\{\s*(\w+)(?::([^}]*))?\s*\}\}";
 
 // report image handling
 pub(crate) const REPORT_CHUNK_SIZE: usize = 500;
 pub(crate) const THUMBNAIL_WIDTH: usize = 50;
 
 // report strings
+pub(crate) const ACRONYM: &str = "acronym";
+pub(crate) const ACRONYM_COLLISIONS: &str = "acronym collisions";
+pub(crate) const ACRONYM_COLLISIONS_DESCRIPTION: &str = "more than one note claims each of these \
+    acronyms - as its acronym, name or alias - so none of them is linked. Give each acronym to one note";
 pub(crate) const ACTION: &str = "action";
 pub(crate) const ADD_FRONTMATTER: &str = "add frontmatter";
+pub(crate) const ADDED: &str = "added";
+pub(crate) const ADMONITION: &str = "admonition";
 pub(crate) const AFTER: &str = "after";
+pub(crate) const ALIAS: &str = "alias";
+pub(crate) const ALL_NOTES: &str = "all notes";
+pub(crate) const AMBIGUOUS_MATCH_TASK: &str = "pick the note to link for";
+pub(crate) const ATTACHMENTS: &str = "attachments";
 pub(crate) const BACK_POPULATE: &str = "back populate";
 pub(crate) const BACK_POPULATE_FILE_FILTER_PREFIX: &str =
     "using back_populate_file_filter config parameter: ";
 pub(crate) const BACK_POPULATE_FILE_FILTER_SUFFIX: &str =
     "remove it from config if you want to process all files";
+pub(crate) const BARE_URL: &str = "bare URL - write it as [text](url)";
+pub(crate) const BASE: &str = "base";
 pub(crate) const BEFORE: &str = "before";
+pub(crate) const BLOCK_ID_ISSUES: &str = "block id issues";
+pub(crate) const BLOCK_ID_ISSUES_DESCRIPTION: &str = "links to these blocks reach only the first \
+    block with the id, or no block at all";
+pub(crate) const BLOCK_IDS: &str = "block ids";
+pub(crate) const BLOCK_IDS_DESCRIPTION: &str = "every ^block-id in the vault and how many links \
+    point at it";
+pub(crate) const BODY: &str = "body";
+pub(crate) const BROKEN_LINKS: &str = "broken links";
+pub(crate) const CALLOUT: &str = "callout";
+pub(crate) const CALLOUT_ISSUES: &str = "callout issues";
+pub(crate) const CALLOUT_ISSUES_DESCRIPTION: &str = "callouts need a space after > and one of \
+    obsidian's callout types - unknown types render as plain notes";
+pub(crate) const CANNOT_CONVERT: &str = "can't convert";
+pub(crate) const CANVAS: &str = "canvas";
+pub(crate) const CITATION_ISSUES: &str = "citation issues";
+pub(crate) const CITATION_ISSUES_DESCRIPTION: &str = "citation keys the bibliography doesn't hold, \
+    and bibliography entries no note cites";
+pub(crate) const CANVAS_AND_BASE_REFERENCES: &str = "canvas and base references";
+pub(crate) const CANVAS_AND_BASE_REFERENCES_DESCRIPTION: &str = "these canvases and bases point \
+    at notes or files that don't exist - Obsidian shows them as empty cards or drops them from views";
+pub(crate) const CHANGED_ON_DISK_NOTES: &str = "notes changed on disk";
+pub(crate) const CHANGED_ON_DISK_NOTES_DESCRIPTION: &str = "these notes were saved again after \
+    ok read them, so their changes are left for the next run";
+pub(crate) const CHANGE_TYPE_TASK: &str = "change the type of";
+pub(crate) const CHECKBOX: &str = "checkbox";
 pub(crate) const COLON: &str = ":";
+pub(crate) const COMMA: &str = ",";
+pub(crate) const COMMON_WORD: &str = "common word";
+pub(crate) const CONFLICT_COPIES: &str = "conflict copies";
+pub(crate) const CONFLICT_COPIES_DESCRIPTION: &str = "these notes are sync conflict copies of the \
+    note beside them - a copy something links to is left for you to resolve in obsidian";
+pub(crate) const CONFLICT_COPIES_FIX_DISABLED: &str = "set resolve_conflict_copies: delete or \
+    merge to remove them";
+pub(crate) const CONFLICT_COPY: &str = "conflict copy";
+pub(crate) const CONFLICT_COPY_TASK: &str = "resolve the conflict copy";
+pub(crate) const CONVERTED_ADMONITIONS: &str = "converted admonitions";
+pub(crate) const CONVERTED_ADMONITIONS_DESCRIPTION: &str = "these admonition code blocks will be \
+    rewritten as native callouts";
+pub(crate) const CONVERTS_TO: &str = "converts to";
 pub(crate) const CREATED: &str = "created";
+pub(crate) const CREATED_AFTER_MODIFIED: &str = "date_created after date_modified";
+pub(crate) const CURLY_QUOTE: &str = "curly quote";
+pub(crate) const CUSTOM_REPLACEMENTS: &str = "custom replacements";
+pub(crate) const CUSTOM_REPLACEMENTS_DESCRIPTION: &str = "these replacements from custom \
+    replacement sources will be made as shown - ones overlapping a built-in fix are skipped";
+pub(crate) const DASH_VARIANT: &str = "dash variant";
+pub(crate) const DASH: &str = "-";
+pub(crate) const DATE: &str = "date";
+pub(crate) const DATE_ISSUES: &str = "date issues";
+pub(crate) const DATE_MENTIONS: &str = "date mentions";
+pub(crate) const DATE_MENTIONS_DESCRIPTION: &str = "these plain-text dates will be linked to \
+    their daily note in date_link_format - dates in code, comments, math and links are skipped";
+pub(crate) const DATE_MISMATCHES: &str = "date mismatches";
+pub(crate) const DATE_PROPERTY_ISSUES: &str = "date property issues";
+pub(crate) const DATE_PROPERTY_ISSUES_DESCRIPTION: &str = "values of date_properties should be \
+    [[YYYY-MM-DD]] wikilinks - a plain date is linked, anything else is left for you to fix";
+pub(crate) const DATE_TIME: &str = "date & time";
 pub(crate) const DELETED: &str = "deleted";
+pub(crate) const DIFFERS: &str = "differs";
 pub(crate) const DUPLICATE: &str = "duplicate";
+pub(crate) const DUPLICATE_BLOCK_ID: &str = "id used by more than one block in the note";
+pub(crate) const DUPLICATE_FRONTMATTER_KEYS: &str = "duplicate frontmatter keys";
+pub(crate) const DUPLICATE_FRONTMATTER_KEYS_DESCRIPTION: &str = "these properties are written \
+    more than once - yaml keeps only the last value, so the others are lost on the next write";
+pub(crate) const DUPLICATE_FRONTMATTER_KEYS_FIX_DISABLED: &str = "set \
+    fix_duplicate_frontmatter_keys: keep_first or merge_lists to fix them";
 pub(crate) const DUPLICATE_IMAGES: &str = "duplicate images";
+pub(crate) const EXTRA_BRACKETS: &str = "extra brackets";
 pub(crate) const FILE: &str = "file";
+pub(crate) const FILE_NOT_FOUND: &str = "file not found";
 pub(crate) const FILES: &str = "files";
+pub(crate) const ENCRYPTED_BLOCKS: &str = "encrypted blocks";
+pub(crate) const ENCRYPTED_BLOCKS_DESCRIPTION: &str = "these lines hold ciphertext from an \
+    encryption plugin such as meld encrypt - nothing inside them is changed, so they still decrypt";
+pub(crate) const ENTITY_NOTES: &str = "entity notes";
+pub(crate) const ENTITY_NOTES_DESCRIPTION: &str = "notes in entity_folders that break their \
+    folder's conventions - a missing property needs a value only you can supply";
+pub(crate) const ENTITY_NOTES_FIX_DISABLED: &str = "set fix_entity_notes: true to add the missing \
+    aliases and index links";
+pub(crate) const FIRST: &str = "first";
+pub(crate) const FIRST_IN: &str = "first in";
+pub(crate) const FIX_FRONTMATTER_TASK: &str = "fix the frontmatter of";
+pub(crate) const FIX_IT_TASKS: &str = "fix-it tasks";
+pub(crate) const FIX_IT_TASKS_DESCRIPTION: &str = "issues ok leaves for you to resolve - check one off \
+    when it's done, and it stays checked for as long as it is listed";
+pub(crate) const FOREIGN_OWNED_NOTES: &str = "foreign-owned notes";
+pub(crate) const FOREIGN_OWNED_NOTES_DESCRIPTION: &str = "notes another owner's property names - \
+    only_modify_owner left these changes unwritten";
 pub(crate) const FOUND: &str = "found";
 pub(crate) const FRONTMATTER: &str = "frontmatter";
+pub(crate) const FRONTMATTER_ERRORS: &str = "frontmatter errors";
 pub(crate) const FRONTMATTER_ISSUES: &str = "frontmatter issues";
+pub(crate) const FRONTMATTER_LINK_ISSUES: &str = "frontmatter link issues";
+pub(crate) const FRONTMATTER_LINK_ISSUES_DESCRIPTION: &str = "values of frontmatter_link_properties \
+    should be wikilinks to existing notes";
+pub(crate) const FRONTMATTER_LINK_ISSUES_FIX_DISABLED: &str = "set fix_frontmatter_link_values: \
+    true to link note names and create_missing_link_targets: true to create missing notes";
+pub(crate) const FRONTMATTER_ROUND_TRIP_FAILED: &str = "frontmatter doesn't read back:";
+pub(crate) const FUTURE_DATE_CREATED: &str = "date_created in the future";
+pub(crate) const FUTURE_DATE_MODIFIED: &str = "date_modified in the future";
+pub(crate) const GENERATED_ALIASES: &str = "generated aliases";
+pub(crate) const GENERATED_ALIASES_DESCRIPTION: &str = "variants of these note names were added \
+    as aliases so back populate can match them - a variant that names another note or alias, or \
+    that more than one note would get, is skipped";
+pub(crate) const HARD_TAB: &str = "tab inside the line";
+pub(crate) const HEADING: &str = "heading";
+pub(crate) const HEAVIEST: &str = "heaviest";
+pub(crate) const HEAVIEST_NOTES: &str = "heaviest notes";
+pub(crate) const HEAVIEST_NOTES_DESCRIPTION: &str = "notes by the total size of the images and pdfs \
+    they reference - a file referenced more than once counts once";
+pub(crate) const IDENTICAL: &str = "identical";
+pub(crate) const HIDDEN_FOLDERS: &str = "hidden folders";
+pub(crate) const HIDDEN_FOLDERS_DESCRIPTION: &str = "obsidian's trash and settings folders - set \
+    purge_trash_after_days to delete trash untouched for that long";
+pub(crate) const IMPOSSIBLE_DATES: &str = "impossible dates";
+pub(crate) const IMPOSSIBLE_DATES_DESCRIPTION: &str = "no note can be created after it was last \
+    modified or have a date in the future - usually a copied file or a wrong clock";
+pub(crate) const IMPOSSIBLE_DATES_FIX_DISABLED: &str = "set fix_impossible_dates: \
+    created_to_modified or modified_to_created to fix them";
 pub(crate) const IMAGE_FILE: &str = "image file";
 pub(crate) const IMAGE_FILE_HASH: &str = "image file hash";
+pub(crate) const IMAGE_ISSUES: &str = "image issues";
+pub(crate) const IMAGE_LINKS_ADDED: &str = "image links added";
 pub(crate) const IMAGES: &str = "images";
 pub(crate) const IN: &str = "in";
 pub(crate) const IN_CHANGESET: &str = "in changeset";
@@ -187,63 +619,419 @@ pub(crate) const INCOMPATIBLE: &str = "incompatible";
 pub(crate) const INCOMPATIBLE_IMAGES: &str = "incompatible images";
 pub(crate) const INFO: &str = "info";
 pub(crate) const INSTANCES: &str = "instances";
+pub(crate) const INTEGRITY_FAILURES: &str = "integrity failures";
+pub(crate) const INTEGRITY_FAILURES_DESCRIPTION: &str = "the content these notes would be written \
+    with failed a check, so they were left as they are on disk - this is a bug worth reporting";
 pub(crate) const INVALID: &str = "invalid";
+pub(crate) const INVALID_PATH_CHARACTERS: &str = "invalid characters";
 pub(crate) const INVALID_WIKILINKS: &str = "invalid wikilinks";
+pub(crate) const ISSUES_BY_FOLDER: &str = "issues by folder";
+pub(crate) const ISSUES_BY_FOLDER_DESCRIPTION: &str = "issues counted by the top-level folder of \
+    the note or image they are in, most first - each count links to the section listing them";
+pub(crate) const JOURNAL_DATE_MISMATCHES: &str = "journal date mismatches";
+pub(crate) const JOURNAL_DATE_MISMATCHES_DESCRIPTION: &str = "these notes are named for a day, but \
+    their date_created or file creation date is another - the name is taken as right and both are \
+    moved to it";
 pub(crate) const KEEPER: &str = "keeper";
+pub(crate) const KEPT: &str = "kept";
+pub(crate) const LENGTH: &str = "length";
 pub(crate) const LINE: &str = "line";
+pub(crate) const LINES: &str = "lines";
+pub(crate) const LINKED_RESOLVE_IN_OBSIDIAN: &str = "linked - resolve in obsidian";
+pub(crate) const LINK_ADDED: &str = "link added";
 pub(crate) const LINK_CLICK_TO_CREATE: &str = "link (click to create)";
+pub(crate) const LINKS_TO: &str = "links to";
+pub(crate) const LIST: &str = "list";
+pub(crate) const LIST_INDENTATION: &str = "indentation";
+pub(crate) const LIST_MARKER: &str = "marker";
+pub(crate) const LIST_NUMBERING: &str = "numbering";
+pub(crate) const LONG_LINE: &str = "line longer than lint_max_line_length";
+pub(crate) const MARKDOWN_LINT: &str = "markdown lint";
+pub(crate) const MARKDOWN_LINT_DESCRIPTION: &str = "these lines break common markdown style - \
+    nothing is changed, so fix them by hand or leave them";
 pub(crate) const MATCHES: &str = "matches";
+pub(crate) const MATCHING: &str = "matching";
+pub(crate) const MERGE_INTO: &str = "merge into";
 pub(crate) const MATCHES_AMBIGUOUS: &str = "ambiguous matches";
+/// Opens a collapsed callout holding the lines around one ambiguous match.
+pub(crate) const MATCH_CONTEXT_CALLOUT: &str = "> [!quote]-";
+pub(crate) const MATCH_CONTEXT_LINE_PREFIX: &str = "> ";
+/// Marks a target whose alias, rather than its name, is the matched text.
+pub(crate) const VIA_ALIAS: &str = "(alias)";
+pub(crate) const MISSING_BLOCK_ID: &str = "no block with this id in the note";
+pub(crate) const MISSING_FRONTMATTER: &str = "missing frontmatter";
 pub(crate) const MISSING_IMAGE: &str = "missing image";
 pub(crate) const MISSING_IMAGE_REFERENCES: &str = "files that refer to images that don't exist";
+pub(crate) const MISSING_REQUIRED_PROPERTY: &str = "missing property";
+pub(crate) const MISSING_SPACE: &str = "missing space after >";
+pub(crate) const MISSING_TARGET: &str = "missing target";
+pub(crate) const MOST_COMMON_TYPE: &str = "most common type";
 pub(crate) const MOST_RECENT_UNIQUE_LINES: &str = "most recent unique lines";
+pub(crate) const NAME_TAKEN: &str = "suggested name taken - rename by hand";
+pub(crate) const NEEDS_REVIEW: &str = "needs review";
+pub(crate) const NEEDS_REVIEW_DESCRIPTION: &str = "these notes haven't been modified for longer \
+    than their review_after rule allows - the longest overdue come first";
+pub(crate) const NEEDS_REVIEW_NEXT_REVIEW_DISABLED: &str = "set set_next_review: true to write \
+    the day each note falls due to its next_review property";
+pub(crate) const NESTED_WIKILINK_BRACKETS: &str = "nested wikilink brackets added";
+pub(crate) const NEW_NOTE: &str = "new note";
+pub(crate) const NEVER_CITED: &str = "never cited";
+pub(crate) const NO_BLANK_LINE_BEFORE_HEADING: &str = "no blank line before heading";
+pub(crate) const NO_BLANK_LINE_BEFORE_LIST: &str = "no blank line before list";
+pub(crate) const NO_ALIAS_STARTING_WITH: &str = "no alias starting with";
 pub(crate) const NO_CHANGE: &str = "no change";
+pub(crate) const NO_EXTENSION: &str = "(none)";
+pub(crate) const NON_BREAKING_SPACE: &str = "non-breaking space";
 pub(crate) const NON_CANONICAL_LINK: &str = "non-canonical link";
 pub(crate) const NON_CANONICAL_LINKS: &str = "non-canonical links";
 pub(crate) const NON_CANONICAL_LINKS_DESCRIPTION: &str = "these links name their target note \
     by vault path or case variant - each will be rewritten to the note's file name";
+pub(crate) const NORMALIZED_LISTS: &str = "normalized lists";
+pub(crate) const NORMALIZED_LISTS_DESCRIPTION: &str = "these list items will be rewritten to use \
+    `-` markers, one tab per nesting level and consecutive numbers, so Obsidian folds each list as \
+    one";
+pub(crate) const NORMALIZED_PUNCTUATION: &str = "normalized punctuation";
+pub(crate) const NORMALIZED_PUNCTUATION_DESCRIPTION: &str = "these characters will be replaced with \
+    their plain equivalents and zero-width characters removed, so wikilinks and searches match";
+pub(crate) const NOTHING_TO_FIX: &str = "nothing needs fixing by hand";
+pub(crate) const NOT_A_LINK: &str = "not a link";
+pub(crate) const NOT_IN_BIBLIOGRAPHY: &str = "not in the bibliography";
+pub(crate) const NOT_LINKED_FROM: &str = "not linked from";
 pub(crate) const NOT_REFERENCED: &str = "not referenced";
+pub(crate) const NOTES: &str = "notes";
+pub(crate) const NOTE_NAME_NOT_LINKED: &str = "note name not linked";
+pub(crate) const NUMBER: &str = "number";
 pub(crate) const OCCURRENCES: &str = "occurrences";
 pub(crate) const OF: &str = "of";
+pub(crate) const ORPHAN_IMAGES: &str = "orphan images";
+pub(crate) const OTHER_FILES: &str = "other files";
+pub(crate) const OTHER_FILES_DESCRIPTION: &str = "these files are neither notes, images, canvases \
+    nor bases - obsidian can't open the unexpected ones, so they probably belong outside the vault";
 pub(crate) const PATH: &str = "path";
+pub(crate) const PATH_TOO_LONG: &str = "too long";
+pub(crate) const PERSIST_REASON_COUNTS_DESCRIPTION: &str = "notes per reason - held notes have the \
+    change found but not made, because hold_persist_reasons names it";
+pub(crate) const PERSONAL_DATA: &str = "personal data";
+pub(crate) const PERSONAL_DATA_DESCRIPTION: &str = "these folders hold email addresses, phone \
+    numbers, national ID numbers or pii_patterns matches - counts only, so the report doesn't \
+    copy the data it found";
 pub(crate) const PHANTOM_LINK: &str = "phantom link";
 pub(crate) const PHANTOM_LINKS: &str = "phantom links";
 pub(crate) const PHANTOM_LINKS_DESCRIPTION: &str = "these links point at missing notes whose \
     text matches exactly one existing alias or filename - each will be re-targeted at that note";
+pub(crate) const PLUGIN_FOLDERS: &str = "plugin folders";
+pub(crate) const PLURAL: &str = "plural";
 pub(crate) const POSITION: &str = "position";
+pub(crate) const POSSIBLE_SECRETS: &str = "possible secrets";
+pub(crate) const POSSIBLE_SECRETS_DESCRIPTION: &str = "these look like keys or tokens - move real \
+    ones out of the vault and rotate them, or put samples in a code block labeled example";
+pub(crate) const PERIODIC_NOTES: &str = "periodic notes";
+pub(crate) const PERIODIC_NOTES_DESCRIPTION: &str = "daily notes link up to their weekly note and \
+    weekly notes to their monthly note through periodic_note_link_property - missing weekly and \
+    monthly notes are created beside the notes that link to them";
+pub(crate) const PERIODIC_PARENT: &str = "links up to";
+pub(crate) const PROPERTY_ERRORS: &str = "property errors";
+pub(crate) const PROPERTY_TYPES: &str = "property types";
+pub(crate) const PROPERTY_TYPES_DESCRIPTION: &str = "these properties hold different types in \
+    different notes - obsidian's properties view expects one type per property and marks the \
+    others invalid";
+pub(crate) const PROPERTY_TYPES_FIX_DISABLED: &str = "set coerce_property_types: true to convert \
+    the values shown to the most common type";
+pub(crate) const RARE_WORDS: &str = "rare words";
+pub(crate) const RARE_WORDS_DESCRIPTION: &str = "these words in titles, headings and aliases are \
+    rare in the vault and one letter away from a much more common word in it, so they may be typos \
+    - they aren't checked against a dictionary. add words you mean to dictionary.md in the output \
+    folder, one per line";
 pub(crate) const REASON: &str = "reason";
+pub(crate) const RECENTLY_MODIFIED_NOTES: &str = "recently modified notes";
+pub(crate) const RECENTLY_MODIFIED_NOTES_DESCRIPTION: &str = "these notes were edited within \
+    skip_recently_modified_minutes, so they may be open - their changes are left for a later run";
+pub(crate) const REDUNDANT_ALIAS: &str = "redundant alias";
 pub(crate) const REFERENCED_BY: &str = "referenced by";
 pub(crate) const REFERENCES_TO: &str = "references to";
 pub(crate) const REFERENCE_CHANGE: &str = "reference change";
 pub(crate) const REFERENCE_REMOVED: &str = " - reference removed";
 pub(crate) const REFERENCE_WILL_BE_REMOVED: &str = "reference will be removed";
+pub(crate) const REFRESHED: &str = "refreshed";
+pub(crate) const REGEX_REPLACEMENTS: &str = "regex replacements";
+pub(crate) const REGEX_REPLACEMENTS_DESCRIPTION: &str = "these matches of the replacements rules \
+    will be rewritten as shown - matches inside link fixes or back populated text are skipped";
+pub(crate) const REMOVED: &str = "removed";
+pub(crate) const RENAME: &str = "rename";
+pub(crate) const RENAME_FOLDER: &str = "rename the folder in obsidian";
+pub(crate) const RENAME_IN_OBSIDIAN: &str = "linked - rename in obsidian";
+pub(crate) const REPAIRED_HEADING_LINKS: &str = "repaired heading links";
+pub(crate) const REPAIRED_HEADING_LINKS_DESCRIPTION: &str = "the headings of these links were \
+    renamed - each link will point at the one current heading it matches";
+pub(crate) const REPAIRED_WIKILINKS: &str = "repaired wikilinks";
+pub(crate) const REPAIRED_WIKILINKS_DESCRIPTION: &str = "these invalid wikilinks have only one \
+    plausible intended form - each will be rewritten as shown";
+pub(crate) const REPLACEMENT_CONFLICTS: &str = "replacement conflicts";
+pub(crate) const REPLACEMENT_CONFLICTS_DESCRIPTION: &str = "these changes overlap a change that \
+    ranks ahead of them on the same text - they were skipped so the line isn't mangled";
+/// The report's tag and css class, so it can be found, queried and styled in Obsidian.
+pub(crate) const REPORT_TAG: &str = "obsidian-knife-report";
+pub(crate) const RESERVED_NAME: &str = "reserved name";
+pub(crate) const ROWS_IN_OVERFLOW_FILE: &str = "rows - every row is in";
+pub(crate) const SCORE: &str = "score";
+pub(crate) const SECTION_NOTE_NAME_TAKEN: &str = "note name taken - section left in place";
+pub(crate) const SELF_LINK: &str = "self link";
+pub(crate) const SELF_LINKS: &str = "self links and redundant aliases";
+pub(crate) const SELF_LINKS_DESCRIPTION: &str = "these links point at the note that contains them \
+    or repeat their target as the alias - self links become plain text and redundant aliases \
+    are dropped";
+pub(crate) const SELF_LINKS_FIX_DISABLED: &str =
+    "set fix_self_links: true in the config to apply these fixes";
 pub(crate) const SHOWING_THE: &str = "showing the";
+pub(crate) const SIMILAR_TITLES: &str = "similar titles";
+pub(crate) const SIMILAR_TITLES_DESCRIPTION: &str = "these notes have near-duplicate titles or \
+    aliases - merge into is the note more links point at";
+pub(crate) const SIMILARITY: &str = "similarity";
+pub(crate) const SINGULAR: &str = "singular";
 pub(crate) const SOURCE_TEXT: &str = "source text";
+pub(crate) const SPLIT_NOTES: &str = "split notes";
+pub(crate) const SPLIT_NOTES_DESCRIPTION: &str = "each of these sections moves to a note named \
+    after its heading and is embedded where it was - links to the heading follow it";
+pub(crate) const STALE_HEADING_LINKS: &str = "stale heading links";
+pub(crate) const STALE_HEADING_LINKS_DESCRIPTION: &str = "these links name a heading their note \
+    no longer has - a suggestion is a current heading as close as another, so fix the link by hand";
+pub(crate) const STALE_WORKSPACE_FILE: &str = "stale workspace file";
+pub(crate) const SUGGESTION: &str = "suggestion";
+pub(crate) const SUPPRESSED_INVALID_WIKILINKS: &str =
+    "not shown because of ignore_invalid_wikilink_reasons:";
 pub(crate) const TEXT: &str = "text";
 pub(crate) const THUMBNAIL: &str = "thumbnail";
+pub(crate) const TIE: &str = "tie";
 pub(crate) const TIFF: &str = "TIFF";
+pub(crate) const TITLE: &str = "title";
+pub(crate) const TRAILING_DOT_OR_SPACE: &str = "trailing dot or space";
+pub(crate) const TRASH: &str = "trash";
+pub(crate) const TREND: &str = "trend";
 pub(crate) const TYPE: &str = "type";
+pub(crate) const UNBALANCED_WIKILINK_BRACKETS: &str = "wikilink brackets no longer balance";
+/// Punctuation an unclosed wikilink's note name may end before, as well as a space.
+pub(crate) const UNCLOSED_LINK_TEXT_ENDS: [char; 7] = [',', '.', ';', ':', '!', '?', ')'];
+pub(crate) const UNCLOSED_WIKILINK: &str = "unclosed";
+pub(crate) const UNEXPECTED_FILE_TYPE: &str = "unexpected file type";
 pub(crate) const UNKNOWN: &str = "unknown";
+pub(crate) const UNKNOWN_CALLOUT_TYPE: &str = "unknown callout type";
+pub(crate) const UNPARSEABLE: &str = "can't be parsed";
 pub(crate) const UNREFERENCED_IMAGES: &str = "unreferenced images";
 pub(crate) const UNRESOLVED_LINKS: &str = "unresolved links";
 pub(crate) const UNRESOLVED_LINKS_DESCRIPTION: &str =
     "these links point at notes that don't exist - click one to create the note, or fix the link";
+pub(crate) const UNRESOLVED_WIKILINK: &str = "no note or file of that name";
+pub(crate) const UNSAFE_PATHS: &str = "unsafe paths";
+pub(crate) const UNSAFE_PATHS_DESCRIPTION: &str = "these paths can't be synced to windows or \
+    android as they are - set rename_unsafe_paths: true to rename the ones marked rename";
 pub(crate) const UPDATE: &str = "update";
+pub(crate) const VALUES: &str = "values";
+pub(crate) const VAULT_HEALTH: &str = "vault health";
+pub(crate) const VAULT_HEALTH_DESCRIPTION: &str = "the score is 100 less the average share of \
+    notes with broken links, missing frontmatter or date mismatches and of unreferenced images - \
+    one entry per day is kept in .ok/vault_health_history.json";
+pub(crate) const VAULT_HEALTH_SCORE: &str = "vault health score";
+pub(crate) const VAULT_ROOT: &str = "(vault root)";
 pub(crate) const WIKILINKS: &str = "wikilinks";
 pub(crate) const WILL_BE_BACK_POPULATED: &str = "will be back populated";
+pub(crate) const WILL_CREATE: &str = "will create";
 pub(crate) const WILL_DELETE: &str = "will delete";
+pub(crate) const WILL_MERGE_AND_DELETE: &str = "will merge, then delete";
+pub(crate) const WILL_PURGE: &str = "will purge";
 pub(crate) const WILL_REPLACE_WITH: &str = "will replace with";
+pub(crate) const WITH_ATTACHMENTS: &str = "with attachments";
+pub(crate) const WITHHELD_FROM_EXPORT: &str = "withheld from export";
+pub(crate) const WITHHELD_FROM_EXPORT_DESCRIPTION: &str = "export.redact left these out of the \
+    exported notes";
+pub(crate) const WITHHELD_LINE_MATCHING: &str = "line matching";
+pub(crate) const WITHHELD_NOTE_TAGGED: &str = "note tagged";
+pub(crate) const WITHOUT_PARENTHETICAL: &str = "without parenthetical";
+pub(crate) const WORD: &str = "word";
 pub(crate) const YAML_APPLY_CHANGES: &str = "apply_changes: ";
+pub(crate) const YAML_CSSCLASSES: &str = "cssclasses: ";
+pub(crate) const YAML_DATE_CREATED: &str = "date_created: ";
+pub(crate) const YAML_DATE_MODIFIED: &str = "date_modified: ";
 pub(crate) const YAML_FALSE: &str = "false";
 pub(crate) const YAML_FILE_LIMIT: &str = "file_limit: ";
 pub(crate) const YAML_NONE: &str = "None";
+pub(crate) const YAML_RUN_ID: &str = "run_id: ";
+pub(crate) const YAML_TAGS: &str = "tags: ";
 pub(crate) const YAML_TIMESTAMP_LOCAL: &str = "local_time: ";
 pub(crate) const YAML_TIMESTAMP_UTC: &str = "utc_time: ";
 pub(crate) const YAML_TRUE: &str = "true";
 pub(crate) const YOU_HAVE_TO_FIX_THESE_YOURSELF: &str = "you have to fix these yourself";
 pub(crate) const ZERO_BYTE: &str = "zero-byte";
+pub(crate) const ZERO_WIDTH_CHARACTER: &str = "zero-width character";
+
+// sandbox
+pub(crate) const SANDBOX_APPLIED: &str =
+    "changes applied to a copy of the vault - the vault is unchanged:";
+/// Start of the sandbox folder name `--sandbox` leaves in the system temp folder.
+pub(crate) const SANDBOX_PREFIX: &str = "obsidian_knife_sandbox_";
+pub(crate) const SANDBOX_STATE_FOLDER: &str = "state";
+/// Follows the prefix, so sandboxes sort by when they were made.
+pub(crate) const SANDBOX_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+pub(crate) const SANDBOX_VAULT_FOLDER: &str = "vault";
+
+// patch
+/// Index of the patch file in `std::env::args()`, after the `apply` subcommand.
+pub(crate) const APPLY_ARGS_START: usize = 2;
+pub(crate) const APPLY_COMMAND: &str = "apply";
+pub(crate) const APPLY_USAGE: &str = "usage: obsidian_knife apply <patch_file> [--only <category or \
+                                      path>]... [--exclude <category or path>]...";
+pub(crate) const EXCLUDE_FLAG: &str = "--exclude";
+pub(crate) const ONLY_FLAG: &str = "--only";
+pub(crate) const PATCH_APPLIED: &str = "applied patch -";
+/// What `--only` and `--exclude` can name - `images` names every `images:` category.
+pub(crate) const PATCH_CATEGORIES: [&str; 27] = [
+    PATCH_CATEGORY_ADMONITIONS,
+    PATCH_CATEGORY_ALIASES,
+    PATCH_CATEGORY_BACK_POPULATE,
+    PATCH_CATEGORY_BLOCK_IDS,
+    PATCH_CATEGORY_CANONICAL_LINKS,
+    PATCH_CATEGORY_CONFLICT_COPIES,
+    PATCH_CATEGORY_CUSTOM_REPLACEMENTS,
+    PATCH_CATEGORY_DATES,
+    PATCH_CATEGORY_ENTITY_NOTES,
+    PATCH_CATEGORY_FLASHCARDS,
+    PATCH_CATEGORY_FRONTMATTER,
+    PATCH_CATEGORY_FRONTMATTER_LINKS,
+    PATCH_CATEGORY_IMAGE_DUPLICATES,
+    PATCH_CATEGORY_IMAGE_INCOMPATIBLE,
+    PATCH_CATEGORY_IMAGE_REFERENCES,
+    PATCH_CATEGORY_IMAGE_UNREFERENCED,
+    PATCH_CATEGORY_LINK_TARGETS,
+    PATCH_CATEGORY_LISTS,
+    PATCH_CATEGORY_PERIODIC_NOTES,
+    PATCH_CATEGORY_PHANTOM_LINKS,
+    PATCH_CATEGORY_PROPERTY_TYPES,
+    PATCH_CATEGORY_PUNCTUATION,
+    PATCH_CATEGORY_REPLACEMENTS,
+    PATCH_CATEGORY_SELF_LINKS,
+    PATCH_CATEGORY_SPLIT_NOTES,
+    PATCH_CATEGORY_TABLE_OF_CONTENTS,
+    PATCH_CATEGORY_WIKILINK_REPAIRS,
+];
+pub(crate) const PATCH_CATEGORY_ADMONITIONS: &str = "admonitions";
+pub(crate) const PATCH_CATEGORY_ALIASES: &str = "aliases";
+pub(crate) const PATCH_CATEGORY_BACK_POPULATE: &str = "back-populate";
+pub(crate) const PATCH_CATEGORY_BLOCK_IDS: &str = "block-ids";
+pub(crate) const PATCH_CATEGORY_CANONICAL_LINKS: &str = "canonical-links";
+pub(crate) const PATCH_CATEGORY_CONFLICT_COPIES: &str = "conflict-copies";
+pub(crate) const PATCH_CATEGORY_CUSTOM_REPLACEMENTS: &str = "custom-replacements";
+pub(crate) const PATCH_CATEGORY_DATES: &str = "dates";
+pub(crate) const PATCH_CATEGORY_ENTITY_NOTES: &str = "entity-notes";
+pub(crate) const PATCH_CATEGORY_FLASHCARDS: &str = "flashcards";
+pub(crate) const PATCH_CATEGORY_FRONTMATTER: &str = "frontmatter";
+pub(crate) const PATCH_CATEGORY_FRONTMATTER_LINKS: &str = "frontmatter-links";
+pub(crate) const PATCH_CATEGORY_IMAGE_DUPLICATES: &str = "images:duplicates";
+pub(crate) const PATCH_CATEGORY_IMAGE_INCOMPATIBLE: &str = "images:incompatible";
+pub(crate) const PATCH_CATEGORY_IMAGE_REFERENCES: &str = "images:references";
+pub(crate) const PATCH_CATEGORY_IMAGE_UNREFERENCED: &str = "images:unreferenced";
+pub(crate) const PATCH_CATEGORY_LINK_TARGETS: &str = "link-targets";
+pub(crate) const PATCH_CATEGORY_LISTS: &str = "lists";
+pub(crate) const PATCH_CATEGORY_PERIODIC_NOTES: &str = "periodic-notes";
+pub(crate) const PATCH_CATEGORY_PHANTOM_LINKS: &str = "phantom-links";
+pub(crate) const PATCH_CATEGORY_PROPERTY_TYPES: &str = "property-types";
+pub(crate) const PATCH_CATEGORY_PUNCTUATION: &str = "punctuation";
+pub(crate) const PATCH_CATEGORY_REPLACEMENTS: &str = "replacements";
+pub(crate) const PATCH_CATEGORY_SELF_LINKS: &str = "self-links";
+/// `images:duplicates` also answers to `images`.
+pub(crate) const PATCH_CATEGORY_SEPARATOR: char = ':';
+pub(crate) const PATCH_CATEGORY_SPLIT_NOTES: &str = "split-notes";
+pub(crate) const PATCH_CATEGORY_TABLE_OF_CONTENTS: &str = "table-of-contents";
+pub(crate) const PATCH_CATEGORY_WIKILINK_REPAIRS: &str = "wikilink-repairs";
+pub(crate) const PATCH_WRITTEN: &str = "wrote patch -";
+
+pub(crate) const RUN_LOCK_FILE: &str = "obsidian_knife.lock";
+/// `kill -0` only checks whether a process exists.
+#[cfg(unix)]
+pub(crate) const RUN_LOCK_PROCESS_CHECK_ARG: &str = "-0";
+#[cfg(unix)]
+pub(crate) const RUN_LOCK_PROCESS_CHECK_EXECUTABLE: &str = "kill";
+/// A lock without its holder that is this new may belong to a run that's still writing it.
+pub(crate) const RUN_LOCK_INCOMPLETE_GRACE_SECONDS: i64 = 10;
+pub(crate) const RUN_LOCK_PID_PREFIX: &str = "pid: ";
+/// A run takes seconds, so a lock this old was left by a crash even if its pid was reused.
+pub(crate) const RUN_LOCK_STALE_AFTER_HOURS: i64 = 12;
+/// A stale lock is renamed to this before it is removed, so only one run can take it over.
+pub(crate) const RUN_LOCK_STALE_SUFFIX: &str = ".stale";
+pub(crate) const RUN_LOCK_STARTED_PREFIX: &str = "started: ";
+pub(crate) const STALE_RUN_LOCK_REMOVED: &str = "removed stale lock left by an earlier run:";
+
+// benchmarks
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_ARGS_START: usize = 2;
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_COMMAND: &str = "bench";
+/// Frontmatter shaped like a well-kept note's - dates, aliases, tags and a nested property.
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_FRONTMATTER: &str = "date_created: \"[[2024-01-15]]\"
+date_modified: \"[[2024-06-02]]\"
+aliases:
+  - Topic One
+  - First Topic
+tags:
+  - project
+  - reference
+status: active
+source:
+  title: A Book
+  year: 2021
+";
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_GROUP_BACK_POPULATE_MATCHING: &str = "back_populate_matching";
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_GROUP_IMAGE_HASHING: &str = "image_hashing";
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_GROUP_REPORT_TABLES: &str = "report_tables";
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_GROUP_WIKILINK_EXTRACTION: &str = "wikilink_extraction";
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_GROUP_YAML_ROUND_TRIP: &str = "yaml_round_trip";
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_OUTPUT_FOLDER: &str = "output";
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_REPORT_HEADERS: [&str; 4] = ["file", "line", "notes", "title"];
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_USAGE: &str = "usage: obsidian_knife bench [filter]";
+/// Note counts of the generated vaults the benchmarks run against.
+#[cfg(feature = "bench")]
+pub(crate) const BENCH_VAULT_SIZES: [usize; 3] = [100, 1_000, 5_000];
+
+// vault generator
+pub(crate) const DEFAULT_GENERATED_IMAGE_DUPLICATION: f64 = 0.1;
+pub(crate) const DEFAULT_GENERATED_LINK_DENSITY: f64 = 0.05;
+pub(crate) const DEFAULT_GENERATED_NOTE_COUNT: usize = 1000;
+pub(crate) const DEFAULT_GENERATED_SEED: u64 = 1;
+/// Index of the subcommand in `std::env::args()`; its own arguments follow it.
+pub(crate) const GENERATE_VAULT_ARGS_START: usize = 2;
+pub(crate) const GENERATE_VAULT_COMMAND: &str = "generate-vault";
+pub(crate) const GENERATE_VAULT_USAGE: &str = "usage: obsidian_knife generate-vault <folder> \
+                                               [--notes N] [--link-density F] \
+                                               [--image-duplication F] [--seed N]";
+pub(crate) const GENERATED_FILLER_WORDS: [&str; 8] = [
+    "the", "and", "about", "with", "then", "notes", "later", "before",
+];
+pub(crate) const GENERATED_IMAGE_PREFIX: &str = "image ";
+/// One image for every this many notes.
+pub(crate) const GENERATED_IMAGES_PER_NOTE_DIVISOR: usize = 10;
+pub(crate) const GENERATED_LINES_PER_NOTE: usize = 12;
+pub(crate) const GENERATED_NOTE_PREFIX: &str = "Topic ";
+pub(crate) const GENERATED_VAULT_SUMMARY: &str = "generated vault:";
+/// Share of links written as wikilinks; the rest are plain mentions.
+pub(crate) const GENERATED_WIKILINK_SHARE: f64 = 0.5;
+pub(crate) const GENERATED_WORDS_PER_LINE: usize = 10;
+pub(crate) const IMAGE_DUPLICATION_FLAG: &str = "--image-duplication";
+pub(crate) const LINK_DENSITY_FLAG: &str = "--link-density";
+pub(crate) const NOTES_FLAG: &str = "--notes";
+pub(crate) const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+pub(crate) const SEED_FLAG: &str = "--seed";
 
 // yaml frontmatter
+pub(crate) const ACRONYM_PROPERTY: &str = "acronym";
+pub(crate) const NEXT_REVIEW_PROPERTY: &str = "next_review";
+pub(crate) const TAGS_PROPERTY: &str = "tags";
 pub(crate) const YAML_EXPECTED_MAPPING: &str = "Expected a mapping";
 pub(crate) const YAML_FRONTMATTER_EMPTY: &str =
     "yaml frontmatter delimiters are present but there is no yaml";
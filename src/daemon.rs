@@ -0,0 +1,71 @@
+use std::thread;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::constants::DAEMON_LOCK_REFRESH_SECONDS;
+use crate::constants::INTERVAL_UNITS;
+use crate::run_lock::RunLock;
+use crate::run_lock::RunLockError;
+
+#[derive(Debug, Error)]
+pub(crate) enum DaemonError {
+    #[error("invalid interval '{0}' - use a number and a unit of s, m, h or d, such as 6h")]
+    InvalidInterval(String),
+}
+
+/// Parses an interval such as `6h` - a whole number followed by `s`, `m`, `h` or `d`.
+pub(crate) fn parse_interval(interval: &str) -> Result<Duration, DaemonError> {
+    let invalid = || DaemonError::InvalidInterval(interval.to_string());
+
+    let unit = interval.chars().last().ok_or_else(invalid)?;
+    let (_, unit_seconds) = INTERVAL_UNITS
+        .iter()
+        .find(|(symbol, _)| *symbol == unit)
+        .ok_or_else(invalid)?;
+    let count: u64 = interval[..interval.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+
+    match count.checked_mul(*unit_seconds) {
+        Some(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Sleeps for `interval`, refreshing the daemon's lock along the way so a long interval
+/// doesn't leave it looking stale to another run.
+pub(crate) fn wait(run_lock: &RunLock, interval: Duration) -> Result<(), RunLockError> {
+    let refresh_interval = Duration::from_secs(DAEMON_LOCK_REFRESH_SECONDS);
+    let mut remaining = interval;
+    while !remaining.is_zero() {
+        let step = remaining.min(refresh_interval);
+        thread::sleep(step);
+        remaining -= step;
+        run_lock.refresh()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::time::Duration;
+
+    use super::parse_interval;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_interval("30m").unwrap(), Duration::from_mins(30));
+        assert_eq!(parse_interval("6h").unwrap(), Duration::from_hours(6));
+        assert_eq!(parse_interval("1d").unwrap(), Duration::from_hours(24));
+
+        for invalid in ["", "6", "h", "0h", "-1h", "1.5h", "6w", "6 h"] {
+            assert!(parse_interval(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! `--explain-config`: every config key with its type, its default and the value the run would
+//! use. Keys and types come from the `Config` struct itself, defaults and values from validating
+//! it, so the listing can't drift from the code.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Debug;
+use std::fmt::Write;
+use std::path::Path;
+
+use regex::Regex;
+use serde_yaml::Value;
+
+use crate::config::Config;
+use crate::constants::EXPLAIN_CONFIG_HEADERS;
+use crate::constants::EXPLAIN_CONFIG_LIST_OF;
+use crate::constants::EXPLAIN_CONFIG_MAP;
+use crate::constants::EXPLAIN_CONFIG_RENAMED_FIELDS;
+use crate::constants::EXPLAIN_CONFIG_SKIPPED_FIELDS;
+use crate::constants::EXPLAIN_CONFIG_TYPE_NAMES;
+use crate::constants::MARKDOWN_TABLE_CELL_SEPARATOR;
+use crate::constants::MARKDOWN_TABLE_SEPARATOR;
+use crate::constants::NEWLINE;
+use crate::constants::NOT_SET;
+use crate::constants::PIPE;
+use crate::validated_config::ChangeMode;
+use crate::validated_config::ValidatedConfig;
+
+/// A config key as the user writes it, with a readable form of its type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ConfigKey {
+    pub name:       &'static str,
+    pub value_type: String,
+}
+
+/// Every key `Config` reads, in the order the struct declares them.
+pub(crate) fn config_keys() -> Vec<ConfigKey> {
+    Config::FIELDS
+        .iter()
+        .filter(|(field, _)| !EXPLAIN_CONFIG_SKIPPED_FIELDS.contains(field))
+        .map(|&(field, field_type)| ConfigKey {
+            name:       EXPLAIN_CONFIG_RENAMED_FIELDS
+                .iter()
+                .find(|(renamed, _)| *renamed == field)
+                .map_or(field, |(_, key)| key),
+            value_type: describe_type(field_type),
+        })
+        .collect()
+}
+
+/// A markdown table of every key's type, default and effective value. The default is what
+/// validating a config that only sets `obsidian_path` gives.
+pub(crate) fn explain(
+    config: &Config,
+    validated_config: &ValidatedConfig,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut default_config = Config::new();
+    default_config
+        .obsidian_path
+        .clone_from(&config.obsidian_path);
+    let defaults = effective_values(&default_config.validate()?);
+    let values = effective_values(validated_config);
+    let config_values = serde_yaml::to_value(config)?;
+
+    let mut explanation = String::new();
+    let _ = write!(
+        explanation,
+        "{}{NEWLINE}{}{NEWLINE}",
+        table_row(&EXPLAIN_CONFIG_HEADERS),
+        table_row(&[MARKDOWN_TABLE_SEPARATOR; EXPLAIN_CONFIG_HEADERS.len()])
+    );
+    for key in config_keys() {
+        // A key validation doesn't keep, such as `profiles`, shows what the config holds.
+        let (default, value) = match (defaults.get(key.name), values.get(key.name)) {
+            (Some(default), Some(value)) => (default.clone(), value.clone()),
+            _ => (NOT_SET.to_string(), config_value(&config_values, key.name)),
+        };
+        let _ = write!(
+            explanation,
+            "{}{NEWLINE}",
+            table_row(&[key.name, &key.value_type, &default, &value])
+        );
+    }
+    Ok(explanation)
+}
+
+fn table_row(cells: &[&str]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| cell.replace(PIPE, &format!("\\{PIPE}")))
+        .collect();
+    format!("| {} |", cells.join(MARKDOWN_TABLE_CELL_SEPARATOR))
+}
+
+fn config_value(config_values: &Value, key: &str) -> String {
+    config_values
+        .get(key)
+        .and_then(|value| serde_yaml::to_string(value).ok())
+        .map_or_else(
+            || NOT_SET.to_string(),
+            |yaml| yaml.trim().replace(NEWLINE, " "),
+        )
+}
+
+/// `Option<Vec<PathBuf>>` reads as `list of path`; a config type such as `AuditTrail` keeps its
+/// name.
+fn describe_type(field_type: &str) -> String {
+    let field_type: String = field_type.split_whitespace().collect();
+    let inner = |prefix: &str| {
+        field_type
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix('>'))
+            .map(str::to_string)
+    };
+    if let Some(optional) = inner("Option<") {
+        return describe_type(&optional);
+    }
+    if let Some(item) = inner("Vec<") {
+        return format!("{EXPLAIN_CONFIG_LIST_OF} {}", describe_type(&item));
+    }
+    if inner("BTreeMap<").is_some() {
+        return EXPLAIN_CONFIG_MAP.to_string();
+    }
+    EXPLAIN_CONFIG_TYPE_NAMES
+        .iter()
+        .find(|(rust_type, _)| *rust_type == field_type)
+        .map_or_else(|| field_type.clone(), |(_, name)| (*name).to_string())
+}
+
+fn shown<T: Debug>(value: T) -> String { format!("{value:?}") }
+
+fn shown_option<T: Debug>(value: Option<T>) -> String {
+    value.map_or_else(|| NOT_SET.to_string(), shown)
+}
+
+fn shown_path(path: Option<&Path>) -> String {
+    path.map_or_else(|| NOT_SET.to_string(), |path| path.display().to_string())
+}
+
+fn shown_patterns(regexes: &[Regex]) -> String {
+    shown(regexes.iter().map(Regex::as_str).collect::<Vec<_>>())
+}
+
+/// Each key's value as validation left it, by config key.
+#[allow(clippy::too_many_lines, reason = "one entry per config key")]
+fn effective_values(validated_config: &ValidatedConfig) -> BTreeMap<&'static str, String> {
+    let vc = validated_config;
+    let note_templates: Vec<&str> = [
+        ("link_target", vc.link_target_template().is_some()),
+        ("periodic_note", vc.periodic_note_template().is_some()),
+        ("split_note", vc.split_note_template().is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(name, loaded)| loaded.then_some(name))
+    .collect();
+
+    BTreeMap::from([
+        (
+            "apply_changes",
+            shown(vc.change_mode() == ChangeMode::Apply),
+        ),
+        (
+            "ambiguous_match_context_lines",
+            shown_option(vc.ambiguous_match_context_lines()),
+        ),
+        ("audit_trail", shown_option(vc.audit_trail())),
+        (
+            "back_populate_file_filter",
+            shown_option(vc.back_populate_file_filter()),
+        ),
+        ("back_populate_grouping", shown(vc.back_populate_grouping())),
+        (
+            "backup_before_apply",
+            shown_option(vc.backup_before_apply()),
+        ),
+        ("bibliography", shown_path(vc.bibliography())),
+        (
+            "block_id_placeholder",
+            shown_option(vc.block_id_placeholder()),
+        ),
+        ("changelog", shown_path(vc.changelog())),
+        ("coerce_property_types", shown(vc.coerce_property_types())),
+        ("convert_admonitions", shown(vc.convert_admonitions())),
+        (
+            "create_missing_link_targets",
+            shown(vc.create_missing_link_targets()),
+        ),
+        ("date_link_format", shown(vc.date_link_format())),
+        ("date_properties", shown(vc.date_properties())),
+        ("detect_pii", shown(vc.detect_pii())),
+        (
+            "do_not_back_populate",
+            vc.do_not_back_populate_regexes()
+                .map_or_else(|| NOT_SET.to_string(), shown_patterns),
+        ),
+        ("entity_folders", shown(vc.entity_folders())),
+        ("exclude_math", shown(vc.exclude_math())),
+        ("export", shown_option(vc.export())),
+        ("file_limit", shown_option(vc.file_limit())),
+        (
+            "fix_duplicate_frontmatter_keys",
+            shown_option(vc.fix_duplicate_frontmatter_keys()),
+        ),
+        ("fix_entity_notes", shown(vc.fix_entity_notes())),
+        (
+            "fix_frontmatter_link_values",
+            shown(vc.fix_frontmatter_link_values()),
+        ),
+        (
+            "fix_impossible_dates",
+            shown_option(vc.fix_impossible_dates()),
+        ),
+        ("fix_self_links", shown(vc.fix_self_links())),
+        ("flashcards", shown_option(vc.flashcards())),
+        (
+            "frontmatter_link_properties",
+            shown(vc.frontmatter_link_properties()),
+        ),
+        ("frontmatter_only", shown(vc.frontmatter_only())),
+        (
+            "future_date_tolerance_days",
+            shown(vc.future_date_tolerance_days()),
+        ),
+        ("generate_aliases", shown(vc.generate_aliases())),
+        ("hash_all_images", shown(vc.hash_all_images())),
+        ("hold_persist_reasons", shown(vc.hold_persist_reasons())),
+        ("ignore_folders", shown_option(vc.ignore_folders())),
+        (
+            "ignore_invalid_wikilink_reasons",
+            shown(vc.ignored_invalid_wikilink_reasons()),
+        ),
+        ("journal_folders", shown(vc.journal_folders())),
+        ("link_acronyms", shown(vc.link_acronyms())),
+        ("link_alias_style", shown(vc.link_alias_style())),
+        ("link_date_mentions", shown(vc.link_date_mentions())),
+        ("lint_markdown", shown(vc.lint_markdown())),
+        (
+            "lint_max_line_length",
+            shown_option(vc.lint_max_line_length()),
+        ),
+        ("list_block_ids", shown(vc.list_block_ids())),
+        ("match_decisions", shown_path(vc.match_decisions())),
+        ("normalize_link_aliases", shown(vc.normalize_link_aliases())),
+        ("normalize_lists", shown(vc.normalize_lists())),
+        ("normalize_punctuation", shown(vc.normalize_punctuation())),
+        ("note_templates", shown(note_templates)),
+        ("notify", shown_option(vc.notify())),
+        ("obsidian_path", shown_path(Some(vc.obsidian_path()))),
+        ("only_modify_owner", shown_option(vc.only_modify_owner())),
+        (
+            "operational_timezone",
+            vc.operational_timezone().to_string(),
+        ),
+        ("output_folder", shown_path(Some(vc.output_folder()))),
+        (
+            "periodic_note_link_property",
+            shown_option(vc.periodic_note_link_property()),
+        ),
+        (
+            "pii_patterns",
+            shown(
+                vc.pii_patterns()
+                    .iter()
+                    .map(|rule| (rule.name.as_str(), rule.regex.as_str()))
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        (
+            "purge_trash_after_days",
+            shown_option(vc.purge_trash_after_days()),
+        ),
+        ("rename_unsafe_paths", shown(vc.rename_unsafe_paths())),
+        ("repair_heading_links", shown(vc.repair_heading_links())),
+        (
+            "repair_invalid_wikilinks",
+            shown(vc.repair_invalid_wikilinks()),
+        ),
+        ("report_deep_links", shown(vc.report_deep_links())),
+        ("report_rare_words", shown(vc.report_rare_words())),
+        ("report_row_limit", shown_option(vc.report_row_limit())),
+        (
+            "replacements",
+            shown(
+                vc.replacement_rules()
+                    .iter()
+                    .map(|rule| (rule.regex.as_str(), rule.replacement.as_str(), rule.scope))
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        (
+            "resolve_conflict_copies",
+            shown_option(vc.resolve_conflict_copies()),
+        ),
+        ("review_after", shown(vc.review_after())),
+        ("saved_reports", shown(vc.saved_reports())),
+        ("scan_secrets", shown(vc.scan_secrets())),
+        ("secret_patterns", shown_patterns(vc.secret_patterns())),
+        ("set_next_review", shown(vc.set_next_review())),
+        (
+            "similar_title_threshold",
+            shown_option(vc.similar_title_threshold()),
+        ),
+        (
+            "skip_recently_modified_minutes",
+            shown_option(vc.skip_recently_modified_minutes()),
+        ),
+        ("split_notes", shown(vc.split_notes())),
+        ("state_folder", shown_path(Some(&vc.state_folder()))),
+        (
+            "table_of_contents_min_headings",
+            shown_option(vc.table_of_contents_min_headings()),
+        ),
+        ("vault_index", shown(vc.vault_index())),
+        ("write_patch", shown(vc.write_patch())),
+    ])
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use serde_yaml::from_str;
+    use tempfile::TempDir;
+
+    use super::config_keys;
+    use super::effective_values;
+    use super::explain;
+    use crate::config::Config;
+    use crate::constants::APPLY_CHANGES_KEY;
+    use crate::constants::PROFILES_KEY;
+
+    #[test]
+    fn test_every_config_key_is_explained() {
+        let temp_dir = TempDir::new().unwrap();
+        let config: Config = from_str(&format!(
+            "obsidian_path: {}\napply_changes: true\nfile_limit: 20\nignore_folders: [Archive]",
+            temp_dir.path().display()
+        ))
+        .unwrap();
+        let validated_config = config.validate().unwrap();
+
+        // A key read from the struct but missing here would fall back to the raw config value.
+        let values = effective_values(&validated_config);
+        for key in config_keys() {
+            assert!(
+                values.contains_key(key.name) || key.name == PROFILES_KEY,
+                "no effective value for {}",
+                key.name
+            );
+        }
+        assert!(
+            config_keys()
+                .iter()
+                .any(|key| key.name == APPLY_CHANGES_KEY && key.value_type == "true/false")
+        );
+
+        let explanation = explain(&config, &validated_config).unwrap();
+        assert!(explanation.contains("| file_limit | number | not set | 20 |"));
+        assert!(explanation.contains("| apply_changes | true/false | false | true |"));
+        assert!(explanation.contains("| ignore_folders | list of path |"));
+    }
+}
@@ -0,0 +1,241 @@
+//! Runs the whole pipeline over a copy of a fixture vault and returns the report it writes.
+//!
+//! Report output - a custom `ReplacementSource`'s included - can then be checked against a
+//! golden file. The `test-fixtures` feature exports it from the library.
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_yaml::Mapping;
+use serde_yaml::Value;
+
+use crate::config::Config;
+use crate::constants::OUTPUT_MARKDOWN_FILE;
+use crate::constants::YAML_RUN_ID;
+use crate::constants::YAML_TIMESTAMP_LOCAL;
+use crate::constants::YAML_TIMESTAMP_UTC;
+use crate::markdown_file::ReplacementSource;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::run_lock::RunLock;
+use crate::sandbox::Sandbox;
+
+/// Start of the sandbox folder name the fixture copy is made in.
+const FIXTURE_SANDBOX_PREFIX: &str = "obsidian_knife_fixture_";
+const OBSIDIAN_PATH_KEY: &str = "obsidian_path";
+const TIMESTAMP_PLACEHOLDER: &str = "<timestamp>";
+const TODAY_PLACEHOLDER: &str = "<today>";
+/// Set to rewrite golden files from the reports compared to them.
+const UPDATE_GOLDEN_VARIABLE: &str = "OK_UPDATE_GOLDEN";
+const VAULT_PLACEHOLDER: &str = "<vault>";
+
+/// A fixture vault copied into the temp folder with the config to run over it. The fixture
+/// folder is never written to, and the copy is removed when this is dropped.
+pub struct FixtureVault {
+    sandbox: Sandbox,
+    config:  Config,
+}
+
+impl FixtureVault {
+    /// Copies the vault at `fixture` and reads `config_yaml` - the frontmatter of a config
+    /// note, without `obsidian_path` - as its config. `apply_changes` keeps the value it has
+    /// there, but the copy is only ever reported on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `config_yaml` isn't a config or the vault can't be copied.
+    pub fn load(fixture: &Path, config_yaml: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let mut mapping: Mapping = serde_yaml::from_str(config_yaml)?;
+        mapping.insert(
+            Value::from(OBSIDIAN_PATH_KEY),
+            Value::from(fixture.to_string_lossy().into_owned()),
+        );
+        let fixture_config: Config = serde_yaml::from_value(Value::Mapping(mapping))?;
+
+        let sandbox = Sandbox::create(fixture, FIXTURE_SANDBOX_PREFIX)?;
+        let mut config = sandbox.config(&fixture_config)?;
+        config.configured_changes = fixture_config.configured_changes;
+        Ok(Self { sandbox, config })
+    }
+
+    /// The copy the pipeline runs over.
+    #[must_use]
+    pub fn vault(&self) -> PathBuf { self.sandbox.vault() }
+
+    /// The report a run over the copy writes, normalized by `normalize_report`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the config doesn't validate or the run fails.
+    pub fn report(&self) -> Result<String, Box<dyn Error + Send + Sync>> {
+        self.report_with_replacement_sources(&[])
+    }
+
+    /// Like `report`, with the replacements `replacement_sources` find applied too.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the config doesn't validate or the run fails.
+    pub fn report_with_replacement_sources(
+        &self,
+        replacement_sources: &[&dyn ReplacementSource],
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let validated_config = self.config.validate()?;
+        // Taken as a real run takes it - it also makes the output folder.
+        let _run_lock = RunLock::acquire(validated_config.output_folder())?;
+        let obsidian_repository =
+            ObsidianRepository::with_replacement_sources(&validated_config, replacement_sources)?;
+        obsidian_repository.write_reports(&validated_config)?;
+
+        let report =
+            fs::read_to_string(validated_config.output_folder().join(OUTPUT_MARKDOWN_FILE))?;
+        Ok(normalize_report(
+            &report,
+            &self.vault(),
+            &validated_config.today(),
+        ))
+    }
+}
+
+/// Compares `report` to the golden file at `golden`.
+///
+/// When the golden file is missing, or `OK_UPDATE_GOLDEN` is set, it is written from `report`
+/// instead and counts as a match - so a new golden test records its first run, and an intended
+/// change is recorded by rerunning.
+///
+/// # Errors
+///
+/// Returns an error when the golden file can't be read or written.
+pub fn matches_golden(report: &str, golden: &Path) -> io::Result<bool> {
+    if env::var_os(UPDATE_GOLDEN_VARIABLE).is_some() || !golden.exists() {
+        if let Some(parent) = golden.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(golden, report)?;
+        return Ok(true);
+    }
+    Ok(fs::read_to_string(golden)? == report)
+}
+
+/// `report` with what changes from run to run replaced by placeholders: the run's id and
+/// timestamps, `today` and the path of the copy.
+fn normalize_report(report: &str, vault: &Path, today: &str) -> String {
+    let vault = vault.to_string_lossy();
+
+    report
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if let Some(prefix) = [YAML_RUN_ID, YAML_TIMESTAMP_LOCAL, YAML_TIMESTAMP_UTC]
+                .into_iter()
+                .find(|prefix| trimmed.starts_with(prefix))
+            {
+                let indent = &line[..line.len() - trimmed.len()];
+                return format!("{indent}{prefix}{TIMESTAMP_PLACEHOLDER}");
+            }
+            line.replace(&*vault, VAULT_PLACEHOLDER)
+                .replace(today, TODAY_PLACEHOLDER)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use serde_yaml::Mapping;
+    use serde_yaml::Value;
+    use tempfile::TempDir;
+
+    use super::FixtureVault;
+    use super::matches_golden;
+    use crate::constants::REPORT_TAG;
+    use crate::frontmatter::FrontMatter;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::yaml_frontmatter;
+    use crate::yaml_frontmatter::YamlFrontMatter;
+
+    #[test]
+    fn test_fixture_vault_report_is_repeatable() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("# Topic")
+            .create(&temp_dir, "Topic.md");
+        let note_path = TestFileBuilder::new()
+            .with_content("a note about Topic")
+            .create(&temp_dir, "notes.md");
+        let original = fs::read_to_string(&note_path).unwrap();
+
+        let fixture_vault = FixtureVault::load(temp_dir.path(), "apply_changes: false").unwrap();
+        let report = fixture_vault.report().unwrap();
+
+        assert!(
+            report.contains("| [[notes]] | 1 | a note about"),
+            "{report}"
+        );
+        assert!(!report.contains(&*fixture_vault.vault().to_string_lossy()));
+        assert!(
+            !report.contains(&validated_today()),
+            "dates of the run are placeholders"
+        );
+        assert_eq!(
+            report,
+            fixture_vault.report().unwrap(),
+            "a second run over the same copy reports the same"
+        );
+        assert_eq!(
+            fs::read_to_string(note_path).unwrap(),
+            original,
+            "the fixture is left alone"
+        );
+    }
+
+    #[test]
+    fn test_matches_golden_records_missing_golden_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let golden = temp_dir.path().join("golden").join("report.md");
+
+        assert!(matches_golden("first", &golden).unwrap());
+        assert_eq!(fs::read_to_string(&golden).unwrap(), "first");
+        assert!(matches_golden("first", &golden).unwrap());
+        assert!(!matches_golden("second", &golden).unwrap());
+    }
+
+    #[test]
+    fn test_report_note_reads_as_a_dated_tagged_note() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("# Topic")
+            .create(&temp_dir, "Topic.md");
+
+        let fixture_vault = FixtureVault::load(temp_dir.path(), "apply_changes: false").unwrap();
+        let report = fixture_vault.report().unwrap();
+
+        let (yaml, _) = yaml_frontmatter::find_yaml_section(&report)
+            .unwrap()
+            .unwrap();
+        let front_matter = FrontMatter::from_yaml_str(yaml).unwrap();
+        assert_eq!(front_matter.date_created(), Some("[[<today>]]"));
+        assert_eq!(front_matter.date_modified(), Some("[[<today>]]"));
+
+        let properties: Mapping = serde_yaml::from_str(yaml).unwrap();
+        let report_tag = Value::Sequence(vec![Value::from(REPORT_TAG)]);
+        assert_eq!(properties.get("tags"), Some(&report_tag));
+        assert_eq!(properties.get("cssclasses"), Some(&report_tag));
+        assert!(properties.contains_key("run_id"));
+    }
+
+    fn validated_today() -> String {
+        let temp_dir = TempDir::new().unwrap();
+        test_support::get_test_validated_config(&temp_dir, None).today()
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use chrono::DateTime;
+use chrono::NaiveDate;
 use chrono::Utc;
 use chrono_tz::Tz;
 use chrono_tz::UTC;
@@ -40,6 +41,9 @@ yaml_frontmatter_struct! {
         pub(crate) modified: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub(crate) do_not_back_populate: Option<Vec<String>>,
+        /// The `chrono` format dates are linked in - `FORMAT_DATE` when not set.
+        #[serde(skip)]
+        pub(crate) date_link_format: Option<String>,
         #[serde(skip)]
         pub(crate) persist_state: PersistState,
         #[serde(skip)]
@@ -52,12 +56,29 @@ yaml_frontmatter_struct! {
 impl FrontMatter {
     pub(crate) fn aliases(&self) -> Option<&[String]> { self.aliases.as_deref() }
 
+    pub(crate) fn add_alias(&mut self, alias: String) {
+        self.aliases.get_or_insert_with(Vec::new).push(alias);
+        self.persist_state = PersistState::Modified;
+    }
+
     pub(crate) fn date_created(&self) -> Option<&str> { self.created.as_deref() }
 
     pub(crate) fn date_modified(&self) -> Option<&str> { self.modified.as_deref() }
 
     pub(crate) fn date_created_fix(&self) -> Option<&str> { self.created_fix.as_deref() }
 
+    pub(crate) fn date_link_format(&self) -> &str {
+        self.date_link_format.as_deref().unwrap_or(FORMAT_DATE)
+    }
+
+    /// `date` as a wikilink in `date_link_format`, as every date ok writes is linked.
+    pub(crate) fn date_link(&self, date: NaiveDate) -> String {
+        format!(
+            "{OPENING_WIKILINK}{}{CLOSING_WIKILINK}",
+            date.format(self.date_link_format())
+        )
+    }
+
     pub(crate) fn remove_date_created_fix(&mut self) {
         // `created_fix = None` skips `created_fix` during serialization.
         self.created_fix = None;
@@ -68,12 +89,8 @@ impl FrontMatter {
     // `set_date_modified_now` records a fallback `raw_modified` value.
     pub(crate) fn set_date_created(&mut self, date: DateTime<Utc>, operational_timezone: &str) {
         let timezone: Tz = operational_timezone.parse().unwrap_or(UTC);
-        let local_date = date.with_timezone(&timezone);
         self.raw_created = Some(date);
-        let formatted_date = local_date.format(FORMAT_DATE);
-        self.created = Some(format!(
-            "{OPENING_WIKILINK}{formatted_date}{CLOSING_WIKILINK}"
-        ));
+        self.created = Some(self.date_link(date.with_timezone(&timezone).date_naive()));
 
         if self.raw_modified.is_none() {
             self.set_date_modified_now(operational_timezone);
@@ -92,17 +109,70 @@ impl FrontMatter {
     // `set_date_modified` fills missing `date_modified` values.
     pub(crate) fn set_date_modified(&mut self, date: DateTime<Utc>, operational_timezone: &str) {
         let timezone: Tz = operational_timezone.parse().unwrap_or(UTC);
-        let local_date = date.with_timezone(&timezone);
         self.raw_modified = Some(date);
-        let formatted_date = local_date.format(FORMAT_DATE);
-        self.modified = Some(format!(
-            "{OPENING_WIKILINK}{formatted_date}{CLOSING_WIKILINK}"
-        ));
+        self.modified = Some(self.date_link(date.with_timezone(&timezone).date_naive()));
         self.persist_state = PersistState::Modified;
     }
 
     pub(crate) fn needs_persist(&self) -> bool { self.persist_state == PersistState::Modified }
 
+    /// Leaves the note as it is on disk - the changes made so far are never written.
+    pub(crate) const fn discard_changes(&mut self) { self.persist_state = PersistState::Clean; }
+
+    /// String values of a property that isn't one of the explicit fields - a single string or
+    /// each string in a list. Other YAML types yield nothing.
+    pub(crate) fn property_strings(&self, property: &str) -> Vec<&str> {
+        match self.other_fields.get(property) {
+            Some(Value::String(value)) => vec![value.as_str()],
+            Some(Value::Sequence(values)) => values.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Properties that aren't one of the explicit fields, in no particular order.
+    pub(crate) fn other_properties(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.other_fields
+            .iter()
+            .map(|(property, value)| (property.as_str(), value))
+    }
+
+    pub(crate) fn property(&self, property: &str) -> Option<&Value> {
+        self.other_fields.get(property)
+    }
+
+    pub(crate) fn set_property(&mut self, property: &str, value: Value) {
+        self.other_fields.insert(property.to_string(), value);
+        self.persist_state = PersistState::Modified;
+    }
+
+    /// Replaces each string value of `property` for which `rewrite` returns `Some`, returning
+    /// how many values changed.
+    pub(crate) fn rewrite_property_strings(
+        &mut self,
+        property: &str,
+        rewrite: impl Fn(&str) -> Option<String>,
+    ) -> usize {
+        let mut rewrite_value = |value: &mut Value| match value {
+            Value::String(text) => rewrite(text).map(|rewritten| *text = rewritten).is_some(),
+            _ => false,
+        };
+
+        let rewritten = match self.other_fields.get_mut(property) {
+            Some(Value::Sequence(values)) => values
+                .iter_mut()
+                .map(&mut rewrite_value)
+                .filter(|changed| *changed)
+                .count(),
+            Some(value) => usize::from(rewrite_value(value)),
+            None => 0,
+        };
+
+        if rewritten > 0 {
+            self.persist_state = PersistState::Modified;
+        }
+        rewritten
+    }
+
     pub(crate) fn get_do_not_back_populate_regexes(&self) -> Option<Vec<Regex>> {
         // `do_not_back_populate` starts with the explicit frontmatter value.
         let mut do_not_populate = self.do_not_back_populate.clone().unwrap_or_default();
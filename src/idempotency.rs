@@ -0,0 +1,100 @@
+//! `--verify-idempotent`: runs a config twice with `apply_changes` on against a copy of the
+//! vault and reports the patch categories the second run would still change. The vault itself
+//! is never touched.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use crate::config::Config;
+use crate::constants::IDEMPOTENCY_SANDBOX_PREFIX;
+use crate::markdown_file::ReplacementSource;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::sandbox::Sandbox;
+
+/// A patch category the second run still changed, and how many files it changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NonIdempotentCategory {
+    pub category: String,
+    pub files:    usize,
+}
+
+/// Applies `config` to a copy of its vault, then analyzes the copy again and returns what that
+/// second pass would change, by category, with `replacement_sources` applied on both. Empty
+/// when the config is idempotent.
+pub(crate) fn verify(
+    config: &Config,
+    replacement_sources: &[&dyn ReplacementSource],
+) -> Result<Vec<NonIdempotentCategory>, Box<dyn Error + Send + Sync>> {
+    let sandbox = Sandbox::create(
+        &config.expanded_obsidian_path()?,
+        IDEMPOTENCY_SANDBOX_PREFIX,
+    )?;
+    let validated_config = sandbox.config(config)?.validate()?;
+
+    ObsidianRepository::with_replacement_sources(&validated_config, replacement_sources)?
+        .persist()?;
+    let patch =
+        ObsidianRepository::with_replacement_sources(&validated_config, replacement_sources)?
+            .build_patch(&validated_config);
+
+    let mut files_by_category: BTreeMap<&str, usize> = BTreeMap::new();
+    for operation in &patch.operations {
+        for category in &operation.categories {
+            *files_by_category.entry(category).or_default() += 1;
+        }
+    }
+    Ok(files_by_category
+        .into_iter()
+        .map(|(category, files)| NonIdempotentCategory {
+            category: category.to_string(),
+            files,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use serde_yaml::from_str;
+    use tempfile::TempDir;
+
+    use super::verify;
+    use crate::config::Config;
+    use crate::test_support as test_utils;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_second_pass_over_sandbox_changes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_date = test_utils::eastern_midnight(2024, 1, 15);
+        TestFileBuilder::new()
+            .with_content("# Other\n".to_string())
+            .with_matching_dates(test_date)
+            .with_file_system_dates(test_date, test_date)
+            .create(&temp_dir, "Other.md");
+        let note_path = TestFileBuilder::new()
+            .with_content("# Note\nSee Other and ![[missing.png]]\n".to_string())
+            .with_matching_dates(test_date)
+            .with_file_system_dates(test_date, test_date)
+            .create(&temp_dir, "Note.md");
+        let original = fs::read_to_string(&note_path).unwrap();
+
+        let config: Config = from_str(&format!(
+            "obsidian_path: {}\napply_changes: false",
+            temp_dir.path().display()
+        ))
+        .unwrap();
+
+        assert_eq!(verify(&config, &[]).unwrap(), Vec::new());
+        assert_eq!(
+            fs::read_to_string(&note_path).unwrap(),
+            original,
+            "the vault itself is left alone"
+        );
+    }
+}
@@ -58,34 +58,47 @@ impl InitialImageState {
         }
     }
 
-    fn into_image_file_state(self, image_hash: &ImageHash) -> ImageFileState {
-        match self {
-            Self::Tiff => ImageFileState::Incompatible {
+    fn into_image_file_state(self, image_hash: Option<&ImageHash>) -> ImageFileState {
+        match (self, image_hash) {
+            (Self::Tiff, _) => ImageFileState::Incompatible {
                 reason: IncompatibilityReason::TiffFormat,
             },
-            Self::EmptyFile => ImageFileState::Incompatible {
+            (Self::EmptyFile, _) => ImageFileState::Incompatible {
                 reason: IncompatibilityReason::ZeroByte,
             },
-            Self::Role {
-                image_role: ImageRole::Original,
-                ..
-            } => ImageFileState::DuplicateKeeper {
+            (
+                Self::Role {
+                    image_role: ImageRole::Original,
+                    ..
+                },
+                Some(image_hash),
+            ) => ImageFileState::DuplicateKeeper {
                 image_hash: image_hash.clone(),
             },
-            Self::Role {
-                image_role: ImageRole::Duplicate,
-                ..
-            } => ImageFileState::Duplicate {
+            (
+                Self::Role {
+                    image_role: ImageRole::Duplicate,
+                    ..
+                },
+                Some(image_hash),
+            ) => ImageFileState::Duplicate {
                 image_hash: image_hash.clone(),
             },
-            Self::Role {
-                image_role: ImageRole::Unique,
-                reference_presence: ReferencePresence::Empty,
-            } => ImageFileState::Unreferenced,
-            Self::Role {
-                image_role: ImageRole::Unique,
-                reference_presence: ReferencePresence::Present,
-            } => ImageFileState::Valid,
+            // Only hashed images are grouped as duplicates, so an unhashed one is unique.
+            (
+                Self::Role {
+                    reference_presence: ReferencePresence::Empty,
+                    ..
+                },
+                _,
+            ) => ImageFileState::Unreferenced,
+            (
+                Self::Role {
+                    reference_presence: ReferencePresence::Present,
+                    ..
+                },
+                _,
+            ) => ImageFileState::Valid,
         }
     }
 }
@@ -95,7 +108,7 @@ pub(super) fn image_file_state_from_parts(
     size: u64,
     references: &[PathBuf],
     image_role: ImageRole,
-    image_hash: &ImageHash,
+    image_hash: Option<&ImageHash>,
 ) -> ImageFileState {
     InitialImageState::from_parts(kind, size, references, image_role)
         .into_image_file_state(image_hash)
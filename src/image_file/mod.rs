@@ -94,7 +94,8 @@ pub(crate) enum IncompatibilityReason {
 pub(crate) struct ImageFile {
     pub deletion_status: DeletionStatus,
     pub kind:            ImageFileType,
-    pub image_hash:      ImageHash,
+    /// `None` when no other image shares its size - it can't be a duplicate, so it isn't hashed.
+    pub image_hash:      Option<ImageHash>,
     pub state:           ImageFileState,
     pub path:            PathBuf,
     pub references:      Vec<PathBuf>,
@@ -104,7 +105,7 @@ pub(crate) struct ImageFile {
 impl ImageFile {
     pub(crate) fn new(
         path: PathBuf,
-        image_hash: ImageHash,
+        image_hash: Option<ImageHash>,
         references: Vec<PathBuf>,
         image_role: ImageRole,
     ) -> io::Result<Self> {
@@ -121,7 +122,7 @@ impl ImageFile {
             size,
             &references,
             image_role,
-            &image_hash,
+            image_hash.as_ref(),
         );
 
         Ok(Self {
@@ -253,11 +254,16 @@ mod tests {
 
             let image_hash = ImageHash::from(hash);
 
-            let image_file =
-                ImageFile::new(path.clone(), image_hash.clone(), references, image_role).unwrap();
+            let image_file = ImageFile::new(
+                path.clone(),
+                Some(image_hash.clone()),
+                references,
+                image_role,
+            )
+            .unwrap();
 
             assert_eq!(image_file.path, path);
-            assert_eq!(image_file.image_hash, image_hash);
+            assert_eq!(image_file.image_hash, Some(image_hash));
             assert_eq!(image_file.size, fs::metadata(&path).unwrap().len());
             assert_eq!(image_file.kind, expected_type);
             assert_eq!(image_file.state, expected_state);
@@ -273,7 +279,7 @@ mod tests {
             .create(&temp_dir, "test.tiff");
         let tiff_image = ImageFile::new(
             tiff_path,
-            ImageHash::from("hash1"),
+            Some(ImageHash::from("hash1")),
             vec![],
             ImageRole::Unique,
         )
@@ -290,7 +296,7 @@ mod tests {
             .create(&temp_dir, "test.jpg");
         let zero_byte_image = ImageFile::new(
             zero_byte_path,
-            ImageHash::from("hash2"),
+            Some(ImageHash::from("hash2")),
             vec![PathBuf::from("note.md")],
             ImageRole::Unique,
         )
@@ -312,7 +318,7 @@ mod tests {
 
         let unreferenced = ImageFile::new(
             path.clone(),
-            ImageHash::from("hash1"),
+            Some(ImageHash::from("hash1")),
             vec![],
             ImageRole::Unique,
         )
@@ -321,7 +327,7 @@ mod tests {
 
         let referenced = ImageFile::new(
             path,
-            ImageHash::from("hash2"),
+            Some(ImageHash::from("hash2")),
             vec![PathBuf::from("note.md")],
             ImageRole::Unique,
         )
@@ -341,7 +347,7 @@ mod tests {
 
         let original = ImageFile::new(
             original_path,
-            ImageHash::from("testhash"),
+            Some(ImageHash::from("testhash")),
             references.clone(),
             ImageRole::Unique,
         )
@@ -356,7 +362,7 @@ mod tests {
 
         let different = ImageFile::new(
             different_path,
-            ImageHash::from("differenthash"),
+            Some(ImageHash::from("differenthash")),
             references,
             ImageRole::Unique,
         )
@@ -379,7 +385,7 @@ mod tests {
 
         let image_file = ImageFile::new(
             path.clone(),
-            ImageHash::from("testhash"),
+            Some(ImageHash::from("testhash")),
             references,
             ImageRole::Unique,
         )
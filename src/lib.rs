@@ -0,0 +1,83 @@
+//! `obsidian_knife` analyzes an Obsidian vault and fixes what it can - back populating
+//! wikilinks, repairing links and dates, deduplicating images - behind the `ok` binary.
+//!
+//! The library runs the same command line, with replacements of its own registered alongside
+//! the built-in ones:
+//!
+//! ```no_run
+//! use obsidian_knife::LineReplacement;
+//! use obsidian_knife::ReplacementSource;
+//!
+//! struct Arrows;
+//!
+//! impl ReplacementSource for Arrows {
+//!     fn name(&self) -> &str { "arrows" }
+//!
+//!     fn find_replacements(&self, line: &str) -> Vec<LineReplacement> {
+//!         line.match_indices("->")
+//!             .map(|(position, found_text)| LineReplacement {
+//!                 position,
+//!                 found_text: found_text.to_string(),
+//!                 replacement: "\u{2192}".to_string(),
+//!             })
+//!             .collect()
+//!     }
+//! }
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//!     obsidian_knife::run_with_replacement_sources(&[&Arrows])
+//! }
+//! ```
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    reason = "tests should panic on unexpected values"
+)]
+mod test_support;
+
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixture_vault;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod test_fixtures;
+
+#[cfg(feature = "bench")]
+mod benchmarks;
+
+mod app_settings;
+mod config;
+mod config_init;
+mod constants;
+mod daemon;
+mod description_builder;
+mod explain_config;
+mod frontmatter;
+mod idempotency;
+mod image_file;
+mod image_files;
+mod markdown_file;
+mod markdown_files;
+mod note_template;
+mod obsidian_repository;
+mod output_file_writer;
+mod patch;
+mod phrase;
+mod query;
+mod report;
+mod run;
+mod run_lock;
+mod sandbox;
+mod sha256_cache;
+mod support;
+mod timer;
+mod validated_config;
+mod vault_generator;
+mod wikilink;
+mod yaml_frontmatter;
+
+pub use markdown_file::LineReplacement;
+pub use markdown_file::ReplacementSource;
+pub use run::run;
+pub use run::run_with_replacement_sources;
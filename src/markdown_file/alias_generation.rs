@@ -0,0 +1,31 @@
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::MarkdownFile;
+use crate::constants::FRONTMATTER_MISSING_AFTER_ENSURE;
+
+impl MarkdownFile {
+    /// Adds `aliases` to the frontmatter and re-collects the wikilinks, so the back populate
+    /// automaton picks up the new aliases as it does hand-written ones.
+    pub(crate) fn add_generated_aliases(
+        &mut self,
+        aliases: &[String],
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        if aliases.is_empty() {
+            return Ok(());
+        }
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        for alias in aliases {
+            front_matter.add_alias(alias.clone());
+        }
+        self.wikilinks = self.process_wikilinks();
+
+        self.mark_aliases_generated(aliases.len(), operational_timezone)
+    }
+}
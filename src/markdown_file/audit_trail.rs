@@ -0,0 +1,99 @@
+use serde_yaml::Value;
+
+use super::MarkdownFile;
+use super::PersistReason;
+use super::constants::AUDIT_ENTRY_SEPARATOR;
+use super::constants::KNIFE_HISTORY_PROPERTY;
+
+impl MarkdownFile {
+    /// The changes ok writes to this note on one line - for example
+    /// `back populated (3), date_modified updated`. `None` when nothing changes.
+    pub(crate) fn audit_changes(&self) -> Option<String> {
+        let mut changes: Vec<(String, Option<usize>)> = Vec::new();
+        for reason in &self.persist_reasons {
+            let description = reason.to_string();
+            let count = self.change_count(reason);
+            match changes
+                .iter_mut()
+                .find(|(existing, _)| *existing == description)
+            {
+                // Only image references are recorded once per change.
+                Some((_, Some(total)))
+                    if matches!(reason, PersistReason::ImageReferencesModified) =>
+                {
+                    *total += 1;
+                },
+                Some(_) => {},
+                None => changes.push((description, count)),
+            }
+        }
+
+        if changes.is_empty() {
+            return None;
+        }
+
+        let described: Vec<String> = changes
+            .into_iter()
+            .map(|(description, count)| match count {
+                Some(count) => format!("{description} ({count})"),
+                None => description,
+            })
+            .collect();
+        Some(described.join(AUDIT_ENTRY_SEPARATOR))
+    }
+
+    /// Appends `entry` to the note's `knife_history` list, turning a single value into a list.
+    pub(crate) fn append_knife_history(&mut self, entry: String) {
+        let Some(front_matter) = self.front_matter.as_mut() else {
+            return;
+        };
+
+        let mut history = match front_matter.property(KNIFE_HISTORY_PROPERTY) {
+            Some(Value::Sequence(entries)) => entries.clone(),
+            Some(Value::Null) | None => Vec::new(),
+            Some(value) => vec![value.clone()],
+        };
+        history.push(Value::String(entry));
+        front_matter.set_property(KNIFE_HISTORY_PROPERTY, Value::Sequence(history));
+    }
+
+    const fn change_count(&self, reason: &PersistReason) -> Option<usize> {
+        match reason {
+            PersistReason::AdmonitionsConverted { count }
+            | PersistReason::AliasesGenerated { count }
+            | PersistReason::BlockIdsAdded { count }
+            | PersistReason::ConflictCopyMerged { count }
+            | PersistReason::CustomReplacementsApplied { count, .. }
+            | PersistReason::DuplicateFrontmatterKeysFixed { count }
+            | PersistReason::EntityIndexLinksAdded { count }
+            | PersistReason::FlashcardIdsAdded { count }
+            | PersistReason::FrontmatterLinksCanonicalized { count }
+            | PersistReason::FrontmatterLinkValuesFixed { count }
+            | PersistReason::HeadingLinksRetargeted { count }
+            | PersistReason::ImpossibleDatesFixed { count }
+            | PersistReason::ListsNormalized { count }
+            | PersistReason::PropertyTypesCoerced { count }
+            | PersistReason::PunctuationNormalized { count }
+            | PersistReason::SectionsSplit { count } => Some(*count),
+            PersistReason::BackPopulated => Some(self.back_populate_matches.unambiguous.len()),
+            PersistReason::DateMentionsLinked => Some(self.date_mention_matches.len()),
+            PersistReason::HeadingLinksRepaired => Some(self.heading_link_repair_matches.len()),
+            PersistReason::DatePropertyUpdated { .. }
+            | PersistReason::EntityAliasAdded { .. }
+            | PersistReason::ImageReferencesModified
+            | PersistReason::NextReviewSet { .. }
+            | PersistReason::PeriodicNoteLinked { .. } => Some(1),
+            PersistReason::LinksCanonicalized => Some(self.canonical_link_matches.len()),
+            PersistReason::PhantomLinksResolved => Some(self.phantom_link_matches.len()),
+            PersistReason::RegexReplacementsApplied => Some(self.regex_replacement_matches.len()),
+            PersistReason::SelfLinksCleaned => Some(self.self_link_matches.len()),
+            PersistReason::WikilinksRepaired => Some(self.wikilink_repair_matches.len()),
+            PersistReason::DateCreatedUpdated { .. }
+            | PersistReason::DateModifiedUpdated { .. }
+            | PersistReason::DateCreatedFixApplied
+            | PersistReason::FrontmatterCreated
+            | PersistReason::JournalDateAligned { .. }
+            | PersistReason::TableOfContentsUpdated { .. } => None,
+        }
+    }
+}
@@ -1,8 +1,12 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::ffi::OsStr;
+use std::sync::Arc;
 
 use aho_corasick::AhoCorasick;
 
 use super::MarkdownFile;
+use super::canonical_link;
 use super::constants::APOSTROPHE;
 use super::constants::MAX_OBSIDIAN_LINK_PIPE_COUNT;
 use super::constants::RIGHT_SINGLE_QUOTATION_MARK;
@@ -11,9 +15,15 @@ use super::constants::T_UPPER;
 use super::constants::UNDERSCORE;
 use super::replaceable_content::MatchType;
 use super::replaceable_content::ReplaceableContent;
+use super::text_excluder;
 use super::text_excluder::CodeBlockExcluder;
-use super::text_excluder::InlineCodeExcluder;
+use super::text_excluder::CommentExcluder;
+use super::text_excluder::MathBlockExcluder;
+use crate::app_settings::LinkFormat;
+use crate::config::LinkAliasStyle;
+use crate::constants::ACRONYM_PROPERTY;
 use crate::constants::ESCAPED_PIPE;
+use crate::constants::FORWARD_SLASH;
 use crate::constants::PIPE;
 use crate::constants::SPACE;
 use crate::support;
@@ -30,15 +40,102 @@ pub enum MatchContext {
     MarkdownTable,
 }
 
+/// `line_text` and `relative_path` are shared by every match on the same line and in the same
+/// file, and `found_text` and `replacement` by every match in the file that finds the same text
+/// and links it the same way - see `SharedTexts` - so a vault with many matches keeps one copy
+/// of each.
 #[derive(Clone, Debug, Default)]
 pub struct BackPopulateMatch {
-    pub found_text:    String,
+    pub found_text:    Arc<str>,
     pub match_context: MatchContext,
     pub line_number:   usize,
-    pub line_text:     String,
+    pub line_text:     Arc<str>,
     pub position:      usize,
-    pub relative_path: String,
-    pub replacement:   String,
+    pub relative_path: Arc<str>,
+    pub replacement:   Arc<str>,
+}
+
+impl BackPopulateMatch {
+    /// The note the replacement links to.
+    pub(crate) fn target(&self) -> Option<String> {
+        let unescaped = self.replacement.replace(ESCAPED_PIPE, &PIPE.to_string());
+        wikilink::extract_wikilinks(&unescaped)
+            .valid
+            .pop()
+            .map(|spanned_wikilink| spanned_wikilink.wikilink.target)
+    }
+
+    /// Points the replacement at `target`, keeping the found text as its alias when they
+    /// differ.
+    pub(crate) fn retarget(&mut self, target: &str) {
+        let mut replacement = if *self.found_text == *target {
+            target.to_wikilink()
+        } else {
+            target.to_aliased_wikilink(&self.found_text)
+        };
+        if self.match_context == MatchContext::MarkdownTable {
+            replacement = replacement.replace(PIPE, ESCAPED_PIPE);
+        }
+        self.replacement = replacement.into();
+    }
+
+    /// Rewrites the replacement in the form `style` asks for; see
+    /// `MarkdownFile::style_back_populate_replacements`.
+    pub(crate) fn style_replacement(
+        &mut self,
+        note_paths: &HashMap<String, String>,
+        style: LinkAliasStyle,
+    ) {
+        let unescaped = self.replacement.replace(ESCAPED_PIPE, &PIPE.to_string());
+        let Some(spanned_wikilink) = wikilink::extract_wikilinks(&unescaped).valid.pop() else {
+            return;
+        };
+        let stem = &spanned_wikilink.wikilink.target;
+        let note_path = note_paths
+            .get(&stem.to_lowercase())
+            .map_or(stem.as_str(), String::as_str);
+
+        let mut replacement =
+            canonical_link::styled_replacement(&spanned_wikilink.wikilink, stem, note_path, style);
+        if self.match_context == MatchContext::MarkdownTable {
+            replacement = replacement.replace(PIPE, ESCAPED_PIPE);
+        }
+        self.replacement = replacement.into();
+    }
+
+    /// Rewrites the replacement as Obsidian would write a new link to the same note under
+    /// `link_format`; see `MarkdownFile::format_back_populate_links`.
+    pub(crate) fn format_link(
+        &mut self,
+        note_paths: &HashMap<String, String>,
+        link_format: LinkFormat,
+    ) {
+        let unescaped = self.replacement.replace(ESCAPED_PIPE, &PIPE.to_string());
+        let Some(spanned_wikilink) = wikilink::extract_wikilinks(&unescaped).valid.pop() else {
+            return;
+        };
+        let Wikilink {
+            display_text,
+            target,
+        } = &spanned_wikilink.wikilink;
+        let note_path = if target.contains(FORWARD_SLASH) {
+            target.as_str()
+        } else {
+            note_paths
+                .get(&target.to_lowercase())
+                .map_or(target.as_str(), String::as_str)
+        };
+        let source_folder = self
+            .relative_path
+            .rsplit_once(FORWARD_SLASH)
+            .map_or("", |(folder, _)| folder);
+
+        let mut replacement = link_format.link(target, note_path, display_text, source_folder);
+        if self.match_context == MatchContext::MarkdownTable {
+            replacement = replacement.replace(PIPE, ESCAPED_PIPE);
+        }
+        self.replacement = replacement.into();
+    }
 }
 
 impl ReplaceableContent for BackPopulateMatch {
@@ -46,13 +143,43 @@ impl ReplaceableContent for BackPopulateMatch {
 
     fn position(&self) -> usize { self.position }
 
-    fn get_replacement(&self) -> String { self.replacement.clone() }
+    fn get_replacement(&self) -> String { self.replacement.to_string() }
 
-    fn matched_text(&self) -> String { self.found_text.clone() }
+    fn matched_text(&self) -> String { self.found_text.to_string() }
 
     fn match_type(&self) -> MatchType { MatchType::BackPopulate }
 }
 
+/// The texts the back populate matches of one file share: the file's relative path, and one
+/// `Arc<str>` per distinct found text and replacement - every match that finds the same text
+/// and links it to the same note.
+#[derive(Debug)]
+pub(super) struct SharedTexts {
+    relative_path: Arc<str>,
+    texts:         HashSet<Arc<str>>,
+}
+
+impl SharedTexts {
+    pub(super) fn new(relative_path: Arc<str>) -> Self {
+        Self {
+            relative_path,
+            texts: HashSet::new(),
+        }
+    }
+
+    pub(super) fn get(&mut self, text: &str) -> Arc<str> { share(&mut self.texts, text) }
+}
+
+/// The `Arc<str>` in `texts` equal to `text`, added when there is none yet.
+fn share(texts: &mut HashSet<Arc<str>>, text: &str) -> Arc<str> {
+    if let Some(shared) = texts.get(text) {
+        return Arc::clone(shared);
+    }
+    let shared: Arc<str> = Arc::from(text);
+    texts.insert(Arc::clone(&shared));
+    shared
+}
+
 #[derive(Clone, Debug, Default)]
 pub(crate) struct BackPopulateMatches {
     pub ambiguous:   Vec<BackPopulateMatch>,
@@ -66,11 +193,19 @@ impl MarkdownFile {
         validated_config: &ValidatedConfig,
         automaton: &AhoCorasick,
     ) {
-        let content = self.content.clone();
+        let relative_path: Arc<str> =
+            support::format_relative_path(&self.path, validated_config.obsidian_path()).into();
         let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut comment_excluder = CommentExcluder::new();
+        let mut math_block_excluder = MathBlockExcluder::new();
+        let mut file_matches = Vec::new();
+        let mut shared_texts = SharedTexts::new(relative_path);
+        let ignored_lines = self.ignored_lines();
 
-        for (line_idx, line) in content.lines().enumerate() {
+        for (line_idx, line) in self.content.lines().enumerate() {
             if line.trim().is_empty() {
+                // Blank lines end raw HTML blocks.
+                comment_excluder.excluded_spans(line);
                 continue;
             }
 
@@ -79,15 +214,64 @@ impl MarkdownFile {
                 continue;
             }
 
-            let matches = self.process_line_for_back_populate_replacements(
+            if validated_config.exclude_math() {
+                math_block_excluder.update(line);
+                if math_block_excluder.is_in_math_block() {
+                    continue;
+                }
+            }
+
+            let comment_spans = comment_excluder.excluded_spans(line);
+            if ignored_lines.contains(&self.get_real_line_number(line_idx)) {
+                continue;
+            }
+
+            let mut matches = self.process_line_for_back_populate_replacements(
                 line,
                 line_idx,
+                &mut shared_texts,
                 automaton,
                 sorted_wikilinks,
                 validated_config,
             );
+            matches.retain(|m| {
+                !range_overlaps(&comment_spans, m.position, m.position + m.found_text.len())
+            });
+
+            file_matches.extend(matches);
+        }
 
-            self.back_populate_matches.unambiguous.extend(matches);
+        self.back_populate_matches.unambiguous.extend(file_matches);
+    }
+
+    /// Rewrites each unambiguous replacement in the form `style` asks for; `note_paths` maps a
+    /// lowercased stem to the vault-relative path `LinkAliasStyle::PipedPath` targets.
+    pub(crate) fn style_back_populate_replacements(
+        &mut self,
+        note_paths: &HashMap<String, String>,
+        style: LinkAliasStyle,
+    ) {
+        let mut replacements = HashSet::new();
+        for back_populate_match in &mut self.back_populate_matches.unambiguous {
+            back_populate_match.style_replacement(note_paths, style);
+            back_populate_match.replacement =
+                share(&mut replacements, &back_populate_match.replacement);
+        }
+    }
+
+    /// Rewrites each unambiguous replacement in the vault's new link format, so the links ok
+    /// adds read like the ones Obsidian adds; `note_paths` maps a lowercased stem to its
+    /// vault-relative path.
+    pub(crate) fn format_back_populate_links(
+        &mut self,
+        note_paths: &HashMap<String, String>,
+        link_format: LinkFormat,
+    ) {
+        let mut replacements = HashSet::new();
+        for back_populate_match in &mut self.back_populate_matches.unambiguous {
+            back_populate_match.format_link(note_paths, link_format);
+            back_populate_match.replacement =
+                share(&mut replacements, &back_populate_match.replacement);
         }
     }
 
@@ -95,11 +279,13 @@ impl MarkdownFile {
         &self,
         line: &str,
         line_idx: usize,
+        shared_texts: &mut SharedTexts,
         automaton: &AhoCorasick,
         sorted_wikilinks: &[&Wikilink],
         validated_config: &ValidatedConfig,
     ) -> Vec<BackPopulateMatch> {
         let mut matches = Vec::new();
+        let mut line_text: Option<Arc<str>> = None;
         let exclusion_zones = self.collect_exclusion_zones(line, validated_config);
 
         for match_result in automaton.find_iter(line) {
@@ -132,17 +318,14 @@ impl MarkdownFile {
                     replacement = replacement.replace(PIPE, ESCAPED_PIPE);
                 }
 
-                let relative_path =
-                    support::format_relative_path(&self.path, validated_config.obsidian_path());
-
                 matches.push(BackPopulateMatch {
-                    found_text: matched_text.to_string(),
+                    found_text: shared_texts.get(matched_text),
                     line_number: self.get_real_line_number(line_idx),
-                    line_text: line.to_string(),
+                    line_text: Arc::clone(line_text.get_or_insert_with(|| Arc::from(line))),
                     position: starts_at,
                     match_context,
-                    relative_path,
-                    replacement,
+                    relative_path: Arc::clone(&shared_texts.relative_path),
+                    replacement: shared_texts.get(&replacement),
                 });
             }
         }
@@ -178,22 +361,12 @@ impl MarkdownFile {
             }
         }
 
-        // InlineCodeExcluder spans block back-populate matches.
-        let mut inline_code_excluder = InlineCodeExcluder::new();
-        let mut span_start = None;
-        for (byte_offset, ch) in line.char_indices() {
-            let was_inside = inline_code_excluder.is_in_code_block();
-            inline_code_excluder.update(ch);
-            let is_inside = inline_code_excluder.is_in_code_block();
-
-            if !was_inside && is_inside {
-                span_start = Some(byte_offset);
-            } else if was_inside
-                && !is_inside
-                && let Some(start) = span_start.take()
-            {
-                exclusion_zones.push((start, byte_offset + ch.len_utf8()));
-            }
+        // Inline code spans block back-populate matches.
+        exclusion_zones.extend(text_excluder::find_inline_code_spans(line));
+
+        // Math spans block back-populate matches.
+        if validated_config.exclude_math() {
+            exclusion_zones.extend(text_excluder::find_inline_math_spans(line));
         }
 
         // Markdown link spans block back-populate matches.
@@ -227,6 +400,16 @@ impl MarkdownFile {
             {
                 return false;
             }
+
+            // `matched_text` cannot target the current `ACRONYM_PROPERTY` values.
+            if let Some(front_matter) = &self.front_matter
+                && front_matter
+                    .property_strings(ACRONYM_PROPERTY)
+                    .iter()
+                    .any(|acronym| acronym.trim().eq_ignore_ascii_case(matched_text))
+            {
+                return false;
+            }
         }
 
         !wikilink::is_within_wikilink(line, absolute_start)
@@ -263,7 +446,7 @@ fn is_word_boundary(line: &str, starts_at: usize, ends_at: usize) -> bool {
     start_is_boundary && end_is_boundary
 }
 
-fn range_overlaps(ranges: &[(usize, usize)], start: usize, end: usize) -> bool {
+pub(super) fn range_overlaps(ranges: &[(usize, usize)], start: usize, end: usize) -> bool {
     ranges.iter().any(|&(r_start, r_end)| {
         (start >= r_start && start < r_end)
             || (end > r_start && end <= r_end)
@@ -286,7 +469,9 @@ pub(super) fn is_in_markdown_table(line: &str, matched_text: &str) -> bool {
 )]
 mod tests {
     use std::slice;
+    use std::sync::Arc;
 
+    use super::SharedTexts;
     use crate::markdown_file::BackPopulateMatch;
     use crate::markdown_file::MarkdownFile;
     use crate::markdown_file::MatchContext;
@@ -395,14 +580,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_exclusion_zones_with_math() {
+        let (temp_dir, validated_config, obsidian_repository) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, None, None);
+        let markdown_file = obsidian_repository.markdown_files.first().unwrap();
+        let line = "where $x = test$ holds";
+
+        let zones = markdown_file.collect_exclusion_zones(line, &validated_config);
+        assert_eq!(zones, vec![(6, 16)], "inline math is excluded by default");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.exclude_math(false);
+            })
+            .unwrap();
+        let zones = markdown_file.collect_exclusion_zones(line, &validated_config);
+        assert!(zones.is_empty(), "exclude_math: false back populates math");
+    }
+
     #[test]
     fn test_should_create_match_in_table() {
         let (temp_dir, validated_config, _) =
             test_support::create_test_environment(ChangeMode::DryRun, None, None, None);
         let file_path = temp_dir.path().join("test.md");
 
-        let markdown_file =
-            MarkdownFile::new(file_path, validated_config.operational_timezone()).unwrap();
+        let markdown_file = MarkdownFile::new(
+            file_path,
+            validated_config.operational_timezone(),
+            validated_config.date_link_format(),
+            None,
+            &[],
+        )
+        .unwrap();
 
         assert!(markdown_file.should_create_match("| Test Link | description |", 2, "Test Link",));
 
@@ -477,6 +687,7 @@ mod tests {
             let matches = markdown_file.process_line_for_back_populate_replacements(
                 line,
                 0,
+                &mut SharedTexts::new(Arc::from("test.md")),
                 &automaton,
                 &wikilink_refs,
                 &validated_config,
@@ -490,7 +701,7 @@ mod tests {
 
             for (match_info, expected) in matches.iter().zip(expected_replacements.iter()) {
                 assert_eq!(
-                    match_info.replacement, *expected,
+                    &*match_info.replacement, *expected,
                     "Incorrect replacement for: {description}"
                 );
                 assert_eq!(
@@ -565,7 +776,7 @@ mod tests {
         case_description: &str,
     ) {
         assert_eq!(
-            actual_match.found_text, expected_text,
+            &*actual_match.found_text, expected_text,
             "Wrong matched text for case: {case_description}"
         );
 
@@ -576,7 +787,7 @@ mod tests {
         };
 
         assert_eq!(
-            actual_match.replacement,
+            *actual_match.replacement,
             expected_replacement,
             "Wrong replacement for case: {}\nExpected: {}\nActual: {}\nIn table: {}",
             case_description,
@@ -602,13 +813,19 @@ mod tests {
             let wikilink = case.wikilink;
             let automaton = test_support::build_aho_corasick(slice::from_ref(&wikilink));
 
-            let markdown_file =
-                MarkdownFile::new(file_path.clone(), validated_config.operational_timezone())
-                    .unwrap();
+            let markdown_file = MarkdownFile::new(
+                file_path.clone(),
+                validated_config.operational_timezone(),
+                validated_config.date_link_format(),
+                None,
+                &[],
+            )
+            .unwrap();
 
             let matches = markdown_file.process_line_for_back_populate_replacements(
                 case.content,
                 0,
+                &mut SharedTexts::new(Arc::from("test.md")),
                 &automaton,
                 &[&wikilink],
                 &validated_config,
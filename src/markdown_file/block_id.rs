@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use anyhow::Result as AnyhowResult;
+
+use super::MarkdownFile;
+use super::constants::BLOCK_ID_HASH_LENGTH;
+use super::text_excluder::CodeBlockExcluder;
+use crate::constants::BLOCK_REFERENCE_PREFIX;
+use crate::constants::HASH;
+use crate::constants::NEWLINE;
+use crate::sha256_cache;
+use crate::support::BLOCK_ID_REGEX;
+
+/// A `^block-id` ending a line of a note's body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockId {
+    pub id:          String,
+    pub line_number: usize,
+}
+
+/// A `[[note#^block-id]]` link in a note's body. `note` is empty for a `[[#^block-id]]` link
+/// to the note itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockReference {
+    pub found_text:  String,
+    pub id:          String,
+    pub line_number: usize,
+    pub note:        String,
+}
+
+impl MarkdownFile {
+    /// Every block id in the body, outside code blocks.
+    pub(crate) fn block_ids(&self) -> Vec<BlockId> {
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut block_ids = Vec::new();
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+            if let Some(captures) = BLOCK_ID_REGEX.captures(line) {
+                block_ids.push(BlockId {
+                    id:          captures[1].to_string(),
+                    line_number: self.get_real_line_number(line_idx),
+                });
+            }
+        }
+
+        block_ids
+    }
+
+    /// Every content wikilink to a block.
+    pub(crate) fn block_references(&self) -> Vec<BlockReference> {
+        let mut references = Vec::new();
+        self.for_each_content_wikilink(|line_number, line, spanned_wikilink| {
+            let target = &spanned_wikilink.wikilink.target;
+            let (Some((note, _)), Some((_, section))) =
+                (target.split_once(HASH), target.rsplit_once(HASH))
+            else {
+                return;
+            };
+            let Some(id) = section.strip_prefix(BLOCK_REFERENCE_PREFIX) else {
+                return;
+            };
+
+            let (start, end) = spanned_wikilink.span;
+            references.push(BlockReference {
+                found_text: line[start..end].to_string(),
+                id: id.trim().to_string(),
+                line_number,
+                note: note.trim().to_string(),
+            });
+        });
+        references
+    }
+
+    /// Replaces `placeholder` at the end of a line, outside code blocks, with a block id made
+    /// from `id_seed` and the line's text - the same line gets the same id on every run until
+    /// it is applied. An id the note already has is never reused.
+    pub(crate) fn generate_block_ids(
+        &mut self,
+        placeholder: &str,
+        id_seed: &str,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        let mut taken: HashSet<String> = self
+            .block_ids()
+            .into_iter()
+            .map(|block_id| block_id.id)
+            .collect();
+        let mut lines: Vec<String> = self.content.lines().map(str::to_string).collect();
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut added = 0;
+
+        for line in &mut lines {
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+            let Some(text) = line.trim_end().strip_suffix(placeholder) else {
+                continue;
+            };
+            if !text.is_empty() && !text.ends_with(char::is_whitespace) {
+                continue;
+            }
+
+            let id = new_block_id(id_seed, text.trim(), &taken);
+            *line = format!("{text}{BLOCK_REFERENCE_PREFIX}{id}");
+            taken.insert(id);
+            added += 1;
+        }
+
+        if added > 0 {
+            let mut updated_content = lines.join(&NEWLINE.to_string());
+            if self.content.ends_with(NEWLINE) {
+                updated_content.push(NEWLINE);
+            }
+            self.content = updated_content;
+            self.mark_block_ids_added(added, operational_timezone)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The first `BLOCK_ID_HASH_LENGTH` hex digits of the hash of the line - or more of them, when
+/// that many name a block the note already has.
+fn new_block_id(id_seed: &str, text: &str, taken: &HashSet<String>) -> String {
+    let hash = sha256_cache::hash_contents(format!("{id_seed}{NEWLINE}{text}").as_bytes());
+    (BLOCK_ID_HASH_LENGTH..=hash.len())
+        .map(|length| hash[..length].to_string())
+        .find(|id| !taken.contains(id))
+        .unwrap_or(hash)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::markdown_file::PersistReason;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_generate_block_ids_replaces_placeholder() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "first point ^new\nkept^new\n```\ncode ^new\n```\nsecond point ^abc\n^new",
+            )
+            .create(&temp_dir, "notes.md");
+        let mut markdown_file = test_support::get_test_markdown_file(file_path);
+
+        markdown_file
+            .generate_block_ids("^new", "notes.md", DEFAULT_TIMEZONE)
+            .unwrap();
+
+        let lines: Vec<&str> = markdown_file.content.lines().collect();
+        assert!(lines[0].starts_with("first point ^"));
+        assert_ne!(lines[0], "first point ^new");
+        assert_eq!(lines[1], "kept^new", "the placeholder must stand apart");
+        assert_eq!(lines[3], "code ^new", "code blocks are left alone");
+        assert!(lines[6].starts_with('^') && lines[6] != "^new");
+
+        let ids: Vec<String> = markdown_file
+            .block_ids()
+            .into_iter()
+            .map(|block_id| block_id.id)
+            .collect();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[1], "abc");
+        assert!(
+            markdown_file
+                .persist_reasons
+                .contains(&PersistReason::BlockIdsAdded { count: 2 })
+        );
+    }
+}
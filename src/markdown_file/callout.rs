@@ -0,0 +1,367 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use super::MarkdownFile;
+use super::constants::ADMONITION_COLLAPSE_CLOSED;
+use super::constants::ADMONITION_COLLAPSE_KEY;
+use super::constants::ADMONITION_COLLAPSE_OPEN;
+use super::constants::ADMONITION_FENCE_PREFIX;
+use super::constants::ADMONITION_OPTION_KEYS;
+use super::constants::ADMONITION_OPTION_SEPARATOR;
+use super::constants::ADMONITION_TITLE_KEY;
+use super::constants::BLOCKQUOTE_MARKER;
+use super::constants::CALLOUT_CLOSING;
+use super::constants::CALLOUT_COLLAPSED;
+use super::constants::CALLOUT_EXPANDED;
+use super::constants::CALLOUT_OPENING;
+use super::constants::INLINE_CODE_DELIMITER;
+use super::constants::KNOWN_CALLOUT_TYPES;
+use super::constants::MIN_CODE_FENCE_LENGTH;
+use super::replaceable_content::MatchType;
+use super::replaceable_content::ReplaceableContent;
+use super::text_excluder::CodeBlockExcluder;
+use crate::constants::MISSING_SPACE;
+use crate::constants::UNKNOWN_CALLOUT_TYPE;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalloutIssueKind {
+    /// `>[!note]` - the callout type directly follows the quote marker.
+    MissingSpace,
+    /// `> [!nto]` - not one of Obsidian's callout types, so it renders as a plain note.
+    UnknownType,
+}
+
+impl Display for CalloutIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSpace => f.write_str(MISSING_SPACE),
+            Self::UnknownType => f.write_str(UNKNOWN_CALLOUT_TYPE),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalloutIssue {
+    pub kind:        CalloutIssueKind,
+    pub line_number: usize,
+    pub line_text:   String,
+}
+
+/// One line of an admonition code block rewritten as part of a callout. Option lines and the
+/// fences are replaced with nothing, which drops the line.
+#[derive(Clone, Debug)]
+pub struct AdmonitionLineMatch {
+    pub found_text:  String,
+    pub line_number: usize,
+    pub replacement: String,
+}
+
+impl ReplaceableContent for AdmonitionLineMatch {
+    fn line_number(&self) -> usize { self.line_number }
+
+    fn position(&self) -> usize { 0 }
+
+    fn get_replacement(&self) -> String { self.replacement.clone() }
+
+    fn matched_text(&self) -> String { self.found_text.clone() }
+
+    fn match_type(&self) -> MatchType { MatchType::AdmonitionConversion }
+}
+
+/// An ```` ```ad-note ```` admonition block and the line matches that turn it into a callout.
+#[derive(Clone, Debug)]
+pub struct AdmonitionConversion {
+    pub admonition:     String,
+    pub callout_header: String,
+    pub line_matches:   Vec<AdmonitionLineMatch>,
+    pub line_number:    usize,
+}
+
+impl MarkdownFile {
+    /// Finds callout headers outside code blocks that are missing the space after `>` or use a
+    /// type Obsidian doesn't know. A line can have both issues.
+    pub(crate) fn find_callout_issues(&self) -> Vec<CalloutIssue> {
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut issues = Vec::new();
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+
+            let Some((has_space, callout_type)) = parse_callout_header(line) else {
+                continue;
+            };
+
+            let mut push_issue = |kind| {
+                issues.push(CalloutIssue {
+                    kind,
+                    line_number: self.get_real_line_number(line_idx),
+                    line_text: line.to_string(),
+                });
+            };
+            if !has_space {
+                push_issue(CalloutIssueKind::MissingSpace);
+            }
+            if !KNOWN_CALLOUT_TYPES.contains(&callout_type.to_lowercase().as_str()) {
+                push_issue(CalloutIssueKind::UnknownType);
+            }
+        }
+
+        issues
+    }
+
+    /// Finds top-level ```` ```ad-<type> ```` blocks from the admonition plugin and builds the
+    /// equivalent callout: `title` becomes the callout title, `collapse: closed` / `open` become
+    /// `-` / `+`, and every content line is quoted. Unclosed blocks are left alone.
+    pub(crate) fn find_admonition_conversions(&self) -> Vec<AdmonitionConversion> {
+        let lines: Vec<&str> = self.content.lines().collect();
+        let mut conversions = Vec::new();
+        let mut open_fence_length: Option<usize> = None;
+        let mut line_idx = 0;
+
+        while line_idx < lines.len() {
+            let line = lines[line_idx];
+            if let Some(fence_length) = open_fence_length {
+                if is_closing_fence(line, fence_length) {
+                    open_fence_length = None;
+                }
+                line_idx += 1;
+                continue;
+            }
+
+            let fence_length = code_fence_length(line);
+            if fence_length >= MIN_CODE_FENCE_LENGTH {
+                if let Some(admonition_type) = line[fence_length..]
+                    .trim()
+                    .strip_prefix(ADMONITION_FENCE_PREFIX)
+                    .filter(|admonition_type| !admonition_type.is_empty())
+                    && let Some((conversion, closing_idx)) =
+                        self.convert_admonition(&lines, line_idx, fence_length, admonition_type)
+                {
+                    conversions.push(conversion);
+                    line_idx = closing_idx + 1;
+                    continue;
+                }
+                open_fence_length = Some(fence_length);
+            }
+            line_idx += 1;
+        }
+
+        conversions
+    }
+
+    fn convert_admonition(
+        &self,
+        lines: &[&str],
+        opening_idx: usize,
+        fence_length: usize,
+        admonition_type: &str,
+    ) -> Option<(AdmonitionConversion, usize)> {
+        let closing_idx = (opening_idx + 1..lines.len())
+            .find(|&line_idx| is_closing_fence(lines[line_idx], fence_length))?;
+
+        let mut title = None;
+        let mut collapse = "";
+        let mut line_matches = Vec::new();
+        let mut content_idx = opening_idx + 1;
+
+        while content_idx < closing_idx {
+            let Some((key, value)) = admonition_option(lines[content_idx]) else {
+                break;
+            };
+            if key == ADMONITION_TITLE_KEY && !value.is_empty() {
+                title = Some(value);
+            } else if key == ADMONITION_COLLAPSE_KEY {
+                collapse = match value {
+                    ADMONITION_COLLAPSE_CLOSED => CALLOUT_COLLAPSED,
+                    ADMONITION_COLLAPSE_OPEN => CALLOUT_EXPANDED,
+                    _ => "",
+                };
+            }
+            line_matches.push(self.admonition_line_match(lines, content_idx, String::new()));
+            content_idx += 1;
+        }
+
+        let callout_header = title.map_or_else(
+            || format!("{BLOCKQUOTE_MARKER} {CALLOUT_OPENING}{admonition_type}{CALLOUT_CLOSING}{collapse}"),
+            |title| {
+                format!(
+                    "{BLOCKQUOTE_MARKER} {CALLOUT_OPENING}{admonition_type}{CALLOUT_CLOSING}{collapse} {title}"
+                )
+            },
+        );
+        line_matches.insert(
+            0,
+            self.admonition_line_match(lines, opening_idx, callout_header.clone()),
+        );
+
+        for line_idx in content_idx..closing_idx {
+            let line = lines[line_idx];
+            let replacement = if line.trim().is_empty() {
+                BLOCKQUOTE_MARKER.to_string()
+            } else {
+                format!("{BLOCKQUOTE_MARKER} {line}")
+            };
+            line_matches.push(self.admonition_line_match(lines, line_idx, replacement));
+        }
+        line_matches.push(self.admonition_line_match(lines, closing_idx, String::new()));
+
+        Some((
+            AdmonitionConversion {
+                admonition: lines[opening_idx].trim().to_string(),
+                callout_header,
+                line_matches,
+                line_number: self.get_real_line_number(opening_idx),
+            },
+            closing_idx,
+        ))
+    }
+
+    fn admonition_line_match(
+        &self,
+        lines: &[&str],
+        line_idx: usize,
+        replacement: String,
+    ) -> AdmonitionLineMatch {
+        AdmonitionLineMatch {
+            found_text: lines[line_idx].to_string(),
+            line_number: self.get_real_line_number(line_idx),
+            replacement,
+        }
+    }
+}
+
+/// Whether the quote marker is followed by a space, and the callout type, for a line such as
+/// `> [!note]+ Title`. Nested quotes (`> > [!tip]`) are accepted.
+fn parse_callout_header(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    if !trimmed.starts_with(BLOCKQUOTE_MARKER) {
+        return None;
+    }
+
+    let header = trimmed.trim_start_matches(|c: char| c == BLOCKQUOTE_MARKER || c.is_whitespace());
+    let inner = header.strip_prefix(CALLOUT_OPENING)?;
+    let callout_type = &inner[..inner.find(CALLOUT_CLOSING)?];
+    let markers = &trimmed[..trimmed.len() - header.len()];
+
+    Some((!markers.ends_with(BLOCKQUOTE_MARKER), callout_type))
+}
+
+/// Number of backticks opening a fence at the start of an unindented line.
+fn code_fence_length(line: &str) -> usize {
+    line.chars()
+        .take_while(|&c| c == INLINE_CODE_DELIMITER)
+        .count()
+}
+
+/// A closing fence has at least as many backticks as the opening one and nothing else.
+fn is_closing_fence(line: &str, fence_length: usize) -> bool {
+    let trimmed = line.trim();
+    trimmed.len() >= fence_length && trimmed.chars().all(|c| c == INLINE_CODE_DELIMITER)
+}
+
+/// `title: Some title` at the top of an admonition block, as a lowercase key and trimmed value.
+fn admonition_option(line: &str) -> Option<(String, &str)> {
+    let (key, value) = line.split_once(ADMONITION_OPTION_SEPARATOR)?;
+    let key = key.trim().to_lowercase();
+    ADMONITION_OPTION_KEYS
+        .contains(&key.as_str())
+        .then(|| (key, value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CalloutIssueKind;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_find_callout_issues() {
+        let (temp_dir, _, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "> [!note] fine\n\
+                 >[!tip] no space\n\
+                 > > [!Warning] nested\n\
+                 >[!nto] both\n\
+                 ```\n\
+                 >[!nope] in code\n\
+                 ```",
+            )
+            .create(&temp_dir, "callouts.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let issues: Vec<(usize, CalloutIssueKind)> = markdown_file
+            .find_callout_issues()
+            .iter()
+            .map(|issue| (issue.line_number, issue.kind))
+            .collect();
+
+        let first_line = markdown_file.get_real_line_number(0);
+        assert_eq!(
+            issues,
+            vec![
+                (first_line + 1, CalloutIssueKind::MissingSpace),
+                (first_line + 3, CalloutIssueKind::MissingSpace),
+                (first_line + 3, CalloutIssueKind::UnknownType),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_admonition_conversions() {
+        let (temp_dir, _, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "````markdown\n\
+                 ```ad-note\n\
+                 ```\n\
+                 ````\n\
+                 ```ad-tip\n\
+                 title: Remember\n\
+                 collapse: closed\n\
+                 first\n\
+                 \n\
+                 ```python\n\
+                 ```\n\
+                 ```ad-warning\n\
+                 unclosed",
+            )
+            .create(&temp_dir, "admonitions.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let conversions = markdown_file.find_admonition_conversions();
+
+        assert_eq!(
+            conversions.len(),
+            1,
+            "fenced examples and unclosed blocks are skipped"
+        );
+        assert_eq!(conversions[0].callout_header, "> [!tip]- Remember");
+        let replacements: Vec<&str> = conversions[0]
+            .line_matches
+            .iter()
+            .map(|line_match| line_match.replacement.as_str())
+            .collect();
+        assert_eq!(
+            replacements,
+            vec![
+                "> [!tip]- Remember",
+                "",
+                "",
+                "> first",
+                ">",
+                "> ```python",
+                ""
+            ]
+        );
+    }
+}
@@ -4,6 +4,8 @@ use super::MarkdownFile;
 use super::back_populate;
 use super::replaceable_content::MatchType;
 use super::replaceable_content::ReplaceableContent;
+use crate::app_settings::NewLinkFormat;
+use crate::config::LinkAliasStyle;
 use crate::constants::ESCAPED_PIPE;
 use crate::constants::FORWARD_SLASH;
 use crate::constants::PIPE;
@@ -11,6 +13,7 @@ use crate::support;
 use crate::validated_config::ValidatedConfig;
 use crate::wikilink::SpannedWikilink;
 use crate::wikilink::ToWikilink;
+use crate::wikilink::Wikilink;
 
 /// One content wikilink whose target names a real note by something other than its file stem
 /// (a vault path like `topics/service/LinkedIn`, or a case variant like `Linkedin`);
@@ -43,11 +46,20 @@ impl MarkdownFile {
     /// becomes `[[LinkedIn]]`). Every other link keeps its display text, so rendered prose
     /// never changes: `[[amazon]]` becomes `[[Amazon|amazon]]` — the same form back-populate
     /// gives a plaintext mention — and an alias equal to the stem collapses to the bare form.
+    ///
+    /// Rewritten links take the form `link_alias_style` asks for, looking the vault-relative
+    /// path up in `note_paths` (lowercased stem to path). Links already using the stem keep
+    /// their form unless `normalize_link_aliases` is on; with `LinkAliasStyle::PipedPath` or
+    /// Obsidian's absolute link format, a link already naming the note's path is canonical too.
     pub(crate) fn find_canonical_link_matches(
         &self,
         canonical_targets: &HashMap<String, String>,
+        note_paths: &HashMap<String, String>,
         validated_config: &ValidatedConfig,
     ) -> Vec<CanonicalLinkMatch> {
+        let configured_style = validated_config.link_alias_style();
+        let normalize = validated_config.normalize_link_aliases();
+
         let mut matches = Vec::new();
         self.for_each_content_wikilink(|line_number, line, spanned_wikilink| {
             let SpannedWikilink { wikilink, span } = spanned_wikilink;
@@ -55,18 +67,27 @@ impl MarkdownFile {
             else {
                 return;
             };
+            let note_path = note_paths
+                .get(&canonical_target.to_lowercase())
+                .map_or(canonical_target.as_str(), String::as_str);
+
+            if !normalize
+                && (configured_style == LinkAliasStyle::PipedPath
+                    || validated_config.link_format().path == NewLinkFormat::Absolute)
+                && wikilink.target == note_path
+            {
+                return;
+            }
+            let style = if normalize || wikilink.target != *canonical_target {
+                configured_style
+            } else {
+                LinkAliasStyle::KeepCase
+            };
 
             let (start, end) = span;
             let found_text = line[start..end].to_string();
 
-            // A bare path-qualified link displays its path; the stem is the readable form.
-            let bare_path_link =
-                wikilink.display_text == wikilink.target && wikilink.target.contains(FORWARD_SLASH);
-            let mut replacement = if bare_path_link {
-                canonical_target.to_wikilink()
-            } else {
-                canonical_target.to_aliased_wikilink(&wikilink.display_text)
-            };
+            let mut replacement = styled_replacement(&wikilink, canonical_target, note_path, style);
             if back_populate::is_in_markdown_table(line, &found_text) {
                 replacement = replacement.replace(PIPE, ESCAPED_PIPE);
             }
@@ -92,6 +113,41 @@ impl MarkdownFile {
     }
 }
 
+/// The canonical form of `wikilink` once re-targeted at `canonical_target`.
+pub(super) fn canonical_replacement(wikilink: &Wikilink, canonical_target: &str) -> String {
+    styled_replacement(
+        wikilink,
+        canonical_target,
+        canonical_target,
+        LinkAliasStyle::KeepCase,
+    )
+}
+
+/// The form of `wikilink`, re-targeted at the note with stem `stem` and vault-relative path
+/// `note_path`, that `style` asks for.
+pub(crate) fn styled_replacement(
+    wikilink: &Wikilink,
+    stem: &str,
+    note_path: &str,
+    style: LinkAliasStyle,
+) -> String {
+    // A bare path-qualified link displays its path; the stem is the readable form.
+    let bare_path_link =
+        wikilink.display_text == wikilink.target && wikilink.target.contains(FORWARD_SLASH);
+    let display_text = if bare_path_link {
+        stem
+    } else {
+        wikilink.display_text.as_str()
+    };
+
+    match style {
+        LinkAliasStyle::KeepCase => stem.to_aliased_wikilink(display_text),
+        LinkAliasStyle::Lowercase if display_text == stem => stem.to_wikilink(),
+        LinkAliasStyle::Lowercase => stem.to_aliased_wikilink(&display_text.to_lowercase()),
+        LinkAliasStyle::PipedPath => note_path.to_aliased_wikilink(display_text),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -125,8 +181,11 @@ mod tests {
             .create(&temp_dir, "diary.md");
         let markdown_file = test_support::get_test_markdown_file(file_path);
 
-        let matches = markdown_file
-            .find_canonical_link_matches(&linkedin_canonical_targets(), &validated_config);
+        let matches = markdown_file.find_canonical_link_matches(
+            &linkedin_canonical_targets(),
+            &HashMap::new(),
+            &validated_config,
+        );
 
         assert_eq!(matches.len(), 4);
         assert_eq!(matches[0].found_text, "[[topics/service/LinkedIn]]");
@@ -162,8 +221,11 @@ mod tests {
             .create(&temp_dir, "diary.md");
         let markdown_file = test_support::get_test_markdown_file(file_path);
 
-        let matches = markdown_file
-            .find_canonical_link_matches(&linkedin_canonical_targets(), &validated_config);
+        let matches = markdown_file.find_canonical_link_matches(
+            &linkedin_canonical_targets(),
+            &HashMap::new(),
+            &validated_config,
+        );
 
         assert!(
             matches.is_empty(),
@@ -181,8 +243,11 @@ mod tests {
             .create(&temp_dir, "diary.md");
         let markdown_file = test_support::get_test_markdown_file(file_path);
 
-        let matches = markdown_file
-            .find_canonical_link_matches(&linkedin_canonical_targets(), &validated_config);
+        let matches = markdown_file.find_canonical_link_matches(
+            &linkedin_canonical_targets(),
+            &HashMap::new(),
+            &validated_config,
+        );
 
         assert_eq!(matches.len(), 1);
         assert_eq!(
@@ -0,0 +1,92 @@
+use super::MarkdownFile;
+use super::text_excluder;
+use super::text_excluder::CodeBlockExcluder;
+use super::text_excluder::CommentExcluder;
+use crate::constants::CITATION_KEY_TRAILING_PUNCTUATION;
+use crate::support::CITATION_GROUP_REGEX;
+use crate::support::CITATION_KEY_REGEX;
+
+/// A pandoc citation key in a note's body, from `[@key]` or a `[[@key]]` literature note link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Citation {
+    pub key:         String,
+    pub line_number: usize,
+}
+
+impl MarkdownFile {
+    /// Every citation key in a `[...]` group, outside code and comments. Several keys can share
+    /// a group, as in `[see @knuth1984, p. 3; @lamport1994]`.
+    pub(crate) fn citations(&self) -> Vec<Citation> {
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut comment_excluder = CommentExcluder::new();
+        let mut citations = Vec::new();
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+
+            let comment_spans = comment_excluder.excluded_spans(line);
+            let uncommented_line = text_excluder::mask_spans(line, &comment_spans);
+            let visible_line = text_excluder::mask_spans(
+                &uncommented_line,
+                &text_excluder::find_inline_code_spans(&uncommented_line),
+            );
+
+            for group in CITATION_GROUP_REGEX.find_iter(&visible_line) {
+                for captures in CITATION_KEY_REGEX.captures_iter(group.as_str()) {
+                    let key = captures[1].trim_end_matches(CITATION_KEY_TRAILING_PUNCTUATION);
+                    if !key.is_empty() {
+                        citations.push(Citation {
+                            key:         key.to_string(),
+                            line_number: self.get_real_line_number(line_idx),
+                        });
+                    }
+                }
+            }
+        }
+
+        citations
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_citations_reads_keys_in_citation_groups() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "As shown [see @knuth1984, p. 3; -@lamport1994].\nSee [[@turing1936]].\n\
+                 Mail [me@example.com] or `[@in_code]` %%[@commented]%%\n```\n[@fenced]\n```"
+                    .to_string(),
+            )
+            .create(&temp_dir, "Paper.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let keys: Vec<(String, usize)> = markdown_file
+            .citations()
+            .into_iter()
+            .map(|citation| (citation.key, citation.line_number))
+            .collect();
+        let first_line = keys[0].1;
+        assert_eq!(
+            keys,
+            vec![
+                ("knuth1984".to_string(), first_line),
+                ("lamport1994".to_string(), first_line),
+                ("turing1936".to_string(), first_line + 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashSet;
+
+use anyhow::Result as AnyhowResult;
+
+use super::MarkdownFile;
+use crate::constants::NEWLINE;
+
+impl MarkdownFile {
+    /// Appends the non-blank lines of a conflict copy's body this note doesn't already have, in
+    /// the copy's order, below a blank line. Nothing is marked when the copy adds nothing.
+    pub(crate) fn merge_conflict_copy(
+        &mut self,
+        copy_content: &str,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        let existing: HashSet<&str> = self.content.lines().map(str::trim).collect();
+        let mut appended = HashSet::new();
+        let missing: Vec<&str> = copy_content
+            .lines()
+            .filter(|line| {
+                let trimmed = line.trim();
+                !trimmed.is_empty() && !existing.contains(trimmed) && appended.insert(trimmed)
+            })
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let merged = format!(
+            "{}{NEWLINE}{NEWLINE}{}{NEWLINE}",
+            self.content.trim_end(),
+            missing.join(&NEWLINE.to_string())
+        );
+        self.content = merged;
+        self.wikilinks = self.process_wikilinks();
+        self.image_links.links = self.process_image_links();
+
+        self.mark_conflict_copy_merged(1, operational_timezone)
+    }
+}
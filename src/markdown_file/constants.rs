@@ -1,14 +1,158 @@
+// audit trail
+pub(super) const AUDIT_ENTRY_SEPARATOR: &str = ", ";
+pub(super) const KNIFE_HISTORY_PROPERTY: &str = "knife_history";
+
+// block ids
+/// Hex digits of a generated block id - more are used when that many collide with an id the
+/// note already has.
+pub(super) const BLOCK_ID_HASH_LENGTH: usize = 6;
+
+// callouts
+pub(super) const ADMONITION_COLLAPSE_CLOSED: &str = "closed";
+pub(super) const ADMONITION_COLLAPSE_KEY: &str = "collapse";
+pub(super) const ADMONITION_COLLAPSE_OPEN: &str = "open";
+pub(super) const ADMONITION_FENCE_PREFIX: &str = "ad-";
+/// Options the admonition plugin reads from the top of the block.
+pub(super) const ADMONITION_OPTION_KEYS: [&str; 4] = ["collapse", "color", "icon", "title"];
+pub(super) const ADMONITION_OPTION_SEPARATOR: char = ':';
+pub(super) const ADMONITION_TITLE_KEY: &str = "title";
+pub(super) const BLOCKQUOTE_MARKER: char = '>';
+pub(super) const CALLOUT_CLOSING: char = ']';
+pub(super) const CALLOUT_COLLAPSED: &str = "-";
+pub(super) const CALLOUT_EXPANDED: &str = "+";
+pub(super) const CALLOUT_OPENING: &str = "[!";
+/// Obsidian's built-in callout types and their aliases.
+pub(super) const KNOWN_CALLOUT_TYPES: [&str; 27] = [
+    "abstract",
+    "attention",
+    "bug",
+    "caution",
+    "check",
+    "cite",
+    "danger",
+    "done",
+    "error",
+    "example",
+    "fail",
+    "failure",
+    "faq",
+    "help",
+    "hint",
+    "important",
+    "info",
+    "missing",
+    "note",
+    "question",
+    "quote",
+    "success",
+    "summary",
+    "tip",
+    "tldr",
+    "todo",
+    "warning",
+];
+pub(super) const MIN_CODE_FENCE_LENGTH: usize = 3;
+
 // code delimiters
 pub(super) const FENCED_CODE_DELIMITER: &str = "```";
 pub(super) const INLINE_CODE_DELIMITER: char = '`';
 
+// math delimiters
+pub(super) const MATH_BLOCK_DELIMITER: &str = "$$";
+pub(super) const MATH_DELIMITER: char = '$';
+
+// comment delimiters
+pub(super) const HTML_COMMENT_CLOSING: &str = "-->";
+pub(super) const HTML_COMMENT_OPENING: &str = "<!--";
+pub(super) const HTML_TAG_OPENING: char = '<';
+pub(super) const OBSIDIAN_COMMENT_DELIMITER: &str = "%%";
+
+// date mentions
+/// Characters that make a date part of a name or path rather than a mention of the day.
+pub(super) const DATE_MENTION_ADJACENT: [char; 3] = ['-', '/', '_'];
+pub(super) const DATE_MENTION_DAY_CAPTURE: usize = 3;
+pub(super) const DATE_MENTION_ISO_CAPTURE: usize = 1;
+pub(super) const DATE_MENTION_MONTH_CAPTURE: usize = 2;
+pub(super) const DATE_MENTION_YEAR_CAPTURE: usize = 4;
+
+// encrypted blocks
+/// Meld Encrypt wraps ciphertext in these, after the opening's version marker (`α`, `β`).
+pub(super) const ENCRYPTED_BLOCK_CLOSING: &str = "🔐%%";
+pub(super) const ENCRYPTED_BLOCK_OPENING: &str = "%%🔐";
+
+// entity notes
+pub(super) const LIST_ITEM_PREFIX: &str = "- ";
+
+// export
+/// A callout's title becomes bold text opening the quote.
+pub(super) const EXPORT_CALLOUT_TITLE_EMPHASIS: &str = "**";
+
+// flashcards
+pub(super) const FLASHCARD_ID_HASH_LENGTH: usize = 12;
+pub(super) const FLASHCARD_ID_PREFIX: &str = "card-";
+
+// frontmatter keys
+pub(super) const FRONTMATTER_COMMENT_PREFIX: char = '#';
+pub(super) const FRONTMATTER_KEY_SEPARATOR: char = ':';
+pub(super) const FRONTMATTER_KEY_VALUE_SEPARATOR: &str = ": ";
+pub(super) const FRONTMATTER_LIST_ITEM_PREFIX: char = '-';
+pub(super) const FRONTMATTER_QUOTES: [char; 2] = ['"', '\''];
+/// The opening `---` is line 1 of the file, so the YAML section starts on line 2.
+pub(super) const YAML_SECTION_FIRST_LINE_NUMBER: usize = 2;
+
+// headings
+pub(super) const MAX_HEADING_LEVEL: usize = 6;
+
+// ignore markers
+/// Ends a line ok leaves unlinked and whose image references it leaves alone.
+pub(super) const IGNORE_LINE_MARKER: &str = "%%ok:ignore%%";
+/// On a line of its own, does the same for the block that follows, up to the next blank line.
+pub(super) const IGNORE_NEXT_MARKER: &str = "<!-- ok:ignore-next -->";
+
 // image links
 pub(super) const HTTP_URL_PREFIX: &str = "http://";
 pub(super) const HTTPS_URL_PREFIX: &str = "https://";
-pub(super) const IMAGE_LINK_SIZE_PARAMETER_INDEX: usize = 1;
 pub(super) const IMAGE_LINK_WHOLE_MATCH_CAPTURE_INDEX: usize = 0;
 pub(super) const INVALID_IMAGE_LINK_FORMAT_PREFIX: &str =
     "invalid image link format passed to ImageLink::new: ";
+/// The quotes a markdown link's title can be in: `![alt](image.png "title")`.
+pub(super) const MARKDOWN_TITLE_QUOTES: [char; 2] = ['"', '\''];
+
+// personal data
+pub(super) const PII_EMAIL_ADDRESS: &str = "email address";
+pub(super) const PII_NATIONAL_ID: &str = "national ID";
+pub(super) const PII_PHONE_NUMBER: &str = "phone number";
+
+// punctuation normalization
+pub(super) const CURLY_DOUBLE_QUOTES: [char; 4] = ['\u{201C}', '\u{201D}', '\u{201E}', '\u{201F}'];
+pub(super) const CURLY_SINGLE_QUOTES: [char; 4] = ['\u{2018}', '\u{2019}', '\u{201A}', '\u{201B}'];
+pub(super) const DOUBLE_QUOTE: char = '"';
+pub(super) const EM_DASH: char = '\u{2014}';
+/// Horizontal bar, small em dash and the vertical presentation form.
+pub(super) const EM_DASH_VARIANTS: [char; 3] = ['\u{2015}', '\u{FE58}', '\u{FE31}'];
+pub(super) const HYPHEN: char = '-';
+/// Unicode hyphen, non-breaking hyphen, figure dash and fullwidth hyphen-minus.
+pub(super) const HYPHEN_VARIANTS: [char; 4] = ['\u{2010}', '\u{2011}', '\u{2012}', '\u{FF0D}'];
+/// No-break, figure and narrow no-break spaces.
+pub(super) const NON_BREAKING_SPACES: [char; 3] = ['\u{00A0}', '\u{2007}', '\u{202F}'];
+/// Zero-width space, word joiner and byte order mark. Zero-width joiners and non-joiners are
+/// left alone because emoji sequences and several scripts depend on them.
+pub(super) const ZERO_WIDTH_CHARACTERS: [char; 3] = ['\u{200B}', '\u{2060}', '\u{FEFF}'];
+
+// list normalization
+/// Indents one nesting level, as Obsidian does with its default of indenting with tabs.
+pub(super) const LIST_INDENT: char = '\t';
+pub(super) const LIST_MARKER: char = '-';
+/// Columns a tab counts for when comparing the indentation of list items.
+pub(super) const LIST_TAB_WIDTH: usize = 4;
+/// A line indented this far that doesn't continue a list is an indented code block.
+pub(super) const INDENTED_CODE_MIN_COLUMNS: usize = 4;
+/// `* * *`, `---` and `___` need at least three of their marker to be a horizontal rule.
+pub(super) const THEMATIC_BREAK_MIN_MARKERS: usize = 3;
+
+// markdown lint
+/// A URL right after one of these is an `<url>` autolink, an HTML attribute or a link target.
+pub(super) const URL_OPENERS: [char; 5] = ['<', '"', '\'', '=', '('];
 
 // matching
 pub(super) const APOSTROPHE: char = '\'';
@@ -17,3 +161,30 @@ pub(super) const RIGHT_SINGLE_QUOTATION_MARK: char = '\u{2019}';
 pub(super) const T_LOWER: char = 't';
 pub(super) const T_UPPER: char = 'T';
 pub(super) const UNDERSCORE: char = '_';
+
+// secret scan
+/// A fenced code block whose info string holds this shows sample values, not real ones.
+pub(super) const EXAMPLE_CODE_BLOCK_LABEL: &str = "example";
+/// A token's characters must average this many bits of entropy to look random.
+pub(super) const HIGH_ENTROPY_MIN_BITS: f64 = 3.5;
+pub(super) const HIGH_ENTROPY_STRING: &str = "high-entropy string";
+/// How much of a possible secret the report shows.
+pub(super) const SECRET_PREVIEW_LENGTH: usize = 4;
+pub(super) const SECRET_PREVIEW_MASK: &str = "…";
+
+// heading links
+/// How close, from 0 to 1, a stale link's heading must be to a current heading to be repointed
+/// at it.
+pub(super) const HEADING_LINK_REPAIR_MIN_SIMILARITY: f64 = 0.8;
+
+// note splitting
+/// `split_notes` extracts the `##` sections.
+pub(super) const SPLIT_HEADING_LEVEL: usize = 2;
+
+// table of contents
+/// Characters Obsidian drops from a heading when it builds a `[[#heading]]` link.
+pub(super) const HEADING_LINK_DISALLOWED_CHARACTERS: [char; 7] =
+    ['#', '%', ':', '[', ']', '^', '|'];
+pub(super) const TABLE_OF_CONTENTS_ENTRY_PREFIX: &str = "- [[#";
+pub(super) const TABLE_OF_CONTENTS_HEADING: &str = "## Table of Contents";
+pub(super) const TABLE_OF_CONTENTS_INDENT: char = '\t';
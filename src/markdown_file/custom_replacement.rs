@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use super::MarkdownFile;
+use super::replaceable_content::MatchType;
+use super::replaceable_content::ReplaceableContent;
+use super::text_excluder::CodeBlockExcluder;
+use crate::support;
+
+/// One rewrite a `ReplacementSource` finds in a line: `found_text` starts at byte `position`
+/// and becomes `replacement`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineReplacement {
+    pub position:    usize,
+    pub found_text:  String,
+    pub replacement: String,
+}
+
+/// Finds replacements of one kind in the lines of a note body - converting bare URLs to
+/// markdown links, say.
+///
+/// Pass one to `run_with_replacement_sources` and its matches are applied with the built-in
+/// ones: overlaps are resolved the same way, each change is listed in the custom replacements
+/// table, and a changed note records `PersistReason::CustomReplacementsApplied` with the
+/// source's name.
+pub trait ReplacementSource: Sync {
+    /// What the reports and the audit trail call this source's changes.
+    fn name(&self) -> &str;
+
+    /// The replacements to make in `line`. Lines in code blocks are never passed in.
+    fn find_replacements(&self, line: &str) -> Vec<LineReplacement>;
+}
+
+/// A replacement a `ReplacementSource` found; `source` is its name.
+#[derive(Clone, Debug)]
+pub struct CustomReplacementMatch {
+    pub found_text:    String,
+    pub line_number:   usize,
+    pub position:      usize,
+    pub relative_path: String,
+    pub replacement:   String,
+    pub source:        String,
+}
+
+impl ReplaceableContent for CustomReplacementMatch {
+    fn line_number(&self) -> usize { self.line_number }
+
+    fn position(&self) -> usize { self.position }
+
+    fn get_replacement(&self) -> String { self.replacement.clone() }
+
+    fn matched_text(&self) -> String { self.found_text.clone() }
+
+    fn match_type(&self) -> MatchType { MatchType::Custom(self.source.clone()) }
+}
+
+impl MarkdownFile {
+    /// Runs each of `sources` over the body lines outside code blocks. A replacement that
+    /// leaves the text as it is, or whose `found_text` isn't at its position, is dropped.
+    pub(crate) fn find_custom_replacement_matches(
+        &self,
+        sources: &[&dyn ReplacementSource],
+        obsidian_path: &Path,
+    ) -> Vec<CustomReplacementMatch> {
+        let relative_path = support::format_relative_path(&self.path, obsidian_path);
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut matches = Vec::new();
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+
+            for source in sources {
+                for line_replacement in source.find_replacements(line) {
+                    let LineReplacement {
+                        position,
+                        found_text,
+                        replacement,
+                    } = line_replacement;
+                    let end = position + found_text.len();
+                    if found_text == replacement || line.get(position..end) != Some(&found_text) {
+                        continue;
+                    }
+                    matches.push(CustomReplacementMatch {
+                        found_text,
+                        line_number: self.get_real_line_number(line_idx),
+                        position,
+                        relative_path: relative_path.clone(),
+                        replacement,
+                        source: source.name().to_string(),
+                    });
+                }
+            }
+        }
+
+        matches
+    }
+}
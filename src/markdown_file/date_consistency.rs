@@ -0,0 +1,229 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use chrono::DateTime;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::TimeZone;
+use chrono::Utc;
+use chrono_tz::Tz;
+use chrono_tz::UTC;
+
+use super::MarkdownFile;
+use super::PersistReason;
+use super::date_validation;
+use crate::config::ImpossibleDatePolicy;
+use crate::constants::CREATED_AFTER_MODIFIED;
+use crate::constants::FUTURE_DATE_CREATED;
+use crate::constants::FUTURE_DATE_MODIFIED;
+use crate::constants::NOON_HOUR;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateConsistencyIssue {
+    CreatedAfterModified,
+    FutureCreated,
+    FutureModified,
+}
+
+impl Display for DateConsistencyIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreatedAfterModified => f.write_str(CREATED_AFTER_MODIFIED),
+            Self::FutureCreated => f.write_str(FUTURE_DATE_CREATED),
+            Self::FutureModified => f.write_str(FUTURE_DATE_MODIFIED),
+        }
+    }
+}
+
+/// A frontmatter date no note can have, with both dates as they stood before any fix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DateInconsistency {
+    pub issue:         DateConsistencyIssue,
+    pub date_created:  Option<NaiveDate>,
+    pub date_modified: Option<NaiveDate>,
+    /// The date `fix_impossible_dates` wrote in place of the impossible one.
+    pub fixed:         Option<NaiveDate>,
+}
+
+pub(super) fn frontmatter_date(value: Option<&str>, date_link_format: &str) -> Option<NaiveDate> {
+    date_validation::parse_date(date_validation::extract_date(value?), date_link_format)
+}
+
+/// Noon keeps the date the same in every timezone near `timezone`, as `date_created_fix` does.
+pub(super) fn noon(date: NaiveDate, timezone: Tz) -> Option<DateTime<Utc>> {
+    timezone
+        .from_local_datetime(&date.and_hms_opt(NOON_HOUR, 0, 0)?)
+        .single()
+        .map(|date_time| date_time.with_timezone(&Utc))
+}
+
+impl MarkdownFile {
+    /// Checks the frontmatter dates as they'll be written: either more than `tolerance_days`
+    /// past `today`, or `date_created` after `date_modified`. With `policy` set, a future date
+    /// becomes today and the pair is then put in order as `policy` says.
+    pub(crate) fn check_date_consistency(
+        &mut self,
+        today: NaiveDate,
+        tolerance_days: u32,
+        policy: Option<ImpossibleDatePolicy>,
+        operational_timezone: &str,
+    ) {
+        let Some(front_matter) = self.front_matter.as_mut() else {
+            return;
+        };
+        let date_created =
+            frontmatter_date(front_matter.date_created(), front_matter.date_link_format());
+        let date_modified = frontmatter_date(
+            front_matter.date_modified(),
+            front_matter.date_link_format(),
+        );
+        let latest_plausible = today.checked_add_days(Days::new(u64::from(tolerance_days)));
+        let is_future = |date: Option<NaiveDate>| {
+            date.zip(latest_plausible)
+                .is_some_and(|(date, latest)| date > latest)
+        };
+
+        let mut inconsistencies = Vec::new();
+        let mut inconsistency = |issue, fixed| {
+            inconsistencies.push(DateInconsistency {
+                issue,
+                date_created,
+                date_modified,
+                fixed: policy.and(fixed),
+            });
+        };
+
+        let (mut created, mut modified) = (date_created, date_modified);
+        if is_future(created) {
+            inconsistency(DateConsistencyIssue::FutureCreated, Some(today));
+            created = policy.map_or(created, |_| Some(today));
+        }
+        if is_future(modified) {
+            inconsistency(DateConsistencyIssue::FutureModified, Some(today));
+            modified = policy.map_or(modified, |_| Some(today));
+        }
+        if let (Some(created_date), Some(modified_date)) = (created, modified)
+            && created_date > modified_date
+        {
+            match policy {
+                Some(ImpossibleDatePolicy::CreatedToModified) => {
+                    inconsistency(DateConsistencyIssue::CreatedAfterModified, modified);
+                    created = modified;
+                },
+                Some(ImpossibleDatePolicy::ModifiedToCreated) => {
+                    inconsistency(DateConsistencyIssue::CreatedAfterModified, created);
+                    modified = created;
+                },
+                None => inconsistency(DateConsistencyIssue::CreatedAfterModified, None),
+            }
+        }
+
+        if policy.is_some() && !inconsistencies.is_empty() {
+            let timezone: Tz = operational_timezone.parse().unwrap_or(UTC);
+            if let Some(date) = created
+                .filter(|_| created != date_created)
+                .and_then(|date| noon(date, timezone))
+            {
+                front_matter.set_date_created(date, operational_timezone);
+            }
+            if let Some(date) = modified
+                .filter(|_| modified != date_modified)
+                .and_then(|date| noon(date, timezone))
+            {
+                front_matter.set_date_modified(date, operational_timezone);
+            }
+            self.persist_reasons
+                .push(PersistReason::ImpossibleDatesFixed {
+                    count: inconsistencies.len(),
+                });
+        }
+        self.date_inconsistencies = inconsistencies;
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    use super::DateConsistencyIssue;
+    use crate::config::ImpossibleDatePolicy;
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::constants::FORMAT_DATE;
+    use crate::markdown_file::MarkdownFile;
+    use crate::markdown_file::PersistReason;
+    use crate::test_support::TestFileBuilder;
+
+    fn date(day: u32) -> NaiveDate { NaiveDate::from_ymd_opt(2024, 5, day).unwrap() }
+
+    #[test]
+    fn test_impossible_dates_flagged_and_fixed_by_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = TestFileBuilder::new()
+            .with_title("Trip")
+            .create(&temp_dir, "Trip.md");
+        let load = |created: &str, modified: &str| {
+            let mut markdown_file =
+                MarkdownFile::new(path.clone(), DEFAULT_TIMEZONE, FORMAT_DATE, None, &[]).unwrap();
+            let front_matter = markdown_file.front_matter.as_mut().unwrap();
+            front_matter.created = Some(created.to_string());
+            front_matter.modified = Some(modified.to_string());
+            markdown_file.persist_reasons.clear();
+            markdown_file
+        };
+
+        let mut reported = load("[[2024-05-10]]", "[[2024-05-01]]");
+        reported.check_date_consistency(date(20), 1, None, DEFAULT_TIMEZONE);
+        assert_eq!(reported.date_inconsistencies.len(), 1);
+        assert_eq!(
+            reported.date_inconsistencies[0].issue,
+            DateConsistencyIssue::CreatedAfterModified
+        );
+        assert_eq!(reported.date_inconsistencies[0].fixed, None);
+        assert!(reported.persist_reasons.is_empty());
+
+        let mut fixed = load("[[2024-05-10]]", "[[2024-05-01]]");
+        fixed.check_date_consistency(
+            date(20),
+            1,
+            Some(ImpossibleDatePolicy::CreatedToModified),
+            DEFAULT_TIMEZONE,
+        );
+        let front_matter = fixed.front_matter.as_ref().unwrap();
+        assert_eq!(front_matter.date_created(), Some("[[2024-05-01]]"));
+        assert_eq!(front_matter.date_modified(), Some("[[2024-05-01]]"));
+        assert_eq!(
+            fixed.persist_reasons,
+            vec![PersistReason::ImpossibleDatesFixed { count: 1 }]
+        );
+
+        // A date one day out is within tolerance; two days out is in the future.
+        let mut future = load("[[2024-05-21]]", "[[2024-05-22]]");
+        future.check_date_consistency(
+            date(20),
+            1,
+            Some(ImpossibleDatePolicy::ModifiedToCreated),
+            DEFAULT_TIMEZONE,
+        );
+        let issues: Vec<DateConsistencyIssue> = future
+            .date_inconsistencies
+            .iter()
+            .map(|inconsistency| inconsistency.issue)
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                DateConsistencyIssue::FutureModified,
+                DateConsistencyIssue::CreatedAfterModified
+            ]
+        );
+        let front_matter = future.front_matter.as_ref().unwrap();
+        assert_eq!(front_matter.date_created(), Some("[[2024-05-21]]"));
+        assert_eq!(front_matter.date_modified(), Some("[[2024-05-21]]"));
+    }
+}
@@ -0,0 +1,215 @@
+use std::ffi::OsStr;
+
+use chrono::NaiveDate;
+use regex::Captures;
+
+use super::MarkdownFile;
+use super::back_populate;
+use super::constants::DATE_MENTION_ADJACENT;
+use super::constants::DATE_MENTION_DAY_CAPTURE;
+use super::constants::DATE_MENTION_ISO_CAPTURE;
+use super::constants::DATE_MENTION_MONTH_CAPTURE;
+use super::constants::DATE_MENTION_YEAR_CAPTURE;
+use super::regex_replacement;
+use super::replaceable_content::MatchType;
+use super::replaceable_content::ReplaceableContent;
+use super::text_excluder;
+use super::text_excluder::CodeBlockExcluder;
+use super::text_excluder::CommentExcluder;
+use super::text_excluder::MathBlockExcluder;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::ESCAPED_PIPE;
+use crate::constants::FORMAT_DATE;
+use crate::constants::FORMAT_DATE_MENTION;
+use crate::constants::OPENING_WIKILINK;
+use crate::constants::PIPE;
+use crate::support;
+use crate::support::DATE_MENTION_REGEX;
+use crate::validated_config::ValidatedConfig;
+
+/// A date written as plain text - `2024-03-02` or `March 2, 2024` - and the daily-note link it
+/// becomes. Prose keeps its wording as the link's alias.
+#[derive(Clone, Debug)]
+pub struct DateMentionMatch {
+    pub found_text:    String,
+    pub line_number:   usize,
+    pub position:      usize,
+    pub relative_path: String,
+    pub replacement:   String,
+}
+
+impl ReplaceableContent for DateMentionMatch {
+    fn line_number(&self) -> usize { self.line_number }
+
+    fn position(&self) -> usize { self.position }
+
+    fn get_replacement(&self) -> String { self.replacement.clone() }
+
+    fn matched_text(&self) -> String { self.found_text.clone() }
+
+    fn match_type(&self) -> MatchType { MatchType::DateMention }
+}
+
+impl MarkdownFile {
+    /// Finds unlinked dates in the note body and links each to its daily note in
+    /// `date_link_format`. Code, comments, links and, with `exclude_math` on, math are skipped,
+    /// as is a daily note's own date.
+    pub(crate) fn find_date_mention_matches(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Vec<DateMentionMatch> {
+        let date_link_format = validated_config.date_link_format();
+        let relative_path =
+            support::format_relative_path(&self.path, validated_config.obsidian_path());
+        let file_stem = self
+            .path
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default();
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut comment_excluder = CommentExcluder::new();
+        let mut math_block_excluder = MathBlockExcluder::new();
+        let mut matches = Vec::new();
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            if line.trim().is_empty() {
+                // Blank lines end raw HTML blocks.
+                comment_excluder.excluded_spans(line);
+                continue;
+            }
+
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+            if validated_config.exclude_math() && {
+                math_block_excluder.update(line);
+                math_block_excluder.is_in_math_block()
+            } {
+                continue;
+            }
+
+            let comment_spans = comment_excluder.excluded_spans(line);
+            let code_zones = text_excluder::find_inline_code_spans(line);
+            let exclusion_zones = regex_replacement::text_exclusion_zones(
+                line,
+                &code_zones,
+                comment_spans,
+                validated_config,
+            );
+
+            for captures in DATE_MENTION_REGEX.captures_iter(line) {
+                let Some(whole_match) = captures.get(0) else {
+                    continue;
+                };
+                let (start, end) = (whole_match.start(), whole_match.end());
+                if back_populate::range_overlaps(&exclusion_zones, start, end)
+                    || !stands_alone(line, start, end)
+                {
+                    continue;
+                }
+                let Some(date) = mentioned_date(&captures) else {
+                    continue;
+                };
+
+                let target = date.format(date_link_format).to_string();
+                if target == file_stem {
+                    continue;
+                }
+
+                let found_text = whole_match.as_str().to_string();
+                let mut replacement = if found_text == target {
+                    format!("{OPENING_WIKILINK}{target}{CLOSING_WIKILINK}")
+                } else {
+                    format!("{OPENING_WIKILINK}{target}{PIPE}{found_text}{CLOSING_WIKILINK}")
+                };
+                if back_populate::is_in_markdown_table(line, &found_text) {
+                    replacement = replacement.replace(PIPE, ESCAPED_PIPE);
+                }
+
+                matches.push(DateMentionMatch {
+                    found_text,
+                    line_number: self.get_real_line_number(line_idx),
+                    position: start,
+                    relative_path: relative_path.clone(),
+                    replacement,
+                });
+            }
+        }
+
+        matches
+    }
+}
+
+/// The date a `DATE_MENTION_REGEX` match names. `February 30, 2024` isn't one.
+fn mentioned_date(captures: &Captures<'_>) -> Option<NaiveDate> {
+    if let Some(iso) = captures.get(DATE_MENTION_ISO_CAPTURE) {
+        return NaiveDate::parse_from_str(iso.as_str(), FORMAT_DATE).ok();
+    }
+    let month = captures.get(DATE_MENTION_MONTH_CAPTURE)?.as_str();
+    let day = captures.get(DATE_MENTION_DAY_CAPTURE)?.as_str();
+    let year = captures.get(DATE_MENTION_YEAR_CAPTURE)?.as_str();
+    NaiveDate::parse_from_str(&format!("{month} {day} {year}"), FORMAT_DATE_MENTION).ok()
+}
+
+/// `2024-03-02-notes` and `v2024-03-02/` are names, not dates - a mention can't run on into
+/// a path or identifier.
+fn stands_alone(line: &str, start: usize, end: usize) -> bool {
+    let before = line[..start].chars().next_back();
+    let after = line[end..].chars().next();
+    [before, after]
+        .into_iter()
+        .flatten()
+        .all(|character| !DATE_MENTION_ADJACENT.contains(&character))
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_find_date_mention_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.link_date_mentions(true);
+            })
+            .unwrap();
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "met on 2024-03-02 and again March 2nd, 2024\n\
+                 | day | Jan 5, 2025 |\n\
+                 `2024-01-01` [[2024-01-02]] build-2024-01-03 and February 30, 2024\n\
+                 ```\n\
+                 2024-01-04\n\
+                 ```\n\
+                 today is 2024-05-02",
+            )
+            .create(&temp_dir, "2024-05-02.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let matches = markdown_file.find_date_mention_matches(&validated_config);
+
+        let found: Vec<(&str, &str)> = matches
+            .iter()
+            .map(|m| (m.found_text.as_str(), m.replacement.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("2024-03-02", "[[2024-03-02]]"),
+                ("March 2nd, 2024", "[[2024-03-02|March 2nd, 2024]]"),
+                ("Jan 5, 2025", r"[[2025-01-05\|Jan 5, 2025]]"),
+            ],
+            "code, links, names, impossible dates and the note's own date are skipped"
+        );
+    }
+}
@@ -11,12 +11,39 @@ use chrono::TimeZone;
 use chrono::Utc;
 use chrono_tz::Tz;
 use chrono_tz::UTC;
+use serde_yaml::Value;
 
+use super::MarkdownFile;
+use super::table_of_contents::TableOfContentsAction;
+use crate::config::PersistReasonKind;
 use crate::constants::CLOSING_WIKILINK;
 use crate::constants::DOUBLE_QUOTE;
 use crate::constants::FORMAT_DATE;
 use crate::constants::NOON_HOUR;
 use crate::constants::OPENING_WIKILINK;
+use crate::constants::PATCH_CATEGORY_ADMONITIONS;
+use crate::constants::PATCH_CATEGORY_ALIASES;
+use crate::constants::PATCH_CATEGORY_BACK_POPULATE;
+use crate::constants::PATCH_CATEGORY_BLOCK_IDS;
+use crate::constants::PATCH_CATEGORY_CANONICAL_LINKS;
+use crate::constants::PATCH_CATEGORY_CONFLICT_COPIES;
+use crate::constants::PATCH_CATEGORY_CUSTOM_REPLACEMENTS;
+use crate::constants::PATCH_CATEGORY_DATES;
+use crate::constants::PATCH_CATEGORY_ENTITY_NOTES;
+use crate::constants::PATCH_CATEGORY_FLASHCARDS;
+use crate::constants::PATCH_CATEGORY_FRONTMATTER;
+use crate::constants::PATCH_CATEGORY_FRONTMATTER_LINKS;
+use crate::constants::PATCH_CATEGORY_IMAGE_REFERENCES;
+use crate::constants::PATCH_CATEGORY_LISTS;
+use crate::constants::PATCH_CATEGORY_PERIODIC_NOTES;
+use crate::constants::PATCH_CATEGORY_PHANTOM_LINKS;
+use crate::constants::PATCH_CATEGORY_PROPERTY_TYPES;
+use crate::constants::PATCH_CATEGORY_PUNCTUATION;
+use crate::constants::PATCH_CATEGORY_REPLACEMENTS;
+use crate::constants::PATCH_CATEGORY_SELF_LINKS;
+use crate::constants::PATCH_CATEGORY_SPLIT_NOTES;
+use crate::constants::PATCH_CATEGORY_TABLE_OF_CONTENTS;
+use crate::constants::PATCH_CATEGORY_WIKILINK_REPAIRS;
 use crate::frontmatter::FrontMatter;
 use crate::wikilink;
 
@@ -25,11 +52,38 @@ pub enum PersistReason {
     DateCreatedUpdated { reason: DateValidationIssue },
     DateModifiedUpdated { reason: DateValidationIssue },
     DateCreatedFixApplied,
+    DatePropertyUpdated { property: String },
+    DateMentionsLinked,
+    AdmonitionsConverted { count: usize },
+    AliasesGenerated { count: usize },
     BackPopulated,
+    BlockIdsAdded { count: usize },
+    ConflictCopyMerged { count: usize },
+    CustomReplacementsApplied { source: String, count: usize },
+    DuplicateFrontmatterKeysFixed { count: usize },
+    EntityAliasAdded { alias: String },
+    EntityIndexLinksAdded { count: usize },
+    FlashcardIdsAdded { count: usize },
     FrontmatterCreated,
+    FrontmatterLinksCanonicalized { count: usize },
+    FrontmatterLinkValuesFixed { count: usize },
+    HeadingLinksRepaired,
+    HeadingLinksRetargeted { count: usize },
     ImageReferencesModified,
+    ImpossibleDatesFixed { count: usize },
+    JournalDateAligned { date: NaiveDate },
     LinksCanonicalized,
+    ListsNormalized { count: usize },
+    NextReviewSet { due: NaiveDate },
+    PeriodicNoteLinked { parent: String },
     PhantomLinksResolved,
+    PropertyTypesCoerced { count: usize },
+    PunctuationNormalized { count: usize },
+    RegexReplacementsApplied,
+    SectionsSplit { count: usize },
+    SelfLinksCleaned,
+    TableOfContentsUpdated { action: TableOfContentsAction },
+    WikilinksRepaired,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -48,6 +102,16 @@ pub struct DateValidation {
     pub operational_timezone: String,
 }
 
+/// A `date_properties` value that isn't a date wikilink in `date_link_format`. `fixed` is the
+/// wikilink it becomes - `None` when the value isn't a date at all.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatePropertyValidation {
+    pub property:    String,
+    pub frontmatter: String,
+    pub issue:       DateValidationIssue,
+    pub fixed:       Option<String>,
+}
+
 #[derive(Debug, Default, Clone)]
 pub(crate) struct DateCreatedFixValidation {
     #[cfg(test)]
@@ -61,15 +125,175 @@ impl Display for PersistReason {
             Self::DateCreatedUpdated { .. } => write!(f, "date_created updated"),
             Self::DateModifiedUpdated { .. } => write!(f, "date_modified updated"),
             Self::DateCreatedFixApplied => write!(f, "date_created_fix applied"),
+            Self::DatePropertyUpdated { .. } => write!(f, "date property updated"),
+            Self::DateMentionsLinked => write!(f, "date mentions linked"),
+            Self::AdmonitionsConverted { .. } => write!(f, "admonitions converted"),
+            Self::AliasesGenerated { .. } => write!(f, "aliases generated"),
             Self::BackPopulated => write!(f, "back populated"),
+            Self::BlockIdsAdded { .. } => write!(f, "block ids added"),
+            Self::ConflictCopyMerged { .. } => write!(f, "conflict copy merged"),
+            Self::CustomReplacementsApplied { .. } => write!(f, "custom replacements applied"),
+            Self::DuplicateFrontmatterKeysFixed { .. } => {
+                write!(f, "duplicate frontmatter keys fixed")
+            },
+            Self::EntityAliasAdded { .. } => write!(f, "entity alias added"),
+            Self::EntityIndexLinksAdded { .. } => write!(f, "entity index links added"),
             Self::FrontmatterCreated => write!(f, "frontmatter created"),
+            Self::FrontmatterLinksCanonicalized { .. } => {
+                write!(f, "frontmatter links canonicalized")
+            },
+            Self::FlashcardIdsAdded { .. } => write!(f, "flashcard ids added"),
+            Self::FrontmatterLinkValuesFixed { .. } => write!(f, "frontmatter link values fixed"),
+            Self::HeadingLinksRepaired => write!(f, "heading links repaired"),
+            Self::HeadingLinksRetargeted { .. } => write!(f, "heading links retargeted"),
             Self::ImageReferencesModified => write!(f, "image references updated"),
+            Self::ImpossibleDatesFixed { .. } => write!(f, "impossible dates fixed"),
+            Self::JournalDateAligned { .. } => write!(f, "journal date aligned"),
             Self::LinksCanonicalized => write!(f, "links canonicalized"),
+            Self::ListsNormalized { .. } => write!(f, "lists normalized"),
+            Self::NextReviewSet { .. } => write!(f, "next review set"),
+            Self::PeriodicNoteLinked { .. } => write!(f, "linked to periodic note"),
             Self::PhantomLinksResolved => write!(f, "phantom links resolved"),
+            Self::PropertyTypesCoerced { .. } => write!(f, "property types coerced"),
+            Self::PunctuationNormalized { .. } => write!(f, "punctuation normalized"),
+            Self::RegexReplacementsApplied => write!(f, "regex replacements applied"),
+            Self::SectionsSplit { .. } => write!(f, "sections split into notes"),
+            Self::SelfLinksCleaned => write!(f, "self links cleaned"),
+            Self::TableOfContentsUpdated { .. } => write!(f, "table of contents updated"),
+            Self::WikilinksRepaired => write!(f, "invalid wikilinks repaired"),
         }
     }
 }
 
+impl PersistReason {
+    /// Whether the change rewrote the note body rather than only its frontmatter.
+    pub(crate) const fn changes_body(&self) -> bool {
+        matches!(
+            self,
+            Self::AdmonitionsConverted { .. }
+                | Self::BackPopulated
+                | Self::BlockIdsAdded { .. }
+                | Self::ConflictCopyMerged { .. }
+                | Self::CustomReplacementsApplied { .. }
+                | Self::DateMentionsLinked
+                | Self::EntityIndexLinksAdded { .. }
+                | Self::FlashcardIdsAdded { .. }
+                | Self::HeadingLinksRepaired
+                | Self::HeadingLinksRetargeted { .. }
+                | Self::ImageReferencesModified
+                | Self::LinksCanonicalized
+                | Self::ListsNormalized { .. }
+                | Self::PhantomLinksResolved
+                | Self::PunctuationNormalized { .. }
+                | Self::RegexReplacementsApplied
+                | Self::SectionsSplit { .. }
+                | Self::SelfLinksCleaned
+                | Self::TableOfContentsUpdated { .. }
+                | Self::WikilinksRepaired
+        )
+    }
+
+    pub(crate) const fn kind(&self) -> PersistReasonKind {
+        match self {
+            Self::DateCreatedUpdated { .. } => PersistReasonKind::DateCreatedUpdated,
+            Self::DateModifiedUpdated { .. } => PersistReasonKind::DateModifiedUpdated,
+            Self::DateCreatedFixApplied => PersistReasonKind::DateCreatedFixApplied,
+            Self::DatePropertyUpdated { .. } => PersistReasonKind::DatePropertyUpdated,
+            Self::DateMentionsLinked => PersistReasonKind::DateMentionsLinked,
+            Self::AdmonitionsConverted { .. } => PersistReasonKind::AdmonitionsConverted,
+            Self::AliasesGenerated { .. } => PersistReasonKind::AliasesGenerated,
+            Self::BackPopulated => PersistReasonKind::BackPopulated,
+            Self::BlockIdsAdded { .. } => PersistReasonKind::BlockIdsAdded,
+            Self::ConflictCopyMerged { .. } => PersistReasonKind::ConflictCopyMerged,
+            Self::CustomReplacementsApplied { .. } => PersistReasonKind::CustomReplacementsApplied,
+            Self::DuplicateFrontmatterKeysFixed { .. } => {
+                PersistReasonKind::DuplicateFrontmatterKeysFixed
+            },
+            Self::EntityAliasAdded { .. } => PersistReasonKind::EntityAliasAdded,
+            Self::EntityIndexLinksAdded { .. } => PersistReasonKind::EntityIndexLinksAdded,
+            Self::FlashcardIdsAdded { .. } => PersistReasonKind::FlashcardIdsAdded,
+            Self::FrontmatterCreated => PersistReasonKind::FrontmatterCreated,
+            Self::FrontmatterLinksCanonicalized { .. } => {
+                PersistReasonKind::FrontmatterLinksCanonicalized
+            },
+            Self::FrontmatterLinkValuesFixed { .. } => {
+                PersistReasonKind::FrontmatterLinkValuesFixed
+            },
+            Self::HeadingLinksRepaired => PersistReasonKind::HeadingLinksRepaired,
+            Self::HeadingLinksRetargeted { .. } => PersistReasonKind::HeadingLinksRetargeted,
+            Self::ImageReferencesModified => PersistReasonKind::ImageReferencesModified,
+            Self::ImpossibleDatesFixed { .. } => PersistReasonKind::ImpossibleDatesFixed,
+            Self::JournalDateAligned { .. } => PersistReasonKind::JournalDateAligned,
+            Self::LinksCanonicalized => PersistReasonKind::LinksCanonicalized,
+            Self::ListsNormalized { .. } => PersistReasonKind::ListsNormalized,
+            Self::NextReviewSet { .. } => PersistReasonKind::NextReviewSet,
+            Self::PeriodicNoteLinked { .. } => PersistReasonKind::PeriodicNoteLinked,
+            Self::PhantomLinksResolved => PersistReasonKind::PhantomLinksResolved,
+            Self::PropertyTypesCoerced { .. } => PersistReasonKind::PropertyTypesCoerced,
+            Self::PunctuationNormalized { .. } => PersistReasonKind::PunctuationNormalized,
+            Self::RegexReplacementsApplied => PersistReasonKind::RegexReplacementsApplied,
+            Self::SectionsSplit { .. } => PersistReasonKind::SectionsSplit,
+            Self::SelfLinksCleaned => PersistReasonKind::SelfLinksCleaned,
+            Self::TableOfContentsUpdated { .. } => PersistReasonKind::TableOfContentsUpdated,
+            Self::WikilinksRepaired => PersistReasonKind::WikilinksRepaired,
+        }
+    }
+
+    /// The category `ok apply --only` and `--exclude` know the change by.
+    pub(crate) const fn patch_category(&self) -> &'static str {
+        match self {
+            Self::DateCreatedUpdated { .. }
+            | Self::DateModifiedUpdated { .. }
+            | Self::DateCreatedFixApplied
+            | Self::DatePropertyUpdated { .. }
+            | Self::DateMentionsLinked
+            | Self::ImpossibleDatesFixed { .. }
+            | Self::JournalDateAligned { .. }
+            | Self::NextReviewSet { .. } => PATCH_CATEGORY_DATES,
+            Self::AdmonitionsConverted { .. } => PATCH_CATEGORY_ADMONITIONS,
+            Self::AliasesGenerated { .. } => PATCH_CATEGORY_ALIASES,
+            Self::BackPopulated => PATCH_CATEGORY_BACK_POPULATE,
+            Self::BlockIdsAdded { .. } => PATCH_CATEGORY_BLOCK_IDS,
+            Self::ConflictCopyMerged { .. } => PATCH_CATEGORY_CONFLICT_COPIES,
+            Self::CustomReplacementsApplied { .. } => PATCH_CATEGORY_CUSTOM_REPLACEMENTS,
+            Self::DuplicateFrontmatterKeysFixed { .. } | Self::FrontmatterCreated => {
+                PATCH_CATEGORY_FRONTMATTER
+            },
+            Self::EntityAliasAdded { .. } | Self::EntityIndexLinksAdded { .. } => {
+                PATCH_CATEGORY_ENTITY_NOTES
+            },
+            Self::FlashcardIdsAdded { .. } => PATCH_CATEGORY_FLASHCARDS,
+            Self::FrontmatterLinksCanonicalized { .. } | Self::LinksCanonicalized => {
+                PATCH_CATEGORY_CANONICAL_LINKS
+            },
+            Self::FrontmatterLinkValuesFixed { .. } => PATCH_CATEGORY_FRONTMATTER_LINKS,
+            Self::HeadingLinksRetargeted { .. } | Self::SectionsSplit { .. } => {
+                PATCH_CATEGORY_SPLIT_NOTES
+            },
+            Self::ImageReferencesModified => PATCH_CATEGORY_IMAGE_REFERENCES,
+            Self::ListsNormalized { .. } => PATCH_CATEGORY_LISTS,
+            Self::PeriodicNoteLinked { .. } => PATCH_CATEGORY_PERIODIC_NOTES,
+            Self::PhantomLinksResolved => PATCH_CATEGORY_PHANTOM_LINKS,
+            Self::PropertyTypesCoerced { .. } => PATCH_CATEGORY_PROPERTY_TYPES,
+            Self::PunctuationNormalized { .. } => PATCH_CATEGORY_PUNCTUATION,
+            Self::RegexReplacementsApplied => PATCH_CATEGORY_REPLACEMENTS,
+            Self::SelfLinksCleaned => PATCH_CATEGORY_SELF_LINKS,
+            Self::TableOfContentsUpdated { .. } => PATCH_CATEGORY_TABLE_OF_CONTENTS,
+            Self::HeadingLinksRepaired | Self::WikilinksRepaired => PATCH_CATEGORY_WIKILINK_REPAIRS,
+        }
+    }
+
+    /// Whether the change only brings `date_created` or `date_modified` in line with the file's
+    /// own dates. That doesn't depend on anything else in the note, so in a patch it goes along
+    /// with whichever rewrite of the note is applied.
+    pub(crate) const fn syncs_file_dates(&self) -> bool {
+        matches!(
+            self,
+            Self::DateCreatedUpdated { .. } | Self::DateModifiedUpdated { .. }
+        )
+    }
+}
+
 impl Display for DateValidationIssue {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let description = match self {
@@ -102,6 +326,7 @@ impl DateCreatedFixValidation {
     ) -> Self {
         let fix_str =
             front_matter.and_then(|front_matter| front_matter.date_created_fix().map(String::from));
+        let date_link_format = front_matter.map_or(FORMAT_DATE, FrontMatter::date_link_format);
 
         let parsed = fix_str.as_ref().and_then(|date_str| {
             let date = if wikilink::is_wikilink(Some(date_str)) {
@@ -110,7 +335,7 @@ impl DateCreatedFixValidation {
                 date_str.trim().trim_matches(DOUBLE_QUOTE)
             };
 
-            let naive_date = NaiveDate::parse_from_str(date.trim(), FORMAT_DATE).ok()?;
+            let naive_date = parse_date(date, date_link_format)?;
             let timezone: Tz = operational_timezone.parse().unwrap_or(UTC);
             let naive_datetime = naive_date.and_hms_opt(NOON_HOUR, 0, 0)?;
 
@@ -144,6 +369,7 @@ pub(super) fn get_date_validations(
     operational_timezone: &str,
 ) -> Result<(DateValidation, DateValidation), Error> {
     let metadata = fs::metadata(path)?;
+    let date_link_format = front_matter.map_or(FORMAT_DATE, FrontMatter::date_link_format);
 
     let dates = [
         (
@@ -164,6 +390,7 @@ pub(super) fn get_date_validations(
                 frontmatter_date.as_deref(),
                 &file_system_date,
                 operational_timezone,
+                date_link_format,
             );
             DateValidation {
                 frontmatter: frontmatter_date,
@@ -180,6 +407,7 @@ pub(super) fn get_date_validation_issue(
     date_opt: Option<&str>,
     file_system_date: &DateTime<Utc>,
     operational_timezone: &str,
+    date_link_format: &str,
 ) -> Option<DateValidationIssue> {
     // `DateValidationIssue::Missing` applies when the frontmatter date is absent.
     let Some(date_str) = date_opt else {
@@ -193,13 +421,9 @@ pub(super) fn get_date_validation_issue(
 
     let extracted_date = extract_date(date_str);
 
-    // `is_valid_date` accepts only `YYYY-MM-DD` dates.
-    if !is_valid_date(extracted_date) {
-        return Some(DateValidationIssue::InvalidFormat);
-    }
-
-    // `frontmatter_date` stores the wikilink date as a `NaiveDate`.
-    let Ok(frontmatter_date) = NaiveDate::parse_from_str(extracted_date.trim(), FORMAT_DATE) else {
+    // `frontmatter_date` stores the wikilink date as a `NaiveDate`. A date in any other
+    // format, `YYYY-MM-DD` included, is rewritten in `date_link_format`.
+    let Ok(frontmatter_date) = NaiveDate::parse_from_str(extracted_date, date_link_format) else {
         return Some(DateValidationIssue::InvalidFormat);
     };
 
@@ -234,18 +458,24 @@ pub(super) fn extract_date(date_str: &str) -> &str {
     }
 }
 
-// `is_valid_date` accepts strings that parse with `FORMAT_DATE`.
-pub(super) fn is_valid_date(date_str: &str) -> bool {
-    NaiveDate::parse_from_str(date_str.trim(), FORMAT_DATE).is_ok()
+/// A date in `date_link_format`, or in `FORMAT_DATE` as dates were linked before one was set.
+pub(super) fn parse_date(date_str: &str, date_link_format: &str) -> Option<NaiveDate> {
+    let date_str = date_str.trim();
+    NaiveDate::parse_from_str(date_str, date_link_format)
+        .or_else(|_| NaiveDate::parse_from_str(date_str, FORMAT_DATE))
+        .ok()
 }
 
+/// Repairs held by `hold_persist_reasons` aren't made - they come back as the second list, so
+/// they can still be reported.
 pub(super) fn process_date_validations(
     front_matter: &mut Option<FrontMatter>,
     created_date_validation: &DateValidation,
     modified_date_validation: &DateValidation,
     date_created_fix_validation: &DateCreatedFixValidation,
     operational_timezone: &str,
-) -> Vec<PersistReason> {
+    held_reasons: &[PersistReasonKind],
+) -> (Vec<PersistReason>, Vec<PersistReason>) {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
     enum CreatedDateUpdate {
         Skip,
@@ -253,6 +483,8 @@ pub(super) fn process_date_validations(
     }
 
     let mut reasons = Vec::new();
+    let mut held = Vec::new();
+    let is_held = |reason: &PersistReason| held_reasons.contains(&reason.kind());
 
     if let Some(front_matter) = front_matter {
         let mut created_date_update = CreatedDateUpdate::IfInvalid;
@@ -260,33 +492,128 @@ pub(super) fn process_date_validations(
         if let Some(fixed) = date_created_fix_validation.fixed {
             created_date_update = CreatedDateUpdate::Skip;
 
-            front_matter.set_date_created(fixed, operational_timezone);
-            front_matter.remove_date_created_fix();
-            reasons.push(PersistReason::DateCreatedFixApplied);
+            let reason = PersistReason::DateCreatedFixApplied;
+            if is_held(&reason) {
+                held.push(reason);
+            } else {
+                front_matter.set_date_created(fixed, operational_timezone);
+                front_matter.remove_date_created_fix();
+                reasons.push(reason);
+            }
         }
 
         // `DateCreatedUpdated` records a created-date repair.
         if let Some(ref issue) = created_date_validation.issue
             && created_date_update == CreatedDateUpdate::IfInvalid
         {
-            front_matter
-                .set_date_created(created_date_validation.file_system, operational_timezone);
-            reasons.push(PersistReason::DateCreatedUpdated {
+            let reason = PersistReason::DateCreatedUpdated {
                 reason: issue.clone(),
-            });
+            };
+            if is_held(&reason) {
+                held.push(reason);
+            } else {
+                front_matter
+                    .set_date_created(created_date_validation.file_system, operational_timezone);
+                reasons.push(reason);
+            }
         }
 
         // `DateModifiedUpdated` records a modified-date repair.
         if let Some(ref issue) = modified_date_validation.issue {
-            front_matter
-                .set_date_modified(modified_date_validation.file_system, operational_timezone);
-            reasons.push(PersistReason::DateModifiedUpdated {
+            let reason = PersistReason::DateModifiedUpdated {
                 reason: issue.clone(),
-            });
+            };
+            if is_held(&reason) {
+                held.push(reason);
+            } else {
+                front_matter
+                    .set_date_modified(modified_date_validation.file_system, operational_timezone);
+                reasons.push(reason);
+            }
         }
     }
 
-    reasons
+    (reasons, held)
+}
+
+/// Checks one `date_properties` value as `date_created` is checked, minus the file system
+/// date. An empty value passes - the properties are optional.
+fn date_property_validation(
+    property: &str,
+    value: &Value,
+    front_matter: &FrontMatter,
+) -> Option<DatePropertyValidation> {
+    let raw_value = match value {
+        Value::Null => return None,
+        Value::String(text) if text.trim().is_empty() => return None,
+        Value::String(text) => text.trim().to_string(),
+        other => serde_yaml::to_string(other)
+            .map(|text| text.trim().to_string())
+            .unwrap_or_default(),
+    };
+
+    let date = extract_date(&raw_value);
+    let is_linked = wikilink::is_wikilink(Some(&raw_value));
+    let in_link_format = NaiveDate::parse_from_str(date, front_matter.date_link_format()).is_ok();
+    let parsed = parse_date(date, front_matter.date_link_format());
+    let issue = match parsed {
+        Some(_) if is_linked && in_link_format => return None,
+        Some(_) if is_linked => DateValidationIssue::InvalidFormat,
+        Some(_) => DateValidationIssue::InvalidWikilink,
+        None => DateValidationIssue::InvalidFormat,
+    };
+    let fixed = parsed.map(|date| front_matter.date_link(date));
+
+    Some(DatePropertyValidation {
+        property: property.to_string(),
+        frontmatter: raw_value,
+        issue,
+        fixed,
+    })
+}
+
+impl MarkdownFile {
+    /// Checks the `date_properties` this note sets. A plain date, or one linked in another
+    /// format, becomes a wikilink in `date_link_format`, as `date_created` is written, unless
+    /// `hold_persist_reasons` holds it - anything else is only reported.
+    pub(crate) fn validate_date_properties(
+        &mut self,
+        properties: &[String],
+        operational_timezone: &str,
+        held_reasons: &[PersistReasonKind],
+    ) {
+        let Some(front_matter) = self.front_matter.as_mut() else {
+            return;
+        };
+        let validations: Vec<DatePropertyValidation> = properties
+            .iter()
+            .filter_map(|property| {
+                date_property_validation(property, front_matter.property(property)?, front_matter)
+            })
+            .collect();
+
+        let mut updated = false;
+        for validation in &validations {
+            let Some(fixed) = &validation.fixed else {
+                continue;
+            };
+            let reason = PersistReason::DatePropertyUpdated {
+                property: validation.property.clone(),
+            };
+            if held_reasons.contains(&reason.kind()) {
+                self.held_persist_reasons.push(reason);
+            } else {
+                front_matter.set_property(&validation.property, Value::String(fixed.clone()));
+                self.persist_reasons.push(reason);
+                updated = true;
+            }
+        }
+
+        if updated {
+            front_matter.set_date_modified_now(operational_timezone);
+        }
+        self.date_property_validations = validations;
+    }
 }
 
 #[cfg(test)]
@@ -300,11 +627,14 @@ mod tests {
     use chrono::TimeZone;
     use chrono::Utc;
     use chrono_tz::Tz;
+    use serde_yaml::Value;
     use tempfile::TempDir;
 
     use super::DateCreatedFixValidation;
     use super::DateValidationIssue;
+    use crate::config::PersistReasonKind;
     use crate::constants::DEFAULT_TIMEZONE;
+    use crate::constants::FORMAT_DATE;
     use crate::frontmatter::FrontMatter;
     use crate::markdown_file::DateValidation;
     use crate::markdown_file::PersistReason;
@@ -572,6 +902,7 @@ mod tests {
                     case.created.as_deref(),
                     &case.file_system.created,
                     DEFAULT_TIMEZONE,
+                    FORMAT_DATE,
                 ),
                 operational_timezone: DEFAULT_TIMEZONE.to_string(),
             };
@@ -583,6 +914,7 @@ mod tests {
                     case.modified.as_deref(),
                     &case.file_system.modified,
                     DEFAULT_TIMEZONE,
+                    FORMAT_DATE,
                 ),
                 operational_timezone: DEFAULT_TIMEZONE.to_string(),
             };
@@ -594,6 +926,7 @@ mod tests {
                 &modified_date_validation,
                 &DateCreatedFixValidation::default(),
                 DEFAULT_TIMEZONE,
+                &[],
             );
 
             test_utils::assert_test_case(
@@ -870,4 +1203,74 @@ mod tests {
             "Should have DateCreatedFixApplied reason"
         );
     }
+
+    #[test]
+    fn test_date_properties_linked_or_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_custom_frontmatter(
+                "published: 2024-02-01\nreviewed: \"[[2024-03-01]]\"\ndue: next week\n".to_string(),
+            )
+            .create(&temp_dir, "test1.md");
+        let properties = ["published", "reviewed", "due", "missing"].map(String::from);
+
+        let load = || {
+            let mut markdown_file = test_utils::get_test_markdown_file(file_path.clone());
+            markdown_file.persist_reasons.clear();
+            markdown_file
+        };
+
+        let mut markdown_file = load();
+        markdown_file.validate_date_properties(&properties, DEFAULT_TIMEZONE, &[]);
+        let issues: Vec<(&str, &DateValidationIssue, Option<&str>)> = markdown_file
+            .date_property_validations
+            .iter()
+            .map(|validation| {
+                (
+                    validation.property.as_str(),
+                    &validation.issue,
+                    validation.fixed.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (
+                    "published",
+                    &DateValidationIssue::InvalidWikilink,
+                    Some("[[2024-02-01]]")
+                ),
+                ("due", &DateValidationIssue::InvalidFormat, None),
+            ]
+        );
+        let front_matter = markdown_file.front_matter.as_ref().unwrap();
+        assert_eq!(
+            front_matter.property("published").and_then(Value::as_str),
+            Some("[[2024-02-01]]")
+        );
+        assert_eq!(
+            markdown_file.persist_reasons,
+            vec![PersistReason::DatePropertyUpdated {
+                property: "published".to_string(),
+            }]
+        );
+
+        let mut held = load();
+        held.validate_date_properties(
+            &properties,
+            DEFAULT_TIMEZONE,
+            &[PersistReasonKind::DatePropertyUpdated],
+        );
+        assert!(held.persist_reasons.is_empty());
+        assert_eq!(held.held_persist_reasons.len(), 1);
+        assert_eq!(
+            held.front_matter
+                .as_ref()
+                .unwrap()
+                .property("published")
+                .and_then(Value::as_str),
+            Some("2024-02-01")
+        );
+    }
 }
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+
+use serde_yaml::Mapping;
+use serde_yaml::Value;
+
+use super::constants::FRONTMATTER_COMMENT_PREFIX;
+use super::constants::FRONTMATTER_KEY_SEPARATOR;
+use super::constants::FRONTMATTER_KEY_VALUE_SEPARATOR;
+use super::constants::FRONTMATTER_LIST_ITEM_PREFIX;
+use super::constants::FRONTMATTER_QUOTES;
+use super::constants::YAML_SECTION_FIRST_LINE_NUMBER;
+use crate::config::DuplicateKeyResolution;
+use crate::constants::NEWLINE;
+
+/// A top level frontmatter key written more than once. Parsing keeps only the last value of an
+/// unknown key, so the earlier ones are lost on the next write, and rejects the whole frontmatter
+/// when the key is one ok reads, like `aliases`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateFrontmatterKey {
+    pub key:          String,
+    /// File line of each occurrence, counting the opening `---` as line 1.
+    pub line_numbers: Vec<usize>,
+    /// The value of each occurrence, parsed on its own.
+    pub values:       Vec<Value>,
+}
+
+impl DuplicateFrontmatterKey {
+    /// The single value `resolution` keeps. Merging combines lists into one list without
+    /// repeats; any other value can't be merged, so the first occurrence wins.
+    pub(crate) fn resolved_value(&self, resolution: DuplicateKeyResolution) -> Value {
+        let first = self.values.first().cloned().unwrap_or(Value::Null);
+        if resolution == DuplicateKeyResolution::KeepFirst
+            || !self
+                .values
+                .iter()
+                .all(|value| value.is_sequence() || value.is_null())
+        {
+            return first;
+        }
+
+        let mut merged: Vec<Value> = Vec::new();
+        for item in self.values.iter().filter_map(Value::as_sequence).flatten() {
+            if !merged.contains(item) {
+                merged.push(item.clone());
+            }
+        }
+        Value::Sequence(merged)
+    }
+}
+
+/// A top level key and the lines `start..end` of the YAML section holding it and its value.
+struct KeyBlock {
+    key:   String,
+    start: usize,
+    end:   usize,
+}
+
+/// Duplicate top level keys in a raw YAML section, in the order they first appear.
+pub(super) fn find_duplicate_keys(yaml: &str) -> Vec<DuplicateFrontmatterKey> {
+    let lines: Vec<&str> = yaml.lines().collect();
+    let blocks = key_blocks(&lines);
+
+    let mut occurrences: HashMap<&str, Vec<&KeyBlock>> = HashMap::new();
+    for block in &blocks {
+        occurrences.entry(&block.key).or_default().push(block);
+    }
+
+    let mut duplicates: Vec<DuplicateFrontmatterKey> = Vec::new();
+    for block in &blocks {
+        let Some(key_blocks) = occurrences.remove(block.key.as_str()) else {
+            continue;
+        };
+        if key_blocks.len() < 2 {
+            continue;
+        }
+
+        duplicates.push(DuplicateFrontmatterKey {
+            key:          block.key.clone(),
+            line_numbers: key_blocks
+                .iter()
+                .map(|key_block| key_block.start + YAML_SECTION_FIRST_LINE_NUMBER)
+                .collect(),
+            values:       key_blocks
+                .iter()
+                .map(|key_block| block_value(&lines[key_block.start..key_block.end]))
+                .collect(),
+        });
+    }
+    duplicates
+}
+
+/// `yaml` with each duplicated key written once, holding the value `resolution` keeps.
+pub(super) fn resolve_duplicate_keys(
+    yaml: &str,
+    duplicates: &[DuplicateFrontmatterKey],
+    resolution: DuplicateKeyResolution,
+) -> String {
+    let lines: Vec<&str> = yaml.lines().collect();
+    let mut written: Vec<&str> = Vec::new();
+    let mut resolved_lines: Vec<String> = Vec::new();
+
+    let mut line_idx = 0;
+    for block in key_blocks(&lines) {
+        resolved_lines.extend(lines[line_idx..block.start].iter().map(ToString::to_string));
+        line_idx = block.end;
+
+        let Some(duplicate) = duplicates
+            .iter()
+            .find(|duplicate| duplicate.key == block.key)
+        else {
+            resolved_lines.extend(
+                lines[block.start..block.end]
+                    .iter()
+                    .map(ToString::to_string),
+            );
+            continue;
+        };
+        if written.contains(&duplicate.key.as_str()) {
+            continue;
+        }
+        written.push(&duplicate.key);
+
+        let mut mapping = Mapping::new();
+        mapping.insert(
+            Value::String(duplicate.key.clone()),
+            duplicate.resolved_value(resolution),
+        );
+        // Serializing a one entry mapping of plain strings and lists can't fail; keeping the
+        // original block is the safe fallback if it somehow does.
+        match serde_yaml::to_string(&mapping) {
+            Ok(serialized) => resolved_lines.extend(serialized.lines().map(ToString::to_string)),
+            Err(_) => {
+                resolved_lines.extend(
+                    lines[block.start..block.end]
+                        .iter()
+                        .map(ToString::to_string),
+                );
+            },
+        }
+    }
+    resolved_lines.extend(lines[line_idx..].iter().map(ToString::to_string));
+
+    resolved_lines.join(&NEWLINE.to_string())
+}
+
+/// Splits the section at each top level key. A block runs until the next top level key, so it
+/// takes in indented lines, unindented `- item` list entries and comments.
+fn key_blocks(lines: &[&str]) -> Vec<KeyBlock> {
+    let mut blocks: Vec<KeyBlock> = Vec::new();
+    for (line_idx, line) in lines.iter().enumerate() {
+        if let Some(key) = top_level_key(line) {
+            if let Some(previous) = blocks.last_mut() {
+                previous.end = line_idx;
+            }
+            blocks.push(KeyBlock {
+                key,
+                start: line_idx,
+                end: lines.len(),
+            });
+        }
+    }
+    blocks
+}
+
+fn top_level_key(line: &str) -> Option<String> {
+    if line.starts_with(char::is_whitespace)
+        || line.starts_with(FRONTMATTER_LIST_ITEM_PREFIX)
+        || line.starts_with(FRONTMATTER_COMMENT_PREFIX)
+    {
+        return None;
+    }
+
+    let key = line
+        .split_once(FRONTMATTER_KEY_VALUE_SEPARATOR)
+        .map(|(key, _)| key)
+        .or_else(|| line.strip_suffix(FRONTMATTER_KEY_SEPARATOR))?
+        .trim()
+        .trim_matches(FRONTMATTER_QUOTES);
+    (!key.is_empty()).then(|| key.to_string())
+}
+
+/// Parses one block by itself. A block that doesn't parse on its own yields `Null`.
+fn block_value(block: &[&str]) -> Value {
+    serde_yaml::from_str::<Mapping>(&block.join(&NEWLINE.to_string()))
+        .ok()
+        .and_then(|mapping| mapping.into_iter().next().map(|(_, value)| value))
+        .unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use serde_yaml::Value;
+    use tempfile::TempDir;
+
+    use super::find_duplicate_keys;
+    use super::resolve_duplicate_keys;
+    use crate::config::DuplicateKeyResolution;
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::constants::FORMAT_DATE;
+    use crate::markdown_file::MarkdownFile;
+    use crate::markdown_file::PersistReason;
+    use crate::test_support::TestFileBuilder;
+
+    fn strings(values: &[&str]) -> Value {
+        Value::Sequence(
+            values
+                .iter()
+                .map(|value| Value::String((*value).to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_duplicate_keys_found_and_resolved() {
+        let yaml =
+            "tags:\n- travel\nstatus: draft\n# notes\ntags: [food, travel]\n\"status\": done";
+
+        let duplicates = find_duplicate_keys(yaml);
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].key, "tags");
+        assert_eq!(duplicates[0].line_numbers, vec![2, 6]);
+        assert_eq!(
+            duplicates[0].values,
+            vec![strings(&["travel"]), strings(&["food", "travel"])]
+        );
+        assert_eq!(duplicates[1].key, "status");
+        assert_eq!(duplicates[1].line_numbers, vec![4, 7]);
+
+        assert_eq!(
+            resolve_duplicate_keys(yaml, &duplicates, DuplicateKeyResolution::KeepFirst),
+            "tags:\n- travel\nstatus: draft"
+        );
+        assert_eq!(
+            resolve_duplicate_keys(yaml, &duplicates, DuplicateKeyResolution::MergeLists),
+            "tags:\n- travel\n- food\nstatus: draft"
+        );
+
+        assert!(find_duplicate_keys("title: one\nnested:\n  title: two").is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_aliases_merged_on_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content("---\naliases:\n- One\ntags: [a]\naliases:\n- Two\n---\nbody")
+            .create(&temp_dir, "note.md");
+
+        let unresolved =
+            MarkdownFile::new(file_path.clone(), DEFAULT_TIMEZONE, FORMAT_DATE, None, &[]).unwrap();
+        assert_eq!(unresolved.duplicate_frontmatter_keys.len(), 1);
+        assert!(
+            unresolved.front_matter.is_none(),
+            "a repeated known field doesn't parse"
+        );
+
+        let merged = MarkdownFile::new(
+            file_path,
+            DEFAULT_TIMEZONE,
+            FORMAT_DATE,
+            Some(DuplicateKeyResolution::MergeLists),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            merged.front_matter.as_ref().unwrap().aliases(),
+            Some(["One".to_string(), "Two".to_string()].as_slice())
+        );
+        assert!(
+            merged
+                .persist_reasons
+                .contains(&PersistReason::DuplicateFrontmatterKeysFixed { count: 1 })
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use super::MarkdownFile;
+use super::constants::ENCRYPTED_BLOCK_CLOSING;
+use super::constants::ENCRYPTED_BLOCK_OPENING;
+
+/// Lines of a note body an encryption plugin such as Meld Encrypt has replaced with
+/// ciphertext, from the line holding `%%🔐` to the line holding `🔐%%`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncryptedBlock {
+    pub start_line: usize,
+    /// The same as `start_line` for a block on one line. A block that never closes runs to
+    /// the end of the note.
+    pub end_line:   usize,
+}
+
+impl EncryptedBlock {
+    pub(crate) const fn contains(&self, line_number: usize) -> bool {
+        self.start_line <= line_number && line_number <= self.end_line
+    }
+}
+
+impl MarkdownFile {
+    /// The encrypted blocks in the note body, in order. Nothing inside one is rewritten - the
+    /// ciphertext only decrypts byte for byte.
+    pub(crate) fn encrypted_blocks(&self) -> Vec<EncryptedBlock> {
+        let mut blocks = Vec::new();
+        let mut open_block: Option<usize> = None;
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            let line_number = self.get_real_line_number(line_idx);
+            let mut rest = line;
+            loop {
+                if let Some(start_line) = open_block {
+                    let Some(closing) = rest.find(ENCRYPTED_BLOCK_CLOSING) else {
+                        break;
+                    };
+                    blocks.push(EncryptedBlock {
+                        start_line,
+                        end_line: line_number,
+                    });
+                    open_block = None;
+                    rest = &rest[closing + ENCRYPTED_BLOCK_CLOSING.len()..];
+                } else {
+                    let Some(opening) = rest.find(ENCRYPTED_BLOCK_OPENING) else {
+                        break;
+                    };
+                    open_block = Some(line_number);
+                    rest = &rest[opening + ENCRYPTED_BLOCK_OPENING.len()..];
+                }
+            }
+        }
+
+        if let Some(start_line) = open_block {
+            blocks.push(EncryptedBlock {
+                start_line,
+                end_line: self.get_real_line_number(self.content.lines().count().saturating_sub(1)),
+            });
+        }
+        // Blocks sharing a line cover the same lines.
+        blocks.dedup();
+        blocks
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::EncryptedBlock;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_encrypted_blocks_span_markers() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "Wifi: %%🔐β aGVsbG8gd29ybGQ= 🔐%% and %%🔐β c2Vjb25k 🔐%%\n\
+                 plain text\n\
+                 %%🔐α bXVsdGk=\n\
+                 bGluZQ==\n\
+                 🔐%%\n\
+                 more text\n\
+                 %%🔐β dW5jbG9zZWQ=\n\
+                 tail"
+                    .to_string(),
+            )
+            .create(&temp_dir, "Passwords.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+        let first = markdown_file.frontmatter_line_count + 1;
+
+        let block = |start: usize, end: usize| EncryptedBlock {
+            start_line: first + start,
+            end_line:   first + end,
+        };
+        assert_eq!(
+            markdown_file.encrypted_blocks(),
+            vec![block(0, 0), block(2, 4), block(6, 7)]
+        );
+    }
+}
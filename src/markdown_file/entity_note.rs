@@ -0,0 +1,58 @@
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::MarkdownFile;
+use super::constants::LIST_ITEM_PREFIX;
+use crate::constants::FRONTMATTER_MISSING_AFTER_ENSURE;
+use crate::constants::NEWLINE;
+use crate::wikilink::ToWikilink;
+
+impl MarkdownFile {
+    pub(crate) fn add_entity_alias(
+        &mut self,
+        alias: &str,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.add_alias(alias.to_string());
+
+        self.mark_entity_alias_added(alias, operational_timezone)
+    }
+
+    /// Appends a `- [[note]]` list item for each of `note_names` to the end of the body -
+    /// continuing a list the body ends with, otherwise below a blank line.
+    pub(crate) fn append_entity_index_links(
+        &mut self,
+        note_names: &[String],
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        if note_names.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<String> = note_names
+            .iter()
+            .map(|note_name| format!("{LIST_ITEM_PREFIX}{}", note_name.to_wikilink()))
+            .collect();
+        let existing = self.content.trim_end();
+        let separator = match existing.lines().last() {
+            None => String::new(),
+            Some(last_line) if last_line.trim_start().starts_with(LIST_ITEM_PREFIX) => {
+                NEWLINE.to_string()
+            },
+            Some(_) => format!("{NEWLINE}{NEWLINE}"),
+        };
+        self.content = format!(
+            "{existing}{separator}{}{NEWLINE}",
+            items.join(&NEWLINE.to_string())
+        );
+        self.wikilinks = self.process_wikilinks();
+
+        self.mark_entity_index_links_added(note_names.len(), operational_timezone)
+    }
+}
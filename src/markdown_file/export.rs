@@ -0,0 +1,286 @@
+use regex::Captures;
+use regex::Regex;
+
+use super::MarkdownFile;
+use super::constants::EXPORT_CALLOUT_TITLE_EMPHASIS;
+use super::image_link::ImageLink;
+use super::image_link::ImageLinkTarget;
+use super::image_link::ImageLinkType;
+use super::table_of_contents;
+use super::text_excluder;
+use super::text_excluder::CodeBlockExcluder;
+use super::text_excluder::CommentExcluder;
+use crate::constants::BLOCK_REFERENCE_PREFIX;
+use crate::constants::CLOSING_PAREN;
+use crate::constants::IMAGE_LINK_PREFIX;
+use crate::constants::MARKDOWN_LINK_SEPARATOR;
+use crate::constants::NEWLINE;
+use crate::support::BLOCK_ID_REGEX;
+use crate::support::CALLOUT_HEADER_REGEX;
+use crate::support::IMAGE_REGEX;
+use crate::support::NOTE_EMBED_REGEX;
+use crate::wikilink;
+use crate::wikilink::Wikilink;
+
+/// Something in a note that an export replaces rather than writing as it stands.
+#[derive(Clone, Copy)]
+pub(crate) enum ExportReference<'a> {
+    /// An image embed or link, by the file name it was written with.
+    Image(&'a str),
+    /// `![[Note]]`, `![[Note#Heading]]` or `![[Note#^block-id]]`.
+    Embed {
+        target:  &'a str,
+        section: Option<&'a str>,
+    },
+    /// A wikilink that isn't an embed.
+    Link(&'a Wikilink),
+}
+
+/// What `export_content` made of a note.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ExportedText {
+    pub text:     String,
+    /// The line number of each line a redaction pattern matched, and the pattern.
+    pub withheld: Vec<(usize, String)>,
+}
+
+impl MarkdownFile {
+    /// The note's body, or only `section` of it, as pandoc reads it: comments, block ids and
+    /// callout markers are removed, and each image, embed and wikilink becomes whatever
+    /// `resolve` returns for it. Code blocks are kept as written. A line matching any of
+    /// `redact_patterns` is withheld, code block or not.
+    pub(crate) fn export_content(
+        &self,
+        section: Option<&str>,
+        convert_callouts: bool,
+        redact_patterns: &[Regex],
+        resolve: &mut dyn FnMut(ExportReference<'_>) -> String,
+    ) -> ExportedText {
+        let lines: Vec<&str> = self.content.lines().collect();
+        let Some((start, end)) = section.map_or(Some((0, lines.len())), |section| {
+            section_lines(&lines, section)
+        }) else {
+            return ExportedText::default();
+        };
+
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut comment_excluder = CommentExcluder::new();
+        let mut exported = Vec::new();
+        let mut withheld = Vec::new();
+        for (line_idx, line) in lines.iter().enumerate().take(end).skip(start) {
+            code_block_excluder.update(line);
+            if let Some(pattern) = redact_patterns
+                .iter()
+                .find(|pattern| pattern.is_match(line))
+            {
+                withheld.push((
+                    self.get_real_line_number(line_idx),
+                    pattern.as_str().to_string(),
+                ));
+                continue;
+            }
+            if code_block_excluder.is_in_code_block() {
+                exported.push((*line).to_string());
+                continue;
+            }
+
+            let comment_spans = comment_excluder.excluded_spans(line);
+            let visible_line = text_excluder::remove_spans(line, &comment_spans);
+            if !comment_spans.is_empty() && visible_line.trim().is_empty() {
+                continue;
+            }
+            if let Some(exported_line) = export_line(&visible_line, convert_callouts, resolve) {
+                exported.push(exported_line);
+            }
+        }
+
+        ExportedText {
+            text: exported.join(&NEWLINE.to_string()).trim().to_string(),
+            withheld,
+        }
+    }
+}
+
+/// Lines `start..end` of the heading named `section` and the headings nested in it, or of the
+/// single line ending in `section` when it is a `^block-id`.
+fn section_lines(lines: &[&str], section: &str) -> Option<(usize, usize)> {
+    if section.starts_with(BLOCK_REFERENCE_PREFIX) {
+        return lines
+            .iter()
+            .position(|line| {
+                line.trim_end()
+                    .strip_suffix(section)
+                    .is_some_and(|before| before.ends_with(char::is_whitespace))
+            })
+            .map(|line_idx| (line_idx, line_idx + 1));
+    }
+
+    let headings = table_of_contents::headings(lines);
+    let wanted = table_of_contents::heading_link_target(section).to_lowercase();
+    let heading_idx = headings.iter().position(|&(_, _, text)| {
+        table_of_contents::heading_link_target(text).to_lowercase() == wanted
+    })?;
+    let (start, level, _) = headings[heading_idx];
+    let end = headings[heading_idx + 1..]
+        .iter()
+        .find(|&&(_, next_level, _)| next_level <= level)
+        .map_or(lines.len(), |&(next_idx, ..)| next_idx);
+    Some((start, end))
+}
+
+/// One line outside code blocks, or `None` when nothing is left of it. Embeds go last, as
+/// what replaces them is already exported.
+fn export_line(
+    line: &str,
+    convert_callouts: bool,
+    resolve: &mut dyn FnMut(ExportReference<'_>) -> String,
+) -> Option<String> {
+    let line = IMAGE_REGEX.replace_all(line, |captures: &Captures| {
+        export_image(&captures[0], resolve)
+    });
+    let line = export_wikilinks(&line, resolve);
+    let line = BLOCK_ID_REGEX.replace(&line, "");
+
+    let line = match CALLOUT_HEADER_REGEX.captures(&line) {
+        Some(captures) => {
+            let quote = &captures[1];
+            let title = captures[3].trim();
+            if convert_callouts {
+                let title = if title.is_empty() {
+                    capitalized(&captures[2])
+                } else {
+                    title.to_string()
+                };
+                format!(
+                    "{quote}{EXPORT_CALLOUT_TITLE_EMPHASIS}{title}{EXPORT_CALLOUT_TITLE_EMPHASIS}"
+                )
+            } else if title.is_empty() {
+                return None;
+            } else {
+                format!("{quote}{title}")
+            }
+        },
+        None => line.into_owned(),
+    };
+
+    Some(
+        NOTE_EMBED_REGEX
+            .replace_all(&line, |captures: &Captures| {
+                resolve(ExportReference::Embed {
+                    target:  captures[1].trim(),
+                    section: captures.get(2).map(|section| section.as_str().trim()),
+                })
+            })
+            .into_owned(),
+    )
+}
+
+/// `![alt](path)` with the path `resolve` gives - external images are left as they are.
+fn export_image(
+    matched_text: &str,
+    resolve: &mut dyn FnMut(ExportReference<'_>) -> String,
+) -> String {
+    match ImageLink::new(matched_text.to_string(), 0, 0) {
+        Ok(image_link)
+            if !matches!(
+                image_link.link_type,
+                ImageLinkType::Markdown(ImageLinkTarget::External, _)
+            ) =>
+        {
+            let path = resolve(ExportReference::Image(&image_link.filename));
+            format!(
+                "{IMAGE_LINK_PREFIX}{}{MARKDOWN_LINK_SEPARATOR}{path}{CLOSING_PAREN}",
+                image_link.alt_text
+            )
+        },
+        _ => matched_text.to_string(),
+    }
+}
+
+fn export_wikilinks(line: &str, resolve: &mut dyn FnMut(ExportReference<'_>) -> String) -> String {
+    let mut exported = line.to_string();
+    for spanned_wikilink in wikilink::extract_wikilinks(line).valid.iter().rev() {
+        let (start, end) = spanned_wikilink.span;
+        exported.replace_range(
+            start..end,
+            &resolve(ExportReference::Link(&spanned_wikilink.wikilink)),
+        );
+    }
+    exported
+}
+
+/// `note` as Obsidian titles a callout that has no title of its own.
+fn capitalized(callout_type: &str) -> String {
+    let mut characters = callout_type.trim().chars();
+    characters.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(characters).collect()
+    })
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use super::ExportReference;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_export_content_strips_obsidian_syntax() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "Intro %%draft%% about [[Tokyo|the capital]] ^intro\n\
+                 %%\nhidden\n%%\n\
+                 > [!tip]+ Remember\n> the train\n\
+                 ![[map.png|300]]\n\
+                 ```\n[[kept]] %%kept%%\n```\n\
+                 ## Section\n![[Trip#Days]]"
+                    .to_string(),
+            )
+            .create(&temp_dir, "Note.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let mut resolve = |reference: ExportReference<'_>| match reference {
+            ExportReference::Image(filename) => format!("images/{filename}"),
+            ExportReference::Embed { target, section } => {
+                format!("<{target}:{}>", section.unwrap_or_default())
+            },
+            ExportReference::Link(wikilink) => wikilink.display_text.clone(),
+        };
+
+        assert_eq!(
+            markdown_file
+                .export_content(None, false, &[], &mut resolve)
+                .text,
+            "Intro  about the capital\n> Remember\n> the train\n![](images/map.png)\n```\n[[kept]] \
+             %%kept%%\n```\n## Section\n<Trip:Days>"
+        );
+        assert_eq!(
+            markdown_file
+                .export_content(Some("section"), true, &[], &mut resolve)
+                .text,
+            "## Section\n<Trip:Days>"
+        );
+        assert_eq!(
+            markdown_file
+                .export_content(Some("^intro"), true, &[], &mut resolve)
+                .text,
+            "Intro  about the capital"
+        );
+
+        let redacted = markdown_file.export_content(
+            None,
+            false,
+            &[Regex::new(r"\[\[kept\]\] %+").unwrap()],
+            &mut resolve,
+        );
+        assert!(!redacted.text.contains("kept"));
+        assert_eq!(redacted.withheld.len(), 1);
+    }
+}
@@ -0,0 +1,167 @@
+use anyhow::Result as AnyhowResult;
+
+use super::MarkdownFile;
+use super::constants::FLASHCARD_ID_HASH_LENGTH;
+use super::constants::FLASHCARD_ID_PREFIX;
+use super::text_excluder::CodeBlockExcluder;
+use crate::constants::BLOCK_REFERENCE_PREFIX;
+use crate::constants::NEWLINE;
+use crate::sha256_cache;
+use crate::support::FLASHCARD_ID_REGEX;
+
+/// A question and answer pair from a note, with the id Anki knows it by.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Flashcard {
+    pub id:       String,
+    pub note:     String,
+    pub question: String,
+    pub answer:   String,
+}
+
+impl MarkdownFile {
+    /// Every line starting with `question_prefix` that is directly followed by one starting
+    /// with `answer_prefix`, outside code blocks. A card without an id gets one, written to the
+    /// end of its answer line as a `^card-` block id, so it keeps the id when its text is edited
+    /// - `id_seed` tells apart cards with the same text in different notes.
+    pub(crate) fn collect_flashcards(
+        &mut self,
+        question_prefix: &str,
+        answer_prefix: &str,
+        id_seed: &str,
+        operational_timezone: &str,
+    ) -> AnyhowResult<Vec<Flashcard>> {
+        let note = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut lines: Vec<String> = self.content.lines().map(str::to_string).collect();
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut question: Option<String> = None;
+        let mut flashcards = Vec::new();
+        let mut added = 0;
+
+        for line in &mut lines {
+            code_block_excluder.update(line);
+            let trimmed = line.trim();
+            let pending_question = question.take();
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+            if let Some(text) = trimmed.strip_prefix(question_prefix) {
+                question = Some(text.trim().to_string());
+                continue;
+            }
+            let (Some(question), Some(answer)) =
+                (pending_question, trimmed.strip_prefix(answer_prefix))
+            else {
+                continue;
+            };
+
+            let (id, answer, is_new) = FLASHCARD_ID_REGEX.captures(answer).map_or_else(
+                || {
+                    (
+                        new_flashcard_id(id_seed, &question, answer),
+                        answer.trim().to_string(),
+                        true,
+                    )
+                },
+                |captures| {
+                    (
+                        captures[1].to_string(),
+                        answer[..captures.get(0).map_or(answer.len(), |id| id.start())]
+                            .trim()
+                            .to_string(),
+                        false,
+                    )
+                },
+            );
+            if question.is_empty() || answer.is_empty() {
+                continue;
+            }
+            if is_new {
+                *line = format!("{} {BLOCK_REFERENCE_PREFIX}{id}", line.trim_end());
+                added += 1;
+            }
+            flashcards.push(Flashcard {
+                id,
+                note: note.clone(),
+                question,
+                answer,
+            });
+        }
+
+        if added > 0 {
+            let mut updated_content = lines.join(&NEWLINE.to_string());
+            if self.content.ends_with(NEWLINE) {
+                updated_content.push(NEWLINE);
+            }
+            self.content = updated_content;
+            self.mark_flashcard_ids_added(added, operational_timezone)?;
+        }
+
+        Ok(flashcards)
+    }
+}
+
+fn new_flashcard_id(id_seed: &str, question: &str, answer: &str) -> String {
+    let hash = sha256_cache::hash_contents(
+        format!("{id_seed}{NEWLINE}{question}{NEWLINE}{}", answer.trim()).as_bytes(),
+    );
+    format!("{FLASHCARD_ID_PREFIX}{}", &hash[..FLASHCARD_ID_HASH_LENGTH])
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::markdown_file::PersistReason;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_collect_flashcards_adds_ids_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "Q:: Capital of Japan?\nA:: Tokyo\n\nQ:: Kept?\nA:: Yes ^card-0123456789ab\n\
+                 ```\nQ:: In code\nA:: Skipped\n```\nQ:: No answer\n\nA:: Orphan\n"
+                    .to_string(),
+            )
+            .create(&temp_dir, "Geography.md");
+        let mut markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let flashcards = markdown_file
+            .collect_flashcards("Q::", "A::", "Geography.md", DEFAULT_TIMEZONE)
+            .unwrap();
+        assert_eq!(flashcards.len(), 2);
+        assert_eq!(flashcards[0].question, "Capital of Japan?");
+        assert_eq!(flashcards[0].answer, "Tokyo");
+        assert_eq!(flashcards[0].note, "Geography");
+        assert!(flashcards[0].id.starts_with("card-"));
+        assert_eq!(flashcards[1].id, "card-0123456789ab");
+        assert_eq!(flashcards[1].answer, "Yes");
+        assert!(
+            markdown_file
+                .content
+                .contains(&format!("A:: Tokyo ^{}\n", flashcards[0].id))
+        );
+        assert!(
+            markdown_file
+                .persist_reasons
+                .contains(&PersistReason::FlashcardIdsAdded { count: 1 })
+        );
+
+        let persist_reason_count = markdown_file.persist_reasons.len();
+        let again = markdown_file
+            .collect_flashcards("Q::", "A::", "Geography.md", DEFAULT_TIMEZONE)
+            .unwrap();
+        assert_eq!(again, flashcards);
+        assert_eq!(markdown_file.persist_reasons.len(), persist_reason_count);
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use super::MarkdownFile;
+use super::canonical_link;
+use crate::wikilink;
+use crate::wikilink::Wikilink;
+
+/// One wikilink found in the value of a configured frontmatter property, such as
+/// `up: "[[Index]]"` or an entry of a `related` list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrontmatterWikilink {
+    pub property: String,
+    pub wikilink: Wikilink,
+}
+
+impl MarkdownFile {
+    /// Extracts wikilinks from the values of `properties` and adds them to `wikilinks.valid`
+    /// so they become back-populate targets and count as references like content links.
+    pub(crate) fn index_frontmatter_wikilinks(&mut self, properties: &[String]) {
+        let Some(front_matter) = &self.front_matter else {
+            return;
+        };
+
+        let frontmatter_wikilinks: Vec<FrontmatterWikilink> = properties
+            .iter()
+            .flat_map(|property| {
+                front_matter
+                    .property_strings(property)
+                    .into_iter()
+                    .flat_map(|value| wikilink::extract_wikilinks(value).valid)
+                    .map(|spanned_wikilink| FrontmatterWikilink {
+                        property: property.clone(),
+                        wikilink: spanned_wikilink.wikilink,
+                    })
+            })
+            .collect();
+
+        self.wikilinks.valid.extend(
+            frontmatter_wikilinks
+                .iter()
+                .map(|frontmatter_wikilink| frontmatter_wikilink.wikilink.clone()),
+        );
+        self.frontmatter_wikilinks = frontmatter_wikilinks;
+    }
+
+    /// Rewrites frontmatter property links through `canonical_targets` the same way
+    /// `find_canonical_link_matches` rewrites content links, returning how many values
+    /// changed. Frontmatter is re-serialized on persist, so values are rewritten in place
+    /// rather than through `ReplaceableContent`.
+    pub(crate) fn canonicalize_frontmatter_wikilinks(
+        &mut self,
+        properties: &[String],
+        canonical_targets: &HashMap<String, String>,
+    ) -> usize {
+        let Some(front_matter) = self.front_matter.as_mut() else {
+            return 0;
+        };
+
+        let rewrite = |value: &str| {
+            let mut rewritten = value.to_string();
+            // Reverse order keeps earlier spans valid while later ones are replaced.
+            for spanned_wikilink in wikilink::extract_wikilinks(value).valid.into_iter().rev() {
+                let Some(canonical_target) =
+                    canonical_targets.get(&spanned_wikilink.wikilink.target.to_lowercase())
+                else {
+                    continue;
+                };
+                let (start, end) = spanned_wikilink.span;
+                let replacement = canonical_link::canonical_replacement(
+                    &spanned_wikilink.wikilink,
+                    canonical_target,
+                );
+                if replacement != value[start..end] {
+                    rewritten.replace_range(start..end, &replacement);
+                }
+            }
+            (rewritten != value).then_some(rewritten)
+        };
+
+        let rewritten_count = properties
+            .iter()
+            .map(|property| front_matter.rewrite_property_strings(property, rewrite))
+            .sum();
+
+        if rewritten_count > 0 {
+            for frontmatter_wikilink in &mut self.frontmatter_wikilinks {
+                if let Some(canonical_target) =
+                    canonical_targets.get(&frontmatter_wikilink.wikilink.target.to_lowercase())
+                {
+                    frontmatter_wikilink
+                        .wikilink
+                        .target
+                        .clone_from(canonical_target);
+                }
+            }
+        }
+
+        rewritten_count
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_frontmatter_wikilinks_indexed_and_canonicalized() {
+        let (temp_dir, _, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_custom_frontmatter(
+                "up: \"[[topics/Index]]\"\nrelated:\n  - \"[[linkedin|my profile]]\"\n  - plain text\nignored: \"[[Other]]\"\n"
+                    .to_string(),
+            )
+            .with_content("body")
+            .create(&temp_dir, "note.md");
+        let mut markdown_file = test_support::get_test_markdown_file(file_path);
+        let properties = vec!["up".to_string(), "related".to_string()];
+
+        markdown_file.index_frontmatter_wikilinks(&properties);
+
+        let targets: Vec<&str> = markdown_file
+            .frontmatter_wikilinks
+            .iter()
+            .map(|frontmatter_wikilink| frontmatter_wikilink.wikilink.target.as_str())
+            .collect();
+        assert_eq!(targets, vec!["topics/Index", "linkedin"]);
+
+        let canonical_targets = HashMap::from([
+            ("topics/index".to_string(), "Index".to_string()),
+            ("linkedin".to_string(), "LinkedIn".to_string()),
+        ]);
+        let rewritten =
+            markdown_file.canonicalize_frontmatter_wikilinks(&properties, &canonical_targets);
+
+        assert_eq!(rewritten, 2);
+        let front_matter = markdown_file.front_matter.as_ref().unwrap();
+        assert_eq!(front_matter.property_strings("up"), vec!["[[Index]]"]);
+        assert_eq!(
+            front_matter.property_strings("related"),
+            vec!["[[LinkedIn|my profile]]", "plain text"]
+        );
+        assert_eq!(front_matter.property_strings("ignored"), vec!["[[Other]]"]);
+    }
+}
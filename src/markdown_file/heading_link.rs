@@ -0,0 +1,237 @@
+use super::MarkdownFile;
+use super::constants::HEADING_LINK_REPAIR_MIN_SIMILARITY;
+use super::replaceable_content::MatchType;
+use super::replaceable_content::ReplaceableContent;
+use super::table_of_contents;
+use crate::constants::BLOCK_REFERENCE_PREFIX;
+use crate::constants::HASH;
+use crate::support;
+use crate::validated_config::ValidatedConfig;
+
+/// A `[[note#heading]]` link whose heading was renamed, and the link to the target's current
+/// heading it becomes.
+#[derive(Clone, Debug)]
+pub struct HeadingLinkRepairMatch {
+    pub found_text:    String,
+    pub line_number:   usize,
+    pub position:      usize,
+    pub relative_path: String,
+    pub replacement:   String,
+}
+
+impl ReplaceableContent for HeadingLinkRepairMatch {
+    fn line_number(&self) -> usize { self.line_number }
+
+    fn position(&self) -> usize { self.position }
+
+    fn get_replacement(&self) -> String { self.replacement.clone() }
+
+    fn matched_text(&self) -> String { self.found_text.clone() }
+
+    fn match_type(&self) -> MatchType { MatchType::HeadingLinkRepair }
+}
+
+/// A `[[note#heading]]` link whose heading matches none of the target's headings closely
+/// enough to repair. `candidates` holds the headings it is equally close to, if any.
+#[derive(Clone, Debug)]
+pub struct StaleHeadingLink {
+    pub candidates:    Vec<String>,
+    pub found_text:    String,
+    pub line_number:   usize,
+    pub relative_path: String,
+}
+
+/// What a link's heading resolves to among its target's headings.
+#[derive(Debug, PartialEq, Eq)]
+enum HeadingResolution {
+    Current,
+    Repaired(String),
+    Stale(Vec<String>),
+}
+
+impl MarkdownFile {
+    /// The note's headings as a `[[note#heading]]` link writes them.
+    pub(crate) fn heading_link_targets(&self) -> Vec<String> {
+        let lines: Vec<&str> = self.content.lines().collect();
+        table_of_contents::headings(&lines)
+            .into_iter()
+            .map(|(_, _, heading)| table_of_contents::heading_link_target(heading))
+            .collect()
+    }
+
+    /// Checks the heading of every content `[[note#heading]]` link against the headings
+    /// `headings_of` returns for its note part - empty for a `[[#heading]]` link to this note.
+    /// A heading that differs from exactly one current heading only in case and punctuation,
+    /// or that is close to exactly one, is repaired; any other missing heading is stale. Links
+    /// to block ids, and to notes `headings_of` doesn't know, are left alone.
+    pub(crate) fn find_heading_link_repairs<'a>(
+        &self,
+        headings_of: impl Fn(&str) -> Option<&'a [String]>,
+        validated_config: &ValidatedConfig,
+    ) -> (Vec<HeadingLinkRepairMatch>, Vec<StaleHeadingLink>) {
+        let relative_path =
+            support::format_relative_path(&self.path, validated_config.obsidian_path());
+        let mut repairs = Vec::new();
+        let mut stale = Vec::new();
+
+        self.for_each_content_wikilink(|line_number, line, spanned_wikilink| {
+            let target = &spanned_wikilink.wikilink.target;
+            // Nested `[[note#section#subsection]]` links are checked by their last heading.
+            let (Some((note, _)), Some((before_heading, heading))) =
+                (target.split_once(HASH), target.rsplit_once(HASH))
+            else {
+                return;
+            };
+            if heading.trim().is_empty() || heading.starts_with(BLOCK_REFERENCE_PREFIX) {
+                return;
+            }
+            let Some(headings) = headings_of(note.trim()) else {
+                return;
+            };
+
+            let (start, end) = spanned_wikilink.span;
+            let found_text = line[start..end].to_string();
+            match resolve_heading(heading, headings) {
+                HeadingResolution::Current => {},
+                HeadingResolution::Repaired(current) => {
+                    let new_target = format!("{before_heading}{HASH}{current}");
+                    let replacement = found_text.replacen(target.as_str(), &new_target, 1);
+                    repairs.push(HeadingLinkRepairMatch {
+                        found_text,
+                        line_number,
+                        position: start,
+                        relative_path: relative_path.clone(),
+                        replacement,
+                    });
+                },
+                HeadingResolution::Stale(candidates) => stale.push(StaleHeadingLink {
+                    candidates,
+                    found_text,
+                    line_number,
+                    relative_path: relative_path.clone(),
+                }),
+            }
+        });
+
+        (repairs, stale)
+    }
+}
+
+/// Obsidian matches a heading regardless of case, so only a heading that matches none that
+/// way needs a repair.
+fn resolve_heading(heading: &str, headings: &[String]) -> HeadingResolution {
+    let link_heading = table_of_contents::heading_link_target(heading);
+    if headings
+        .iter()
+        .any(|current| current.eq_ignore_ascii_case(&link_heading))
+    {
+        return HeadingResolution::Current;
+    }
+
+    let normalized = normalize_heading(heading);
+    let same_words = distinct(
+        headings
+            .iter()
+            .filter(|current| normalize_heading(current) == normalized),
+    );
+    if let [current] = same_words.as_slice() {
+        return HeadingResolution::Repaired(current.clone());
+    }
+    if !same_words.is_empty() {
+        return HeadingResolution::Stale(same_words);
+    }
+
+    // `Budget 2025` is close to `Budget 2024` but isn't the same heading.
+    let numbers = heading_numbers(&normalized);
+    let close = distinct(headings.iter().filter(|current| {
+        let current = normalize_heading(current);
+        heading_numbers(&current) == numbers
+            && strsim::normalized_levenshtein(&current, &normalized)
+                >= HEADING_LINK_REPAIR_MIN_SIMILARITY
+    }));
+    match close.as_slice() {
+        [current] => HeadingResolution::Repaired(current.clone()),
+        _ => HeadingResolution::Stale(close),
+    }
+}
+
+/// Lowercase words with punctuation and extra spacing dropped - `Day 1: Paris!` becomes
+/// `day 1 paris`.
+fn normalize_heading(heading: &str) -> String {
+    heading
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The numbers of a normalized heading, in order.
+fn heading_numbers(normalized: &str) -> Vec<&str> {
+    normalized
+        .split(' ')
+        .filter(|word| word.chars().all(|c| c.is_ascii_digit()))
+        .collect()
+}
+
+/// A heading repeated in a note still names one place to link to.
+fn distinct<'a>(headings: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for heading in headings {
+        if !unique.contains(heading) {
+            unique.push(heading.clone());
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use super::HeadingResolution;
+    use super::resolve_heading;
+
+    #[test]
+    fn test_resolve_heading() {
+        let headings: Vec<String> = [
+            "Day 1 Paris",
+            "Packing List",
+            "Budget 2024",
+            "Notes A",
+            "Notes B",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        assert_eq!(
+            resolve_heading("packing list", &headings),
+            HeadingResolution::Current
+        );
+        assert_eq!(
+            resolve_heading("Day 1 - Paris", &headings),
+            HeadingResolution::Repaired("Day 1 Paris".to_string())
+        );
+        assert_eq!(
+            resolve_heading("Packing Lists", &headings),
+            HeadingResolution::Repaired("Packing List".to_string())
+        );
+        assert_eq!(
+            resolve_heading("Itinerary", &headings),
+            HeadingResolution::Stale(Vec::new())
+        );
+        assert_eq!(
+            resolve_heading("Budget 2025", &headings),
+            HeadingResolution::Stale(Vec::new()),
+            "a heading with other numbers is another heading"
+        );
+        assert_eq!(
+            resolve_heading("Notes C", &headings),
+            HeadingResolution::Stale(vec!["Notes A".to_string(), "Notes B".to_string()]),
+            "a heading as close to two others isn't a confident match"
+        );
+    }
+}
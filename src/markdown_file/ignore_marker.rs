@@ -0,0 +1,122 @@
+use std::collections::BTreeSet;
+
+use super::MarkdownFile;
+use super::constants::IGNORE_LINE_MARKER;
+use super::constants::IGNORE_NEXT_MARKER;
+
+impl MarkdownFile {
+    /// The lines an author has marked for ok to leave alone: a line ending in
+    /// `IGNORE_LINE_MARKER`, and the block after an `IGNORE_NEXT_MARKER` line, up to the next
+    /// blank line. Back populate skips them and their image references aren't rewritten.
+    pub(crate) fn ignored_lines(&self) -> BTreeSet<usize> {
+        let mut ignored = BTreeSet::new();
+        let mut in_ignored_block = false;
+        let mut block_started = false;
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed == IGNORE_NEXT_MARKER {
+                in_ignored_block = true;
+                block_started = false;
+                continue;
+            }
+            if in_ignored_block {
+                if trimmed.is_empty() {
+                    // Blank lines between the marker and its block don't end it.
+                    in_ignored_block = !block_started;
+                } else {
+                    block_started = true;
+                    ignored.insert(self.get_real_line_number(line_idx));
+                }
+            }
+            if trimmed.ends_with(IGNORE_LINE_MARKER) {
+                ignored.insert(self.get_real_line_number(line_idx));
+            }
+        }
+        ignored
+    }
+
+    /// The text of `ignored_lines` as the body holds it now.
+    pub(crate) fn ignored_line_texts(&self) -> Vec<&str> {
+        let ignored = self.ignored_lines();
+        self.content
+            .lines()
+            .enumerate()
+            .filter(|(line_idx, _)| ignored.contains(&self.get_real_line_number(*line_idx)))
+            .map(|(_, line)| line)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support as test_utils;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_ignore_markers_leave_lines_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_date = test_utils::eastern_midnight(2024, 1, 15);
+        TestFileBuilder::new()
+            .with_content("# Other\n".to_string())
+            .with_matching_dates(test_date)
+            .with_file_system_dates(test_date, test_date)
+            .create(&temp_dir, "Other.md");
+        let content = "Other here\n\
+                       Other kept ![[gone.png]] %%ok:ignore%%\n\
+                       <!-- ok:ignore-next -->\n\
+                       \n\
+                       Other in the block\n\
+                       ![[gone.png]]\n\
+                       \n\
+                       Other after\n";
+        let note_path = TestFileBuilder::new()
+            .with_content(content.to_string())
+            .with_matching_dates(test_date)
+            .with_file_system_dates(test_date, test_date)
+            .create(&temp_dir, "Note.md");
+
+        let validated_config = test_utils::get_test_validated_config(&temp_dir, None);
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let note = repository
+            .markdown_files
+            .iter()
+            .find(|markdown_file| markdown_file.path == note_path)
+            .unwrap();
+
+        let offset = note.frontmatter_line_count;
+        assert_eq!(
+            note.ignored_lines(),
+            BTreeSet::from([offset + 2, offset + 5, offset + 6])
+        );
+        assert_eq!(
+            note.ignored_line_texts(),
+            [
+                "Other kept ![[gone.png]] %%ok:ignore%%",
+                "Other in the block",
+                "![[gone.png]]"
+            ]
+        );
+        assert_eq!(
+            note.content,
+            "[[Other]] here\n\
+             Other kept ![[gone.png]] %%ok:ignore%%\n\
+             <!-- ok:ignore-next -->\n\
+             \n\
+             Other in the block\n\
+             ![[gone.png]]\n\
+             \n\
+             [[Other]] after",
+            "marked lines keep their text and their image references"
+        );
+    }
+}
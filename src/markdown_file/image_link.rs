@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::path::PathBuf;
 
@@ -7,22 +8,22 @@ use derive_more::IntoIterator;
 
 use super::constants::HTTP_URL_PREFIX;
 use super::constants::HTTPS_URL_PREFIX;
-use super::constants::IMAGE_LINK_SIZE_PARAMETER_INDEX;
 use super::constants::INVALID_IMAGE_LINK_FORMAT_PREFIX;
+use super::constants::MARKDOWN_TITLE_QUOTES;
 use super::replaceable_content::MatchType;
 use super::replaceable_content::ReplaceableContent;
 use crate::constants::BACKSLASH;
 use crate::constants::CLOSING_PAREN;
 use crate::constants::CLOSING_WIKILINK;
-use crate::constants::DEFAULT_MEDIA_PATH;
 use crate::constants::FORWARD_SLASH;
 use crate::constants::IMAGE_EMBED_MARKER;
-use crate::constants::IMAGE_LINK_PREFIX;
 use crate::constants::MARKDOWN_LINK_SEPARATOR;
+use crate::constants::MARKDOWN_LINK_SPACE;
 use crate::constants::OPENING_BRACKET;
 use crate::constants::OPENING_PAREN;
 use crate::constants::OPENING_WIKILINK;
 use crate::constants::PIPE;
+use crate::constants::SPACE;
 use crate::image_file::IncompatibilityReason;
 use crate::support::EnumFilter;
 
@@ -59,15 +60,19 @@ pub enum ImageLinkState {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ImageLink {
-    pub matched_text:   String,
-    pub position:       usize,
-    pub line_number:    usize,
-    pub filename:       String,
-    pub relative_path:  String,
-    pub alt_text:       String,
-    pub size_parameter: Option<String>,
-    pub state:          ImageLinkState,
-    pub link_type:      ImageLinkType,
+    pub matched_text:  String,
+    pub position:      usize,
+    pub line_number:   usize,
+    pub filename:      String,
+    pub relative_path: String,
+    pub alt_text:      String,
+    /// Everything after the target, kept when the target changes: a wikilink's `|400` or
+    /// `|alt|400`, or a markdown link's ` "title"`.
+    pub target_suffix: String,
+    pub state:         ImageLinkState,
+    pub link_type:     ImageLinkType,
+    /// Size of the file it points at - set once the vault's image files are loaded.
+    pub file_size:     Option<u64>,
 }
 
 impl ImageLink {
@@ -89,9 +94,10 @@ impl ImageLink {
             filename: parsed_link.filename,
             relative_path,
             alt_text: parsed_link.alt_text,
-            size_parameter: parsed_link.size_parameter,
+            target_suffix: parsed_link.target_suffix,
             state: ImageLinkState::default(),
             link_type: parsed_link.link_type,
+            file_size: None,
         })
     }
 }
@@ -116,40 +122,30 @@ impl ReplaceableContent for ImageLink {
                     .file_name()
                     .and_then(OsStr::to_str)
                     .unwrap_or_default();
-                let new_relative = format!("{}{FORWARD_SLASH}{new_name}", self.relative_path);
+                let new_relative = if self.relative_path.is_empty() {
+                    new_name.to_string()
+                } else {
+                    format!("{}{FORWARD_SLASH}{new_name}", self.relative_path)
+                };
+                let suffix = &self.target_suffix;
 
                 match &self.link_type {
-                    ImageLinkType::Wiki(rendering) => match rendering {
-                        ImageRendering::Embedded => self.size_parameter.as_ref().map_or_else(
-                            || {
-                                format!(
-                                    "{IMAGE_EMBED_MARKER}{OPENING_WIKILINK}{new_relative}{CLOSING_WIKILINK}"
-                                )
-                            },
-                            |size| {
-                                format!(
-                                    "{IMAGE_EMBED_MARKER}{OPENING_WIKILINK}{new_relative}{PIPE}{size}{CLOSING_WIKILINK}"
-                                )
-                            },
-                        ),
-                        ImageRendering::Linked => {
-                            format!("{OPENING_WIKILINK}{new_relative}{CLOSING_WIKILINK}")
-                        },
+                    ImageLinkType::Wiki(rendering) => {
+                        let marker = embed_marker(rendering);
+                        format!(
+                            "{marker}{OPENING_WIKILINK}{new_relative}{suffix}{CLOSING_WIKILINK}"
+                        )
                     },
-                    ImageLinkType::Markdown(target, rendering) => match (target, rendering) {
-                        (ImageLinkTarget::Internal, ImageRendering::Embedded) => {
-                            format!(
-                                "{IMAGE_LINK_PREFIX}{}{MARKDOWN_LINK_SEPARATOR}{new_relative}{CLOSING_PAREN}",
-                                self.alt_text
-                            )
-                        },
-                        (ImageLinkTarget::Internal, ImageRendering::Linked) => {
-                            format!(
-                                "{OPENING_BRACKET}{}{MARKDOWN_LINK_SEPARATOR}{new_relative}{CLOSING_PAREN}",
-                                self.alt_text
-                            )
-                        },
-                        (ImageLinkTarget::External, _) => self.matched_text.clone(),
+                    ImageLinkType::Markdown(ImageLinkTarget::Internal, rendering) => {
+                        let marker = embed_marker(rendering);
+                        let path = new_relative.replace(SPACE, MARKDOWN_LINK_SPACE);
+                        format!(
+                            "{marker}{OPENING_BRACKET}{}{MARKDOWN_LINK_SEPARATOR}{path}{suffix}{CLOSING_PAREN}",
+                            self.alt_text
+                        )
+                    },
+                    ImageLinkType::Markdown(ImageLinkTarget::External, _) => {
+                        self.matched_text.clone()
                     },
                 }
             },
@@ -169,6 +165,21 @@ pub(crate) struct ImageLinks {
     pub links: Vec<ImageLink>,
 }
 
+impl ImageLinks {
+    /// The distinct files in the vault these links point at, each with its size. A file linked
+    /// more than once counts once.
+    fn attachments(&self) -> HashMap<String, u64> {
+        self.links
+            .iter()
+            .filter_map(|link| Some((link.filename.to_lowercase(), link.file_size?)))
+            .collect()
+    }
+
+    pub(crate) fn attachment_count(&self) -> usize { self.attachments().len() }
+
+    pub(crate) fn attachment_size(&self) -> u64 { self.attachments().values().sum() }
+}
+
 impl FromIterator<ImageLink> for ImageLinks {
     fn from_iter<I: IntoIterator<Item = ImageLink>>(iter: I) -> Self {
         Self {
@@ -178,10 +189,10 @@ impl FromIterator<ImageLink> for ImageLinks {
 }
 
 struct ParsedImageLink {
-    filename:       String,
-    link_type:      ImageLinkType,
-    alt_text:       String,
-    size_parameter: Option<String>,
+    filename:      String,
+    link_type:     ImageLinkType,
+    alt_text:      String,
+    target_suffix: String,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -212,45 +223,46 @@ fn image_rendering(raw_link: &str) -> ImageRendering {
     }
 }
 
+fn embed_marker(rendering: &ImageRendering) -> String {
+    match rendering {
+        ImageRendering::Embedded => IMAGE_EMBED_MARKER.to_string(),
+        ImageRendering::Linked => String::new(),
+    }
+}
+
 fn parse_wiki_image_link(raw_link: &str) -> ParsedImageLink {
     let rendering = image_rendering(raw_link);
 
-    let filename = raw_link
+    let inner = raw_link
         .trim_start_matches(IMAGE_EMBED_MARKER)
         .trim_start_matches(OPENING_WIKILINK)
-        .trim_end_matches(CLOSING_WIKILINK)
-        .split(PIPE)
-        .next()
-        .unwrap_or("")
-        .trim()
-        .trim_matches(BACKSLASH)
-        .to_lowercase();
-
-    let size_parameter = raw_link
-        .split(PIPE)
-        .nth(IMAGE_LINK_SIZE_PARAMETER_INDEX)
-        .map(|s| s.trim_end_matches(CLOSING_WIKILINK).to_string());
+        .trim_end_matches(CLOSING_WIKILINK);
+    let target = inner.split(PIPE).next().unwrap_or_default();
+    // A pipe escaped in a table leaves its backslash at the end of the target.
+    let target = target.strip_suffix(BACKSLASH).unwrap_or(target);
 
     ParsedImageLink {
-        filename,
-        link_type: ImageLinkType::Wiki(rendering),
-        alt_text: String::new(),
-        size_parameter,
+        filename:      target.trim().trim_matches(BACKSLASH).to_lowercase(),
+        link_type:     ImageLinkType::Wiki(rendering),
+        alt_text:      String::new(),
+        target_suffix: inner[target.len()..].to_string(),
     }
 }
 
 fn parse_markdown_image_link(raw_link: &str) -> ParsedImageLink {
     let rendering = image_rendering(raw_link);
 
+    // Past the `[`, which a linked image has no `!` before.
+    let alt_start = raw_link.find(OPENING_BRACKET).map_or(0, |index| index + 1);
     let alt_text = raw_link
         .find(MARKDOWN_LINK_SEPARATOR)
-        .map(|alt_end| raw_link[IMAGE_LINK_PREFIX.len()..alt_end].to_string())
+        .map(|alt_end| raw_link[alt_start..alt_end].to_string())
         .unwrap_or_default();
 
     let url_start = raw_link
         .find(MARKDOWN_LINK_SEPARATOR)
         .map_or(0, |index| index + MARKDOWN_LINK_SEPARATOR.len());
-    let url = &raw_link[url_start..raw_link.len() - 1];
+    let (url, title) = split_markdown_title(&raw_link[url_start..raw_link.len() - 1]);
 
     let target = if url.starts_with(HTTP_URL_PREFIX) || url.starts_with(HTTPS_URL_PREFIX) {
         ImageLinkTarget::External
@@ -263,6 +275,7 @@ fn parse_markdown_image_link(raw_link: &str) -> ParsedImageLink {
             .rsplit(FORWARD_SLASH)
             .next()
             .unwrap_or("")
+            .replace(MARKDOWN_LINK_SPACE, &SPACE.to_string())
             .to_lowercase(),
         ImageLinkTarget::External => url.to_lowercase(),
     };
@@ -271,13 +284,30 @@ fn parse_markdown_image_link(raw_link: &str) -> ParsedImageLink {
         filename,
         link_type: ImageLinkType::Markdown(target, rendering),
         alt_text,
-        size_parameter: None,
+        target_suffix: title.to_string(),
     }
 }
 
+/// Splits a markdown link's `url "title"` into the url and the title with the space before it.
+fn split_markdown_title(url: &str) -> (&str, &str) {
+    let trimmed = url.trim_end();
+    MARKDOWN_TITLE_QUOTES
+        .iter()
+        .filter(|quote| trimmed.len() > 1 && trimmed.ends_with(**quote))
+        .find_map(|quote| {
+            let title_start = trimmed[..trimmed.len() - 1].rfind(*quote)?;
+            let path = &trimmed[..title_start];
+            path.ends_with(char::is_whitespace)
+                .then(|| (path.trim_end(), &url[path.trim_end().len()..]))
+        })
+        .unwrap_or((url, ""))
+}
+
+/// The folder a link names its image in - empty when it names only the file, and
+/// `identify_image_reference_replacements` fills in the attachment folder.
 fn extract_relative_path(matched: &str) -> String {
     if !matched.contains(FORWARD_SLASH) {
-        return DEFAULT_MEDIA_PATH.to_string();
+        return String::new();
     }
 
     let prefix = matched
@@ -289,7 +319,7 @@ fn extract_relative_path(matched: &str) -> String {
         .map(|index| &prefix[index + 1..])
         .map(|path| path.trim_end_matches(FORWARD_SLASH))
         .filter(|path| !path.is_empty())
-        .unwrap_or(DEFAULT_MEDIA_PATH)
+        .unwrap_or_default()
         .to_string()
 }
 
@@ -300,10 +330,14 @@ fn extract_relative_path(matched: &str) -> String {
     reason = "tests should panic on unexpected values"
 )]
 mod tests {
+    use std::path::PathBuf;
+
     use super::ImageLink;
+    use super::ImageLinkState;
     use super::ImageLinkTarget;
     use super::ImageLinkType;
     use super::ImageRendering;
+    use crate::markdown_file::ReplaceableContent;
     use crate::support::IMAGE_REGEX;
 
     const TEST_IMAGE_LINK_LINE_NUMBER: usize = 1;
@@ -402,4 +436,48 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_duplicate_replacement_keeps_size_alt_and_title() {
+        let cases = [
+            ("![[dup.png|400]]", "![[keep me.png|400]]"),
+            (
+                "![[dup.png|a diagram|400x300]]",
+                "![[keep me.png|a diagram|400x300]]",
+            ),
+            ("[[dup.png|see this]]", "[[keep me.png|see this]]"),
+            (r"| ![[dup.png\|400]] |", r"![[keep me.png\|400]]"),
+            (
+                "![a diagram|400](dup.png)",
+                "![a diagram|400](keep%20me.png)",
+            ),
+            (
+                r#"![a diagram](dup.png "The diagram")"#,
+                r#"![a diagram](keep%20me.png "The diagram")"#,
+            ),
+            (
+                "[a diagram](dup.png 'The diagram')",
+                "[a diagram](keep%20me.png 'The diagram')",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let raw_link = IMAGE_REGEX.find(input).unwrap().as_str();
+            let mut image_link = ImageLink::new(raw_link.to_string(), 1, 0).unwrap();
+            assert_eq!(image_link.filename, "dup.png", "{input}");
+
+            image_link.state = ImageLinkState::Duplicate {
+                keeper_path: PathBuf::from("/vault/keep me.png"),
+            };
+            assert_eq!(image_link.get_replacement(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_markdown_path_spaces_are_decoded() {
+        let image_link = ImageLink::new("![](assets/my%20photo.png)".to_string(), 1, 0).unwrap();
+
+        assert_eq!(image_link.filename, "my photo.png");
+        assert_eq!(image_link.relative_path, "assets");
+    }
 }
@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use chrono_tz::UTC;
+
+use super::MarkdownFile;
+use super::PersistReason;
+use super::date_consistency;
+use crate::config::PersistReasonKind;
+
+/// A note in a `journal_folders` folder whose name, `date_created` and file creation date don't
+/// all agree, with the dates as they stood when the note was read. The name wins: `date` is
+/// what both are set to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JournalDateMismatch {
+    pub date:         NaiveDate,
+    pub date_created: Option<NaiveDate>,
+    pub file_system:  NaiveDate,
+}
+
+impl MarkdownFile {
+    /// Checks a note named for `date` against its `date_created` and file creation date, and
+    /// moves both to `date` as `date_created_fix` would. That replaces the repair made on load,
+    /// which took the file's date over the name's. A note with its own `date_created_fix` is
+    /// left to it.
+    pub(crate) fn check_journal_date(
+        &mut self,
+        date: NaiveDate,
+        operational_timezone: &str,
+        held_reasons: &[PersistReasonKind],
+    ) {
+        if self.date_created_fix_validation.fixed.is_some() {
+            return;
+        }
+        let Some(front_matter) = self.front_matter.as_mut() else {
+            return;
+        };
+        let timezone: Tz = operational_timezone.parse().unwrap_or(UTC);
+        let date_created = date_consistency::frontmatter_date(
+            self.created_date_validation.frontmatter.as_deref(),
+            front_matter.date_link_format(),
+        );
+        let file_system = self
+            .created_date_validation
+            .file_system
+            .with_timezone(&timezone)
+            .date_naive();
+        if date_created == Some(date) && file_system == date {
+            return;
+        }
+
+        self.journal_date_mismatch = Some(JournalDateMismatch {
+            date,
+            date_created,
+            file_system,
+        });
+        let reason = PersistReason::JournalDateAligned { date };
+        if held_reasons.contains(&reason.kind()) {
+            self.held_persist_reasons.push(reason);
+            return;
+        }
+        let Some(noon) = date_consistency::noon(date, timezone) else {
+            return;
+        };
+        front_matter.set_date_created(noon, operational_timezone);
+        self.persist_reasons
+            .retain(|reason| !matches!(reason, PersistReason::DateCreatedUpdated { .. }));
+        self.persist_reasons.push(reason);
+    }
+}
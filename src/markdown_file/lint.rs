@@ -0,0 +1,163 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use super::MarkdownFile;
+use super::back_populate;
+use super::constants::URL_OPENERS;
+use super::list_style;
+use super::punctuation;
+use super::rare_words;
+use super::text_excluder::CodeBlockExcluder;
+use crate::constants::BARE_URL;
+use crate::constants::HARD_TAB;
+use crate::constants::LONG_LINE;
+use crate::constants::NO_BLANK_LINE_BEFORE_HEADING;
+use crate::constants::NO_BLANK_LINE_BEFORE_LIST;
+use crate::constants::PIPE;
+use crate::support::LIST_ITEM_REGEX;
+use crate::support::RAW_HTTP_REGEX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintKind {
+    /// A URL outside a link, which reads as raw text in other markdown apps.
+    BareUrl,
+    /// A tab after the start of the line - tabs indenting a line are how Obsidian nests lists.
+    HardTab,
+    /// A line longer than `lint_max_line_length`, other than a table row.
+    LongLine,
+    /// A heading right below another line.
+    NoBlankLineBeforeHeading,
+    /// The first item of a list right below a line that isn't part of it.
+    NoBlankLineBeforeList,
+}
+
+impl Display for LintKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BareUrl => f.write_str(BARE_URL),
+            Self::HardTab => f.write_str(HARD_TAB),
+            Self::LongLine => f.write_str(LONG_LINE),
+            Self::NoBlankLineBeforeHeading => f.write_str(NO_BLANK_LINE_BEFORE_HEADING),
+            Self::NoBlankLineBeforeList => f.write_str(NO_BLANK_LINE_BEFORE_LIST),
+        }
+    }
+}
+
+/// One style issue on one line of a note's body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintIssue {
+    pub line_number: usize,
+    pub kind:        LintKind,
+}
+
+impl MarkdownFile {
+    /// Style issues in the body, outside code blocks. Long lines are only checked with
+    /// `max_line_length` set. Nothing is changed.
+    pub(crate) fn lint(&self, max_line_length: Option<usize>) -> Vec<LintIssue> {
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut issues = Vec::new();
+        let mut previous_line: Option<&str> = None;
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                previous_line = Some(line);
+                continue;
+            }
+
+            let line_number = self.get_real_line_number(line_idx);
+            let mut push = |kind| issues.push(LintIssue { line_number, kind });
+
+            if line.trim_start().contains('\t') {
+                push(LintKind::HardTab);
+            }
+            if max_line_length.is_some_and(|max| line.chars().count() > max)
+                && !line.trim_start().starts_with(PIPE)
+            {
+                push(LintKind::LongLine);
+            }
+            if has_bare_url(line) {
+                push(LintKind::BareUrl);
+            }
+
+            let follows_text = previous_line.is_some_and(|previous| !previous.trim().is_empty());
+            if follows_text && rare_words::heading_text(line).is_some() {
+                push(LintKind::NoBlankLineBeforeHeading);
+            }
+            if follows_text
+                && is_list_item(line)
+                && previous_line.is_some_and(|previous| {
+                    !is_list_item(previous) && !previous.starts_with(char::is_whitespace)
+                })
+            {
+                push(LintKind::NoBlankLineBeforeList);
+            }
+
+            previous_line = Some(line);
+        }
+
+        issues
+    }
+}
+
+fn is_list_item(line: &str) -> bool {
+    LIST_ITEM_REGEX.is_match(line) && !list_style::is_thematic_break(line)
+}
+
+/// A URL that isn't in inline code, a wikilink or a markdown link, and isn't an `<url>`
+/// autolink or an HTML attribute.
+fn has_bare_url(line: &str) -> bool {
+    let protected_spans = punctuation::protected_spans(line);
+    RAW_HTTP_REGEX.find_iter(line).any(|url| {
+        !back_populate::range_overlaps(&protected_spans, url.start(), url.end())
+            && !line[..url.start()].ends_with(URL_OPENERS)
+    })
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::LintKind;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_lint() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "intro text\n## Heading\nsee https://example.com and <https://example.org>\n\
+                 - item\tone\n\t- nested\nparagraph\n- list\n\n[site](https://example.net) \
+                 `https://code.example`\n```\nhttps://in.code\tblock\n```\n\
+                 | a long table row that goes on |\na line that goes on for too long",
+            )
+            .create(&temp_dir, "notes.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let issues: Vec<(usize, LintKind)> = markdown_file
+            .lint(Some(30))
+            .into_iter()
+            .map(|issue| (issue.line_number, issue.kind))
+            .collect();
+
+        assert_eq!(
+            issues,
+            vec![
+                (2, LintKind::NoBlankLineBeforeHeading),
+                (3, LintKind::LongLine),
+                (3, LintKind::BareUrl),
+                (4, LintKind::HardTab),
+                (4, LintKind::NoBlankLineBeforeList),
+                (7, LintKind::NoBlankLineBeforeList),
+                (9, LintKind::LongLine),
+                (14, LintKind::LongLine),
+            ]
+        );
+    }
+}
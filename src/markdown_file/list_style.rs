@@ -0,0 +1,244 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::iter;
+
+use anyhow::Result as AnyhowResult;
+
+use super::MarkdownFile;
+use super::constants::INDENTED_CODE_MIN_COLUMNS;
+use super::constants::LIST_INDENT;
+use super::constants::LIST_MARKER;
+use super::constants::LIST_TAB_WIDTH;
+use super::constants::THEMATIC_BREAK_MIN_MARKERS;
+use super::text_excluder::CodeBlockExcluder;
+use crate::constants::LIST_INDENTATION;
+use crate::constants::LIST_MARKER as LIST_MARKER_KIND;
+use crate::constants::LIST_NUMBERING;
+use crate::support::LIST_ITEM_REGEX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListStyleKind {
+    /// The item is indented with one tab per nesting level.
+    Indentation,
+    /// A `*` or `+` marker becomes `-`.
+    Marker,
+    /// The item is numbered one past the item before it.
+    Numbering,
+}
+
+impl Display for ListStyleKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Indentation => f.write_str(LIST_INDENTATION),
+            Self::Marker => f.write_str(LIST_MARKER_KIND),
+            Self::Numbering => f.write_str(LIST_NUMBERING),
+        }
+    }
+}
+
+/// A list item rewritten, and what about it changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListNormalization {
+    pub line_number: usize,
+    pub kinds:       Vec<ListStyleKind>,
+}
+
+/// One nesting level of the list being read: the indentation its items are written with, and
+/// for an ordered level, its delimiter and the number its next item should have.
+struct ListLevel {
+    columns:   usize,
+    numbering: Option<(char, u64)>,
+}
+
+impl MarkdownFile {
+    /// Rewrites list items in the note body so every bullet uses `-`, nested items are indented
+    /// one tab per level, and ordered items count up from the list's first number - Obsidian
+    /// folds a list by its indentation and starts a new list at a changed marker, so mixed
+    /// styles break folding. A blank line doesn't end a list; an unindented line that isn't a
+    /// list item does. Code blocks and encrypted blocks are left alone, and only the marker and
+    /// indentation of an item are touched, never its text.
+    pub(crate) fn normalize_lists(&mut self, operational_timezone: &str) -> AnyhowResult<()> {
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let encrypted_blocks = self.encrypted_blocks();
+        let mut normalized_content = String::with_capacity(self.content.len());
+        let mut normalizations = Vec::new();
+        let mut levels: Vec<ListLevel> = Vec::new();
+
+        for (line_idx, line_with_ending) in self.content.split_inclusive('\n').enumerate() {
+            let line = line_with_ending.trim_end_matches(['\r', '\n']);
+            let ending = &line_with_ending[line.len()..];
+            code_block_excluder.update(line);
+            let line_number = self.get_real_line_number(line_idx);
+            if code_block_excluder.is_in_code_block()
+                || encrypted_blocks
+                    .iter()
+                    .any(|block| block.contains(line_number))
+            {
+                if !line.starts_with(char::is_whitespace) {
+                    levels.clear();
+                }
+                normalized_content.push_str(line_with_ending);
+                continue;
+            }
+
+            let Some((normalized, kinds)) = normalize_list_line(line, &mut levels) else {
+                normalized_content.push_str(line_with_ending);
+                continue;
+            };
+
+            normalized_content.push_str(&normalized);
+            normalized_content.push_str(ending);
+            if !kinds.is_empty() {
+                normalizations.push(ListNormalization { line_number, kinds });
+            }
+        }
+
+        if normalizations.is_empty() {
+            return Ok(());
+        }
+
+        self.content = normalized_content;
+        self.wikilinks = self.process_wikilinks();
+        self.image_links.links = self.process_image_links();
+
+        let count = normalizations.len();
+        self.list_normalizations = normalizations;
+        self.mark_lists_normalized(count, operational_timezone)
+    }
+}
+
+/// The line as a normalized list item, and what changed, when it is one - `levels` tracks the
+/// list the line belongs to and is cleared when a line ends it.
+fn normalize_list_line(
+    line: &str,
+    levels: &mut Vec<ListLevel>,
+) -> Option<(String, Vec<ListStyleKind>)> {
+    let captures = LIST_ITEM_REGEX
+        .captures(line)
+        .filter(|_| !is_thematic_break(line));
+    let Some(captures) = captures else {
+        if !line.trim().is_empty() && !line.starts_with(char::is_whitespace) {
+            levels.clear();
+        }
+        return None;
+    };
+
+    let indent = &captures[1];
+    let columns = indent_columns(indent);
+    // Indented this far, the first item of a list would be an indented code block.
+    if levels.is_empty() && columns >= INDENTED_CODE_MIN_COLUMNS {
+        return None;
+    }
+
+    while levels.last().is_some_and(|level| level.columns > columns) {
+        levels.pop();
+    }
+    if levels.last().is_none_or(|level| level.columns < columns) {
+        levels.push(ListLevel {
+            columns,
+            numbering: None,
+        });
+    }
+    let depth = levels.len() - 1;
+    let level = levels.last_mut()?;
+
+    let mut kinds = Vec::new();
+    let new_indent: String = iter::repeat_n(LIST_INDENT, depth).collect();
+    if new_indent != indent {
+        kinds.push(ListStyleKind::Indentation);
+    }
+
+    let marker_end = captures.get(2).or_else(|| captures.get(4))?.end();
+    let new_marker = if let Some(bullet) = captures.get(2) {
+        level.numbering = None;
+        if !bullet.as_str().starts_with(LIST_MARKER) {
+            kinds.push(ListStyleKind::Marker);
+        }
+        LIST_MARKER.to_string()
+    } else {
+        let number_text = &captures[3];
+        let delimiter = captures[4].chars().next()?;
+        let number = match level.numbering {
+            Some((previous_delimiter, next)) if previous_delimiter == delimiter => next,
+            _ => number_text.parse().ok()?,
+        };
+        level.numbering = Some((delimiter, number + 1));
+        if number_text.parse::<u64>().ok()? == number {
+            format!("{number_text}{delimiter}")
+        } else {
+            kinds.push(ListStyleKind::Numbering);
+            format!("{number}{delimiter}")
+        }
+    };
+
+    Some((
+        format!("{new_indent}{new_marker}{}", &line[marker_end..]),
+        kinds,
+    ))
+}
+
+/// The width of the indentation, with a tab reaching the next tab stop.
+fn indent_columns(indent: &str) -> usize {
+    indent.chars().fold(0, |columns, ch| {
+        if ch == '\t' {
+            (columns / LIST_TAB_WIDTH + 1) * LIST_TAB_WIDTH
+        } else {
+            columns + 1
+        }
+    })
+}
+
+/// `* * *`, `- - -` and the like are horizontal rules, not list items.
+pub(super) fn is_thematic_break(line: &str) -> bool {
+    let markers: Vec<char> = line.chars().filter(|ch| !ch.is_whitespace()).collect();
+    markers.len() >= THEMATIC_BREAK_MIN_MARKERS
+        && ['*', '-', '_']
+            .iter()
+            .any(|marker| markers.iter().all(|ch| ch == marker))
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::ListStyleKind;
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::markdown_file::PersistReason;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_normalize_lists() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "* first\n  + nested\n    - deeper\n* second\n\n1. one\n1. two\n\n7) three\n\
+                 \t1. sub\n\t5. sub\n\n* * *\n```\n* code\n```\n**bold** text\n    * indented code",
+            )
+            .create(&temp_dir, "notes.md");
+        let mut markdown_file = test_support::get_test_markdown_file(file_path);
+
+        markdown_file.normalize_lists(DEFAULT_TIMEZONE).unwrap();
+
+        assert_eq!(
+            markdown_file.content,
+            "- first\n\t- nested\n\t\t- deeper\n- second\n\n1. one\n2. two\n\n7) three\n\
+             \t1. sub\n\t2. sub\n\n* * *\n```\n* code\n```\n**bold** text\n    * indented code\n",
+            "a new delimiter starts a new list, and rules, code and emphasis are left alone"
+        );
+        assert_eq!(
+            markdown_file.list_normalizations[1].kinds,
+            vec![ListStyleKind::Indentation, ListStyleKind::Marker]
+        );
+        assert!(
+            markdown_file
+                .persist_reasons
+                .contains(&PersistReason::ListsNormalized { count: 6 })
+        );
+    }
+}
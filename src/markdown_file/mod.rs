@@ -1,15 +1,47 @@
+mod alias_generation;
+mod audit_trail;
 mod back_populate;
+mod block_id;
+mod callout;
 mod canonical_link;
+mod citation;
+mod conflict_copy;
 mod constants;
+mod custom_replacement;
+mod date_consistency;
+mod date_mention;
 mod date_validation;
+mod duplicate_frontmatter_key;
+mod encrypted_block;
+mod entity_note;
+mod export;
+mod flashcard;
+mod frontmatter_link;
+mod heading_link;
+mod ignore_marker;
 mod image_link;
+mod journal_date;
+mod lint;
+mod list_style;
+mod note_split;
+mod periodic_note;
 mod phantom_link;
+mod pii;
+mod punctuation;
+mod rare_words;
+mod regex_replacement;
 mod replaceable_content;
+mod review;
+mod secret_scan;
+mod self_link;
+mod table_of_contents;
 mod text_excluder;
+mod wikilink_repair;
 
 use std::error::Error;
 use std::ffi::OsStr;
 use std::fs;
+use std::iter;
 use std::path::PathBuf;
 
 use aho_corasick::AhoCorasick;
@@ -17,16 +49,46 @@ use anyhow::Result as AnyhowResult;
 use anyhow::anyhow;
 pub use back_populate::BackPopulateMatch;
 pub use back_populate::MatchContext;
+pub use callout::AdmonitionConversion;
+pub use callout::CalloutIssue;
 pub use canonical_link::CanonicalLinkMatch;
+use chrono::DateTime;
+use chrono::Utc;
+pub use custom_replacement::CustomReplacementMatch;
+pub use custom_replacement::LineReplacement;
+pub use custom_replacement::ReplacementSource;
+pub use date_consistency::DateInconsistency;
+pub use date_mention::DateMentionMatch;
+pub use date_validation::DatePropertyValidation;
 pub use date_validation::DateValidation;
 pub use date_validation::PersistReason;
+pub use duplicate_frontmatter_key::DuplicateFrontmatterKey;
+pub use encrypted_block::EncryptedBlock;
+pub(crate) use export::ExportReference;
+pub use flashcard::Flashcard;
+pub use frontmatter_link::FrontmatterWikilink;
+pub use heading_link::HeadingLinkRepairMatch;
+pub use heading_link::StaleHeadingLink;
 pub use image_link::ImageLink;
 pub use image_link::ImageLinkState;
+pub use journal_date::JournalDateMismatch;
+pub use lint::LintKind;
+pub use list_style::ListNormalization;
+pub use note_split::NoteSection;
 pub use phantom_link::PhantomLinkMatch;
+pub(crate) use pii::BuiltInPiiDetector;
+pub(crate) use pii::PiiDetector;
+pub use punctuation::PunctuationNormalization;
+pub use rare_words::RareWordSource;
 use regex::Regex;
+pub use regex_replacement::RegexReplacementMatch;
+pub use replaceable_content::ByteSpan;
 pub use replaceable_content::MatchType;
 pub use replaceable_content::ReplaceableContent;
+pub use self_link::SelfLinkMatch;
+pub use table_of_contents::TableOfContentsAction;
 pub use text_excluder::InlineCodeExcluder;
+pub use wikilink_repair::WikilinkRepairMatch;
 
 use self::back_populate::BackPopulateMatches;
 use self::constants::IMAGE_LINK_WHOLE_MATCH_CAPTURE_INDEX;
@@ -35,13 +97,18 @@ use self::image_link::ImageLinkTarget;
 use self::image_link::ImageLinkType;
 use self::image_link::ImageLinks;
 use self::text_excluder::CodeBlockExcluder;
+use self::text_excluder::CommentExcluder;
+use crate::config::DuplicateKeyResolution;
+use crate::config::PersistReasonKind;
 use crate::constants::FRONTMATTER_DELIMITER_LINE_COUNT;
 use crate::constants::FRONTMATTER_MISSING_AFTER_ENSURE;
+use crate::constants::NEWLINE;
 use crate::constants::PERSIST_REQUIRES_FRONTMATTER;
 use crate::constants::PERSIST_REQUIRES_RAW_DATE_MODIFIED;
 use crate::constants::YAML_CLOSING_DELIMITER;
 use crate::constants::YAML_OPENING_DELIMITER;
 use crate::frontmatter::FrontMatter;
+use crate::sha256_cache;
 use crate::support;
 use crate::support::IMAGE_REGEX;
 use crate::validated_config::ValidatedConfig;
@@ -60,42 +127,85 @@ pub(crate) struct Wikilinks {
 
 #[derive(Debug, Clone)]
 pub(crate) struct MarkdownFile {
+    pub(crate) admonition_conversions:       Vec<AdmonitionConversion>,
     pub(crate) content:                      String,
+    pub(crate) custom_replacement_matches:   Vec<CustomReplacementMatch>,
     pub(crate) date_created_fix_validation:  DateCreatedFixValidation,
+    pub(crate) date_link_format:             String,
+    pub(crate) date_inconsistencies:         Vec<DateInconsistency>,
+    pub(crate) date_mention_matches:         Vec<DateMentionMatch>,
+    pub(crate) date_property_validations:    Vec<DatePropertyValidation>,
     pub(crate) created_date_validation:      DateValidation,
     pub(crate) modified_date_validation:     DateValidation,
     pub(crate) do_not_back_populate_regexes: Option<Vec<Regex>>,
+    pub(crate) duplicate_frontmatter_keys:   Vec<DuplicateFrontmatterKey>,
     pub(crate) front_matter:                 Option<FrontMatter>,
     pub(crate) frontmatter_error:            Option<YamlFrontMatterError>,
     pub(crate) frontmatter_line_count:       usize,
+    pub(crate) frontmatter_wikilinks:        Vec<FrontmatterWikilink>,
+    pub(crate) heading_link_repair_matches:  Vec<HeadingLinkRepairMatch>,
+    /// Changes found but not made because `hold_persist_reasons` names them.
+    pub(crate) held_persist_reasons:         Vec<PersistReason>,
     pub(crate) image_links:                  ImageLinks,
+    pub(crate) journal_date_mismatch:        Option<JournalDateMismatch>,
+    pub(crate) list_normalizations:          Vec<ListNormalization>,
     pub(crate) wikilinks:                    Wikilinks,
     pub(crate) back_populate_matches:        BackPopulateMatches,
     pub(crate) canonical_link_matches:       Vec<CanonicalLinkMatch>,
+    /// SHA-256 of the file as read, to catch edits made while ok was running.
+    pub(crate) content_hash:                 String,
     pub(crate) phantom_link_matches:         Vec<PhantomLinkMatch>,
     pub(crate) path:                         PathBuf,
     pub(crate) persist_reasons:              Vec<PersistReason>,
+    pub(crate) punctuation_normalizations:   Vec<PunctuationNormalization>,
+    pub(crate) regex_replacement_matches:    Vec<RegexReplacementMatch>,
+    pub(crate) self_link_matches:            Vec<SelfLinkMatch>,
+    pub(crate) wikilink_repair_matches:      Vec<WikilinkRepairMatch>,
 }
 
 impl MarkdownFile {
+    /// With `duplicate_key_resolution` set, frontmatter keys written more than once are resolved
+    /// before the YAML is parsed, since parsing alone keeps only the last occurrence. Dates are
+    /// read and written as wikilinks in `date_link_format`, and date repairs in `held_reasons`
+    /// are found but not made.
     pub(crate) fn new(
         path: PathBuf,
         operational_timezone: &str,
+        date_link_format: &str,
+        duplicate_key_resolution: Option<DuplicateKeyResolution>,
+        held_reasons: &[PersistReasonKind],
     ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let full_content = support::read_contents_from_file(&path)?;
+        let content_hash = sha256_cache::hash_contents(full_content.as_bytes());
 
         let yaml_result = yaml_frontmatter::find_yaml_section(&full_content);
-        let frontmatter_line_count = match &yaml_result {
-            Ok(Some((yaml_section, _))) => {
-                yaml_section.lines().count() + FRONTMATTER_DELIMITER_LINE_COUNT
-            },
-            _ => 0,
+        let (frontmatter_line_count, duplicate_frontmatter_keys) = match &yaml_result {
+            Ok(Some((yaml_section, _))) => (
+                yaml_section.lines().count() + FRONTMATTER_DELIMITER_LINE_COUNT,
+                duplicate_frontmatter_key::find_duplicate_keys(yaml_section),
+            ),
+            _ => (0, Vec::new()),
         };
+        let resolve_duplicates =
+            duplicate_key_resolution.filter(|_| !duplicate_frontmatter_keys.is_empty());
 
         let (mut front_matter, content, frontmatter_error) = match yaml_result {
             Ok(Some((yaml_section, after_yaml))) => {
-                match FrontMatter::from_yaml_str(yaml_section) {
-                    Ok(front_matter) => (Some(front_matter), after_yaml.to_string(), None),
+                let yaml_section = resolve_duplicates.map_or_else(
+                    || yaml_section.to_string(),
+                    |resolution| {
+                        duplicate_frontmatter_key::resolve_duplicate_keys(
+                            yaml_section,
+                            &duplicate_frontmatter_keys,
+                            resolution,
+                        )
+                    },
+                );
+                match FrontMatter::from_yaml_str(&yaml_section) {
+                    Ok(mut front_matter) => {
+                        front_matter.date_link_format = Some(date_link_format.to_string());
+                        (Some(front_matter), after_yaml.to_string(), None)
+                    },
                     Err(e) => (None, after_yaml.to_string(), Some(e)),
                 }
             },
@@ -116,12 +226,13 @@ impl MarkdownFile {
             operational_timezone,
         );
 
-        let persist_reasons = date_validation::process_date_validations(
+        let (persist_reasons, held_persist_reasons) = date_validation::process_date_validations(
             &mut front_matter,
             &created_date_validation,
             &modified_date_validation,
             &date_created_fix_validation,
             operational_timezone,
+            held_reasons,
         );
 
         let do_not_back_populate_regexes = front_matter
@@ -129,27 +240,49 @@ impl MarkdownFile {
             .and_then(FrontMatter::get_do_not_back_populate_regexes);
 
         let mut markdown_file = Self {
+            admonition_conversions: Vec::new(),
             content,
+            custom_replacement_matches: Vec::new(),
             date_created_fix_validation,
+            date_link_format: date_link_format.to_string(),
+            date_inconsistencies: Vec::new(),
+            date_mention_matches: Vec::new(),
+            date_property_validations: Vec::new(),
             do_not_back_populate_regexes,
+            duplicate_frontmatter_keys,
             created_date_validation,
             modified_date_validation,
             front_matter,
             frontmatter_error,
             frontmatter_line_count,
+            frontmatter_wikilinks: Vec::new(),
+            heading_link_repair_matches: Vec::new(),
+            held_persist_reasons,
             wikilinks: Wikilinks::default(),
             image_links: ImageLinks::default(),
+            journal_date_mismatch: None,
+            list_normalizations: Vec::new(),
             back_populate_matches: BackPopulateMatches::default(),
             canonical_link_matches: Vec::new(),
+            content_hash,
             phantom_link_matches: Vec::new(),
             path,
             persist_reasons,
+            punctuation_normalizations: Vec::new(),
+            regex_replacement_matches: Vec::new(),
+            self_link_matches: Vec::new(),
+            wikilink_repair_matches: Vec::new(),
         };
 
         // MarkdownFile keeps parsed Wikilinks and ImageLinks for later reports.
         markdown_file.wikilinks = markdown_file.process_wikilinks();
         markdown_file.image_links.links = markdown_file.process_image_links();
 
+        if resolve_duplicates.is_some() && markdown_file.front_matter.is_some() {
+            let count = markdown_file.duplicate_frontmatter_keys.len();
+            markdown_file.mark_duplicate_frontmatter_keys_fixed(count, operational_timezone)?;
+        }
+
         Ok(markdown_file)
     }
 
@@ -166,7 +299,7 @@ impl MarkdownFile {
         );
     }
 
-    fn to_full_content(&self) -> String {
+    pub(crate) fn to_full_content(&self) -> String {
         self.front_matter.as_ref().map_or_else(
             || self.content.clone(),
             |front_matter| {
@@ -184,8 +317,22 @@ impl MarkdownFile {
         )
     }
 
+    /// Whether the file on disk no longer matches what was read - Obsidian or a sync client
+    /// saved it during the run. A file that can't be read counts as changed.
+    pub(crate) fn changed_on_disk(&self) -> bool {
+        fs::read(&self.path).map_or(true, |contents| {
+            sha256_cache::hash_contents(&contents) != self.content_hash
+        })
+    }
+
     pub(crate) fn persist(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
-        fs::write(&self.path, self.to_full_content())?;
+        // The atomic write makes a new file, so its creation date is put back afterwards -
+        // `date_created` is checked against it.
+        let original_created = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.created())
+            .ok()
+            .map(DateTime::<Utc>::from);
+        support::write_atomically(&self.path, self.to_full_content())?;
 
         let Some(front_matter) = self.front_matter.as_ref() else {
             return Err(PERSIST_REQUIRES_FRONTMATTER.into());
@@ -193,16 +340,30 @@ impl MarkdownFile {
         let modified_date = front_matter
             .raw_modified
             .ok_or_else(|| PERSIST_REQUIRES_RAW_DATE_MODIFIED.to_string())?;
-        let created_date = front_matter.raw_created;
+        let created_date = front_matter.raw_created.or(original_created);
 
         support::set_file_dates(&self.path, created_date, modified_date)?;
 
         Ok(())
     }
 
+    /// Puts back the body as it is on disk, undoing every change made to it this run. The
+    /// frontmatter is left as it is.
+    pub(crate) fn restore_body(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let full_content = support::read_contents_from_file(&self.path)?;
+        self.content = match yaml_frontmatter::find_yaml_section(&full_content) {
+            Ok(Some((_, after_yaml))) => after_yaml.to_string(),
+            _ => full_content,
+        };
+        self.wikilinks = self.process_wikilinks();
+        self.image_links.links = self.process_image_links();
+        Ok(())
+    }
+
     fn ensure_frontmatter(&mut self, operational_timezone: &str) {
         if self.front_matter.is_none() {
             let mut front_matter = FrontMatter::default();
+            front_matter.date_link_format = Some(self.date_link_format.clone());
             front_matter.set_date_created(
                 self.created_date_validation.file_system,
                 operational_timezone,
@@ -233,6 +394,38 @@ impl MarkdownFile {
         Ok(())
     }
 
+    pub(crate) fn mark_frontmatter_links_canonicalized(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        // Only reached when frontmatter already holds the rewritten properties.
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::FrontmatterLinksCanonicalized { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_frontmatter_link_values_fixed(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        // Only reached when frontmatter already holds the rewritten properties.
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::FrontmatterLinkValuesFixed { count });
+        Ok(())
+    }
+
     pub(crate) fn mark_image_reference_as_updated(
         &mut self,
         operational_timezone: &str,
@@ -264,6 +457,107 @@ impl MarkdownFile {
         Ok(())
     }
 
+    pub(crate) fn mark_admonitions_converted(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::AdmonitionsConverted { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_conflict_copy_merged(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::ConflictCopyMerged { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_duplicate_frontmatter_keys_fixed(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::DuplicateFrontmatterKeysFixed { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_aliases_generated(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        // Only reached when frontmatter already holds the generated aliases.
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::AliasesGenerated { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_entity_alias_added(
+        &mut self,
+        alias: &str,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        // Only reached when frontmatter already holds the new alias.
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons.push(PersistReason::EntityAliasAdded {
+            alias: alias.to_string(),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn mark_entity_index_links_added(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::EntityIndexLinksAdded { count });
+        Ok(())
+    }
+
     pub(crate) fn mark_phantom_links_resolved(
         &mut self,
         operational_timezone: &str,
@@ -280,6 +574,258 @@ impl MarkdownFile {
         Ok(())
     }
 
+    pub(crate) fn mark_periodic_note_linked(
+        &mut self,
+        parent: &str,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        // Only reached when frontmatter already holds the new link.
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::PeriodicNoteLinked {
+                parent: parent.to_string(),
+            });
+        Ok(())
+    }
+
+    pub(crate) fn mark_property_types_coerced(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::PropertyTypesCoerced { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_heading_links_retargeted(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::HeadingLinksRetargeted { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_lists_normalized(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::ListsNormalized { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_punctuation_normalized(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::PunctuationNormalized { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_block_ids_added(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::BlockIdsAdded { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_flashcard_ids_added(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::FlashcardIdsAdded { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_sections_split(
+        &mut self,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::SectionsSplit { count });
+        Ok(())
+    }
+
+    pub(crate) fn mark_custom_replacements_applied(
+        &mut self,
+        source: &str,
+        count: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::CustomReplacementsApplied {
+                source: source.to_string(),
+                count,
+            });
+        Ok(())
+    }
+
+    pub(crate) fn mark_date_mentions_linked(
+        &mut self,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons.push(PersistReason::DateMentionsLinked);
+        Ok(())
+    }
+
+    pub(crate) fn mark_heading_links_repaired(
+        &mut self,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::HeadingLinksRepaired);
+        Ok(())
+    }
+
+    pub(crate) fn mark_regex_replacements_applied(
+        &mut self,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::RegexReplacementsApplied);
+        Ok(())
+    }
+
+    pub(crate) fn mark_table_of_contents_updated(
+        &mut self,
+        action: TableOfContentsAction,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons
+            .push(PersistReason::TableOfContentsUpdated { action });
+        Ok(())
+    }
+
+    pub(crate) fn mark_self_links_cleaned(
+        &mut self,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons.push(PersistReason::SelfLinksCleaned);
+        Ok(())
+    }
+
+    pub(crate) fn mark_wikilinks_repaired(
+        &mut self,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        front_matter.set_date_modified_now(operational_timezone);
+        self.persist_reasons.push(PersistReason::WikilinksRepaired);
+        Ok(())
+    }
+
     fn process_wikilinks(&self) -> Wikilinks {
         let mut wikilinks = Wikilinks::default();
 
@@ -308,6 +854,7 @@ impl MarkdownFile {
         }
 
         let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut comment_excluder = CommentExcluder::new();
 
         for (line_idx, line) in self.content.lines().enumerate() {
             code_block_excluder.update(line);
@@ -315,7 +862,11 @@ impl MarkdownFile {
                 continue;
             }
 
-            let extracted = wikilink::extract_wikilinks(line);
+            // Wikilinks inside comments and raw HTML blocks are left alone, as Obsidian does.
+            let comment_spans = comment_excluder.excluded_spans(line);
+            let visible_line = text_excluder::mask_spans(line, &comment_spans);
+
+            let extracted = wikilink::extract_wikilinks(&visible_line);
             wikilinks.valid.extend(
                 extracted
                     .valid
@@ -341,9 +892,14 @@ impl MarkdownFile {
 
     fn process_image_links(&self) -> Vec<ImageLink> {
         let mut image_links = Vec::new();
+        let mut comment_excluder = CommentExcluder::new();
 
         for (line_idx, line) in self.content.lines().enumerate() {
-            for capture in IMAGE_REGEX.captures_iter(line) {
+            // Image references inside comments and raw HTML blocks are never rewritten.
+            let comment_spans = comment_excluder.excluded_spans(line);
+            let visible_line = text_excluder::mask_spans(line, &comment_spans);
+
+            for capture in IMAGE_REGEX.captures_iter(&visible_line) {
                 if let Some(raw_image_link) = capture.get(IMAGE_LINK_WHOLE_MATCH_CAPTURE_INDEX) {
                     let Ok(image_link) = ImageLink::new(
                         raw_image_link.as_str().to_string(),
@@ -370,6 +926,40 @@ impl MarkdownFile {
         self.frontmatter_line_count + line_idx + 1
     }
 
+    /// The zero-based body line a file `line_number` names, or `None` inside the frontmatter.
+    /// The inverse of `get_real_line_number` - replacements resolve their line through it, so
+    /// they can only ever touch the body.
+    pub(crate) const fn body_line_index(&self, line_number: usize) -> Option<usize> {
+        line_number.checked_sub(self.frontmatter_line_count + 1)
+    }
+
+    /// The byte offset of the start of each body line.
+    pub(crate) fn body_line_starts(&self) -> Vec<usize> {
+        iter::once(0)
+            .chain(
+                self.content
+                    .match_indices(NEWLINE)
+                    .map(|(offset, _)| offset + 1),
+            )
+            .collect()
+    }
+
+    /// The body span `replaceable` covers: its line resolves through `body_line_index`, and its
+    /// position counts from that line's entry in `line_starts`. `None` in the frontmatter or
+    /// past the last body line.
+    pub(crate) fn body_span(
+        &self,
+        line_starts: &[usize],
+        replaceable: &dyn ReplaceableContent,
+    ) -> Option<ByteSpan> {
+        let line_idx = self.body_line_index(replaceable.line_number())?;
+        let start = line_starts.get(line_idx)? + replaceable.position();
+        Some(ByteSpan {
+            start,
+            end: start + replaceable.matched_text().len(),
+        })
+    }
+
     pub(crate) const fn has_ambiguous_matches(&self) -> bool {
         !self.back_populate_matches.ambiguous.is_empty()
     }
@@ -385,6 +975,12 @@ impl MarkdownFile {
     pub(crate) const fn has_phantom_link_matches(&self) -> bool {
         !self.phantom_link_matches.is_empty()
     }
+
+    pub(crate) const fn has_self_link_matches(&self) -> bool { !self.self_link_matches.is_empty() }
+
+    pub(crate) const fn has_wikilink_repair_matches(&self) -> bool {
+        !self.wikilink_repair_matches.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -409,6 +1005,7 @@ mod tests {
     use super::date_validation::DateValidationIssue;
     use crate::constants::DEFAULT_TIMEZONE;
     use crate::constants::ERROR_NOT_FOUND;
+    use crate::constants::FORMAT_DATE;
     use crate::constants::FRONTMATTER_DELIMITER_LINE_COUNT;
     use crate::constants::PERSIST_REQUIRES_RAW_DATE_MODIFIED;
     use crate::constants::YAML_CLOSING_DELIMITER_NEWLINE;
@@ -487,7 +1084,8 @@ mod tests {
 
         for (content, expected_frontmatter_lines) in &test_cases {
             let file_path = create_test_file(content, temp_dir.path());
-            let markdown_file = MarkdownFile::new(file_path, "UTC").unwrap();
+            let markdown_file =
+                MarkdownFile::new(file_path, "UTC", FORMAT_DATE, None, &[]).unwrap();
             assert_eq!(
                 markdown_file.frontmatter_line_count, *expected_frontmatter_lines,
                 "Failed for content:\n{content}"
@@ -750,6 +1348,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg_attr(
+        target_os = "linux",
+        ignore = "requires filesystem access unavailable on Linux CI"
+    )]
+    fn test_back_populate_persist_keeps_created_time() -> Result<(), Box<dyn Error + Send + Sync>> {
+        let temp_dir = TempDir::new()?;
+        let created_date = test_utils::parse_datetime("2024-01-05 10:00:00");
+        let file_path = TestFileBuilder::new()
+            .with_matching_dates(created_date)
+            .with_content("mentions Topic")
+            .create(&temp_dir, "note.md");
+
+        let mut markdown_file = test_utils::get_test_markdown_file(file_path.clone());
+        markdown_file.content = "mentions [[Topic]]".to_string();
+        markdown_file.mark_as_back_populated(DEFAULT_TIMEZONE)?;
+        assert_eq!(
+            markdown_file.front_matter.as_ref().unwrap().raw_created,
+            None,
+            "a plain back populate doesn't set the created date"
+        );
+        markdown_file.persist()?;
+
+        let created_time_after = FileTime::from_creation_time(&fs::metadata(&file_path)?).unwrap();
+        assert_eq!(created_time_after.unix_seconds(), created_date.timestamp());
+        Ok(())
+    }
+
     #[test]
     fn test_disallow_persist_if_date_modified_not_set() {
         let temp_dir = TempDir::new().unwrap();
@@ -878,7 +1504,8 @@ mod tests {
             )
             .create(&temp_dir, "test.md");
 
-        let markdown_file = MarkdownFile::new(file_path, DEFAULT_TIMEZONE).unwrap();
+        let markdown_file =
+            MarkdownFile::new(file_path, DEFAULT_TIMEZONE, FORMAT_DATE, None, &[]).unwrap();
         let valid_wikilinks = markdown_file.wikilinks.valid;
 
         // `valid_wikilinks` includes the file name and inline wikilink.
@@ -932,7 +1559,8 @@ Also linking to [[Alias One]] which is defined in frontmatter."
             )
             .create(&temp_dir, "test_note.md");
 
-        let markdown_file = MarkdownFile::new(file_path, DEFAULT_TIMEZONE).unwrap();
+        let markdown_file =
+            MarkdownFile::new(file_path, DEFAULT_TIMEZONE, FORMAT_DATE, None, &[]).unwrap();
         let wikilinks = markdown_file.wikilinks.valid;
 
         // Collect unique target-display pairs
@@ -978,7 +1606,13 @@ Also linking to [[Alias One]] which is defined in frontmatter."
     #[test]
     fn test_config_file_not_found() {
         let nonexistent_path = PathBuf::from("nonexistent/config.md");
-        let result = MarkdownFile::new(nonexistent_path.clone(), DEFAULT_TIMEZONE);
+        let result = MarkdownFile::new(
+            nonexistent_path.clone(),
+            DEFAULT_TIMEZONE,
+            FORMAT_DATE,
+            None,
+            &[],
+        );
 
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains(&format!(
@@ -1061,7 +1695,7 @@ Also linking to [[Alias One]] which is defined in frontmatter."
             .with_aliases(aliases.as_ref().unwrap_or(&Vec::new()).clone())
             .create(&temp_dir, "test file.md");
 
-        let markdown_file = MarkdownFile::new(file_path, "UTC").unwrap();
+        let markdown_file = MarkdownFile::new(file_path, "UTC", FORMAT_DATE, None, &[]).unwrap();
         let extracted = markdown_file.process_wikilinks();
         let image_links = markdown_file.process_image_links();
 
@@ -1113,7 +1747,7 @@ Also linking to [[Alias One]] which is defined in frontmatter."
             .with_content(content.to_string())
             .create(&temp_dir, "test.md");
 
-        let markdown_file = MarkdownFile::new(file_path, "UTC").unwrap();
+        let markdown_file = MarkdownFile::new(file_path, "UTC", FORMAT_DATE, None, &[]).unwrap();
         let extracted = markdown_file.process_wikilinks();
         let image_links = markdown_file.process_image_links();
 
@@ -1167,7 +1801,7 @@ Also linking to [[Alias One]] which is defined in frontmatter."
             .with_content(content.to_string())
             .create(&temp_dir, "test.md");
 
-        let markdown_file = MarkdownFile::new(file_path, "UTC").unwrap();
+        let markdown_file = MarkdownFile::new(file_path, "UTC", FORMAT_DATE, None, &[]).unwrap();
         let extracted = markdown_file.process_wikilinks();
         let image_links = markdown_file.process_image_links();
 
@@ -1201,7 +1835,7 @@ Also linking to [[Alias One]] which is defined in frontmatter."
             .with_content(content.to_string())
             .create(&temp_dir, "test.md");
 
-        let markdown_file = MarkdownFile::new(file_path, "UTC").unwrap();
+        let markdown_file = MarkdownFile::new(file_path, "UTC", FORMAT_DATE, None, &[]).unwrap();
         let extracted = markdown_file.process_wikilinks();
         let image_links = markdown_file.process_image_links();
 
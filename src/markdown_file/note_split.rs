@@ -0,0 +1,236 @@
+use anyhow::Result as AnyhowResult;
+
+use super::MarkdownFile;
+use super::constants::SPLIT_HEADING_LEVEL;
+use super::constants::TABLE_OF_CONTENTS_HEADING;
+use super::table_of_contents;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::IMAGE_EMBED_MARKER;
+use crate::constants::NEWLINE;
+use crate::constants::OPENING_WIKILINK;
+
+/// One `## heading` section of a note listed in `split_notes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoteSection {
+    pub heading:      String,
+    /// The heading as a `[[note#heading]]` link writes it.
+    pub heading_link: String,
+    /// Lines `start..end` of the note body, the heading line included.
+    pub start:        usize,
+    pub end:          usize,
+    /// The headings nested in the section, as links write them.
+    pub subheadings:  Vec<String>,
+}
+
+impl MarkdownFile {
+    /// Every `##` section outside code blocks, each running to the next `#` or `##` heading.
+    /// The managed table of contents isn't a section of its own.
+    pub(crate) fn h2_sections(&self) -> Vec<NoteSection> {
+        let lines: Vec<&str> = self.content.lines().collect();
+        let headings = table_of_contents::headings(&lines);
+
+        let mut sections = Vec::new();
+        for (heading_idx, &(line_idx, level, text)) in headings.iter().enumerate() {
+            if level != SPLIT_HEADING_LEVEL || lines[line_idx].trim() == TABLE_OF_CONTENTS_HEADING {
+                continue;
+            }
+
+            let nested: Vec<&(usize, usize, &str)> = headings[heading_idx + 1..]
+                .iter()
+                .take_while(|&&(_, nested_level, _)| nested_level > SPLIT_HEADING_LEVEL)
+                .collect();
+            let end = headings
+                .get(heading_idx + 1 + nested.len())
+                .map_or(lines.len(), |&(next_idx, ..)| next_idx);
+
+            sections.push(NoteSection {
+                heading: text.to_string(),
+                heading_link: table_of_contents::heading_link_target(text),
+                start: line_idx,
+                end,
+                subheadings: nested
+                    .iter()
+                    .map(|&&(_, _, nested_text)| {
+                        table_of_contents::heading_link_target(nested_text)
+                    })
+                    .collect(),
+            });
+        }
+        sections
+    }
+
+    /// The body of `section` without its heading line - the content of the note it becomes.
+    pub(crate) fn section_body(&self, section: &NoteSection) -> String {
+        let lines: Vec<&str> = self.content.lines().collect();
+        lines[section.start + 1..section.end]
+            .join(&NEWLINE.to_string())
+            .trim()
+            .to_string()
+    }
+
+    /// Replaces each section of `extractions` with an embed of the note it was moved to, given
+    /// as `(section, note name)`.
+    pub(crate) fn extract_sections(
+        &mut self,
+        extractions: &[(NoteSection, String)],
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        if extractions.is_empty() {
+            return Ok(());
+        }
+
+        let lines: Vec<&str> = self.content.lines().collect();
+        let mut updated_lines: Vec<String> = Vec::new();
+        let mut line_idx = 0;
+        for (section, note_name) in extractions {
+            updated_lines.extend(
+                lines[line_idx..section.start]
+                    .iter()
+                    .map(ToString::to_string),
+            );
+            updated_lines.push(format!(
+                "{IMAGE_EMBED_MARKER}{OPENING_WIKILINK}{note_name}{CLOSING_WIKILINK}"
+            ));
+            // Keeps the blank line that separated the section from what follows.
+            if section.end < lines.len()
+                && lines[section.start..section.end]
+                    .last()
+                    .is_some_and(|line| line.trim().is_empty())
+            {
+                updated_lines.push(String::new());
+            }
+            line_idx = section.end;
+        }
+        updated_lines.extend(lines[line_idx..].iter().map(ToString::to_string));
+
+        let mut updated_content = updated_lines.join(&NEWLINE.to_string());
+        if self.content.ends_with(NEWLINE) {
+            updated_content.push(NEWLINE);
+        }
+        self.content = updated_content;
+        self.wikilinks = self.process_wikilinks();
+        self.image_links.links = self.process_image_links();
+
+        self.mark_sections_split(extractions.len(), operational_timezone)
+    }
+
+    /// Points every content wikilink for which `retarget` returns a new target at it, keeping
+    /// the display text and any embed marker.
+    pub(crate) fn retarget_heading_links(
+        &mut self,
+        retarget: impl Fn(&str) -> Option<String>,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        let first_line_number = self.get_real_line_number(0);
+        let mut replacements: Vec<(usize, usize, usize, String)> = Vec::new();
+        self.for_each_content_wikilink(|line_number, line, spanned_wikilink| {
+            let Some(new_target) = retarget(&spanned_wikilink.wikilink.target) else {
+                return;
+            };
+            let (start, end) = spanned_wikilink.span;
+            let replacement =
+                line[start..end].replacen(&spanned_wikilink.wikilink.target, &new_target, 1);
+            replacements.push((line_number - first_line_number, start, end, replacement));
+        });
+        if replacements.is_empty() {
+            return Ok(());
+        }
+
+        let mut lines: Vec<String> = self.content.lines().map(ToString::to_string).collect();
+        // Right to left, so earlier spans on a line stay valid.
+        for (line_idx, start, end, replacement) in replacements.iter().rev() {
+            if let Some(line) = lines.get_mut(*line_idx) {
+                line.replace_range(*start..*end, replacement);
+            }
+        }
+
+        let mut updated_content = lines.join(&NEWLINE.to_string());
+        if self.content.ends_with(NEWLINE) {
+            updated_content.push(NEWLINE);
+        }
+        self.content = updated_content;
+        self.wikilinks = self.process_wikilinks();
+
+        self.mark_heading_links_retargeted(replacements.len(), operational_timezone)
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::constants::FORMAT_DATE;
+    use crate::markdown_file::MarkdownFile;
+    use crate::markdown_file::PersistReason;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_h2_sections_extracted_and_links_retargeted() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_title("Trip".to_string())
+            .with_content(
+                "# Trip\n\
+                 intro [[#Day 2]]\n\
+                 ## Day 1\n\
+                 ### Louvre\n\
+                 ![[louvre.jpg]]\n\
+                 \n\
+                 ## Day 2\n\
+                 ```\n\
+                 ## not a heading\n\
+                 ```\n\
+                 done",
+            )
+            .create(&temp_dir, "trip.md");
+        let mut markdown_file =
+            MarkdownFile::new(file_path, DEFAULT_TIMEZONE, FORMAT_DATE, None, &[]).unwrap();
+
+        let sections = markdown_file.h2_sections();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].heading, "Day 1");
+        assert_eq!(sections[0].subheadings, vec!["Louvre"]);
+        assert_eq!(
+            markdown_file.section_body(&sections[0]),
+            "### Louvre\n![[louvre.jpg]]"
+        );
+        assert_eq!(sections[1].end, markdown_file.content.lines().count());
+
+        markdown_file
+            .retarget_heading_links(
+                |target| (target == "#Day 2").then(|| "Day 2".to_string()),
+                DEFAULT_TIMEZONE,
+            )
+            .unwrap();
+        let extractions: Vec<_> = sections
+            .into_iter()
+            .map(|section| {
+                let name = section.heading.clone();
+                (section, name)
+            })
+            .collect();
+        markdown_file
+            .extract_sections(&extractions, DEFAULT_TIMEZONE)
+            .unwrap();
+
+        assert_eq!(
+            markdown_file.content.trim_end(),
+            "# Trip\nintro [[Day 2]]\n![[Day 1]]\n\n![[Day 2]]"
+        );
+        assert!(
+            markdown_file
+                .persist_reasons
+                .contains(&PersistReason::SectionsSplit { count: 2 })
+        );
+        assert!(
+            markdown_file
+                .persist_reasons
+                .contains(&PersistReason::HeadingLinksRetargeted { count: 1 })
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+use serde_yaml::Value;
+
+use super::MarkdownFile;
+use crate::constants::FRONTMATTER_MISSING_AFTER_ENSURE;
+
+impl MarkdownFile {
+    /// Adds `link` to `property`, keeping any value already there - a single value becomes a
+    /// list holding both.
+    pub(crate) fn add_periodic_note_link(
+        &mut self,
+        property: &str,
+        link: &str,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        self.ensure_frontmatter(operational_timezone);
+
+        let front_matter = self
+            .front_matter
+            .as_mut()
+            .ok_or_else(|| anyhow!("{FRONTMATTER_MISSING_AFTER_ENSURE} {}", self.path.display()))?;
+        let link_value = Value::String(link.to_string());
+        let value = match front_matter.property(property) {
+            Some(Value::Sequence(values)) => {
+                let mut values = values.clone();
+                values.push(link_value);
+                Value::Sequence(values)
+            },
+            Some(Value::String(existing)) if !existing.trim().is_empty() => {
+                Value::Sequence(vec![Value::String(existing.clone()), link_value])
+            },
+            _ => link_value,
+        };
+        front_matter.set_property(property, value);
+
+        self.mark_periodic_note_linked(link, operational_timezone)
+    }
+}
@@ -5,7 +5,9 @@ use super::MarkdownFile;
 use super::back_populate;
 use super::replaceable_content::MatchType;
 use super::replaceable_content::ReplaceableContent;
+use super::text_excluder;
 use super::text_excluder::CodeBlockExcluder;
+use super::text_excluder::CommentExcluder;
 use crate::constants::ESCAPED_PIPE;
 use crate::constants::PIPE;
 use crate::support;
@@ -94,6 +96,7 @@ impl MarkdownFile {
         mut callback: impl FnMut(usize, &str, SpannedWikilink),
     ) {
         let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut comment_excluder = CommentExcluder::new();
 
         for (line_idx, line) in self.content.lines().enumerate() {
             code_block_excluder.update(line);
@@ -101,7 +104,10 @@ impl MarkdownFile {
                 continue;
             }
 
-            for spanned_wikilink in wikilink::extract_wikilinks(line).valid {
+            let comment_spans = comment_excluder.excluded_spans(line);
+            let visible_line = text_excluder::mask_spans(line, &comment_spans);
+
+            for spanned_wikilink in wikilink::extract_wikilinks(&visible_line).valid {
                 callback(self.get_real_line_number(line_idx), line, spanned_wikilink);
             }
         }
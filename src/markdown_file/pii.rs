@@ -0,0 +1,112 @@
+use regex::Regex;
+
+use super::MarkdownFile;
+use super::constants::PII_EMAIL_ADDRESS;
+use super::constants::PII_NATIONAL_ID;
+use super::constants::PII_PHONE_NUMBER;
+use crate::support::EMAIL_REGEX;
+use crate::support::NATIONAL_ID_REGEX;
+use crate::support::PHONE_NUMBER_REGEX;
+use crate::validated_config::PiiPatternRule;
+
+/// Finds one kind of personal data in a line of a note. `detect_pii` runs the built-in
+/// detectors and one per `pii_patterns` entry; anything else that implements this can be
+/// added to the list it passes to `pii_counts`.
+pub(crate) trait PiiDetector {
+    /// What the report calls a match.
+    fn name(&self) -> &str;
+
+    /// How many times this kind of personal data appears in `line`.
+    fn count(&self, line: &str) -> usize;
+}
+
+/// The detectors `detect_pii` always runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BuiltInPiiDetector {
+    EmailAddress,
+    NationalId,
+    PhoneNumber,
+}
+
+impl BuiltInPiiDetector {
+    pub(crate) const ALL: [Self; 3] = [Self::EmailAddress, Self::PhoneNumber, Self::NationalId];
+
+    fn regex(self) -> &'static Regex {
+        match self {
+            Self::EmailAddress => &EMAIL_REGEX,
+            Self::NationalId => &NATIONAL_ID_REGEX,
+            Self::PhoneNumber => &PHONE_NUMBER_REGEX,
+        }
+    }
+}
+
+impl PiiDetector for BuiltInPiiDetector {
+    fn name(&self) -> &str {
+        match self {
+            Self::EmailAddress => PII_EMAIL_ADDRESS,
+            Self::NationalId => PII_NATIONAL_ID,
+            Self::PhoneNumber => PII_PHONE_NUMBER,
+        }
+    }
+
+    fn count(&self, line: &str) -> usize { self.regex().find_iter(line).count() }
+}
+
+impl PiiDetector for PiiPatternRule {
+    fn name(&self) -> &str { &self.name }
+
+    fn count(&self, line: &str) -> usize { self.regex.find_iter(line).count() }
+}
+
+impl MarkdownFile {
+    /// How many matches each of `detectors` finds in the note's body, in the same order.
+    pub(crate) fn pii_counts(&self, detectors: &[&dyn PiiDetector]) -> Vec<usize> {
+        detectors
+            .iter()
+            .map(|detector| self.content.lines().map(|line| detector.count(line)).sum())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use super::BuiltInPiiDetector;
+    use super::PiiDetector;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::PiiPatternRule;
+
+    #[test]
+    fn test_pii_counts_per_detector() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "Call Dana on 555-123-4567 or (555) 987 6543.\n\
+                 dana@example.com, cc ops@example.org\n\
+                 SSN 123-45-6789, NINO AB 12 34 56 C\n\
+                 Employee E-204519 starts 2024-03-01"
+                    .to_string(),
+            )
+            .create(&temp_dir, "Onboarding.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+        let employee_id = PiiPatternRule {
+            name:  "employee id".to_string(),
+            regex: Regex::new(r"\bE-\d{6}\b").unwrap(),
+        };
+
+        let mut detectors: Vec<&dyn PiiDetector> = BuiltInPiiDetector::ALL
+            .iter()
+            .map(|detector| detector as &dyn PiiDetector)
+            .collect();
+        detectors.push(&employee_id);
+
+        assert_eq!(markdown_file.pii_counts(&detectors), vec![2, 2, 2, 1]);
+    }
+}
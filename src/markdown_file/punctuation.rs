@@ -0,0 +1,229 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use anyhow::Result as AnyhowResult;
+
+use super::MarkdownFile;
+use super::back_populate;
+use super::constants::APOSTROPHE;
+use super::constants::CURLY_DOUBLE_QUOTES;
+use super::constants::CURLY_SINGLE_QUOTES;
+use super::constants::DOUBLE_QUOTE;
+use super::constants::EM_DASH;
+use super::constants::EM_DASH_VARIANTS;
+use super::constants::HYPHEN;
+use super::constants::HYPHEN_VARIANTS;
+use super::constants::NON_BREAKING_SPACES;
+use super::constants::ZERO_WIDTH_CHARACTERS;
+use super::text_excluder;
+use super::text_excluder::CodeBlockExcluder;
+use crate::constants::CURLY_QUOTE;
+use crate::constants::DASH_VARIANT;
+use crate::constants::NON_BREAKING_SPACE;
+use crate::constants::SPACE;
+use crate::constants::ZERO_WIDTH_CHARACTER;
+use crate::support::MARKDOWN_REGEX;
+use crate::wikilink;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PunctuationKind {
+    /// `‘ ’ “ ”` and their low and reversed forms become `'` and `"`.
+    CurlyQuote,
+    /// Look-alike hyphens become `-` and em dash look-alikes become `—`.
+    DashVariant,
+    /// Non-breaking spaces become plain spaces.
+    NonBreakingSpace,
+    /// Zero-width spaces, word joiners and stray byte order marks are removed.
+    ZeroWidthCharacter,
+}
+
+impl Display for PunctuationKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CurlyQuote => f.write_str(CURLY_QUOTE),
+            Self::DashVariant => f.write_str(DASH_VARIANT),
+            Self::NonBreakingSpace => f.write_str(NON_BREAKING_SPACE),
+            Self::ZeroWidthCharacter => f.write_str(ZERO_WIDTH_CHARACTER),
+        }
+    }
+}
+
+/// `count` characters of one kind normalized on one line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PunctuationNormalization {
+    pub line_number: usize,
+    pub kind:        PunctuationKind,
+    pub count:       usize,
+}
+
+impl MarkdownFile {
+    /// Rewrites curly quotes, dash look-alikes, non-breaking spaces and zero-width characters in
+    /// the note body. Code blocks, inline code, wikilinks and markdown links are left alone so
+    /// code and link targets still match the files they name, and encrypted blocks so they
+    /// still decrypt. Wikilinks and image links are reparsed afterwards so their line text
+    /// matches the new content.
+    pub(crate) fn normalize_punctuation(&mut self, operational_timezone: &str) -> AnyhowResult<()> {
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let encrypted_blocks = self.encrypted_blocks();
+        let mut normalized_content = String::with_capacity(self.content.len());
+        let mut normalizations = Vec::new();
+
+        for (line_idx, line_with_ending) in self.content.split_inclusive('\n').enumerate() {
+            let line = line_with_ending.trim_end_matches(['\r', '\n']);
+            code_block_excluder.update(line);
+            let line_number = self.get_real_line_number(line_idx);
+            if code_block_excluder.is_in_code_block()
+                || encrypted_blocks
+                    .iter()
+                    .any(|block| block.contains(line_number))
+            {
+                normalized_content.push_str(line_with_ending);
+                continue;
+            }
+
+            let protected_spans = protected_spans(line);
+            let mut line_normalizations: Vec<PunctuationNormalization> = Vec::new();
+            for (byte_offset, ch) in line_with_ending.char_indices() {
+                let normalized = normalized_char(ch).filter(|_| {
+                    !back_populate::range_overlaps(
+                        &protected_spans,
+                        byte_offset,
+                        byte_offset + ch.len_utf8(),
+                    )
+                });
+                let Some((kind, replacement)) = normalized else {
+                    normalized_content.push(ch);
+                    continue;
+                };
+
+                normalized_content.extend(replacement);
+                if let Some(existing) = line_normalizations.iter_mut().find(|n| n.kind == kind) {
+                    existing.count += 1;
+                } else {
+                    line_normalizations.push(PunctuationNormalization {
+                        line_number,
+                        kind,
+                        count: 1,
+                    });
+                }
+            }
+            normalizations.extend(line_normalizations);
+        }
+
+        if normalizations.is_empty() {
+            return Ok(());
+        }
+
+        self.content = normalized_content;
+        self.wikilinks = self.process_wikilinks();
+        self.image_links.links = self.process_image_links();
+
+        let count = normalizations.iter().map(|n| n.count).sum();
+        self.punctuation_normalizations = normalizations;
+        self.mark_punctuation_normalized(count, operational_timezone)
+    }
+}
+
+/// Inline code, wikilinks and markdown links on one line.
+pub(super) fn protected_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut spans = text_excluder::find_inline_code_spans(line);
+    spans.extend(
+        wikilink::extract_wikilinks(line)
+            .valid
+            .iter()
+            .map(|spanned| spanned.span),
+    );
+    spans.extend(
+        MARKDOWN_REGEX
+            .find_iter(line)
+            .map(|markdown_link| (markdown_link.start(), markdown_link.end())),
+    );
+    spans
+}
+
+/// The kind and plain replacement for a character that should be normalized; a `None`
+/// replacement removes the character.
+fn normalized_char(ch: char) -> Option<(PunctuationKind, Option<char>)> {
+    if CURLY_SINGLE_QUOTES.contains(&ch) {
+        Some((PunctuationKind::CurlyQuote, Some(APOSTROPHE)))
+    } else if CURLY_DOUBLE_QUOTES.contains(&ch) {
+        Some((PunctuationKind::CurlyQuote, Some(DOUBLE_QUOTE)))
+    } else if HYPHEN_VARIANTS.contains(&ch) {
+        Some((PunctuationKind::DashVariant, Some(HYPHEN)))
+    } else if EM_DASH_VARIANTS.contains(&ch) {
+        Some((PunctuationKind::DashVariant, Some(EM_DASH)))
+    } else if NON_BREAKING_SPACES.contains(&ch) {
+        Some((PunctuationKind::NonBreakingSpace, Some(SPACE)))
+    } else if ZERO_WIDTH_CHARACTERS.contains(&ch) {
+        Some((PunctuationKind::ZeroWidthCharacter, None))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use super::PunctuationKind;
+    use crate::markdown_file::PersistReason;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_normalize_punctuation() {
+        let (temp_dir, validated_config, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "\u{201C}Tom\u{2019}s\u{201D} note\u{2015}a\u{00A0}re\u{200B}view\n\
+                 [[Tom\u{2019}s note]] and `it\u{2019}s`\n\
+                 ```\n\
+                 it\u{2019}s code\n\
+                 ```\n\
+                 co\u{2010}op\u{200D}",
+            )
+            .create(&temp_dir, "review.md");
+        let mut markdown_file = test_support::get_test_markdown_file(file_path);
+
+        markdown_file
+            .normalize_punctuation(validated_config.operational_timezone())
+            .unwrap();
+
+        assert_eq!(
+            markdown_file.content,
+            "\"Tom's\" note\u{2014}a review\n\
+             [[Tom\u{2019}s note]] and `it\u{2019}s`\n\
+             ```\n\
+             it\u{2019}s code\n\
+             ```\n\
+             co-op\u{200D}\n"
+        );
+
+        let kinds: Vec<(PunctuationKind, usize)> = markdown_file
+            .punctuation_normalizations
+            .iter()
+            .map(|n| (n.kind, n.count))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (PunctuationKind::CurlyQuote, 3),
+                (PunctuationKind::DashVariant, 1),
+                (PunctuationKind::NonBreakingSpace, 1),
+                (PunctuationKind::ZeroWidthCharacter, 1),
+                (PunctuationKind::DashVariant, 1),
+            ]
+        );
+        assert!(
+            markdown_file
+                .persist_reasons
+                .contains(&PersistReason::PunctuationNormalized { count: 7 })
+        );
+    }
+}
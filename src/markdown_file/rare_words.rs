@@ -0,0 +1,145 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use super::MarkdownFile;
+use super::constants::MAX_HEADING_LEVEL;
+use super::text_excluder::CodeBlockExcluder;
+use crate::constants::ALIAS;
+use crate::constants::HASH;
+use crate::constants::HEADING;
+use crate::constants::TITLE;
+
+/// Where a piece of text checked for rare words comes from. These are the texts that become
+/// wikilink targets and display text, so a typo in one spreads into every link to the note.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RareWordSource {
+    Alias,
+    Heading,
+    Title,
+}
+
+impl Display for RareWordSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Alias => f.write_str(ALIAS),
+            Self::Heading => f.write_str(HEADING),
+            Self::Title => f.write_str(TITLE),
+        }
+    }
+}
+
+/// One title, heading or alias to check for rare words; `line_number` is only set for headings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RareWordText {
+    pub source:      RareWordSource,
+    pub line_number: Option<usize>,
+    pub text:        String,
+}
+
+impl MarkdownFile {
+    /// Collects the file stem, every heading outside code blocks and every frontmatter alias.
+    pub(crate) fn rare_word_texts(&self) -> Vec<RareWordText> {
+        let mut texts = Vec::new();
+
+        if let Some(stem) = self.path.file_stem() {
+            texts.push(RareWordText {
+                source:      RareWordSource::Title,
+                line_number: None,
+                text:        stem.to_string_lossy().into_owned(),
+            });
+        }
+
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        for (line_idx, line) in self.content.lines().enumerate() {
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+
+            if let Some(heading) = heading_text(line) {
+                texts.push(RareWordText {
+                    source:      RareWordSource::Heading,
+                    line_number: Some(self.get_real_line_number(line_idx)),
+                    text:        heading.to_string(),
+                });
+            }
+        }
+
+        if let Some(aliases) = self
+            .front_matter
+            .as_ref()
+            .and_then(|front_matter| front_matter.aliases())
+        {
+            texts.extend(aliases.iter().map(|alias| RareWordText {
+                source:      RareWordSource::Alias,
+                line_number: None,
+                text:        alias.clone(),
+            }));
+        }
+
+        texts
+    }
+}
+
+/// Text of an ATX heading (`## Heading ##`) without its markers, or `None` for other lines.
+pub(super) fn heading_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == HASH).count();
+    if level == 0 || level > MAX_HEADING_LEVEL {
+        return None;
+    }
+
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let heading = rest.trim().trim_end_matches(HASH).trim_end();
+    (!heading.is_empty()).then_some(heading)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RareWordSource;
+    use super::heading_text;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_heading_text() {
+        assert_eq!(heading_text("## Soup Recipes ##"), Some("Soup Recipes"));
+        assert_eq!(heading_text("#tag"), None);
+        assert_eq!(heading_text("####### too deep"), None);
+        assert_eq!(heading_text("#"), None);
+    }
+
+    #[test]
+    fn test_rare_word_texts() {
+        let (temp_dir, _, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_aliases(vec!["Soups".to_string()])
+            .with_content("# Soup Recipes\n```\n# not a heading\n```\ntext")
+            .create(&temp_dir, "Soup.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let texts = markdown_file.rare_word_texts();
+
+        let sources: Vec<(RareWordSource, &str)> = texts
+            .iter()
+            .map(|text| (text.source, text.text.as_str()))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                (RareWordSource::Title, "Soup"),
+                (RareWordSource::Heading, "Soup Recipes"),
+                (RareWordSource::Alias, "Soups"),
+            ]
+        );
+        assert!(texts[1].line_number.is_some());
+    }
+}
@@ -0,0 +1,233 @@
+use super::MarkdownFile;
+use super::back_populate;
+use super::rare_words;
+use super::replaceable_content::MatchType;
+use super::replaceable_content::ReplaceableContent;
+use super::text_excluder;
+use super::text_excluder::CodeBlockExcluder;
+use super::text_excluder::CommentExcluder;
+use super::text_excluder::MathBlockExcluder;
+use crate::config::ReplacementScope;
+use crate::support;
+use crate::support::MARKDOWN_REGEX;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink;
+
+/// One match of a `replacements` rule; `pattern` is the rule's source so the report can show
+/// which rule made the change.
+#[derive(Clone, Debug)]
+pub struct RegexReplacementMatch {
+    pub found_text:    String,
+    pub line_number:   usize,
+    pub pattern:       String,
+    pub position:      usize,
+    pub relative_path: String,
+    pub replacement:   String,
+}
+
+impl ReplaceableContent for RegexReplacementMatch {
+    fn line_number(&self) -> usize { self.line_number }
+
+    fn position(&self) -> usize { self.position }
+
+    fn get_replacement(&self) -> String { self.replacement.clone() }
+
+    fn matched_text(&self) -> String { self.found_text.clone() }
+
+    fn match_type(&self) -> MatchType { MatchType::RegexReplacement }
+}
+
+impl MarkdownFile {
+    /// Runs every `replacements` rule over the note body. All rules match the original text, so
+    /// one rule never rewrites another rule's output, and where two matches overlap the rule
+    /// listed first wins. Matches that leave the text unchanged are dropped.
+    pub(crate) fn find_regex_replacement_matches(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Vec<RegexReplacementMatch> {
+        let rules = validated_config.replacement_rules();
+        if rules.is_empty() {
+            return Vec::new();
+        }
+
+        let relative_path =
+            support::format_relative_path(&self.path, validated_config.obsidian_path());
+        let mut code_block_excluder = CodeBlockExcluder::new();
+        let mut comment_excluder = CommentExcluder::new();
+        let mut math_block_excluder = MathBlockExcluder::new();
+        let mut matches = Vec::new();
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            if line.trim().is_empty() {
+                // Blank lines end raw HTML blocks.
+                comment_excluder.excluded_spans(line);
+                continue;
+            }
+
+            code_block_excluder.update(line);
+            if code_block_excluder.is_in_code_block() {
+                continue;
+            }
+
+            let is_in_math_block = validated_config.exclude_math() && {
+                math_block_excluder.update(line);
+                math_block_excluder.is_in_math_block()
+            };
+            let comment_spans = comment_excluder.excluded_spans(line);
+            let is_heading = rare_words::heading_text(line).is_some();
+
+            let code_zones = text_excluder::find_inline_code_spans(line);
+            let text_zones =
+                text_exclusion_zones(line, &code_zones, comment_spans, validated_config);
+
+            let mut line_matches: Vec<RegexReplacementMatch> = Vec::new();
+            for rule in rules {
+                let exclusion_zones = match rule.scope {
+                    ReplacementScope::Content => &code_zones,
+                    ReplacementScope::Headings if !is_heading => continue,
+                    ReplacementScope::Headings | ReplacementScope::Text if is_in_math_block => {
+                        continue;
+                    },
+                    ReplacementScope::Headings | ReplacementScope::Text => &text_zones,
+                };
+
+                for captures in rule.regex.captures_iter(line) {
+                    let Some(whole_match) = captures.get(0) else {
+                        continue;
+                    };
+                    let (start, end) = (whole_match.start(), whole_match.end());
+                    if whole_match.is_empty()
+                        || back_populate::range_overlaps(exclusion_zones, start, end)
+                        || line_matches.iter().any(|m| {
+                            back_populate::range_overlaps(
+                                &[(m.position, m.position + m.found_text.len())],
+                                start,
+                                end,
+                            )
+                        })
+                    {
+                        continue;
+                    }
+
+                    let mut replacement = String::new();
+                    captures.expand(&rule.replacement, &mut replacement);
+                    if replacement == whole_match.as_str() {
+                        continue;
+                    }
+
+                    line_matches.push(RegexReplacementMatch {
+                        found_text: whole_match.as_str().to_string(),
+                        line_number: self.get_real_line_number(line_idx),
+                        pattern: rule.regex.as_str().to_string(),
+                        position: start,
+                        relative_path: relative_path.clone(),
+                        replacement,
+                    });
+                }
+            }
+
+            line_matches.sort_by_key(|m| m.position);
+            matches.extend(line_matches);
+        }
+
+        matches
+    }
+}
+
+/// Spans the `Text` and `Headings` scopes skip: inline code, HTML comments, wikilinks (valid or
+/// not), markdown links and, with `exclude_math` on, inline math.
+pub(super) fn text_exclusion_zones(
+    line: &str,
+    code_zones: &[(usize, usize)],
+    comment_spans: Vec<(usize, usize)>,
+    validated_config: &ValidatedConfig,
+) -> Vec<(usize, usize)> {
+    let mut exclusion_zones = comment_spans;
+    exclusion_zones.extend_from_slice(code_zones);
+
+    let extracted_wikilinks = wikilink::extract_wikilinks(line);
+    exclusion_zones.extend(extracted_wikilinks.valid.iter().map(|spanned| spanned.span));
+    exclusion_zones.extend(
+        extracted_wikilinks
+            .invalid
+            .iter()
+            .map(|invalid| invalid.span),
+    );
+
+    exclusion_zones.extend(
+        MARKDOWN_REGEX
+            .find_iter(line)
+            .map(|markdown_link| (markdown_link.start(), markdown_link.end())),
+    );
+
+    if validated_config.exclude_math() {
+        exclusion_zones.extend(text_excluder::find_inline_math_spans(line));
+    }
+
+    exclusion_zones
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use crate::config::ReplacementScope;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::RegexReplacementRule;
+
+    fn rule(pattern: &str, replacement: &str, scope: ReplacementScope) -> RegexReplacementRule {
+        RegexReplacementRule {
+            regex: Regex::new(pattern).unwrap(),
+            replacement: replacement.to_string(),
+            scope,
+        }
+    }
+
+    #[test]
+    fn test_find_regex_replacement_matches_respects_scope() {
+        let temp_dir = TempDir::new().unwrap();
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.replacement_rules(vec![
+                    rule(r"\bteh\b", "the", ReplacementScope::Text),
+                    rule(r"TODO", "To do", ReplacementScope::Headings),
+                    rule(r"(\d+)-(\d+)", "$2-$1", ReplacementScope::Content),
+                ]);
+            })
+            .unwrap();
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "# TODO teh list\n\
+                 teh [[teh note]] and `teh` and [teh](teh.md) TODO\n\
+                 ```\n\
+                 teh 1-2\n\
+                 ```\n\
+                 <!-- teh --> 3-4 `5-6`",
+            )
+            .create(&temp_dir, "list.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let matches = markdown_file.find_regex_replacement_matches(&validated_config);
+
+        let found: Vec<(usize, &str, &str)> = matches
+            .iter()
+            .map(|m| (m.position, m.found_text.as_str(), m.replacement.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, "TODO", "To do"),
+                (7, "teh", "the"),
+                (0, "teh", "the"),
+                (13, "3-4", "4-3"),
+            ]
+        );
+    }
+}
@@ -1,9 +1,61 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MatchType {
+    AdmonitionConversion,
     BackPopulate,
     CanonicalLink,
+    /// A replacement from a `ReplacementSource`, by the source's name.
+    Custom(String),
+    DateMention,
+    HeadingLinkRepair,
     ImageReference,
     PhantomLink,
+    RegexReplacement,
+    SelfLink,
+    WikilinkRepair,
+}
+
+impl Display for MatchType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AdmonitionConversion => write!(f, "admonition conversion"),
+            Self::BackPopulate => write!(f, "back populate"),
+            Self::CanonicalLink => write!(f, "canonical link"),
+            Self::Custom(source) => f.write_str(source),
+            Self::DateMention => write!(f, "date mention"),
+            Self::HeadingLinkRepair => write!(f, "heading link repair"),
+            Self::ImageReference => write!(f, "image reference"),
+            Self::PhantomLink => write!(f, "phantom link"),
+            Self::RegexReplacement => write!(f, "regex replacement"),
+            Self::SelfLink => write!(f, "self link"),
+            Self::WikilinkRepair => write!(f, "wikilink repair"),
+        }
+    }
+}
+
+impl MatchType {
+    /// Which of two overlapping replacements is applied - the lower value wins. Image and link
+    /// repairs come first, since the text the other changes were found in is what they fix;
+    /// replacements from a `ReplacementSource` follow the `replacements` rules, and links added
+    /// to plain text by back populate and date mentions come last.
+    pub(crate) const fn priority(&self) -> u8 {
+        match self {
+            Self::ImageReference => 0,
+            Self::WikilinkRepair => 1,
+            Self::HeadingLinkRepair => 2,
+            Self::PhantomLink => 3,
+            Self::CanonicalLink => 4,
+            Self::SelfLink => 5,
+            Self::AdmonitionConversion => 6,
+            Self::RegexReplacement => 7,
+            Self::Custom(_) => 8,
+            Self::BackPopulate => 9,
+            Self::DateMention => 10,
+        }
+    }
 }
 
 pub trait ReplaceableContent {
@@ -13,3 +65,10 @@ pub trait ReplaceableContent {
     fn matched_text(&self) -> String;
     fn match_type(&self) -> MatchType;
 }
+
+/// A half-open byte range of a note body - the text a `ReplaceableContent` rewrites.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ByteSpan {
+    pub start: usize,
+    pub end:   usize,
+}
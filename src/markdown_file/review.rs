@@ -0,0 +1,52 @@
+use chrono::Days;
+use chrono::NaiveDate;
+use serde_yaml::Value;
+
+use super::MarkdownFile;
+use super::PersistReason;
+use super::date_consistency;
+use crate::config::PersistReasonKind;
+use crate::constants::NEXT_REVIEW_PROPERTY;
+
+impl MarkdownFile {
+    /// The note's `date_modified` as it will be written, and the day `days` later when the note
+    /// is due for review.
+    pub(crate) fn review_dates(&self, days: u32) -> Option<(NaiveDate, NaiveDate)> {
+        let front_matter = self.front_matter.as_ref()?;
+        let date_modified = date_consistency::frontmatter_date(
+            front_matter.date_modified(),
+            front_matter.date_link_format(),
+        )?;
+        let due = date_modified.checked_add_days(Days::new(u64::from(days)))?;
+        Some((date_modified, due))
+    }
+
+    /// Writes `due` as the note's `next_review` unless it's already there. `date_modified` is
+    /// left alone - a reminder isn't an edit, and stamping today would put the review off.
+    pub(crate) fn set_next_review(&mut self, due: NaiveDate, held_reasons: &[PersistReasonKind]) {
+        let Some(front_matter) = self.front_matter.as_mut() else {
+            return;
+        };
+        let next_review = front_matter.date_link(due);
+        if front_matter
+            .property(NEXT_REVIEW_PROPERTY)
+            .and_then(Value::as_str)
+            == Some(next_review.as_str())
+        {
+            return;
+        }
+
+        let reason = PersistReason::NextReviewSet { due };
+        if held_reasons.contains(&reason.kind()) {
+            self.held_persist_reasons.push(reason);
+            return;
+        }
+
+        // The file keeps its modified time as well, or the next run would date the note today.
+        if front_matter.raw_modified.is_none() {
+            front_matter.raw_modified = Some(self.modified_date_validation.file_system);
+        }
+        front_matter.set_property(NEXT_REVIEW_PROPERTY, Value::String(next_review));
+        self.persist_reasons.push(reason);
+    }
+}
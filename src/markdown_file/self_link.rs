@@ -0,0 +1,184 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use super::MarkdownFile;
+use super::replaceable_content::MatchType;
+use super::replaceable_content::ReplaceableContent;
+use crate::constants::ESCAPED_PIPE;
+use crate::constants::HASH;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::PIPE;
+use crate::constants::REDUNDANT_ALIAS;
+use crate::constants::SELF_LINK;
+use crate::support;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::SpannedWikilink;
+use crate::wikilink::ToWikilink;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelfLinkKind {
+    /// `[[This Note]]` inside `This Note.md` - the link becomes its display text.
+    SelfLink,
+    /// `[[Foo|Foo]]` - the alias repeats the target, so the link becomes `[[Foo]]`.
+    RedundantAlias,
+}
+
+impl Display for SelfLinkKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SelfLink => f.write_str(SELF_LINK),
+            Self::RedundantAlias => f.write_str(REDUNDANT_ALIAS),
+        }
+    }
+}
+
+/// One content wikilink that either targets the note containing it or carries an alias equal
+/// to its target; `replacement` is the simplified form.
+#[derive(Clone, Debug)]
+pub struct SelfLinkMatch {
+    pub found_text:    String,
+    pub kind:          SelfLinkKind,
+    pub line_number:   usize,
+    pub position:      usize,
+    pub relative_path: String,
+    pub replacement:   String,
+}
+
+impl ReplaceableContent for SelfLinkMatch {
+    fn line_number(&self) -> usize { self.line_number }
+
+    fn position(&self) -> usize { self.position }
+
+    fn get_replacement(&self) -> String { self.replacement.clone() }
+
+    fn matched_text(&self) -> String { self.found_text.clone() }
+
+    fn match_type(&self) -> MatchType { MatchType::SelfLink }
+}
+
+impl MarkdownFile {
+    /// Finds self-links and redundant aliases in content wikilinks. Heading links into the
+    /// current note (`[[This Note#Heading]]`) are intra-note navigation and stay untouched.
+    /// Links already claimed by a `CanonicalLinkMatch` or `PhantomLinkMatch` are skipped so a
+    /// span is only ever rewritten once.
+    pub(crate) fn find_self_link_matches(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Vec<SelfLinkMatch> {
+        let relative_path =
+            support::format_relative_path(&self.path, validated_config.obsidian_path());
+        let relative_stem = relative_path
+            .strip_suffix(MARKDOWN_SUFFIX)
+            .unwrap_or(&relative_path)
+            .to_lowercase();
+        let file_stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let mut matches = Vec::new();
+        self.for_each_content_wikilink(|line_number, line, spanned_wikilink| {
+            let SpannedWikilink { wikilink, span } = spanned_wikilink;
+            let (start, end) = span;
+
+            if self.is_claimed_by_link_match(line_number, start) {
+                return;
+            }
+
+            let found_text = line[start..end].to_string();
+            let target = wikilink.target.to_lowercase();
+            let target = target.strip_suffix(MARKDOWN_SUFFIX).unwrap_or(&target);
+
+            let (kind, replacement) = if !wikilink.target.contains(HASH)
+                && (target == file_stem || target == relative_stem)
+            {
+                (SelfLinkKind::SelfLink, wikilink.display_text)
+            } else if !wikilink.is_alias()
+                && (found_text.contains(PIPE) || found_text.contains(ESCAPED_PIPE))
+            {
+                (SelfLinkKind::RedundantAlias, wikilink.target.to_wikilink())
+            } else {
+                return;
+            };
+
+            matches.push(SelfLinkMatch {
+                found_text,
+                kind,
+                line_number,
+                position: start,
+                relative_path: relative_path.clone(),
+                replacement,
+            });
+        });
+
+        matches
+    }
+
+    fn is_claimed_by_link_match(&self, line_number: usize, position: usize) -> bool {
+        self.canonical_link_matches
+            .iter()
+            .map(|m| (m.line_number, m.position))
+            .chain(
+                self.phantom_link_matches
+                    .iter()
+                    .map(|m| (m.line_number, m.position)),
+            )
+            .any(|claimed| claimed == (line_number, position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SelfLinkKind;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_find_self_link_matches() {
+        let (temp_dir, validated_config, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "see [[Recipes]] for more\n\
+                 also [[recipes|my recipes]] list\n\
+                 jump to [[Recipes#Soups]]\n\
+                 and [[Bread|Bread]] too",
+            )
+            .create(&temp_dir, "Recipes.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let matches = markdown_file.find_self_link_matches(&validated_config);
+
+        assert_eq!(matches.len(), 3, "heading links are not self-links");
+        assert_eq!(matches[0].kind, SelfLinkKind::SelfLink);
+        assert_eq!(matches[0].replacement, "Recipes");
+        assert_eq!(
+            matches[1].replacement, "my recipes",
+            "aliased self-links keep their display text"
+        );
+        assert_eq!(matches[2].kind, SelfLinkKind::RedundantAlias);
+        assert_eq!(matches[2].found_text, "[[Bread|Bread]]");
+        assert_eq!(matches[2].replacement, "[[Bread]]");
+    }
+
+    #[test]
+    fn test_find_self_link_matches_keeps_case_variant_alias() {
+        let (temp_dir, validated_config, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_content("[[Bread|bread]] renders differently from its target")
+            .create(&temp_dir, "diary.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        assert!(
+            markdown_file
+                .find_self_link_matches(&validated_config)
+                .is_empty()
+        );
+    }
+}
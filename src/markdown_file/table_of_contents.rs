@@ -0,0 +1,265 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use anyhow::Result as AnyhowResult;
+
+use super::MarkdownFile;
+use super::constants::HEADING_LINK_DISALLOWED_CHARACTERS;
+use super::constants::TABLE_OF_CONTENTS_ENTRY_PREFIX;
+use super::constants::TABLE_OF_CONTENTS_HEADING;
+use super::constants::TABLE_OF_CONTENTS_INDENT;
+use super::rare_words;
+use super::text_excluder::CodeBlockExcluder;
+use crate::constants::ADDED;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::HASH;
+use crate::constants::NEWLINE;
+use crate::constants::REFRESHED;
+use crate::constants::REMOVED;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableOfContentsAction {
+    Added,
+    Refreshed,
+    /// The note dropped below `table_of_contents_min_headings`.
+    Removed,
+}
+
+impl Display for TableOfContentsAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added => f.write_str(ADDED),
+            Self::Refreshed => f.write_str(REFRESHED),
+            Self::Removed => f.write_str(REMOVED),
+        }
+    }
+}
+
+impl MarkdownFile {
+    /// Keeps the managed `## Table of Contents` section in step with the note's headings. The
+    /// section is the heading plus the `- [[#heading]]` list under it and is rebuilt from the
+    /// headings that follow it; a note without one gets it at the top of the body, below a
+    /// leading `# Title`. Notes with fewer than `min_headings` headings lose the section. A
+    /// section that is already current is left untouched, so repeated runs change nothing.
+    pub(crate) fn refresh_table_of_contents(
+        &mut self,
+        min_headings: usize,
+        operational_timezone: &str,
+    ) -> AnyhowResult<()> {
+        let lines: Vec<&str> = self.content.lines().collect();
+        let headings = headings(&lines);
+
+        let existing_section = headings
+            .iter()
+            .find(|(line_idx, ..)| lines[*line_idx].trim() == TABLE_OF_CONTENTS_HEADING)
+            .map(|&(line_idx, ..)| (line_idx, managed_section_end(&lines, line_idx)));
+        let (section_start, section_end) =
+            existing_section.unwrap_or_else(|| insertion_point(&lines, &headings));
+
+        let entries: Vec<String> = {
+            let section_headings: Vec<(usize, String)> = headings
+                .iter()
+                .filter(|(line_idx, ..)| *line_idx >= section_end)
+                .map(|&(_, level, text)| (level, heading_link_target(text)))
+                .filter(|(_, target)| !target.is_empty())
+                .collect();
+            let min_level = section_headings
+                .iter()
+                .map(|(level, _)| *level)
+                .min()
+                .unwrap_or_default();
+            section_headings
+                .iter()
+                .map(|(level, target)| {
+                    let indent = TABLE_OF_CONTENTS_INDENT
+                        .to_string()
+                        .repeat(level - min_level);
+                    format!("{indent}{TABLE_OF_CONTENTS_ENTRY_PREFIX}{target}{CLOSING_WIKILINK}")
+                })
+                .collect()
+        };
+
+        let mut section: Vec<String> = Vec::new();
+        let action = if entries.len() >= min_headings {
+            section.push(TABLE_OF_CONTENTS_HEADING.to_string());
+            section.extend(entries);
+            if section_end < lines.len() {
+                section.push(String::new());
+            }
+            match existing_section {
+                Some(_) if lines[section_start..section_end] == section => return Ok(()),
+                Some(_) => TableOfContentsAction::Refreshed,
+                None => TableOfContentsAction::Added,
+            }
+        } else if existing_section.is_some() {
+            TableOfContentsAction::Removed
+        } else {
+            return Ok(());
+        };
+
+        let mut updated_lines: Vec<&str> = lines[..section_start].to_vec();
+        updated_lines.extend(section.iter().map(String::as_str));
+        updated_lines.extend_from_slice(&lines[section_end..]);
+
+        let mut updated_content = updated_lines.join(&NEWLINE.to_string());
+        if self.content.ends_with(NEWLINE) {
+            updated_content.push(NEWLINE);
+        }
+        self.content = updated_content;
+
+        self.mark_table_of_contents_updated(action, operational_timezone)
+    }
+}
+
+/// Line index, level and text of every heading outside code blocks.
+pub(super) fn headings<'a>(lines: &[&'a str]) -> Vec<(usize, usize, &'a str)> {
+    let mut code_block_excluder = CodeBlockExcluder::new();
+    let mut headings = Vec::new();
+
+    for (line_idx, line) in lines.iter().enumerate() {
+        code_block_excluder.update(line);
+        if code_block_excluder.is_in_code_block() {
+            continue;
+        }
+        if let Some(text) = rare_words::heading_text(line) {
+            let level = line.trim_start().chars().take_while(|&c| c == HASH).count();
+            headings.push((line_idx, level, text));
+        }
+    }
+
+    headings
+}
+
+/// End (exclusive) of the managed section starting at `start`: its list entries and the blank
+/// line separating them from the rest of the note.
+fn managed_section_end(lines: &[&str], start: usize) -> usize {
+    let mut end = start + 1;
+    while end < lines.len()
+        && lines[end]
+            .trim_start()
+            .starts_with(TABLE_OF_CONTENTS_ENTRY_PREFIX)
+    {
+        end += 1;
+    }
+    if end < lines.len() && lines[end].trim().is_empty() {
+        end += 1;
+    }
+    end
+}
+
+/// Where a new section goes: just below a `# Title` opening the note, otherwise the top.
+fn insertion_point(lines: &[&str], headings: &[(usize, usize, &str)]) -> (usize, usize) {
+    let first_line = lines.iter().position(|line| !line.trim().is_empty());
+    let opens_with_title = headings
+        .first()
+        .is_some_and(|&(line_idx, level, _)| Some(line_idx) == first_line && level == 1);
+
+    let line_idx = if opens_with_title {
+        first_line.map_or(0, |line_idx| line_idx + 1)
+    } else {
+        0
+    };
+    (line_idx, line_idx)
+}
+
+/// The heading as Obsidian writes it in a `[[#heading]]` link: characters a link can't hold
+/// become spaces and runs of whitespace collapse to one.
+pub(super) fn heading_link_target(heading: &str) -> String {
+    heading
+        .replace(HEADING_LINK_DISALLOWED_CHARACTERS, " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use super::TableOfContentsAction;
+    use crate::markdown_file::PersistReason;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_refresh_table_of_contents() {
+        let (temp_dir, validated_config, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+        let timezone = validated_config.operational_timezone();
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "# Trip\n\
+                 intro\n\
+                 ## Day 1: [[Paris]]\n\
+                 ### Louvre #1\n\
+                 ```\n\
+                 ## not a heading\n\
+                 ```\n\
+                 ## Day 2",
+            )
+            .create(&temp_dir, "trip.md");
+        let mut markdown_file = test_support::get_test_markdown_file(file_path);
+
+        markdown_file
+            .refresh_table_of_contents(3, timezone)
+            .unwrap();
+        let with_table = "# Trip\n\
+                          ## Table of Contents\n\
+                          - [[#Day 1 Paris]]\n\
+                          \t- [[#Louvre 1]]\n\
+                          - [[#Day 2]]\n\
+                          \n\
+                          intro\n\
+                          ## Day 1: [[Paris]]\n\
+                          ### Louvre #1\n\
+                          ```\n\
+                          ## not a heading\n\
+                          ```\n\
+                          ## Day 2\n";
+        assert_eq!(markdown_file.content, with_table);
+        assert_eq!(
+            markdown_file.persist_reasons.last(),
+            Some(&PersistReason::TableOfContentsUpdated {
+                action: TableOfContentsAction::Added,
+            })
+        );
+
+        let reason_count = markdown_file.persist_reasons.len();
+        markdown_file
+            .refresh_table_of_contents(3, timezone)
+            .unwrap();
+        assert_eq!(
+            markdown_file.content, with_table,
+            "a current table is left alone"
+        );
+        assert_eq!(markdown_file.persist_reasons.len(), reason_count);
+
+        markdown_file.content = markdown_file.content.replace("## Day 2", "## Day Two");
+        markdown_file
+            .refresh_table_of_contents(3, timezone)
+            .unwrap();
+        assert!(markdown_file.content.contains("- [[#Day Two]]\n\nintro"));
+
+        markdown_file
+            .refresh_table_of_contents(4, timezone)
+            .unwrap();
+        assert_eq!(
+            markdown_file.content,
+            with_table.replace("## Day 2", "## Day Two").replace(
+                "## Table of Contents\n- [[#Day 1 Paris]]\n\t- [[#Louvre 1]]\n- [[#Day 2]]\n\n",
+                ""
+            )
+        );
+        assert_eq!(
+            markdown_file.persist_reasons.last(),
+            Some(&PersistReason::TableOfContentsUpdated {
+                action: TableOfContentsAction::Removed,
+            })
+        );
+    }
+}
@@ -1,9 +1,19 @@
+use std::borrow::Cow;
+
 use super::constants::FENCED_CODE_DELIMITER;
+use super::constants::HTML_COMMENT_CLOSING;
+use super::constants::HTML_COMMENT_OPENING;
+use super::constants::HTML_TAG_OPENING;
 use super::constants::INLINE_CODE_DELIMITER;
+use super::constants::MATH_BLOCK_DELIMITER;
+use super::constants::MATH_DELIMITER;
+use super::constants::OBSIDIAN_COMMENT_DELIMITER;
+use crate::constants::BACKSLASH;
 
 #[derive(Debug, PartialEq)]
 enum CodeBlockDelimiter {
     Backtick,
+    DoubleDollar,
     TripleBacktick,
 }
 
@@ -11,8 +21,12 @@ impl TryFrom<&str> for CodeBlockDelimiter {
     type Error = (); // Using unit type for error since we don't care if it fails
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        if s.trim().starts_with(FENCED_CODE_DELIMITER) {
+        let trimmed = s.trim();
+        if trimmed.starts_with(FENCED_CODE_DELIMITER) {
             Ok(Self::TripleBacktick)
+        } else if trimmed.matches(MATH_BLOCK_DELIMITER).count() % 2 == 1 {
+            // `$$ x = y $$` opens and closes on one line - only an unpaired `$$` toggles a block.
+            Ok(Self::DoubleDollar)
         } else {
             Err(())
         }
@@ -47,6 +61,12 @@ impl BlockDelimiter for TripleBacktickDelimiter {
     fn delimiter_type(&self) -> CodeBlockDelimiter { CodeBlockDelimiter::TripleBacktick }
 }
 
+#[derive(Debug)]
+struct DoubleDollarDelimiter;
+impl BlockDelimiter for DoubleDollarDelimiter {
+    fn delimiter_type(&self) -> CodeBlockDelimiter { CodeBlockDelimiter::DoubleDollar }
+}
+
 #[derive(Debug)]
 struct SingleBacktickDelimiter;
 impl BlockDelimiter for SingleBacktickDelimiter {
@@ -116,6 +136,20 @@ impl CodeBlockExcluder {
     pub(super) const fn is_in_code_block(&self) -> bool { self.0.is_in_code_block() }
 }
 
+/// Tracks multi-line `$$` math blocks the same way `CodeBlockExcluder` tracks fenced code.
+#[derive(Debug)]
+pub(super) struct MathBlockExcluder(BlockTracker<DoubleDollarDelimiter>);
+
+impl MathBlockExcluder {
+    pub(super) const fn new() -> Self {
+        Self(BlockTracker::new_with_delimiter(DoubleDollarDelimiter))
+    }
+
+    pub(super) fn update(&mut self, content: &str) { self.0.update(content); }
+
+    pub(super) const fn is_in_math_block(&self) -> bool { self.0.is_in_code_block() }
+}
+
 #[derive(Debug)]
 pub struct InlineCodeExcluder(BlockTracker<SingleBacktickDelimiter>);
 
@@ -129,6 +163,236 @@ impl InlineCodeExcluder {
     pub fn is_inside(&self) -> bool { self.0.is_inside() }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CommentKind {
+    Html,
+    Obsidian,
+}
+
+impl CommentKind {
+    const fn closing(self) -> &'static str {
+        match self {
+            Self::Html => HTML_COMMENT_CLOSING,
+            Self::Obsidian => OBSIDIAN_COMMENT_DELIMITER,
+        }
+    }
+}
+
+/// Tracks `<!-- -->` comments, `%% %%` Obsidian comments and raw HTML blocks across lines.
+/// Comments can open and close anywhere on a line, so `excluded_spans` returns byte spans
+/// rather than a single flag. A raw HTML block starts with a tag at the beginning of a line
+/// that follows a blank line and runs until the next blank line, as in `CommonMark`.
+#[derive(Debug)]
+pub(super) struct CommentExcluder {
+    open_comment:        Option<CommentKind>,
+    in_html_block:       bool,
+    previous_line_blank: bool,
+}
+
+impl CommentExcluder {
+    pub(super) const fn new() -> Self {
+        Self {
+            open_comment:        None,
+            in_html_block:       false,
+            previous_line_blank: true,
+        }
+    }
+
+    /// Must be called for every line in order, blank lines included.
+    pub(super) fn excluded_spans(&mut self, line: &str) -> Vec<(usize, usize)> {
+        let is_blank = line.trim().is_empty();
+        let starts_html_block = self.previous_line_blank && is_html_block_start(line);
+        self.previous_line_blank = is_blank;
+
+        if self.in_html_block {
+            self.in_html_block = !is_blank;
+        } else if self.open_comment.is_none() && starts_html_block {
+            self.in_html_block = true;
+        }
+        if self.in_html_block {
+            return vec![(0, line.len())];
+        }
+
+        let mut spans = Vec::new();
+        let mut span_start = 0;
+        let mut search_from = 0;
+
+        loop {
+            if let Some(comment_kind) = self.open_comment {
+                let closing = comment_kind.closing();
+                let Some(offset) = line[search_from..].find(closing) else {
+                    spans.push((span_start, line.len()));
+                    break;
+                };
+                let end = search_from + offset + closing.len();
+                spans.push((span_start, end));
+                self.open_comment = None;
+                search_from = end;
+            } else {
+                let html = line[search_from..]
+                    .find(HTML_COMMENT_OPENING)
+                    .map(|offset| (offset, CommentKind::Html, HTML_COMMENT_OPENING.len()));
+                let obsidian = line[search_from..]
+                    .find(OBSIDIAN_COMMENT_DELIMITER)
+                    .map(|offset| {
+                        (
+                            offset,
+                            CommentKind::Obsidian,
+                            OBSIDIAN_COMMENT_DELIMITER.len(),
+                        )
+                    });
+                let Some((offset, comment_kind, opening_len)) = [html, obsidian]
+                    .into_iter()
+                    .flatten()
+                    .min_by_key(|&(offset, ..)| offset)
+                else {
+                    break;
+                };
+                span_start = search_from + offset;
+                search_from = span_start + opening_len;
+                self.open_comment = Some(comment_kind);
+            }
+        }
+
+        spans
+    }
+}
+
+fn is_html_block_start(line: &str) -> bool {
+    let Some(rest) = line.trim_start().strip_prefix(HTML_TAG_OPENING) else {
+        return false;
+    };
+    let rest = rest.strip_prefix('/').unwrap_or(rest);
+    if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return false;
+    }
+
+    // `<https://example.com>` is an autolink, not a tag.
+    let after_name = rest.trim_start_matches(|c: char| c.is_ascii_alphanumeric() || c == '-');
+    after_name.is_empty()
+        || after_name.starts_with(char::is_whitespace)
+        || after_name.starts_with('>')
+        || after_name.starts_with("/>")
+}
+
+/// Replaces the bytes in `spans` with spaces so parsers skip them while every other byte
+/// keeps its position.
+pub(super) fn mask_spans<'a>(line: &'a str, spans: &[(usize, usize)]) -> Cow<'a, str> {
+    if spans.is_empty() {
+        return Cow::Borrowed(line);
+    }
+
+    let mut masked = String::with_capacity(line.len());
+    let mut last_end = 0;
+    for &(start, end) in spans {
+        masked.push_str(&line[last_end..start]);
+        masked.extend(std::iter::repeat_n(' ', end - start));
+        last_end = end;
+    }
+    masked.push_str(&line[last_end..]);
+    Cow::Owned(masked)
+}
+
+/// Drops the bytes in `spans` altogether - for output that shouldn't hold them at all.
+pub(super) fn remove_spans<'a>(line: &'a str, spans: &[(usize, usize)]) -> Cow<'a, str> {
+    if spans.is_empty() {
+        return Cow::Borrowed(line);
+    }
+
+    let mut kept = String::with_capacity(line.len());
+    let mut last_end = 0;
+    for &(start, end) in spans {
+        kept.push_str(&line[last_end..start]);
+        last_end = end;
+    }
+    kept.push_str(&line[last_end..]);
+    Cow::Owned(kept)
+}
+
+/// Byte spans of inline code on a single line, backticks included.
+pub(super) fn find_inline_code_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut inline_code_excluder = InlineCodeExcluder::new();
+    let mut span_start = None;
+    for (byte_offset, ch) in line.char_indices() {
+        let was_inside = inline_code_excluder.is_in_code_block();
+        inline_code_excluder.update(ch);
+        let is_inside = inline_code_excluder.is_in_code_block();
+
+        if !was_inside && is_inside {
+            span_start = Some(byte_offset);
+        } else if was_inside
+            && !is_inside
+            && let Some(start) = span_start.take()
+        {
+            spans.push((start, byte_offset + ch.len_utf8()));
+        }
+    }
+
+    // A closing backtick only leaves the span once the next char is read.
+    if let Some(start) = span_start
+        && inline_code_excluder.is_in_code_block()
+        && !inline_code_excluder.is_inside()
+    {
+        spans.push((start, line.len()));
+    }
+    spans
+}
+
+/// Byte spans of math on a single line: `$$...$$` and `$...$`. Inline math follows the
+/// pandoc rules so currency like `$5 and $10` is left alone - the opening `$` can't be followed
+/// by whitespace, the closing `$` can't be preceded by whitespace or followed by a digit, and
+/// `\$` is never a delimiter.
+pub(super) fn find_inline_math_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(offset) = line[search_from..].find(MATH_DELIMITER) {
+        let start = search_from + offset;
+        if line[..start].ends_with(BACKSLASH) {
+            search_from = start + MATH_DELIMITER.len_utf8();
+            continue;
+        }
+
+        if line[start..].starts_with(MATH_BLOCK_DELIMITER) {
+            let content_start = start + MATH_BLOCK_DELIMITER.len();
+            let Some(length) = line[content_start..].find(MATH_BLOCK_DELIMITER) else {
+                // An unpaired `$$` belongs to `MathBlockExcluder`.
+                break;
+            };
+            let end = content_start + length + MATH_BLOCK_DELIMITER.len();
+            spans.push((start, end));
+            search_from = end;
+            continue;
+        }
+
+        let content_start = start + MATH_DELIMITER.len_utf8();
+        let opens = line[content_start..]
+            .chars()
+            .next()
+            .is_some_and(|c| !c.is_whitespace() && c != MATH_DELIMITER);
+        let Some(length) = line[content_start..].find(MATH_DELIMITER) else {
+            break;
+        };
+        let closing = content_start + length;
+        let closes = !line[..closing].ends_with(char::is_whitespace)
+            && !line[..closing].ends_with(BACKSLASH)
+            && !line[closing + MATH_DELIMITER.len_utf8()..]
+                .starts_with(|c: char| c.is_ascii_digit());
+
+        if opens && closes {
+            let end = closing + MATH_DELIMITER.len_utf8();
+            spans.push((start, end));
+            search_from = end;
+        } else {
+            // The next `$` may still open a span of its own.
+            search_from = closing;
+        }
+    }
+
+    spans
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -208,4 +472,73 @@ mod tests {
             "Should not skip regular text after an inline code block"
         );
     }
+
+    #[test]
+    fn test_math_block_tracking() {
+        let mut tracker = MathBlockExcluder::new();
+
+        tracker.update("$$");
+        assert!(tracker.is_in_math_block(), "Should skip the opening line");
+        tracker.update("e = mc^2");
+        assert!(tracker.is_in_math_block(), "Should skip inside math block");
+        tracker.update("$$");
+        assert!(tracker.is_in_math_block(), "Should skip the closing line");
+        tracker.update("$$ a = b $$");
+        assert!(
+            !tracker.is_in_math_block(),
+            "Single-line display math does not open a block"
+        );
+    }
+
+    #[test]
+    fn test_find_inline_math_spans() {
+        let line = r"cost $5 and $10 but $x^2$ and $$y$$ not \$z$";
+        let spans = find_inline_math_spans(line);
+        let texts: Vec<&str> = spans
+            .iter()
+            .map(|&(start, end)| &line[start..end])
+            .collect();
+
+        assert_eq!(texts, vec!["$x^2$", "$$y$$"]);
+    }
+
+    #[test]
+    fn test_comment_excluder_spans() {
+        let mut excluder = CommentExcluder::new();
+
+        let line = "keep <!-- hide --> keep %% hide %% keep";
+        let texts: Vec<&str> = excluder
+            .excluded_spans(line)
+            .iter()
+            .map(|&(start, end)| &line[start..end])
+            .collect();
+        assert_eq!(texts, vec!["<!-- hide -->", "%% hide %%"]);
+
+        assert_eq!(excluder.excluded_spans("text %% opens"), vec![(5, 13)]);
+        assert_eq!(excluder.excluded_spans("inside"), vec![(0, 6)]);
+        assert_eq!(excluder.excluded_spans("closes %% text"), vec![(0, 9)]);
+        assert!(excluder.excluded_spans("plain").is_empty());
+    }
+
+    #[test]
+    fn test_comment_excluder_html_block() {
+        let mut excluder = CommentExcluder::new();
+
+        assert_eq!(excluder.excluded_spans("<div>"), vec![(0, 5)]);
+        assert_eq!(excluder.excluded_spans("Bob"), vec![(0, 3)]);
+        assert!(excluder.excluded_spans("").is_empty());
+        assert!(
+            excluder.excluded_spans("<https://example.com>").is_empty(),
+            "autolinks are not html"
+        );
+        assert!(
+            excluder.excluded_spans("<span>").is_empty(),
+            "a tag continuing a paragraph is inline html"
+        );
+    }
+
+    #[test]
+    fn test_mask_spans() {
+        assert_eq!(mask_spans("a %%b%% c", &[(2, 7)]), "a       c");
+    }
 }
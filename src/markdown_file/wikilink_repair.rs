@@ -0,0 +1,249 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::iter;
+
+use super::MarkdownFile;
+use super::replaceable_content::MatchType;
+use super::replaceable_content::ReplaceableContent;
+use crate::constants::CLOSING_BRACKET;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::EXTRA_BRACKETS;
+use crate::constants::OPENING_BRACKET;
+use crate::constants::OPENING_WIKILINK;
+use crate::constants::PIPE;
+use crate::constants::UNCLOSED_LINK_TEXT_ENDS;
+use crate::constants::UNCLOSED_WIKILINK;
+use crate::support;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::InvalidWikilink;
+use crate::wikilink::InvalidWikilinkReason;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WikilinkRepairKind {
+    /// `[[[Note]]]` - surplus brackets around an otherwise valid link are stripped.
+    ExtraBrackets,
+    /// `see [[Note` - a link left open is closed after the text that names a note.
+    Unclosed,
+}
+
+impl Display for WikilinkRepairKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExtraBrackets => f.write_str(EXTRA_BRACKETS),
+            Self::Unclosed => f.write_str(UNCLOSED_WIKILINK),
+        }
+    }
+}
+
+/// One invalid wikilink whose intended form is unambiguous; `found_text` is the broken
+/// source and `replacement` the repaired link.
+#[derive(Clone, Debug)]
+pub struct WikilinkRepairMatch {
+    pub found_text:    String,
+    pub kind:          WikilinkRepairKind,
+    pub line_number:   usize,
+    pub position:      usize,
+    pub relative_path: String,
+    pub replacement:   String,
+}
+
+impl ReplaceableContent for WikilinkRepairMatch {
+    fn line_number(&self) -> usize { self.line_number }
+
+    fn position(&self) -> usize { self.position }
+
+    fn get_replacement(&self) -> String { self.replacement.clone() }
+
+    fn matched_text(&self) -> String { self.found_text.clone() }
+
+    fn match_type(&self) -> MatchType { MatchType::WikilinkRepair }
+}
+
+impl MarkdownFile {
+    /// Finds invalid wikilinks that can be repaired without guessing. Anything else stays in
+    /// the invalid wikilinks report for the user to fix by hand. `names_note` tells whether a
+    /// link target resolves to a note, which an unclosed link is only closed on.
+    pub(crate) fn find_wikilink_repair_matches(
+        &self,
+        validated_config: &ValidatedConfig,
+        names_note: impl Fn(&str) -> bool,
+    ) -> Vec<WikilinkRepairMatch> {
+        let relative_path =
+            support::format_relative_path(&self.path, validated_config.obsidian_path());
+
+        let mut matches: Vec<WikilinkRepairMatch> = Vec::new();
+        for invalid_wikilink in &self.wikilinks.invalid {
+            let Some((found_text, kind, replacement)) =
+                repair_invalid_wikilink(invalid_wikilink, &names_note)
+            else {
+                continue;
+            };
+
+            // `[[[[Note]]]]` produces more than one invalid entry at the same position.
+            if matches.iter().any(|m| {
+                m.line_number == invalid_wikilink.line_number
+                    && m.position == invalid_wikilink.span.0
+            }) {
+                continue;
+            }
+
+            matches.push(WikilinkRepairMatch {
+                found_text,
+                kind,
+                line_number: invalid_wikilink.line_number,
+                position: invalid_wikilink.span.0,
+                relative_path: relative_path.clone(),
+                replacement,
+            });
+        }
+
+        matches
+    }
+
+    /// True when `invalid_wikilink` is rewritten by a `WikilinkRepairMatch`, so it no longer
+    /// needs to be reported as something the user has to fix.
+    pub(crate) fn is_repaired_wikilink(&self, invalid_wikilink: &InvalidWikilink) -> bool {
+        self.wikilink_repair_matches.iter().any(|m| {
+            m.line_number == invalid_wikilink.line_number && m.position == invalid_wikilink.span.0
+        })
+    }
+}
+
+fn repair_invalid_wikilink(
+    invalid_wikilink: &InvalidWikilink,
+    names_note: impl Fn(&str) -> bool,
+) -> Option<(String, WikilinkRepairKind, String)> {
+    let rest = invalid_wikilink.line.get(invalid_wikilink.span.0..)?;
+
+    match invalid_wikilink.reason {
+        InvalidWikilinkReason::NestedOpening | InvalidWikilinkReason::UnmatchedSingle => {
+            repair_extra_brackets(rest)
+        },
+        InvalidWikilinkReason::UnmatchedOpening => repair_unclosed_link(rest, names_note),
+        _ => None,
+    }
+}
+
+/// `[[[Note]]]`, `[[[Note]]` and `[[[[Note|alias]]]]` all become `[[Note]]` / `[[Note|alias]]`.
+/// The inner text must be a plausible link: non-empty, no brackets and at most one alias.
+fn repair_extra_brackets(rest: &str) -> Option<(String, WikilinkRepairKind, String)> {
+    let opening = rest.chars().take_while(|&c| c == OPENING_BRACKET).count();
+    let inner_len = rest[opening..].find(CLOSING_BRACKET)?;
+    let inner = &rest[opening..opening + inner_len];
+    let closing = rest[opening + inner_len..]
+        .chars()
+        .take_while(|&c| c == CLOSING_BRACKET)
+        .count();
+
+    if opening < 2 || closing < 2 || (opening == 2 && closing == 2) {
+        return None;
+    }
+    if !is_repairable_inner(inner) {
+        return None;
+    }
+
+    let found_text = rest[..opening + inner_len + closing].to_string();
+    let replacement = format!("{OPENING_WIKILINK}{inner}{CLOSING_WIKILINK}");
+    Some((found_text, WikilinkRepairKind::ExtraBrackets, replacement))
+}
+
+/// `see [[Note` becomes `see [[Note]]`, and `see [[Note, then more` becomes
+/// `see [[Note]], then more` - the link is closed after the longest text up to the end of the
+/// line, a space or punctuation that names a note. Text naming no note is left as it is: the
+/// brackets may have been meant to open a link further on.
+fn repair_unclosed_link(
+    rest: &str,
+    names_note: impl Fn(&str) -> bool,
+) -> Option<(String, WikilinkRepairKind, String)> {
+    let text = rest.strip_prefix(OPENING_WIKILINK)?.trim_end();
+    let inner = text
+        .char_indices()
+        .filter(|&(_, c)| c.is_whitespace() || UNCLOSED_LINK_TEXT_ENDS.contains(&c))
+        .map(|(end, _)| &text[..end])
+        .chain(iter::once(text))
+        .rev()
+        .map(str::trim_end)
+        .find(|inner| {
+            is_repairable_inner(inner) && inner.split(PIPE).next().is_some_and(&names_note)
+        })?;
+
+    let found_text = format!("{OPENING_WIKILINK}{inner}");
+    let replacement = format!("{found_text}{CLOSING_WIKILINK}");
+    Some((found_text, WikilinkRepairKind::Unclosed, replacement))
+}
+
+fn is_repairable_inner(inner: &str) -> bool {
+    !inner.trim().is_empty()
+        && !inner.contains([OPENING_BRACKET, CLOSING_BRACKET])
+        && inner.matches(PIPE).count() <= 1
+        && inner.split(PIPE).all(|part| !part.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WikilinkRepairKind;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::ChangeMode;
+
+    const NOTES: [&str; 3] = ["open note", "alpha", "beta note"];
+
+    fn names_note(target: &str) -> bool { NOTES.contains(&target.to_lowercase().as_str()) }
+
+    #[test]
+    fn test_find_wikilink_repair_matches() {
+        let (temp_dir, validated_config, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "start [[[Note]]] end\n\
+                 then [[[[Other|alias]]]] too\n\
+                 trailing [[Open Note\n\
+                 left alone [[a [[b]] c",
+            )
+            .create(&temp_dir, "diary.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let matches = markdown_file.find_wikilink_repair_matches(&validated_config, names_note);
+
+        assert_eq!(matches.len(), 3, "ambiguous nesting is not repaired");
+        assert_eq!(matches[0].kind, WikilinkRepairKind::ExtraBrackets);
+        assert_eq!(matches[0].found_text, "[[[Note]]]");
+        assert_eq!(matches[0].replacement, "[[Note]]");
+        assert_eq!(matches[1].found_text, "[[[[Other|alias]]]]");
+        assert_eq!(matches[1].replacement, "[[Other|alias]]");
+        assert_eq!(matches[2].kind, WikilinkRepairKind::Unclosed);
+        assert_eq!(matches[2].found_text, "[[Open Note");
+        assert_eq!(matches[2].replacement, "[[Open Note]]");
+    }
+
+    #[test]
+    fn test_unclosed_link_closed_only_after_a_note_name() {
+        let (temp_dir, validated_config, _) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, Some(vec![]), None);
+
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "met [[Alpha, then went home\n\
+                 saw [[Beta Note and the rest of the day\n\
+                 wrote [[Gamma and more prose\n\
+                 wrote [[something that names nothing",
+            )
+            .create(&temp_dir, "diary.md");
+        let markdown_file = test_support::get_test_markdown_file(file_path);
+
+        let matches = markdown_file.find_wikilink_repair_matches(&validated_config, names_note);
+
+        let repairs: Vec<(&str, &str)> = matches
+            .iter()
+            .map(|m| (m.found_text.as_str(), m.replacement.as_str()))
+            .collect();
+        assert_eq!(
+            repairs,
+            vec![("[[Alpha", "[[Alpha]]"), ("[[Beta Note", "[[Beta Note]]"),],
+            "the prose after the note name stays outside the link, and a link naming no note is left"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::path::PathBuf;
 use std::slice::Iter;
 use std::slice::IterMut;
 
@@ -59,11 +60,18 @@ impl MarkdownFiles {
             .collect()
     }
 
-    pub(crate) fn persist_all(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+    /// Writes every file in order and returns the paths skipped because they changed on disk
+    /// after they were read - writing them would overwrite those edits.
+    pub(crate) fn persist_all(&self) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+        let mut skipped = Vec::new();
         for file_info in &self.files {
+            if file_info.changed_on_disk() {
+                skipped.push(file_info.path.clone());
+                continue;
+            }
             file_info.persist()?;
         }
-        Ok(())
+        Ok(skipped)
     }
 
     pub(crate) fn total_files_to_persist(&self) -> usize {
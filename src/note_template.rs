@@ -0,0 +1,366 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::Utc;
+use chrono_tz::Tz;
+use chrono_tz::UTC;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::MOMENT_LITERAL_CLOSE;
+use crate::constants::MOMENT_LITERAL_OPEN;
+use crate::constants::MOMENT_TOKENS;
+use crate::constants::NEWLINE;
+use crate::constants::OBSIDIAN_FOLDER;
+use crate::constants::PLUGINS_FOLDER;
+use crate::constants::STRFTIME_ESCAPED_PERCENT;
+use crate::constants::STRFTIME_PERCENT;
+use crate::constants::TEMPLATE_DEFAULT_DATE_FORMAT;
+use crate::constants::TEMPLATE_DEFAULT_TIME_FORMAT;
+use crate::constants::TEMPLATE_VARIABLE_DATE;
+use crate::constants::TEMPLATE_VARIABLE_FOLDER;
+use crate::constants::TEMPLATE_VARIABLE_TIME;
+use crate::constants::TEMPLATE_VARIABLE_TITLE;
+use crate::constants::TEMPLATER_PLUGIN_FOLDER;
+use crate::constants::TEMPLATER_SETTINGS_FILE;
+use crate::constants::TEMPLATES_SETTINGS_FILE;
+use crate::constants::YAML_CLOSING_DELIMITER;
+use crate::constants::YAML_OPENING_DELIMITER;
+use crate::frontmatter::FrontMatter;
+use crate::support::TEMPLATE_VARIABLE_REGEX;
+use crate::yaml_frontmatter;
+use crate::yaml_frontmatter::YamlFrontMatter;
+use crate::yaml_frontmatter::YamlFrontMatterError;
+
+#[derive(Debug, Error)]
+pub(crate) enum NoteTemplateError {
+    #[error("note template not found: {}", .0.display())]
+    Missing(PathBuf),
+    #[error("can't read note template {}: {}", .0.display(), .1)]
+    Read(PathBuf, std::io::Error),
+}
+
+/// The core Templates plugin's settings - only the fields ok uses.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplatesSettings {
+    #[serde(default)]
+    folder:      String,
+    #[serde(default)]
+    date_format: String,
+    #[serde(default)]
+    time_format: String,
+}
+
+/// The Templater plugin's settings - only the fields ok uses.
+#[derive(Debug, Default, Deserialize)]
+struct TemplaterSettings {
+    #[serde(default)]
+    templates_folder: String,
+}
+
+/// The folders the core Templates plugin and the Templater plugin keep their templates in,
+/// relative to the vault. Templates hold placeholders such as `{{date}}` that date and link
+/// fixes would mangle, so ok leaves these folders alone. A plugin set to the vault root
+/// names no folder.
+pub(crate) fn template_folders(obsidian_path: &Path) -> Vec<PathBuf> {
+    let settings_path = obsidian_path.join(OBSIDIAN_FOLDER);
+    let core = read_settings::<TemplatesSettings>(&settings_path.join(TEMPLATES_SETTINGS_FILE))
+        .map(|settings| settings.folder);
+    let templater = read_settings::<TemplaterSettings>(
+        &settings_path
+            .join(PLUGINS_FOLDER)
+            .join(TEMPLATER_PLUGIN_FOLDER)
+            .join(TEMPLATER_SETTINGS_FILE),
+    )
+    .map(|settings| settings.templates_folder);
+
+    let mut folders: Vec<PathBuf> = [core, templater]
+        .into_iter()
+        .flatten()
+        .map(|folder| PathBuf::from(folder.trim().trim_matches('/')))
+        .filter(|folder| !folder.as_os_str().is_empty() && obsidian_path.join(folder).is_dir())
+        .collect();
+    folders.dedup();
+    folders
+}
+
+pub(crate) fn read_settings<T: for<'de> Deserialize<'de>>(path: &Path) -> Option<T> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+/// A note in the vault's templates folder, filled in for each note ok creates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct NoteTemplate {
+    text:        String,
+    date_format: String,
+    time_format: String,
+}
+
+impl NoteTemplate {
+    /// Reads template `name` from the folder the core Templates plugin is set to use, or from
+    /// the vault root when it isn't set, along with the plugin's date and time formats.
+    pub(crate) fn load(obsidian_path: &Path, name: &str) -> Result<Self, NoteTemplateError> {
+        let settings: TemplatesSettings = read_settings(
+            &obsidian_path
+                .join(OBSIDIAN_FOLDER)
+                .join(TEMPLATES_SETTINGS_FILE),
+        )
+        .unwrap_or_default();
+
+        let name = name.trim();
+        let file_name = if name.ends_with(MARKDOWN_SUFFIX) {
+            name.to_string()
+        } else {
+            format!("{name}{MARKDOWN_SUFFIX}")
+        };
+        let path = obsidian_path.join(&settings.folder).join(file_name);
+        if !path.is_file() {
+            return Err(NoteTemplateError::Missing(path));
+        }
+        let text =
+            fs::read_to_string(&path).map_err(|error| NoteTemplateError::Read(path, error))?;
+
+        let format_or = |format: String, default: &str| {
+            if format.trim().is_empty() {
+                default.to_string()
+            } else {
+                format
+            }
+        };
+        Ok(Self {
+            text,
+            date_format: format_or(settings.date_format, TEMPLATE_DEFAULT_DATE_FORMAT),
+            time_format: format_or(settings.time_format, TEMPLATE_DEFAULT_TIME_FORMAT),
+        })
+    }
+
+    /// Fills in `{{title}}`, `{{date}}` and `{{time}}` - optionally with a format, as in
+    /// `{{date:dddd D MMMM}}` - and `{{folder}}`, the new note's folder within the vault.
+    /// Any other variable is left as written.
+    pub(crate) fn render(
+        &self,
+        note_path: &Path,
+        obsidian_path: &Path,
+        operational_timezone: &str,
+    ) -> String {
+        let timezone: Tz = operational_timezone.parse().unwrap_or(UTC);
+        let now = Utc::now().with_timezone(&timezone);
+        let title = note_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let folder = note_path
+            .parent()
+            .and_then(|parent| parent.strip_prefix(obsidian_path).ok())
+            .map(|folder| folder.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        TEMPLATE_VARIABLE_REGEX
+            .replace_all(&self.text, |captures: &regex::Captures| {
+                let format = captures.get(2).map(|format| format.as_str().trim());
+                match &captures[1] {
+                    TEMPLATE_VARIABLE_TITLE => title.clone(),
+                    TEMPLATE_VARIABLE_FOLDER => folder.clone(),
+                    TEMPLATE_VARIABLE_DATE => now
+                        .format(&chrono_format(format.unwrap_or(&self.date_format)))
+                        .to_string(),
+                    TEMPLATE_VARIABLE_TIME => now
+                        .format(&chrono_format(format.unwrap_or(&self.time_format)))
+                        .to_string(),
+                    _ => captures[0].to_string(),
+                }
+            })
+            .into_owned()
+    }
+}
+
+/// Converts a Moment.js format such as `YYYY-MM-DD` to a `chrono` one. Text in `[brackets]`
+/// and characters that aren't tokens are kept as they are.
+pub(crate) fn chrono_format(moment: &str) -> String {
+    let mut format = String::new();
+    let mut rest = moment;
+    while let Some(character) = rest.chars().next() {
+        if character == MOMENT_LITERAL_OPEN
+            && let Some((literal, after)) = rest[1..].split_once(MOMENT_LITERAL_CLOSE)
+        {
+            format.push_str(&literal.replace(STRFTIME_PERCENT, STRFTIME_ESCAPED_PERCENT));
+            rest = after;
+        } else if let Some((token, specifier)) = MOMENT_TOKENS
+            .iter()
+            .find(|(token, _)| rest.starts_with(token))
+        {
+            format.push_str(specifier);
+            rest = &rest[token.len()..];
+        } else {
+            if character == STRFTIME_PERCENT {
+                format.push_str(STRFTIME_ESCAPED_PERCENT);
+            } else {
+                format.push(character);
+            }
+            rest = &rest[character.len_utf8()..];
+        }
+    }
+    format
+}
+
+/// Lays out a note ok creates: the rendered template's properties with `set_properties`
+/// applied over them, then the template's body, then `body`. Without a template the note is
+/// just the properties and `body`.
+pub(crate) fn compose_note(
+    rendered_template: Option<&str>,
+    set_properties: impl FnOnce(&mut FrontMatter),
+    body: &str,
+) -> Result<String, YamlFrontMatterError> {
+    let (mut front_matter, template_body) = match rendered_template {
+        None => (FrontMatter::default(), ""),
+        Some(template) => match yaml_frontmatter::find_yaml_section(template) {
+            Ok(Some((yaml, template_body))) => (FrontMatter::from_yaml_str(yaml)?, template_body),
+            Ok(None) | Err(YamlFrontMatterError::Missing | YamlFrontMatterError::Empty) => {
+                (FrontMatter::default(), template)
+            },
+            Err(error) => return Err(error),
+        },
+    };
+    set_properties(&mut front_matter);
+
+    let separator = if !template_body.is_empty() && !template_body.ends_with(NEWLINE) {
+        NEWLINE.to_string()
+    } else {
+        String::new()
+    };
+    Ok(format!(
+        "{YAML_OPENING_DELIMITER}{}\n{YAML_CLOSING_DELIMITER}{template_body}{separator}{body}",
+        front_matter.to_yaml_str()?.trim()
+    ))
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use chrono::Utc;
+    use chrono_tz::Tz;
+    use serde_yaml::Value;
+    use tempfile::TempDir;
+
+    use super::NoteTemplate;
+    use super::NoteTemplateError;
+    use super::chrono_format;
+    use super::compose_note;
+    use super::template_folders;
+    use crate::config::Config;
+    use crate::constants::DEFAULT_TIMEZONE;
+
+    #[test]
+    fn test_note_template_renders_core_templates_variables() {
+        assert_eq!(chrono_format("YYYY-MM-DD"), "%Y-%m-%d");
+        assert_eq!(
+            chrono_format("dddd D MMMM [at] HH:mm"),
+            "%A %-d %B at %H:%M"
+        );
+        assert_eq!(chrono_format("[100%] GGGG-[W]WW"), "100%% %G-W%V");
+
+        let temp_dir = TempDir::new().unwrap();
+        let obsidian_folder = temp_dir.path().join(".obsidian");
+        fs::create_dir_all(&obsidian_folder).unwrap();
+        fs::write(
+            obsidian_folder.join("templates.json"),
+            r#"{"folder": "meta/templates", "dateFormat": "YYYY"}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(temp_dir.path().join("meta/templates")).unwrap();
+        fs::write(
+            temp_dir.path().join("meta/templates/Person.md"),
+            "---\ntags: [person]\n---\n# {{title}} in {{folder}}\n{{date}} {{date:YY}} {{weather}}\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            NoteTemplate::load(temp_dir.path(), "Missing"),
+            Err(NoteTemplateError::Missing(_))
+        ));
+        let template = NoteTemplate::load(temp_dir.path(), "Person").unwrap();
+        let rendered = template.render(
+            &temp_dir.path().join("people/Ada.md"),
+            temp_dir.path(),
+            DEFAULT_TIMEZONE,
+        );
+        let year = Utc::now()
+            .with_timezone(&DEFAULT_TIMEZONE.parse::<Tz>().unwrap())
+            .format("%Y")
+            .to_string();
+        assert_eq!(
+            rendered,
+            format!(
+                "---\ntags: [person]\n---\n# Ada in people\n{year} {} {{{{weather}}}}\n",
+                &year[2..]
+            )
+        );
+
+        let note = compose_note(
+            Some(&rendered),
+            |front_matter| front_matter.set_property("up", Value::String("[[People]]".into())),
+            "body\n",
+        )
+        .unwrap();
+        assert_eq!(
+            note,
+            format!(
+                "---\ntags:\n- person\nup: '[[People]]'\n---\n# Ada in people\n{year} {} \
+                 {{{{weather}}}}\nbody\n",
+                &year[2..]
+            )
+        );
+
+        assert_eq!(
+            compose_note(None, |_| {}, "body\n").unwrap(),
+            "---\n{}\n---\nbody\n"
+        );
+    }
+
+    #[test]
+    fn test_template_folders_detected_and_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let obsidian_folder = temp_dir.path().join(".obsidian");
+        let templater_folder = obsidian_folder.join("plugins/templater-obsidian");
+        fs::create_dir_all(&templater_folder).unwrap();
+        fs::write(
+            obsidian_folder.join("templates.json"),
+            r#"{"folder": "Templates/"}"#,
+        )
+        .unwrap();
+        fs::write(
+            templater_folder.join("data.json"),
+            r#"{"templates_folder": "meta/templater", "trigger_on_file_creation": true}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(temp_dir.path().join("Templates")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("meta/templater")).unwrap();
+
+        assert_eq!(
+            template_folders(temp_dir.path()),
+            vec![PathBuf::from("Templates"), PathBuf::from("meta/templater")]
+        );
+
+        let config: Config = serde_yaml::from_str(&format!(
+            "obsidian_path: {}\napply_changes: false",
+            temp_dir.path().display()
+        ))
+        .unwrap();
+        let validated_config = config.validate().unwrap();
+        let ignore_folders = validated_config.ignore_folders().unwrap();
+        assert!(ignore_folders.contains(&temp_dir.path().join("Templates")));
+        assert!(ignore_folders.contains(&temp_dir.path().join("meta/templater")));
+        assert_eq!(validated_config.template_folders().len(), 2);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use crate::constants::ACRONYM_PROPERTY;
+use crate::markdown_files::MarkdownFiles;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink;
+use crate::wikilink::Wikilink;
+
+/// An acronym left unlinked because more than one note claims it - as its acronym, its name or
+/// one of its aliases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct AcronymCollision {
+    pub acronym: String,
+    pub paths:   Vec<PathBuf>,
+}
+
+impl ObsidianRepository {
+    /// With `link_acronyms` on, each acronym a note declares in its `acronym` property becomes
+    /// a back populate pattern for that note, so `OKR` is linked as
+    /// `[[Objectives and Key Results|OKR]]`. An acronym claimed by more than one note is left
+    /// out and reported instead. Returns the acronyms that were added.
+    pub(super) fn link_acronyms(
+        markdown_files: &mut MarkdownFiles,
+        validated_config: &ValidatedConfig,
+    ) -> (Vec<String>, Vec<AcronymCollision>) {
+        if !validated_config.link_acronyms() {
+            return (Vec::new(), Vec::new());
+        }
+
+        // Keyed by the lowercased acronym - the automaton matches case-insensitively, so `OKR`
+        // and `okr` would compete for the same text.
+        let mut declared: BTreeMap<String, Vec<(usize, String)>> = BTreeMap::new();
+        for (position, markdown_file) in markdown_files.iter().enumerate() {
+            let Some(front_matter) = &markdown_file.front_matter else {
+                continue;
+            };
+            for acronym in front_matter.property_strings(ACRONYM_PROPERTY) {
+                let acronym = acronym.trim();
+                if !acronym.is_empty() {
+                    declared
+                        .entry(acronym.to_lowercase())
+                        .or_default()
+                        .push((position, acronym.to_string()));
+                }
+            }
+        }
+
+        let mut acronyms = Vec::new();
+        let mut collisions = Vec::new();
+        for (lowercase, declarations) in declared {
+            let mut claimants: Vec<usize> =
+                declarations.iter().map(|(position, _)| *position).collect();
+            claimants.extend(
+                markdown_files
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, markdown_file)| {
+                        markdown_file
+                            .wikilinks
+                            .valid
+                            .iter()
+                            .any(|wikilink| wikilink.display_text.to_lowercase() == lowercase)
+                    })
+                    .map(|(position, _)| position),
+            );
+            claimants.sort_unstable();
+            claimants.dedup();
+
+            let (position, acronym) = &declarations[0];
+            if claimants.len() > 1 {
+                collisions.push(AcronymCollision {
+                    acronym: acronym.clone(),
+                    paths:   claimants
+                        .iter()
+                        .map(|&claimant| markdown_files[claimant].path.clone())
+                        .collect(),
+                });
+                continue;
+            }
+
+            let markdown_file = &mut markdown_files[*position];
+            let filename = markdown_file
+                .path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default();
+            markdown_file.wikilinks.valid.push(Wikilink {
+                display_text: acronym.clone(),
+                target:       wikilink::create_filename_wikilink(filename).target,
+            });
+            acronyms.push(acronym.clone());
+        }
+
+        (acronyms, collisions)
+    }
+
+    /// Drops back populate matches that found an acronym written in another case - `okr` in
+    /// prose is rarely the acronym.
+    pub(super) fn drop_miscased_acronym_matches(&mut self) {
+        if self.acronyms.is_empty() {
+            return;
+        }
+        for markdown_file in &mut self.markdown_files {
+            markdown_file
+                .back_populate_matches
+                .unambiguous
+                .retain(|back_populate_match| {
+                    !self.acronyms.iter().any(|acronym| {
+                        acronym.eq_ignore_ascii_case(&back_populate_match.found_text)
+                            && **acronym != *back_populate_match.found_text
+                    })
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_acronyms_linked_case_sensitively_and_collisions_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Objectives and Key Results".to_string())
+            .with_custom_frontmatter("acronym: OKR".to_string())
+            .create(&temp_dir, "Objectives and Key Results.md");
+        TestFileBuilder::new()
+            .with_title("Return on Investment".to_string())
+            .with_custom_frontmatter("acronym: ROI".to_string())
+            .create(&temp_dir, "Return on Investment.md");
+        TestFileBuilder::new()
+            .with_title("Region of Interest".to_string())
+            .with_custom_frontmatter("acronym: ROI".to_string())
+            .create(&temp_dir, "Region of Interest.md");
+        TestFileBuilder::new()
+            .with_title("Planning".to_string())
+            .with_content(
+                "Our OKR for the quarter, not the okr draft, has a ROI target.".to_string(),
+            )
+            .create(&temp_dir, "Planning.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.link_acronyms(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        assert_eq!(obsidian_repository.acronyms, vec!["OKR".to_string()]);
+        assert_eq!(obsidian_repository.acronym_collisions.len(), 1);
+        assert_eq!(obsidian_repository.acronym_collisions[0].acronym, "ROI");
+        assert_eq!(obsidian_repository.acronym_collisions[0].paths.len(), 2);
+
+        let planning = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|markdown_file| markdown_file.path.ends_with("Planning.md"))
+            .unwrap();
+        assert!(
+            planning.content.contains(
+                "Our [[Objectives and Key Results|OKR]] for the quarter, not the okr draft"
+            )
+        );
+        assert!(planning.content.contains("has a ROI target"));
+    }
+}
@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use anyhow::Result as AnyhowResult;
+use regex::Regex;
+
+use super::ObsidianRepository;
+use super::constants::MIN_ACRONYM_WORDS;
+use super::constants::MIN_INFLECTED_WORD_LENGTH;
+use super::constants::PARENTHETICAL_SUFFIX_PATTERN;
+use super::constants::PLURAL_ES_ENDINGS;
+use super::constants::PLURAL_SUFFIX_IES;
+use super::constants::SINGULAR_SUFFIX_Y;
+use super::constants::SUFFIX_ES;
+use super::constants::SUFFIX_S;
+use super::constants::UNINFLECTED_S_ENDINGS;
+use super::constants::VOWELS;
+use crate::constants::ACRONYM;
+use crate::constants::PLURAL;
+use crate::constants::SINGULAR;
+use crate::constants::WITHOUT_PARENTHETICAL;
+use crate::markdown_files::MarkdownFiles;
+use crate::support;
+use crate::validated_config::ValidatedConfig;
+
+static PARENTHETICAL_SUFFIX_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| support::compile_regex(PARENTHETICAL_SUFFIX_PATTERN));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AliasVariant {
+    Acronym,
+    Plural,
+    Singular,
+    WithoutParenthetical,
+}
+
+impl Display for AliasVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Acronym => f.write_str(ACRONYM),
+            Self::Plural => f.write_str(PLURAL),
+            Self::Singular => f.write_str(SINGULAR),
+            Self::WithoutParenthetical => f.write_str(WITHOUT_PARENTHETICAL),
+        }
+    }
+}
+
+/// An alias `generate_aliases` added to a note.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct GeneratedAlias {
+    pub path:    PathBuf,
+    pub alias:   String,
+    pub variant: AliasVariant,
+}
+
+/// The plural of a singular last word, or the singular of a plural one. Words too short to
+/// inflect reliably, or holding anything but letters, or written in capitals, are left alone.
+fn inflect(word: &str) -> Option<(String, AliasVariant)> {
+    if word.chars().count() < MIN_INFLECTED_WORD_LENGTH
+        || !word.chars().all(char::is_alphabetic)
+        || !word.chars().any(char::is_lowercase)
+    {
+        return None;
+    }
+
+    if let Some(base) = word.strip_suffix(PLURAL_SUFFIX_IES) {
+        return Some((format!("{base}{SINGULAR_SUFFIX_Y}"), AliasVariant::Singular));
+    }
+    if let Some(base) = word.strip_suffix(SUFFIX_ES)
+        && PLURAL_ES_ENDINGS
+            .iter()
+            .any(|ending| base.ends_with(ending))
+    {
+        return Some((base.to_string(), AliasVariant::Singular));
+    }
+    if PLURAL_ES_ENDINGS
+        .iter()
+        .any(|ending| word.ends_with(ending))
+    {
+        return Some((format!("{word}{SUFFIX_ES}"), AliasVariant::Plural));
+    }
+    if UNINFLECTED_S_ENDINGS
+        .iter()
+        .any(|ending| word.ends_with(ending))
+    {
+        return None;
+    }
+    if let Some(base) = word.strip_suffix(SUFFIX_S) {
+        return Some((base.to_string(), AliasVariant::Singular));
+    }
+    if let Some(base) = word.strip_suffix(SINGULAR_SUFFIX_Y)
+        && !base.ends_with(VOWELS)
+    {
+        return Some((format!("{base}{PLURAL_SUFFIX_IES}"), AliasVariant::Plural));
+    }
+    Some((format!("{word}{SUFFIX_S}"), AliasVariant::Plural))
+}
+
+/// The variants of a note name worth matching: the name without a parenthetical suffix
+/// (`Mercury (planet)`), the acronym of a name whose words are all capitalized, and the name
+/// with its last word made plural or singular.
+fn alias_variants(stem: &str) -> Vec<(String, AliasVariant)> {
+    let mut variants = Vec::new();
+
+    let without_parenthetical = PARENTHETICAL_SUFFIX_REGEX
+        .captures(stem)
+        .and_then(|captures| captures.get(1))
+        .map(|base| base.as_str().trim());
+    if let Some(base) = without_parenthetical {
+        variants.push((base.to_string(), AliasVariant::WithoutParenthetical));
+    }
+    let base = without_parenthetical.unwrap_or_else(|| stem.trim());
+
+    let words: Vec<&str> = base.split_whitespace().collect();
+    if words.len() >= MIN_ACRONYM_WORDS
+        && words
+            .iter()
+            .all(|word| word.chars().next().is_some_and(char::is_uppercase))
+    {
+        variants.push((
+            words
+                .iter()
+                .filter_map(|word| word.chars().next())
+                .collect(),
+            AliasVariant::Acronym,
+        ));
+    }
+
+    if let Some(last_word) = words.last()
+        && let Some((inflected, variant)) = inflect(last_word)
+        && let Some(leading) = base.strip_suffix(last_word)
+    {
+        variants.push((format!("{leading}{inflected}"), variant));
+    }
+
+    variants
+}
+
+impl ObsidianRepository {
+    /// With `generate_aliases` on, adds the variants of each note's name as aliases so back
+    /// populate finds them. A variant that matches any note name or alias, or that more than
+    /// one note would generate, would make matches ambiguous and is skipped. Runs before the
+    /// wikilinks are collected, so the new aliases are matched like any other.
+    pub(super) fn generate_aliases(
+        markdown_files: &mut MarkdownFiles,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<Vec<GeneratedAlias>> {
+        if !validated_config.generate_aliases() {
+            return Ok(Vec::new());
+        }
+
+        let mut taken: HashSet<String> = HashSet::new();
+        for markdown_file in markdown_files.iter() {
+            if let Some(stem) = markdown_file.path.file_stem().and_then(OsStr::to_str) {
+                taken.insert(stem.to_lowercase());
+            }
+            if let Some(aliases) = markdown_file
+                .front_matter
+                .as_ref()
+                .and_then(|front_matter| front_matter.aliases())
+            {
+                taken.extend(aliases.iter().map(|alias| alias.to_lowercase()));
+            }
+        }
+
+        let candidates: Vec<Vec<(String, AliasVariant)>> = markdown_files
+            .iter()
+            .map(|markdown_file| {
+                let stem = markdown_file
+                    .path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or_default();
+                let mut seen = HashSet::new();
+                alias_variants(stem)
+                    .into_iter()
+                    .filter(|(alias, _)| {
+                        let alias = alias.to_lowercase();
+                        !taken.contains(&alias) && seen.insert(alias)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut generated_by: HashMap<String, usize> = HashMap::new();
+        for (alias, _) in candidates.iter().flatten() {
+            *generated_by.entry(alias.to_lowercase()).or_default() += 1;
+        }
+
+        let mut generated = Vec::new();
+        for (markdown_file, file_candidates) in markdown_files.iter_mut().zip(candidates) {
+            let file_aliases: Vec<(String, AliasVariant)> = file_candidates
+                .into_iter()
+                .filter(|(alias, _)| generated_by.get(&alias.to_lowercase()) == Some(&1))
+                .collect();
+            if file_aliases.is_empty() {
+                continue;
+            }
+
+            let aliases: Vec<String> = file_aliases
+                .iter()
+                .map(|(alias, _)| alias.clone())
+                .collect();
+            markdown_file
+                .add_generated_aliases(&aliases, validated_config.operational_timezone())?;
+            generated.extend(
+                file_aliases
+                    .into_iter()
+                    .map(|(alias, variant)| GeneratedAlias {
+                        path: markdown_file.path.clone(),
+                        alias,
+                        variant,
+                    }),
+            );
+        }
+
+        Ok(generated)
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::AliasVariant;
+    use super::alias_variants;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_alias_variants() {
+        assert_eq!(
+            alias_variants("Mercury (planet)"),
+            vec![
+                ("Mercury".to_string(), AliasVariant::WithoutParenthetical),
+                ("Mercuries".to_string(), AliasVariant::Plural),
+            ]
+        );
+        assert_eq!(
+            alias_variants("Natural Language Processing"),
+            vec![
+                ("NLP".to_string(), AliasVariant::Acronym),
+                (
+                    "Natural Language Processings".to_string(),
+                    AliasVariant::Plural
+                ),
+            ]
+        );
+        assert_eq!(
+            alias_variants("Board Games"),
+            vec![("Board Game".to_string(), AliasVariant::Singular)]
+        );
+        assert_eq!(
+            alias_variants("Strategies"),
+            vec![("Strategy".to_string(), AliasVariant::Singular)]
+        );
+        assert_eq!(
+            alias_variants("Sandwich"),
+            vec![("Sandwiches".to_string(), AliasVariant::Plural)]
+        );
+        assert!(alias_variants("Status").is_empty());
+        assert!(alias_variants("2024-05-02").is_empty());
+        assert!(alias_variants("API").is_empty());
+    }
+
+    #[test]
+    fn test_generated_aliases_skip_collisions_and_back_populate() {
+        let temp_dir = TempDir::new().unwrap();
+        for (file_name, content) in [
+            ("Board Games.md", "collection"),
+            ("Board Game.md", "taken singular"),
+            ("Tokyo (city).md", "capital"),
+            ("Journal.md", "visited tokyo last spring"),
+        ] {
+            TestFileBuilder::new()
+                .with_title(file_name.trim_end_matches(".md").to_string())
+                .with_content(content.to_string())
+                .create(&temp_dir, file_name);
+        }
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.generate_aliases(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let generated: Vec<(String, String)> = obsidian_repository
+            .generated_aliases
+            .iter()
+            .map(|generated| {
+                (
+                    generated
+                        .path
+                        .file_stem()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    generated.alias.clone(),
+                )
+            })
+            .collect();
+        assert!(generated.contains(&("Tokyo (city)".to_string(), "Tokyo".to_string())));
+        assert!(
+            !generated
+                .iter()
+                .any(|(_, alias)| alias.eq_ignore_ascii_case("board game")),
+            "a variant naming an existing note is skipped"
+        );
+
+        let journal = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|markdown_file| markdown_file.path.ends_with("Journal.md"))
+            .unwrap();
+        assert_eq!(journal.back_populate_matches.unambiguous.len(), 1);
+    }
+}
@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use crate::config::AuditTrail;
+use crate::constants::AUDIT_NOTE_FILE;
+use crate::constants::NEWLINE;
+use crate::frontmatter::FrontMatter;
+use crate::support;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+impl ObsidianRepository {
+    /// With `audit_trail: frontmatter`, adds today's entry to `knife_history` in every note with
+    /// changes to write. Runs last in the analysis so the entry names every change.
+    pub(crate) fn record_audit_trail(&mut self, validated_config: &ValidatedConfig) {
+        if validated_config.audit_trail() != Some(AuditTrail::Frontmatter) {
+            return;
+        }
+
+        let today = validated_config.today();
+        for markdown_file in &mut self.markdown_files {
+            if !markdown_file
+                .front_matter
+                .as_ref()
+                .is_some_and(FrontMatter::needs_persist)
+            {
+                continue;
+            }
+            if let Some(changes) = markdown_file.audit_changes() {
+                markdown_file.append_knife_history(format!("{today} {changes}"));
+            }
+        }
+    }
+
+    /// With `audit_trail: note`, appends a line for each written note to the audit note in the
+    /// output folder. Notes in `skipped` weren't written, so they're left out.
+    pub(crate) fn append_audit_note(
+        &self,
+        validated_config: &ValidatedConfig,
+        skipped: &[PathBuf],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if validated_config.audit_trail() != Some(AuditTrail::Note) {
+            return Ok(());
+        }
+
+        let today = validated_config.today();
+        let lines: Vec<String> = self
+            .markdown_files
+            .files_to_persist()
+            .iter()
+            .filter(|markdown_file| !skipped.contains(&markdown_file.path))
+            .filter_map(|markdown_file| {
+                let changes = markdown_file.audit_changes()?;
+                let note = markdown_file
+                    .path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or_default()
+                    .to_wikilink();
+                Some(format!("- {today} {note} {changes}"))
+            })
+            .collect();
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(validated_config.output_folder())?;
+        let audit_note_path = validated_config.output_folder().join(AUDIT_NOTE_FILE);
+        let mut content = fs::read_to_string(&audit_note_path).unwrap_or_default();
+        if !content.is_empty() && !content.ends_with(NEWLINE) {
+            content.push(NEWLINE);
+        }
+        for line in lines {
+            content.push_str(&line);
+            content.push(NEWLINE);
+        }
+
+        support::write_atomically(&audit_note_path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use serde_yaml::Value;
+    use tempfile::TempDir;
+
+    use crate::config::AuditTrail;
+    use crate::constants::AUDIT_NOTE_FILE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_audit_trail_records_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Topic".to_string())
+            .with_content("about the topic")
+            .create(&temp_dir, "topic.md");
+        TestFileBuilder::new()
+            .with_custom_frontmatter("knife_history: 2020-01-01 earlier".to_string())
+            .with_content("mentions Topic twice: Topic")
+            .create(&temp_dir, "mentions.md");
+
+        for audit_trail in [AuditTrail::Frontmatter, AuditTrail::Note] {
+            let validated_config =
+                test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                    builder.audit_trail(Some(audit_trail));
+                })
+                .unwrap();
+            let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+            let mentions = obsidian_repository
+                .markdown_files
+                .iter()
+                .find(|file| file.path.ends_with("mentions.md"))
+                .unwrap();
+            let changes = mentions.audit_changes().unwrap();
+            assert!(changes.contains("back populated (2)"), "{changes}");
+
+            let today = validated_config.today();
+            let history = mentions
+                .front_matter
+                .as_ref()
+                .unwrap()
+                .property("knife_history")
+                .unwrap();
+            obsidian_repository
+                .append_audit_note(&validated_config, &[])
+                .unwrap();
+            let audit_note =
+                fs::read_to_string(validated_config.output_folder().join(AUDIT_NOTE_FILE))
+                    .unwrap_or_default();
+
+            match audit_trail {
+                AuditTrail::Frontmatter => {
+                    assert_eq!(
+                        history,
+                        &Value::Sequence(vec![
+                            Value::String("2020-01-01 earlier".to_string()),
+                            Value::String(format!("{today} {changes}")),
+                        ])
+                    );
+                    assert!(audit_note.is_empty());
+                },
+                AuditTrail::Note => {
+                    assert_eq!(history, &Value::String("2020-01-01 earlier".to_string()));
+                    assert!(audit_note.contains(&format!("- {today} [[mentions]] {changes}\n")));
+                },
+            }
+        }
+    }
+}
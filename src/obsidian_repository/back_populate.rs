@@ -1,7 +1,6 @@
-use std::cmp::Reverse;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::convert::AsRef;
 use std::mem::take;
 use std::path::Path;
 
@@ -10,32 +9,45 @@ use anyhow::Result as AnyhowResult;
 use anyhow::bail;
 
 use super::ObsidianRepository;
-use super::constants::FIRST_CONTENT_LINE_NUMBER;
 use super::constants::INVALID_UTF8_BOUNDARY_PREFIX;
 use super::constants::MIN_AMBIGUOUS_TARGETS;
 use super::constants::NESTED_PATTERN_WARNING;
+use super::constants::OVERLAPPING_REPLACEMENT_PREFIX;
+use super::constants::REPLACEMENT_OUTSIDE_BODY_PREFIX;
+use super::constants::REPLACEMENT_TEXT_MISMATCH_PREFIX;
 use super::constants::TRIPLE_CLOSING_BRACKETS;
 use super::constants::TRIPLE_OPENING_BRACKETS;
 use super::constants::UNCLASSIFIED_MATCH_WARNING;
 use super::constants::WIKILINKS_AUTOMATON_NOT_INITIALIZED;
 use super::constants::WIKILINKS_AUTOMATON_NOT_INITIALIZED_DETAIL;
+use super::replacement_conflicts;
+use super::replacement_conflicts::ReplacementConflict;
+use crate::app_settings::LinkFormat;
+use crate::config::LinkAliasStyle;
+use crate::constants::CARRIAGE_RETURN;
 use crate::constants::NEWLINE;
 use crate::markdown_file::BackPopulateMatch;
+use crate::markdown_file::ByteSpan;
 use crate::markdown_file::ImageLinkState;
 use crate::markdown_file::MarkdownFile;
 use crate::markdown_file::MatchType;
 use crate::markdown_file::ReplaceableContent;
-use crate::support::VecEnumFilter;
 use crate::validated_config::ValidatedConfig;
 use crate::wikilink::Wikilink;
 
 #[derive(Debug, Default)]
 struct ChangeSet {
-    match_types: Vec<MatchType>,
+    match_types:   Vec<MatchType>,
+    /// Replacements applied per `ReplacementSource`, by name - each source gets its own
+    /// persist reason.
+    custom_counts: BTreeMap<String, usize>,
 }
 
 impl ChangeSet {
     fn merge(&mut self, match_type: MatchType) {
+        if let MatchType::Custom(source) = &match_type {
+            *self.custom_counts.entry(source.clone()).or_default() += 1;
+        }
         if !self.contains(&match_type) {
             self.match_types.push(match_type);
         }
@@ -46,28 +58,7 @@ impl ChangeSet {
 
 impl ObsidianRepository {
     pub fn identify_ambiguous_matches(&mut self) {
-        // `target_map` records the canonical `Wikilink.target` for each lowercase target.
-        let mut target_map: HashMap<String, String> = HashMap::new();
-        for wikilink in &self.wikilinks_sorted {
-            let lower_target = wikilink.target.to_lowercase();
-            if !target_map.contains_key(&lower_target)
-                || wikilink.target.to_lowercase() == wikilink.target
-            {
-                target_map.insert(lower_target.clone(), wikilink.target.clone());
-            }
-        }
-
-        let mut display_text_map: HashMap<String, HashSet<String>> = HashMap::new();
-        for wikilink in &self.wikilinks_sorted {
-            let lower_display_text = wikilink.display_text.to_lowercase();
-            let lower_target = wikilink.target.to_lowercase();
-            if let Some(canonical_target) = target_map.get(&lower_target) {
-                display_text_map
-                    .entry(lower_display_text.clone())
-                    .or_default()
-                    .insert(canonical_target.clone());
-            }
-        }
+        let display_text_map = self.targets_by_display_text();
 
         // `MarkdownFile.back_populate_matches.unambiguous` is split into ambiguous
         // and still-unambiguous matches.
@@ -119,6 +110,34 @@ impl ObsidianRepository {
         }
     }
 
+    /// Maps each lowercased wikilink display text to the canonical targets it links, so a text
+    /// with more than one target is ambiguous.
+    pub(super) fn targets_by_display_text(&self) -> HashMap<String, HashSet<String>> {
+        // `target_map` records the canonical `Wikilink.target` for each lowercase target.
+        let mut target_map: HashMap<String, String> = HashMap::new();
+        for wikilink in &self.wikilinks_sorted {
+            let lower_target = wikilink.target.to_lowercase();
+            if !target_map.contains_key(&lower_target)
+                || wikilink.target.to_lowercase() == wikilink.target
+            {
+                target_map.insert(lower_target.clone(), wikilink.target.clone());
+            }
+        }
+
+        let mut display_text_map: HashMap<String, HashSet<String>> = HashMap::new();
+        for wikilink in &self.wikilinks_sorted {
+            let lower_display_text = wikilink.display_text.to_lowercase();
+            let lower_target = wikilink.target.to_lowercase();
+            if let Some(canonical_target) = target_map.get(&lower_target) {
+                display_text_map
+                    .entry(lower_display_text.clone())
+                    .or_default()
+                    .insert(canonical_target.clone());
+            }
+        }
+        display_text_map
+    }
+
     pub fn find_all_back_populate_matches(
         &mut self,
         validated_config: &ValidatedConfig,
@@ -135,204 +154,372 @@ impl ObsidianRepository {
             &sorted_wikilinks,
             automaton,
         );
+
+        let style = validated_config.link_alias_style();
+        if style != LinkAliasStyle::KeepCase {
+            let note_paths = self.note_paths_by_stem(validated_config);
+            for markdown_file in &mut self.markdown_files {
+                markdown_file.style_back_populate_replacements(&note_paths, style);
+            }
+        }
         Ok(())
     }
 
-    pub fn apply_replaceable_matches(&mut self, operational_timezone: &str) -> AnyhowResult<()> {
+    /// Writes the back populate links that are left after match decisions in the vault's new
+    /// link format - after, because markdown links are no longer wikilinks to retarget.
+    pub(super) fn format_back_populate_links(&mut self, validated_config: &ValidatedConfig) {
+        let link_format = validated_config.link_format();
+        if link_format == LinkFormat::default() {
+            return;
+        }
+        let note_paths = self.note_paths_by_stem(validated_config);
         for markdown_file in &mut self.markdown_files {
-            let has_replaceable_image_links = markdown_file.image_links.iter().any(|link| {
-                matches!(
-                    link.state,
-                    ImageLinkState::Missing
-                        | ImageLinkState::Duplicate { .. }
-                        | ImageLinkState::Incompatible { .. }
-                )
-            });
-
-            if !markdown_file.has_unambiguous_matches()
-                && !markdown_file.has_canonical_link_matches()
-                && !markdown_file.has_phantom_link_matches()
-                && !has_replaceable_image_links
-            {
-                continue;
-            }
+            markdown_file.format_back_populate_links(&note_paths, link_format);
+        }
+    }
 
-            let sorted_replaceable_matches = Self::collect_replaceable_matches(markdown_file);
+    pub fn apply_replaceable_matches(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let operational_timezone = validated_config.operational_timezone();
+        let fix_self_links = validated_config.fix_self_links();
+        let mut replacement_conflicts = Vec::new();
 
-            if sorted_replaceable_matches.is_empty() {
+        for markdown_file in &mut self.markdown_files {
+            // Collecting only borrows the matches, so a note with none left after filtering is
+            // skipped without its body being copied.
+            let (sorted_replaceable_matches, conflicts) =
+                Self::collect_replaceable_matches(markdown_file, fix_self_links);
+            let replaced = if sorted_replaceable_matches.is_empty() {
+                None
+            } else {
+                Some(replace_body_spans(
+                    markdown_file,
+                    &sorted_replaceable_matches,
+                )?)
+            };
+            replacement_conflicts::drop_skipped_matches(markdown_file, &conflicts);
+            replacement_conflicts.extend(conflicts);
+            let Some((updated_content, change_set)) = replaced else {
                 continue;
-            }
-
-            let mut updated_content = String::new();
-            let mut content_line_number = FIRST_CONTENT_LINE_NUMBER;
-            let mut change_set = ChangeSet::default();
-
-            for (zero_based_idx, line) in markdown_file.content.lines().enumerate() {
-                let current_content_line = zero_based_idx + 1;
-                let absolute_line_number =
-                    current_content_line + markdown_file.frontmatter_line_count;
-
-                if content_line_number != current_content_line {
-                    updated_content.push_str(line);
-                    updated_content.push(NEWLINE);
-                    continue;
-                }
-
-                let line_matches: Vec<&dyn ReplaceableContent> = sorted_replaceable_matches
-                    .iter()
-                    .filter(|m| m.line_number() == absolute_line_number)
-                    .map(AsRef::as_ref)
-                    .collect();
-
-                if line_matches.is_empty() {
-                    updated_content.push_str(line);
-                    updated_content.push(NEWLINE);
-                } else {
-                    let updated_line =
-                        apply_line_replacements(line, &line_matches, &markdown_file.path)?;
-
-                    // ChangeSet records which MatchType values changed the MarkdownFile.
-                    for line_match in &line_matches {
-                        change_set.merge(line_match.match_type());
-                    }
+            };
+            markdown_file.content = updated_content;
 
-                    if !updated_line.is_empty() {
-                        updated_content.push_str(&updated_line);
-                        updated_content.push(NEWLINE);
-                    }
-                }
-                content_line_number += 1;
+            if change_set.contains(&MatchType::AdmonitionConversion) {
+                let count = markdown_file.admonition_conversions.len();
+                markdown_file.mark_admonitions_converted(count, operational_timezone)?;
             }
-
-            markdown_file.content = updated_content.trim_end().to_string();
-
             if change_set.contains(&MatchType::BackPopulate) {
                 markdown_file.mark_as_back_populated(operational_timezone)?;
             }
             if change_set.contains(&MatchType::CanonicalLink) {
                 markdown_file.mark_links_canonicalized(operational_timezone)?;
             }
+            for (source, count) in &change_set.custom_counts {
+                markdown_file.mark_custom_replacements_applied(
+                    source,
+                    *count,
+                    operational_timezone,
+                )?;
+            }
+            if change_set.contains(&MatchType::DateMention) {
+                markdown_file.mark_date_mentions_linked(operational_timezone)?;
+            }
+            if change_set.contains(&MatchType::HeadingLinkRepair) {
+                markdown_file.mark_heading_links_repaired(operational_timezone)?;
+            }
             if change_set.contains(&MatchType::ImageReference) {
                 markdown_file.mark_image_reference_as_updated(operational_timezone)?;
             }
             if change_set.contains(&MatchType::PhantomLink) {
                 markdown_file.mark_phantom_links_resolved(operational_timezone)?;
             }
+            if change_set.contains(&MatchType::RegexReplacement) {
+                markdown_file.mark_regex_replacements_applied(operational_timezone)?;
+            }
+            if change_set.contains(&MatchType::SelfLink) {
+                markdown_file.mark_self_links_cleaned(operational_timezone)?;
+            }
+            if change_set.contains(&MatchType::WikilinkRepair) {
+                markdown_file.mark_wikilinks_repaired(operational_timezone)?;
+            }
         }
+
+        self.replacement_conflicts = replacement_conflicts;
         Ok(())
     }
 
-    fn collect_replaceable_matches(
+    /// Every replacement queued for `markdown_file`, in document order, and the ones left out
+    /// because they overlap a replacement ranking ahead of them - see `resolve_conflicts`.
+    /// Later phases call this to keep their own matches clear of the ones already found.
+    pub(super) fn collect_replaceable_matches(
         markdown_file: &MarkdownFile,
-    ) -> Vec<Box<dyn ReplaceableContent>> {
-        let mut matches = Vec::new();
+        fix_self_links: bool,
+    ) -> (Vec<&dyn ReplaceableContent>, Vec<ReplacementConflict>) {
+        let mut matches: Vec<&dyn ReplaceableContent> = Vec::new();
 
         matches.extend(
             markdown_file
                 .back_populate_matches
                 .unambiguous
                 .iter()
-                .cloned()
-                .map(|m| Box::new(m) as Box<dyn ReplaceableContent>),
+                .map(|m| m as &dyn ReplaceableContent),
+        );
+
+        matches.extend(
+            markdown_file
+                .admonition_conversions
+                .iter()
+                .flat_map(|conversion| conversion.line_matches.iter())
+                .map(|m| m as &dyn ReplaceableContent),
         );
 
         matches.extend(
             markdown_file
                 .canonical_link_matches
                 .iter()
-                .cloned()
-                .map(|m| Box::new(m) as Box<dyn ReplaceableContent>),
+                .map(|m| m as &dyn ReplaceableContent),
+        );
+
+        matches.extend(
+            markdown_file
+                .custom_replacement_matches
+                .iter()
+                .map(|m| m as &dyn ReplaceableContent),
+        );
+
+        matches.extend(
+            markdown_file
+                .date_mention_matches
+                .iter()
+                .map(|m| m as &dyn ReplaceableContent),
+        );
+
+        matches.extend(
+            markdown_file
+                .heading_link_repair_matches
+                .iter()
+                .map(|m| m as &dyn ReplaceableContent),
         );
 
         matches.extend(
             markdown_file
                 .phantom_link_matches
                 .iter()
-                .cloned()
-                .map(|m| Box::new(m) as Box<dyn ReplaceableContent>),
+                .map(|m| m as &dyn ReplaceableContent),
+        );
+
+        matches.extend(
+            markdown_file
+                .regex_replacement_matches
+                .iter()
+                .map(|m| m as &dyn ReplaceableContent),
+        );
+
+        // Self links are always reported but only rewritten when `fix_self_links` is on.
+        if fix_self_links {
+            matches.extend(
+                markdown_file
+                    .self_link_matches
+                    .iter()
+                    .map(|m| m as &dyn ReplaceableContent),
+            );
+        }
+
+        matches.extend(
+            markdown_file
+                .wikilink_repair_matches
+                .iter()
+                .map(|m| m as &dyn ReplaceableContent),
         );
 
         matches.extend(
             markdown_file
                 .image_links
-                .filter_by_predicate(|state| {
+                .iter()
+                .filter(|link| {
                     matches!(
-                        state,
+                        link.state,
                         ImageLinkState::Incompatible { .. }
                             | ImageLinkState::Duplicate { .. }
                             | ImageLinkState::Missing
                     )
                 })
-                .iter()
-                .cloned()
-                .map(|m| Box::new(m) as Box<dyn ReplaceableContent>),
+                .map(|m| m as &dyn ReplaceableContent),
         );
 
-        // `apply_replaceable_matches` sorts replacement positions in descending byte order so
-        // earlier `replace_range` calls cannot shift later `ReplaceableContent` spans.
-        matches.sort_by_key(|m| (m.line_number(), Reverse(m.position())));
+        if matches.is_empty() {
+            return (matches, Vec::new());
+        }
 
-        matches
-    }
-}
+        // Ciphertext only decrypts byte for byte, so nothing inside an encrypted block changes.
+        let encrypted_blocks = markdown_file.encrypted_blocks();
+        matches.retain(|m| {
+            !encrypted_blocks
+                .iter()
+                .any(|block| block.contains(m.line_number()))
+        });
 
-fn apply_line_replacements(
-    line: &str,
-    line_matches: &[&dyn ReplaceableContent],
-    file_path: &Path,
-) -> AnyhowResult<String> {
-    let mut updated_line = line.to_string();
+        let (mut matches, conflicts) =
+            replacement_conflicts::resolve_conflicts(markdown_file, matches);
+
+        // Matches come back in document order; `replace_body_spans` resolves them to byte spans.
+        matches.sort_by_key(|m| (m.line_number(), m.position()));
 
-    // `sorted_matches` orders `ReplaceableContent` by descending `position`.
-    let mut sorted_matches = line_matches.to_vec();
-    sorted_matches.sort_by_key(|m| Reverse(m.position()));
+        (matches, conflicts)
+    }
+}
 
-    let has_image_replacement = sorted_matches
-        .iter()
-        .any(|m| m.match_type() == MatchType::ImageReference);
+/// One `ReplaceableContent` resolved to the body span it rewrites.
+struct SpanReplacement<'a> {
+    span:        ByteSpan,
+    replaceable: &'a dyn ReplaceableContent,
+}
 
-    // `match_info` replacements use right-to-left order.
-    for match_info in sorted_matches {
-        let start = match_info.position();
-        let end = start + match_info.matched_text().len();
+/// Where a replacement landed in the rewritten body, for the per-line pass in `tidy_lines`.
+struct AppliedEdit {
+    offset:      usize,
+    line_number: usize,
+    match_type:  MatchType,
+}
 
-        // `updated_line.is_char_boundary` guards `replace_range` byte indexes.
-        if !updated_line.is_char_boundary(start) || !updated_line.is_char_boundary(end) {
+/// Applies `matches` to the body of `markdown_file`. Each match resolves to an absolute
+/// `ByteSpan` through `MarkdownFile::body_span`; a span landing in the frontmatter or past the
+/// body, not holding the match's text, or overlapping another fails the note instead of
+/// rewriting the wrong text. The spans are then applied in one pass over the body, so a match
+/// may cover more than one line.
+fn replace_body_spans(
+    markdown_file: &MarkdownFile,
+    matches: &[&dyn ReplaceableContent],
+) -> AnyhowResult<(String, ChangeSet)> {
+    let body = markdown_file.content.as_str();
+    let line_starts = markdown_file.body_line_starts();
+
+    let mut replacements = Vec::with_capacity(matches.len());
+    for &replaceable in matches {
+        let Some(span) = markdown_file.body_span(&line_starts, replaceable) else {
             bail!(
-                "{INVALID_UTF8_BOUNDARY_PREFIX}{}, line {}: match {start}..{end} in \
-                 {updated_line:?}, found {:?}",
-                file_path.display(),
-                match_info.line_number(),
-                match_info.matched_text(),
+                "{REPLACEMENT_OUTSIDE_BODY_PREFIX}{}, line {}: {:?}",
+                markdown_file.path.display(),
+                replaceable.line_number(),
+                replaceable.matched_text(),
             );
-        }
-
-        // `updated_line.replace_range` writes the `ReplaceableContent` replacement.
-        updated_line.replace_range(start..end, &match_info.get_replacement());
-
-        // `TRIPLE_OPENING_BRACKETS` and `TRIPLE_CLOSING_BRACKETS` flag nested patterns.
-        if updated_line.contains(TRIPLE_OPENING_BRACKETS)
-            || updated_line.contains(TRIPLE_CLOSING_BRACKETS)
-        {
-            eprintln!(
-                "\n{NESTED_PATTERN_WARNING} '{}', line {}.\nCurrent line:\n{updated_line}\n",
-                file_path.display(),
-                match_info.line_number(),
+        };
+        validate_span(body, span, replaceable, &markdown_file.path)?;
+        replacements.push(SpanReplacement { span, replaceable });
+    }
+    replacements.sort_by_key(|replacement| replacement.span);
+
+    let mut updated_body = String::with_capacity(body.len());
+    let mut applied_edits = Vec::with_capacity(replacements.len());
+    let mut change_set = ChangeSet::default();
+    let mut cursor = 0;
+    for SpanReplacement { span, replaceable } in replacements {
+        if span.start < cursor {
+            bail!(
+                "{OVERLAPPING_REPLACEMENT_PREFIX}{}, line {}: {:?}",
+                markdown_file.path.display(),
+                replaceable.line_number(),
+                replaceable.matched_text(),
             );
         }
+
+        updated_body.push_str(&body[cursor..span.start]);
+        applied_edits.push(AppliedEdit {
+            offset:      updated_body.len(),
+            line_number: replaceable.line_number(),
+            match_type:  replaceable.match_type(),
+        });
+        updated_body.push_str(&replaceable.get_replacement());
+        // ChangeSet records which MatchType values changed the MarkdownFile.
+        change_set.merge(replaceable.match_type());
+        cursor = span.end;
+    }
+    updated_body.push_str(&body[cursor..]);
+
+    Ok((
+        tidy_lines(&updated_body, &applied_edits, &markdown_file.path),
+        change_set,
+    ))
+}
+
+/// Checks that `span` sits on character boundaries of `body` and holds the match's text; a
+/// span that doesn't was computed against another version of the note.
+fn validate_span(
+    body: &str,
+    span: ByteSpan,
+    replaceable: &dyn ReplaceableContent,
+    file_path: &Path,
+) -> AnyhowResult<()> {
+    let ByteSpan { start, end } = span;
+    if end <= body.len() && (!body.is_char_boundary(start) || !body.is_char_boundary(end)) {
+        bail!(
+            "{INVALID_UTF8_BOUNDARY_PREFIX}{}, line {}: match {start}..{end}, found {:?}",
+            file_path.display(),
+            replaceable.line_number(),
+            replaceable.matched_text(),
+        );
     }
 
-    // `ImageReference` replacements use `normalize_spaces` after trimming.
-    Ok(if has_image_replacement {
-        let trimmed = updated_line.trim();
-        if trimmed.is_empty() {
-            String::new()
+    let matched_text = replaceable.matched_text();
+    if body.get(start..end) != Some(matched_text.as_str()) {
+        bail!(
+            "{REPLACEMENT_TEXT_MISMATCH_PREFIX}{}, line {}: expected {matched_text:?} at \
+             {start}..{end}",
+            file_path.display(),
+            replaceable.line_number(),
+        );
+    }
+    Ok(())
+}
+
+/// Splits the rewritten body back into lines. A line a replacement emptied is dropped, and one
+/// an `ImageReference` replacement touched is trimmed with its spacing collapsed first. Other
+/// lines are copied as they are, without an allocation each.
+fn tidy_lines(updated_body: &str, applied_edits: &[AppliedEdit], file_path: &Path) -> String {
+    let mut tidied = String::with_capacity(updated_body.len());
+    let mut applied_edits = applied_edits.iter().peekable();
+    let mut line_start = 0;
+
+    for line in updated_body.split(NEWLINE) {
+        let line_end = line_start + line.len();
+        line_start = line_end + 1;
+        let line = line.strip_suffix(CARRIAGE_RETURN).unwrap_or(line);
+
+        let mut edited = false;
+        let mut has_image_replacement = false;
+        while let Some(applied_edit) = applied_edits.next_if(|edit| edit.offset <= line_end) {
+            edited = true;
+            has_image_replacement |= applied_edit.match_type == MatchType::ImageReference;
+
+            // `TRIPLE_OPENING_BRACKETS` and `TRIPLE_CLOSING_BRACKETS` flag nested patterns.
+            if line.contains(TRIPLE_OPENING_BRACKETS) || line.contains(TRIPLE_CLOSING_BRACKETS) {
+                eprintln!(
+                    "\n{NESTED_PATTERN_WARNING} '{}', line {}.\nCurrent line:\n{line}\n",
+                    file_path.display(),
+                    applied_edit.line_number,
+                );
+            }
+        }
+
+        if has_image_replacement {
+            let line = normalize_spaces(line.trim());
+            if line.is_empty() {
+                continue;
+            }
+            tidied.push_str(&line);
         } else {
-            normalize_spaces(trimmed)
+            if edited && line.is_empty() {
+                continue;
+            }
+            tidied.push_str(line);
         }
-    } else {
-        updated_line
-    })
+        tidied.push(NEWLINE);
+    }
+
+    tidied.truncate(tidied.trim_end().len());
+    tidied
 }
 
 fn normalize_spaces(text: &str) -> String { text.split_whitespace().collect::<Vec<_>>().join(" ") }
@@ -344,16 +531,33 @@ fn normalize_spaces(text: &str) -> String { text.split_whitespace().collect::<Ve
     reason = "tests should panic on unexpected values"
 )]
 mod tests {
+    use std::fs;
+    use std::sync::Arc;
+
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+    use tempfile::TempDir;
+
+    use super::replace_body_spans;
+    use crate::app_settings::LinkFormat;
+    use crate::app_settings::NewLinkFormat;
+    use crate::constants::CLOSING_WIKILINK;
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::constants::FORMAT_DATE;
+    use crate::constants::OPENING_WIKILINK;
     use crate::markdown_file::BackPopulateMatch;
     use crate::markdown_file::MarkdownFile;
     use crate::markdown_file::MatchContext;
+    use crate::markdown_file::ReplaceableContent;
     use crate::markdown_files::MarkdownFiles;
     use crate::obsidian_repository::ObsidianRepository;
     use crate::support;
+    use crate::test_fixtures;
     use crate::test_support;
     use crate::test_support as test_utils;
     use crate::test_support::TestFileBuilder;
     use crate::validated_config::ChangeMode;
+    use crate::wikilink::ToWikilink;
     use crate::wikilink::Wikilink;
     #[test]
     fn test_identify_ambiguous_matches() {
@@ -386,14 +590,17 @@ mod tests {
         let mut test_file = MarkdownFile::new(
             temp_dir.path().join("test1.md"),
             validated_config.operational_timezone(),
+            validated_config.date_link_format(),
+            None,
+            &[],
         )
         .unwrap();
         test_file.back_populate_matches.unambiguous = vec![BackPopulateMatch {
-            relative_path: "test1.md".to_string(),
+            relative_path: "test1.md".into(),
             line_number:   1,
-            line_text:     "Ed wrote this".to_string(),
-            found_text:    "Ed".to_string(),
-            replacement:   "[[Ed Barnes|Ed]]".to_string(),
+            line_text:     "Ed wrote this".into(),
+            found_text:    "Ed".into(),
+            replacement:   "[[Ed Barnes|Ed]]".into(),
             position:      0,
             match_context: MatchContext::Plaintext,
         }];
@@ -401,14 +608,17 @@ mod tests {
         let mut test_file2 = MarkdownFile::new(
             temp_dir.path().join("test2.md"),
             validated_config.operational_timezone(),
+            validated_config.date_link_format(),
+            None,
+            &[],
         )
         .unwrap();
         test_file2.back_populate_matches.unambiguous = vec![BackPopulateMatch {
-            relative_path: "test2.md".to_string(),
+            relative_path: "test2.md".into(),
             line_number:   1,
-            line_text:     "Unique wrote this".to_string(),
-            found_text:    "Unique".to_string(),
-            replacement:   "[[Unique Target]]".to_string(),
+            line_text:     "Unique wrote this".into(),
+            found_text:    "Unique".into(),
+            replacement:   "[[Unique Target]]".into(),
             position:      0,
             match_context: MatchContext::Plaintext,
         }];
@@ -434,8 +644,8 @@ mod tests {
             "Ed match should be moved to ambiguous"
         );
         let ambiguous_match = &test_file.back_populate_matches.ambiguous[0];
-        assert_eq!(ambiguous_match.found_text, "Ed");
-        assert_eq!(ambiguous_match.line_text, "Ed wrote this");
+        assert_eq!(&*ambiguous_match.found_text, "Ed");
+        assert_eq!(&*ambiguous_match.line_text, "Ed wrote this");
 
         let test_file2 = obsidian_repository
             .markdown_files
@@ -448,7 +658,7 @@ mod tests {
             "Should have one unambiguous match"
         );
         assert_eq!(
-            test_file2.back_populate_matches.unambiguous[0].found_text,
+            &*test_file2.back_populate_matches.unambiguous[0].found_text,
             "Unique"
         );
         assert!(
@@ -498,8 +708,8 @@ mod tests {
         );
 
         let ambiguous_match = &test_file.back_populate_matches.ambiguous[0];
-        assert_eq!(ambiguous_match.found_text, "Amazon");
-        assert_eq!(ambiguous_match.line_text, "Amazon is huge");
+        assert_eq!(&*ambiguous_match.found_text, "Amazon");
+        assert_eq!(&*ambiguous_match.line_text, "Amazon is huge");
     }
 
     #[test]
@@ -569,7 +779,7 @@ Amazon is ambiguous",
             "Should have one ambiguous match"
         );
         assert_eq!(
-            test_file.back_populate_matches.ambiguous[0].found_text, "Amazon",
+            &*test_file.back_populate_matches.ambiguous[0].found_text, "Amazon",
             "Amazon should be in ambiguous matches"
         );
     }
@@ -643,7 +853,7 @@ Nate was here and so was Nate"
             .back_populate_matches
             .ambiguous
             .iter()
-            .filter(|m| m.found_text == "Nate")
+            .filter(|m| &*m.found_text == "Nate")
             .collect();
         assert_eq!(
             nate_ambiguous_matches.len(),
@@ -654,7 +864,7 @@ Nate was here and so was Nate"
         assert!(
             nate_ambiguous_matches
                 .iter()
-                .any(|m| m.line_text == "Nate was here and so was Nate")
+                .any(|m| &*m.line_text == "Nate was here and so was Nate")
         );
     }
 
@@ -691,6 +901,56 @@ Nate was here and so was Nate"
         );
     }
 
+    #[test]
+    fn test_matches_share_found_text_and_replacement() {
+        let content = "Test Link here\nand Test Link there";
+
+        let (_temp_dir, validated_config, mut obsidian_repository) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, None, Some(content));
+
+        obsidian_repository
+            .find_all_back_populate_matches(&validated_config)
+            .unwrap();
+
+        let matches = &obsidian_repository.markdown_files[0]
+            .back_populate_matches
+            .unambiguous;
+        assert_eq!(matches.len(), 2);
+        assert!(Arc::ptr_eq(&matches[0].found_text, &matches[1].found_text));
+        assert!(Arc::ptr_eq(
+            &matches[0].replacement,
+            &matches[1].replacement
+        ));
+    }
+
+    #[test]
+    fn test_no_matches_in_comments_or_html_blocks() {
+        let content = "Test Link %% Test Link %% Test Link\n\
+       <!-- Test Link\n\
+       Test Link --> Test Link\n\
+       \n\
+       <div>\n\
+       Test Link\n\
+       \n\
+       Test Link";
+
+        let (_temp_dir, validated_config, mut obsidian_repository) =
+            test_support::create_test_environment(ChangeMode::DryRun, None, None, Some(content));
+
+        obsidian_repository
+            .find_all_back_populate_matches(&validated_config)
+            .unwrap();
+
+        let matches = &obsidian_repository.markdown_files[0].back_populate_matches;
+        let actual: Vec<(usize, usize)> = matches
+            .unambiguous
+            .iter()
+            .map(|m| (m.line_number, m.position))
+            .collect();
+
+        assert_eq!(actual, vec![(5, 0), (5, 26), (7, 14), (12, 0)]);
+    }
+
     #[test]
     fn test_overlapping_wikilink_matches() {
         let content = "[[Kyriana McCoy|Kyriana]] - Kyri and [[Kalina McCoy|Kali]]";
@@ -772,9 +1032,9 @@ Nate was here and so was Nate"
             .expect("Should have a file with matches");
 
         let first_match = &file_with_matches.back_populate_matches.unambiguous[0];
-        assert_eq!(first_match.found_text, "tomatoes");
+        assert_eq!(&*first_match.found_text, "tomatoes");
         assert_eq!(
-            first_match.replacement, "[[tomato|tomatoes]]",
+            &*first_match.replacement, "[[tomato|tomatoes]]",
             "Should use the alias form [[tomato|tomatoes]] instead of [[tomatoes]]"
         );
     }
@@ -936,7 +1196,7 @@ Nate was here and so was Nate"
             .unwrap();
 
         obsidian_repository
-            .apply_replaceable_matches(validated_config.operational_timezone())
+            .apply_replaceable_matches(&validated_config)
             .unwrap();
 
         assert_eq!(
@@ -990,6 +1250,67 @@ Nate was here and so was Nate"
         );
     }
 
+    #[test]
+    fn test_back_populate_links_follow_new_link_format() {
+        let replacements = |link_format: LinkFormat| {
+            let temp_dir = TempDir::new().unwrap();
+            fs::create_dir_all(temp_dir.path().join("projects")).unwrap();
+            fs::create_dir_all(temp_dir.path().join("daily")).unwrap();
+            fs::write(
+                temp_dir.path().join("projects").join("Big Plan.md"),
+                "# Big Plan",
+            )
+            .unwrap();
+            fs::write(
+                temp_dir.path().join("daily").join("today.md"),
+                "the big plan is ready\n\n| what | who |\n| --- | --- |\n| Big Plan | me |",
+            )
+            .unwrap();
+            let validated_config =
+                test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                    builder.link_format(link_format);
+                })
+                .unwrap();
+
+            let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+            obsidian_repository
+                .markdown_files
+                .iter()
+                .find(|file| file.path.ends_with("today.md"))
+                .unwrap()
+                .back_populate_matches
+                .unambiguous
+                .iter()
+                .map(|match_info| match_info.replacement.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            replacements(LinkFormat::default()),
+            vec![r"[[Big Plan|big plan]]", "[[Big Plan]]"]
+        );
+        assert_eq!(
+            replacements(LinkFormat {
+                path:     NewLinkFormat::Relative,
+                markdown: false,
+            }),
+            vec![
+                r"[[../projects/Big Plan|big plan]]",
+                r"[[../projects/Big Plan\|Big Plan]]",
+            ]
+        );
+        assert_eq!(
+            replacements(LinkFormat {
+                path:     NewLinkFormat::Absolute,
+                markdown: true,
+            }),
+            vec![
+                "[big plan](projects/Big%20Plan.md)",
+                "[Big Plan](projects/Big%20Plan.md)",
+            ]
+        );
+    }
+
     #[test]
     fn test_back_populate_content() {
         let (temp_dir, validated_config, mut obsidian_repository) =
@@ -999,7 +1320,8 @@ Nate was here and so was Nate"
             "# Test Table\n|Name|Description|\n|---|---|\n|Test Link|Sample text|\n",
             vec![BackPopulateMatch {
                 relative_path: "test.md".into(),
-                line_number:   4,
+                // Three frontmatter lines precede the fourth body line.
+                line_number:   7,
                 line_text:     "|Test Link|Sample text|".into(),
                 found_text:    "Test Link".into(),
                 replacement:   "[[Test Link\\|Another Name]]".into(),
@@ -1016,9 +1338,14 @@ Nate was here and so was Nate"
                 .create(&temp_dir, "test.md");
 
             let markdown_file = {
-                let mut markdown_file =
-                    MarkdownFile::new(file.clone(), validated_config.operational_timezone())
-                        .unwrap();
+                let mut markdown_file = MarkdownFile::new(
+                    file.clone(),
+                    validated_config.operational_timezone(),
+                    validated_config.date_link_format(),
+                    None,
+                    &[],
+                )
+                .unwrap();
                 markdown_file.content = content.to_string();
                 markdown_file.back_populate_matches.unambiguous = matches.clone();
                 markdown_file
@@ -1027,7 +1354,7 @@ Nate was here and so was Nate"
             obsidian_repository.markdown_files = MarkdownFiles::new(vec![markdown_file], None);
 
             obsidian_repository
-                .apply_replaceable_matches(validated_config.operational_timezone())
+                .apply_replaceable_matches(&validated_config)
                 .unwrap();
 
             // `file.content` contains the back-populate replacements.
@@ -1038,7 +1365,7 @@ Nate was here and so was Nate"
             {
                 for match_info in &matches {
                     assert!(
-                        file.content.contains(&match_info.replacement),
+                        file.content.contains(&*match_info.replacement),
                         "Failed for: {}\nReplacement '{}' not found in content:\n{}",
                         description,
                         match_info.replacement,
@@ -1048,4 +1375,137 @@ Nate was here and so was Nate"
             }
         }
     }
+
+    fn boxed_match(line_number: usize, found_text: &str) -> Box<dyn ReplaceableContent> {
+        Box::new(BackPopulateMatch {
+            found_text: found_text.into(),
+            line_number,
+            replacement: found_text.to_wikilink().into(),
+            ..BackPopulateMatch::default()
+        })
+    }
+
+    #[test]
+    fn test_replace_body_spans_crosses_lines_and_refuses_overlaps() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("note.md");
+        fs::write(&path, "---\ntitle: note\n---\nmet Ed\nBarnes today").unwrap();
+        let markdown_file =
+            MarkdownFile::new(path, DEFAULT_TIMEZONE, FORMAT_DATE, None, &[]).unwrap();
+
+        // A soft-wrapped mention spans the line break; its span starts on the first body line.
+        let wrapped = Box::new(BackPopulateMatch {
+            found_text: "Ed\nBarnes".into(),
+            line_number: 4,
+            position: 4,
+            replacement: "[[Ed Barnes|Ed\nBarnes]]".into(),
+            ..BackPopulateMatch::default()
+        }) as Box<dyn ReplaceableContent>;
+        let (updated_content, _) = replace_body_spans(&markdown_file, &[wrapped.as_ref()]).unwrap();
+        assert_eq!(updated_content, "met [[Ed Barnes|Ed\nBarnes]] today");
+
+        let overlapping = boxed_match(4, "met Ed");
+        assert!(
+            replace_body_spans(&markdown_file, &[wrapped.as_ref(), overlapping.as_ref()]).is_err()
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_replacements_only_touch_the_body(
+            property_count in 1_usize..6,
+            body in prop::collection::vec("[a-z]{1,8} [a-z]{1,8}", 1..8),
+            target_line in any::<Index>(),
+        ) {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("note.md");
+            let properties: Vec<String> = (0..property_count)
+                .map(|property| format!("key{property}: value"))
+                .collect();
+            fs::write(
+                &path,
+                format!("---\n{}\n---\n{}", properties.join("\n"), body.join("\n")),
+            )
+            .unwrap();
+            let markdown_file = MarkdownFile::new(path, DEFAULT_TIMEZONE, FORMAT_DATE, None, &[]).unwrap();
+            let frontmatter_line_count = markdown_file.frontmatter_line_count;
+            prop_assert_eq!(frontmatter_line_count, property_count + 2);
+
+            // A match on a body line rewrites that line and no other.
+            let line_idx = target_line.index(body.len());
+            let found_text = body[line_idx].split(' ').next().unwrap();
+            let line_number = frontmatter_line_count + line_idx + 1;
+            let (updated_content, _) =
+                replace_body_spans(&markdown_file, &[boxed_match(line_number, found_text).as_ref()])
+                    .unwrap();
+            let expected: Vec<String> = body
+                .iter()
+                .enumerate()
+                .map(|(idx, line)| {
+                    if idx == line_idx {
+                        line.replacen(found_text, &found_text.to_wikilink(), 1)
+                    } else {
+                        line.clone()
+                    }
+                })
+                .collect();
+            prop_assert_eq!(updated_content, expected.join("\n"));
+
+            // A match in the frontmatter or past the last body line is refused.
+            let past_body = frontmatter_line_count + body.len() + 1;
+            for line_number in (0..=frontmatter_line_count).chain([past_body]) {
+                prop_assert!(
+                    replace_body_spans(&markdown_file, &[boxed_match(line_number, found_text).as_ref()])
+                        .is_err()
+                );
+            }
+        }
+
+        #[test]
+        fn prop_applied_matches_are_balanced_and_idempotent(notes in test_fixtures::vault()) {
+            let temp_dir = TempDir::new().unwrap();
+            test_fixtures::write_vault(&notes, temp_dir.path()).unwrap();
+            let validated_config =
+                test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                    builder.change_mode(ChangeMode::Apply);
+                })
+                .unwrap();
+            let bodies = |obsidian_repository: &ObsidianRepository| -> Vec<(String, String)> {
+                let mut bodies: Vec<(String, String)> = obsidian_repository
+                    .markdown_files
+                    .iter()
+                    .map(|file| (file.path.display().to_string(), file.content.clone()))
+                    .collect();
+                bodies.sort();
+                bodies
+            };
+
+            let first_run = ObsidianRepository::new(&validated_config).unwrap();
+            let first_bodies = bodies(&first_run);
+            for (path, body) in &first_bodies {
+                prop_assert_eq!(
+                    body.matches(OPENING_WIKILINK).count(),
+                    body.matches(CLOSING_WIKILINK).count(),
+                    "unbalanced brackets in {}: {}",
+                    path,
+                    body
+                );
+            }
+            for note in &notes {
+                let (_, body) = first_bodies
+                    .iter()
+                    .find(|(path, _)| path.ends_with(&format!("{}.md", note.name)))
+                    .unwrap();
+                for target in &note.links {
+                    prop_assert!(body.contains(&target.to_wikilink()));
+                }
+            }
+
+            first_run.persist().unwrap();
+            let second_run = ObsidianRepository::new(&validated_config).unwrap();
+            prop_assert_eq!(first_bodies, bodies(&second_run));
+        }
+    }
 }
@@ -0,0 +1,207 @@
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use tar::Builder;
+
+use super::ObsidianRepository;
+use super::hidden_folders::HiddenFolderItemKind;
+use crate::constants::BACKUP_ARCHIVE;
+use crate::constants::BACKUP_ARCHIVE_EXTENSION;
+use crate::constants::BACKUP_COMMAND_FAILED;
+use crate::constants::BACKUP_FILE_LIST_EXTENSION;
+use crate::constants::BACKUP_FILES_PLACEHOLDER;
+use crate::constants::BACKUP_FOLDER;
+use crate::constants::BACKUP_RUN_ID_PLACEHOLDER;
+use crate::constants::BACKUP_SHELL;
+use crate::constants::NEWLINE;
+use crate::patch::PatchChange;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// Every existing file `persist` would rewrite, rename or delete, in path order. Notes it
+    /// only creates have nothing to back up.
+    pub(crate) fn files_to_change(&self, validated_config: &ValidatedConfig) -> Vec<PathBuf> {
+        let mut paths: BTreeSet<PathBuf> = self
+            .build_patch(validated_config)
+            .operations
+            .into_iter()
+            .filter(|operation| !matches!(operation.change, PatchChange::Create { .. }))
+            .map(|operation| operation.path)
+            .collect();
+
+        let renamed: HashSet<&PathBuf> =
+            self.paths_to_rename.iter().map(|(path, _)| path).collect();
+        paths.extend(renamed.iter().map(|path| (*path).clone()));
+        paths.extend(
+            self.structured_files
+                .iter()
+                .filter(|structured_file| {
+                    structured_file
+                        .file_references
+                        .iter()
+                        .any(|file_reference| renamed.contains(&file_reference.path))
+                })
+                .map(|structured_file| structured_file.path.clone()),
+        );
+        paths.extend(
+            self.hidden_folder_items
+                .iter()
+                .filter(|item| item.kind == HiddenFolderItemKind::TrashToPurge)
+                .map(|item| item.path.clone()),
+        );
+
+        paths.into_iter().filter(|path| path.exists()).collect()
+    }
+
+    /// With `backup_before_apply` set, backs up `files_to_change` for the run `run_id` and
+    /// returns how many there were. `archive` writes them, by vault-relative path, to
+    /// `<run_id>.tar` in the output folder's backups folder; anything else is a command run
+    /// with `{files}` replaced by a file listing their paths and `{run_id}` by the run's id. A
+    /// command that fails stops the run before anything is changed.
+    pub(crate) fn back_up_before_apply(
+        &self,
+        validated_config: &ValidatedConfig,
+        run_id: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let Some(backup) = validated_config.backup_before_apply() else {
+            return Ok(0);
+        };
+        let files = self.files_to_change(validated_config);
+        if files.is_empty() {
+            return Ok(0);
+        }
+
+        let backup_folder = validated_config.output_folder().join(BACKUP_FOLDER);
+        fs::create_dir_all(&backup_folder)?;
+        if backup == BACKUP_ARCHIVE {
+            write_archive(
+                &backup_folder.join(format!("{run_id}.{BACKUP_ARCHIVE_EXTENSION}")),
+                validated_config.obsidian_path(),
+                &files,
+            )?;
+        } else {
+            let file_list = backup_folder.join(format!("{run_id}.{BACKUP_FILE_LIST_EXTENSION}"));
+            let mut listed = String::new();
+            for path in &files {
+                let _ = write!(listed, "{}{NEWLINE}", path.display());
+            }
+            fs::write(&file_list, listed)?;
+
+            let [shell, command_flag] = BACKUP_SHELL;
+            let command = backup
+                .replace(
+                    BACKUP_FILES_PLACEHOLDER,
+                    &format!("\"{}\"", file_list.display()),
+                )
+                .replace(BACKUP_RUN_ID_PLACEHOLDER, run_id);
+            let status = Command::new(shell)
+                .arg(command_flag)
+                .arg(&command)
+                .status()?;
+            if !status.success() {
+                return Err(format!("{BACKUP_COMMAND_FAILED} {command} ({status})").into());
+            }
+        }
+        Ok(files.len())
+    }
+}
+
+fn write_archive(
+    archive_path: &Path,
+    obsidian_path: &Path,
+    files: &[PathBuf],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut archive = Builder::new(File::create(archive_path)?);
+    for path in files {
+        let name = path.strip_prefix(obsidian_path).unwrap_or(path);
+        if path.is_dir() {
+            archive.append_dir_all(name, path)?;
+        } else {
+            archive.append_path_with_name(path, name)?;
+        }
+    }
+    archive.into_inner()?.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::fs::File;
+    use std::io::Read;
+
+    use tar::Archive;
+    use tempfile::TempDir;
+
+    use crate::constants::BACKUP_ARCHIVE;
+    use crate::constants::BACKUP_FOLDER;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support as test_utils;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_archive_holds_files_about_to_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_date = test_utils::eastern_midnight(2024, 1, 15);
+        TestFileBuilder::new()
+            .with_content("# Other\n".to_string())
+            .with_matching_dates(test_date)
+            .with_file_system_dates(test_date, test_date)
+            .create(&temp_dir, "Other.md");
+        let note_path = TestFileBuilder::new()
+            .with_content("# Note\nSee Other here\n".to_string())
+            .with_matching_dates(test_date)
+            .with_file_system_dates(test_date, test_date)
+            .create(&temp_dir, "Note.md");
+        let original = fs::read_to_string(&note_path).unwrap();
+
+        let validated_config = test_utils::get_test_validated_config_result(&temp_dir, |builder| {
+            builder
+                .change_mode(ChangeMode::Apply)
+                .backup_before_apply(Some(BACKUP_ARCHIVE.to_string()));
+        })
+        .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let files_to_change = obsidian_repository.files_to_change(&validated_config);
+        assert!(files_to_change.contains(&note_path));
+        let backed_up = obsidian_repository
+            .back_up_before_apply(&validated_config, "20240115-000000")
+            .unwrap();
+        assert_eq!(backed_up, files_to_change.len());
+
+        let archive_path = validated_config
+            .output_folder()
+            .join(BACKUP_FOLDER)
+            .join("20240115-000000.tar");
+        let mut archive = Archive::new(File::open(archive_path).unwrap());
+        let entries: Vec<(String, String)> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let name = entry.path().unwrap().display().to_string();
+                let mut contents = String::new();
+                entry.read_to_string(&mut contents).unwrap();
+                (name, contents)
+            })
+            .collect();
+        assert_eq!(entries.len(), backed_up);
+        assert!(
+            entries.contains(&("Note.md".to_string(), original)),
+            "notes are archived by vault-relative path as they were before the run"
+        );
+    }
+}
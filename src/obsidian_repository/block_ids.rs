@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::ObsidianRepository;
+use super::phantom_links;
+use super::vault_index;
+use crate::constants::BLOCK_REFERENCE_PREFIX;
+use crate::constants::DUPLICATE_BLOCK_ID;
+use crate::constants::MISSING_BLOCK_ID;
+use crate::validated_config::ValidatedConfig;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BlockIdIssueKind {
+    /// A block id a note gives more than one block - links reach only the first.
+    Duplicate,
+    /// A `[[note#^id]]` link to a block its note doesn't have.
+    MissingBlock,
+}
+
+impl Display for BlockIdIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate => f.write_str(DUPLICATE_BLOCK_ID),
+            Self::MissingBlock => f.write_str(MISSING_BLOCK_ID),
+        }
+    }
+}
+
+/// A block id, or a link to one, that doesn't resolve to a single block. `text` is the
+/// `^block-id` or the link as written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct BlockIdIssue {
+    pub path:        PathBuf,
+    pub line_number: usize,
+    pub text:        String,
+    pub kind:        BlockIdIssueKind,
+}
+
+/// A block id in the vault and the number of links to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct NoteBlockId {
+    pub path:        PathBuf,
+    pub line_number: usize,
+    pub id:          String,
+    pub references:  usize,
+}
+
+impl ObsidianRepository {
+    /// With `block_id_placeholder` set, gives each line ending in it a block id first. Then
+    /// reports block ids a note uses twice and `[[note#^id]]` links to blocks that don't exist,
+    /// and with `list_block_ids` on, lists every block id. Links to a note name more than one
+    /// note shares, or to a missing note, are left to the other link reports. Runs after
+    /// `collect_all_flashcards`, so its `^card-` ids are counted.
+    pub(crate) fn check_block_ids(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        if let Some(placeholder) = validated_config.block_id_placeholder() {
+            for markdown_file in &mut self.markdown_files {
+                let id_seed = vault_index::relative_path(
+                    validated_config.obsidian_path(),
+                    &markdown_file.path,
+                );
+                markdown_file.generate_block_ids(
+                    placeholder,
+                    &id_seed,
+                    validated_config.operational_timezone(),
+                )?;
+            }
+        }
+
+        let mut issues = Vec::new();
+        let mut listed = Vec::new();
+        let mut ids_by_stem: HashMap<String, Option<HashSet<String>>> = HashMap::new();
+        for markdown_file in &self.markdown_files {
+            let block_ids = markdown_file.block_ids();
+            let mut ids = HashSet::new();
+            for block_id in &block_ids {
+                if !ids.insert(block_id.id.clone()) {
+                    issues.push(BlockIdIssue {
+                        path:        markdown_file.path.clone(),
+                        line_number: block_id.line_number,
+                        text:        format!("{BLOCK_REFERENCE_PREFIX}{}", block_id.id),
+                        kind:        BlockIdIssueKind::Duplicate,
+                    });
+                }
+            }
+            ids_by_stem
+                .entry(note_stem(&markdown_file.path))
+                .and_modify(|shared| *shared = None)
+                .or_insert(Some(ids));
+
+            if validated_config.list_block_ids() {
+                listed.extend(block_ids.into_iter().map(|block_id| NoteBlockId {
+                    path:        markdown_file.path.clone(),
+                    line_number: block_id.line_number,
+                    id:          block_id.id,
+                    references:  0,
+                }));
+            }
+        }
+
+        let mut reference_counts: HashMap<(String, String), usize> = HashMap::new();
+        for markdown_file in &self.markdown_files {
+            for reference in markdown_file.block_references() {
+                let stem = if reference.note.is_empty() {
+                    note_stem(&markdown_file.path)
+                } else {
+                    phantom_links::target_note_stem(&reference.note)
+                };
+                let Some(Some(ids)) = ids_by_stem.get(&stem) else {
+                    continue;
+                };
+                if ids.contains(&reference.id) {
+                    *reference_counts.entry((stem, reference.id)).or_default() += 1;
+                } else {
+                    issues.push(BlockIdIssue {
+                        path:        markdown_file.path.clone(),
+                        line_number: reference.line_number,
+                        text:        reference.found_text,
+                        kind:        BlockIdIssueKind::MissingBlock,
+                    });
+                }
+            }
+        }
+
+        for block_id in &mut listed {
+            block_id.references = reference_counts
+                .get(&(note_stem(&block_id.path), block_id.id.clone()))
+                .copied()
+                .unwrap_or_default();
+        }
+
+        self.block_id_issues = issues;
+        self.block_ids = listed;
+        Ok(())
+    }
+}
+
+fn note_stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::BlockIdIssueKind;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_check_block_ids_reports_duplicates_and_missing_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("first ^quote\nsecond ^quote\nthird ^kept")
+            .create(&temp_dir, "Source.md");
+        TestFileBuilder::new()
+            .with_content(
+                "see [[Source#^quote]], [[Source#^kept]], [[Source#^gone]] and \
+                 [[Missing#^any]]\nlocal [[#^here]] ^here",
+            )
+            .create(&temp_dir, "notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.list_block_ids(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let mut issues: Vec<(&str, BlockIdIssueKind)> = obsidian_repository
+            .block_id_issues
+            .iter()
+            .map(|issue| (issue.text.as_str(), issue.kind))
+            .collect();
+        issues.sort_by_key(|&(text, _)| text);
+        assert_eq!(
+            issues,
+            vec![
+                ("[[Source#^gone]]", BlockIdIssueKind::MissingBlock),
+                ("^quote", BlockIdIssueKind::Duplicate),
+            ]
+        );
+
+        let mut listed: Vec<(&str, usize)> = obsidian_repository
+            .block_ids
+            .iter()
+            .map(|block_id| (block_id.id.as_str(), block_id.references))
+            .collect();
+        listed.sort_unstable();
+        assert_eq!(
+            listed,
+            vec![("here", 1), ("kept", 1), ("quote", 1), ("quote", 1)]
+        );
+    }
+}
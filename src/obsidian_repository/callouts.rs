@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use crate::markdown_file::CalloutIssue;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `convert_admonitions` on, records an `AdmonitionConversion` for every admonition
+    /// code block. Runs after the regex pass so a block with a line another fix already changes
+    /// (an image reference update, say) is left as it is rather than rewritten twice.
+    pub(crate) fn find_all_admonition_conversions(&mut self, validated_config: &ValidatedConfig) {
+        if !validated_config.convert_admonitions() {
+            return;
+        }
+
+        let fix_self_links = validated_config.fix_self_links();
+        for markdown_file in &mut self.markdown_files {
+            let mut conversions = markdown_file.find_admonition_conversions();
+            if conversions.is_empty() {
+                continue;
+            }
+
+            let (other_matches, _) =
+                Self::collect_replaceable_matches(markdown_file, fix_self_links);
+            conversions.retain(|conversion| {
+                !conversion.line_matches.iter().any(|line_match| {
+                    other_matches
+                        .iter()
+                        .any(|other| other.line_number() == line_match.line_number)
+                })
+            });
+
+            markdown_file.admonition_conversions = conversions;
+        }
+    }
+
+    /// Malformed callout headers in every note, ordered by path.
+    pub(crate) fn collect_callout_issues(&self) -> Vec<(PathBuf, CalloutIssue)> {
+        let mut issues: Vec<(PathBuf, CalloutIssue)> = self
+            .markdown_files
+            .iter()
+            .flat_map(|markdown_file| {
+                markdown_file
+                    .find_callout_issues()
+                    .into_iter()
+                    .map(|issue| (markdown_file.path.clone(), issue))
+            })
+            .collect();
+        issues.sort_by(|(a_path, a), (b_path, b)| {
+            a_path
+                .cmp(b_path)
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+        issues
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_admonitions_converted_to_callouts() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "before\n\
+                 ```ad-warning\n\
+                 title: Careful\n\
+                 hot surface\n\
+                 \n\
+                 do not touch\n\
+                 ```\n\
+                 after",
+            )
+            .create(&temp_dir, "stove.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.convert_admonitions(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let stove = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path == file_path)
+            .unwrap();
+
+        assert_eq!(
+            stove.content,
+            "before\n\
+             > [!warning] Careful\n\
+             > hot surface\n\
+             >\n\
+             > do not touch\n\
+             after"
+        );
+        assert!(
+            stove
+                .persist_reasons
+                .contains(&PersistReason::AdmonitionsConverted { count: 1 })
+        );
+        assert!(
+            obsidian_repository.collect_callout_issues().is_empty(),
+            "the converted callout is well formed"
+        );
+    }
+}
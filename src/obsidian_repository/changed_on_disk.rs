@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use super::ownership;
+use crate::frontmatter::FrontMatter;
+
+/// A note with changes to write that was saved again after it was read - by Obsidian or a sync
+/// client - so they're skipped this run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ChangedOnDiskNote {
+    pub path:    PathBuf,
+    /// The skipped changes, as `audit_trail` would record them.
+    pub changes: Vec<String>,
+}
+
+impl ObsidianRepository {
+    /// Discards the changes to every note to write that no longer matches what was read, along
+    /// with its queued rename and what depended on its rewrite - the note on disk still needs
+    /// its images and conflict copies. Runs right before the report and `persist`, so nothing
+    /// is deleted or created for a note that won't be written. Returns the skipped paths.
+    pub(crate) fn guard_changed_on_disk_notes(&mut self) -> Vec<PathBuf> {
+        let mut changed_on_disk_notes = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            let has_changes = markdown_file
+                .front_matter
+                .as_ref()
+                .is_some_and(FrontMatter::needs_persist);
+            if !has_changes || !markdown_file.changed_on_disk() {
+                continue;
+            }
+
+            let changes = ownership::discard_changes(markdown_file, &self.paths_to_rename);
+            changed_on_disk_notes.push(ChangedOnDiskNote {
+                path: markdown_file.path.clone(),
+                changes,
+            });
+        }
+
+        let skipped: Vec<PathBuf> = changed_on_disk_notes
+            .iter()
+            .map(|note| note.path.clone())
+            .collect();
+        self.release_discarded_notes(&skipped);
+        self.changed_on_disk_notes = changed_on_disk_notes;
+        skipped
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_changed_on_disk_note_keeps_its_duplicate_images() {
+        let temp_dir = TempDir::new().unwrap();
+        for image in ["first.jpg", "second.jpg"] {
+            fs::write(temp_dir.path().join(image), [0xFF, 0xD8, 0xFF, 0xE0]).unwrap();
+        }
+        TestFileBuilder::new()
+            .with_content("![[first.jpg]]")
+            .create(&temp_dir, "Gallery.md");
+        let notes_path = TestFileBuilder::new()
+            .with_content("![[second.jpg]]")
+            .create(&temp_dir, "Notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+        let mut obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        fs::write(&notes_path, "edited in obsidian\n![[second.jpg]]").unwrap();
+
+        let skipped = obsidian_repository.guard_changed_on_disk_notes();
+        assert_eq!(skipped, vec![notes_path.clone()]);
+        assert_eq!(obsidian_repository.changed_on_disk_notes.len(), 1);
+        assert!(
+            !obsidian_repository.changed_on_disk_notes[0]
+                .changes
+                .is_empty()
+        );
+
+        assert!(obsidian_repository.persist().unwrap().is_empty());
+        assert_eq!(
+            fs::read_to_string(&notes_path).unwrap(),
+            "edited in obsidian\n![[second.jpg]]"
+        );
+        assert!(temp_dir.path().join("first.jpg").exists());
+        assert!(temp_dir.path().join("second.jpg").exists());
+    }
+}
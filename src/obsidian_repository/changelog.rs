@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::Utc;
+
+use super::ObsidianRepository;
+use super::constants::CHANGELOG_ENTRY_PREFIX;
+use crate::constants::LEVEL2;
+use crate::constants::LEVEL3;
+use crate::constants::NEWLINE;
+use crate::frontmatter::FrontMatter;
+use crate::note_template;
+use crate::support;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+use crate::yaml_frontmatter;
+use crate::yaml_frontmatter::YamlFrontMatter;
+use crate::yaml_frontmatter::YamlFrontMatterError;
+
+/// The notes written on one day under each reason they were changed.
+type NotesByReason = BTreeMap<String, BTreeSet<String>>;
+
+impl ObsidianRepository {
+    /// With `changelog` set, adds the notes written this run to today's section of the changelog
+    /// note, under each reason they changed. Notes in `skipped` weren't written, so they're left
+    /// out. Earlier days are left as they are, newest first.
+    pub(crate) fn write_changelog(
+        &self,
+        validated_config: &ValidatedConfig,
+        skipped: &[PathBuf],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(changelog_path) = validated_config.changelog() else {
+            return Ok(());
+        };
+
+        let mut notes_by_reason = NotesByReason::new();
+        for markdown_file in &self.markdown_files.files_to_persist() {
+            if skipped.contains(&markdown_file.path) || markdown_file.path == changelog_path {
+                continue;
+            }
+            let note = markdown_file
+                .path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default()
+                .to_wikilink();
+            for reason in &markdown_file.persist_reasons {
+                notes_by_reason
+                    .entry(reason.to_string())
+                    .or_default()
+                    .insert(note.clone());
+            }
+        }
+        if notes_by_reason.is_empty() {
+            return Ok(());
+        }
+
+        let existing = fs::read_to_string(changelog_path).unwrap_or_default();
+        let (front_matter, body) = match yaml_frontmatter::find_yaml_section(&existing) {
+            Ok(Some((yaml, body))) => (FrontMatter::from_yaml_str(yaml)?, body),
+            Ok(None) | Err(YamlFrontMatterError::Missing | YamlFrontMatterError::Empty) => {
+                (FrontMatter::default(), existing.as_str())
+            },
+            Err(error) => return Err(error.into()),
+        };
+
+        let operational_timezone = validated_config.operational_timezone();
+        let note = note_template::compose_note(
+            None,
+            |new_front_matter| {
+                *new_front_matter = front_matter;
+                if new_front_matter.date_created().is_none() {
+                    new_front_matter.set_date_created(Utc::now(), operational_timezone);
+                }
+                new_front_matter.set_date_modified_now(operational_timezone);
+            },
+            &merge_changelog(body, &validated_config.today(), notes_by_reason),
+        )?;
+
+        if let Some(parent) = changelog_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        support::write_atomically(changelog_path, note)?;
+        Ok(())
+    }
+}
+
+/// `body` with today's section holding `notes_by_reason` as well as what it held before. A new
+/// section goes above the first day's, after any text that opens the note.
+fn merge_changelog(body: &str, today: &str, mut notes_by_reason: NotesByReason) -> String {
+    let lines: Vec<&str> = body.lines().collect();
+    let today_heading = format!("{LEVEL2} {today}");
+    let is_day_heading = |line: &&str| line.starts_with(&format!("{LEVEL2} "));
+
+    let (start, end) = lines
+        .iter()
+        .position(|line| *line == today_heading)
+        .map_or_else(
+            || {
+                let start = lines.iter().position(is_day_heading).unwrap_or(lines.len());
+                (start, start)
+            },
+            |start| {
+                let end = lines[start + 1..]
+                    .iter()
+                    .position(is_day_heading)
+                    .map_or(lines.len(), |offset| start + 1 + offset);
+                add_section_notes(&lines[start + 1..end], &mut notes_by_reason);
+                (start, end)
+            },
+        );
+
+    let mut section = vec![today_heading];
+    for (reason, notes) in notes_by_reason {
+        section.push(format!("{LEVEL3} {reason}"));
+        section.extend(
+            notes
+                .into_iter()
+                .map(|note| format!("{CHANGELOG_ENTRY_PREFIX}{note}")),
+        );
+    }
+    section.push(String::new());
+
+    let merged: Vec<String> = lines[..start]
+        .iter()
+        .map(ToString::to_string)
+        .chain(section)
+        .chain(lines[end..].iter().map(ToString::to_string))
+        .collect();
+    let mut merged = merged.join(&NEWLINE.to_string());
+    merged.push(NEWLINE);
+    merged
+}
+
+/// Reads the reasons and notes an existing day's section lists.
+fn add_section_notes(section: &[&str], notes_by_reason: &mut NotesByReason) {
+    let mut reason = None;
+    for line in section {
+        if let Some(heading) = line.strip_prefix(&format!("{LEVEL3} ")) {
+            reason = Some(heading.trim().to_string());
+        } else if let (Some(reason), Some(note)) =
+            (&reason, line.strip_prefix(CHANGELOG_ENTRY_PREFIX))
+        {
+            notes_by_reason
+                .entry(reason.clone())
+                .or_default()
+                .insert(note.trim().to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_write_changelog_groups_notes_by_day_and_reason() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Topic".to_string())
+            .with_content("about the topic")
+            .create(&temp_dir, "topic.md");
+        TestFileBuilder::new()
+            .with_content("mentions Topic")
+            .create(&temp_dir, "mentions.md");
+
+        let changelog_path = temp_dir.path().join("Changelog.md");
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.changelog(Some(changelog_path.clone()));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        fs::write(
+            &changelog_path,
+            "# Changelog\n\n## 2020-01-01\n### back populated\n- [[earlier]]\n",
+        )
+        .unwrap();
+
+        // A second write on the same day adds nothing new.
+        for _ in 0..2 {
+            obsidian_repository
+                .write_changelog(&validated_config, &[])
+                .unwrap();
+        }
+
+        let changelog = fs::read_to_string(&changelog_path).unwrap();
+        let today = validated_config.today();
+        let body = changelog.split("---\n").last().unwrap();
+        assert!(
+            body.starts_with(&format!("# Changelog\n\n## {today}\n")),
+            "{changelog}"
+        );
+        assert_eq!(
+            body.matches("### back populated\n- [[mentions]]\n").count(),
+            1,
+            "{changelog}"
+        );
+        assert!(body.ends_with("\n## 2020-01-01\n### back populated\n- [[earlier]]\n"));
+        assert!(changelog.contains("date_modified:"));
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::ObsidianRepository;
+use crate::constants::BIBTEX_NON_REFERENCE_TYPES;
+use crate::constants::NEVER_CITED;
+use crate::constants::NOT_IN_BIBLIOGRAPHY;
+use crate::support::BIBTEX_ENTRY_REGEX;
+use crate::validated_config::ValidatedConfig;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CitationIssueKind {
+    /// A note cites a key the bibliography doesn't hold.
+    UnknownKey,
+    /// A bibliography entry no note cites.
+    UnusedEntry,
+}
+
+impl Display for CitationIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownKey => f.write_str(NOT_IN_BIBLIOGRAPHY),
+            Self::UnusedEntry => f.write_str(NEVER_CITED),
+        }
+    }
+}
+
+/// A citation key that doesn't match up - `path` and `line_number` are the note citing an
+/// unknown key, or the bibliography line opening an unused entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CitationIssue {
+    pub key:         String,
+    pub path:        PathBuf,
+    pub line_number: usize,
+    pub kind:        CitationIssueKind,
+}
+
+impl ObsidianRepository {
+    /// With `bibliography` set, checks every citation key in the notes against the entries of
+    /// the BibTeX file, and every entry against the keys the notes cite.
+    pub(crate) fn check_citations(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let Some(bibliography) = validated_config.bibliography() else {
+            return Ok(());
+        };
+
+        let entries = bibliography_entries(&fs::read_to_string(bibliography)?);
+        let known: HashSet<&str> = entries.iter().map(|(key, _)| key.as_str()).collect();
+
+        let mut cited = HashSet::new();
+        let mut issues = Vec::new();
+        for markdown_file in &self.markdown_files {
+            for citation in markdown_file.citations() {
+                if !known.contains(citation.key.as_str()) {
+                    issues.push(CitationIssue {
+                        key:         citation.key.clone(),
+                        path:        markdown_file.path.clone(),
+                        line_number: citation.line_number,
+                        kind:        CitationIssueKind::UnknownKey,
+                    });
+                }
+                cited.insert(citation.key);
+            }
+        }
+
+        issues.extend(
+            entries
+                .into_iter()
+                .filter(|(key, _)| !cited.contains(key))
+                .map(|(key, line_number)| CitationIssue {
+                    key,
+                    path: bibliography.to_path_buf(),
+                    line_number,
+                    kind: CitationIssueKind::UnusedEntry,
+                }),
+        );
+
+        self.citation_issues = issues;
+        Ok(())
+    }
+}
+
+/// The key and line number of every reference in a BibTeX file - `@string`, `@preamble` and
+/// `@comment` blocks aren't references.
+fn bibliography_entries(bibtex: &str) -> Vec<(String, usize)> {
+    bibtex
+        .lines()
+        .enumerate()
+        .filter_map(|(line_idx, line)| {
+            let captures = BIBTEX_ENTRY_REGEX.captures(line)?;
+            let entry_type = captures[1].to_lowercase();
+            (!BIBTEX_NON_REFERENCE_TYPES.contains(&entry_type.as_str()))
+                .then(|| (captures[2].to_string(), line_idx + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::CitationIssueKind;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_check_citations_reports_unknown_keys_and_unused_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("references.bib"),
+            "@string{acm = \"ACM\"}\n@book{knuth1984,\n  title = {TeX},\n}\n\n@article{unused2001,\n  \
+             title = {Nobody},\n}\n",
+        )
+        .unwrap();
+        TestFileBuilder::new()
+            .with_content("As [@knuth1984] and [@missing2020] show.".to_string())
+            .create(&temp_dir, "Paper.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.bibliography(Some(temp_dir.path().join("references.bib")));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let issues: Vec<(&str, CitationIssueKind)> = obsidian_repository
+            .citation_issues
+            .iter()
+            .map(|issue| (issue.key.as_str(), issue.kind))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                ("missing2020", CitationIssueKind::UnknownKey),
+                ("unused2001", CitationIssueKind::UnusedEntry),
+            ]
+        );
+        assert_eq!(obsidian_repository.citation_issues[1].line_number, 6);
+    }
+}
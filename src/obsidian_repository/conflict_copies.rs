@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::ObsidianRepository;
+use super::constants::CONFLICTED_COPY_PATTERN;
+use super::constants::NUMBERED_COPY_PATTERN;
+use crate::config::ConflictResolution;
+use crate::constants::LINKED_RESOLVE_IN_OBSIDIAN;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::NO_CHANGE;
+use crate::constants::WILL_DELETE;
+use crate::constants::WILL_MERGE_AND_DELETE;
+use crate::sha256_cache;
+use crate::support;
+use crate::validated_config::ValidatedConfig;
+
+static CONFLICTED_COPY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| support::compile_regex(CONFLICTED_COPY_PATTERN));
+static NUMBERED_COPY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| support::compile_regex(NUMBERED_COPY_PATTERN));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ConflictCopyAction {
+    Delete,
+    /// Something links to the copy, so removing it would break the link.
+    Linked,
+    Keep,
+    Merge,
+}
+
+impl Display for ConflictCopyAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Delete => f.write_str(WILL_DELETE),
+            Self::Linked => f.write_str(LINKED_RESOLVE_IN_OBSIDIAN),
+            Self::Keep => f.write_str(NO_CHANGE),
+            Self::Merge => f.write_str(WILL_MERGE_AND_DELETE),
+        }
+    }
+}
+
+/// A note a sync client wrote beside `original` when both sides of a sync changed it.
+#[derive(Clone, Debug)]
+pub(crate) struct ConflictCopy {
+    pub original:  PathBuf,
+    pub copy:      PathBuf,
+    /// SHA-256 of the copy as read, so a copy edited during the run is never deleted.
+    pub copy_hash: String,
+    /// The bodies match once surrounding whitespace is trimmed.
+    pub identical: bool,
+    pub action:    ConflictCopyAction,
+}
+
+/// The note `path` is a conflict copy of, and whether its name alone says so - a numbered
+/// copy only counts when its body matches.
+fn original_path(path: &Path) -> Option<(PathBuf, bool)> {
+    let stem = path.file_stem().and_then(OsStr::to_str)?;
+    let (original_stem, named_as_conflict) = CONFLICTED_COPY_REGEX.captures(stem).map_or_else(
+        || {
+            NUMBERED_COPY_REGEX
+                .captures(stem)
+                .and_then(|captures| captures.get(1))
+                .map(|original| (original.as_str(), false))
+        },
+        |captures| captures.get(1).map(|original| (original.as_str(), true)),
+    )?;
+    Some((
+        path.with_file_name(format!("{original_stem}{MARKDOWN_SUFFIX}")),
+        named_as_conflict,
+    ))
+}
+
+impl ObsidianRepository {
+    /// Pairs each conflict copy with the note beside it. With `resolve_conflict_copies` set,
+    /// copies matching their original - and with `merge`, the others too - leave
+    /// `markdown_files` so nothing else analyzes or rewrites them, and `persist` deletes them.
+    /// Runs before any analysis, so merged lines are analyzed as part of the original.
+    pub(super) fn resolve_conflict_copies(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let resolution = validated_config.resolve_conflict_copies();
+        let reference_counts = self.reference_counts();
+        let wikilinked_names = self.structured_file_wikilink_names();
+        let indexes: HashMap<&PathBuf, usize> = self
+            .markdown_files
+            .iter()
+            .enumerate()
+            .map(|(index, markdown_file)| (&markdown_file.path, index))
+            .collect();
+
+        let mut conflict_copies = Vec::new();
+        let mut merges = Vec::new();
+        for markdown_file in &self.markdown_files {
+            let Some((original, named_as_conflict)) = original_path(&markdown_file.path) else {
+                continue;
+            };
+            let Some(&original_index) = indexes.get(&original) else {
+                continue;
+            };
+            let identical =
+                self.markdown_files[original_index].content.trim() == markdown_file.content.trim();
+            if !named_as_conflict && !identical {
+                continue;
+            }
+
+            let stem = markdown_file
+                .path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default()
+                .to_lowercase();
+            let action = if reference_counts.contains_key(&stem) || wikilinked_names.contains(&stem)
+            {
+                ConflictCopyAction::Linked
+            } else {
+                match resolution {
+                    Some(_) if identical => ConflictCopyAction::Delete,
+                    Some(ConflictResolution::Merge) => ConflictCopyAction::Merge,
+                    Some(ConflictResolution::Delete) | None => ConflictCopyAction::Keep,
+                }
+            };
+            if action == ConflictCopyAction::Merge {
+                merges.push((original_index, markdown_file.content.clone()));
+            }
+
+            conflict_copies.push(ConflictCopy {
+                original,
+                copy: markdown_file.path.clone(),
+                copy_hash: markdown_file.content_hash.clone(),
+                identical,
+                action,
+            });
+        }
+
+        for (original_index, copy_content) in merges {
+            self.markdown_files[original_index]
+                .merge_conflict_copy(&copy_content, validated_config.operational_timezone())?;
+        }
+
+        let resolved: HashSet<&PathBuf> = conflict_copies
+            .iter()
+            .filter(|conflict_copy| {
+                matches!(
+                    conflict_copy.action,
+                    ConflictCopyAction::Delete | ConflictCopyAction::Merge
+                )
+            })
+            .map(|conflict_copy| &conflict_copy.copy)
+            .collect();
+        if !resolved.is_empty() {
+            self.markdown_files
+                .retain(|markdown_file| !resolved.contains(&markdown_file.path));
+            let (sorted, automaton) = Self::initialize_wikilinks(&self.markdown_files)?;
+            self.wikilinks_sorted = sorted;
+            self.wikilinks_automaton = Some(automaton);
+        }
+
+        self.conflict_copies = conflict_copies;
+        Ok(())
+    }
+
+    /// Deletes the copies `resolve_conflict_copies` resolved. A copy edited during the run, or
+    /// merged into a note `persist` skipped, is left in place.
+    pub(super) fn delete_conflict_copies(
+        &self,
+        skipped: &[PathBuf],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for conflict_copy in &self.conflict_copies {
+            let resolved = match conflict_copy.action {
+                ConflictCopyAction::Delete => true,
+                ConflictCopyAction::Merge => !skipped.contains(&conflict_copy.original),
+                ConflictCopyAction::Keep | ConflictCopyAction::Linked => false,
+            };
+            let unchanged = fs::read(&conflict_copy.copy).is_ok_and(|contents| {
+                sha256_cache::hash_contents(&contents) == conflict_copy.copy_hash
+            });
+            if resolved && unchanged {
+                fs::remove_file(&conflict_copy.copy)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::ConflictCopyAction;
+    use crate::config::ConflictResolution;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_conflict_copies_detected_merged_and_deleted() {
+        let temp_dir = TempDir::new().unwrap();
+        for (file_name, content) in [
+            ("Plan.md", "first idea\nsecond idea"),
+            ("Plan 2.md", "first idea\nsecond idea"),
+            (
+                "Plan (conflicted copy 2024-05-02).md",
+                "first idea\nthird idea",
+            ),
+            ("Goals.md", "ship it"),
+            ("Goals (Conflict 2024-05-03).md", "ship it later"),
+            ("Chapter 1.md", "a chapter"),
+            ("Index.md", "see [[Goals (Conflict 2024-05-03)]]"),
+        ] {
+            TestFileBuilder::new()
+                .with_title(file_name.trim_end_matches(".md").to_string())
+                .with_content(content.to_string())
+                .create(&temp_dir, file_name);
+        }
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.resolve_conflict_copies(Some(ConflictResolution::Merge));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let actions: Vec<(String, bool, ConflictCopyAction)> = obsidian_repository
+            .conflict_copies
+            .iter()
+            .map(|conflict_copy| {
+                (
+                    conflict_copy
+                        .copy
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    conflict_copy.identical,
+                    conflict_copy.action,
+                )
+            })
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (
+                    "Goals (Conflict 2024-05-03).md".to_string(),
+                    false,
+                    ConflictCopyAction::Linked
+                ),
+                (
+                    "Plan (conflicted copy 2024-05-02).md".to_string(),
+                    false,
+                    ConflictCopyAction::Merge
+                ),
+                ("Plan 2.md".to_string(), true, ConflictCopyAction::Delete),
+            ]
+        );
+        assert_eq!(obsidian_repository.markdown_files.len(), 5);
+
+        obsidian_repository.persist().unwrap();
+        assert!(!temp_dir.path().join("Plan 2.md").exists());
+        assert!(
+            !temp_dir
+                .path()
+                .join("Plan (conflicted copy 2024-05-02).md")
+                .exists()
+        );
+        assert!(
+            temp_dir
+                .path()
+                .join("Goals (Conflict 2024-05-03).md")
+                .exists()
+        );
+        let plan = fs::read_to_string(temp_dir.path().join("Plan.md")).unwrap();
+        assert!(plan.contains("second idea\n\nthird idea"));
+        assert_eq!(plan.matches("first idea").count(), 1);
+    }
+}
@@ -1,9 +1,27 @@
+// alias generation
+/// Fewer capitalized words than this make an acronym too short to be worth matching.
+pub(super) const MIN_ACRONYM_WORDS: usize = 3;
+/// Shorter last words are left alone - inflecting `bus` or `gas` guesses wrong too often.
+pub(super) const MIN_INFLECTED_WORD_LENGTH: usize = 4;
+pub(super) const PARENTHETICAL_SUFFIX_PATTERN: &str = r"^(.+?)\s*\([^)]*\)$";
+/// Words ending like this take `es` in the plural.
+pub(super) const PLURAL_ES_ENDINGS: [&str; 5] = ["ch", "sh", "ss", "x", "z"];
+pub(super) const PLURAL_SUFFIX_IES: &str = "ies";
+pub(super) const SINGULAR_SUFFIX_Y: &str = "y";
+pub(super) const SUFFIX_ES: &str = "es";
+pub(super) const SUFFIX_S: &str = "s";
+/// `analysis` and `status` aren't plurals, and their plurals are irregular.
+pub(super) const UNINFLECTED_S_ENDINGS: [&str; 2] = ["is", "us"];
+pub(super) const VOWELS: [char; 5] = ['a', 'e', 'i', 'o', 'u'];
+
 // back populate
-pub(super) const FIRST_CONTENT_LINE_NUMBER: usize = 1;
 pub(super) const INVALID_UTF8_BOUNDARY_PREFIX: &str = "invalid UTF-8 boundary in ";
 pub(super) const MIN_AMBIGUOUS_TARGETS: usize = 2;
 pub(super) const NESTED_PATTERN_WARNING: &str =
     "Warning: Potential nested pattern detected after replacement in file";
+pub(super) const OVERLAPPING_REPLACEMENT_PREFIX: &str = "overlapping replacements in ";
+pub(super) const REPLACEMENT_OUTSIDE_BODY_PREFIX: &str = "replacement outside the note body in ";
+pub(super) const REPLACEMENT_TEXT_MISMATCH_PREFIX: &str = "replacement text mismatch in ";
 pub(super) const TRIPLE_CLOSING_BRACKETS: &str = "]]]";
 pub(super) const TRIPLE_OPENING_BRACKETS: &str = "[[[";
 pub(super) const UNCLASSIFIED_MATCH_WARNING: &str = "[WARNING] Found unclassified matches for";
@@ -12,11 +30,165 @@ pub(super) const WIKILINKS_AUTOMATON_NOT_INITIALIZED: &str =
 pub(super) const WIKILINKS_AUTOMATON_NOT_INITIALIZED_DETAIL: &str =
     "ObsidianRepository::new must run first";
 
+// changelog
+pub(super) const CHANGELOG_ENTRY_PREFIX: &str = "- ";
+
+// conflict copies
+/// Obsidian Sync and Dropbox name a conflict `Note (conflicted copy 2024-05-02).md`, or
+/// `Note (Conflict ...)`.
+pub(super) const CONFLICTED_COPY_PATTERN: &str =
+    r"(?i)^(.+?) \((?:conflicted copy|conflict)\b[^)]*\)$";
+/// iCloud names a conflict `Note 2.md` - only a copy when its body matches the original.
+pub(super) const NUMBERED_COPY_PATTERN: &str = r"^(.+) \d+$";
+
+// export
+pub(super) const EXPORT_FOOTNOTE_CLOSING: char = ']';
+pub(super) const EXPORT_FOOTNOTE_OPENING: &str = "[^";
+pub(super) const EXPORT_FOOTNOTE_SEPARATOR: &str = ": ";
+/// `[[Note#Heading]]` without an alias reads as `Note > Heading`.
+pub(super) const EXPORT_HEADING_SEPARATOR: &str = " > ";
+pub(super) const EXPORT_IMAGES_FOLDER: &str = "images";
+
+// flashcards
+pub(super) const FLASHCARD_FIELD_SEPARATOR: char = '\t';
+/// Tells Anki's importer the columns, so a card's id updates the card imported before it.
+pub(super) const FLASHCARD_FILE_HEADER: &str =
+    "#separator:tab\n#html:false\n#notetype:Basic\n#guid column:1\n#tags column:4\n";
+/// Anki tags can't hold spaces.
+pub(super) const FLASHCARD_TAG_SPACE: &str = "_";
+
+// hidden folders
+/// The workspace files current Obsidian versions read - others starting with `workspace` are
+/// left over from older versions or sync conflicts.
+pub(super) const CURRENT_WORKSPACE_FILES: [&str; 3] =
+    ["workspace-mobile.json", "workspace.json", "workspaces.json"];
+pub(super) const SECONDS_PER_DAY: u64 = 86_400;
+pub(super) const WORKSPACE_FILE_PREFIX: &str = "workspace";
+
+// match decisions
+pub(super) const MATCH_DECISIONS_UNREADABLE: &str = "unreadable match decisions file";
+
+// other files
+/// Audio, video and image formats Obsidian opens that ok doesn't process as images.
+pub(super) const OBSIDIAN_ATTACHMENT_EXTENSIONS: [&str; 14] = [
+    "3gp", "avif", "bmp", "flac", "m4a", "mkv", "mov", "mp3", "mp4", "ogg", "ogv", "svg", "wav",
+    "webm",
+];
+
+// ownership
+/// The frontmatter property naming who a note belongs to in a shared vault.
+pub(super) const OWNER_PROPERTY: &str = "owner";
+pub(super) const PATH_RENAME_CHANGE: &str = "path renamed";
+
+// path audit
+/// Room left for where the vault lives on a Windows machine (`C:\Users\name\Documents\...`)
+/// within its 260 character path limit.
+pub(super) const MAX_VAULT_RELATIVE_PATH_LENGTH: usize = 200;
+/// Shortening a file name never takes it below this many characters.
+pub(super) const MIN_SHORTENED_STEM_LENGTH: usize = 16;
+pub(super) const RESERVED_NAME_SUFFIX: char = '_';
+pub(super) const TRAILING_PATH_CHARACTERS: [char; 2] = ['.', ' '];
+/// Characters Windows rejects in a file or folder name; Android's shared storage rejects the same.
+pub(super) const UNSAFE_PATH_CHARACTERS: [char; 8] = ['"', '*', ':', '<', '>', '?', '\\', '|'];
+pub(super) const UNSAFE_PATH_CHARACTER_REPLACEMENT: char = ' ';
+/// Device names Windows reserves regardless of extension - `aux.md` can't be created.
+pub(super) const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "AUX", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "CON", "LPT1",
+    "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9", "NUL", "PRN",
+];
+
+// periodic notes
+/// Parses a weekly note name once `WEEK_FIRST_DAY_SUFFIX` picks its Monday.
+pub(super) const FORMAT_WEEK_DAY: &str = "%G-W%V-%u";
+pub(super) const WEEK_FIRST_DAY_SUFFIX: &str = "-1";
+/// A week belongs to the month holding its Thursday, as ISO 8601 assigns weeks to years.
+pub(super) const WEEK_MONTH_DAY_OFFSET: u64 = 3;
+
+// personal data
+/// How the PII report names the folder at the top of the vault.
+pub(super) const VAULT_ROOT_FOLDER: &str = "/";
+
+// property types
+/// Obsidian writes a date & time property as `2024-01-31T09:30`, with optional seconds.
+pub(super) const PROPERTY_DATE_TIME_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"];
+pub(super) const PROPERTY_MIDNIGHT_SUFFIX: &str = "T00:00";
+
 // repository
 pub(super) const ANALYZE_TIMER_LABEL: &str = "analyze";
 pub(super) const DUPLICATE_KEEPER_INDEX: usize = 0;
 pub(super) const ERROR_PROCESSING_FILE: &str = "Error processing file";
+/// Line number reported for links found in frontmatter properties rather than content.
+pub(super) const FRONTMATTER_LINE_NUMBER: usize = 0;
 pub(super) const MARKDOWN_FILE_COLLECTION_SHARED_REFERENCES: &str =
     "markdown file collection still had shared references";
 pub(super) const MIN_DUPLICATE_GROUP_SIZE: usize = 2;
 pub(super) const PRESCAN_ANALYZE_TIMER_LABEL: &str = "prescan+analyze";
+
+// note splitting
+/// Characters a wikilink can't hold - a heading's note name drops them.
+pub(super) const SPLIT_NOTE_NAME_DISALLOWED_CHARACTERS: [char; 5] = ['#', '[', ']', '^', '|'];
+
+// structured files
+/// The field of a canvas file card that holds the vault-relative path of its file.
+pub(super) const STRUCTURED_FILE_PATH_KEY: &str = "file";
+
+// rare words
+pub(super) const ASCII_LOWERCASE_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+/// A common word must appear at least this many times as often as the rare word beside it.
+pub(super) const COMMON_WORD_FREQUENCY_RATIO: usize = 3;
+/// A common word must appear at least this many times across the vault.
+pub(super) const MIN_COMMON_WORD_OCCURRENCES: usize = 3;
+pub(super) const MIN_RARE_WORD_LENGTH: usize = 4;
+
+// similar titles
+pub(super) const PERCENT: f64 = 100.0;
+pub(super) const PERCENT_SCALE: usize = 100;
+pub(super) const ZERO_DIGIT: char = '0';
+
+// vault health
+/// Broken links, missing frontmatter, orphan images and date mismatches.
+pub(super) const VAULT_HEALTH_CATEGORY_COUNT: usize = 4;
+
+// vault index
+pub(super) const INSERT_ALIAS: &str = "INSERT INTO aliases (path, alias) VALUES (?1, ?2)";
+pub(super) const INSERT_IMAGE_REFERENCE: &str =
+    "INSERT INTO image_references (path, line, image) VALUES (?1, ?2, ?3)";
+pub(super) const INSERT_LINK: &str =
+    "INSERT INTO links (path, line, target, note, display_text) VALUES (?1, ?2, ?3, ?4, ?5)";
+pub(super) const INSERT_NOTE: &str = "INSERT INTO notes (path, name, date_created, date_modified, \
+                                      file_created, file_modified, content_hash, changed) VALUES \
+                                      (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)";
+pub(super) const INSERT_TAG: &str = "INSERT INTO tags (path, tag) VALUES (?1, ?2)";
+pub(super) const SELECT_CONTENT_HASHES: &str = "SELECT path, content_hash FROM notes";
+/// Each run replaces the whole index. Paths are relative to the vault, links found in
+/// frontmatter are on line 0, and a link's `note` is the lowercased name of the note its
+/// target points at.
+pub(super) const VAULT_INDEX_SCHEMA: &str = "
+    DROP TABLE IF EXISTS notes;
+    DROP TABLE IF EXISTS aliases;
+    DROP TABLE IF EXISTS tags;
+    DROP TABLE IF EXISTS links;
+    DROP TABLE IF EXISTS image_references;
+    CREATE TABLE notes (
+        path          TEXT PRIMARY KEY,
+        name          TEXT NOT NULL,
+        date_created  TEXT,
+        date_modified TEXT,
+        file_created  TEXT NOT NULL,
+        file_modified TEXT NOT NULL,
+        content_hash  TEXT NOT NULL,
+        changed       INTEGER NOT NULL
+    );
+    CREATE TABLE aliases (path TEXT NOT NULL, alias TEXT NOT NULL);
+    CREATE TABLE tags (path TEXT NOT NULL, tag TEXT NOT NULL);
+    CREATE TABLE links (
+        path         TEXT NOT NULL,
+        line         INTEGER NOT NULL,
+        target       TEXT NOT NULL,
+        note         TEXT NOT NULL,
+        display_text TEXT NOT NULL
+    );
+    CREATE TABLE image_references (path TEXT NOT NULL, line INTEGER NOT NULL, image TEXT NOT NULL);
+    CREATE INDEX links_note ON links (note);
+    CREATE INDEX tags_tag ON tags (tag);
+";
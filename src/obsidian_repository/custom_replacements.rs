@@ -0,0 +1,101 @@
+use super::ObsidianRepository;
+use crate::markdown_file::ReplacementSource;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// Records a `CustomReplacementMatch` for every replacement the `replacement_sources`
+    /// find. Overlaps with the built-in matches are left to `apply_replaceable_matches`, which
+    /// ranks custom replacements after regex replacements.
+    pub(crate) fn find_all_custom_replacement_matches(
+        &mut self,
+        replacement_sources: &[&dyn ReplacementSource],
+        validated_config: &ValidatedConfig,
+    ) {
+        if replacement_sources.is_empty() {
+            return;
+        }
+
+        for markdown_file in &mut self.markdown_files {
+            markdown_file.custom_replacement_matches = markdown_file
+                .find_custom_replacement_matches(
+                    replacement_sources,
+                    validated_config.obsidian_path(),
+                );
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::markdown_file::LineReplacement;
+    use crate::markdown_file::PersistReason;
+    use crate::markdown_file::ReplacementSource;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    struct BareUrlLinks;
+
+    impl ReplacementSource for BareUrlLinks {
+        fn name(&self) -> &'static str { "bare urls" }
+
+        fn find_replacements(&self, line: &str) -> Vec<LineReplacement> {
+            line.match_indices("https://")
+                .filter(|(position, _)| !line[..*position].ends_with('('))
+                .map(|(position, _)| {
+                    let end = line[position..]
+                        .find(char::is_whitespace)
+                        .map_or(line.len(), |offset| position + offset);
+                    let url = &line[position..end];
+                    LineReplacement {
+                        position,
+                        found_text: url.to_string(),
+                        replacement: format!("[{url}]({url})"),
+                    }
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_custom_replacements_applied() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "read https://example.com today\n```\nhttps://example.org\n```\nsee [x](https://example.net)",
+            )
+            .create(&temp_dir, "notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+        let obsidian_repository =
+            ObsidianRepository::with_replacement_sources(&validated_config, &[&BareUrlLinks])
+                .unwrap();
+
+        let notes = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path == file_path)
+            .unwrap();
+
+        assert_eq!(
+            notes.content,
+            "read [https://example.com](https://example.com) today\n```\nhttps://example.org\n```\nsee [x](https://example.net)",
+            "code blocks and links already made are left alone"
+        );
+        assert!(
+            notes
+                .persist_reasons
+                .contains(&PersistReason::CustomReplacementsApplied {
+                    source: "bare urls".to_string(),
+                    count:  1,
+                })
+        );
+    }
+}
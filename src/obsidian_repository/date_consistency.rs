@@ -0,0 +1,147 @@
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+
+use super::ObsidianRepository;
+use super::periodic_notes;
+use crate::constants::FORMAT_DATE;
+use crate::validated_config::ValidatedConfig;
+
+/// The date a note in one of the `journal_folders` is named for, as a daily note is.
+fn journal_date(path: &Path, folders: &[PathBuf], date_link_format: &str) -> Option<NaiveDate> {
+    if !folders.iter().any(|folder| path.starts_with(folder)) {
+        return None;
+    }
+    periodic_notes::daily_note_date(path.file_stem().and_then(OsStr::to_str)?, date_link_format)
+}
+
+impl ObsidianRepository {
+    /// Checks every date-named note in `journal_folders` against its `date_created` and file
+    /// creation date, moving both to the name's date. Runs first, while `date_created` still
+    /// holds what the note was read with.
+    pub(crate) fn check_all_journal_dates(&mut self, validated_config: &ValidatedConfig) {
+        let folders: Vec<PathBuf> = validated_config
+            .journal_folders()
+            .iter()
+            .map(|folder| validated_config.obsidian_path().join(folder))
+            .collect();
+        if folders.is_empty() {
+            return;
+        }
+
+        for markdown_file in &mut self.markdown_files {
+            if let Some(date) = journal_date(
+                &markdown_file.path,
+                &folders,
+                validated_config.date_link_format(),
+            ) {
+                markdown_file.check_journal_date(
+                    date,
+                    validated_config.operational_timezone(),
+                    validated_config.hold_persist_reasons(),
+                );
+            }
+        }
+    }
+
+    /// Checks the `date_properties` of every note, turning plain dates into `[[YYYY-MM-DD]]`
+    /// wikilinks. Runs before `check_property_types`, so a fixed value counts as the text it
+    /// will be written as.
+    pub(crate) fn validate_all_date_properties(&mut self, validated_config: &ValidatedConfig) {
+        let properties = validated_config.date_properties();
+        if properties.is_empty() {
+            return;
+        }
+
+        for markdown_file in &mut self.markdown_files {
+            markdown_file.validate_date_properties(
+                properties,
+                validated_config.operational_timezone(),
+                validated_config.hold_persist_reasons(),
+            );
+        }
+    }
+
+    /// Flags impossible frontmatter dates in every note and, with `fix_impossible_dates` set,
+    /// fixes them. Runs after the passes that stamp `date_modified`, so it checks the dates as
+    /// they will be written.
+    pub(crate) fn check_all_date_consistency(&mut self, validated_config: &ValidatedConfig) {
+        let Ok(today) = NaiveDate::parse_from_str(&validated_config.today(), FORMAT_DATE) else {
+            return;
+        };
+
+        for markdown_file in &mut self.markdown_files {
+            markdown_file.check_date_consistency(
+                today,
+                validated_config.future_date_tolerance_days(),
+                validated_config.fix_impossible_dates(),
+                validated_config.operational_timezone(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_journal_dates_moved_to_the_note_name() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("journal")).unwrap();
+        let date = test_support::eastern_midnight(2024, 4, 30);
+        for path in ["journal/2024-05-02.md", "journal/Ideas.md", "2024-05-03.md"] {
+            TestFileBuilder::new()
+                .with_matching_dates(date)
+                .create(&temp_dir, path);
+        }
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.journal_folders(vec![PathBuf::from("journal")]);
+            })
+            .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let named = NaiveDate::from_ymd_opt(2024, 5, 2).unwrap();
+        for markdown_file in &repository.markdown_files {
+            if !markdown_file.path.ends_with("2024-05-02.md") {
+                assert!(markdown_file.journal_date_mismatch.is_none());
+                continue;
+            }
+            let mismatch = markdown_file.journal_date_mismatch.as_ref().unwrap();
+            assert_eq!(mismatch.date, named);
+            assert_eq!(mismatch.date_created, NaiveDate::from_ymd_opt(2024, 4, 30));
+
+            let front_matter = markdown_file.front_matter.as_ref().unwrap();
+            assert_eq!(front_matter.date_created(), Some("[[2024-05-02]]"));
+            assert!(
+                markdown_file
+                    .persist_reasons
+                    .contains(&PersistReason::JournalDateAligned { date: named })
+            );
+            assert!(
+                !markdown_file
+                    .persist_reasons
+                    .iter()
+                    .any(|reason| matches!(reason, PersistReason::DateCreatedUpdated { .. })),
+                "the name's date replaces the file's"
+            );
+        }
+    }
+}
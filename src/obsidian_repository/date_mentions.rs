@@ -0,0 +1,90 @@
+use super::ObsidianRepository;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `link_date_mentions` on, records a `DateMentionMatch` for every plain-text date in
+    /// a note body. A date back populate already links - a daily note's name - or that overlaps
+    /// a link fix or image reference update is left to that change.
+    pub(crate) fn find_all_date_mention_matches(&mut self, validated_config: &ValidatedConfig) {
+        if !validated_config.link_date_mentions() {
+            return;
+        }
+
+        let fix_self_links = validated_config.fix_self_links();
+        for markdown_file in &mut self.markdown_files {
+            let mut matches = markdown_file.find_date_mention_matches(validated_config);
+            if matches.is_empty() {
+                continue;
+            }
+
+            let (other_matches, _) =
+                Self::collect_replaceable_matches(markdown_file, fix_self_links);
+            matches.retain(|date_match| {
+                let end = date_match.position + date_match.found_text.len();
+                !other_matches.iter().any(|other| {
+                    other.line_number() == date_match.line_number
+                        && date_match.position < other.position() + other.matched_text().len()
+                        && other.position() < end
+                })
+            });
+
+            markdown_file.date_mention_matches = matches;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_date_mentions_linked_in_date_link_format() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("Thursday")
+            .create(&temp_dir, "02.05.2024.md");
+        let file_path = TestFileBuilder::new()
+            .with_content("met on 2024-03-02, again on 02.05.2024 and on May 2, 2024")
+            .create(&temp_dir, "notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder
+                    .link_date_mentions(true)
+                    .date_link_format("%d.%m.%Y".to_string());
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let notes = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path == file_path)
+            .unwrap();
+
+        assert_eq!(
+            notes.date_mention_matches.len(),
+            2,
+            "the daily note's name is back populated instead"
+        );
+        assert_eq!(
+            notes.content,
+            "met on [[02.03.2024|2024-03-02]], again on [[02.05.2024]] and on \
+             [[02.05.2024|May 2, 2024]]"
+        );
+        assert!(
+            notes
+                .persist_reasons
+                .contains(&PersistReason::DateMentionsLinked)
+        );
+    }
+}
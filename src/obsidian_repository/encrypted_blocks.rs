@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use crate::markdown_file::EncryptedBlock;
+
+/// An encrypted block and the note holding it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct EncryptedNoteBlock {
+    pub path:  PathBuf,
+    pub block: EncryptedBlock,
+}
+
+impl ObsidianRepository {
+    /// Lists every encrypted block so the report shows what the run left alone. The passes
+    /// that rewrite note bodies skip these lines themselves.
+    pub(crate) fn find_encrypted_blocks(&mut self) {
+        self.encrypted_blocks = self
+            .markdown_files
+            .iter()
+            .flat_map(|markdown_file| {
+                markdown_file
+                    .encrypted_blocks()
+                    .into_iter()
+                    .map(|block| EncryptedNoteBlock {
+                        path: markdown_file.path.clone(),
+                        block,
+                    })
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_encrypted_blocks_are_listed_and_left_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content(
+                "It’s open\n%%🔐β 💡it’s the usual💡YWJj\nZGVm 🔐%%\nIt’s closed".to_string(),
+            )
+            .create(&temp_dir, "Vault Codes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.normalize_punctuation(true);
+            })
+            .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        assert_eq!(repository.encrypted_blocks.len(), 1);
+        let markdown_file = &repository.markdown_files[0];
+        assert_eq!(
+            markdown_file.content.lines().collect::<Vec<_>>(),
+            vec![
+                "It's open",
+                "%%🔐β 💡it’s the usual💡YWJj",
+                "ZGVm 🔐%%",
+                "It's closed"
+            ]
+        );
+    }
+}
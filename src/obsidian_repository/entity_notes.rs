@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use serde_yaml::Value;
+
+use super::ObsidianRepository;
+use super::phantom_links;
+use crate::config::EntityFolderRule;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::MISSING_REQUIRED_PROPERTY;
+use crate::constants::NO_ALIAS_STARTING_WITH;
+use crate::constants::NOT_LINKED_FROM;
+use crate::constants::OPENING_WIKILINK;
+use crate::frontmatter::FrontMatter;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum EntityNoteIssueKind {
+    MissingAlias { prefix: String },
+    MissingProperty { property: String },
+    NotInIndex { index_note: String },
+}
+
+impl Display for EntityNoteIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAlias { prefix } => write!(f, "{NO_ALIAS_STARTING_WITH} {prefix}"),
+            Self::MissingProperty { property } => {
+                write!(f, "{MISSING_REQUIRED_PROPERTY} {property}")
+            },
+            Self::NotInIndex { index_note } => {
+                write!(f, "{NOT_LINKED_FROM} {}", index_note.to_wikilink())
+            },
+        }
+    }
+}
+
+/// A note in one of the `entity_folders` that breaks its folder's conventions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct EntityNoteIssue {
+    pub path:   PathBuf,
+    pub folder: PathBuf,
+    pub kind:   EntityNoteIssueKind,
+    /// What `fix_entity_notes` adds - `None` when it's off or there is no unambiguous fix.
+    pub fix:    Option<String>,
+}
+
+fn note_stem(path: &Path) -> &str { path.file_stem().and_then(OsStr::to_str).unwrap_or_default() }
+
+/// A property with nothing in it - absent, null, blank or an empty list.
+fn is_blank(front_matter: Option<&FrontMatter>, property: &str) -> bool {
+    match front_matter.and_then(|front_matter| front_matter.property(property)) {
+        None | Some(Value::Null) => true,
+        Some(Value::String(value)) => value.trim().is_empty(),
+        Some(Value::Sequence(values)) => values.is_empty(),
+        Some(_) => false,
+    }
+}
+
+impl ObsidianRepository {
+    /// Checks every note under each of the `entity_folders` against its rule: linked from the
+    /// index note, an alias with the rule's prefix, and a value for each required property.
+    /// With `fix_entity_notes` on, a missing alias is added as the prefix and the note name,
+    /// and a note missing from an existing index note is appended to it as a list item.
+    /// Missing property values are only reported.
+    pub(crate) fn check_entity_folders(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let rules = validated_config.entity_folders();
+        if rules.is_empty() {
+            return Ok(());
+        }
+
+        let operational_timezone = validated_config.operational_timezone();
+        let fix = validated_config.fix_entity_notes();
+        let mut issues = Vec::new();
+        let mut index_links: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+
+        for rule in rules {
+            let folder = validated_config.obsidian_path().join(&rule.folder);
+            let index_note = rule.index_note.as_deref().map(|index_note| {
+                index_note
+                    .trim()
+                    .trim_start_matches(OPENING_WIKILINK)
+                    .trim_end_matches(CLOSING_WIKILINK)
+                    .to_string()
+            });
+            let index_position = index_note.as_deref().and_then(|index_note| {
+                let index_stem = phantom_links::target_note_stem(index_note);
+                self.markdown_files.iter().position(|markdown_file| {
+                    note_stem(&markdown_file.path).to_lowercase() == index_stem
+                })
+            });
+            let indexed: HashSet<String> = index_position
+                .map(|position| {
+                    self.markdown_files[position]
+                        .wikilinks
+                        .valid
+                        .iter()
+                        .map(|wikilink| phantom_links::target_note_stem(&wikilink.target))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            for (position, markdown_file) in self.markdown_files.iter_mut().enumerate() {
+                if !markdown_file.path.starts_with(&folder) || Some(position) == index_position {
+                    continue;
+                }
+                let stem = note_stem(&markdown_file.path).to_string();
+
+                if let Some(index_note) = &index_note
+                    && !indexed.contains(&stem.to_lowercase())
+                {
+                    let fixable = fix && index_position.is_some();
+                    if let Some(position) = index_position.filter(|_| fixable) {
+                        index_links.entry(position).or_default().push(stem.clone());
+                    }
+                    issues.push(entity_note_issue(
+                        &markdown_file.path,
+                        rule,
+                        EntityNoteIssueKind::NotInIndex {
+                            index_note: index_note.clone(),
+                        },
+                        fixable.then(|| stem.to_wikilink()),
+                    ));
+                }
+
+                if let Some(prefix) = &rule.alias_prefix
+                    && !markdown_file
+                        .front_matter
+                        .as_ref()
+                        .and_then(FrontMatter::aliases)
+                        .is_some_and(|aliases| {
+                            aliases.iter().any(|alias| alias.starts_with(prefix))
+                        })
+                {
+                    let alias = format!("{prefix}{stem}");
+                    if fix {
+                        markdown_file.add_entity_alias(&alias, operational_timezone)?;
+                    }
+                    issues.push(entity_note_issue(
+                        &markdown_file.path,
+                        rule,
+                        EntityNoteIssueKind::MissingAlias {
+                            prefix: prefix.clone(),
+                        },
+                        fix.then_some(alias),
+                    ));
+                }
+
+                for property in &rule.required_properties {
+                    if is_blank(markdown_file.front_matter.as_ref(), property) {
+                        issues.push(entity_note_issue(
+                            &markdown_file.path,
+                            rule,
+                            EntityNoteIssueKind::MissingProperty {
+                                property: property.clone(),
+                            },
+                            None,
+                        ));
+                    }
+                }
+            }
+        }
+
+        for (position, note_names) in index_links {
+            self.markdown_files[position]
+                .append_entity_index_links(&note_names, operational_timezone)?;
+        }
+
+        self.entity_note_issues = issues;
+        Ok(())
+    }
+}
+
+fn entity_note_issue(
+    path: &Path,
+    rule: &EntityFolderRule,
+    kind: EntityNoteIssueKind,
+    fix: Option<String>,
+) -> EntityNoteIssue {
+    EntityNoteIssue {
+        path: path.to_path_buf(),
+        folder: rule.folder.clone(),
+        kind,
+        fix,
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+
+    use super::EntityNoteIssueKind;
+    use crate::config::EntityFolderRule;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_entity_folder_conventions_checked_and_fixed() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("people")).unwrap();
+        TestFileBuilder::new()
+            .with_title("People Index".to_string())
+            .with_content("- [[Ada Lovelace]]".to_string())
+            .create(&temp_dir, "People Index.md");
+        TestFileBuilder::new()
+            .with_title("Ada Lovelace".to_string())
+            .with_aliases(vec!["\"@Ada\"".to_string()])
+            .with_custom_frontmatter("birthday: 1815-12-10".to_string())
+            .create(&temp_dir, "people/Ada Lovelace.md");
+        TestFileBuilder::new()
+            .with_title("Alan Turing".to_string())
+            .create(&temp_dir, "people/Alan Turing.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder
+                    .entity_folders(vec![EntityFolderRule {
+                        folder:              PathBuf::from("people"),
+                        index_note:          Some("[[People Index]]".to_string()),
+                        alias_prefix:        Some("@".to_string()),
+                        required_properties: vec!["birthday".to_string()],
+                    }])
+                    .fix_entity_notes(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let issues: Vec<(String, EntityNoteIssueKind, Option<String>)> = obsidian_repository
+            .entity_note_issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue
+                        .path
+                        .file_stem()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    issue.kind.clone(),
+                    issue.fix.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (
+                    "Alan Turing".to_string(),
+                    EntityNoteIssueKind::NotInIndex {
+                        index_note: "People Index".to_string(),
+                    },
+                    Some("[[Alan Turing]]".to_string())
+                ),
+                (
+                    "Alan Turing".to_string(),
+                    EntityNoteIssueKind::MissingAlias {
+                        prefix: "@".to_string(),
+                    },
+                    Some("@Alan Turing".to_string())
+                ),
+                (
+                    "Alan Turing".to_string(),
+                    EntityNoteIssueKind::MissingProperty {
+                        property: "birthday".to_string(),
+                    },
+                    None
+                ),
+            ]
+        );
+
+        obsidian_repository.persist().unwrap();
+        let index = fs::read_to_string(temp_dir.path().join("People Index.md")).unwrap();
+        assert!(index.contains("- [[Ada Lovelace]]\n- [[Alan Turing]]"));
+        let turing = fs::read_to_string(temp_dir.path().join("people/Alan Turing.md")).unwrap();
+        assert!(turing.contains("@Alan Turing"));
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use serde_yaml::Value;
+
+use super::ObsidianRepository;
+use super::constants::EXPORT_FOOTNOTE_CLOSING;
+use super::constants::EXPORT_FOOTNOTE_OPENING;
+use super::constants::EXPORT_FOOTNOTE_SEPARATOR;
+use super::constants::EXPORT_HEADING_SEPARATOR;
+use super::constants::EXPORT_IMAGES_FOLDER;
+use super::phantom_links;
+use super::vault_index;
+use crate::config::ExportLinkStyle;
+use crate::config::ExportProfile;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::DEFAULT_EXPORT_FOLDER;
+use crate::constants::FORWARD_SLASH;
+use crate::constants::HASH;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::NEWLINE;
+use crate::constants::OPENING_WIKILINK;
+use crate::constants::TITLE;
+use crate::markdown_file::ExportReference;
+use crate::markdown_file::MarkdownFile;
+use crate::note_template;
+use crate::validated_config::RedactionRule;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::Wikilink;
+
+/// A listed note as the export writes it.
+#[derive(Clone, Debug)]
+pub(crate) struct ExportedNote {
+    pub path:    PathBuf,
+    pub content: String,
+    /// Image file names as written in the export, and the files they're copied from.
+    pub images:  BTreeMap<String, PathBuf>,
+}
+
+/// What `export.redact` kept out of an export.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Withheld {
+    /// The whole note, for its private tag.
+    Note { tag: String },
+    /// A line matching one of the redaction patterns.
+    Line {
+        line_number: usize,
+        pattern:     String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WithheldContent {
+    pub path:     PathBuf,
+    pub withheld: Withheld,
+}
+
+impl ObsidianRepository {
+    /// With `export` set, lays out each listed note as pandoc-ready markdown: a `title`
+    /// property, embeds replaced by the text they show, wikilinks as plain text or footnotes,
+    /// and images to copy to `images/` beside it. Listed notes that don't exist are skipped.
+    /// With `redact` set, private notes and lines matching its patterns are withheld. Runs in
+    /// the analysis so the report can list what was withheld.
+    pub(crate) fn prepare_exports(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let Some(profile) = validated_config.export() else {
+            return Ok(());
+        };
+
+        let export_folder = validated_config
+            .output_folder()
+            .join(profile.folder.as_deref().unwrap_or(DEFAULT_EXPORT_FOLDER));
+        let notes_by_stem: HashMap<String, &MarkdownFile> = self
+            .markdown_files
+            .iter()
+            .map(|markdown_file| (note_stem(&markdown_file.path), markdown_file))
+            .collect();
+
+        let mut exported_notes = Vec::new();
+        let mut withheld_content = Vec::new();
+        for note in &profile.notes {
+            let name = note
+                .trim()
+                .trim_start_matches(OPENING_WIKILINK)
+                .trim_end_matches(CLOSING_WIKILINK);
+            let Some(&markdown_file) = notes_by_stem.get(&phantom_links::target_note_stem(name))
+            else {
+                continue;
+            };
+
+            let mut note_export = NoteExport {
+                repository: self,
+                profile,
+                redaction: validated_config.export_redaction(),
+                notes_by_stem: &notes_by_stem,
+                embedding: Vec::new(),
+                footnotes: Vec::new(),
+                images: BTreeMap::new(),
+                withheld: withheld_content,
+            };
+            if !note_export.withhold_private(markdown_file) {
+                let body = note_export.note_text(markdown_file, None);
+                exported_notes.push(note_export.exported_note(
+                    &export_folder,
+                    markdown_file,
+                    &body,
+                )?);
+            }
+            withheld_content = note_export.withheld;
+        }
+
+        self.exported_notes = exported_notes;
+        self.withheld_content = withheld_content;
+        Ok(())
+    }
+
+    /// Writes the notes `prepare_exports` laid out, and copies the images they show.
+    pub(crate) fn write_exports(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for exported_note in &self.exported_notes {
+            if let Some(export_folder) = exported_note.path.parent() {
+                fs::create_dir_all(export_folder)?;
+            }
+            fs::write(&exported_note.path, &exported_note.content)?;
+
+            if exported_note.images.is_empty() {
+                continue;
+            }
+            let images_folder = exported_note.path.with_file_name(EXPORT_IMAGES_FOLDER);
+            fs::create_dir_all(&images_folder)?;
+            for (name, path) in &exported_note.images {
+                fs::copy(path, images_folder.join(name))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The state of exporting one listed note and everything it embeds.
+struct NoteExport<'a> {
+    repository:    &'a ObsidianRepository,
+    profile:       &'a ExportProfile,
+    redaction:     Option<&'a RedactionRule>,
+    notes_by_stem: &'a HashMap<String, &'a MarkdownFile>,
+    /// The notes being flattened, outermost first - an embed of one of them would never end.
+    embedding:     Vec<&'a Path>,
+    /// Linked notes, numbered by position from 1.
+    footnotes:     Vec<String>,
+    /// Image file names as written in the export, and the files they're copied from.
+    images:        BTreeMap<String, &'a Path>,
+    /// Everything withheld so far, across the listed notes.
+    withheld:      Vec<WithheldContent>,
+}
+
+impl<'a> NoteExport<'a> {
+    fn note_text(&mut self, markdown_file: &'a MarkdownFile, section: Option<&str>) -> String {
+        self.embedding.push(&markdown_file.path);
+        let convert_callouts = self.profile.convert_callouts;
+        let redact_patterns = self
+            .redaction
+            .map_or(&[][..], |redaction| redaction.regexes.as_slice());
+        let exported = markdown_file.export_content(
+            section,
+            convert_callouts,
+            redact_patterns,
+            &mut |reference| self.resolve(reference),
+        );
+        self.embedding.pop();
+
+        for (line_number, pattern) in exported.withheld {
+            self.withhold(
+                markdown_file,
+                Withheld::Line {
+                    line_number,
+                    pattern,
+                },
+            );
+        }
+        exported.text
+    }
+
+    /// Whether `markdown_file` has the private tag, recording it as withheld when it does.
+    fn withhold_private(&mut self, markdown_file: &MarkdownFile) -> bool {
+        let Some(redaction) = self.redaction else {
+            return false;
+        };
+        let is_private = vault_index::note_tags(markdown_file)
+            .iter()
+            .any(|tag| tag.to_lowercase() == redaction.private_tag);
+        if is_private {
+            self.withhold(
+                markdown_file,
+                Withheld::Note {
+                    tag: redaction.private_tag.clone(),
+                },
+            );
+        }
+        is_private
+    }
+
+    fn withhold(&mut self, markdown_file: &MarkdownFile, withheld: Withheld) {
+        let withheld_content = WithheldContent {
+            path: markdown_file.path.clone(),
+            withheld,
+        };
+        if !self.withheld.contains(&withheld_content) {
+            self.withheld.push(withheld_content);
+        }
+    }
+
+    fn resolve(&mut self, reference: ExportReference<'_>) -> String {
+        match reference {
+            ExportReference::Image(filename) => self.image_path(filename),
+            ExportReference::Embed { target, section } => {
+                match self
+                    .notes_by_stem
+                    .get(&phantom_links::target_note_stem(target))
+                {
+                    Some(&markdown_file) if self.withhold_private(markdown_file) => String::new(),
+                    Some(&markdown_file)
+                        if !self.embedding.contains(&markdown_file.path.as_path()) =>
+                    {
+                        self.note_text(markdown_file, section)
+                    },
+                    _ => link_text(target),
+                }
+            },
+            ExportReference::Link(wikilink) => self.link(wikilink),
+        }
+    }
+
+    /// The image's path relative to the export, once it's known to the vault.
+    fn image_path(&mut self, filename: &str) -> String {
+        let wanted = Path::new(filename)
+            .file_name()
+            .and_then(OsStr::to_str)
+            .unwrap_or(filename)
+            .to_lowercase();
+        let Some(image_file) = self.repository.image_files.iter().find(|image_file| {
+            image_file
+                .path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .is_some_and(|name| name.to_lowercase() == wanted)
+        }) else {
+            return filename.to_string();
+        };
+
+        let name = image_file
+            .path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .unwrap_or(filename)
+            .to_string();
+        self.images.insert(name.clone(), &image_file.path);
+        format!("{EXPORT_IMAGES_FOLDER}{FORWARD_SLASH}{name}")
+    }
+
+    fn link(&mut self, wikilink: &Wikilink) -> String {
+        let target_text = link_text(&wikilink.target);
+        let text = if wikilink.is_alias() {
+            wikilink.display_text.clone()
+        } else {
+            target_text.clone()
+        };
+
+        match self.profile.links {
+            ExportLinkStyle::Plain => text,
+            ExportLinkStyle::Footnotes => {
+                let number = self
+                    .footnotes
+                    .iter()
+                    .position(|footnote| *footnote == target_text)
+                    .unwrap_or_else(|| {
+                        self.footnotes.push(target_text);
+                        self.footnotes.len() - 1
+                    })
+                    + 1;
+                format!("{text}{EXPORT_FOOTNOTE_OPENING}{number}{EXPORT_FOOTNOTE_CLOSING}")
+            },
+        }
+    }
+
+    fn exported_note(
+        &self,
+        export_folder: &Path,
+        markdown_file: &MarkdownFile,
+        body: &str,
+    ) -> AnyhowResult<ExportedNote> {
+        let title = markdown_file
+            .path
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default();
+
+        let mut text = format!("{body}{NEWLINE}");
+        if !self.footnotes.is_empty() {
+            text.push(NEWLINE);
+        }
+        for (index, footnote) in self.footnotes.iter().enumerate() {
+            let _ = write!(
+                text,
+                "{EXPORT_FOOTNOTE_OPENING}{}{EXPORT_FOOTNOTE_CLOSING}{EXPORT_FOOTNOTE_SEPARATOR}\
+                 {footnote}{NEWLINE}",
+                index + 1
+            );
+        }
+        let content = note_template::compose_note(
+            None,
+            |front_matter| front_matter.set_property(TITLE, Value::String(title.to_string())),
+            &format!("{NEWLINE}{text}"),
+        )?;
+
+        Ok(ExportedNote {
+            path: export_folder.join(format!("{title}{MARKDOWN_SUFFIX}")),
+            content,
+            images: self
+                .images
+                .iter()
+                .map(|(name, path)| (name.clone(), path.to_path_buf()))
+                .collect(),
+        })
+    }
+}
+
+fn note_stem(path: &Path) -> String {
+    phantom_links::target_note_stem(path.file_stem().and_then(OsStr::to_str).unwrap_or_default())
+}
+
+/// A link target as prose: `folder/Note#Heading` reads as `Note > Heading`.
+fn link_text(target: &str) -> String {
+    let without_folder = target.rsplit(FORWARD_SLASH).next().unwrap_or(target);
+    without_folder
+        .trim_start_matches(HASH)
+        .split(HASH)
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(EXPORT_HEADING_SEPARATOR)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use super::Withheld;
+    use crate::config::ExportLinkStyle;
+    use crate::config::ExportProfile;
+    use crate::config::ExportRedaction;
+    use crate::constants::DEFAULT_REDACTION_PATTERNS;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::RedactionRule;
+
+    #[test]
+    fn test_write_exports_flattens_embeds_and_footnotes_links() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content(
+                "See [[Tokyo]] and [[Tokyo|the capital]].\n![[Details#Trains]]\n![[Essay]]"
+                    .to_string(),
+            )
+            .create(&temp_dir, "Essay.md");
+        TestFileBuilder::new()
+            .with_content("## Trains\n> [!note]\n> Fast, see [[Kyoto]]\n## Food\nRamen".to_string())
+            .create(&temp_dir, "Details.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.export(Some(ExportProfile {
+                    notes:            vec!["[[Essay]]".to_string()],
+                    folder:           None,
+                    links:            ExportLinkStyle::Footnotes,
+                    convert_callouts: true,
+                    redact:           None,
+                }));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        obsidian_repository.write_exports().unwrap();
+
+        let exported = fs::read_to_string(
+            validated_config
+                .output_folder()
+                .join("export")
+                .join("Essay.md"),
+        )
+        .unwrap();
+        assert_eq!(
+            exported,
+            "---\ntitle: Essay\n---\n\nSee Tokyo[^1] and the capital[^1].\n## Trains\n> \
+             **Note**\n> Fast, see Kyoto[^2]\nEssay\n\n[^1]: Tokyo\n[^2]: Kyoto\n"
+        );
+    }
+
+    #[test]
+    fn test_prepare_exports_withholds_private_notes_and_secrets() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("Call 555-123-4567 today.\nPlans below.\n![[Diary]]".to_string())
+            .create(&temp_dir, "Essay.md");
+        TestFileBuilder::new()
+            .with_content("#private\nDear diary".to_string())
+            .create(&temp_dir, "Diary.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                let profile = ExportProfile {
+                    notes:            vec!["Essay".to_string(), "Diary".to_string()],
+                    folder:           None,
+                    links:            ExportLinkStyle::Plain,
+                    convert_callouts: false,
+                    redact:           Some(ExportRedaction::default()),
+                };
+                builder.export_redaction(Some(RedactionRule {
+                    regexes:     DEFAULT_REDACTION_PATTERNS
+                        .iter()
+                        .map(|pattern| Regex::new(pattern).unwrap())
+                        .collect(),
+                    private_tag: "private".to_string(),
+                }));
+                builder.export(Some(profile));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        assert_eq!(obsidian_repository.exported_notes.len(), 1);
+        let content = &obsidian_repository.exported_notes[0].content;
+        assert!(content.ends_with("\nPlans below.\n"), "{content}");
+
+        let withheld: Vec<(&str, &Withheld)> = obsidian_repository
+            .withheld_content
+            .iter()
+            .map(|content| {
+                (
+                    content.path.file_name().unwrap().to_str().unwrap(),
+                    &content.withheld,
+                )
+            })
+            .collect();
+        assert_eq!(withheld.len(), 2);
+        // The embed is resolved while the essay's lines are read, so the diary comes first.
+        assert_eq!(
+            withheld[0],
+            (
+                "Diary.md",
+                &Withheld::Note {
+                    tag: "private".to_string(),
+                }
+            )
+        );
+        assert!(matches!(withheld[1], ("Essay.md", Withheld::Line { .. })));
+    }
+}
@@ -0,0 +1,100 @@
+use std::error::Error;
+use std::fs;
+
+use anyhow::Result as AnyhowResult;
+
+use super::ObsidianRepository;
+use super::constants::FLASHCARD_FIELD_SEPARATOR;
+use super::constants::FLASHCARD_FILE_HEADER;
+use super::constants::FLASHCARD_TAG_SPACE;
+use super::vault_index;
+use crate::constants::DEFAULT_FLASHCARD_ANSWER_PREFIX;
+use crate::constants::DEFAULT_FLASHCARD_QUESTION_PREFIX;
+use crate::constants::FLASHCARDS_FILE;
+use crate::constants::NEWLINE;
+use crate::constants::SPACE;
+use crate::constants::TAG_PREFIX;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `flashcards` set, gathers the question and answer pairs from every note - or only
+    /// from the notes with its `tag` - and gives each new card the id that stays with it.
+    /// Runs after `apply_replaceable_matches`, since the ids change the lines they end.
+    pub(crate) fn collect_all_flashcards(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let Some(rule) = validated_config.flashcards() else {
+            return Ok(());
+        };
+
+        let question_prefix = rule
+            .question_prefix
+            .as_deref()
+            .unwrap_or(DEFAULT_FLASHCARD_QUESTION_PREFIX);
+        let answer_prefix = rule
+            .answer_prefix
+            .as_deref()
+            .unwrap_or(DEFAULT_FLASHCARD_ANSWER_PREFIX);
+        let tag = rule
+            .tag
+            .as_deref()
+            .map(|tag| tag.trim().trim_start_matches(TAG_PREFIX).to_lowercase());
+
+        let mut flashcards = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            if let Some(tag) = &tag
+                && !vault_index::note_tags(markdown_file)
+                    .iter()
+                    .any(|note_tag| note_tag.to_lowercase() == *tag)
+            {
+                continue;
+            }
+
+            let id_seed =
+                vault_index::relative_path(validated_config.obsidian_path(), &markdown_file.path);
+            flashcards.extend(markdown_file.collect_flashcards(
+                question_prefix,
+                answer_prefix,
+                &id_seed,
+                validated_config.operational_timezone(),
+            )?);
+        }
+
+        self.flashcards = flashcards;
+        Ok(())
+    }
+
+    /// With `flashcards` set, writes every card to a tab-separated file in the output folder
+    /// for Anki's importer, which updates the cards it already has by their ids and tags each
+    /// with the note it came from.
+    pub(crate) fn write_flashcards(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if validated_config.flashcards().is_none() {
+            return Ok(());
+        }
+
+        let mut tsv = FLASHCARD_FILE_HEADER.to_string();
+        for flashcard in &self.flashcards {
+            let fields = [
+                flashcard.id.clone(),
+                tsv_field(&flashcard.question),
+                tsv_field(&flashcard.answer),
+                tsv_field(&flashcard.note).replace(SPACE, FLASHCARD_TAG_SPACE),
+            ];
+            tsv.push_str(&fields.join(&FLASHCARD_FIELD_SEPARATOR.to_string()));
+            tsv.push(NEWLINE);
+        }
+
+        fs::create_dir_all(validated_config.output_folder())?;
+        fs::write(validated_config.output_folder().join(FLASHCARDS_FILE), tsv)?;
+        Ok(())
+    }
+}
+
+/// A field can't hold the tabs and line breaks that separate fields and rows.
+fn tsv_field(text: &str) -> String {
+    text.replace([FLASHCARD_FIELD_SEPARATOR, NEWLINE], &SPACE.to_string())
+}
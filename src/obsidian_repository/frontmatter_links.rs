@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::ObsidianRepository;
+use super::phantom_links;
+use crate::constants::HASH;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::MISSING_TARGET;
+use crate::constants::NOT_A_LINK;
+use crate::constants::NOTE_NAME_NOT_LINKED;
+use crate::markdown_file::MarkdownFile;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink;
+use crate::wikilink::ToWikilink;
+
+/// Why a value of a `frontmatter_link_properties` property is not a working link.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum FrontmatterLinkIssueKind {
+    /// `up: "[[Missing]]"` - the link's target note does not exist.
+    MissingTarget,
+    /// `up: index` where `Index.md` exists - the value names exactly one note but isn't a link.
+    NoteNameNotLinked,
+    /// `up: some text` - the value is neither a link nor the name of a note.
+    NotALink,
+}
+
+impl Display for FrontmatterLinkIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingTarget => f.write_str(MISSING_TARGET),
+            Self::NoteNameNotLinked => f.write_str(NOTE_NAME_NOT_LINKED),
+            Self::NotALink => f.write_str(NOT_A_LINK),
+        }
+    }
+}
+
+/// One `frontmatter_link_properties` value that doesn't link to an existing note. `fix` is the
+/// rewritten value or the vault-relative note to create, when the matching option is on.
+#[derive(Clone, Debug)]
+pub(crate) struct FrontmatterLinkIssue {
+    pub file_path: PathBuf,
+    pub property:  String,
+    pub value:     String,
+    pub kind:      FrontmatterLinkIssueKind,
+    pub fix:       Option<String>,
+}
+
+/// A missing frontmatter link target `persist` creates - empty unless `note_templates` sets a
+/// `link_target` template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LinkTargetNote {
+    pub path:    PathBuf,
+    pub content: String,
+}
+
+impl LinkTargetNote {
+    fn new(validated_config: &ValidatedConfig, note_path: &Path) -> Self {
+        let obsidian_path = validated_config.obsidian_path();
+        let path = obsidian_path.join(note_path);
+        let content = validated_config
+            .link_target_template()
+            .map(|template| {
+                template.render(
+                    &path,
+                    obsidian_path,
+                    validated_config.operational_timezone(),
+                )
+            })
+            .unwrap_or_default();
+        Self { path, content }
+    }
+}
+
+impl ObsidianRepository {
+    /// Checks that every value of the `frontmatter_link_properties` properties links to an
+    /// existing note, recording a `FrontmatterLinkIssue` for each one that doesn't. Runs after
+    /// `canonicalize_wikilink_targets`, which already fixes links whose target differs only in
+    /// case or path from a real note.
+    ///
+    /// With `fix_frontmatter_link_values` on, a plain value naming exactly one note becomes a
+    /// link to it. With `create_missing_link_targets` on, missing targets are queued in
+    /// `link_targets_to_create` for `persist`.
+    pub(crate) fn validate_frontmatter_links(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let properties = validated_config.frontmatter_link_properties();
+        if properties.is_empty() {
+            return Ok(());
+        }
+
+        let note_stems = self.markdown_note_stems();
+        let mut stems_by_lower: HashMap<String, Vec<String>> = HashMap::new();
+        for markdown_file in &self.markdown_files {
+            if let Some(stem) = markdown_file.path.file_stem().and_then(OsStr::to_str) {
+                stems_by_lower
+                    .entry(stem.to_lowercase())
+                    .or_default()
+                    .push(stem.to_string());
+            }
+        }
+
+        let mut issues = Vec::new();
+        let mut link_targets_to_create: Vec<PathBuf> = Vec::new();
+
+        for markdown_file in &mut self.markdown_files {
+            let Some(front_matter) = &markdown_file.front_matter else {
+                continue;
+            };
+
+            let mut file_issues = Vec::new();
+            for property in properties {
+                for value in front_matter.property_strings(property) {
+                    let valid_wikilinks = wikilink::extract_wikilinks(value).valid;
+
+                    if valid_wikilinks.is_empty() {
+                        if value.trim().is_empty() {
+                            continue;
+                        }
+
+                        let note = match stems_by_lower
+                            .get(&value.trim().to_lowercase())
+                            .map(Vec::as_slice)
+                        {
+                            Some([only_note_stem]) => Some(only_note_stem),
+                            _ => None,
+                        };
+
+                        file_issues.push(FrontmatterLinkIssue {
+                            file_path: markdown_file.path.clone(),
+                            property:  property.clone(),
+                            value:     value.to_string(),
+                            kind:      note.map_or(FrontmatterLinkIssueKind::NotALink, |_| {
+                                FrontmatterLinkIssueKind::NoteNameNotLinked
+                            }),
+                            fix:       note
+                                .filter(|_| validated_config.fix_frontmatter_link_values())
+                                .map(ToWikilink::to_wikilink),
+                        });
+                        continue;
+                    }
+
+                    for spanned_wikilink in valid_wikilinks {
+                        let target = &spanned_wikilink.wikilink.target;
+                        if phantom_links::target_resolves(&note_stems, target)
+                            || phantom_links::is_date_target(
+                                target,
+                                validated_config.date_link_format(),
+                            )
+                        {
+                            continue;
+                        }
+
+                        let note_to_create = link_target_note_path(target)
+                            .filter(|_| validated_config.create_missing_link_targets());
+                        if let Some(note_path) = &note_to_create
+                            && !link_targets_to_create.iter().any(|queued| {
+                                queued.to_string_lossy().to_lowercase()
+                                    == note_path.to_string_lossy().to_lowercase()
+                            })
+                        {
+                            link_targets_to_create.push(note_path.clone());
+                        }
+
+                        file_issues.push(FrontmatterLinkIssue {
+                            file_path: markdown_file.path.clone(),
+                            property:  property.clone(),
+                            value:     value.to_string(),
+                            kind:      FrontmatterLinkIssueKind::MissingTarget,
+                            fix:       note_to_create
+                                .map(|note_path| note_path.to_string_lossy().into_owned()),
+                        });
+                    }
+                }
+            }
+
+            apply_frontmatter_link_value_fixes(
+                markdown_file,
+                &file_issues,
+                validated_config.operational_timezone(),
+            )?;
+
+            issues.extend(file_issues);
+        }
+
+        self.frontmatter_link_issues = issues;
+        self.link_targets_to_create = link_targets_to_create
+            .into_iter()
+            .map(|note_path| LinkTargetNote::new(validated_config, &note_path))
+            .collect();
+
+        Ok(())
+    }
+
+    /// Creates a note for each queued missing link target. Existing files are left alone.
+    pub(super) fn create_link_targets(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for link_target in &self.link_targets_to_create {
+            if link_target.path.exists() {
+                continue;
+            }
+            if let Some(parent) = link_target.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&link_target.path)?
+                .write_all(link_target.content.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Rewrites the values `file_issues` found naming a note into links to it.
+fn apply_frontmatter_link_value_fixes(
+    markdown_file: &mut MarkdownFile,
+    file_issues: &[FrontmatterLinkIssue],
+    operational_timezone: &str,
+) -> AnyhowResult<()> {
+    let Some(front_matter) = markdown_file.front_matter.as_mut() else {
+        return Ok(());
+    };
+
+    let mut fixed_count = 0;
+    for issue in file_issues {
+        if let (FrontmatterLinkIssueKind::NoteNameNotLinked, Some(fix)) = (issue.kind, &issue.fix) {
+            fixed_count += front_matter.rewrite_property_strings(&issue.property, |value| {
+                (value == issue.value).then(|| fix.clone())
+            });
+        }
+    }
+
+    if fixed_count > 0 {
+        markdown_file.mark_frontmatter_link_values_fixed(fixed_count, operational_timezone)?;
+    }
+    Ok(())
+}
+
+/// Vault-relative path of the note a missing link `target` names. Heading suffixes are dropped;
+/// targets that would leave the vault return `None`.
+fn link_target_note_path(target: &str) -> Option<PathBuf> {
+    let without_heading = target.split(HASH).next().unwrap_or(target).trim();
+    let without_suffix = without_heading
+        .strip_suffix(MARKDOWN_SUFFIX)
+        .unwrap_or(without_heading);
+    if without_suffix.is_empty() {
+        return None;
+    }
+
+    let note_path = PathBuf::from(format!("{without_suffix}{MARKDOWN_SUFFIX}"));
+    note_path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+        .then_some(note_path)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+
+    use super::FrontmatterLinkIssueKind;
+    use super::LinkTargetNote;
+    use super::link_target_note_path;
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_link_target_note_path() {
+        assert_eq!(
+            link_target_note_path("topics/Missing#Heading"),
+            Some(PathBuf::from("topics/Missing.md"))
+        );
+        assert_eq!(link_target_note_path("../outside"), None);
+        assert_eq!(link_target_note_path("#Heading"), None);
+    }
+
+    #[test]
+    fn test_validate_frontmatter_links() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("index")
+            .create(&temp_dir, "Index.md");
+        TestFileBuilder::new()
+            .with_custom_frontmatter(
+                "up: index\nrelated:\n  - \"[[Missing]]\"\n  - \"[[Index]]\"\n  - plain text\n"
+                    .to_string(),
+            )
+            .with_content("body")
+            .create(&temp_dir, "note.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.frontmatter_link_properties(vec!["up".to_string(), "related".to_string()]);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let kinds: Vec<FrontmatterLinkIssueKind> = obsidian_repository
+            .frontmatter_link_issues
+            .iter()
+            .map(|issue| issue.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FrontmatterLinkIssueKind::NoteNameNotLinked,
+                FrontmatterLinkIssueKind::MissingTarget,
+                FrontmatterLinkIssueKind::NotALink,
+            ]
+        );
+        assert!(
+            obsidian_repository
+                .frontmatter_link_issues
+                .iter()
+                .all(|issue| issue.fix.is_none()),
+            "fixes are opt-in"
+        );
+        assert!(obsidian_repository.link_targets_to_create.is_empty());
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder
+                    .frontmatter_link_properties(vec!["up".to_string(), "related".to_string()])
+                    .fix_frontmatter_link_values(true)
+                    .create_missing_link_targets(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let note = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path.ends_with("note.md"))
+            .unwrap();
+        assert_eq!(
+            note.front_matter.as_ref().unwrap().property_strings("up"),
+            vec!["[[Index]]"]
+        );
+        assert!(
+            note.persist_reasons
+                .contains(&PersistReason::FrontmatterLinkValuesFixed { count: 1 })
+        );
+        assert_eq!(
+            obsidian_repository.link_targets_to_create,
+            vec![LinkTargetNote {
+                path:    temp_dir.path().join("Missing.md"),
+                content: String::new(),
+            }]
+        );
+
+        obsidian_repository.create_link_targets().unwrap();
+        assert!(temp_dir.path().join("Missing.md").exists());
+    }
+}
@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::ObsidianRepository;
+use super::conflict_copies::ConflictCopyAction;
+use crate::image_file::DeletionStatus;
+use crate::markdown_file::PersistReason;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `frontmatter_only` set, puts back the body of every note a pass rewrote and keeps
+    /// only its frontmatter changes. The rewrites are still found and reported, so the run
+    /// shows what it would change. Notes left with nothing to write are dropped, and so is
+    /// what depended on the rewrites: the notes split out of a body, the conflict copies
+    /// merged into one and the images whose references were moved. Runs after every pass that
+    /// changes a body and before the audit trail records the changes.
+    pub(crate) fn hold_body_changes(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        if !validated_config.frontmatter_only() {
+            return Ok(());
+        }
+
+        let mut held: Vec<PathBuf> = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            if !markdown_file
+                .persist_reasons
+                .iter()
+                .any(PersistReason::changes_body)
+            {
+                continue;
+            }
+
+            markdown_file
+                .restore_body()
+                .map_err(|error| anyhow!("{}: {error}", markdown_file.path.display()))?;
+            markdown_file
+                .persist_reasons
+                .retain(|reason| !reason.changes_body());
+            // Frontmatter created only to date a body change isn't worth writing on its own.
+            if markdown_file
+                .persist_reasons
+                .iter()
+                .all(|reason| *reason == PersistReason::FrontmatterCreated)
+            {
+                markdown_file.persist_reasons.clear();
+                if let Some(front_matter) = markdown_file.front_matter.as_mut() {
+                    front_matter.discard_changes();
+                }
+            }
+            held.push(markdown_file.path.clone());
+        }
+
+        self.release_restored_bodies(&held);
+        Ok(())
+    }
+
+    /// Drops what depended on the body rewrites of `restored` notes: the notes split out of
+    /// them, the conflict copies merged into them and the images whose references moved.
+    pub(super) fn release_restored_bodies(&mut self, restored: &[PathBuf]) {
+        self.section_splits
+            .retain(|split| !restored.contains(&split.source));
+        for conflict_copy in &mut self.conflict_copies {
+            if conflict_copy.action == ConflictCopyAction::Merge
+                && restored.contains(&conflict_copy.original)
+            {
+                conflict_copy.action = ConflictCopyAction::Keep;
+            }
+        }
+        for image_file in &mut self.image_files.images {
+            if image_file
+                .references
+                .iter()
+                .any(|reference| restored.contains(reference))
+            {
+                image_file.deletion_status = DeletionStatus::Keep;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_hold_body_changes_keeps_frontmatter_fixes() {
+        let temp_dir = TempDir::new().unwrap();
+        let date = test_support::eastern_midnight(2024, 1, 1);
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "Alpha.md");
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_frontmatter_dates(None, Some("[[2024-01-01]]".to_string()))
+            .with_content("Mentions Alpha here".to_string())
+            .create(&temp_dir, "Undated.md");
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_content("Alpha again".to_string())
+            .create(&temp_dir, "Dated.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.frontmatter_only(true);
+            })
+            .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let note = |name: &str| {
+            repository
+                .markdown_files
+                .iter()
+                .find(|markdown_file| markdown_file.path.ends_with(name))
+                .unwrap()
+        };
+
+        let undated = note("Undated.md");
+        assert_eq!(undated.content.trim(), "Mentions Alpha here");
+        assert!(!undated.back_populate_matches.unambiguous.is_empty());
+        assert!(
+            !undated
+                .persist_reasons
+                .contains(&PersistReason::BackPopulated)
+        );
+        assert!(undated.front_matter.as_ref().unwrap().needs_persist());
+
+        let dated = note("Dated.md");
+        assert_eq!(dated.content.trim(), "Alpha again");
+        assert!(
+            !dated
+                .persist_reasons
+                .iter()
+                .any(PersistReason::changes_body)
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+
+use super::ObsidianRepository;
+use super::phantom_links;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `repair_heading_links` on, records a `HeadingLinkRepairMatch` for every link to a
+    /// heading renamed since, and a `StaleHeadingLink` for one that can't be matched to a
+    /// current heading. Links to a note name more than one note shares are skipped, as are
+    /// links another fix already rewrites.
+    pub(crate) fn find_all_heading_link_repairs(&mut self, validated_config: &ValidatedConfig) {
+        if !validated_config.repair_heading_links() {
+            return;
+        }
+
+        let mut headings_by_stem: HashMap<String, Option<Vec<String>>> = HashMap::new();
+        for markdown_file in &self.markdown_files {
+            let Some(stem) = markdown_file.path.file_stem().and_then(OsStr::to_str) else {
+                continue;
+            };
+            headings_by_stem
+                .entry(stem.to_lowercase())
+                .and_modify(|headings| *headings = None)
+                .or_insert_with(|| Some(markdown_file.heading_link_targets()));
+        }
+
+        let fix_self_links = validated_config.fix_self_links();
+        let mut stale_heading_links = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            let own_stem = markdown_file
+                .path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default()
+                .to_lowercase();
+            let (mut repairs, stale) = markdown_file.find_heading_link_repairs(
+                |note| {
+                    let stem = if note.is_empty() {
+                        own_stem.clone()
+                    } else {
+                        phantom_links::target_note_stem(note)
+                    };
+                    headings_by_stem.get(&stem).and_then(Option::as_deref)
+                },
+                validated_config,
+            );
+            stale_heading_links.extend(stale);
+            if repairs.is_empty() {
+                continue;
+            }
+
+            let (other_matches, _) =
+                Self::collect_replaceable_matches(markdown_file, fix_self_links);
+            repairs.retain(|repair| {
+                let end = repair.position + repair.found_text.len();
+                !other_matches.iter().any(|other| {
+                    other.line_number() == repair.line_number
+                        && repair.position < other.position() + other.matched_text().len()
+                        && other.position() < end
+                })
+            });
+
+            markdown_file.heading_link_repair_matches = repairs;
+        }
+
+        self.stale_heading_links = stale_heading_links;
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_heading_links_repaired_and_stale_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("# Trip\n## Day 1: Paris\n## Packing List\n## Notes A\n## Notes B")
+            .create(&temp_dir, "Trip.md");
+        let file_path = TestFileBuilder::new()
+            .with_content(
+                "## Plans\n\
+                 see [[Trip#Day 1 - Paris|day one]], [[Trip#packing lists]] and \
+                 [[#plans]]\n\
+                 not [[Trip#Itinerary]], [[Trip#Notes C]], [[Trip#^abc123]] or [[Gone#Heading]]",
+            )
+            .create(&temp_dir, "notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.repair_heading_links(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let notes = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path == file_path)
+            .unwrap();
+
+        assert_eq!(
+            notes.content,
+            "## Plans\n\
+             see [[Trip#Day 1 Paris|day one]], [[Trip#Packing List]] and [[#plans]]\n\
+             not [[Trip#Itinerary]], [[Trip#Notes C]], [[Trip#^abc123]] or [[Gone#Heading]]"
+        );
+        assert!(
+            notes
+                .persist_reasons
+                .contains(&PersistReason::HeadingLinksRepaired)
+        );
+
+        let stale: Vec<(&str, usize)> = obsidian_repository
+            .stale_heading_links
+            .iter()
+            .map(|link| (link.found_text.as_str(), link.candidates.len()))
+            .collect();
+        assert_eq!(
+            stale,
+            vec![("[[Trip#Itinerary]]", 0), ("[[Trip#Notes C]]", 2)],
+            "block references and links to missing notes are left alone"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::mem;
+
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::ObsidianRepository;
+use crate::markdown_file::PersistReason;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// Holds every note with a change `hold_persist_reasons` names. A held change can't be
+    /// left out of a write that goes ahead for another - body changes share the body, and each
+    /// change stamps `date_modified` - so the whole note is left as it is on disk and all its
+    /// changes are reported as held. Date repairs are held earlier, when the notes are read.
+    /// Runs after `hold_body_changes`.
+    pub(crate) fn hold_persist_reasons(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let held_reasons = validated_config.hold_persist_reasons();
+        if held_reasons.is_empty() {
+            return Ok(());
+        }
+        let is_held = |reason: &PersistReason| held_reasons.contains(&reason.kind());
+
+        let mut restored = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            if !markdown_file.persist_reasons.iter().any(is_held) {
+                continue;
+            }
+
+            if markdown_file
+                .persist_reasons
+                .iter()
+                .any(PersistReason::changes_body)
+            {
+                markdown_file
+                    .restore_body()
+                    .map_err(|error| anyhow!("{}: {error}", markdown_file.path.display()))?;
+                restored.push(markdown_file.path.clone());
+            }
+            // Frontmatter created only to date a change isn't a change of its own.
+            let held = mem::take(&mut markdown_file.persist_reasons)
+                .into_iter()
+                .filter(|reason| *reason != PersistReason::FrontmatterCreated);
+            markdown_file.held_persist_reasons.extend(held);
+            if let Some(front_matter) = markdown_file.front_matter.as_mut() {
+                front_matter.discard_changes();
+            }
+        }
+
+        self.release_restored_bodies(&restored);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use crate::config::PersistReasonKind;
+    use crate::config::ReplacementScope;
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::RegexReplacementRule;
+
+    #[test]
+    fn test_held_reasons_are_found_but_not_made() {
+        let temp_dir = TempDir::new().unwrap();
+        let date = test_support::eastern_midnight(2024, 1, 1);
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "Alpha.md");
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_frontmatter_dates(Some("[[2024-01-01]]".to_string()), None)
+            .with_content("Mentions Alpha here".to_string())
+            .create(&temp_dir, "Notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.hold_persist_reasons(vec![
+                    PersistReasonKind::BackPopulated,
+                    PersistReasonKind::DateModifiedUpdated,
+                ]);
+            })
+            .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let notes = repository
+            .markdown_files
+            .iter()
+            .find(|markdown_file| markdown_file.path.ends_with("Notes.md"))
+            .unwrap();
+
+        assert_eq!(notes.content.trim(), "Mentions Alpha here");
+        assert!(
+            notes
+                .held_persist_reasons
+                .contains(&PersistReason::BackPopulated)
+        );
+        assert!(
+            notes
+                .held_persist_reasons
+                .iter()
+                .any(|reason| matches!(reason, PersistReason::DateModifiedUpdated { .. }))
+        );
+        assert!(notes.persist_reasons.iter().all(|reason| !matches!(
+            reason.kind(),
+            PersistReasonKind::BackPopulated | PersistReasonKind::DateModifiedUpdated
+        )));
+    }
+
+    #[test]
+    fn test_held_reason_sharing_a_write_holds_the_note() {
+        let temp_dir = TempDir::new().unwrap();
+        let date = test_support::eastern_midnight(2024, 1, 1);
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "Alpha.md");
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_content("Mentions Alpha -- and more".to_string())
+            .create(&temp_dir, "Body.md");
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_date_created_fix(Some("2023-06-01".to_string()))
+            .with_content("Mentions Alpha".to_string())
+            .create(&temp_dir, "Dated.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder
+                    .hold_persist_reasons(vec![PersistReasonKind::BackPopulated])
+                    .replacement_rules(vec![RegexReplacementRule {
+                        regex:       Regex::new("-{2,3}").unwrap(),
+                        replacement: "\u{2014}".to_string(),
+                        scope:       ReplacementScope::Text,
+                    }]);
+            })
+            .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let files_to_persist = repository.markdown_files.files_to_persist();
+
+        for (name, content, kept_kind) in [
+            (
+                "Body.md",
+                "Mentions Alpha -- and more",
+                PersistReasonKind::RegexReplacementsApplied,
+            ),
+            (
+                "Dated.md",
+                "Mentions Alpha",
+                PersistReasonKind::DateCreatedFixApplied,
+            ),
+        ] {
+            let note = repository
+                .markdown_files
+                .iter()
+                .find(|markdown_file| markdown_file.path.ends_with(name))
+                .unwrap();
+            assert_eq!(note.content.trim(), content, "{name}");
+            assert!(note.persist_reasons.is_empty(), "{name}");
+            assert!(
+                note.held_persist_reasons
+                    .contains(&PersistReason::BackPopulated),
+                "{name}"
+            );
+            assert!(
+                note.held_persist_reasons
+                    .iter()
+                    .any(|reason| reason.kind() == kept_kind),
+                "{name}: the change sharing the write is held too"
+            );
+            assert!(
+                !files_to_persist
+                    .iter()
+                    .any(|markdown_file| markdown_file.path.ends_with(name)),
+                "{name} isn't written"
+            );
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use super::ObsidianRepository;
+use super::constants::CURRENT_WORKSPACE_FILES;
+use super::constants::SECONDS_PER_DAY;
+use super::constants::WORKSPACE_FILE_PREFIX;
+use crate::constants::OBSIDIAN_FOLDER;
+use crate::constants::PLUGIN_FOLDERS;
+use crate::constants::PLUGINS_FOLDER;
+use crate::constants::STALE_WORKSPACE_FILE;
+use crate::constants::TRASH;
+use crate::constants::TRASH_FOLDER;
+use crate::constants::WILL_PURGE;
+use crate::validated_config::ValidatedConfig;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HiddenFolderItemKind {
+    /// `.obsidian/plugins` - `count` is the number of plugin folders.
+    PluginFolders,
+    /// A workspace file Obsidian no longer reads - an old layout or a sync conflict copy.
+    StaleWorkspaceFile,
+    /// `.trash` as a whole.
+    Trash,
+    /// A deleted note or folder in `.trash` older than `purge_trash_after_days`.
+    TrashToPurge,
+}
+
+impl Display for HiddenFolderItemKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PluginFolders => f.write_str(PLUGIN_FOLDERS),
+            Self::StaleWorkspaceFile => f.write_str(STALE_WORKSPACE_FILE),
+            Self::Trash => f.write_str(TRASH),
+            Self::TrashToPurge => f.write_str(WILL_PURGE),
+        }
+    }
+}
+
+/// What `.trash` or `.obsidian` holds. `count` is files, except for plugin folders.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HiddenFolderItem {
+    pub path:  PathBuf,
+    pub kind:  HiddenFolderItemKind,
+    pub count: usize,
+    pub size:  u64,
+}
+
+/// File count, total size and newest modification time of everything under `path`.
+fn folder_stats(path: &Path) -> (usize, u64, Option<SystemTime>) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return (0, 0, None);
+    };
+    if !metadata.is_dir() {
+        return (1, metadata.len(), metadata.modified().ok());
+    }
+
+    fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| folder_stats(&entry.path()))
+        .fold(
+            (0, 0, None),
+            |(count, size, newest), (entry_count, entry_size, entry_newest)| {
+                (
+                    count + entry_count,
+                    size + entry_size,
+                    newest.max(entry_newest),
+                )
+            },
+        )
+}
+
+fn child_paths(path: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn is_stale_workspace_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_some_and(|name| {
+                name.starts_with(WORKSPACE_FILE_PREFIX) && !CURRENT_WORKSPACE_FILES.contains(&name)
+            })
+}
+
+impl ObsidianRepository {
+    /// Sizes up `.trash` and counts the plugin folders and stale workspace files in `.obsidian`.
+    /// With `purge_trash_after_days` set, each top-level trash item whose newest file is older
+    /// than that is listed for `persist` to delete.
+    pub(super) fn inspect_hidden_folders(
+        validated_config: &ValidatedConfig,
+    ) -> Vec<HiddenFolderItem> {
+        let obsidian_path = validated_config.obsidian_path();
+        let mut items = Vec::new();
+
+        let trash_path = obsidian_path.join(TRASH_FOLDER);
+        if trash_path.is_dir() {
+            let (count, size, _) = folder_stats(&trash_path);
+            items.push(HiddenFolderItem {
+                path: trash_path.clone(),
+                kind: HiddenFolderItemKind::Trash,
+                count,
+                size,
+            });
+
+            if let Some(days) = validated_config.purge_trash_after_days() {
+                let cutoff = SystemTime::now()
+                    .checked_sub(Duration::from_secs(u64::from(days) * SECONDS_PER_DAY));
+                for path in child_paths(&trash_path) {
+                    let (count, size, newest) = folder_stats(&path);
+                    if newest
+                        .zip(cutoff)
+                        .is_some_and(|(newest, cutoff)| newest < cutoff)
+                    {
+                        items.push(HiddenFolderItem {
+                            path,
+                            kind: HiddenFolderItemKind::TrashToPurge,
+                            count,
+                            size,
+                        });
+                    }
+                }
+            }
+        }
+
+        let settings_path = obsidian_path.join(OBSIDIAN_FOLDER);
+        let plugin_folders: Vec<PathBuf> = child_paths(&settings_path.join(PLUGINS_FOLDER))
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .collect();
+        if !plugin_folders.is_empty() {
+            items.push(HiddenFolderItem {
+                path:  settings_path.join(PLUGINS_FOLDER),
+                kind:  HiddenFolderItemKind::PluginFolders,
+                count: plugin_folders.len(),
+                size:  plugin_folders.iter().map(|path| folder_stats(path).1).sum(),
+            });
+        }
+
+        items.extend(
+            child_paths(&settings_path)
+                .into_iter()
+                .filter(|path| is_stale_workspace_file(path))
+                .map(|path| {
+                    let (count, size, _) = folder_stats(&path);
+                    HiddenFolderItem {
+                        path,
+                        kind: HiddenFolderItemKind::StaleWorkspaceFile,
+                        count,
+                        size,
+                    }
+                }),
+        );
+
+        items
+    }
+
+    /// Deletes the trash items `inspect_hidden_folders` listed for purging.
+    pub(super) fn purge_trash(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for item in &self.hidden_folder_items {
+            if item.kind != HiddenFolderItemKind::TrashToPurge {
+                continue;
+            }
+            if item.path.is_dir() {
+                fs::remove_dir_all(&item.path)?;
+            } else if item.path.exists() {
+                fs::remove_file(&item.path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+    use std::time::SystemTime;
+
+    use filetime::FileTime;
+    use tempfile::TempDir;
+
+    use super::HiddenFolderItemKind;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+
+    #[test]
+    fn test_hidden_folders_inspected_and_old_trash_purged() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = temp_dir.path().join(".trash");
+        fs::create_dir_all(trash.join("old folder")).unwrap();
+        fs::write(trash.join("old folder").join("a.txt"), "12345").unwrap();
+        fs::write(trash.join("recent.txt"), "123").unwrap();
+        let long_ago =
+            FileTime::from_system_time(SystemTime::now() - Duration::from_hours(24 * 40));
+        filetime::set_file_mtime(trash.join("old folder").join("a.txt"), long_ago).unwrap();
+
+        let settings = temp_dir.path().join(".obsidian");
+        fs::create_dir_all(settings.join("plugins").join("dataview")).unwrap();
+        fs::create_dir_all(settings.join("plugins").join("calendar")).unwrap();
+        fs::write(
+            settings.join("plugins").join("dataview").join("main.js"),
+            "1",
+        )
+        .unwrap();
+        fs::write(settings.join("workspace.json"), "{}").unwrap();
+        fs::write(settings.join("workspace"), "{}").unwrap();
+        fs::write(settings.join("workspace 2.json"), "{}").unwrap();
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.purge_trash_after_days(Some(30));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let summary: Vec<(String, HiddenFolderItemKind, usize, u64)> = obsidian_repository
+            .hidden_folder_items
+            .iter()
+            .map(|item| {
+                (
+                    item.path.file_name().unwrap().to_string_lossy().to_string(),
+                    item.kind,
+                    item.count,
+                    item.size,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (".trash".to_string(), HiddenFolderItemKind::Trash, 2, 8),
+                (
+                    "old folder".to_string(),
+                    HiddenFolderItemKind::TrashToPurge,
+                    1,
+                    5
+                ),
+                (
+                    "plugins".to_string(),
+                    HiddenFolderItemKind::PluginFolders,
+                    2,
+                    1
+                ),
+                (
+                    "workspace".to_string(),
+                    HiddenFolderItemKind::StaleWorkspaceFile,
+                    1,
+                    2
+                ),
+                (
+                    "workspace 2.json".to_string(),
+                    HiddenFolderItemKind::StaleWorkspaceFile,
+                    1,
+                    2
+                ),
+            ]
+        );
+
+        obsidian_repository.persist().unwrap();
+        assert!(!trash.join("old folder").exists());
+        assert!(trash.join("recent.txt").exists());
+    }
+}
@@ -1,13 +1,16 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::error::Error;
+use std::fs;
+use std::path::Path;
 use std::path::PathBuf;
 
 use super::ObsidianRepository;
 use super::constants::DUPLICATE_KEEPER_INDEX;
 use super::constants::MIN_DUPLICATE_GROUP_SIZE;
-use crate::constants::CACHE_FILE;
-use crate::constants::CACHE_FOLDER;
+use super::phantom_links;
+use crate::constants::CACHE_CORRUPTED_REBUILT;
+use crate::constants::CACHE_OUTDATED_REBUILT;
 use crate::image_file::DeletionStatus;
 use crate::image_file::ImageFile;
 use crate::image_file::ImageFileState;
@@ -15,10 +18,32 @@ use crate::image_file::ImageFiles;
 use crate::image_file::ImageHash;
 use crate::image_file::ImageRole;
 use crate::markdown_file::ImageLinkState;
+use crate::markdown_file::MarkdownFile;
+use crate::markdown_file::MatchType;
+use crate::sha256_cache;
+use crate::sha256_cache::CacheFileStatus;
 use crate::sha256_cache::Sha256Cache;
+use crate::support;
 use crate::support::VecEnumFilter;
 use crate::validated_config::ValidatedConfig;
 
+/// What images are grouped by to find duplicates.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum ImageGroupKey {
+    Hash(ImageHash),
+    /// An image no other image shares a size with - it can't be a duplicate, so it isn't hashed.
+    UniqueSize(PathBuf),
+}
+
+impl ImageGroupKey {
+    fn into_image_hash(self) -> Option<ImageHash> {
+        match self {
+            Self::Hash(image_hash) => Some(image_hash),
+            Self::UniqueSize(_) => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum KeeperSelection {
     None,
@@ -80,15 +105,16 @@ impl ObsidianRepository {
         // `markdown_references` maps each `MarkdownFile.path` to referenced image filenames.
         let markdown_references = self.get_markdown_file_image_reference_map();
 
-        // `hash_groups` groups `image_files` by `ImageHash` and markdown references.
+        // `hash_groups` groups `image_files` by `ImageGroupKey` and markdown references.
         let hash_groups = Self::get_image_hash_to_markdown_references_map(
             &mut sha256_cache,
             image_files,
             &markdown_references,
+            validated_config.hash_all_images(),
         );
 
         // `images` stores `ImageFile` states chosen from `DuplicateGroupRole`.
-        let images = Self::generate_image_files(hash_groups)?;
+        let images = Self::generate_image_files(hash_groups, validated_config)?;
 
         // `Sha256Cache::save` persists entries when `Sha256Cache::has_changes` is true.
         if sha256_cache.has_changes() {
@@ -101,18 +127,28 @@ impl ObsidianRepository {
     // `DuplicateGroupRole` selects `ImageFileState::DuplicateKeeper` for the
     // first referenced path and `ImageFileState::Duplicate` for the remaining paths.
     fn generate_image_files(
-        hash_groups: HashMap<ImageHash, Vec<(PathBuf, Vec<String>)>>,
+        hash_groups: HashMap<ImageGroupKey, Vec<(PathBuf, Vec<String>)>>,
+        validated_config: &ValidatedConfig,
     ) -> Result<Vec<ImageFile>, Box<dyn Error + Send + Sync>> {
         let mut images = Vec::new();
 
-        for (image_hash, mut group) in hash_groups {
+        for (group_key, mut group) in hash_groups {
+            let image_hash = group_key.into_image_hash();
             let duplicate_group_role = DuplicateGroupRole::from(group.as_slice());
 
             if matches!(
                 duplicate_group_role,
                 DuplicateGroupRole::Duplicate { keeper_selection } if keeper_selection.should_sort()
             ) {
-                group.sort_by(|a, b| a.0.cmp(&b.0));
+                // A copy already in the vault's attachment folder is kept, so the references
+                // rewritten to it point where Obsidian puts attachments - otherwise the first by
+                // path.
+                group.sort_by_cached_key(|(path, references)| {
+                    (
+                        !in_attachment_folder(path, references, validated_config),
+                        path.clone(),
+                    )
+                });
             }
 
             for (idx, (path, references)) in group.into_iter().enumerate() {
@@ -129,20 +165,34 @@ impl ObsidianRepository {
             }
         }
 
+        // `hash_groups` iterates in random order; path order keeps reports reproducible.
+        images.sort_by(|a, b| a.path.cmp(&b.path));
+
         Ok(images)
     }
 
-    // `HashMap<ImageHash, Vec<(PathBuf, Vec<String>)>>` is keyed by `ImageHash`.
+    // `HashMap<ImageGroupKey, Vec<(PathBuf, Vec<String>)>>` is keyed by `ImageGroupKey`. Only
+    // duplicate candidates are hashed unless `hash_all_images` is set.
     fn get_image_hash_to_markdown_references_map(
         sha256_cache: &mut Sha256Cache,
         image_files: &[PathBuf],
         markdown_references: &HashMap<String, HashSet<String>>,
-    ) -> HashMap<ImageHash, Vec<(PathBuf, Vec<String>)>> {
+        hash_all_images: bool,
+    ) -> HashMap<ImageGroupKey, Vec<(PathBuf, Vec<String>)>> {
+        let candidates = (!hash_all_images).then(|| duplicate_candidates(image_files));
         image_files
             .iter()
             .filter_map(|image_path| {
-                // `ok()?` converts `Sha256Cache::get_or_update` into an optional `ImageHash`.
-                let (image_hash, _) = sha256_cache.get_or_update(image_path).ok()?; // `ImageHash`
+                let group_key = if candidates
+                    .as_ref()
+                    .is_none_or(|candidates| candidates.contains(image_path.as_path()))
+                {
+                    // `ok()?` converts `Sha256Cache::get_or_update` into an optional `ImageHash`.
+                    let (image_hash, _) = sha256_cache.get_or_update(image_path).ok()?;
+                    ImageGroupKey::Hash(image_hash)
+                } else {
+                    ImageGroupKey::UniqueSize(image_path.clone())
+                };
                 let image_name = image_path.file_name()?.to_str()?.to_lowercase();
 
                 let references = markdown_references
@@ -156,16 +206,37 @@ impl ObsidianRepository {
                     })
                     .collect::<Vec<_>>();
 
-                Some((image_hash, (image_path.clone(), references))) // Keyed by `ImageHash`
+                Some((group_key, (image_path.clone(), references)))
             })
-            .fold(HashMap::new(), |mut accumulator, (image_hash, entry)| {
-                accumulator.entry(image_hash).or_default().push(entry); // Use `ImageHash` as the key
+            .fold(HashMap::new(), |mut accumulator, (group_key, entry)| {
+                accumulator.entry(group_key).or_default().push(entry);
                 accumulator
             })
     }
 
     // Map of `markdown_file` paths to the image file names referenced on that `markdown_file`.
+    // Bases and canvases count too, so an image only a canvas shows is never deleted as
+    // unreferenced - and as ok can't rewrite them, never deleted as a duplicate either.
     fn get_markdown_file_image_reference_map(&self) -> HashMap<String, HashSet<String>> {
+        let structured_file_references = self.structured_files.iter().map(|structured_file| {
+            let image_file_names: HashSet<_> = structured_file
+                .file_references
+                .iter()
+                .filter_map(|file_reference| file_reference.path.file_name())
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .chain(
+                    structured_file
+                        .wikilink_targets
+                        .iter()
+                        .map(|target| phantom_links::target_note_stem(target)),
+                )
+                .collect();
+            (
+                structured_file.path.to_string_lossy().to_string(),
+                image_file_names,
+            )
+        });
+
         self.markdown_files
             .iter()
             .filter(|file| !file.image_links.is_empty())
@@ -178,6 +249,7 @@ impl ObsidianRepository {
                     .collect();
                 (markdown_file_path, image_file_names)
             })
+            .chain(structured_file_references.filter(|(_, names)| !names.is_empty()))
             .collect::<HashMap<_, _>>()
     }
 
@@ -185,29 +257,65 @@ impl ObsidianRepository {
         validated_config: &ValidatedConfig,
         image_files: &[PathBuf],
     ) -> Sha256Cache {
-        let file_path = validated_config
-            .obsidian_path()
-            .join(CACHE_FOLDER)
-            .join(CACHE_FILE);
+        let file_path = sha256_cache::cache_file_path(&validated_config.state_folder());
         let valid_paths: HashSet<_> = image_files.iter().map(PathBuf::as_path).collect();
 
-        let mut sha256_cache = Sha256Cache::load_or_create(file_path).0;
+        let (mut sha256_cache, status) = Sha256Cache::load_or_create(file_path.clone());
+        match status {
+            CacheFileStatus::Corrupted => {
+                eprintln!("{CACHE_CORRUPTED_REBUILT} {}", file_path.display());
+            },
+            CacheFileStatus::Outdated => {
+                eprintln!("{CACHE_OUTDATED_REBUILT} {}", file_path.display());
+            },
+            CacheFileStatus::Read | CacheFileStatus::Created => {},
+        }
         sha256_cache.mark_deletions(&valid_paths);
         sha256_cache
     }
 
-    pub(super) fn identify_image_reference_replacements(&mut self) {
+    /// Gives each image link the size of the file it points at, so notes can be weighed by
+    /// their attachments.
+    pub(super) fn attach_image_file_sizes(&mut self) {
+        let sizes: HashMap<String, u64> = self
+            .image_files
+            .iter()
+            .filter_map(|image_file| {
+                let name = image_file
+                    .path
+                    .file_name()?
+                    .to_string_lossy()
+                    .to_lowercase();
+                Some((name, image_file.size))
+            })
+            .collect();
+
+        for markdown_file in &mut self.markdown_files {
+            for link in markdown_file.image_links.iter_mut() {
+                link.file_size = sizes.get(&link.filename.to_lowercase()).copied();
+            }
+        }
+    }
+
+    pub(super) fn identify_image_reference_replacements(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) {
         // Missing filenames assign `ImageLinkState::Missing`.
-        let image_filenames: HashSet<String> = self
+        let mut image_filename_counts: HashMap<String, usize> = HashMap::new();
+        for name in self
             .image_files
             .iter()
             .filter_map(|image_file| image_file.path.file_name())
-            .map(|name| name.to_string_lossy().to_lowercase())
-            .collect();
+        {
+            *image_filename_counts
+                .entry(name.to_string_lossy().to_lowercase())
+                .or_default() += 1;
+        }
 
         for markdown_file in &mut self.markdown_files {
             for link in markdown_file.image_links.iter_mut() {
-                if !image_filenames.contains(&link.filename.to_lowercase()) {
+                if !image_filename_counts.contains_key(&link.filename.to_lowercase()) {
                     link.state = ImageLinkState::Missing;
                 }
             }
@@ -263,13 +371,28 @@ impl ObsidianRepository {
                     matches!(&k.state, ImageFileState::DuplicateKeeper { image_hash: keeper_image_hash } if keeper_image_hash == image_hash)
                 })
             {
-                // Duplicate `ImageLink` entries store `ImageLinkState::Duplicate`.
+                // Duplicate `ImageLink` entries store `ImageLinkState::Duplicate`. A link
+                // naming only the file still does while the keeper's name is unique in the
+                // vault - otherwise it gets the folder the keeper is in.
+                let keeper_name_is_unique = keeper
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_lowercase())
+                    .and_then(|name| image_filename_counts.get(&name))
+                    == Some(&1);
+                let keeper_folder = support::format_relative_path(
+                    keeper.path.parent().unwrap_or(&keeper.path),
+                    validated_config.obsidian_path(),
+                );
                 for markdown_file in &mut self.markdown_files {
                     if let Some(image_link) = markdown_file
                         .image_links
                         .iter_mut()
                         .find(|link| link.filename == duplicate_file_name)
                     {
+                        if !image_link.relative_path.is_empty() || !keeper_name_is_unique {
+                            image_link.relative_path.clone_from(&keeper_folder);
+                        }
                         image_link.state = ImageLinkState::Duplicate {
                             keeper_path: keeper.path.clone(),
                         };
@@ -277,6 +400,19 @@ impl ObsidianRepository {
                 }
             }
         }
+
+        // References on lines marked to be left alone stay as they are.
+        for markdown_file in &mut self.markdown_files {
+            let ignored_lines = markdown_file.ignored_lines();
+            if ignored_lines.is_empty() {
+                continue;
+            }
+            for link in markdown_file.image_links.iter_mut() {
+                if ignored_lines.contains(&link.line_number) {
+                    link.state = ImageLinkState::Found;
+                }
+            }
+        }
     }
 
     pub(super) fn mark_image_files_for_deletion(&mut self) {
@@ -292,7 +428,34 @@ impl ObsidianRepository {
 
         let files_to_persist: HashSet<_> = files_to_persist.iter().map(|f| &f.path).collect();
 
+        // An image a line marked to be left alone, or a reference skipped for overlapping
+        // another replacement, still names has to stay.
+        let held_texts: Vec<String> = self
+            .markdown_files
+            .iter()
+            .flat_map(MarkdownFile::ignored_line_texts)
+            .chain(
+                self.replacement_conflicts
+                    .iter()
+                    .filter(|conflict| conflict.skipped == MatchType::ImageReference)
+                    .map(|conflict| conflict.found_text.as_str()),
+            )
+            .map(str::to_lowercase)
+            .collect();
+        let is_held = |image_file: &ImageFile| {
+            let file_name = image_file
+                .path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_lowercase();
+            held_texts.iter().any(|text| text.contains(&file_name))
+        };
+
         for image_file in &mut self.image_files.images {
+            if !held_texts.is_empty() && is_held(image_file) {
+                continue;
+            }
             match &image_file.state {
                 ImageFileState::Unreferenced => {
                     image_file.deletion_status = DeletionStatus::Delete;
@@ -314,6 +477,43 @@ impl ObsidianRepository {
     }
 }
 
+/// Images that share their size with another image - only these can be duplicates, so only these
+/// need hashing. An image whose size can't be read is a candidate too, so a file that can't be
+/// read is still skipped when its hash fails.
+/// Whether `image_path` is in the attachment folder of the vault, or - for an attachment
+/// folder beside each note - of one of the `references` linking to it.
+fn in_attachment_folder(
+    image_path: &Path,
+    references: &[String],
+    validated_config: &ValidatedConfig,
+) -> bool {
+    let obsidian_path = validated_config.obsidian_path();
+    let folder_of =
+        |path: &Path| support::format_relative_path(path.parent().unwrap_or(path), obsidian_path);
+    let note_folders: Vec<String> = references
+        .iter()
+        .map(|reference| folder_of(Path::new(reference)))
+        .collect();
+    validated_config.attachment_folder().holds(
+        &folder_of(image_path),
+        note_folders.iter().map(String::as_str),
+    )
+}
+
+fn duplicate_candidates(image_files: &[PathBuf]) -> HashSet<&Path> {
+    let mut paths_by_size: HashMap<Option<u64>, Vec<&Path>> = HashMap::new();
+    for image_path in image_files {
+        let size = fs::metadata(image_path).ok().map(|metadata| metadata.len());
+        paths_by_size.entry(size).or_default().push(image_path);
+    }
+
+    paths_by_size
+        .into_iter()
+        .filter(|(size, paths)| size.is_none() || paths.len() >= MIN_DUPLICATE_GROUP_SIZE)
+        .flat_map(|(_, paths)| paths)
+        .collect()
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -326,15 +526,18 @@ mod tests {
 
     use tempfile::TempDir;
 
+    use crate::app_settings::AttachmentFolder;
     use crate::constants::MARKDOWN_EXTENSION;
     use crate::constants::YAML_CLOSING_DELIMITER_NEWLINE;
     use crate::constants::YAML_OPENING_DELIMITER;
     use crate::frontmatter::FrontMatter;
     use crate::image_file::DeletionStatus;
     use crate::image_file::ImageFileState;
+    use crate::image_file::ImageHash;
     use crate::markdown_file::ImageLinkState;
     use crate::markdown_file::MarkdownFile;
     use crate::markdown_file::PersistReason;
+    use crate::markdown_file::ReplaceableContent;
     use crate::markdown_files::MarkdownFiles;
     use crate::obsidian_repository::ObsidianRepository;
     use crate::support::VecEnumFilter;
@@ -524,16 +727,9 @@ mod tests {
                     for (i, markdown_path) in paths[2..].iter().enumerate() {
                         let content = fs::read_to_string(markdown_path).unwrap();
 
-                        let possible_refs = [
-                            format!("![[{keeper_name}]]"),
-                            format!("![[conf/media/{keeper_name}]]"),
-                        ];
-
                         assert!(
-                            possible_refs
-                                .iter()
-                                .any(|ref_str| content.contains(ref_str)),
-                            "Markdown file {} should reference keeper image '{}' either directly or in conf/media/\nActual content:\n{}",
+                            content.contains(&format!("![[{keeper_name}]]")),
+                            "Markdown file {} should reference keeper image '{}' in the vault root\nActual content:\n{}",
                             i + 1,
                             keeper_name,
                             content
@@ -861,4 +1057,198 @@ mod tests {
             "referenced2.jpg should be deleted as it's a duplicate"
         );
     }
+
+    #[test]
+    fn test_duplicate_kept_in_attachment_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        for folder in ["archive", "media", "daily/assets"] {
+            fs::create_dir_all(temp_dir.path().join(folder)).unwrap();
+        }
+        for name in [
+            "archive/photo.jpg",
+            "media/photo.jpg",
+            "daily/assets/photo.jpg",
+            "photo.jpg",
+        ] {
+            TestFileBuilder::new()
+                .with_content(vec![0xFF, 0xD8, 0xFF, 0xE0])
+                .create(&temp_dir, name);
+        }
+        TestFileBuilder::new()
+            .with_content("![photo](archive/photo.jpg)")
+            .create(&temp_dir, "daily/today.md");
+
+        for (attachment_folder, keeper) in [
+            (AttachmentFolder::VaultRoot, "photo.jpg"),
+            (
+                AttachmentFolder::Folder("media".to_string()),
+                "media/photo.jpg",
+            ),
+            (
+                AttachmentFolder::BesideNote("assets".to_string()),
+                "daily/assets/photo.jpg",
+            ),
+        ] {
+            let validated_config =
+                test_utils::get_test_validated_config_result(&temp_dir, |builder| {
+                    builder.attachment_folder(attachment_folder.clone());
+                })
+                .unwrap();
+            let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+            let keepers = obsidian_repository
+                .image_files
+                .filter_by_predicate(|state| {
+                    matches!(state, ImageFileState::DuplicateKeeper { .. })
+                });
+            assert_eq!(keepers.len(), 1, "{attachment_folder:?}");
+            assert!(
+                keepers.images[0].path == temp_dir.path().join(keeper),
+                "{attachment_folder:?} keeps {}",
+                keepers.images[0].path.display()
+            );
+            let replacement =
+                obsidian_repository.markdown_files[0].image_links[0].get_replacement();
+            assert_eq!(
+                replacement,
+                format!("![photo]({keeper})"),
+                "{attachment_folder:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_duplicate_references_point_at_keeper() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("daily").join("assets")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("media")).unwrap();
+        for name in [
+            "duplicate.jpg",
+            "media/keeper.jpg",
+            "daily/assets/other.jpg",
+        ] {
+            TestFileBuilder::new()
+                .with_content(vec![0xFF, 0xD8, 0xFF, 0xE0])
+                .create(&temp_dir, name);
+        }
+        let note_path = TestFileBuilder::new()
+            .with_content("![[duplicate.jpg|400]]\n![alt](daily/assets/other.jpg)")
+            .create(&temp_dir, "daily/today.md");
+
+        let replacements = || {
+            // Keeps the image the note already links to when the vault is first read, so only
+            // the states set below move its references.
+            let validated_config =
+                test_utils::get_test_validated_config_result(&temp_dir, |builder| {
+                    builder.attachment_folder(AttachmentFolder::Folder("daily/assets".to_string()));
+                })
+                .unwrap();
+            let mut obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+            let image_hash = ImageHash::from("same");
+            for image_file in &mut obsidian_repository.image_files.images {
+                image_file.state = if image_file.path.ends_with("media/keeper.jpg") {
+                    ImageFileState::DuplicateKeeper {
+                        image_hash: image_hash.clone(),
+                    }
+                } else if image_file.path.ends_with("old/keeper.jpg") {
+                    ImageFileState::Valid
+                } else {
+                    ImageFileState::Duplicate {
+                        image_hash: image_hash.clone(),
+                    }
+                };
+            }
+            obsidian_repository.identify_image_reference_replacements(&validated_config);
+
+            obsidian_repository
+                .markdown_files
+                .get_mut(&note_path)
+                .unwrap()
+                .image_links
+                .iter()
+                .map(ReplaceableContent::get_replacement)
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(
+            replacements(),
+            vec!["![[keeper.jpg|400]]", "![alt](media/keeper.jpg)"],
+            "a link naming only the file keeps doing so, one naming a folder gets the keeper's"
+        );
+
+        fs::create_dir_all(temp_dir.path().join("old")).unwrap();
+        fs::write(temp_dir.path().join("old/keeper.jpg"), b"another image").unwrap();
+        assert_eq!(
+            replacements(),
+            vec!["![[media/keeper.jpg|400]]", "![alt](media/keeper.jpg)"],
+            "another image with the keeper's name needs the keeper's folder to tell them apart"
+        );
+    }
+
+    #[test]
+    fn test_attachment_sizes_counted_once_per_file() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content(vec![1_u8; 300])
+            .create(&temp_dir, "scan.pdf");
+        TestFileBuilder::new()
+            .with_content(vec![2_u8; 40])
+            .create(&temp_dir, "photo.png");
+        TestFileBuilder::new()
+            .with_content("![[scan.pdf]] ![[Photo.png]] and again ![[scan.pdf]] ![[gone.png]]")
+            .create(&temp_dir, "dump.md");
+
+        let validated_config = test_utils::get_test_validated_config(&temp_dir, None);
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        let image_links = &obsidian_repository.markdown_files[0].image_links;
+
+        assert_eq!(image_links.attachment_count(), 2);
+        assert_eq!(image_links.attachment_size(), 340);
+    }
+
+    #[test]
+    fn test_only_images_sharing_a_size_are_hashed() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content(vec![1_u8; 40])
+            .create(&temp_dir, "first.png");
+        TestFileBuilder::new()
+            .with_content(vec![2_u8; 40])
+            .create(&temp_dir, "second.png");
+        TestFileBuilder::new()
+            .with_content(vec![3_u8; 90])
+            .create(&temp_dir, "photo.png");
+        TestFileBuilder::new()
+            .with_content("![[first.png]] ![[second.png]] ![[photo.png]]")
+            .create(&temp_dir, "note.md");
+
+        let hash_of = |repository: &ObsidianRepository, name: &str| {
+            repository
+                .image_files
+                .iter()
+                .find(|image| image.path.ends_with(name))
+                .unwrap()
+                .image_hash
+                .clone()
+        };
+
+        let validated_config = test_utils::get_test_validated_config(&temp_dir, None);
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        assert!(hash_of(&obsidian_repository, "first.png").is_some());
+        assert!(hash_of(&obsidian_repository, "second.png").is_some());
+        assert!(hash_of(&obsidian_repository, "photo.png").is_none());
+        assert!(
+            obsidian_repository
+                .image_files
+                .iter()
+                .all(|image| image.state == ImageFileState::Valid)
+        );
+
+        let validated_config = test_utils::get_test_validated_config_result(&temp_dir, |builder| {
+            builder.hash_all_images(true);
+        })
+        .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        assert!(hash_of(&obsidian_repository, "photo.png").is_some());
+    }
 }
@@ -0,0 +1,247 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use super::constants::TRIPLE_CLOSING_BRACKETS;
+use super::constants::TRIPLE_OPENING_BRACKETS;
+use super::ownership;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::FRONTMATTER_ROUND_TRIP_FAILED;
+use crate::constants::IMAGE_LINKS_ADDED;
+use crate::constants::NESTED_WIKILINK_BRACKETS;
+use crate::constants::OPENING_WIKILINK;
+use crate::constants::UNBALANCED_WIKILINK_BRACKETS;
+use crate::frontmatter::FrontMatter;
+use crate::markdown_file::MarkdownFile;
+use crate::support;
+use crate::support::IMAGE_REGEX;
+use crate::yaml_frontmatter;
+use crate::yaml_frontmatter::YamlFrontMatter;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum IntegrityIssue {
+    /// The frontmatter to be written doesn't read back - the message is the parse error.
+    FrontmatterUnparsable(String),
+    /// More image links than the note had, when changes only ever rewrite or remove them.
+    ImageLinksAdded { before: usize, after: usize },
+    /// A `[[[` or `]]]` the note didn't have - one replacement landed inside another's link.
+    NestedWikilinkBrackets,
+    /// More `[[` left without a `]]`, or the other way round, than the note had.
+    UnbalancedWikilinkBrackets,
+}
+
+impl Display for IntegrityIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FrontmatterUnparsable(message) => {
+                write!(f, "{FRONTMATTER_ROUND_TRIP_FAILED} {message}")
+            },
+            Self::ImageLinksAdded { before, after } => {
+                write!(f, "{IMAGE_LINKS_ADDED} ({before} before, {after} after)")
+            },
+            Self::NestedWikilinkBrackets => f.write_str(NESTED_WIKILINK_BRACKETS),
+            Self::UnbalancedWikilinkBrackets => f.write_str(UNBALANCED_WIKILINK_BRACKETS),
+        }
+    }
+}
+
+/// A note whose changes were discarded because the content to be written failed a check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct IntegrityFailure {
+    pub path:    PathBuf,
+    pub issue:   IntegrityIssue,
+    /// The discarded changes, as `audit_trail` would record them.
+    pub changes: Vec<String>,
+}
+
+impl ObsidianRepository {
+    /// Reads back the content every changed note would be written with, and compares it to the
+    /// note on disk: the frontmatter has to parse, no more wikilink brackets may go unpaired, no
+    /// `[[[` or `]]]` may appear, and no image link may be added. A note that fails keeps its
+    /// content on disk - its changes and rename are dropped, along with the splits, merged
+    /// conflict copies and image deletions that depended on them, and it is listed with the
+    /// reason. Runs after every other pass, so it sees the content as it would be written.
+    pub(crate) fn guard_content_integrity(&mut self) {
+        let mut integrity_failures = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            if !markdown_file
+                .front_matter
+                .as_ref()
+                .is_some_and(FrontMatter::needs_persist)
+            {
+                continue;
+            }
+            // A note that can't be read is skipped when persisting, as changed on disk.
+            let Ok(original) = support::read_contents_from_file(&markdown_file.path) else {
+                continue;
+            };
+            let Some(issue) = integrity_issue(markdown_file, &original) else {
+                continue;
+            };
+
+            let changes = ownership::discard_changes(markdown_file, &self.paths_to_rename);
+            integrity_failures.push(IntegrityFailure {
+                path: markdown_file.path.clone(),
+                issue,
+                changes,
+            });
+        }
+
+        let failed: Vec<PathBuf> = integrity_failures
+            .iter()
+            .map(|failure| failure.path.clone())
+            .collect();
+        self.release_discarded_notes(&failed);
+        self.integrity_failures = integrity_failures;
+    }
+}
+
+/// The first check the content `markdown_file` would be written with fails, measured against
+/// `original`, the note as it is on disk.
+fn integrity_issue(markdown_file: &MarkdownFile, original: &str) -> Option<IntegrityIssue> {
+    let full_content = markdown_file.to_full_content();
+
+    if markdown_file.front_matter.is_some() {
+        let parsed = yaml_frontmatter::find_yaml_section(&full_content).and_then(|section| {
+            section.map_or(Ok(()), |(yaml, _)| {
+                FrontMatter::from_yaml_str(yaml).map(|_| ())
+            })
+        });
+        if let Err(error) = parsed {
+            return Some(IntegrityIssue::FrontmatterUnparsable(error.to_string()));
+        }
+    }
+
+    if bracket_imbalance(&full_content) > bracket_imbalance(original) {
+        return Some(IntegrityIssue::UnbalancedWikilinkBrackets);
+    }
+    if nested_bracket_count(&full_content) > nested_bracket_count(original) {
+        return Some(IntegrityIssue::NestedWikilinkBrackets);
+    }
+
+    let before = IMAGE_REGEX.find_iter(original).count();
+    let after = IMAGE_REGEX.find_iter(&full_content).count();
+    (after > before).then_some(IntegrityIssue::ImageLinksAdded { before, after })
+}
+
+/// How many more `[[` than `]]` there are, or the other way round - repairing a wikilink can
+/// lower it, but no change should raise it.
+fn bracket_imbalance(content: &str) -> usize {
+    content
+        .matches(OPENING_WIKILINK)
+        .count()
+        .abs_diff(content.matches(CLOSING_WIKILINK).count())
+}
+
+fn nested_bracket_count(content: &str) -> usize {
+    content.matches(TRIPLE_OPENING_BRACKETS).count()
+        + content.matches(TRIPLE_CLOSING_BRACKETS).count()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::IntegrityIssue;
+    use super::integrity_issue;
+    use crate::config::ConflictResolution;
+    use crate::constants::DEFAULT_TIMEZONE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_integrity_issue() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content("see [[Topic]] and ![[photo.png]]")
+            .create(&temp_dir, "notes.md");
+        let original = fs::read_to_string(&file_path).unwrap();
+        let mut markdown_file = test_support::get_test_markdown_file(file_path);
+        markdown_file
+            .mark_as_back_populated(DEFAULT_TIMEZONE)
+            .unwrap();
+
+        markdown_file.content = "see [[Topic]] and ![[photo.png]] with [[Other]]".to_string();
+        assert_eq!(integrity_issue(&markdown_file, &original), None);
+
+        markdown_file.content = "see [[Topic and ![[photo.png]]".to_string();
+        assert_eq!(
+            integrity_issue(&markdown_file, &original),
+            Some(IntegrityIssue::UnbalancedWikilinkBrackets)
+        );
+
+        markdown_file.content = "see [[[Topic]]] and ![[photo.png]]".to_string();
+        assert_eq!(
+            integrity_issue(&markdown_file, &original),
+            Some(IntegrityIssue::NestedWikilinkBrackets)
+        );
+
+        markdown_file.content = "see [[Topic]] and ![[photo.png]] ![[photo.png]]".to_string();
+        assert_eq!(
+            integrity_issue(&markdown_file, &original),
+            Some(IntegrityIssue::ImageLinksAdded {
+                before: 1,
+                after:  2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_failed_note_keeps_what_it_references() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["first.jpg", "second.jpg"] {
+            TestFileBuilder::new()
+                .with_content(vec![0xFF, 0xD8, 0xFF, 0xE0])
+                .create(&temp_dir, name);
+        }
+        for (name, content) in [
+            ("Plan.md", "![[first.jpg]]\n![[second.jpg]]"),
+            ("Plan (conflicted copy 2024-05-02).md", "a later idea"),
+        ] {
+            TestFileBuilder::new()
+                .with_content(content)
+                .create(&temp_dir, name);
+        }
+        let plan_path = temp_dir.path().join("Plan.md");
+        let plan = fs::read_to_string(&plan_path).unwrap();
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.resolve_conflict_copies(Some(ConflictResolution::Merge));
+            })
+            .unwrap();
+        let mut obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        // A rewrite gone wrong, caught by the check before anything is written.
+        let markdown_file = obsidian_repository
+            .markdown_files
+            .iter_mut()
+            .find(|markdown_file| markdown_file.path == plan_path)
+            .unwrap();
+        markdown_file.content.push_str("\n[[[broken]]]");
+        obsidian_repository.guard_content_integrity();
+        assert_eq!(obsidian_repository.integrity_failures.len(), 1);
+
+        obsidian_repository.persist().unwrap();
+
+        assert_eq!(fs::read_to_string(&plan_path).unwrap(), plan);
+        for name in [
+            "first.jpg",
+            "second.jpg",
+            "Plan (conflicted copy 2024-05-02).md",
+        ] {
+            assert!(
+                temp_dir.path().join(name).exists(),
+                "{name} is still needed by the note left on disk"
+            );
+        }
+    }
+}
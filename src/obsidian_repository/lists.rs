@@ -0,0 +1,23 @@
+use anyhow::Result as AnyhowResult;
+
+use super::ObsidianRepository;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `normalize_lists` on, rewrites list items in every note body to `-` markers, tab
+    /// indentation and consecutive numbering. Runs after the body rewrites and table of
+    /// contents refresh so the lists they write are normalized too.
+    pub(crate) fn normalize_all_lists(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        if !validated_config.normalize_lists() {
+            return Ok(());
+        }
+
+        for markdown_file in &mut self.markdown_files {
+            markdown_file.normalize_lists(validated_config.operational_timezone())?;
+        }
+        Ok(())
+    }
+}
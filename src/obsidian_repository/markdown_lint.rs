@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use crate::markdown_file::LintKind;
+use crate::validated_config::ValidatedConfig;
+
+/// A markdown style issue in a note, found by `lint_all_markdown`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct MarkdownLintIssue {
+    pub path:        PathBuf,
+    pub line_number: usize,
+    pub kind:        LintKind,
+}
+
+impl ObsidianRepository {
+    /// With `lint_markdown` on, records hard tabs, bare URLs, headings and lists with no blank
+    /// line before them and, with `lint_max_line_length` set, long lines in every note. Runs
+    /// after the body rewrites, so it reports the notes as they will be written.
+    pub(crate) fn lint_all_markdown(&mut self, validated_config: &ValidatedConfig) {
+        if !validated_config.lint_markdown() {
+            return;
+        }
+
+        self.markdown_lint_issues = self
+            .markdown_files
+            .iter()
+            .flat_map(|markdown_file| {
+                markdown_file
+                    .lint(validated_config.lint_max_line_length())
+                    .into_iter()
+                    .map(|issue| MarkdownLintIssue {
+                        path:        markdown_file.path.clone(),
+                        line_number: issue.line_number,
+                        kind:        issue.kind,
+                    })
+            })
+            .collect();
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::fs;
+use std::mem::take;
+
+use anyhow::Context as _;
+use anyhow::Result as AnyhowResult;
+use serde::Deserialize;
+
+use super::ObsidianRepository;
+use super::constants::MATCH_DECISIONS_UNREADABLE;
+use crate::config::LinkAliasStyle;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::OPENING_WIKILINK;
+use crate::markdown_file::BackPopulateMatch;
+use crate::validated_config::ValidatedConfig;
+
+/// What to do with back populate matches of `found_text` that would link `target`.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Decision {
+    /// Link the matches to `target`, even when the text names other notes too.
+    Apply,
+    /// Leave the matches alone.
+    Ignore,
+}
+
+/// One entry of the `match_decisions` file. Without `file` it holds in every note.
+#[derive(Debug, Clone, Deserialize)]
+struct MatchDecision {
+    found_text: String,
+    target:     String,
+    #[serde(default)]
+    file:       Option<String>,
+    decision:   Decision,
+}
+
+impl MatchDecision {
+    fn covers(&self, back_populate_match: &BackPopulateMatch) -> bool {
+        self.found_text
+            .eq_ignore_ascii_case(&back_populate_match.found_text)
+            && self.file.as_deref().is_none_or(|file| {
+                let relative_path = &*back_populate_match.relative_path;
+                relative_path == file || relative_path.strip_suffix(MARKDOWN_SUFFIX) == Some(file)
+            })
+    }
+
+    fn names(&self, target: &str) -> bool {
+        self.target
+            .trim_start_matches(OPENING_WIKILINK)
+            .trim_end_matches(CLOSING_WIKILINK)
+            .eq_ignore_ascii_case(target)
+    }
+}
+
+impl ObsidianRepository {
+    /// With `match_decisions` set, applies the review decisions it records so they hold across
+    /// runs: `ignore` drops the matches that would link its target, and `apply` links the
+    /// ambiguous matches its target is one of the candidates for to that target. Unambiguous
+    /// matches are applied anyway. Where two decisions cover a match, the first holds.
+    pub(super) fn apply_match_decisions(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let Some(path) = validated_config.match_decisions() else {
+            return Ok(());
+        };
+        let contents = fs::read_to_string(path)?;
+        if contents.trim().is_empty() {
+            return Ok(());
+        }
+        let decisions: Vec<MatchDecision> = serde_yaml::from_str(&contents)
+            .with_context(|| format!("{MATCH_DECISIONS_UNREADABLE} {}", path.display()))?;
+
+        let targets_by_text = self.targets_by_display_text();
+        let style = validated_config.link_alias_style();
+        let note_paths = if style == LinkAliasStyle::KeepCase {
+            HashMap::new()
+        } else {
+            self.note_paths_by_stem(validated_config)
+        };
+
+        for markdown_file in &mut self.markdown_files {
+            markdown_file
+                .back_populate_matches
+                .unambiguous
+                .retain(|back_populate_match| {
+                    let Some(target) = back_populate_match.target() else {
+                        return true;
+                    };
+                    decisions
+                        .iter()
+                        .find(|decision| {
+                            decision.covers(back_populate_match) && decision.names(&target)
+                        })
+                        .is_none_or(|decision| decision.decision == Decision::Apply)
+                });
+
+            for mut back_populate_match in take(&mut markdown_file.back_populate_matches.ambiguous)
+            {
+                let candidates =
+                    targets_by_text.get(&back_populate_match.found_text.to_lowercase());
+                let decided = decisions
+                    .iter()
+                    .filter(|decision| decision.covers(&back_populate_match))
+                    .find_map(|decision| {
+                        candidates?
+                            .iter()
+                            .find(|target| decision.names(target))
+                            .map(|target| (decision.decision, target))
+                    });
+                match decided {
+                    Some((Decision::Ignore, _)) => {},
+                    Some((Decision::Apply, target)) => {
+                        back_populate_match.retarget(target);
+                        if style != LinkAliasStyle::KeepCase {
+                            back_populate_match.style_replacement(&note_paths, style);
+                        }
+                        markdown_file
+                            .back_populate_matches
+                            .unambiguous
+                            .push(back_populate_match);
+                    },
+                    None => markdown_file
+                        .back_populate_matches
+                        .ambiguous
+                        .push(back_populate_match),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support as test_utils;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_match_decisions_apply_and_ignore() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_date = test_utils::eastern_midnight(2024, 1, 15);
+        for name in ["Apple", "Banana"] {
+            TestFileBuilder::new()
+                .with_aliases(vec!["fruit".to_string()])
+                .with_matching_dates(test_date)
+                .with_file_system_dates(test_date, test_date)
+                .create(&temp_dir, &format!("{name}.md"));
+        }
+        for (name, content) in [
+            ("Other", "# Other\n"),
+            ("Story", "I ate fruit\nSee Other\n"),
+            ("Diary", "More fruit\nSee Other\n"),
+        ] {
+            TestFileBuilder::new()
+                .with_content(content.to_string())
+                .with_matching_dates(test_date)
+                .with_file_system_dates(test_date, test_date)
+                .create(&temp_dir, &format!("{name}.md"));
+        }
+        let decisions_path = temp_dir.path().join("decisions.yaml");
+        fs::write(
+            &decisions_path,
+            "- found_text: fruit\n  target: \"[[Banana]]\"\n  file: Story\n  decision: apply\n\
+             - found_text: other\n  target: Other\n  file: Story.md\n  decision: ignore\n",
+        )
+        .unwrap();
+
+        let validated_config = test_utils::get_test_validated_config_result(&temp_dir, |builder| {
+            builder.match_decisions(Some(decisions_path));
+        })
+        .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let matches_in = |name: &str| {
+            let markdown_file = repository
+                .markdown_files
+                .iter()
+                .find(|markdown_file| markdown_file.path.ends_with(format!("{name}.md")))
+                .unwrap();
+            let replacements: Vec<String> = markdown_file
+                .back_populate_matches
+                .unambiguous
+                .iter()
+                .map(|back_populate_match| back_populate_match.replacement.to_string())
+                .collect();
+            (
+                replacements,
+                markdown_file.back_populate_matches.ambiguous.len(),
+            )
+        };
+
+        assert_eq!(
+            matches_in("Story"),
+            (vec!["[[Banana|fruit]]".to_string()], 0),
+            "the applied decision picks a target and the ignored match is dropped"
+        );
+        assert_eq!(
+            matches_in("Diary"),
+            (vec!["[[Other]]".to_string()], 1),
+            "decisions for one file leave the others alone"
+        );
+    }
+}
@@ -1,7 +1,54 @@
+mod acronyms;
+mod alias_generation;
+mod audit_trail;
 mod back_populate;
+mod backup;
+mod block_ids;
+mod callouts;
+mod changed_on_disk;
+mod changelog;
+mod citations;
+mod conflict_copies;
 mod constants;
+mod custom_replacements;
+mod date_consistency;
+mod date_mentions;
+mod encrypted_blocks;
+mod entity_notes;
+mod export;
+mod flashcards;
+mod frontmatter_links;
+mod frontmatter_only;
+mod heading_links;
+mod held_persist_reasons;
+mod hidden_folders;
 mod image_processing;
+mod integrity;
+mod lists;
+mod markdown_lint;
+mod match_decisions;
+mod note_splits;
+mod notification;
+mod other_files;
+mod ownership;
+mod patch;
+mod path_audit;
+mod periodic_notes;
 mod phantom_links;
+mod pii;
+mod property_types;
+mod punctuation;
+mod rare_words;
+mod recently_modified;
+mod regex_replacements;
+mod replacement_conflicts;
+mod reviews;
+mod secret_scan;
+mod similar_titles;
+mod structured_files;
+mod table_of_contents;
+mod vault_health;
+mod vault_index;
 
 use std::collections::HashSet;
 use std::error::Error;
@@ -9,21 +56,63 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+pub(crate) use acronyms::AcronymCollision;
 use aho_corasick::AhoCorasick;
 use aho_corasick::AhoCorasickBuilder;
 use aho_corasick::MatchKind;
+pub(crate) use alias_generation::GeneratedAlias;
 use anyhow::Result as AnyhowResult;
+pub(crate) use block_ids::BlockIdIssue;
+pub(crate) use block_ids::NoteBlockId;
+pub(crate) use changed_on_disk::ChangedOnDiskNote;
+use chrono::Local;
+pub(crate) use citations::CitationIssue;
+pub(crate) use citations::CitationIssueKind;
+pub(crate) use conflict_copies::ConflictCopy;
+pub(crate) use conflict_copies::ConflictCopyAction;
+pub(crate) use encrypted_blocks::EncryptedNoteBlock;
+pub(crate) use entity_notes::EntityNoteIssue;
+pub(crate) use export::ExportedNote;
+pub(crate) use export::Withheld;
+pub(crate) use export::WithheldContent;
+pub(crate) use frontmatter_links::FrontmatterLinkIssue;
+pub(crate) use frontmatter_links::LinkTargetNote;
+pub(crate) use hidden_folders::HiddenFolderItem;
+pub(crate) use integrity::IntegrityFailure;
+pub(crate) use markdown_lint::MarkdownLintIssue;
+pub(crate) use note_splits::SectionSplit;
+pub(crate) use other_files::OtherFileType;
+pub(crate) use ownership::ForeignOwnedNote;
+pub(crate) use path_audit::PathIssue;
+pub(crate) use periodic_notes::PeriodicNoteChange;
 pub(crate) use phantom_links::UnresolvedLink;
+pub(crate) use pii::PiiFolderSummary;
+pub(crate) use property_types::PropertyTypeMismatch;
+pub(crate) use rare_words::RareWord;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
+pub(crate) use recently_modified::RecentlyModifiedNote;
+pub(crate) use replacement_conflicts::ReplacementConflict;
+pub(crate) use reviews::OverdueReview;
+pub(crate) use secret_scan::PossibleSecret;
+pub(crate) use similar_titles::SimilarTitlePair;
+pub(crate) use structured_files::StructuredFile;
+pub(crate) use structured_files::StructuredFileIssue;
+pub(crate) use vault_health::VaultHealthEntry;
 
 use self::constants::ANALYZE_TIMER_LABEL;
 use self::constants::ERROR_PROCESSING_FILE;
 use self::constants::MARKDOWN_FILE_COLLECTION_SHARED_REFERENCES;
 use self::constants::PRESCAN_ANALYZE_TIMER_LABEL;
+use crate::config::DuplicateKeyResolution;
+use crate::config::PersistReasonKind;
+use crate::constants::BACKUP_RUN_ID_FORMAT;
 use crate::constants::MARKDOWN_FILE_COLLECTION_LOCK_POISONED;
 use crate::image_file::ImageFiles;
+use crate::markdown_file::Flashcard;
 use crate::markdown_file::MarkdownFile;
+use crate::markdown_file::ReplacementSource;
+use crate::markdown_file::StaleHeadingLink;
 use crate::markdown_files::MarkdownFiles;
 use crate::support;
 use crate::timer::Timer;
@@ -32,40 +121,138 @@ use crate::wikilink::Wikilink;
 
 #[derive(Default)]
 pub(crate) struct ObsidianRepository {
-    pub markdown_files:      MarkdownFiles,
-    pub image_files:         ImageFiles,
-    pub wikilinks_automaton: Option<AhoCorasick>,
-    pub wikilinks_sorted:    Vec<Wikilink>,
+    pub markdown_files:           MarkdownFiles,
+    pub image_files:              ImageFiles,
+    pub acronym_collisions:       Vec<AcronymCollision>,
+    pub acronyms:                 Vec<String>,
+    pub block_id_issues:          Vec<BlockIdIssue>,
+    pub block_ids:                Vec<NoteBlockId>,
+    pub changed_on_disk_notes:    Vec<ChangedOnDiskNote>,
+    pub citation_issues:          Vec<CitationIssue>,
+    pub conflict_copies:          Vec<ConflictCopy>,
+    pub encrypted_blocks:         Vec<EncryptedNoteBlock>,
+    pub entity_note_issues:       Vec<EntityNoteIssue>,
+    pub exported_notes:           Vec<ExportedNote>,
+    pub flashcards:               Vec<Flashcard>,
+    pub foreign_owned_notes:      Vec<ForeignOwnedNote>,
+    pub frontmatter_link_issues:  Vec<FrontmatterLinkIssue>,
+    pub generated_aliases:        Vec<GeneratedAlias>,
+    pub hidden_folder_items:      Vec<HiddenFolderItem>,
+    pub integrity_failures:       Vec<IntegrityFailure>,
+    pub link_targets_to_create:   Vec<LinkTargetNote>,
+    pub markdown_lint_issues:     Vec<MarkdownLintIssue>,
+    pub other_file_types:         Vec<OtherFileType>,
+    pub path_issues:              Vec<PathIssue>,
+    pub paths_to_rename:          Vec<(PathBuf, PathBuf)>,
+    pub periodic_note_changes:    Vec<PeriodicNoteChange>,
+    pub pii_summaries:            Vec<PiiFolderSummary>,
+    pub possible_secrets:         Vec<PossibleSecret>,
+    pub overdue_reviews:          Vec<OverdueReview>,
+    pub property_type_mismatches: Vec<PropertyTypeMismatch>,
+    pub recently_modified_notes:  Vec<RecentlyModifiedNote>,
+    pub replacement_conflicts:    Vec<ReplacementConflict>,
+    /// When the run started, in `BACKUP_RUN_ID_FORMAT` - names its backup and is in the report.
+    pub run_id:                   String,
+    pub section_splits:           Vec<SectionSplit>,
+    pub stale_heading_links:      Vec<StaleHeadingLink>,
+    pub structured_file_issues:   Vec<StructuredFileIssue>,
+    pub structured_files:         Vec<StructuredFile>,
+    pub vault_health_history:     Vec<VaultHealthEntry>,
+    pub wikilinks_automaton:      Option<AhoCorasick>,
+    pub wikilinks_sorted:         Vec<Wikilink>,
+    pub withheld_content:         Vec<WithheldContent>,
 }
 
 impl ObsidianRepository {
+    #[cfg(any(test, feature = "bench"))]
     pub(crate) fn new(
         validated_config: &ValidatedConfig,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Self::with_replacement_sources(validated_config, &[])
+    }
+
+    /// Like `new`, with the replacements `replacement_sources` find applied along with the
+    /// built-in ones.
+    pub(crate) fn with_replacement_sources(
+        validated_config: &ValidatedConfig,
+        replacement_sources: &[&dyn ReplacementSource],
     ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let _timer = Timer::new(PRESCAN_ANALYZE_TIMER_LABEL);
         let ignore_folders = validated_config.ignore_folders().unwrap_or(&[]);
 
         let repository_files = support::collect_repository_files(validated_config, ignore_folders)?;
 
-        let markdown_files = Self::initialize_markdown_files(
+        let mut markdown_files = Self::initialize_markdown_files(
             &repository_files.markdown,
             validated_config.operational_timezone(),
+            validated_config.date_link_format(),
+            validated_config.fix_duplicate_frontmatter_keys(),
+            validated_config.hold_persist_reasons(),
             validated_config.file_limit(),
         )?;
 
+        for markdown_file in &mut markdown_files {
+            markdown_file
+                .index_frontmatter_wikilinks(validated_config.frontmatter_link_properties());
+        }
+
+        let generated_aliases = Self::generate_aliases(&mut markdown_files, validated_config)?;
+        let (acronyms, acronym_collisions) =
+            Self::link_acronyms(&mut markdown_files, validated_config);
+
         let (sorted, automaton) = Self::initialize_wikilinks(&markdown_files)?;
 
         let mut repository = Self {
             markdown_files,
             image_files: ImageFiles::default(),
+            acronym_collisions,
+            acronyms,
+            block_id_issues: Vec::new(),
+            block_ids: Vec::new(),
+            changed_on_disk_notes: Vec::new(),
+            citation_issues: Vec::new(),
+            conflict_copies: Vec::new(),
+            encrypted_blocks: Vec::new(),
+            entity_note_issues: Vec::new(),
+            exported_notes: Vec::new(),
+            flashcards: Vec::new(),
+            foreign_owned_notes: Vec::new(),
+            frontmatter_link_issues: Vec::new(),
+            generated_aliases,
+            hidden_folder_items: Self::inspect_hidden_folders(validated_config),
+            integrity_failures: Vec::new(),
+            link_targets_to_create: Vec::new(),
+            markdown_lint_issues: Vec::new(),
+            other_file_types: Self::summarize_other_files(&repository_files.other),
+            path_issues: Vec::new(),
+            paths_to_rename: Vec::new(),
+            periodic_note_changes: Vec::new(),
+            pii_summaries: Vec::new(),
+            overdue_reviews: Vec::new(),
+            possible_secrets: Vec::new(),
+            property_type_mismatches: Vec::new(),
+            recently_modified_notes: Vec::new(),
+            replacement_conflicts: Vec::new(),
+            run_id: Local::now().format(BACKUP_RUN_ID_FORMAT).to_string(),
+            section_splits: Vec::new(),
+            stale_heading_links: Vec::new(),
+            structured_file_issues: Vec::new(),
+            structured_files: Self::load_structured_files(
+                &repository_files,
+                validated_config.obsidian_path(),
+            ),
+            vault_health_history: Vec::new(),
             wikilinks_automaton: Some(automaton),
             wikilinks_sorted: sorted,
+            withheld_content: Vec::new(),
         };
 
+        repository.resolve_conflict_copies(validated_config)?;
         repository.image_files =
             repository.initialize_image_files(&repository_files.images, validated_config)?;
 
-        repository.analyze_repository(validated_config)?;
+        repository.analyze_repository(validated_config, replacement_sources)?;
+        repository.record_vault_health(validated_config)?;
 
         Ok(repository)
     }
@@ -73,12 +260,21 @@ impl ObsidianRepository {
     fn initialize_markdown_files(
         markdown_paths: &[PathBuf],
         timezone: &str,
+        date_link_format: &str,
+        duplicate_key_resolution: Option<DuplicateKeyResolution>,
+        held_reasons: &[PersistReasonKind],
         file_limit: Option<usize>,
     ) -> Result<MarkdownFiles, Box<dyn Error + Send + Sync>> {
         let markdown_files = Arc::new(Mutex::new(MarkdownFiles::default()));
 
         markdown_paths.par_iter().try_for_each(|file_path| {
-            match MarkdownFile::new(file_path.clone(), timezone) {
+            match MarkdownFile::new(
+                file_path.clone(),
+                timezone,
+                date_link_format,
+                duplicate_key_resolution,
+                held_reasons,
+            ) {
                 Ok(markdown_file) => {
                     markdown_files
                         .lock()
@@ -101,6 +297,8 @@ impl ObsidianRepository {
             .into_inner()
             .map_err(|error| format!("{MARKDOWN_FILE_COLLECTION_LOCK_POISONED}: {error}"))?;
 
+        // Files are loaded in parallel; path order keeps analysis and reports reproducible.
+        markdown_files.sort_by(|a, b| a.path.cmp(&b.path));
         markdown_files.file_limit = file_limit;
 
         Ok(markdown_files)
@@ -133,21 +331,75 @@ impl ObsidianRepository {
         Ok((wikilinks, automaton))
     }
 
-    fn analyze_repository(&mut self, validated_config: &ValidatedConfig) -> AnyhowResult<()> {
+    fn analyze_repository(
+        &mut self,
+        validated_config: &ValidatedConfig,
+        replacement_sources: &[&dyn ReplacementSource],
+    ) -> AnyhowResult<()> {
         let _timer = Timer::new(ANALYZE_TIMER_LABEL);
-        self.canonicalize_wikilink_targets(validated_config);
+        self.find_encrypted_blocks();
+        self.check_all_journal_dates(validated_config);
+        self.validate_all_date_properties(validated_config);
+        self.normalize_all_punctuation(validated_config)?;
+        self.split_listed_notes(validated_config)?;
+        self.canonicalize_wikilink_targets(validated_config)?;
+        self.check_property_types(validated_config)?;
+        self.validate_frontmatter_links(validated_config)?;
+        self.link_periodic_notes(validated_config)?;
+        self.check_entity_folders(validated_config)?;
+        self.check_citations(validated_config)?;
+        self.scan_for_secrets(validated_config);
+        self.detect_pii(validated_config);
+        self.validate_structured_files();
         self.resolve_phantom_wikilinks(validated_config);
+        self.find_all_self_link_matches(validated_config);
+        self.find_all_wikilink_repair_matches(validated_config);
+        self.find_all_heading_link_repairs(validated_config);
         self.find_all_back_populate_matches(validated_config)?;
+        self.drop_miscased_acronym_matches();
         self.identify_ambiguous_matches();
-        self.identify_image_reference_replacements();
-        self.apply_replaceable_matches(validated_config.operational_timezone())?;
+        self.apply_match_decisions(validated_config)?;
+        self.format_back_populate_links(validated_config);
+        self.attach_image_file_sizes();
+        self.identify_image_reference_replacements(validated_config);
+        self.find_all_date_mention_matches(validated_config);
+        self.find_all_regex_replacement_matches(validated_config);
+        self.find_all_custom_replacement_matches(replacement_sources, validated_config);
+        self.find_all_admonition_conversions(validated_config);
+        self.apply_replaceable_matches(validated_config)?;
+        self.refresh_all_tables_of_contents(validated_config)?;
+        self.normalize_all_lists(validated_config)?;
+        self.collect_all_flashcards(validated_config)?;
+        self.check_block_ids(validated_config)?;
+        self.lint_all_markdown(validated_config);
+        self.check_all_date_consistency(validated_config);
+        self.check_reviews(validated_config);
         self.mark_image_files_for_deletion();
+        self.hold_body_changes(validated_config)?;
+        self.hold_persist_reasons(validated_config)?;
+        self.audit_paths(validated_config);
+        self.record_audit_trail(validated_config);
+        self.guard_foreign_owned_notes(validated_config);
+        self.guard_recently_modified_notes(validated_config);
+        self.guard_content_integrity();
+        self.prepare_exports(validated_config)?;
         Ok(())
     }
 
-    pub(crate) fn persist(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+    /// Returns the notes left unwritten because they changed on disk after
+    /// `guard_changed_on_disk_notes` checked them.
+    pub(crate) fn persist(&self) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
         self.image_files.delete_marked()?;
-        self.markdown_files.files_to_persist().persist_all()
+        self.create_link_targets()?;
+        self.create_split_notes()?;
+        self.create_periodic_notes()?;
+        let skipped = self.markdown_files.files_to_persist().persist_all()?;
+        self.delete_conflict_copies(&skipped)?;
+        self.purge_trash()?;
+        // Renames run last so notes are written back to the paths they were read from.
+        self.rename_unsafe_paths()?;
+        self.propagate_renames()?;
+        Ok(skipped)
     }
 }
 
@@ -604,6 +856,26 @@ date_modified: 2024-01-01
         Ok(())
     }
 
+    #[test]
+    fn test_persist_skips_files_changed_on_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("Recipe")
+            .create(&temp_dir, "Recipe.md");
+        let notes_path = TestFileBuilder::new()
+            .with_content("a recipe")
+            .create(&temp_dir, "notes.md");
+        let validated_config = test_utils::get_test_validated_config(&temp_dir, None);
+
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        fs::write(&notes_path, "edited in obsidian").unwrap();
+
+        let skipped = obsidian_repository.persist().unwrap();
+
+        assert_eq!(skipped, vec![notes_path.clone()]);
+        assert_eq!(read_to_string(&notes_path).unwrap(), "edited in obsidian");
+    }
+
     fn create_test_cases() -> Vec<PersistenceTestCase> {
         let last_week = test_utils::eastern_midnight(2024, 1, 8);
 
@@ -809,29 +1081,35 @@ date_modified: 2024-01-01
             let cache_path = temp_dir.path().join(CACHE_FOLDER).join(CACHE_FILE);
 
             TestFileBuilder::new()
-                .with_content("# Test\n![test](test.png)".to_string())
+                .with_content("# Test\n![test](test.png)\n![copy](copy.png)".to_string())
                 .with_title("Test Document".to_string())
                 .create(&temp_dir, "test.md");
 
+            // Two images of the same size, so both are hashed into the cache.
             TestFileBuilder::new()
                 .with_content(vec![0xFF, 0xD8, 0xFF, 0xE0]) // Simple PNG header
                 .create(&temp_dir, "test.png");
+            TestFileBuilder::new()
+                .with_content(vec![0xFF, 0xD8, 0xFF, 0xE1])
+                .create(&temp_dir, "copy.png");
 
             let validated_config = test_utils::get_test_validated_config(&temp_dir, None);
 
             let _ = ObsidianRepository::new(&validated_config).unwrap();
+            assert!(cache_path.exists(), "Cache should hold the hashed images");
 
-            // Delete the image file
+            // Delete the image files
             fs::remove_file(temp_dir.path().join("test.png")).unwrap();
+            fs::remove_file(temp_dir.path().join("copy.png")).unwrap();
 
             let _ = ObsidianRepository::new(&validated_config).unwrap();
 
             let cache_content = read_to_string(&cache_path).unwrap();
             let cache: Value = from_str(&cache_content).unwrap();
-            let cache = cache
+            let entries = cache["entries"]
                 .as_object()
-                .expect("cache file should deserialize to a JSON object");
-            assert!(cache.is_empty(), "Cache should be empty after cleanup");
+                .expect("cache entries should deserialize to a JSON object");
+            assert!(entries.is_empty(), "Cache should be empty after cleanup");
 
             // Exiting this block drops `TempDir` and removes the cached repository files.
         }
@@ -869,8 +1147,14 @@ date_modified: 2024-01-01
                 markdown_file.path.extension().and_then(OsStr::to_str) == Some(MARKDOWN_EXTENSION)
             })
             .flat_map(|markdown_file| {
-                let markdown_file =
-                    MarkdownFile::new(markdown_file.path.clone(), DEFAULT_TIMEZONE).unwrap();
+                let markdown_file = MarkdownFile::new(
+                    markdown_file.path.clone(),
+                    DEFAULT_TIMEZONE,
+                    FORMAT_DATE,
+                    None,
+                    &[],
+                )
+                .unwrap();
                 let file_wikilinks = markdown_file.wikilinks.valid;
                 file_wikilinks.into_iter().map(|w| w.display_text)
             })
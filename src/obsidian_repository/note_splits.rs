@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use chrono::Utc;
+
+use super::ObsidianRepository;
+use super::constants::SPLIT_NOTE_NAME_DISALLOWED_CHARACTERS;
+use super::path_audit;
+use super::phantom_links;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::HASH;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::NEWLINE;
+use crate::constants::OPENING_WIKILINK;
+use crate::markdown_file::NoteSection;
+use crate::note_template;
+use crate::validated_config::ValidatedConfig;
+
+/// One `##` section of a note listed in `split_notes`.
+#[derive(Clone, Debug)]
+pub(crate) struct SectionSplit {
+    pub source:    PathBuf,
+    pub heading:   String,
+    /// The note the section moves to - `None` when a note of that name already exists.
+    pub note_path: Option<PathBuf>,
+    /// The new note as it will be written: frontmatter dates and the section's body.
+    pub content:   String,
+}
+
+impl SectionSplit {
+    pub(crate) fn note_name(&self) -> Option<&str> {
+        self.note_path
+            .as_ref()
+            .and_then(|note_path| note_path.file_stem())
+            .and_then(OsStr::to_str)
+    }
+}
+
+impl ObsidianRepository {
+    /// Moves every `##` section of the notes listed in `split_notes` into a note named after
+    /// its heading, next to the original, and leaves an embed of it in its place. Links to an
+    /// extracted heading, or to a heading nested in it, follow it to the new note. The image
+    /// embeds of a section move with its text; the new note sits in the same folder, so their
+    /// paths still resolve. A section whose note name is taken stays where it is.
+    pub(crate) fn split_listed_notes(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        if validated_config.split_notes().is_empty() {
+            return Ok(());
+        }
+
+        let listed: HashSet<String> = validated_config
+            .split_notes()
+            .iter()
+            .map(|note| {
+                phantom_links::target_note_stem(
+                    note.trim()
+                        .trim_start_matches(OPENING_WIKILINK)
+                        .trim_end_matches(CLOSING_WIKILINK),
+                )
+            })
+            .collect();
+        let mut taken_names = self.markdown_note_stems();
+
+        let mut splits: Vec<SectionSplit> = Vec::new();
+        let mut extractions: HashMap<PathBuf, Vec<(NoteSection, String)>> = HashMap::new();
+        for markdown_file in &self.markdown_files {
+            if !listed.contains(&phantom_links::target_note_stem(&stem_of(
+                &markdown_file.path,
+            ))) {
+                continue;
+            }
+
+            for section in markdown_file.h2_sections() {
+                let note_name = path_audit::sanitize_name(
+                    &section
+                        .heading
+                        .replace(SPLIT_NOTE_NAME_DISALLOWED_CHARACTERS, " "),
+                );
+                let available = taken_names.insert(note_name.to_lowercase());
+                splits.push(SectionSplit {
+                    source:    markdown_file.path.clone(),
+                    heading:   section.heading.clone(),
+                    note_path: available.then(|| {
+                        markdown_file
+                            .path
+                            .with_file_name(format!("{note_name}{MARKDOWN_SUFFIX}"))
+                    }),
+                    content:   String::new(),
+                });
+                if available {
+                    extractions
+                        .entry(markdown_file.path.clone())
+                        .or_default()
+                        .push((section, note_name));
+                }
+            }
+        }
+        if extractions.is_empty() {
+            self.section_splits = splits;
+            return Ok(());
+        }
+
+        let extractions_by_stem: HashMap<String, &Vec<(NoteSection, String)>> = extractions
+            .iter()
+            .map(|(path, file_extractions)| (stem_of(path).to_lowercase(), file_extractions))
+            .collect();
+        let operational_timezone = validated_config.operational_timezone();
+        for markdown_file in &mut self.markdown_files {
+            let own_stem = stem_of(&markdown_file.path).to_lowercase();
+            markdown_file.retarget_heading_links(
+                |target| retargeted_link(target, &own_stem, &extractions_by_stem),
+                operational_timezone,
+            )?;
+        }
+
+        for markdown_file in &mut self.markdown_files {
+            let Some(file_extractions) = extractions.get(&markdown_file.path) else {
+                continue;
+            };
+            let source = &markdown_file.path;
+            for (section, _) in file_extractions {
+                if let Some(split) = splits
+                    .iter_mut()
+                    .find(|split| split.source == *source && split.heading == section.heading)
+                {
+                    let rendered_template = validated_config
+                        .split_note_template()
+                        .zip(split.note_path.as_deref())
+                        .map(|(template, note_path)| {
+                            template.render(
+                                note_path,
+                                validated_config.obsidian_path(),
+                                operational_timezone,
+                            )
+                        });
+                    split.content = note_template::compose_note(
+                        rendered_template.as_deref(),
+                        |front_matter| {
+                            front_matter.set_date_created(Utc::now(), operational_timezone);
+                        },
+                        &format!("{}{NEWLINE}", markdown_file.section_body(section)),
+                    )?;
+                }
+            }
+            markdown_file.extract_sections(file_extractions, operational_timezone)?;
+        }
+
+        self.section_splits = splits;
+        Ok(())
+    }
+
+    /// Writes each note `split_listed_notes` planned. A file created since the analysis is
+    /// left alone.
+    pub(super) fn create_split_notes(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for split in &self.section_splits {
+            let Some(note_path) = &split.note_path else {
+                continue;
+            };
+            if note_path.exists() {
+                continue;
+            }
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(note_path)?
+                .write_all(split.content.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+fn stem_of(path: &Path) -> String {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// The target a link to `target` takes once its heading has moved to a note of its own:
+/// `[[Trip#Day 1]]` becomes `[[Day 1]]` and `[[Trip#Day 1#Louvre]]` or `[[Trip#Louvre]]`
+/// becomes `[[Day 1#Louvre]]`. `[[#Day 1]]` names a heading of the note it's in.
+fn retargeted_link(
+    target: &str,
+    own_stem: &str,
+    extractions_by_stem: &HashMap<String, &Vec<(NoteSection, String)>>,
+) -> Option<String> {
+    let (note, headings) = target.split_once(HASH)?;
+    let note_stem = if note.trim().is_empty() {
+        own_stem.to_string()
+    } else {
+        phantom_links::target_note_stem(note)
+    };
+    let file_extractions = extractions_by_stem.get(&note_stem)?;
+    let heading = headings.rsplit(HASH).next()?.trim();
+
+    file_extractions.iter().find_map(|(section, note_name)| {
+        if section.heading_link.eq_ignore_ascii_case(heading) {
+            Some(note_name.clone())
+        } else {
+            section
+                .subheadings
+                .iter()
+                .any(|subheading| subheading.eq_ignore_ascii_case(heading))
+                .then(|| format!("{note_name}{HASH}{heading}"))
+        }
+    })
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::constants::FORMAT_DATE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_split_listed_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Trip".to_string())
+            .with_content(
+                "# Trip\n\
+                 ## Day 1\n\
+                 ### Louvre\n\
+                 ![[louvre.jpg]]\n\
+                 ## Paris\n\
+                 taken",
+            )
+            .create(&temp_dir, "trip.md");
+        TestFileBuilder::new()
+            .with_title("Paris".to_string())
+            .with_content("see [[Trip#Day 1|the first day]] and [[trip#Louvre]]")
+            .create(&temp_dir, "paris.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.split_notes(vec!["[[Trip]]".to_string()]);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let splits = &obsidian_repository.section_splits;
+        assert_eq!(splits.len(), 2);
+        assert_eq!(splits[0].note_name(), Some("Day 1"));
+        assert!(
+            splits[0]
+                .content
+                .ends_with("---\n### Louvre\n![[louvre.jpg]]\n")
+        );
+        assert_eq!(splits[1].note_name(), None, "paris.md already exists");
+
+        let file = |name: &str| {
+            obsidian_repository
+                .markdown_files
+                .iter()
+                .find(|file| file.path.ends_with(name))
+                .unwrap()
+        };
+        assert_eq!(
+            file("trip.md").content.trim_end(),
+            "# Trip\n![[Day 1]]\n## [[paris|Paris]]\ntaken"
+        );
+        assert_eq!(
+            file("paris.md").content.trim_end(),
+            "see [[Day 1|the first day]] and [[Day 1#Louvre]]"
+        );
+        assert!(
+            obsidian_repository
+                .collect_unresolved_links(FORMAT_DATE)
+                .is_empty()
+        );
+
+        obsidian_repository.persist().unwrap();
+        let day_one = fs::read_to_string(temp_dir.path().join("Day 1.md")).unwrap();
+        assert_eq!(day_one, splits[0].content);
+    }
+}
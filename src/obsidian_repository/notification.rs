@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use serde::Serialize;
+
+use super::ObsidianRepository;
+use crate::constants::CURL_ARGS;
+use crate::constants::CURL_EXECUTABLE;
+use crate::constants::NEWLINE;
+use crate::constants::NOTIFICATION_SUBJECT;
+use crate::constants::NOTIFICATION_TO;
+use crate::constants::NOTIFY_MAILTO_SCHEME;
+use crate::constants::SENDMAIL_EXECUTABLE;
+use crate::constants::SENDMAIL_READ_RECIPIENTS_FLAG;
+use crate::image_file::DeletionStatus;
+use crate::validated_config::ChangeMode;
+use crate::validated_config::ValidatedConfig;
+
+/// What `notify` sends after a run. The counts are what the run changed - or, with apply
+/// changes off, what it would have changed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct RunSummary {
+    pub vault:          String,
+    pub date:           String,
+    pub apply_changes:  bool,
+    pub health_score:   Option<usize>,
+    pub files_changed:  usize,
+    /// Notes left unwritten because they changed on disk during the run.
+    pub files_skipped:  usize,
+    pub images_deleted: usize,
+    /// The notes changed for each reason, keyed by the reason as the reports name it.
+    pub changes:        BTreeMap<String, usize>,
+}
+
+impl ObsidianRepository {
+    fn run_summary(&self, validated_config: &ValidatedConfig, skipped: &[PathBuf]) -> RunSummary {
+        let files_to_persist = self.markdown_files.files_to_persist();
+
+        let mut changes: BTreeMap<String, usize> = BTreeMap::new();
+        for markdown_file in &files_to_persist {
+            let mut reasons: Vec<String> = markdown_file
+                .persist_reasons
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            reasons.sort_unstable();
+            reasons.dedup();
+            for reason in reasons {
+                *changes.entry(reason).or_default() += 1;
+            }
+        }
+
+        RunSummary {
+            vault: validated_config
+                .obsidian_path()
+                .file_name()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default()
+                .to_string(),
+            date: validated_config.today(),
+            apply_changes: matches!(validated_config.change_mode(), ChangeMode::Apply),
+            health_score: self.vault_health_history.last().map(|entry| entry.score),
+            files_changed: files_to_persist.len().saturating_sub(skipped.len()),
+            files_skipped: skipped.len(),
+            images_deleted: self
+                .image_files
+                .iter()
+                .filter(|image| image.deletion_status == DeletionStatus::Delete)
+                .count(),
+            changes,
+        }
+    }
+
+    /// With `notify` set, sends the run summary - as JSON posted to a webhook with `curl`, or
+    /// as YAML mailed with `sendmail` for a `mailto:` address.
+    pub(crate) fn notify(
+        &self,
+        validated_config: &ValidatedConfig,
+        skipped: &[PathBuf],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(notify) = validated_config.notify() else {
+            return Ok(());
+        };
+        let summary = self.run_summary(validated_config, skipped);
+
+        if let Some(address) = notify.strip_prefix(NOTIFY_MAILTO_SCHEME) {
+            let message = format!(
+                "{NOTIFICATION_TO}{address}{NEWLINE}{NOTIFICATION_SUBJECT}{NEWLINE}{NEWLINE}{}",
+                serde_yaml::to_string(&summary)?
+            );
+            let mut sendmail = Command::new(SENDMAIL_EXECUTABLE);
+            sendmail.arg(SENDMAIL_READ_RECIPIENTS_FLAG);
+            pipe_to_command(&mut sendmail, &message)
+        } else {
+            let mut curl = Command::new(CURL_EXECUTABLE);
+            curl.args(CURL_ARGS).arg(notify);
+            pipe_to_command(&mut curl, &serde_json::to_string(&summary)?)
+        }
+    }
+}
+
+fn pipe_to_command(command: &mut Command, input: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "{} {}: {}",
+            command.get_program().to_string_lossy(),
+            output.status,
+            stderr.trim()
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_run_summary_counts_changes_by_reason() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Tokyo".to_string())
+            .create(&temp_dir, "Tokyo.md");
+        for file_name in ["Trip.md", "Plans.md"] {
+            TestFileBuilder::new()
+                .with_title(file_name.trim_end_matches(".md").to_string())
+                .with_content("We flew to Tokyo.".to_string())
+                .create(&temp_dir, file_name);
+        }
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.notify(Some("mailto:me@example.com".to_string()));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        let summary = obsidian_repository.run_summary(&validated_config, &[]);
+
+        assert!(!summary.apply_changes);
+        assert_eq!(summary.files_skipped, 0);
+        assert_eq!(summary.images_deleted, 0);
+        assert_eq!(summary.changes.get("back populated"), Some(&2));
+        assert!(summary.files_changed >= 2);
+        assert_eq!(
+            obsidian_repository
+                .run_summary(&validated_config, &[temp_dir.path().join("Trip.md")])
+                .files_skipped,
+            1
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use super::constants::OBSIDIAN_ATTACHMENT_EXTENSIONS;
+
+/// The files of one extension that are neither notes, images, canvases nor bases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct OtherFileType {
+    /// Lowercased, without the dot - empty for files without an extension.
+    pub extension:  String,
+    pub count:      usize,
+    pub total_size: u64,
+    /// Obsidian can't open it, so it probably doesn't belong in the vault.
+    pub unexpected: bool,
+}
+
+impl ObsidianRepository {
+    /// Groups the other files by extension, unexpected types first and then by extension.
+    pub(super) fn summarize_other_files(other_files: &[PathBuf]) -> Vec<OtherFileType> {
+        let mut by_extension: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+        for path in other_files {
+            let extension = path
+                .extension()
+                .and_then(OsStr::to_str)
+                .map(str::to_lowercase)
+                .unwrap_or_default();
+            let size = fs::metadata(path).map_or(0, |metadata| metadata.len());
+            let (count, total_size) = by_extension.entry(extension).or_default();
+            *count += 1;
+            *total_size += size;
+        }
+
+        let mut other_file_types: Vec<OtherFileType> = by_extension
+            .into_iter()
+            .map(|(extension, (count, total_size))| OtherFileType {
+                unexpected: !OBSIDIAN_ATTACHMENT_EXTENSIONS.contains(&extension.as_str()),
+                extension,
+                count,
+                total_size,
+            })
+            .collect();
+        other_file_types.sort_by_key(|other_file_type| !other_file_type.unexpected);
+        other_file_types
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+
+    #[test]
+    fn test_other_files_summarized_by_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("song.mp3"), "12345").unwrap();
+        fs::write(temp_dir.path().join("Report.DOCX"), "123").unwrap();
+        fs::write(temp_dir.path().join("old.docx"), "1").unwrap();
+        fs::create_dir(temp_dir.path().join(".trash")).unwrap();
+        fs::write(temp_dir.path().join(".trash").join("deleted.zip"), "1").unwrap();
+
+        let validated_config = test_support::get_test_validated_config(&temp_dir, None);
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let summary: Vec<(&str, usize, u64, bool)> = obsidian_repository
+            .other_file_types
+            .iter()
+            .map(|other_file_type| {
+                (
+                    other_file_type.extension.as_str(),
+                    other_file_type.count,
+                    other_file_type.total_size,
+                    other_file_type.unexpected,
+                )
+            })
+            .collect();
+        assert_eq!(summary, vec![("docx", 2, 4, true), ("mp3", 1, 5, false)]);
+    }
+}
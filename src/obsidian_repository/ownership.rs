@@ -0,0 +1,233 @@
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use super::constants::OWNER_PROPERTY;
+use super::constants::PATH_RENAME_CHANGE;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::OPENING_WIKILINK;
+use crate::frontmatter::FrontMatter;
+use crate::markdown_file::MarkdownFile;
+use crate::validated_config::ValidatedConfig;
+
+/// A note with changes to write whose `owner` property names someone else, so they're skipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ForeignOwnedNote {
+    pub path:    PathBuf,
+    pub owner:   String,
+    /// The skipped changes, as `audit_trail` would record them.
+    pub changes: Vec<String>,
+}
+
+impl ObsidianRepository {
+    /// With `only_modify_owner` set, discards the changes to every note whose `owner` property
+    /// names someone else, along with its queued rename and what depended on its rewrite. Notes
+    /// without an `owner` are shared, so they're changed as usual. Runs last in the analysis so
+    /// every change is known.
+    pub(crate) fn guard_foreign_owned_notes(&mut self, validated_config: &ValidatedConfig) {
+        let Some(owner) = validated_config.only_modify_owner() else {
+            return;
+        };
+        let owner = normalized_owner(owner);
+
+        let mut foreign_owned_notes = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            let Some(front_matter) = markdown_file.front_matter.as_ref() else {
+                continue;
+            };
+            let owners = front_matter.property_strings(OWNER_PROPERTY);
+            if owners.is_empty()
+                || owners
+                    .iter()
+                    .any(|note_owner| normalized_owner(note_owner) == owner)
+            {
+                continue;
+            }
+            let foreign_owner = owners.join(", ");
+
+            let changes = discard_changes(markdown_file, &self.paths_to_rename);
+            if changes.is_empty() {
+                continue;
+            }
+            foreign_owned_notes.push(ForeignOwnedNote {
+                path: markdown_file.path.clone(),
+                owner: foreign_owner,
+                changes,
+            });
+        }
+
+        let discarded: Vec<PathBuf> = foreign_owned_notes
+            .iter()
+            .map(|foreign_owned_note| foreign_owned_note.path.clone())
+            .collect();
+        self.release_discarded_notes(&discarded);
+        self.foreign_owned_notes = foreign_owned_notes;
+    }
+
+    /// Drops the renames of `discarded` notes and what depended on their rewrites: the notes
+    /// split out of them, the conflict copies merged into them and the images whose references
+    /// moved - the note on disk still needs all of those.
+    pub(super) fn release_discarded_notes(&mut self, discarded: &[PathBuf]) {
+        self.paths_to_rename
+            .retain(|(path, _)| !discarded.contains(path));
+        self.release_restored_bodies(discarded);
+    }
+}
+
+/// Leaves the note as it is on disk, returning the changes it would have had, as `audit_trail`
+/// records them - empty when it had none. The caller passes it to `release_discarded_notes`.
+pub(super) fn discard_changes(
+    markdown_file: &mut MarkdownFile,
+    paths_to_rename: &[(PathBuf, PathBuf)],
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    if markdown_file
+        .front_matter
+        .as_ref()
+        .is_some_and(FrontMatter::needs_persist)
+    {
+        changes.extend(markdown_file.audit_changes());
+    }
+    if paths_to_rename
+        .iter()
+        .any(|(path, _)| *path == markdown_file.path)
+    {
+        changes.push(PATH_RENAME_CHANGE.to_string());
+    }
+    if changes.is_empty() {
+        return changes;
+    }
+
+    if let Some(front_matter) = markdown_file.front_matter.as_mut() {
+        front_matter.discard_changes();
+    }
+    markdown_file.persist_reasons.clear();
+    changes
+}
+
+/// `[[Alice]]` and `alice` name the same owner.
+fn normalized_owner(owner: &str) -> String {
+    owner
+        .trim()
+        .trim_start_matches(OPENING_WIKILINK)
+        .trim_end_matches(CLOSING_WIKILINK)
+        .trim()
+        .to_lowercase()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::config::ConflictResolution;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::obsidian_repository::conflict_copies::ConflictCopyAction;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_guard_foreign_owned_notes_skips_other_owners() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Topic".to_string())
+            .with_content("about the topic")
+            .create(&temp_dir, "topic.md");
+        for (name, owner) in [
+            ("mine.md", "owner: \"[[Alice]]\""),
+            ("theirs.md", "owner: bob"),
+            ("shared.md", "tags: [shared]"),
+        ] {
+            TestFileBuilder::new()
+                .with_custom_frontmatter(owner.to_string())
+                .with_content("mentions Topic")
+                .create(&temp_dir, name);
+        }
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.only_modify_owner(Some("alice".to_string()));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let persisted: Vec<String> = obsidian_repository
+            .markdown_files
+            .files_to_persist()
+            .iter()
+            .filter_map(|file| file.path.file_name()?.to_str().map(str::to_string))
+            .collect();
+        assert!(persisted.contains(&"mine.md".to_string()));
+        assert!(persisted.contains(&"shared.md".to_string()));
+        assert!(!persisted.contains(&"theirs.md".to_string()));
+
+        assert_eq!(obsidian_repository.foreign_owned_notes.len(), 1);
+        let foreign_owned_note = &obsidian_repository.foreign_owned_notes[0];
+        assert!(foreign_owned_note.path.ends_with("theirs.md"));
+        assert_eq!(foreign_owned_note.owner, "bob");
+        assert!(
+            foreign_owned_note
+                .changes
+                .iter()
+                .any(|change| change.contains("back populated")),
+            "{:?}",
+            foreign_owned_note.changes
+        );
+    }
+
+    #[test]
+    fn test_foreign_owned_note_keeps_its_images_and_conflict_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let image = vec![0xFF, 0xD8, 0xFF, 0xE0];
+        for name in ["first.jpg", "second.jpg"] {
+            TestFileBuilder::new()
+                .with_content(image.clone())
+                .create(&temp_dir, name);
+        }
+        for (name, content) in [
+            ("Plan.md", "![[first.jpg]]\n![[second.jpg]]"),
+            ("Plan (conflicted copy 2024-05-02).md", "a later idea"),
+        ] {
+            TestFileBuilder::new()
+                .with_custom_frontmatter("owner: bob".to_string())
+                .with_content(content)
+                .create(&temp_dir, name);
+        }
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder
+                    .only_modify_owner(Some("alice".to_string()))
+                    .resolve_conflict_copies(Some(ConflictResolution::Merge));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        assert_eq!(obsidian_repository.foreign_owned_notes.len(), 1);
+        assert_eq!(
+            obsidian_repository.conflict_copies[0].action,
+            ConflictCopyAction::Keep
+        );
+
+        let plan = fs::read_to_string(temp_dir.path().join("Plan.md")).unwrap();
+        obsidian_repository.persist().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("Plan.md")).unwrap(),
+            plan
+        );
+        for name in [
+            "first.jpg",
+            "second.jpg",
+            "Plan (conflicted copy 2024-05-02).md",
+        ] {
+            assert!(
+                temp_dir.path().join(name).exists(),
+                "{name} is still needed by the untouched note"
+            );
+        }
+    }
+}
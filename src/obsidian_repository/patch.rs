@@ -0,0 +1,521 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+
+use super::ObsidianRepository;
+use super::conflict_copies::ConflictCopyAction;
+use super::periodic_notes::PeriodicNoteAction;
+use crate::constants::PATCH_CATEGORY_CONFLICT_COPIES;
+use crate::constants::PATCH_CATEGORY_IMAGE_DUPLICATES;
+use crate::constants::PATCH_CATEGORY_IMAGE_INCOMPATIBLE;
+use crate::constants::PATCH_CATEGORY_IMAGE_UNREFERENCED;
+use crate::constants::PATCH_CATEGORY_LINK_TARGETS;
+use crate::constants::PATCH_CATEGORY_PERIODIC_NOTES;
+use crate::constants::PATCH_CATEGORY_SPLIT_NOTES;
+use crate::constants::PATCH_FILE;
+use crate::constants::PATCH_WRITTEN;
+use crate::image_file::DeletionStatus;
+use crate::image_file::ImageFileState;
+use crate::markdown_file::PersistReason;
+use crate::patch;
+use crate::patch::Patch;
+use crate::patch::PatchChange;
+use crate::patch::PatchOperation;
+use crate::sha256_cache;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// What `persist` would do, as a patch: images to delete, notes to create, notes to
+    /// rewrite and conflict copies to remove, in the order `persist` does them. Renames and
+    /// trash purges aren't included - they only happen in a run with `apply_changes` on.
+    pub(crate) fn build_patch(&self, validated_config: &ValidatedConfig) -> Patch {
+        let mut operations = Vec::new();
+
+        for image_file in &self.image_files.images {
+            if image_file.deletion_status == DeletionStatus::Delete
+                && let Some(category) = image_category(&image_file.state)
+                && let Ok(contents) = fs::read(&image_file.path)
+            {
+                operations.push(PatchOperation {
+                    path:       image_file.path.clone(),
+                    categories: vec![category.to_string()],
+                    incidental: Vec::new(),
+                    requires:   image_file.references.clone(),
+                    change:     PatchChange::Delete {
+                        hash: sha256_cache::hash_contents(&contents),
+                    },
+                });
+            }
+        }
+
+        operations.extend(self.create_operations());
+
+        let files_to_persist = self.markdown_files.files_to_persist();
+        for markdown_file in &files_to_persist {
+            let Some(front_matter) = markdown_file.front_matter.as_ref() else {
+                continue;
+            };
+            let Some(date_modified) = front_matter.raw_modified else {
+                continue;
+            };
+            let categories: BTreeSet<&str> = markdown_file
+                .persist_reasons
+                .iter()
+                .map(PersistReason::patch_category)
+                .collect();
+            let standalone: BTreeSet<&str> = markdown_file
+                .persist_reasons
+                .iter()
+                .filter(|reason| !reason.syncs_file_dates())
+                .map(PersistReason::patch_category)
+                .collect();
+            // A split-out section leaves the note only once its new note is written.
+            let split_notes = self
+                .section_splits
+                .iter()
+                .filter(|split| split.source == markdown_file.path)
+                .filter_map(|split| split.note_path.clone())
+                .collect();
+            operations.push(PatchOperation {
+                path:       markdown_file.path.clone(),
+                categories: categories
+                    .iter()
+                    .map(|category| (*category).to_string())
+                    .collect(),
+                incidental: categories
+                    .difference(&standalone)
+                    .map(|category| (*category).to_string())
+                    .collect(),
+                requires:   split_notes,
+                change:     PatchChange::Write {
+                    hash:          markdown_file.content_hash.clone(),
+                    content:       markdown_file.to_full_content(),
+                    date_created:  front_matter.raw_created.map(patch::format_date),
+                    date_modified: patch::format_date(date_modified),
+                },
+            });
+        }
+
+        for conflict_copy in &self.conflict_copies {
+            let merged = conflict_copy.action == ConflictCopyAction::Merge;
+            let resolved = match conflict_copy.action {
+                ConflictCopyAction::Delete => true,
+                ConflictCopyAction::Merge => files_to_persist
+                    .iter()
+                    .any(|markdown_file| markdown_file.path == conflict_copy.original),
+                ConflictCopyAction::Keep | ConflictCopyAction::Linked => false,
+            };
+            if resolved {
+                operations.push(PatchOperation {
+                    path:       conflict_copy.copy.clone(),
+                    categories: vec![PATCH_CATEGORY_CONFLICT_COPIES.to_string()],
+                    incidental: Vec::new(),
+                    requires:   if merged {
+                        vec![conflict_copy.original.clone()]
+                    } else {
+                        Vec::new()
+                    },
+                    change:     PatchChange::Delete {
+                        hash: conflict_copy.copy_hash.clone(),
+                    },
+                });
+            }
+        }
+
+        Patch {
+            obsidian_path: validated_config.obsidian_path().to_path_buf(),
+            output_folder: validated_config.output_folder().to_path_buf(),
+            operations,
+        }
+    }
+
+    /// The notes `persist` creates that don't exist yet - a split-out note can't be created
+    /// without the rewrite that takes its section out of the source note.
+    fn create_operations(&self) -> Vec<PatchOperation> {
+        let mut operations = Vec::new();
+        let created = self
+            .link_targets_to_create
+            .iter()
+            .map(|link_target| {
+                (
+                    &link_target.path,
+                    &link_target.content,
+                    PATCH_CATEGORY_LINK_TARGETS,
+                    Vec::new(),
+                )
+            })
+            .chain(self.section_splits.iter().filter_map(|split| {
+                split.note_path.as_ref().map(|note_path| {
+                    (
+                        note_path,
+                        &split.content,
+                        PATCH_CATEGORY_SPLIT_NOTES,
+                        vec![split.source.clone()],
+                    )
+                })
+            }))
+            .chain(
+                self.periodic_note_changes
+                    .iter()
+                    .filter(|change| change.action == PeriodicNoteAction::Create)
+                    .map(|change| {
+                        (
+                            &change.path,
+                            &change.content,
+                            PATCH_CATEGORY_PERIODIC_NOTES,
+                            Vec::new(),
+                        )
+                    }),
+            );
+        for (path, content, category, requires) in created {
+            if !path.exists() {
+                operations.push(PatchOperation {
+                    path: path.clone(),
+                    categories: vec![category.to_string()],
+                    incidental: Vec::new(),
+                    requires,
+                    change: PatchChange::Create {
+                        content: content.clone(),
+                    },
+                });
+            }
+        }
+        operations
+    }
+
+    /// With `write_patch` set, saves the patch to the output folder instead of changing the
+    /// vault.
+    pub(crate) fn write_patch(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if !validated_config.write_patch() {
+            return Ok(());
+        }
+
+        let patch = self.build_patch(validated_config);
+        let patch_path = validated_config.output_folder().join(PATCH_FILE);
+        patch.write(&patch_path)?;
+        println!(
+            "{PATCH_WRITTEN} {} changes in {}",
+            patch.operations.len(),
+            patch_path.display()
+        );
+        Ok(())
+    }
+}
+
+/// Valid images and the copies of a duplicate that are kept are never deleted.
+const fn image_category(state: &ImageFileState) -> Option<&'static str> {
+    match state {
+        ImageFileState::Duplicate { .. } => Some(PATCH_CATEGORY_IMAGE_DUPLICATES),
+        ImageFileState::Incompatible { .. } => Some(PATCH_CATEGORY_IMAGE_INCOMPATIBLE),
+        ImageFileState::Unreferenced => Some(PATCH_CATEGORY_IMAGE_UNREFERENCED),
+        ImageFileState::DuplicateKeeper { .. } | ImageFileState::Valid => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::constants::FORMAT_DATE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::patch;
+    use crate::patch::Patch;
+    use crate::patch::PatchChange;
+    use crate::patch::PatchError;
+    use crate::patch::PatchOperation;
+    use crate::sha256_cache;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_patch_applies_only_to_unchanged_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let date = test_support::eastern_midnight(2024, 1, 1);
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "Alpha.md");
+        let notes_path = TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_content("Mentions Alpha here".to_string())
+            .create(&temp_dir, "Notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let patch = repository.build_patch(&validated_config);
+        let written = patch
+            .operations
+            .iter()
+            .find_map(|operation| match &operation.change {
+                PatchChange::Write { content, .. } if operation.path == notes_path => {
+                    Some(content.clone())
+                },
+                _ => None,
+            })
+            .unwrap();
+        assert!(written.contains("[[Alpha]]"));
+
+        let original = fs::read_to_string(&notes_path).unwrap();
+        fs::write(&notes_path, format!("{original}\nedited")).unwrap();
+        assert!(
+            matches!(patch.apply(), Err(PatchError::Stale(paths)) if paths == vec![notes_path.clone()])
+        );
+        assert!(
+            !fs::read_to_string(&notes_path)
+                .unwrap()
+                .contains("[[Alpha]]")
+        );
+
+        fs::write(&notes_path, original).unwrap();
+        assert_eq!(patch.apply().unwrap(), patch.operations.len());
+        assert_eq!(fs::read_to_string(&notes_path).unwrap(), written);
+    }
+
+    #[test]
+    fn test_patch_filter_selects_by_category_and_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let date = test_support::eastern_midnight(2024, 1, 1);
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "Alpha.md");
+        let notes_path = TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_content("Mentions Alpha here".to_string())
+            .create(&temp_dir, "Notes.md");
+        fs::create_dir(temp_dir.path().join("journal")).unwrap();
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_content("Alpha again".to_string())
+            .create(&temp_dir, "journal/Day.md");
+        fs::write(temp_dir.path().join("unused.png"), b"image").unwrap();
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let patch = repository.build_patch(&validated_config);
+        assert!(patch.operations.iter().any(|operation| {
+            operation.categories == vec!["images:unreferenced"]
+                && matches!(operation.change, PatchChange::Delete { .. })
+        }));
+
+        let args: Vec<String> = [
+            "patch.json",
+            "--only",
+            "back-populate",
+            "--exclude",
+            "journal/**",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        let (_, filter) = patch::from_args(&args).unwrap();
+        let filtered = patch.filtered(&filter).unwrap();
+        assert_eq!(filtered.operations.len(), 1);
+        assert_eq!(filtered.operations[0].path, notes_path);
+
+        let args: Vec<String> = ["patch.json", "--exclude", "images"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let (_, filter) = patch::from_args(&args).unwrap();
+        assert_eq!(
+            patch.filtered(&filter).unwrap().operations.len(),
+            patch.operations.len() - 1
+        );
+
+        let args: Vec<String> = ["patch.json", "--only"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert!(matches!(patch::from_args(&args), Err(PatchError::Usage)));
+    }
+
+    #[test]
+    fn test_patch_filter_refuses_to_split_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let date = test_support::eastern_midnight(2024, 1, 1);
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "Alpha.md");
+        let notes_path = TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_content("Mentions Alpha here\n![[second.jpg]]".to_string())
+            .create(&temp_dir, "Notes.md");
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_content("![[first.jpg]]".to_string())
+            .create(&temp_dir, "Gallery.md");
+        for image in ["first.jpg", "second.jpg"] {
+            fs::write(temp_dir.path().join(image), [0xFF, 0xD8, 0xFF, 0xE0]).unwrap();
+        }
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let patch = repository.build_patch(&validated_config);
+        let filter = |values: &[&str]| {
+            let args: Vec<String> = std::iter::once("patch.json")
+                .chain(values.iter().copied())
+                .map(ToString::to_string)
+                .collect();
+            patch::from_args(&args).unwrap().1
+        };
+
+        assert!(matches!(
+            patch.filtered(&filter(&["--only", "back-populate"])),
+            Err(PatchError::Partial(paths)) if paths == vec![notes_path.clone()]
+        ));
+        assert!(matches!(
+            patch.filtered(&filter(&["--exclude", "images:references"])),
+            Err(PatchError::Partial(paths)) if paths == vec![notes_path]
+        ));
+        assert!(matches!(
+            patch.filtered(&filter(&["--only", "images:duplicates"])),
+            Err(PatchError::Dependent(paths)) if paths.len() == 1
+        ));
+        assert!(matches!(
+            patch.filtered(&filter(&["--only", "images", "--exclude", "Notes.md"])),
+            Err(PatchError::Dependent(_))
+        ));
+        // Only a note whose dates are its one change is left out.
+        let filtered = patch
+            .filtered(&filter(&["--only", "images", "--only", "back-populate"]))
+            .unwrap();
+        assert!(patch.operations.iter().all(|operation| {
+            filtered.operations.contains(operation) || operation.categories == vec!["dates"]
+        }));
+    }
+
+    #[test]
+    fn test_patch_filter_takes_date_syncs_along_with_a_rewrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let date = test_support::eastern_midnight(2024, 1, 1);
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "Alpha.md");
+        // `date_created` doesn't match the file, so the rewrite brings it in line too.
+        let notes_path = TestFileBuilder::new()
+            .with_matching_dates(date)
+            .with_frontmatter_dates(
+                Some("[[2023-06-01]]".to_string()),
+                Some(format!("[[{}]]", date.format(FORMAT_DATE))),
+            )
+            .with_content("Mentions Alpha here".to_string())
+            .create(&temp_dir, "Notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let patch = repository.build_patch(&validated_config);
+        let notes_operation = patch
+            .operations
+            .iter()
+            .find(|operation| operation.path == notes_path)
+            .unwrap();
+        assert_eq!(
+            notes_operation.categories,
+            vec!["back-populate".to_string(), "dates".to_string()]
+        );
+        assert_eq!(notes_operation.incidental, vec!["dates".to_string()]);
+
+        let filter = |values: &[&str]| {
+            let args: Vec<String> = std::iter::once("patch.json")
+                .chain(values.iter().copied())
+                .map(ToString::to_string)
+                .collect();
+            patch::from_args(&args).unwrap().1
+        };
+        let filtered = patch
+            .filtered(&filter(&["--only", "back-populate"]))
+            .unwrap();
+        assert_eq!(filtered.operations.len(), 1);
+        assert_eq!(filtered.operations[0].path, notes_path);
+
+        let filtered = patch.filtered(&filter(&["--exclude", "dates"])).unwrap();
+        assert!(
+            filtered
+                .operations
+                .iter()
+                .any(|operation| operation.path == notes_path)
+        );
+    }
+
+    #[test]
+    fn test_patch_failing_part_way_undoes_what_it_made() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_path = TestFileBuilder::new()
+            .with_content("before".to_string())
+            .create(&temp_dir, "Notes.md");
+        let old_path = TestFileBuilder::new()
+            .with_content("old".to_string())
+            .create(&temp_dir, "Old.md");
+        let hash = |path| sha256_cache::hash_contents(&fs::read(path).unwrap());
+        let moved_folder = temp_dir.path().join("Moved");
+
+        // The last create clashes with the folder the one before it makes, so it only fails
+        // once the others are made.
+        let operation = |path, change| PatchOperation {
+            path,
+            categories: vec!["back-populate".to_string()],
+            incidental: Vec::new(),
+            requires: Vec::new(),
+            change,
+        };
+        let patch = Patch {
+            obsidian_path: temp_dir.path().to_path_buf(),
+            output_folder: temp_dir.path().join("output"),
+            operations:    vec![
+                operation(
+                    notes_path.clone(),
+                    PatchChange::Write {
+                        hash:          hash(&notes_path),
+                        content:       "after".to_string(),
+                        date_created:  None,
+                        date_modified: "2024-01-01T00:00:00+00:00".to_string(),
+                    },
+                ),
+                operation(
+                    old_path.clone(),
+                    PatchChange::Delete {
+                        hash: hash(&old_path),
+                    },
+                ),
+                operation(
+                    moved_folder.join("New.md"),
+                    PatchChange::Create {
+                        content: "new".to_string(),
+                    },
+                ),
+                operation(
+                    moved_folder.clone(),
+                    PatchChange::Create {
+                        content: "clash".to_string(),
+                    },
+                ),
+            ],
+        };
+
+        assert!(matches!(
+            patch.apply(),
+            Err(PatchError::RolledBack { path, .. }) if path == moved_folder
+        ));
+        assert!(fs::read_to_string(&notes_path).unwrap().contains("before"));
+        assert!(fs::read_to_string(&old_path).unwrap().contains("old"));
+        assert!(!moved_folder.exists());
+        let left_behind: Vec<String> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with('.'))
+            .collect();
+        assert!(left_behind.is_empty(), "{left_behind:?}");
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use super::constants::MAX_VAULT_RELATIVE_PATH_LENGTH;
+use super::constants::MIN_SHORTENED_STEM_LENGTH;
+use super::constants::RESERVED_NAME_SUFFIX;
+use super::constants::TRAILING_PATH_CHARACTERS;
+use super::constants::UNSAFE_PATH_CHARACTER_REPLACEMENT;
+use super::constants::UNSAFE_PATH_CHARACTERS;
+use super::constants::WINDOWS_RESERVED_NAMES;
+use crate::constants::INVALID_PATH_CHARACTERS;
+use crate::constants::NAME_TAKEN;
+use crate::constants::PATH_TOO_LONG;
+use crate::constants::RENAME;
+use crate::constants::RENAME_FOLDER;
+use crate::constants::RENAME_IN_OBSIDIAN;
+use crate::constants::RESERVED_NAME;
+use crate::constants::TRAILING_DOT_OR_SPACE;
+use crate::validated_config::ValidatedConfig;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PathIssueKind {
+    /// `"*:<>?\|` or a control character in a file or folder name.
+    InvalidCharacters,
+    /// Longer than `MAX_VAULT_RELATIVE_PATH_LENGTH`, counted the way Windows counts.
+    TooLong,
+    /// `CON`, `NUL`, `COM1` and the other Windows device names.
+    ReservedName,
+    /// Windows strips a trailing dot or space, so the name no longer matches.
+    TrailingDotOrSpace,
+}
+
+impl Display for PathIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacters => f.write_str(INVALID_PATH_CHARACTERS),
+            Self::TooLong => f.write_str(PATH_TOO_LONG),
+            Self::ReservedName => f.write_str(RESERVED_NAME),
+            Self::TrailingDotOrSpace => f.write_str(TRAILING_DOT_OR_SPACE),
+        }
+    }
+}
+
+/// How the suggested name can be applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PathFix {
+    /// Only the file name changes and nothing links to the file, so ok can rename it.
+    Rename,
+    /// Links point at the file - renaming it in Obsidian updates them.
+    RenameInObsidian,
+    /// A folder on the path needs the change.
+    RenameFolder,
+    /// A file with the suggested name already exists.
+    NameTaken,
+}
+
+impl Display for PathFix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rename => f.write_str(RENAME),
+            Self::RenameInObsidian => f.write_str(RENAME_IN_OBSIDIAN),
+            Self::RenameFolder => f.write_str(RENAME_FOLDER),
+            Self::NameTaken => f.write_str(NAME_TAKEN),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct PathIssue {
+    pub fix:       PathFix,
+    pub kinds:     Vec<PathIssueKind>,
+    pub length:    usize,
+    pub path:      PathBuf,
+    pub suggested: PathBuf,
+}
+
+impl ObsidianRepository {
+    /// Checks every note, image, base and canvas path for names Windows or Android can't store,
+    /// so a vault synced from macOS doesn't silently lose files. With `rename_unsafe_paths` on,
+    /// files that only need a new file name and that nothing links to are queued for `persist`
+    /// to rename. Canvas file cards don't count as links - `persist` points them at the new name.
+    pub(crate) fn audit_paths(&mut self, validated_config: &ValidatedConfig) {
+        let obsidian_path = validated_config.obsidian_path();
+        let reference_counts = self.reference_counts();
+        let wikilinked_names = self.structured_file_wikilink_names();
+        let structured_paths: HashSet<&PathBuf> = self
+            .structured_files
+            .iter()
+            .map(|structured_file| &structured_file.path)
+            .collect();
+        let is_wikilinked = |path: &Path| {
+            path.file_name()
+                .and_then(OsStr::to_str)
+                .is_some_and(|name| wikilinked_names.contains(&name.to_lowercase()))
+        };
+
+        let mut path_issues: Vec<PathIssue> = self
+            .markdown_files
+            .iter()
+            .map(|markdown_file| {
+                let stem = markdown_file
+                    .path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or_default()
+                    .to_lowercase();
+                let is_referenced =
+                    reference_counts.contains_key(&stem) || wikilinked_names.contains(&stem);
+                (&markdown_file.path, is_referenced)
+            })
+            .chain(self.image_files.iter().map(|image_file| {
+                let is_referenced = image_file
+                    .references
+                    .iter()
+                    .any(|reference| !structured_paths.contains(reference))
+                    || is_wikilinked(&image_file.path);
+                (&image_file.path, is_referenced)
+            }))
+            .chain(self.structured_files.iter().map(|structured_file| {
+                (&structured_file.path, is_wikilinked(&structured_file.path))
+            }))
+            .filter_map(|(path, is_referenced)| audit_path(path, obsidian_path, is_referenced))
+            .collect();
+        path_issues.sort_by(|a, b| a.path.cmp(&b.path));
+
+        mark_taken_names(&mut path_issues);
+
+        if validated_config.rename_unsafe_paths() {
+            self.paths_to_rename = path_issues
+                .iter()
+                .filter(|issue| issue.fix == PathFix::Rename)
+                .map(|issue| (issue.path.clone(), issue.suggested.clone()))
+                .collect();
+        }
+        self.path_issues = path_issues;
+    }
+
+    /// Renames the files `audit_paths` queued. A name taken since the analysis is left alone.
+    pub(super) fn rename_unsafe_paths(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for (path, suggested) in &self.paths_to_rename {
+            if path.exists() && !suggested.exists() {
+                fs::rename(path, suggested)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn audit_path(path: &Path, obsidian_path: &Path, is_referenced: bool) -> Option<PathIssue> {
+    let relative_path = path.strip_prefix(obsidian_path).unwrap_or(path);
+    let names: Vec<&str> = relative_path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+    let (file_name, folders) = names.split_last()?;
+
+    let mut kinds = Vec::new();
+    let mut push_kind = |kind| {
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    };
+    for name in &names {
+        if name.chars().any(is_unsafe_character) {
+            push_kind(PathIssueKind::InvalidCharacters);
+        }
+        if is_reserved_name(name) {
+            push_kind(PathIssueKind::ReservedName);
+        }
+        if name.ends_with(TRAILING_PATH_CHARACTERS) {
+            push_kind(PathIssueKind::TrailingDotOrSpace);
+        }
+    }
+    let length = windows_length(relative_path);
+    if length > MAX_VAULT_RELATIVE_PATH_LENGTH {
+        push_kind(PathIssueKind::TooLong);
+    }
+
+    if kinds.is_empty() {
+        return None;
+    }
+
+    let sanitized_folders: Vec<String> = folders.iter().map(|name| sanitize_name(name)).collect();
+    let folders_unchanged = sanitized_folders
+        .iter()
+        .zip(folders.iter())
+        .all(|(sanitized, original)| sanitized == original);
+    let relative_folder: PathBuf = sanitized_folders.iter().collect();
+    // the separator between the folder and the file name counts toward the limit too
+    let folder_length =
+        windows_length(&relative_folder) + usize::from(!sanitized_folders.is_empty());
+    let suggested = obsidian_path
+        .join(relative_folder)
+        .join(shortened_file_name(
+            &sanitize_file_name(file_name),
+            folder_length,
+        ));
+
+    // a folder too deep to fit can only be fixed by shortening the folders themselves
+    let fix = if !folders_unchanged || suggested == path {
+        PathFix::RenameFolder
+    } else if is_referenced {
+        PathFix::RenameInObsidian
+    } else {
+        PathFix::Rename
+    };
+
+    Some(PathIssue {
+        fix,
+        kinds,
+        length,
+        path: path.to_path_buf(),
+        suggested,
+    })
+}
+
+/// Two issues suggesting the same name, or a suggestion naming an existing file, can't both be
+/// renamed - the later ones are marked `NameTaken`.
+fn mark_taken_names(path_issues: &mut [PathIssue]) {
+    // Windows and macOS compare names without case, so `A.md` and `a.md` collide there.
+    let mut suggestion_counts: HashMap<String, usize> = HashMap::new();
+    for issue in path_issues.iter_mut() {
+        let count = suggestion_counts
+            .entry(issue.suggested.to_string_lossy().to_lowercase())
+            .or_default();
+        *count += 1;
+        if (*count > 1 || issue.suggested.exists()) && issue.fix == PathFix::Rename {
+            issue.fix = PathFix::NameTaken;
+        }
+    }
+}
+
+fn is_unsafe_character(c: char) -> bool { UNSAFE_PATH_CHARACTERS.contains(&c) || c.is_control() }
+
+/// `aux.md` and `Con.tar.gz` are reserved too - Windows only looks at the part before the first
+/// dot.
+fn is_reserved_name(name: &str) -> bool {
+    let base = name.split('.').next().unwrap_or(name).trim_end();
+    WINDOWS_RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(base))
+}
+
+/// Path length in UTF-16 code units - what the Windows limit counts.
+fn windows_length(path: &Path) -> usize { path.to_string_lossy().encode_utf16().count() }
+
+pub(super) fn sanitize_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| {
+            if is_unsafe_character(c) {
+                UNSAFE_PATH_CHARACTER_REPLACEMENT
+            } else {
+                c
+            }
+        })
+        .collect();
+    let mut sanitized = replaced
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(TRAILING_PATH_CHARACTERS)
+        .to_string();
+
+    if sanitized.is_empty() || is_reserved_name(&sanitized) {
+        sanitized.push(RESERVED_NAME_SUFFIX);
+    }
+    sanitized
+}
+
+/// Sanitizes the stem and keeps the extension, so `aux.md` becomes `aux_.md`.
+fn sanitize_file_name(file_name: &str) -> String {
+    let path = Path::new(file_name);
+    match (
+        path.file_stem().and_then(OsStr::to_str),
+        path.extension().and_then(OsStr::to_str),
+    ) {
+        (Some(stem), Some(extension)) => format!("{}.{extension}", sanitize_name(stem)),
+        _ => sanitize_name(file_name),
+    }
+}
+
+/// Trims the stem until `folder_length` plus the file name fits the limit, keeping at least
+/// `MIN_SHORTENED_STEM_LENGTH` characters - a folder too deep to fit is reported, not fixed.
+fn shortened_file_name(file_name: &str, folder_length: usize) -> String {
+    let available = MAX_VAULT_RELATIVE_PATH_LENGTH.saturating_sub(folder_length);
+    let excess = file_name.encode_utf16().count().saturating_sub(available);
+    if excess == 0 {
+        return file_name.to_string();
+    }
+
+    let path = Path::new(file_name);
+    let stem = path
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or(file_name);
+    let extension = path.extension().and_then(OsStr::to_str);
+
+    let stem_length = stem.encode_utf16().count();
+    let keep = stem_length
+        .saturating_sub(excess)
+        .max(MIN_SHORTENED_STEM_LENGTH.min(stem_length));
+    let mut kept_length = 0;
+    let shortened: String = stem
+        .chars()
+        .take_while(|c| {
+            kept_length += c.len_utf16();
+            kept_length <= keep
+        })
+        .collect();
+    let shortened = shortened.trim_end_matches(TRAILING_PATH_CHARACTERS);
+
+    extension.map_or_else(
+        || shortened.to_string(),
+        |extension| format!("{shortened}.{extension}"),
+    )
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::path::Path;
+
+    use super::PathFix;
+    use super::PathIssueKind;
+    use super::audit_path;
+
+    #[test]
+    fn test_audit_path() {
+        let vault = Path::new("/vault");
+
+        let issue = audit_path(Path::new("/vault/notes/Meeting: plan?.md"), vault, false).unwrap();
+        assert_eq!(issue.kinds, vec![PathIssueKind::InvalidCharacters]);
+        assert_eq!(issue.suggested, Path::new("/vault/notes/Meeting plan.md"));
+        assert_eq!(issue.fix, PathFix::Rename);
+
+        let issue = audit_path(Path::new("/vault/aux.md"), vault, true).unwrap();
+        assert_eq!(issue.kinds, vec![PathIssueKind::ReservedName]);
+        assert_eq!(issue.suggested, Path::new("/vault/aux_.md"));
+        assert_eq!(issue.fix, PathFix::RenameInObsidian);
+
+        let issue = audit_path(Path::new("/vault/draft. /note.md"), vault, false).unwrap();
+        assert_eq!(issue.kinds, vec![PathIssueKind::TrailingDotOrSpace]);
+        assert_eq!(issue.suggested, Path::new("/vault/draft/note.md"));
+        assert_eq!(issue.fix, PathFix::RenameFolder);
+
+        let long_name = format!("{}.md", "a".repeat(250));
+        let issue = audit_path(&Path::new("/vault/long").join(&long_name), vault, false).unwrap();
+        assert_eq!(issue.kinds, vec![PathIssueKind::TooLong]);
+        assert_eq!(issue.length, 258);
+        assert_eq!(
+            issue.suggested,
+            Path::new("/vault/long").join(format!("{}.md", "a".repeat(192)))
+        );
+
+        assert!(audit_path(Path::new("/vault/notes/Plain note.md"), vault, false).is_none());
+    }
+}
@@ -0,0 +1,304 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::Utc;
+use serde_yaml::Value;
+
+use super::ObsidianRepository;
+use super::constants::FORMAT_WEEK_DAY;
+use super::constants::WEEK_FIRST_DAY_SUFFIX;
+use super::constants::WEEK_MONTH_DAY_OFFSET;
+use super::phantom_links;
+use crate::constants::FORMAT_MONTH;
+use crate::constants::FORMAT_WEEK;
+use crate::constants::LINK_ADDED;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::WILL_CREATE;
+use crate::note_template;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink;
+use crate::wikilink::ToWikilink;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PeriodicNoteAction {
+    LinkAdded,
+    Create,
+}
+
+impl Display for PeriodicNoteAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LinkAdded => f.write_str(LINK_ADDED),
+            Self::Create => f.write_str(WILL_CREATE),
+        }
+    }
+}
+
+/// A daily or weekly note given the link up to its weekly or monthly note, or a missing
+/// weekly or monthly note to create.
+#[derive(Clone, Debug)]
+pub(crate) struct PeriodicNoteChange {
+    pub path:    PathBuf,
+    /// The note `path` links up to - `None` for a monthly note, which has nothing above it.
+    pub parent:  Option<String>,
+    pub action:  PeriodicNoteAction,
+    /// The note as `persist` will create it - empty for `LinkAdded`.
+    pub content: String,
+}
+
+/// The day a daily note is named for, its name being a date in `date_link_format`. Names that
+/// only parse loosely, like `2024-5-2`, aren't dates.
+pub(super) fn daily_note_date(stem: &str, date_link_format: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(stem, date_link_format)
+        .ok()
+        .filter(|date| date.format(date_link_format).to_string() == stem)
+}
+
+/// The weekly note a daily note (`2024-05-02`) belongs to, or the monthly note a weekly note
+/// (`2024-W18`) belongs to. Names that only parse loosely, like `2024-5-2`, aren't periodic.
+fn parent_note_name(stem: &str, date_link_format: &str) -> Option<String> {
+    if let Some(date) = daily_note_date(stem, date_link_format) {
+        return Some(date.format(FORMAT_WEEK).to_string());
+    }
+
+    let monday =
+        NaiveDate::parse_from_str(&format!("{stem}{WEEK_FIRST_DAY_SUFFIX}"), FORMAT_WEEK_DAY)
+            .ok()
+            .filter(|monday| monday.format(FORMAT_WEEK).to_string() == stem)?;
+    monday
+        .checked_add_days(Days::new(WEEK_MONTH_DAY_OFFSET))
+        .map(|thursday| thursday.format(FORMAT_MONTH).to_string())
+}
+
+fn note_stem(path: &Path) -> &str { path.file_stem().and_then(OsStr::to_str).unwrap_or_default() }
+
+impl ObsidianRepository {
+    /// With `periodic_note_link_property` set, links each daily note up to its weekly note and
+    /// each weekly note up to its monthly note through that property, unless it already links
+    /// there. A missing weekly or monthly note is queued for `persist` to create beside the
+    /// first note linking to it, already linked up in turn.
+    pub(crate) fn link_periodic_notes(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let Some(property) = validated_config.periodic_note_link_property() else {
+            return Ok(());
+        };
+        let operational_timezone = validated_config.operational_timezone();
+        let mut taken_names = self.markdown_note_stems();
+        let mut changes = Vec::new();
+        let mut notes_to_create: Vec<PathBuf> = Vec::new();
+
+        for markdown_file in &mut self.markdown_files {
+            let Some(parent) = parent_note_name(
+                note_stem(&markdown_file.path),
+                validated_config.date_link_format(),
+            ) else {
+                continue;
+            };
+            let parent_stem = parent.to_lowercase();
+            let linked = markdown_file
+                .front_matter
+                .as_ref()
+                .is_some_and(|front_matter| {
+                    front_matter
+                        .property_strings(property)
+                        .into_iter()
+                        .flat_map(|value| wikilink::extract_wikilinks(value).valid)
+                        .any(|spanned_wikilink| {
+                            phantom_links::target_note_stem(&spanned_wikilink.wikilink.target)
+                                == parent_stem
+                        })
+                });
+
+            if !linked {
+                markdown_file.add_periodic_note_link(
+                    property,
+                    &parent.to_wikilink(),
+                    operational_timezone,
+                )?;
+                changes.push(PeriodicNoteChange {
+                    path:    markdown_file.path.clone(),
+                    parent:  Some(parent.clone()),
+                    action:  PeriodicNoteAction::LinkAdded,
+                    content: String::new(),
+                });
+            }
+            if taken_names.insert(parent_stem) {
+                notes_to_create.push(
+                    markdown_file
+                        .path
+                        .with_file_name(format!("{parent}{MARKDOWN_SUFFIX}")),
+                );
+            }
+        }
+
+        // A created weekly note links up to its month, which may need creating too.
+        let mut index = 0;
+        while let Some(path) = notes_to_create.get(index).cloned() {
+            index += 1;
+            let parent = parent_note_name(note_stem(&path), validated_config.date_link_format());
+            if let Some(parent) = &parent
+                && taken_names.insert(parent.to_lowercase())
+            {
+                notes_to_create.push(path.with_file_name(format!("{parent}{MARKDOWN_SUFFIX}")));
+            }
+
+            let rendered_template = validated_config.periodic_note_template().map(|template| {
+                template.render(
+                    &path,
+                    validated_config.obsidian_path(),
+                    operational_timezone,
+                )
+            });
+            let content = note_template::compose_note(
+                rendered_template.as_deref(),
+                |front_matter| {
+                    front_matter.set_date_created(Utc::now(), operational_timezone);
+                    if let Some(parent) = &parent {
+                        front_matter.set_property(property, Value::String(parent.to_wikilink()));
+                    }
+                },
+                "",
+            )?;
+
+            changes.push(PeriodicNoteChange {
+                path,
+                parent,
+                action: PeriodicNoteAction::Create,
+                content,
+            });
+        }
+
+        self.periodic_note_changes = changes;
+        Ok(())
+    }
+
+    /// Creates the weekly and monthly notes `link_periodic_notes` queued. Existing files are
+    /// left alone.
+    pub(super) fn create_periodic_notes(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for change in &self.periodic_note_changes {
+            if change.action != PeriodicNoteAction::Create || change.path.exists() {
+                continue;
+            }
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&change.path)?
+                .write_all(change.content.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::PeriodicNoteAction;
+    use super::parent_note_name;
+    use crate::constants::FORMAT_DATE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_parent_note_name() {
+        assert_eq!(
+            parent_note_name("2024-05-02", FORMAT_DATE),
+            Some("2024-W18".to_string())
+        );
+        // 2024-12-30 falls in the first ISO week of 2025, whose Thursday is in January.
+        assert_eq!(
+            parent_note_name("2024-12-30", FORMAT_DATE),
+            Some("2025-W01".to_string())
+        );
+        assert_eq!(
+            parent_note_name("2025-W01", FORMAT_DATE),
+            Some("2025-01".to_string())
+        );
+        assert_eq!(
+            parent_note_name("2024-W18", FORMAT_DATE),
+            Some("2024-05".to_string())
+        );
+        assert_eq!(parent_note_name("2024-05", FORMAT_DATE), None);
+        assert_eq!(parent_note_name("2024-5-2", FORMAT_DATE), None);
+        assert_eq!(parent_note_name("Meeting", FORMAT_DATE), None);
+    }
+
+    #[test]
+    fn test_periodic_notes_linked_and_created() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("2024-05-02".to_string())
+            .with_content("thursday".to_string())
+            .create(&temp_dir, "2024-05-02.md");
+        TestFileBuilder::new()
+            .with_title("2024-05-03".to_string())
+            .with_custom_frontmatter("up: \"[[2024-W18]]\"".to_string())
+            .create(&temp_dir, "2024-05-03.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.periodic_note_link_property(Some("up".to_string()));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let changes: Vec<(String, Option<String>, PeriodicNoteAction)> = obsidian_repository
+            .periodic_note_changes
+            .iter()
+            .map(|change| {
+                (
+                    change
+                        .path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    change.parent.clone(),
+                    change.action,
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "2024-05-02.md".to_string(),
+                    Some("2024-W18".to_string()),
+                    PeriodicNoteAction::LinkAdded
+                ),
+                (
+                    "2024-W18.md".to_string(),
+                    Some("2024-05".to_string()),
+                    PeriodicNoteAction::Create
+                ),
+                ("2024-05.md".to_string(), None, PeriodicNoteAction::Create),
+            ]
+        );
+
+        obsidian_repository.persist().unwrap();
+        let daily = fs::read_to_string(temp_dir.path().join("2024-05-02.md")).unwrap();
+        assert!(daily.contains("up: '[[2024-W18]]'"));
+        let weekly = fs::read_to_string(temp_dir.path().join("2024-W18.md")).unwrap();
+        assert!(weekly.contains("up: '[[2024-05]]'"));
+        assert!(temp_dir.path().join("2024-05.md").exists());
+    }
+}
@@ -3,9 +3,12 @@ use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::PathBuf;
 
+use anyhow::Result as AnyhowResult;
 use chrono::NaiveDate;
 
 use super::ObsidianRepository;
+use super::SectionSplit;
+use super::constants::FRONTMATTER_LINE_NUMBER;
 use crate::constants::FORMAT_DATE;
 use crate::constants::FORWARD_SLASH;
 use crate::constants::HASH;
@@ -14,6 +17,7 @@ use crate::support;
 use crate::validated_config::ValidatedConfig;
 
 /// One wikilink whose target note does not exist and cannot be re-targeted automatically.
+/// Links in frontmatter properties use `FRONTMATTER_LINE_NUMBER`.
 #[derive(Clone, Debug)]
 pub(crate) struct UnresolvedLink {
     pub target:      String,
@@ -32,7 +36,13 @@ impl ObsidianRepository {
     /// Content links spelled with a rewritten target become `CanonicalLinkMatch` entries on
     /// their `MarkdownFile`, so `apply_replaceable_matches` also rewrites the existing links
     /// (`[[topics/service/LinkedIn|linkedin]]` to `[[LinkedIn|linkedin]]`).
-    pub(crate) fn canonicalize_wikilink_targets(&mut self, validated_config: &ValidatedConfig) {
+    ///
+    /// Links in `frontmatter_link_properties` values are rewritten the same way. Date links
+    /// written as `YYYY-MM-DD` move to `date_link_format` when it is set to something else.
+    pub(crate) fn canonicalize_wikilink_targets(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
         // `stems_by_lower` maps a lowercased stem to the actual-case stem of every note
         // bearing it; only a stem naming a single note canonicalizes.
         let mut stems_by_lower: HashMap<String, Vec<String>> = HashMap::new();
@@ -67,16 +77,37 @@ impl ObsidianRepository {
         for wikilink in &mut self.wikilinks_sorted {
             if let Some(canonical_target) =
                 canonical_note_target(&wikilink.target, &stems_by_lower, &stems_by_relative_path)
+                    .or_else(|| {
+                        migrated_date_target(&wikilink.target, validated_config.date_link_format())
+                    })
             {
                 canonical_targets.insert(wikilink.target.to_lowercase(), canonical_target.clone());
                 wikilink.target = canonical_target;
             }
         }
 
+        let note_paths = self.note_paths_by_stem(validated_config);
+        let frontmatter_link_properties = validated_config.frontmatter_link_properties();
         for markdown_file in &mut self.markdown_files {
-            markdown_file.canonical_link_matches =
-                markdown_file.find_canonical_link_matches(&canonical_targets, validated_config);
+            markdown_file.canonical_link_matches = markdown_file.find_canonical_link_matches(
+                &canonical_targets,
+                &note_paths,
+                validated_config,
+            );
+
+            let rewritten = markdown_file.canonicalize_frontmatter_wikilinks(
+                frontmatter_link_properties,
+                &canonical_targets,
+            );
+            if rewritten > 0 {
+                markdown_file.mark_frontmatter_links_canonicalized(
+                    rewritten,
+                    validated_config.operational_timezone(),
+                )?;
+            }
         }
+
+        Ok(())
     }
 
     /// A phantom wikilink names a note that does not exist. When its target text matches the
@@ -132,17 +163,45 @@ impl ObsidianRepository {
         }
     }
 
-    /// Collects every content wikilink still pointing at a note that does not exist, one
-    /// `UnresolvedLink` per occurrence. Date targets are daily-note placeholders and are
-    /// excluded.
-    pub(crate) fn collect_unresolved_links(&self) -> Vec<UnresolvedLink> {
+    /// Records a `SelfLinkMatch` for every content link that targets its own note or repeats
+    /// its target as the alias. Runs after `canonicalize_wikilink_targets` and
+    /// `resolve_phantom_wikilinks` so links those passes rewrite are left to them.
+    pub(crate) fn find_all_self_link_matches(&mut self, validated_config: &ValidatedConfig) {
+        for markdown_file in &mut self.markdown_files {
+            markdown_file.self_link_matches =
+                markdown_file.find_self_link_matches(validated_config);
+        }
+    }
+
+    /// With `repair_invalid_wikilinks` on, records a `WikilinkRepairMatch` for every invalid
+    /// wikilink whose intended form is unambiguous - an unclosed one only when its text names
+    /// a note.
+    pub(crate) fn find_all_wikilink_repair_matches(&mut self, validated_config: &ValidatedConfig) {
+        if !validated_config.repair_invalid_wikilinks() {
+            return;
+        }
+
+        let note_stems = self.markdown_note_stems();
+        for markdown_file in &mut self.markdown_files {
+            markdown_file.wikilink_repair_matches = markdown_file
+                .find_wikilink_repair_matches(validated_config, |target| {
+                    target_resolves(&note_stems, target)
+                });
+        }
+    }
+
+    /// Collects every content and frontmatter property wikilink still pointing at a note that
+    /// does not exist, one `UnresolvedLink` per occurrence. Date targets are daily-note
+    /// placeholders and are excluded.
+    pub(crate) fn collect_unresolved_links(&self, date_link_format: &str) -> Vec<UnresolvedLink> {
         let note_stems = self.markdown_note_stems();
 
         let unresolved_targets: HashSet<String> = self
             .wikilinks_sorted
             .iter()
             .filter(|wikilink| {
-                !target_resolves(&note_stems, &wikilink.target) && !is_date_target(&wikilink.target)
+                !target_resolves(&note_stems, &wikilink.target)
+                    && !is_date_target(&wikilink.target, date_link_format)
             })
             .map(|wikilink| wikilink.target.to_lowercase())
             .collect();
@@ -153,6 +212,17 @@ impl ObsidianRepository {
 
         let mut unresolved_links = Vec::new();
         for markdown_file in &self.markdown_files {
+            for frontmatter_wikilink in &markdown_file.frontmatter_wikilinks {
+                let target = &frontmatter_wikilink.wikilink.target;
+                if unresolved_targets.contains(&target.to_lowercase()) {
+                    unresolved_links.push(UnresolvedLink {
+                        target:      target.clone(),
+                        file_path:   markdown_file.path.clone(),
+                        line_number: FRONTMATTER_LINE_NUMBER,
+                    });
+                }
+            }
+
             markdown_file.for_each_content_wikilink(|line_number, _, spanned_wikilink| {
                 if unresolved_targets.contains(&spanned_wikilink.wikilink.target.to_lowercase()) {
                     unresolved_links.push(UnresolvedLink {
@@ -175,10 +245,48 @@ impl ObsidianRepository {
         unresolved_links
     }
 
-    fn markdown_note_stems(&self) -> HashSet<String> {
+    /// Maps the lowercased stem of every note no other note shares to its vault-relative path
+    /// without `MARKDOWN_SUFFIX` - the target `LinkAliasStyle::PipedPath` links use.
+    pub(super) fn note_paths_by_stem(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> HashMap<String, String> {
+        let mut paths_by_stem: HashMap<String, Vec<String>> = HashMap::new();
+        for markdown_file in &self.markdown_files {
+            let Some(stem) = markdown_file.path.file_stem().and_then(OsStr::to_str) else {
+                continue;
+            };
+            let relative_path = support::format_relative_path(
+                &markdown_file.path,
+                validated_config.obsidian_path(),
+            );
+            paths_by_stem.entry(stem.to_lowercase()).or_default().push(
+                relative_path
+                    .strip_suffix(MARKDOWN_SUFFIX)
+                    .unwrap_or(&relative_path)
+                    .to_string(),
+            );
+        }
+
+        paths_by_stem
+            .into_iter()
+            .filter_map(|(stem, paths)| match paths.as_slice() {
+                [only_path] => Some((stem, only_path.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The lowercased stem of every note, counting the notes `split_notes` is about to create.
+    pub(super) fn markdown_note_stems(&self) -> HashSet<String> {
         self.markdown_files
             .iter()
             .filter_map(|markdown_file| markdown_file.path.file_stem().and_then(OsStr::to_str))
+            .chain(
+                self.section_splits
+                    .iter()
+                    .filter_map(SectionSplit::note_name),
+            )
             .map(str::to_lowercase)
             .collect()
     }
@@ -186,7 +294,7 @@ impl ObsidianRepository {
 
 /// Returns the note stem a wikilink `target` names: the last path segment with any heading
 /// suffix and `MARKDOWN_SUFFIX` removed, lowercased.
-fn target_note_stem(target: &str) -> String {
+pub(super) fn target_note_stem(target: &str) -> String {
     let without_heading = target.split(HASH).next().unwrap_or(target);
     let last_segment = without_heading
         .rsplit(FORWARD_SLASH)
@@ -200,7 +308,7 @@ fn target_note_stem(target: &str) -> String {
         .to_lowercase()
 }
 
-fn target_resolves(note_stems: &HashSet<String>, target: &str) -> bool {
+pub(super) fn target_resolves(note_stems: &HashSet<String>, target: &str) -> bool {
     note_stems.contains(&target_note_stem(target))
 }
 
@@ -234,9 +342,23 @@ fn canonical_note_target(
     }
 }
 
+/// The `date_link_format` spelling of a `YYYY-MM-DD` date target, when that format differs.
+fn migrated_date_target(target: &str, date_link_format: &str) -> Option<String> {
+    if date_link_format == FORMAT_DATE {
+        return None;
+    }
+    NaiveDate::parse_from_str(target, FORMAT_DATE)
+        .ok()
+        .filter(|date| date.format(FORMAT_DATE).to_string() == target)
+        .map(|date| date.format(date_link_format).to_string())
+}
+
 /// Date targets are daily-note links; a missing daily note is a placeholder, not a phantom.
-fn is_date_target(target: &str) -> bool {
-    NaiveDate::parse_from_str(&target_note_stem(target), FORMAT_DATE).is_ok()
+/// Links written before `date_link_format` was set still count.
+pub(super) fn is_date_target(target: &str, date_link_format: &str) -> bool {
+    let stem = target_note_stem(target);
+    NaiveDate::parse_from_str(&stem, date_link_format).is_ok()
+        || NaiveDate::parse_from_str(&stem, FORMAT_DATE).is_ok()
 }
 
 #[cfg(test)]
@@ -248,8 +370,14 @@ fn is_date_target(target: &str) -> bool {
 mod tests {
     use std::collections::HashMap;
 
+    use tempfile::TempDir;
+
     use super::canonical_note_target;
+    use super::migrated_date_target;
     use super::target_note_stem;
+    use crate::config::LinkAliasStyle;
+    use crate::constants::FORMAT_DATE;
+    use crate::markdown_file::PersistReason;
     use crate::obsidian_repository::ObsidianRepository;
     use crate::test_support;
     use crate::test_support::TestFileBuilder;
@@ -290,7 +418,7 @@ mod tests {
         assert!(!diary.has_ambiguous_matches());
         assert_eq!(diary.back_populate_matches.unambiguous.len(), 1);
         assert_eq!(
-            diary.back_populate_matches.unambiguous[0].replacement,
+            &*diary.back_populate_matches.unambiguous[0].replacement,
             "[[Kali Amen|Kali]]"
         );
 
@@ -338,6 +466,89 @@ mod tests {
         assert_eq!(diary.content.trim_end(), "[[Kali]] and Kali in plain text");
     }
 
+    #[test]
+    fn test_self_links_rewritten_only_when_fix_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("[[Recipes|my recipes]] and [[Bread|Bread]]")
+            .with_title("recipes".to_string())
+            .create(&temp_dir, "Recipes.md");
+
+        let validated_config = test_support::get_test_validated_config(&temp_dir, None);
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        let recipes = obsidian_repository.markdown_files.first().unwrap();
+        assert_eq!(recipes.self_link_matches.len(), 2);
+        assert_eq!(
+            recipes.content.trim_end(),
+            "[[Recipes|my recipes]] and [[Bread|Bread]]",
+            "self links are only reported without fix_self_links"
+        );
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.fix_self_links(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        let recipes = obsidian_repository.markdown_files.first().unwrap();
+        assert_eq!(recipes.content, "my recipes and [[Bread]]");
+        assert!(
+            recipes
+                .persist_reasons
+                .contains(&PersistReason::SelfLinksCleaned)
+        );
+    }
+
+    #[test]
+    fn test_invalid_wikilinks_repaired_only_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("[[[Note]]] and later\nsee [[Other")
+            .with_title("diary".to_string())
+            .create(&temp_dir, "diary.md");
+        TestFileBuilder::new()
+            .with_title("other".to_string())
+            .create(&temp_dir, "Other.md");
+
+        let validated_config = test_support::get_test_validated_config(&temp_dir, None);
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        let diary = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|markdown_file| markdown_file.path.ends_with("diary.md"))
+            .unwrap();
+        assert!(diary.wikilink_repair_matches.is_empty());
+        assert_eq!(
+            diary.content.trim_end(),
+            "[[[Note]]] and later\nsee [[Other"
+        );
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.repair_invalid_wikilinks(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        let diary = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|markdown_file| markdown_file.path.ends_with("diary.md"))
+            .unwrap();
+        assert_eq!(diary.content, "[[Note]] and later\nsee [[Other]]");
+        assert!(
+            diary
+                .persist_reasons
+                .contains(&PersistReason::WikilinksRepaired)
+        );
+        assert!(
+            diary
+                .wikilinks
+                .invalid
+                .iter()
+                .all(|invalid| diary.is_repaired_wikilink(invalid))
+        );
+    }
+
     #[test]
     fn test_collect_unresolved_links_excludes_dates_and_resolved() {
         let (temp_dir, validated_config, _) =
@@ -356,7 +567,7 @@ mod tests {
 
         let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
 
-        let unresolved_links = obsidian_repository.collect_unresolved_links();
+        let unresolved_links = obsidian_repository.collect_unresolved_links(FORMAT_DATE);
 
         assert_eq!(
             unresolved_links.len(),
@@ -397,7 +608,7 @@ mod tests {
         );
         assert_eq!(diary.back_populate_matches.unambiguous.len(), 1);
         assert_eq!(
-            diary.back_populate_matches.unambiguous[0].replacement,
+            &*diary.back_populate_matches.unambiguous[0].replacement,
             "[[LinkedIn|linkedin]]"
         );
 
@@ -415,6 +626,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_link_alias_style_applied_to_new_and_normalized_links() {
+        let diary_content = "[[LinkedIn|Jobs]] board\n[[topics/service/LinkedIn|LinkedIn]] \
+                             profile\nrun a linkedin campaign";
+        let styled_diary = |style: LinkAliasStyle, normalize: bool| {
+            let temp_dir = TempDir::new().unwrap();
+            std::fs::create_dir_all(temp_dir.path().join("topics/service")).unwrap();
+            TestFileBuilder::new()
+                .with_title("LinkedIn".to_string())
+                .create(&temp_dir, "topics/service/LinkedIn.md");
+            TestFileBuilder::new()
+                .with_title("diary".to_string())
+                .with_content(diary_content)
+                .create(&temp_dir, "diary.md");
+
+            let validated_config =
+                test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                    builder
+                        .link_alias_style(style)
+                        .normalize_link_aliases(normalize);
+                })
+                .unwrap();
+            let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+            obsidian_repository
+                .markdown_files
+                .iter()
+                .find(|file| file.path.ends_with("diary.md"))
+                .unwrap()
+                .content
+                .clone()
+        };
+
+        assert_eq!(
+            styled_diary(LinkAliasStyle::Lowercase, false),
+            "[[LinkedIn|Jobs]] board\n[[LinkedIn]] profile\nrun a [[LinkedIn|linkedin]] campaign"
+        );
+        assert_eq!(
+            styled_diary(LinkAliasStyle::Lowercase, true),
+            "[[LinkedIn|jobs]] board\n[[LinkedIn]] profile\nrun a [[LinkedIn|linkedin]] campaign"
+        );
+        assert_eq!(
+            styled_diary(LinkAliasStyle::PipedPath, false),
+            "[[LinkedIn|Jobs]] board\n[[topics/service/LinkedIn|LinkedIn]] profile\nrun a \
+             [[topics/service/LinkedIn|linkedin]] campaign",
+            "a link already naming the note's path is canonical"
+        );
+        assert_eq!(
+            styled_diary(LinkAliasStyle::PipedPath, true),
+            "[[topics/service/LinkedIn|Jobs]] board\n[[topics/service/LinkedIn|LinkedIn]] \
+             profile\nrun a [[topics/service/LinkedIn|linkedin]] campaign"
+        );
+    }
+
     #[test]
     fn test_shared_stem_targets_stay_ambiguous() {
         let (temp_dir, validated_config, _) =
@@ -483,6 +747,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_migrated_date_target_variants() {
+        let test_cases = vec![
+            ("2024-05-02", "%d.%m.%Y", Some("02.05.2024")),
+            ("2024-05-02", "%Y-%m-%d %a", Some("2024-05-02 Thu")),
+            ("2024-05-02", FORMAT_DATE, None),
+            ("2024-5-2", "%d.%m.%Y", None),
+            ("02.05.2024", "%d.%m.%Y", None),
+            ("Meeting", "%d.%m.%Y", None),
+        ];
+
+        for (target, date_link_format, expected) in test_cases {
+            assert_eq!(
+                migrated_date_target(target, date_link_format).as_deref(),
+                expected,
+                "migrated target mismatch for: {target}"
+            );
+        }
+    }
+
     #[test]
     fn test_target_note_stem_variants() {
         let test_cases = vec![
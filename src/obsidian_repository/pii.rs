@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::ObsidianRepository;
+use super::constants::VAULT_ROOT_FOLDER;
+use crate::markdown_file::BuiltInPiiDetector;
+use crate::markdown_file::PiiDetector;
+use crate::validated_config::ValidatedConfig;
+
+/// How much of one kind of personal data a folder's notes hold, found by `detect_pii`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PiiFolderSummary {
+    /// Relative to the vault, or `/` for notes at its root.
+    pub folder:   String,
+    pub detector: String,
+    pub matches:  usize,
+    /// How many of the folder's notes hold at least one match.
+    pub notes:    usize,
+}
+
+impl ObsidianRepository {
+    /// With `detect_pii` set, counts email addresses, phone numbers, national ID numbers and
+    /// anything `pii_patterns` matches in every note, and totals them per folder. Folders are
+    /// summarized rather than listing notes so the report doesn't repeat the data it found.
+    pub(crate) fn detect_pii(&mut self, validated_config: &ValidatedConfig) {
+        if !validated_config.detect_pii() {
+            return;
+        }
+
+        let detectors: Vec<&dyn PiiDetector> = BuiltInPiiDetector::ALL
+            .iter()
+            .map(|detector| detector as &dyn PiiDetector)
+            .chain(
+                validated_config
+                    .pii_patterns()
+                    .iter()
+                    .map(|rule| rule as &dyn PiiDetector),
+            )
+            .collect();
+
+        let mut totals: BTreeMap<(String, usize), (usize, usize)> = BTreeMap::new();
+        for markdown_file in &self.markdown_files {
+            let folder = folder_name(validated_config.obsidian_path(), &markdown_file.path);
+            for (index, count) in markdown_file
+                .pii_counts(&detectors)
+                .into_iter()
+                .enumerate()
+                .filter(|(_, count)| *count > 0)
+            {
+                let (matches, notes) = totals.entry((folder.clone(), index)).or_default();
+                *matches += count;
+                *notes += 1;
+            }
+        }
+
+        self.pii_summaries = totals
+            .into_iter()
+            .map(|((folder, index), (matches, notes))| PiiFolderSummary {
+                folder,
+                detector: detectors[index].name().to_string(),
+                matches,
+                notes,
+            })
+            .collect();
+    }
+}
+
+fn folder_name(obsidian_path: &Path, path: &Path) -> String {
+    path.parent()
+        .and_then(|parent| parent.strip_prefix(obsidian_path).ok())
+        .map(|folder| folder.to_string_lossy().to_string())
+        .filter(|folder| !folder.is_empty())
+        .unwrap_or_else(|| VAULT_ROOT_FOLDER.to_string())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use super::PiiFolderSummary;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::PiiPatternRule;
+
+    #[test]
+    fn test_detect_pii_summarizes_per_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("people")).unwrap();
+        TestFileBuilder::new()
+            .with_content("dana@example.com and lee@example.com".to_string())
+            .create(&temp_dir, "people/Dana.md");
+        TestFileBuilder::new()
+            .with_content("lee@example.com, badge B-0042".to_string())
+            .create(&temp_dir, "people/Lee.md");
+        TestFileBuilder::new()
+            .with_content("Front desk: 555-123-4567".to_string())
+            .create(&temp_dir, "Office.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.detect_pii(true).pii_patterns(vec![PiiPatternRule {
+                    name:  "badge number".to_string(),
+                    regex: Regex::new(r"\bB-\d{4}\b").unwrap(),
+                }]);
+            })
+            .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let summary = |folder: &str, detector: &str, matches, notes| PiiFolderSummary {
+            folder: folder.to_string(),
+            detector: detector.to_string(),
+            matches,
+            notes,
+        };
+        assert_eq!(
+            repository.pii_summaries,
+            vec![
+                summary("/", "phone number", 1, 1),
+                summary("people", "email address", 3, 2),
+                summary("people", "badge number", 1, 1),
+            ]
+        );
+    }
+}
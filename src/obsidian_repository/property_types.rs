@@ -0,0 +1,297 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use serde_yaml::Value;
+
+use super::ObsidianRepository;
+use super::constants::PROPERTY_DATE_TIME_FORMATS;
+use super::constants::PROPERTY_MIDNIGHT_SUFFIX;
+use crate::constants::CHECKBOX;
+use crate::constants::DATE;
+use crate::constants::DATE_TIME;
+use crate::constants::FORMAT_DATE;
+use crate::constants::LIST;
+use crate::constants::NUMBER;
+use crate::constants::TEXT;
+use crate::validated_config::ValidatedConfig;
+
+/// The property types of Obsidian's properties view. Nested mappings have no type there and
+/// are left out of the check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum PropertyType {
+    Checkbox,
+    Date,
+    DateTime,
+    List,
+    Number,
+    Text,
+}
+
+impl Display for PropertyType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Checkbox => f.write_str(CHECKBOX),
+            Self::Date => f.write_str(DATE),
+            Self::DateTime => f.write_str(DATE_TIME),
+            Self::List => f.write_str(LIST),
+            Self::Number => f.write_str(NUMBER),
+            Self::Text => f.write_str(TEXT),
+        }
+    }
+}
+
+/// One note's value of a property whose type differs between notes, when that value isn't of
+/// the property's most common type.
+#[derive(Clone, Debug)]
+pub(crate) struct PropertyTypeMismatch {
+    pub file_path:     PathBuf,
+    pub property:      String,
+    pub property_type: PropertyType,
+    pub value:         Value,
+    /// The most common type of the property across the vault - `None` when two types tie.
+    pub majority:      Option<PropertyType>,
+    /// `value` converted to `majority`, when it converts without losing anything.
+    pub coerced:       Option<Value>,
+}
+
+impl ObsidianRepository {
+    /// Finds properties holding different types in different notes - Obsidian's properties view
+    /// expects one type per property across the vault and shows the rest as invalid. Records a
+    /// `PropertyTypeMismatch` for each value that isn't of the most common type; with
+    /// `coerce_property_types` on, values that convert cleanly are rewritten.
+    pub(crate) fn check_property_types(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let mut type_counts: HashMap<&str, HashMap<PropertyType, usize>> = HashMap::new();
+        for front_matter in self
+            .markdown_files
+            .iter()
+            .filter_map(|markdown_file| markdown_file.front_matter.as_ref())
+        {
+            for (property, value) in front_matter.other_properties() {
+                if let Some(property_type) = property_type(value) {
+                    *type_counts
+                        .entry(property)
+                        .or_default()
+                        .entry(property_type)
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        let majorities: HashMap<String, Option<PropertyType>> = type_counts
+            .iter()
+            .filter(|(_, counts)| counts.len() > 1)
+            .map(|(property, counts)| ((*property).to_string(), majority_type(counts)))
+            .collect();
+        if majorities.is_empty() {
+            return Ok(());
+        }
+
+        let mut mismatches: Vec<PropertyTypeMismatch> = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            let Some(front_matter) = &mut markdown_file.front_matter else {
+                continue;
+            };
+
+            let mut file_mismatches: Vec<PropertyTypeMismatch> = front_matter
+                .other_properties()
+                .filter_map(|(property, value)| {
+                    let majority = *majorities.get(property)?;
+                    let property_type = property_type(value)?;
+                    if majority.is_some_and(|majority| fits(property_type, majority)) {
+                        return None;
+                    }
+
+                    Some(PropertyTypeMismatch {
+                        file_path: markdown_file.path.clone(),
+                        property: property.to_string(),
+                        property_type,
+                        value: value.clone(),
+                        majority,
+                        coerced: majority.and_then(|majority| coerce_value(value, majority)),
+                    })
+                })
+                .collect();
+            file_mismatches.sort_by(|a, b| a.property.cmp(&b.property));
+
+            if validated_config.coerce_property_types() {
+                let mut coerced_count = 0;
+                for mismatch in &file_mismatches {
+                    if let Some(coerced) = &mismatch.coerced {
+                        front_matter.set_property(&mismatch.property, coerced.clone());
+                        coerced_count += 1;
+                    }
+                }
+                if coerced_count > 0 {
+                    markdown_file.mark_property_types_coerced(
+                        coerced_count,
+                        validated_config.operational_timezone(),
+                    )?;
+                }
+            }
+
+            mismatches.extend(file_mismatches);
+        }
+
+        self.property_type_mismatches = mismatches;
+        Ok(())
+    }
+}
+
+fn property_type(value: &Value) -> Option<PropertyType> {
+    match value {
+        Value::Bool(_) => Some(PropertyType::Checkbox),
+        Value::Number(_) => Some(PropertyType::Number),
+        Value::Sequence(_) => Some(PropertyType::List),
+        Value::String(text) if NaiveDate::parse_from_str(text, FORMAT_DATE).is_ok() => {
+            Some(PropertyType::Date)
+        },
+        Value::String(text) if is_date_time(text) => Some(PropertyType::DateTime),
+        Value::String(_) => Some(PropertyType::Text),
+        Value::Null | Value::Mapping(_) | Value::Tagged(_) => None,
+    }
+}
+
+fn is_date_time(text: &str) -> bool {
+    PROPERTY_DATE_TIME_FORMATS
+        .iter()
+        .any(|format| NaiveDateTime::parse_from_str(text, format).is_ok())
+}
+
+/// A date is valid text too, so a date in a text property isn't a mismatch.
+fn fits(property_type: PropertyType, majority: PropertyType) -> bool {
+    property_type == majority
+        || (majority == PropertyType::Text
+            && matches!(property_type, PropertyType::Date | PropertyType::DateTime))
+}
+
+/// The type most notes use, or `None` when the top two tie.
+fn majority_type(counts: &HashMap<PropertyType, usize>) -> Option<PropertyType> {
+    let mut ranked: Vec<(PropertyType, usize)> = counts
+        .iter()
+        .map(|(property_type, count)| (*property_type, *count))
+        .collect();
+    ranked.sort_by_key(|&(_, count)| Reverse(count));
+
+    match ranked.as_slice() {
+        [(first, first_count), (_, second_count), ..] if first_count > second_count => Some(*first),
+        [(only, _)] => Some(*only),
+        _ => None,
+    }
+}
+
+/// `value` as `to`, when nothing is lost: a scalar wraps into a one item list, a one item list
+/// unwraps, numbers and checkboxes become text and text that reads as a number or checkbox
+/// converts back. A date becomes a date & time at midnight; the reverse would drop the time.
+fn coerce_value(value: &Value, to: PropertyType) -> Option<Value> {
+    match (value, to) {
+        (Value::Sequence(items), _) => match items.as_slice() {
+            [item] if property_type(item).is_some_and(|item_type| fits(item_type, to)) => {
+                Some(item.clone())
+            },
+            [item] if !item.is_sequence() => coerce_value(item, to),
+            _ => None,
+        },
+        (_, PropertyType::List) => Some(Value::Sequence(vec![value.clone()])),
+        (Value::Number(number), PropertyType::Text) => Some(Value::String(number.to_string())),
+        (Value::Bool(checked), PropertyType::Text) => Some(Value::String(checked.to_string())),
+        (Value::String(text), PropertyType::Number) => serde_yaml::from_str::<Value>(text.trim())
+            .ok()
+            .filter(Value::is_number),
+        (Value::String(text), PropertyType::Checkbox) => {
+            text.trim().parse::<bool>().ok().map(Value::Bool)
+        },
+        (Value::String(text), PropertyType::DateTime)
+            if property_type(value) == Some(PropertyType::Date) =>
+        {
+            Some(Value::String(format!("{text}{PROPERTY_MIDNIGHT_SUFFIX}")))
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_property_types_coerced_to_majority() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, frontmatter) in [
+            ("a.md", "rating: 4\ntopics:\n- rust"),
+            ("b.md", "rating: 5\ntopics: rust"),
+            ("c.md", "rating: \"3\"\ntopics:\n- go\n- zig"),
+            ("d.md", "rating: great\nstatus: draft"),
+            ("e.md", "status: [done]\nstarted: 2024-01-01"),
+            ("f.md", "rating: 1\nstarted: 2024-01-02T09:30"),
+        ] {
+            TestFileBuilder::new()
+                .with_content(format!("---\n{frontmatter}\n---\nbody"))
+                .create(&temp_dir, name);
+        }
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.coerce_property_types(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let mismatches: Vec<String> = obsidian_repository
+            .property_type_mismatches
+            .iter()
+            .map(|mismatch| {
+                format!(
+                    "{} {}: {} -> {} {}",
+                    mismatch.file_path.file_name().unwrap().to_string_lossy(),
+                    mismatch.property,
+                    mismatch.property_type,
+                    mismatch
+                        .majority
+                        .map_or_else(|| "tie".to_string(), |majority| majority.to_string()),
+                    serde_json::to_string(&mismatch.coerced).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            mismatches,
+            vec![
+                r#"b.md topics: text -> list ["rust"]"#,
+                "c.md rating: text -> number 3",
+                "d.md rating: text -> number null",
+                "d.md status: text -> tie null",
+                "e.md started: date -> tie null",
+                "e.md status: list -> tie null",
+                "f.md started: date & time -> tie null",
+            ]
+        );
+
+        let c = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path.ends_with("c.md"))
+            .unwrap();
+        assert!(
+            c.persist_reasons
+                .contains(&PersistReason::PropertyTypesCoerced { count: 1 })
+        );
+    }
+}
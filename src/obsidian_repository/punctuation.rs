@@ -0,0 +1,23 @@
+use anyhow::Result as AnyhowResult;
+
+use super::ObsidianRepository;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `normalize_punctuation` on, replaces curly quotes, dash look-alikes, non-breaking
+    /// spaces and zero-width characters in every note body. Runs before the other passes so
+    /// back populate and the replacement rules see plain text.
+    pub(crate) fn normalize_all_punctuation(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        if !validated_config.normalize_punctuation() {
+            return Ok(());
+        }
+
+        for markdown_file in &mut self.markdown_files {
+            markdown_file.normalize_punctuation(validated_config.operational_timezone())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use super::constants::ASCII_LOWERCASE_ALPHABET;
+use super::constants::COMMON_WORD_FREQUENCY_RATIO;
+use super::constants::MIN_COMMON_WORD_OCCURRENCES;
+use super::constants::MIN_RARE_WORD_LENGTH;
+use crate::constants::ACCEPTED_WORDS_FILE;
+use crate::constants::HASH;
+use crate::markdown_file::RareWordSource;
+use crate::validated_config::ValidatedConfig;
+
+/// A word in a title, heading or alias that is rare in the vault but one edit away from a much
+/// more common word in it - often a typo of `common_word`, though nothing checks it against a
+/// real word list.
+#[derive(Clone, Debug)]
+pub(crate) struct RareWord {
+    pub file_path:   PathBuf,
+    pub source:      RareWordSource,
+    pub line_number: Option<usize>,
+    pub word:        String,
+    pub common_word: String,
+    pub text:        String,
+}
+
+impl ObsidianRepository {
+    /// With `report_rare_words` on, compares the words of every title, heading and alias with
+    /// the vault's own word counts - there is no word list. A word is reported when a word one
+    /// edit away appears at least `COMMON_WORD_FREQUENCY_RATIO` times as often across all note
+    /// content. Words in the `ACCEPTED_WORDS_FILE` in the output folder are never reported.
+    pub(crate) fn collect_rare_words(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<Vec<RareWord>, Box<dyn Error + Send + Sync>> {
+        if !validated_config.report_rare_words() {
+            return Ok(Vec::new());
+        }
+
+        let accepted_words =
+            read_accepted_words(&validated_config.output_folder().join(ACCEPTED_WORDS_FILE))?;
+
+        let mut word_counts: HashMap<String, usize> = HashMap::new();
+        for markdown_file in &self.markdown_files {
+            for word in words(&markdown_file.content) {
+                *word_counts.entry(word.to_lowercase()).or_default() += 1;
+            }
+        }
+
+        let mut rare_words = Vec::new();
+        for markdown_file in &self.markdown_files {
+            for rare_word_text in markdown_file.rare_word_texts() {
+                let mut reported: HashSet<String> = HashSet::new();
+                for word in words(&rare_word_text.text) {
+                    let lowercase_word = word.to_lowercase();
+                    if !is_checkable(word)
+                        || accepted_words.contains(&lowercase_word)
+                        || !reported.insert(lowercase_word.clone())
+                    {
+                        continue;
+                    }
+
+                    if let Some(common_word) = common_neighbor(&lowercase_word, &word_counts) {
+                        rare_words.push(RareWord {
+                            file_path: markdown_file.path.clone(),
+                            source: rare_word_text.source,
+                            line_number: rare_word_text.line_number,
+                            word: word.to_string(),
+                            common_word,
+                            text: rare_word_text.text.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        rare_words.sort_by(|a, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+
+        Ok(rare_words)
+    }
+}
+
+/// Lowercased words from the accepted words file. Heading lines are skipped and punctuation
+/// such as list markers separates words, so the file can be kept as a normal note. A missing
+/// file accepts no words.
+fn read_accepted_words(path: &Path) -> Result<HashSet<String>, Box<dyn Error + Send + Sync>> {
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with(HASH))
+        .flat_map(|line| words(line).map(str::to_lowercase))
+        .collect())
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+}
+
+/// Short words, non-ASCII words and acronyms or camel case (`NASA`, `iPhone`) are left alone.
+fn is_checkable(word: &str) -> bool {
+    word.len() >= MIN_RARE_WORD_LENGTH
+        && word.is_ascii()
+        && !word.chars().skip(1).any(|c| c.is_ascii_uppercase())
+}
+
+/// The most frequent vault word one edit away from `word`, if it is common enough - both in
+/// absolute terms and relative to `word` - to make `word` look like a misspelling of it.
+fn common_neighbor(word: &str, word_counts: &HashMap<String, usize>) -> Option<String> {
+    let word_count = word_counts.get(word).copied().unwrap_or_default().max(1);
+
+    single_edits(word)
+        .into_iter()
+        .filter_map(|candidate| word_counts.get(&candidate).map(|&count| (candidate, count)))
+        .filter(|(_, count)| {
+            *count >= MIN_COMMON_WORD_OCCURRENCES
+                && *count >= word_count * COMMON_WORD_FREQUENCY_RATIO
+        })
+        .max_by(|(a_word, a_count), (b_word, b_count)| {
+            a_count.cmp(b_count).then_with(|| b_word.cmp(a_word))
+        })
+        .map(|(candidate, _)| candidate)
+}
+
+/// Every string one deletion, transposition, substitution or insertion away from `word`.
+fn single_edits(word: &str) -> HashSet<String> {
+    let mut edits = HashSet::new();
+
+    for split in 0..=word.len() {
+        let (left, right) = word.split_at(split);
+
+        if let Some(rest) = right.get(1..) {
+            edits.insert(format!("{left}{rest}"));
+        }
+        if let (Some(first), Some(second), Some(rest)) =
+            (right.get(..1), right.get(1..2), right.get(2..))
+        {
+            edits.insert(format!("{left}{second}{first}{rest}"));
+        }
+        for letter in ASCII_LOWERCASE_ALPHABET.chars() {
+            if let Some(rest) = right.get(1..) {
+                edits.insert(format!("{left}{letter}{rest}"));
+            }
+            edits.insert(format!("{left}{letter}{right}"));
+        }
+    }
+
+    edits.remove(word);
+    edits
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::common_neighbor;
+    use super::single_edits;
+    use crate::constants::ACCEPTED_WORDS_FILE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_common_neighbor_requires_a_much_more_common_word() {
+        let word_counts = HashMap::from([
+            ("recipe".to_string(), 12),
+            ("recipie".to_string(), 1),
+            ("soups".to_string(), 2),
+        ]);
+
+        assert!(single_edits("recpie").contains("recipe"));
+        assert_eq!(
+            common_neighbor("recipie", &word_counts),
+            Some("recipe".to_string())
+        );
+        assert_eq!(
+            common_neighbor("soup", &word_counts),
+            None,
+            "too rare to suggest"
+        );
+    }
+
+    #[test]
+    fn test_collect_rare_words() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("recipe recipe recipe recipe recipe\nkitchen kitchen kitchen")
+            .create(&temp_dir, "notes.md");
+        TestFileBuilder::new()
+            .with_aliases(vec!["Kitchin".to_string()])
+            .with_content("# Best Recipie Ideas")
+            .create(&temp_dir, "Ideas.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.report_rare_words(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let rare_words = obsidian_repository
+            .collect_rare_words(&validated_config)
+            .unwrap();
+        let words: Vec<(&str, &str)> = rare_words
+            .iter()
+            .map(|rare_word| (rare_word.word.as_str(), rare_word.common_word.as_str()))
+            .collect();
+        assert_eq!(words, vec![("Kitchin", "kitchen"), ("Recipie", "recipe")]);
+
+        fs::create_dir_all(validated_config.output_folder()).unwrap();
+        fs::write(
+            validated_config.output_folder().join(ACCEPTED_WORDS_FILE),
+            "# words\n- recipie\n",
+        )
+        .unwrap();
+
+        let rare_words = obsidian_repository
+            .collect_rare_words(&validated_config)
+            .unwrap();
+        assert_eq!(rare_words.len(), 1, "accepted words are left out");
+        assert_eq!(rare_words[0].word, "Kitchin");
+    }
+}
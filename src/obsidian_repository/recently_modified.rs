@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+
+use chrono::Duration;
+use chrono::Utc;
+
+use super::ObsidianRepository;
+use super::ownership;
+use crate::validated_config::ValidatedConfig;
+
+/// A note with changes to write that was edited too recently, so they're skipped this run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RecentlyModifiedNote {
+    pub path:        PathBuf,
+    pub minutes_ago: i64,
+    /// The skipped changes, as `audit_trail` would record them.
+    pub changes:     Vec<String>,
+}
+
+impl ObsidianRepository {
+    /// With `skip_recently_modified_minutes` set, discards the changes to every note modified
+    /// on disk within that many minutes, along with its queued rename and what depended on its
+    /// rewrite - it may be open in Obsidian mid-edit. The note is still analyzed and reported;
+    /// its changes are found again on the first run after it settles.
+    pub(crate) fn guard_recently_modified_notes(&mut self, validated_config: &ValidatedConfig) {
+        let Some(minutes) = validated_config.skip_recently_modified_minutes() else {
+            return;
+        };
+        let now = Utc::now();
+        let cutoff = now - Duration::minutes(i64::from(minutes));
+
+        let mut recently_modified_notes = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            let modified = markdown_file.modified_date_validation.file_system;
+            if modified < cutoff {
+                continue;
+            }
+
+            let changes = ownership::discard_changes(markdown_file, &self.paths_to_rename);
+            if changes.is_empty() {
+                continue;
+            }
+            recently_modified_notes.push(RecentlyModifiedNote {
+                path: markdown_file.path.clone(),
+                minutes_ago: (now - modified).num_minutes().max(0),
+                changes,
+            });
+        }
+
+        let skipped: Vec<PathBuf> = recently_modified_notes
+            .iter()
+            .map(|note| note.path.clone())
+            .collect();
+        self.release_discarded_notes(&skipped);
+        self.recently_modified_notes = recently_modified_notes;
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use chrono::Duration;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_guard_recently_modified_notes_skips_fresh_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Topic".to_string())
+            .with_content("about the topic")
+            .create(&temp_dir, "topic.md");
+        TestFileBuilder::new()
+            .with_content("mentions Topic")
+            .create(&temp_dir, "fresh.md");
+        let two_days_ago = Utc::now() - Duration::days(2);
+        TestFileBuilder::new()
+            .with_file_system_dates(two_days_ago, two_days_ago)
+            .with_content("mentions Topic")
+            .create(&temp_dir, "settled.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.skip_recently_modified_minutes(Some(30));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let persisted: Vec<String> = obsidian_repository
+            .markdown_files
+            .files_to_persist()
+            .iter()
+            .filter_map(|file| file.path.file_name()?.to_str().map(str::to_string))
+            .collect();
+        assert!(persisted.contains(&"settled.md".to_string()));
+        assert!(!persisted.contains(&"fresh.md".to_string()));
+
+        let recently_modified_note = obsidian_repository
+            .recently_modified_notes
+            .iter()
+            .find(|note| note.path.ends_with("fresh.md"))
+            .unwrap();
+        assert!(
+            recently_modified_note
+                .changes
+                .iter()
+                .any(|change| change.contains("back populated")),
+            "{:?}",
+            recently_modified_note.changes
+        );
+    }
+
+    #[test]
+    fn test_recently_modified_note_keeps_its_duplicate_images() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["first.jpg", "second.jpg"] {
+            TestFileBuilder::new()
+                .with_content(vec![0xFF, 0xD8, 0xFF, 0xE0])
+                .create(&temp_dir, name);
+        }
+        TestFileBuilder::new()
+            .with_content("![[first.jpg]]\n![[second.jpg]]")
+            .create(&temp_dir, "fresh.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.skip_recently_modified_minutes(Some(30));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        assert_eq!(obsidian_repository.recently_modified_notes.len(), 1);
+        obsidian_repository.persist().unwrap();
+
+        for name in ["first.jpg", "second.jpg"] {
+            assert!(
+                temp_dir.path().join(name).exists(),
+                "{name} is still embedded in the note being edited"
+            );
+        }
+        assert!(
+            fs::read_to_string(temp_dir.path().join("fresh.md"))
+                .unwrap()
+                .contains("![[second.jpg]]")
+        );
+    }
+}
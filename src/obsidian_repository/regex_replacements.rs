@@ -0,0 +1,104 @@
+use super::ObsidianRepository;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// Records a `RegexReplacementMatch` for every match of the `replacements` rules. Runs last
+    /// before `apply_replaceable_matches` so a rule match overlapping a link fix, back-populate
+    /// match or image reference update can be dropped - those fixes take precedence.
+    pub(crate) fn find_all_regex_replacement_matches(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) {
+        if validated_config.replacement_rules().is_empty() {
+            return;
+        }
+
+        let fix_self_links = validated_config.fix_self_links();
+        for markdown_file in &mut self.markdown_files {
+            let mut matches = markdown_file.find_regex_replacement_matches(validated_config);
+            if matches.is_empty() {
+                continue;
+            }
+
+            let (other_matches, _) =
+                Self::collect_replaceable_matches(markdown_file, fix_self_links);
+            matches.retain(|regex_match| {
+                let end = regex_match.position + regex_match.found_text.len();
+                !other_matches.iter().any(|other| {
+                    other.line_number() == regex_match.line_number
+                        && regex_match.position < other.position() + other.matched_text().len()
+                        && other.position() < end
+                })
+            });
+
+            markdown_file.regex_replacement_matches = matches;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use crate::config::ReplacementScope;
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+    use crate::validated_config::RegexReplacementRule;
+
+    #[test]
+    fn test_regex_replacements_applied_and_yield_to_link_fixes() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("Recipe")
+            .create(&temp_dir, "Recipe.md");
+        let file_path = TestFileBuilder::new()
+            .with_content("see [[recipe]] -- a Recipe -- now")
+            .create(&temp_dir, "notes.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.replacement_rules(vec![
+                    RegexReplacementRule {
+                        regex:       Regex::new("-{2,3}").unwrap(),
+                        replacement: "\u{2014}".to_string(),
+                        scope:       ReplacementScope::Text,
+                    },
+                    RegexReplacementRule {
+                        regex:       Regex::new("(?i)recipe").unwrap(),
+                        replacement: "RECIPE".to_string(),
+                        scope:       ReplacementScope::Content,
+                    },
+                ]);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let notes = obsidian_repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path == file_path)
+            .unwrap();
+
+        assert_eq!(
+            notes.regex_replacement_matches.len(),
+            2,
+            "the rule match inside the canonicalized link and the back-populated text is dropped"
+        );
+        assert_eq!(
+            notes.content,
+            "see [[Recipe|recipe]] \u{2014} a [[Recipe]] \u{2014} now"
+        );
+        assert!(
+            notes
+                .persist_reasons
+                .contains(&PersistReason::RegexReplacementsApplied)
+        );
+    }
+}
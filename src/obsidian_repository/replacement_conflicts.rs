@@ -0,0 +1,256 @@
+use std::cmp::Reverse;
+use std::path::PathBuf;
+
+use crate::markdown_file::ByteSpan;
+use crate::markdown_file::ImageLinkState;
+use crate::markdown_file::MarkdownFile;
+use crate::markdown_file::MatchType;
+use crate::markdown_file::ReplaceableContent;
+
+/// A replacement left out because its text overlaps a replacement another phase found, which
+/// ranks ahead of it by `MatchType::priority`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ReplacementConflict {
+    pub path:        PathBuf,
+    pub line_number: usize,
+    pub position:    usize,
+    pub applied:     MatchType,
+    pub skipped:     MatchType,
+    /// The text the skipped replacement would have rewritten.
+    pub found_text:  String,
+}
+
+/// Splits `matches` into the ones to apply and the ones skipped for overlapping another.
+/// Matches are taken by `MatchType::priority`, then by where they start, with the longer of
+/// two starting together first - so the outcome never depends on which phase ran first. A
+/// match whose span doesn't resolve is kept, for `replace_body_spans` to fail the note on.
+pub(super) fn resolve_conflicts<'a>(
+    markdown_file: &MarkdownFile,
+    matches: Vec<&'a dyn ReplaceableContent>,
+) -> (Vec<&'a dyn ReplaceableContent>, Vec<ReplacementConflict>) {
+    let line_starts = markdown_file.body_line_starts();
+    let mut ranked: Vec<(Option<ByteSpan>, &dyn ReplaceableContent)> = matches
+        .into_iter()
+        .map(|replaceable| {
+            (
+                markdown_file.body_span(&line_starts, replaceable),
+                replaceable,
+            )
+        })
+        .collect();
+    ranked.sort_by_key(|(span, replaceable)| {
+        (
+            replaceable.match_type().priority(),
+            span.map(|span| (span.start, Reverse(span.end))),
+        )
+    });
+
+    let mut taken: Vec<(ByteSpan, MatchType)> = Vec::new();
+    let mut kept = Vec::with_capacity(ranked.len());
+    let mut conflicts = Vec::new();
+    for (span, replaceable) in ranked {
+        let Some(span) = span else {
+            kept.push(replaceable);
+            continue;
+        };
+
+        let overlapped = taken
+            .iter()
+            .find(|(other, _)| span.start < other.end && other.start < span.end);
+        if let Some((_, applied)) = overlapped {
+            conflicts.push(ReplacementConflict {
+                path:        markdown_file.path.clone(),
+                line_number: replaceable.line_number(),
+                position:    replaceable.position(),
+                applied:     applied.clone(),
+                skipped:     replaceable.match_type(),
+                found_text:  replaceable.matched_text(),
+            });
+            continue;
+        }
+
+        taken.push((span, replaceable.match_type()));
+        kept.push(replaceable);
+    }
+
+    (kept, conflicts)
+}
+
+/// Takes the matches `conflicts` skipped out of `markdown_file`'s collections, so its reports
+/// and counts only see the replacements that were made. A skipped image link is left as found -
+/// `mark_image_files_for_deletion` keeps the image it still names.
+pub(super) fn drop_skipped_matches(
+    markdown_file: &mut MarkdownFile,
+    conflicts: &[ReplacementConflict],
+) {
+    if conflicts.is_empty() {
+        return;
+    }
+    let is_skipped = |replaceable: &dyn ReplaceableContent| {
+        conflicts.iter().any(|conflict| {
+            conflict.skipped == replaceable.match_type()
+                && conflict.line_number == replaceable.line_number()
+                && conflict.position == replaceable.position()
+        })
+    };
+
+    markdown_file
+        .back_populate_matches
+        .unambiguous
+        .retain(|m| !is_skipped(m));
+    for conversion in &mut markdown_file.admonition_conversions {
+        conversion.line_matches.retain(|m| !is_skipped(m));
+    }
+    markdown_file
+        .admonition_conversions
+        .retain(|conversion| !conversion.line_matches.is_empty());
+    markdown_file
+        .canonical_link_matches
+        .retain(|m| !is_skipped(m));
+    markdown_file
+        .custom_replacement_matches
+        .retain(|m| !is_skipped(m));
+    markdown_file
+        .date_mention_matches
+        .retain(|m| !is_skipped(m));
+    markdown_file
+        .heading_link_repair_matches
+        .retain(|m| !is_skipped(m));
+    markdown_file
+        .phantom_link_matches
+        .retain(|m| !is_skipped(m));
+    markdown_file
+        .regex_replacement_matches
+        .retain(|m| !is_skipped(m));
+    markdown_file.self_link_matches.retain(|m| !is_skipped(m));
+    markdown_file
+        .wikilink_repair_matches
+        .retain(|m| !is_skipped(m));
+    for link in markdown_file.image_links.iter_mut() {
+        if link.state != ImageLinkState::Found && is_skipped(link) {
+            link.state = ImageLinkState::Found;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::markdown_file::BackPopulateMatch;
+    use crate::markdown_file::DateMentionMatch;
+    use crate::markdown_file::LineReplacement;
+    use crate::markdown_file::MatchContext;
+    use crate::markdown_file::MatchType;
+    use crate::markdown_file::RegexReplacementMatch;
+    use crate::markdown_file::ReplacementSource;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_overlapping_replacements_resolved_by_priority() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = TestFileBuilder::new()
+            .with_content("Ed wrote on 2024-03-02")
+            .create(&temp_dir, "notes.md");
+        let mut markdown_file = test_support::get_test_markdown_file(file_path);
+
+        markdown_file.back_populate_matches.unambiguous = vec![BackPopulateMatch {
+            relative_path: "notes.md".into(),
+            line_number:   1,
+            line_text:     "Ed wrote on 2024-03-02".into(),
+            found_text:    "Ed".into(),
+            replacement:   "[[Ed Barnes|Ed]]".into(),
+            position:      0,
+            match_context: MatchContext::Plaintext,
+        }];
+        markdown_file.regex_replacement_matches = vec![RegexReplacementMatch {
+            found_text:    "Ed wrote".to_string(),
+            line_number:   1,
+            pattern:       "Ed wrote".to_string(),
+            position:      0,
+            relative_path: "notes.md".to_string(),
+            replacement:   "Ed said".to_string(),
+        }];
+        markdown_file.date_mention_matches = vec![DateMentionMatch {
+            found_text:    "2024-03-02".to_string(),
+            line_number:   1,
+            position:      12,
+            relative_path: "notes.md".to_string(),
+            replacement:   "[[2024-03-02]]".to_string(),
+        }];
+
+        let (matches, conflicts) =
+            ObsidianRepository::collect_replaceable_matches(&markdown_file, false);
+
+        let applied: Vec<MatchType> = matches.iter().map(|m| m.match_type()).collect();
+        assert_eq!(
+            applied,
+            vec![MatchType::RegexReplacement, MatchType::DateMention],
+            "matches that don't overlap are all applied, in document order"
+        );
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].applied, MatchType::RegexReplacement);
+        assert_eq!(conflicts[0].skipped, MatchType::BackPopulate);
+        assert_eq!(conflicts[0].found_text, "Ed");
+    }
+
+    struct TripNotes;
+
+    impl ReplacementSource for TripNotes {
+        fn name(&self) -> &'static str { "trip notes" }
+
+        fn find_replacements(&self, line: &str) -> Vec<LineReplacement> {
+            line.find("Tokyo trip")
+                .map(|position| LineReplacement {
+                    position,
+                    found_text: "Tokyo trip".to_string(),
+                    replacement: "Tokyo journey".to_string(),
+                })
+                .into_iter()
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_skipped_back_populate_match_left_out_of_its_table() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("the city")
+            .create(&temp_dir, "Tokyo.md");
+        TestFileBuilder::new()
+            .with_content("landed in Tokyo\nthe Tokyo trip")
+            .create(&temp_dir, "Trip.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+        let obsidian_repository =
+            ObsidianRepository::with_replacement_sources(&validated_config, &[&TripNotes]).unwrap();
+
+        let conflicts = &obsidian_repository.replacement_conflicts;
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].applied,
+            MatchType::Custom("trip notes".to_string())
+        );
+        assert_eq!(conflicts[0].skipped, MatchType::BackPopulate);
+
+        let table_lines: Vec<usize> = obsidian_repository
+            .markdown_files
+            .files_to_persist()
+            .unambiguous_matches()
+            .iter()
+            .map(|m| m.line_number)
+            .collect();
+        assert_eq!(
+            table_lines,
+            vec![conflicts[0].line_number - 1],
+            "only the applied match is listed as a change"
+        );
+    }
+}
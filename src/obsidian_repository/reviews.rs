@@ -0,0 +1,167 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+
+use super::ObsidianRepository;
+use super::vault_index;
+use crate::config::ReviewRule;
+use crate::constants::FORMAT_DATE;
+use crate::constants::TAG_PREFIX;
+use crate::markdown_file::MarkdownFile;
+use crate::validated_config::ValidatedConfig;
+
+/// A note left unmodified for longer than the `review_after` rule covering it allows.
+#[derive(Clone, Debug)]
+pub(crate) struct OverdueReview {
+    pub file_path:     PathBuf,
+    pub rule:          ReviewRule,
+    pub date_modified: NaiveDate,
+    pub due:           NaiveDate,
+    pub days_overdue:  i64,
+}
+
+impl ObsidianRepository {
+    /// Lists the notes due for review under `review_after` and, with `set_next_review` on,
+    /// writes the day each covered note falls due to its `next_review`. Runs after
+    /// `check_all_date_consistency`, so it goes by the `date_modified` each note will be written
+    /// with - a note changed by this run isn't due.
+    pub(crate) fn check_reviews(&mut self, validated_config: &ValidatedConfig) {
+        let rules = validated_config.review_after();
+        if rules.is_empty() {
+            return;
+        }
+        let Ok(today) = NaiveDate::parse_from_str(&validated_config.today(), FORMAT_DATE) else {
+            return;
+        };
+
+        let mut overdue_reviews = Vec::new();
+        for markdown_file in &mut self.markdown_files {
+            let Some(rule) = rules
+                .iter()
+                .find(|rule| covers(rule, markdown_file, validated_config.obsidian_path()))
+            else {
+                continue;
+            };
+            let Some((date_modified, due)) = markdown_file.review_dates(rule.days) else {
+                continue;
+            };
+
+            if due < today {
+                overdue_reviews.push(OverdueReview {
+                    file_path: markdown_file.path.clone(),
+                    rule: rule.clone(),
+                    date_modified,
+                    due,
+                    days_overdue: (today - due).num_days(),
+                });
+            }
+            if validated_config.set_next_review() {
+                markdown_file.set_next_review(due, validated_config.hold_persist_reasons());
+            }
+        }
+
+        overdue_reviews.sort_by(|a, b| {
+            a.due
+                .cmp(&b.due)
+                .then_with(|| a.file_path.cmp(&b.file_path))
+        });
+        self.overdue_reviews = overdue_reviews;
+    }
+}
+
+fn covers(rule: &ReviewRule, markdown_file: &MarkdownFile, obsidian_path: &Path) -> bool {
+    let in_folder = rule
+        .folder
+        .as_ref()
+        .is_none_or(|folder| markdown_file.path.starts_with(obsidian_path.join(folder)));
+    let tagged = rule.tag.as_deref().is_none_or(|tag| {
+        let tag = tag.trim().trim_start_matches(TAG_PREFIX).to_lowercase();
+        vault_index::note_tags(markdown_file)
+            .iter()
+            .any(|note_tag| note_tag.to_lowercase() == tag)
+    });
+    in_folder && tagged
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use chrono::NaiveDate;
+    use serde_yaml::Value;
+    use tempfile::TempDir;
+
+    use crate::config::PersistReasonKind;
+    use crate::config::ReviewRule;
+    use crate::constants::NEXT_REVIEW_PROPERTY;
+    use crate::markdown_file::PersistReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_overdue_notes_listed_and_next_review_set() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("projects")).unwrap();
+        let date = test_support::eastern_midnight(2024, 1, 1);
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "projects/Plan.md");
+        TestFileBuilder::new()
+            .with_matching_dates(date)
+            .create(&temp_dir, "Evergreen.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder
+                    .review_after(vec![
+                        ReviewRule {
+                            days:   30,
+                            folder: Some(PathBuf::from("projects")),
+                            tag:    None,
+                        },
+                        ReviewRule {
+                            days:   100_000,
+                            folder: None,
+                            tag:    None,
+                        },
+                    ])
+                    .set_next_review(true)
+                    // Not every file system keeps the creation time set above, and fixing
+                    // date_created would date the notes today.
+                    .hold_persist_reasons(vec![PersistReasonKind::DateCreatedUpdated]);
+            })
+            .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let due = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        assert_eq!(repository.overdue_reviews.len(), 1);
+        assert!(repository.overdue_reviews[0].file_path.ends_with("Plan.md"));
+        assert_eq!(repository.overdue_reviews[0].due, due);
+
+        let plan = repository
+            .markdown_files
+            .iter()
+            .find(|markdown_file| markdown_file.path.ends_with("Plan.md"))
+            .unwrap();
+        let front_matter = plan.front_matter.as_ref().unwrap();
+        assert_eq!(
+            front_matter
+                .property(NEXT_REVIEW_PROPERTY)
+                .and_then(Value::as_str),
+            Some("[[2024-01-31]]")
+        );
+        assert_eq!(front_matter.date_modified(), Some("[[2024-01-01]]"));
+        assert_eq!(front_matter.raw_modified, Some(date));
+        assert_eq!(
+            plan.persist_reasons,
+            vec![PersistReason::NextReviewSet { due }]
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use super::ObsidianRepository;
+use crate::validated_config::ValidatedConfig;
+
+/// Something in a note that looks like a credential, found by `scan_secrets`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PossibleSecret {
+    pub path:        PathBuf,
+    pub line_number: usize,
+    pub detector:    String,
+    pub preview:     String,
+}
+
+impl ObsidianRepository {
+    /// With `scan_secrets` set, looks through every note for keys, tokens and random-looking
+    /// strings, and for anything `secret_patterns` matches.
+    pub(crate) fn scan_for_secrets(&mut self, validated_config: &ValidatedConfig) {
+        if !validated_config.scan_secrets() {
+            return;
+        }
+
+        self.possible_secrets = self
+            .markdown_files
+            .iter()
+            .flat_map(|markdown_file| {
+                markdown_file
+                    .possible_secrets(validated_config.secret_patterns())
+                    .into_iter()
+                    .map(|secret_match| PossibleSecret {
+                        path:        markdown_file.path.clone(),
+                        line_number: secret_match.line_number,
+                        detector:    secret_match.detector,
+                        preview:     secret_match.preview,
+                    })
+            })
+            .collect();
+    }
+}
@@ -0,0 +1,307 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use rayon::prelude::*;
+
+use super::ObsidianRepository;
+use super::constants::PERCENT;
+use super::constants::PERCENT_SCALE;
+use super::constants::ZERO_DIGIT;
+use super::phantom_links;
+use crate::validated_config::ValidatedConfig;
+
+/// Two notes whose titles or aliases are near duplicates of each other. `merge_into` is the note
+/// more links point at, when one does.
+#[derive(Clone, Debug)]
+pub(crate) struct SimilarTitlePair {
+    pub first:       PathBuf,
+    pub first_name:  String,
+    pub second:      PathBuf,
+    pub second_name: String,
+    pub similarity:  f64,
+    pub merge_into:  Option<PathBuf>,
+}
+
+/// A title or alias of one note, normalized for comparison.
+struct ComparableName {
+    note_index: usize,
+    name:       String,
+    normalized: String,
+    numbers:    Vec<String>,
+}
+
+impl ObsidianRepository {
+    /// With `similar_title_threshold` set, compares the titles and aliases of every pair of notes
+    /// by normalized Levenshtein similarity after lowercasing, collapsing punctuation and
+    /// dropping leading zeros (`Meeting notes 2024-03-02` and `Meeting Notes 2024-3-2` are
+    /// identical). Names whose numbers differ never match, so daily notes and numbered series
+    /// aren't reported.
+    pub(crate) fn collect_similar_titles(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Vec<SimilarTitlePair> {
+        let Some(threshold_percent) = validated_config.similar_title_threshold() else {
+            return Vec::new();
+        };
+        let names = self.comparable_names();
+        let best_matches = best_name_matches(&names, threshold_percent);
+
+        if best_matches.is_empty() {
+            return Vec::new();
+        }
+
+        let reference_counts = self.reference_counts();
+        let references_to = |note_index: usize| {
+            self.markdown_files[note_index]
+                .path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .and_then(|stem| reference_counts.get(&stem.to_lowercase()))
+                .copied()
+                .unwrap_or_default()
+        };
+
+        let mut pairs: Vec<SimilarTitlePair> = best_matches
+            .into_iter()
+            .map(|((first_note, second_note), (similarity, i, j))| {
+                let (first_name, second_name) = if names[i].note_index == first_note {
+                    (&names[i].name, &names[j].name)
+                } else {
+                    (&names[j].name, &names[i].name)
+                };
+                let merge_into = match references_to(first_note).cmp(&references_to(second_note)) {
+                    Ordering::Greater => Some(first_note),
+                    Ordering::Less => Some(second_note),
+                    Ordering::Equal => None,
+                };
+
+                SimilarTitlePair {
+                    first: self.markdown_files[first_note].path.clone(),
+                    first_name: first_name.clone(),
+                    second: self.markdown_files[second_note].path.clone(),
+                    second_name: second_name.clone(),
+                    similarity,
+                    merge_into: merge_into
+                        .map(|note_index| self.markdown_files[note_index].path.clone()),
+                }
+            })
+            .collect();
+
+        pairs.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then_with(|| a.first.cmp(&b.first))
+                .then_with(|| a.second.cmp(&b.second))
+        });
+
+        pairs
+    }
+
+    /// Every title and alias, sorted by normalized length.
+    fn comparable_names(&self) -> Vec<ComparableName> {
+        let mut names: Vec<ComparableName> = self
+            .markdown_files
+            .iter()
+            .enumerate()
+            .flat_map(|(note_index, markdown_file)| {
+                markdown_file
+                    .path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .map(str::to_string)
+                    .into_iter()
+                    .chain(
+                        markdown_file
+                            .front_matter
+                            .as_ref()
+                            .and_then(|front_matter| front_matter.aliases())
+                            .unwrap_or_default()
+                            .iter()
+                            .cloned(),
+                    )
+                    .map(move |name| {
+                        let (normalized, numbers) = normalize_title(&name);
+                        ComparableName {
+                            note_index,
+                            name,
+                            normalized,
+                            numbers,
+                        }
+                    })
+            })
+            .filter(|name| !name.normalized.is_empty())
+            .collect();
+        names.sort_by_key(|name| name.normalized.chars().count());
+        names
+    }
+
+    /// Number of content and frontmatter property links to each lowercased note stem.
+    pub(super) fn reference_counts(&self) -> HashMap<String, usize> {
+        let mut reference_counts: HashMap<String, usize> = HashMap::new();
+        for markdown_file in &self.markdown_files {
+            for frontmatter_wikilink in &markdown_file.frontmatter_wikilinks {
+                *reference_counts
+                    .entry(phantom_links::target_note_stem(
+                        &frontmatter_wikilink.wikilink.target,
+                    ))
+                    .or_default() += 1;
+            }
+            markdown_file.for_each_content_wikilink(|_, _, spanned_wikilink| {
+                *reference_counts
+                    .entry(phantom_links::target_note_stem(
+                        &spanned_wikilink.wikilink.target,
+                    ))
+                    .or_default() += 1;
+            });
+        }
+        reference_counts
+    }
+}
+
+/// The most similar pair of names for each pair of notes at or above `threshold_percent`, keyed
+/// by note indexes and holding the similarity and both indexes into `names`.
+fn best_name_matches(
+    names: &[ComparableName],
+    threshold_percent: u8,
+) -> HashMap<(usize, usize), (f64, usize, usize)> {
+    let threshold = f64::from(threshold_percent) / PERCENT;
+    let threshold_percent = usize::from(threshold_percent);
+
+    (0..names.len())
+        .into_par_iter()
+        .flat_map_iter(|i| {
+            let names = &names;
+            let shorter_len = names[i].normalized.chars().count();
+            names[i + 1..]
+                .iter()
+                .enumerate()
+                // Sorted by length: once the length gap alone puts similarity under the
+                // threshold, no longer name can match.
+                .take_while(move |(_, longer)| {
+                    shorter_len * PERCENT_SCALE
+                        >= threshold_percent * longer.normalized.chars().count()
+                })
+                .map(move |(offset, _)| (i, i + 1 + offset))
+        })
+        .filter_map(|(i, j)| {
+            let (a, b) = (&names[i], &names[j]);
+            if a.note_index == b.note_index || a.numbers != b.numbers {
+                return None;
+            }
+            let similarity = strsim::normalized_levenshtein(&a.normalized, &b.normalized);
+            (similarity >= threshold).then_some((i, j, similarity))
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .fold(HashMap::new(), |mut best, (i, j, similarity)| {
+            let key = (
+                names[i].note_index.min(names[j].note_index),
+                names[i].note_index.max(names[j].note_index),
+            );
+            let entry = best.entry(key).or_insert((similarity, i, j));
+            if similarity > entry.0 {
+                *entry = (similarity, i, j);
+            }
+            best
+        })
+}
+
+/// Lowercases `title`, collapses every run of punctuation or whitespace to one space and strips
+/// leading zeros from numbers. Also returns the numbers, which must match exactly.
+fn normalize_title(title: &str) -> (String, Vec<String>) {
+    let mut numbers = Vec::new();
+    let tokens: Vec<String> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            if token.chars().all(|c| c.is_ascii_digit()) {
+                let trimmed = token.trim_start_matches(ZERO_DIGIT);
+                let number = if trimmed.is_empty() {
+                    ZERO_DIGIT.to_string()
+                } else {
+                    trimmed.to_string()
+                };
+                numbers.push(number.clone());
+                number
+            } else {
+                token.to_lowercase()
+            }
+        })
+        .collect();
+
+    (tokens.join(" "), numbers)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::normalize_title;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_normalize_title() {
+        assert_eq!(
+            normalize_title("Meeting notes 2024-03-02"),
+            normalize_title("Meeting Notes 2024-3-2")
+        );
+        assert_eq!(
+            normalize_title("Chapter 007: The End").0,
+            "chapter 7 the end"
+        );
+    }
+
+    #[test]
+    fn test_collect_similar_titles() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("note")
+            .create(&temp_dir, "Meeting notes 2024-03-02.md");
+        TestFileBuilder::new()
+            .with_content("note")
+            .create(&temp_dir, "Meeting Notes 2024-3-2.md");
+        TestFileBuilder::new()
+            .with_content("note")
+            .create(&temp_dir, "Meeting notes 2024-03-03.md");
+        TestFileBuilder::new()
+            .with_aliases(vec!["Sourdough Starter".to_string()])
+            .with_content("note")
+            .create(&temp_dir, "Starter.md");
+        TestFileBuilder::new()
+            .with_content("[[Sourdough Startr]] and [[Sourdough Startr]]")
+            .create(&temp_dir, "Sourdough Startr.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.similar_title_threshold(Some(90));
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let pairs = obsidian_repository.collect_similar_titles(&validated_config);
+
+        assert_eq!(pairs.len(), 2, "different dates are never similar");
+        assert!((pairs[0].similarity - 1.0).abs() < f64::EPSILON);
+        assert!(pairs[0].merge_into.is_none());
+
+        let names = [pairs[1].first_name.as_str(), pairs[1].second_name.as_str()];
+        assert!(names.contains(&"Sourdough Starter"));
+        assert!(names.contains(&"Sourdough Startr"));
+        assert!(
+            pairs[1]
+                .merge_into
+                .as_ref()
+                .unwrap()
+                .ends_with("Sourdough Startr.md"),
+            "the note more links point at is the merge target"
+        );
+    }
+}
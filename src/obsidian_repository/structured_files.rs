@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use super::ObsidianRepository;
+use super::constants::STRUCTURED_FILE_PATH_KEY;
+use super::phantom_links;
+use crate::constants::BASE;
+use crate::constants::CANVAS;
+use crate::constants::FILE_NOT_FOUND;
+use crate::constants::FORWARD_SLASH;
+use crate::constants::UNPARSEABLE;
+use crate::constants::UNRESOLVED_WIKILINK;
+use crate::support;
+use crate::support::RepositoryFiles;
+use crate::wikilink;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum StructuredFileKind {
+    /// An Obsidian base - YAML holding views and filters over notes.
+    Base,
+    /// An Obsidian canvas - JSON cards and the edges between them.
+    Canvas,
+}
+
+impl Display for StructuredFileKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Base => f.write_str(BASE),
+            Self::Canvas => f.write_str(CANVAS),
+        }
+    }
+}
+
+/// A vault-relative path held in a `file` field - a canvas file card.
+#[derive(Clone, Debug)]
+pub(crate) struct FileReference {
+    pub raw:  String,
+    pub path: PathBuf,
+}
+
+/// A `.base` or `.canvas` file. Neither has frontmatter, so ok only reads the notes and files
+/// it points at.
+#[derive(Clone, Debug)]
+pub(crate) struct StructuredFile {
+    pub path:             PathBuf,
+    pub kind:             StructuredFileKind,
+    pub file_references:  Vec<FileReference>,
+    /// Targets of the wikilinks in its text - canvas text cards and base filters.
+    pub wikilink_targets: Vec<String>,
+    pub parse_error:      Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum StructuredFileIssueReason {
+    FileNotFound,
+    UnresolvedWikilink,
+    Unparseable,
+}
+
+impl Display for StructuredFileIssueReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FileNotFound => f.write_str(FILE_NOT_FOUND),
+            Self::UnresolvedWikilink => f.write_str(UNRESOLVED_WIKILINK),
+            Self::Unparseable => f.write_str(UNPARSEABLE),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct StructuredFileIssue {
+    pub path:      PathBuf,
+    pub kind:      StructuredFileKind,
+    pub reference: String,
+    pub reason:    StructuredFileIssueReason,
+}
+
+impl StructuredFile {
+    fn load(path: &Path, kind: StructuredFileKind, obsidian_path: &Path) -> Self {
+        let mut structured_file = Self {
+            path: path.to_path_buf(),
+            kind,
+            file_references: Vec::new(),
+            wikilink_targets: Vec::new(),
+            parse_error: None,
+        };
+
+        match parse(path, kind) {
+            Ok(value) => structured_file.collect_references(&value, obsidian_path),
+            Err(error) => structured_file.parse_error = Some(error.to_string()),
+        }
+        structured_file
+    }
+
+    fn collect_references(&mut self, value: &Value, obsidian_path: &Path) {
+        match value {
+            Value::Object(fields) => {
+                for (key, field) in fields {
+                    if key == STRUCTURED_FILE_PATH_KEY
+                        && let Value::String(raw) = field
+                    {
+                        self.file_references.push(FileReference {
+                            raw:  raw.clone(),
+                            path: obsidian_path.join(raw),
+                        });
+                    } else {
+                        self.collect_references(field, obsidian_path);
+                    }
+                }
+            },
+            Value::Array(values) => {
+                for value in values {
+                    self.collect_references(value, obsidian_path);
+                }
+            },
+            Value::String(text) => self.wikilink_targets.extend(
+                wikilink::extract_wikilinks(text)
+                    .valid
+                    .into_iter()
+                    .map(|spanned_wikilink| spanned_wikilink.wikilink.target),
+            ),
+            Value::Null | Value::Bool(_) | Value::Number(_) => {},
+        }
+    }
+}
+
+/// Canvases are JSON; bases are YAML, read into the same shape.
+fn parse(path: &Path, kind: StructuredFileKind) -> Result<Value, Box<dyn Error + Send + Sync>> {
+    let content = fs::read_to_string(path)?;
+    Ok(match kind {
+        StructuredFileKind::Canvas => serde_json::from_str(&content)?,
+        StructuredFileKind::Base => {
+            serde_json::to_value(serde_yaml::from_str::<serde_yaml::Value>(&content)?)?
+        },
+    })
+}
+
+fn lowercase_file_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .and_then(OsStr::to_str)
+        .map(str::to_lowercase)
+}
+
+impl ObsidianRepository {
+    pub(super) fn load_structured_files(
+        repository_files: &RepositoryFiles,
+        obsidian_path: &Path,
+    ) -> Vec<StructuredFile> {
+        repository_files
+            .bases
+            .iter()
+            .map(|path| StructuredFile::load(path, StructuredFileKind::Base, obsidian_path))
+            .chain(
+                repository_files.canvases.iter().map(|path| {
+                    StructuredFile::load(path, StructuredFileKind::Canvas, obsidian_path)
+                }),
+            )
+            .collect()
+    }
+
+    /// Lists every `file` path in a base or canvas that names no file, and every wikilink in one
+    /// that names no note, image, base or canvas.
+    pub(crate) fn validate_structured_files(&mut self) {
+        let note_stems = self.markdown_note_stems();
+        let file_names: HashSet<String> = self
+            .image_files
+            .iter()
+            .map(|image_file| &image_file.path)
+            .chain(self.structured_files.iter().map(|file| &file.path))
+            .filter_map(|path| lowercase_file_name(path))
+            .collect();
+
+        let mut issues = Vec::new();
+        for structured_file in &self.structured_files {
+            let mut push_issue = |reference: &str, reason| {
+                issues.push(StructuredFileIssue {
+                    path: structured_file.path.clone(),
+                    kind: structured_file.kind,
+                    reference: reference.to_string(),
+                    reason,
+                });
+            };
+
+            if let Some(parse_error) = &structured_file.parse_error {
+                push_issue(parse_error, StructuredFileIssueReason::Unparseable);
+                continue;
+            }
+            for file_reference in &structured_file.file_references {
+                if !file_reference.path.exists() {
+                    push_issue(&file_reference.raw, StructuredFileIssueReason::FileNotFound);
+                }
+            }
+            for target in &structured_file.wikilink_targets {
+                let stem = phantom_links::target_note_stem(target);
+                if !note_stems.contains(&stem) && !file_names.contains(&stem) {
+                    push_issue(target, StructuredFileIssueReason::UnresolvedWikilink);
+                }
+            }
+        }
+        self.structured_file_issues = issues;
+    }
+
+    /// Note stems and file names the wikilinks in bases and canvases point at, lowercased. ok
+    /// can't rewrite these, so they keep a file from being renamed.
+    pub(super) fn structured_file_wikilink_names(&self) -> HashSet<String> {
+        self.structured_files
+            .iter()
+            .flat_map(|structured_file| &structured_file.wikilink_targets)
+            .map(|target| phantom_links::target_note_stem(target))
+            .collect()
+    }
+
+    /// Points the canvas file cards at the files `rename_unsafe_paths` renamed. Runs after the
+    /// renames, so a renamed canvas is read from its new path.
+    pub(super) fn propagate_renames(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let renamed: HashMap<&PathBuf, &PathBuf> = self
+            .paths_to_rename
+            .iter()
+            .map(|(path, suggested)| (path, suggested))
+            .collect();
+
+        for structured_file in &self.structured_files {
+            let replacements: Vec<(String, String)> = structured_file
+                .file_references
+                .iter()
+                .filter_map(|file_reference| {
+                    let suggested = renamed.get(&file_reference.path)?;
+                    let new_name = suggested.file_name().and_then(OsStr::to_str)?;
+                    let new_raw = match file_reference.raw.rsplit_once(FORWARD_SLASH) {
+                        Some((folder, _)) => format!("{folder}{FORWARD_SLASH}{new_name}"),
+                        None => new_name.to_string(),
+                    };
+                    Some((
+                        serde_json::to_string(&file_reference.raw).ok()?,
+                        serde_json::to_string(&new_raw).ok()?,
+                    ))
+                })
+                .collect();
+            if replacements.is_empty() {
+                continue;
+            }
+
+            let path = renamed
+                .get(&structured_file.path)
+                .filter(|suggested| suggested.exists())
+                .map_or(&structured_file.path, |suggested| *suggested);
+            let mut content = fs::read_to_string(path)?;
+            for (old, new) in replacements {
+                content = content.replace(&old, &new);
+            }
+            support::write_atomically(path, content)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::StructuredFileIssueReason;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_structured_file_references_validated_and_renames_propagated() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Plan".to_string())
+            .create(&temp_dir, "Plan?.md");
+        TestFileBuilder::new()
+            .with_title("Goals".to_string())
+            .create(&temp_dir, "goals.md");
+        fs::write(
+            temp_dir.path().join("board.canvas"),
+            "{\n\t\"nodes\":[\n\t\t{\"id\":\"1\",\"type\":\"file\",\"file\":\"Plan?.md\"},\n\t\t\
+             {\"id\":\"2\",\"type\":\"file\",\"file\":\"gone.md\"},\n\t\t\
+             {\"id\":\"3\",\"type\":\"text\",\"text\":\"see [[goals]] and [[missing]]\"}\n\t],\n\t\
+             \"edges\":[]\n}",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("broken.base"), "views: [unclosed").unwrap();
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.rename_unsafe_paths(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        assert_eq!(obsidian_repository.structured_files.len(), 2);
+        let issues: Vec<(&str, StructuredFileIssueReason)> = obsidian_repository
+            .structured_file_issues
+            .iter()
+            .filter(|issue| issue.reason != StructuredFileIssueReason::Unparseable)
+            .map(|issue| (issue.reference.as_str(), issue.reason))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                ("gone.md", StructuredFileIssueReason::FileNotFound),
+                ("missing", StructuredFileIssueReason::UnresolvedWikilink),
+            ]
+        );
+        assert!(
+            obsidian_repository
+                .structured_file_issues
+                .iter()
+                .any(|issue| issue.path.ends_with("broken.base")
+                    && issue.reason == StructuredFileIssueReason::Unparseable)
+        );
+
+        // Only a canvas file card points at the note, and ok rewrites those on a rename.
+        assert_eq!(obsidian_repository.paths_to_rename.len(), 1);
+        obsidian_repository.persist().unwrap();
+        assert!(temp_dir.path().join("Plan.md").exists());
+        let canvas = fs::read_to_string(temp_dir.path().join("board.canvas")).unwrap();
+        assert!(canvas.contains("\"file\":\"Plan.md\""));
+        assert!(canvas.contains("\"file\":\"gone.md\""));
+    }
+}
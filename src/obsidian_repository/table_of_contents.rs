@@ -0,0 +1,24 @@
+use anyhow::Result as AnyhowResult;
+
+use super::ObsidianRepository;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `table_of_contents_min_headings` set, adds, refreshes or removes the managed table
+    /// of contents in every note. Runs after `apply_replaceable_matches` so the entries match
+    /// headings as they will be written.
+    pub(crate) fn refresh_all_tables_of_contents(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> AnyhowResult<()> {
+        let Some(min_headings) = validated_config.table_of_contents_min_headings() else {
+            return Ok(());
+        };
+
+        for markdown_file in &mut self.markdown_files {
+            markdown_file
+                .refresh_table_of_contents(min_headings, validated_config.operational_timezone())?;
+        }
+        Ok(())
+    }
+}
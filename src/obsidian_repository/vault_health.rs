@@ -0,0 +1,231 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::from_reader;
+use serde_json::to_vec_pretty;
+
+use super::ObsidianRepository;
+use super::constants::PERCENT_SCALE;
+use super::constants::VAULT_HEALTH_CATEGORY_COUNT;
+use crate::constants::VAULT_HEALTH_HISTORY_FILE;
+use crate::image_file::ImageFileState;
+use crate::markdown_file::ImageLinkState;
+use crate::support;
+use crate::support::VecEnumFilter;
+use crate::validated_config::ValidatedConfig;
+
+/// One day's vault health. Each count is the notes or images with at least one issue of that
+/// kind, so the score weighs a category by the share of the vault it touches.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct VaultHealthEntry {
+    pub date:                String,
+    /// 100 when nothing is wrong - each category takes off its share of the vault affected,
+    /// averaged across the categories.
+    pub score:               usize,
+    pub notes:               usize,
+    pub images:              usize,
+    pub broken_links:        usize,
+    pub missing_frontmatter: usize,
+    pub orphan_images:       usize,
+    pub date_mismatches:     usize,
+}
+
+impl ObsidianRepository {
+    /// Scores the vault as analyzed and records it in the history in the state folder. The
+    /// history holds one entry per day - a later run on the same day replaces the earlier one.
+    pub(crate) fn record_vault_health(
+        &mut self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let entry = self.vault_health(
+            validated_config.today(),
+            validated_config.date_link_format(),
+        );
+
+        let history_path = validated_config
+            .state_folder()
+            .join(VAULT_HEALTH_HISTORY_FILE);
+        let mut history = load_history(&history_path);
+        history.retain(|recorded| recorded.date != entry.date);
+        history.push(entry);
+        history.sort_by(|a, b| a.date.cmp(&b.date));
+        save_history(&history_path, &history)?;
+
+        self.vault_health_history = history;
+        Ok(())
+    }
+
+    fn vault_health(&self, date: String, date_link_format: &str) -> VaultHealthEntry {
+        let mut broken_link_files: HashSet<PathBuf> = self
+            .collect_unresolved_links(date_link_format)
+            .into_iter()
+            .map(|unresolved_link| unresolved_link.file_path)
+            .collect();
+        broken_link_files.extend(
+            self.markdown_files
+                .iter()
+                .filter(|markdown_file| {
+                    !markdown_file
+                        .image_links
+                        .filter_by_variant(ImageLinkState::Missing)
+                        .is_empty()
+                })
+                .map(|markdown_file| markdown_file.path.clone()),
+        );
+
+        let notes = self.markdown_files.len();
+        let images = self.image_files.images.len();
+        let broken_links = broken_link_files.len();
+        let missing_frontmatter = self
+            .markdown_files
+            .iter()
+            .filter(|markdown_file| markdown_file.frontmatter_error.is_some())
+            .count();
+        let orphan_images = self
+            .image_files
+            .images
+            .iter()
+            .filter(|image| matches!(image.state, ImageFileState::Unreferenced))
+            .count();
+        let date_mismatches = self
+            .markdown_files
+            .iter()
+            .filter(|markdown_file| {
+                markdown_file.created_date_validation.issue.is_some()
+                    || markdown_file.modified_date_validation.issue.is_some()
+            })
+            .count();
+
+        let penalty = percent_of(broken_links, notes)
+            + percent_of(missing_frontmatter, notes)
+            + percent_of(orphan_images, images)
+            + percent_of(date_mismatches, notes);
+
+        VaultHealthEntry {
+            date,
+            score: PERCENT_SCALE
+                - (penalty + VAULT_HEALTH_CATEGORY_COUNT / 2) / VAULT_HEALTH_CATEGORY_COUNT,
+            notes,
+            images,
+            broken_links,
+            missing_frontmatter,
+            orphan_images,
+            date_mismatches,
+        }
+    }
+}
+
+/// `count` as a rounded percentage of `total` - an empty vault or image folder has nothing wrong.
+const fn percent_of(count: usize, total: usize) -> usize {
+    if total == 0 {
+        return 0;
+    }
+    (count * PERCENT_SCALE + total / 2) / total
+}
+
+/// A missing or unreadable history starts over rather than failing the run.
+fn load_history(path: &Path) -> Vec<VaultHealthEntry> {
+    File::open(path).map_or_else(
+        |_| Vec::new(),
+        |file| from_reader(BufReader::new(file)).unwrap_or_default(),
+    )
+}
+
+fn save_history(
+    path: &Path,
+    history: &[VaultHealthEntry],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    support::write_atomically(path, to_vec_pretty(history)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::VaultHealthEntry;
+    use super::save_history;
+    use crate::constants::CACHE_FOLDER;
+    use crate::constants::VAULT_HEALTH_HISTORY_FILE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_vault_health_scored_and_recorded_once_per_day() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_content("links to [[nowhere]]")
+            .create(&temp_dir, "broken.md");
+        TestFileBuilder::new()
+            .with_content("just text")
+            .create(&temp_dir, "plain.md");
+        TestFileBuilder::new()
+            .with_content(vec![0xFF, 0xD8, 0xFF, 0xE0])
+            .create(&temp_dir, "orphan.jpg");
+
+        let history_path = temp_dir
+            .path()
+            .join(CACHE_FOLDER)
+            .join(VAULT_HEALTH_HISTORY_FILE);
+        let earlier = VaultHealthEntry {
+            date:                "2020-01-01".to_string(),
+            score:               40,
+            notes:               2,
+            images:              1,
+            broken_links:        2,
+            missing_frontmatter: 2,
+            orphan_images:       1,
+            date_mismatches:     2,
+        };
+        save_history(&history_path, std::slice::from_ref(&earlier)).unwrap();
+
+        let validated_config = test_support::get_test_validated_config(&temp_dir, None);
+        let _ = ObsidianRepository::new(&validated_config).unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+
+        let history = &obsidian_repository.vault_health_history;
+        assert_eq!(
+            history.len(),
+            2,
+            "a second run the same day replaces the entry"
+        );
+        assert_eq!(history[0], earlier);
+
+        let today = &history[1];
+        assert_eq!(
+            (
+                today.notes,
+                today.images,
+                today.broken_links,
+                today.missing_frontmatter,
+                today.orphan_images,
+            ),
+            (2, 1, 1, 2, 1)
+        );
+        // Broken links 50%, missing frontmatter 100%, orphan images 100%, and every note is
+        // missing its dates, so 100% - the average penalty is 88%.
+        assert_eq!(today.date_mismatches, 2);
+        assert_eq!(today.score, 12);
+
+        let saved: Vec<VaultHealthEntry> =
+            serde_json::from_str(&fs::read_to_string(&history_path).unwrap()).unwrap();
+        assert_eq!(&saved, history);
+    }
+}
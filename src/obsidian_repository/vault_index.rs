@@ -0,0 +1,298 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use rusqlite::Connection;
+use rusqlite::Transaction;
+use rusqlite::params;
+
+use super::ObsidianRepository;
+use super::constants::FRONTMATTER_LINE_NUMBER;
+use super::constants::INSERT_ALIAS;
+use super::constants::INSERT_IMAGE_REFERENCE;
+use super::constants::INSERT_LINK;
+use super::constants::INSERT_NOTE;
+use super::constants::INSERT_TAG;
+use super::constants::SELECT_CONTENT_HASHES;
+use super::constants::VAULT_INDEX_SCHEMA;
+use super::phantom_links;
+use crate::constants::TAG_PREFIX;
+use crate::constants::TAGS_PROPERTY;
+use crate::constants::VAULT_INDEX_FILE;
+use crate::frontmatter::FrontMatter;
+use crate::markdown_file::MarkdownFile;
+use crate::support::TAG_REGEX;
+use crate::validated_config::ValidatedConfig;
+
+impl ObsidianRepository {
+    /// With `vault_index` on, writes the notes, aliases, tags, links and image references ok
+    /// found to a `SQLite` database in the output folder, replacing the previous run's. A note's
+    /// `changed` column says whether it differs from what the previous run read, so scripts can
+    /// pick up only the notes that moved.
+    pub(crate) fn write_vault_index(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if !validated_config.vault_index() {
+            return Ok(());
+        }
+
+        let state_folder = validated_config.state_folder();
+        fs::create_dir_all(&state_folder)?;
+        let mut connection = Connection::open(state_folder.join(VAULT_INDEX_FILE))?;
+        let previous_hashes = previous_content_hashes(&connection);
+        self.index_into(
+            &mut connection,
+            validated_config.obsidian_path(),
+            &previous_hashes,
+        )?;
+
+        Ok(())
+    }
+
+    /// The same index, held in memory and built from the notes as this run leaves them, for
+    /// saved reports to query before anything is written.
+    pub(crate) fn in_memory_vault_index(
+        &self,
+        obsidian_path: &Path,
+    ) -> rusqlite::Result<Connection> {
+        let mut connection = Connection::open_in_memory()?;
+        self.index_into(&mut connection, obsidian_path, &HashMap::new())?;
+        Ok(connection)
+    }
+
+    fn index_into(
+        &self,
+        connection: &mut Connection,
+        obsidian_path: &Path,
+        previous_hashes: &HashMap<String, String>,
+    ) -> rusqlite::Result<()> {
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(VAULT_INDEX_SCHEMA)?;
+        for markdown_file in &self.markdown_files {
+            let path = relative_path(obsidian_path, &markdown_file.path);
+            let changed = previous_hashes.get(&path) != Some(&markdown_file.content_hash);
+            index_note(&transaction, markdown_file, &path, changed)?;
+            index_links(&transaction, markdown_file, &path)?;
+        }
+        transaction.commit()
+    }
+}
+
+/// The content hash of each note in the existing index - empty when there is none yet.
+fn previous_content_hashes(connection: &Connection) -> HashMap<String, String> {
+    connection
+        .prepare(SELECT_CONTENT_HASHES)
+        .and_then(|mut statement| {
+            statement
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub(super) fn relative_path(obsidian_path: &Path, path: &Path) -> String {
+    path.strip_prefix(obsidian_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn file_time(time: DateTime<Utc>) -> String { time.to_rfc3339_opts(SecondsFormat::Secs, true) }
+
+fn index_note(
+    transaction: &Transaction,
+    markdown_file: &MarkdownFile,
+    path: &str,
+    changed: bool,
+) -> rusqlite::Result<()> {
+    let front_matter = markdown_file.front_matter.as_ref();
+    transaction.execute(
+        INSERT_NOTE,
+        params![
+            path,
+            markdown_file
+                .path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default(),
+            front_matter.and_then(FrontMatter::date_created),
+            front_matter.and_then(FrontMatter::date_modified),
+            file_time(markdown_file.created_date_validation.file_system),
+            file_time(markdown_file.modified_date_validation.file_system),
+            markdown_file.content_hash,
+            changed,
+        ],
+    )?;
+
+    let mut insert_alias = transaction.prepare_cached(INSERT_ALIAS)?;
+    for alias in front_matter
+        .and_then(FrontMatter::aliases)
+        .unwrap_or_default()
+    {
+        insert_alias.execute(params![path, alias])?;
+    }
+
+    let mut insert_tag = transaction.prepare_cached(INSERT_TAG)?;
+    for tag in note_tags(markdown_file) {
+        insert_tag.execute(params![path, tag])?;
+    }
+
+    Ok(())
+}
+
+/// Tags from the `tags` property and from `#tag` in the note body, without the `#`.
+pub(super) fn note_tags(markdown_file: &MarkdownFile) -> BTreeSet<String> {
+    let mut tags: BTreeSet<String> = markdown_file
+        .front_matter
+        .as_ref()
+        .map(|front_matter| front_matter.property_strings(TAGS_PROPERTY))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| tag.trim().trim_start_matches(TAG_PREFIX).to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.extend(
+        TAG_REGEX
+            .captures_iter(&markdown_file.content)
+            .filter_map(|captures| captures.get(1))
+            .map(|tag| tag.as_str().trim_start_matches(TAG_PREFIX).to_string()),
+    );
+    tags
+}
+
+fn index_links(
+    transaction: &Transaction,
+    markdown_file: &MarkdownFile,
+    path: &str,
+) -> rusqlite::Result<()> {
+    let mut insert_link = transaction.prepare_cached(INSERT_LINK)?;
+    for frontmatter_wikilink in &markdown_file.frontmatter_wikilinks {
+        insert_link.execute(params![
+            path,
+            FRONTMATTER_LINE_NUMBER,
+            frontmatter_wikilink.wikilink.target,
+            phantom_links::target_note_stem(&frontmatter_wikilink.wikilink.target),
+            frontmatter_wikilink.wikilink.display_text,
+        ])?;
+    }
+
+    let mut result = Ok(());
+    markdown_file.for_each_content_wikilink(|line_number, _, spanned_wikilink| {
+        if result.is_ok() {
+            result = insert_link
+                .execute(params![
+                    path,
+                    line_number,
+                    spanned_wikilink.wikilink.target,
+                    phantom_links::target_note_stem(&spanned_wikilink.wikilink.target),
+                    spanned_wikilink.wikilink.display_text,
+                ])
+                .map(|_| ());
+        }
+    });
+    result?;
+
+    let mut insert_image_reference = transaction.prepare_cached(INSERT_IMAGE_REFERENCE)?;
+    for image_link in markdown_file.image_links.iter() {
+        insert_image_reference.execute(params![
+            path,
+            image_link.line_number,
+            image_link.filename
+        ])?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use rusqlite::Connection;
+    use tempfile::TempDir;
+
+    use crate::constants::VAULT_INDEX_FILE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    fn count(connection: &Connection, sql: &str) -> i64 {
+        connection.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_vault_index_records_notes_links_and_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Tokyo".to_string())
+            .with_aliases(vec!["Edo".to_string()])
+            .with_custom_frontmatter("tags:\n  - city".to_string())
+            .create(&temp_dir, "Tokyo.md");
+        TestFileBuilder::new()
+            .with_title("Trip".to_string())
+            .with_content("We flew to [[Tokyo]] #travel\n![[map.png]]".to_string())
+            .create(&temp_dir, "Trip.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.vault_index(true);
+            })
+            .unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        obsidian_repository
+            .write_vault_index(&validated_config)
+            .unwrap();
+
+        let index_path = validated_config.state_folder().join(VAULT_INDEX_FILE);
+        let connection = Connection::open(&index_path).unwrap();
+        assert_eq!(count(&connection, "SELECT COUNT(*) FROM notes"), 2);
+        assert_eq!(
+            count(&connection, "SELECT COUNT(*) FROM notes WHERE changed"),
+            2
+        );
+        assert_eq!(
+            count(
+                &connection,
+                "SELECT COUNT(*) FROM links WHERE path = 'Trip.md' AND target = 'Tokyo'"
+            ),
+            1
+        );
+        assert_eq!(
+            count(
+                &connection,
+                "SELECT COUNT(*) FROM aliases WHERE alias = 'Edo'"
+            ),
+            1
+        );
+        assert_eq!(
+            count(
+                &connection,
+                "SELECT COUNT(*) FROM tags WHERE tag IN ('city', 'travel')"
+            ),
+            2
+        );
+        assert_eq!(
+            count(&connection, "SELECT COUNT(*) FROM image_references"),
+            1
+        );
+        drop(connection);
+
+        obsidian_repository
+            .write_vault_index(&validated_config)
+            .unwrap();
+        let connection = Connection::open(&index_path).unwrap();
+        assert_eq!(
+            count(&connection, "SELECT COUNT(*) FROM notes WHERE changed"),
+            0
+        );
+    }
+}
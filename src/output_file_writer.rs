@@ -1,11 +1,18 @@
+use std::ffi::OsStr;
+use std::fs;
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io;
 use std::io::Write;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
+use crate::constants::FIRST;
+use crate::constants::MARKDOWN_SUFFIX;
 use crate::constants::MARKDOWN_TABLE_ALIGNMENT_CENTER;
 use crate::constants::MARKDOWN_TABLE_ALIGNMENT_LEFT;
 use crate::constants::MARKDOWN_TABLE_ALIGNMENT_RIGHT;
@@ -15,11 +22,18 @@ use crate::constants::MARKDOWN_TABLE_ROW_PLACEHOLDER_REPLACEMENT_LIMIT;
 use crate::constants::MARKDOWN_TABLE_ROW_TEMPLATE;
 use crate::constants::MARKDOWN_TABLE_SEPARATOR;
 use crate::constants::MARKDOWN_TABLE_TRAILING_SEPARATOR;
+use crate::constants::NEWLINE;
+use crate::constants::OF;
 use crate::constants::OUTPUT_FILE_LOCK_POISONED;
 use crate::constants::OUTPUT_MARKDOWN_FILE;
+use crate::constants::OVERFLOW_MARKDOWN_FILE_PREFIX;
+use crate::constants::ROWS_IN_OVERFLOW_FILE;
+use crate::constants::SHOWING_THE;
 use crate::constants::SPACE;
 use crate::constants::YAML_CLOSING_DELIMITER;
 use crate::constants::YAML_OPENING_DELIMITER;
+use crate::support;
+use crate::wikilink::ToWikilink;
 
 #[derive(Clone, Copy)]
 pub(crate) enum ColumnAlignment {
@@ -35,12 +49,39 @@ enum MarkdownPrefix<'a> {
     NeedsSpace(&'a str),
 }
 
+/// Where a writer's output goes - the report file, or a section held in memory until
+/// `append` adds it to the report.
+enum ReportSink {
+    File(File),
+    Buffer(Vec<ReportPiece>),
+}
+
+/// A buffered section keeps its overflow tables aside, so they are numbered in report order
+/// when the section is appended rather than in the order sections finish.
+enum ReportPiece {
+    Text(String),
+    Overflow {
+        table: String,
+        shown: usize,
+        total: usize,
+    },
+}
+
+/// Writes the report to a hidden temp file; `finish` renames it into place so a failed run
+/// never leaves a half-written report behind. `buffer` gives a writer for one section, so
+/// sections can be written in parallel and appended in order.
 pub(crate) struct OutputFileWriter {
-    file: Mutex<File>,
+    sink:            Mutex<ReportSink>,
+    file_path:       PathBuf,
+    /// The vault deep links open notes in - `None` when `report_deep_links` is off.
+    deep_link_vault: Option<String>,
+    /// Tables longer than this are cut short and written in full to an overflow file.
+    row_limit:       Option<usize>,
+    overflow_files:  AtomicUsize,
 }
 
 impl OutputFileWriter {
-    fn markdown_table_row(cells: &str) -> String {
+    pub(crate) fn markdown_table_row(cells: &str) -> String {
         MARKDOWN_TABLE_ROW_TEMPLATE.replacen(
             MARKDOWN_TABLE_PLACEHOLDER,
             cells,
@@ -48,26 +89,101 @@ impl OutputFileWriter {
         )
     }
 
-    fn lock_file(&self) -> io::Result<MutexGuard<'_, File>> {
-        self.file
+    fn lock_sink(&self) -> io::Result<MutexGuard<'_, ReportSink>> {
+        self.sink
             .lock()
             .map_err(|error| io::Error::other(format!("{OUTPUT_FILE_LOCK_POISONED}: {error}")))
     }
 
+    fn write_text(&self, text: String) -> io::Result<()> {
+        match &mut *self.lock_sink()? {
+            ReportSink::File(file) => {
+                file.write_all(text.as_bytes())?;
+                file.flush()
+            },
+            ReportSink::Buffer(pieces) => {
+                pieces.push(ReportPiece::Text(text));
+                Ok(())
+            },
+        }
+    }
+
     pub(crate) fn new(obsidian_path: &Path) -> io::Result<Self> {
         let file_path = obsidian_path.join(OUTPUT_MARKDOWN_FILE);
+        remove_overflow_files(obsidian_path)?;
 
         let file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(file_path)?;
+            .open(support::atomic_temp_path(&file_path))?;
 
         Ok(Self {
-            file: Mutex::new(file),
+            sink: Mutex::new(ReportSink::File(file)),
+            file_path,
+            deep_link_vault: None,
+            row_limit: None,
+            overflow_files: AtomicUsize::new(0),
         })
     }
 
+    pub(crate) fn with_row_limit(self, row_limit: Option<usize>) -> Self {
+        Self { row_limit, ..self }
+    }
+
+    pub(crate) fn with_deep_link_vault(self, deep_link_vault: Option<String>) -> Self {
+        Self {
+            deep_link_vault,
+            ..self
+        }
+    }
+
+    pub(crate) fn deep_link_vault(&self) -> Option<&str> { self.deep_link_vault.as_deref() }
+
+    /// A writer with the same settings that holds what is written to it until `append`.
+    pub(crate) fn buffer(&self) -> Self {
+        Self {
+            sink:            Mutex::new(ReportSink::Buffer(Vec::new())),
+            file_path:       self.file_path.clone(),
+            deep_link_vault: self.deep_link_vault.clone(),
+            row_limit:       self.row_limit,
+            overflow_files:  AtomicUsize::new(0),
+        }
+    }
+
+    /// Writes what `section` holds, writing its overflow files as they come.
+    pub(crate) fn append(&self, section: Self) -> io::Result<()> {
+        let sink = section
+            .sink
+            .into_inner()
+            .map_err(|error| io::Error::other(format!("{OUTPUT_FILE_LOCK_POISONED}: {error}")))?;
+        let ReportSink::Buffer(pieces) = sink else {
+            return Ok(());
+        };
+        for piece in pieces {
+            match piece {
+                ReportPiece::Text(text) => self.write_text(text)?,
+                ReportPiece::Overflow {
+                    table,
+                    shown,
+                    total,
+                } => self.write_overflow(table, shown, total)?,
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn finish(self) -> io::Result<()> {
+        let sink = self
+            .sink
+            .into_inner()
+            .map_err(|error| io::Error::other(format!("{OUTPUT_FILE_LOCK_POISONED}: {error}")))?;
+        if let ReportSink::File(file) = sink {
+            file.sync_all()?;
+        }
+        fs::rename(support::atomic_temp_path(&self.file_path), &self.file_path)
+    }
+
     pub(crate) fn write_markdown_table(
         &self,
         headers: &[&str],
@@ -97,50 +213,104 @@ impl OutputFileWriter {
             },
         );
 
-        let mut file = self.lock_file()?;
+        let header_row = Self::markdown_table_row(&headers.join(MARKDOWN_TABLE_CELL_SEPARATOR));
+        let (shown_rows, overflow_table) = match self.row_limit {
+            Some(limit) if rows.len() > limit => (
+                &rows[..limit],
+                Some(Self::overflow_table(&header_row, &separator, rows)),
+            ),
+            _ => (rows, None),
+        };
 
         // Markdown tables require a blank line before the header row.
-        writeln!(
-            file,
-            "\n{}",
-            Self::markdown_table_row(&headers.join(MARKDOWN_TABLE_CELL_SEPARATOR))
-        )?;
-        writeln!(file, "{separator}")?;
+        let mut table = format!("\n{header_row}\n{separator}\n");
+        for row in shown_rows {
+            table.push_str(&Self::markdown_table_row(
+                &row.join(MARKDOWN_TABLE_CELL_SEPARATOR),
+            ));
+            table.push(NEWLINE);
+        }
+        self.write_text(table)?;
 
-        for row in rows {
-            writeln!(
-                file,
-                "{}",
-                Self::markdown_table_row(&row.join(MARKDOWN_TABLE_CELL_SEPARATOR))
-            )?;
+        if let Some(overflow_table) = overflow_table {
+            let mut sink = self.lock_sink()?;
+            if let ReportSink::Buffer(pieces) = &mut *sink {
+                pieces.push(ReportPiece::Overflow {
+                    table: overflow_table,
+                    shown: shown_rows.len(),
+                    total: rows.len(),
+                });
+            } else {
+                drop(sink);
+                self.write_overflow(overflow_table, shown_rows.len(), rows.len())?;
+            }
         }
 
         // `MARKDOWN_TABLE_TRAILING_SEPARATOR` terminates the rendered table.
-        writeln!(file, "{MARKDOWN_TABLE_TRAILING_SEPARATOR}")?;
+        self.write_text(format!("{MARKDOWN_TABLE_TRAILING_SEPARATOR}\n"))
+    }
 
-        file.flush()?;
-        drop(file);
-        Ok(())
+    fn overflow_table(header_row: &str, separator: &str, rows: &[Vec<String>]) -> String {
+        let mut table = format!("{header_row}\n{separator}\n");
+        for row in rows {
+            table.push_str(&Self::markdown_table_row(
+                &row.join(MARKDOWN_TABLE_CELL_SEPARATOR),
+            ));
+            table.push(NEWLINE);
+        }
+        table
+    }
+
+    /// Writes the whole table beside the report as the next numbered overflow file, and a note
+    /// linking it after the rows shown.
+    fn write_overflow(&self, table: String, shown: usize, total: usize) -> io::Result<()> {
+        let number = self.overflow_files.fetch_add(1, Ordering::Relaxed) + 1;
+        let overflow_name = format!("{OVERFLOW_MARKDOWN_FILE_PREFIX}{number}");
+        let overflow_path = self
+            .file_path
+            .with_file_name(format!("{overflow_name}{MARKDOWN_SUFFIX}"));
+        fs::write(&overflow_path, table)?;
+        self.write_text(format!(
+            "\n{SHOWING_THE} {FIRST} {shown} {OF} {total} {ROWS_IN_OVERFLOW_FILE} {}\n",
+            overflow_name.to_wikilink()
+        ))
     }
 
     pub(crate) fn write_properties(&self, properties: &str) -> io::Result<()> {
-        let mut file = self.lock_file()?;
-        write!(file, "{YAML_OPENING_DELIMITER}")?;
-        writeln!(file, "{properties}")?;
-        write!(file, "{YAML_CLOSING_DELIMITER}")?;
-        file.flush()
+        self.write_text(format!(
+            "{YAML_OPENING_DELIMITER}{properties}\n{YAML_CLOSING_DELIMITER}"
+        ))
     }
 
     pub(crate) fn writeln(&self, markdown_prefix: &str, message: &str) -> io::Result<()> {
         let prefix = MarkdownPrefix::from(markdown_prefix).into_string();
 
-        let file_message = format!("{prefix}{message}\n");
-        let mut file = self.lock_file()?;
-        file.write_all(file_message.as_bytes())?;
-        file.flush()
+        self.write_text(format!("{prefix}{message}\n"))
     }
 }
 
+/// Overflow files belong to the run that wrote them - the report only links the new ones.
+fn remove_overflow_files(output_folder: &Path) -> io::Result<()> {
+    let Ok(entries) = fs::read_dir(output_folder) else {
+        return Ok(());
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_file()
+            && path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .is_some_and(|name| {
+                    name.starts_with(OVERFLOW_MARKDOWN_FILE_PREFIX)
+                        && name.ends_with(MARKDOWN_SUFFIX)
+                })
+        {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
 impl<'a> From<&'a str> for MarkdownPrefix<'a> {
     fn from(markdown_prefix: &'a str) -> Self {
         match (markdown_prefix.is_empty(), markdown_prefix.ends_with(SPACE)) {
@@ -160,3 +330,84 @@ impl MarkdownPrefix<'_> {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::OutputFileWriter;
+    use crate::constants::OUTPUT_MARKDOWN_FILE;
+
+    #[test]
+    fn test_row_limit_writes_overflow_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let stale = temp_dir.path().join("obsidian knife overflow 7.md");
+        fs::write(&stale, "old").unwrap();
+
+        let output_file_writer = OutputFileWriter::new(temp_dir.path())
+            .unwrap()
+            .with_row_limit(Some(2));
+        assert!(!stale.exists());
+
+        let rows: Vec<Vec<String>> = (1..=3).map(|row| vec![row.to_string()]).collect();
+        output_file_writer
+            .write_markdown_table(&["row"], &rows, None)
+            .unwrap();
+        output_file_writer
+            .write_markdown_table(&["row"], &rows[..2], None)
+            .unwrap();
+        output_file_writer.finish().unwrap();
+
+        let report = fs::read_to_string(temp_dir.path().join(OUTPUT_MARKDOWN_FILE)).unwrap();
+        assert!(report.contains("| 2 |"));
+        assert!(!report.contains("| 3 |"));
+        assert!(report.contains(
+            "showing the first 2 of 3 rows - every row is in [[obsidian knife overflow 1]]"
+        ));
+        assert_eq!(report.matches("overflow").count(), 1);
+
+        let overflow =
+            fs::read_to_string(temp_dir.path().join("obsidian knife overflow 1.md")).unwrap();
+        assert!(overflow.contains("| 3 |"));
+    }
+
+    #[test]
+    fn test_buffered_sections_append_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_file_writer = OutputFileWriter::new(temp_dir.path())
+            .unwrap()
+            .with_row_limit(Some(1));
+        let first = output_file_writer.buffer();
+        let second = output_file_writer.buffer();
+
+        // The second section finishes first, as it may when sections are written in parallel.
+        let rows: Vec<Vec<String>> = (1..=2).map(|row| vec![row.to_string()]).collect();
+        second.writeln("", "second").unwrap();
+        second
+            .write_markdown_table(&["second"], &rows, None)
+            .unwrap();
+        first.writeln("", "first").unwrap();
+        first.write_markdown_table(&["first"], &rows, None).unwrap();
+
+        output_file_writer.append(first).unwrap();
+        output_file_writer.append(second).unwrap();
+        output_file_writer.finish().unwrap();
+
+        let report = fs::read_to_string(temp_dir.path().join(OUTPUT_MARKDOWN_FILE)).unwrap();
+        let first_at = report.find("[[obsidian knife overflow 1]]").unwrap();
+        let second_at = report.find("[[obsidian knife overflow 2]]").unwrap();
+        assert!(report.find("first").unwrap() < first_at);
+        assert!(first_at < report.find("second").unwrap());
+        assert!(report.find("second").unwrap() < second_at);
+
+        let overflow =
+            fs::read_to_string(temp_dir.path().join("obsidian knife overflow 1.md")).unwrap();
+        assert!(overflow.contains("| first |"));
+    }
+}
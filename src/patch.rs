@@ -0,0 +1,609 @@
+use std::ffi::OsString;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Utc;
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::constants::APPLY_USAGE;
+use crate::constants::ATOMIC_WRITE_TEMP_PREFIX;
+use crate::constants::EXCLUDE_FLAG;
+use crate::constants::ONLY_FLAG;
+use crate::constants::PATCH_ASIDE_SUFFIX;
+use crate::constants::PATCH_CATEGORIES;
+use crate::constants::PATCH_CATEGORY_SEPARATOR;
+use crate::run_lock::RunLock;
+use crate::run_lock::RunLockError;
+use crate::sha256_cache;
+use crate::support;
+
+#[derive(Debug, Error)]
+pub(crate) enum PatchError {
+    #[error("{APPLY_USAGE}")]
+    Usage,
+    #[error("not applied - changed since the patch was made, run ok again for a new patch: {}",
+        .0.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "))]
+    Stale(Vec<PathBuf>),
+    #[error("not applied - these notes also have changes the filter leaves out, select all of their categories: {}",
+        .0.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "))]
+    Partial(Vec<PathBuf>),
+    #[error("not applied - these changes need others the filter leaves out: {}",
+        .0.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "))]
+    Dependent(Vec<PathBuf>),
+    #[error("not applied - {}: {error}, the changes already made were undone", .path.display())]
+    RolledBack { path: PathBuf, error: io::Error },
+    #[error("applied partly - {}: {error}, and these changes couldn't be undone: {}", .path.display(),
+        .not_undone.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "))]
+    PartlyApplied {
+        path:       PathBuf,
+        error:      io::Error,
+        not_undone: Vec<PathBuf>,
+    },
+    #[error("invalid date in patch: {0}")]
+    InvalidDate(String),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    RunLock(#[from] RunLockError),
+}
+
+/// One change to the vault, with the categories of change it is for - a rewrite carries one
+/// category for each kind of change made to the note.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct PatchOperation {
+    pub path:       PathBuf,
+    pub categories: Vec<String>,
+    /// The categories only there for changes that bring the note's dates in line with its file -
+    /// they go along with the rest of the rewrite, so they neither select nor split it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub incidental: Vec<String>,
+    /// The paths of the other operations this one can't be made without - the notes whose
+    /// image links move off a deleted image, or the note a conflict copy was merged into.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires:   Vec<PathBuf>,
+    #[serde(flatten)]
+    pub change:     PatchChange,
+}
+
+/// Each change to an existing file carries the SHA-256 of the file it was worked out from, so
+/// it is only made to that exact file.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub(crate) enum PatchChange {
+    /// A note that doesn't exist yet.
+    Create {
+        content: String,
+    },
+    Delete {
+        hash: String,
+    },
+    /// Replaces a note, then sets its file dates to the RFC 3339 dates given.
+    Write {
+        hash:          String,
+        content:       String,
+        date_created:  Option<String>,
+        date_modified: String,
+    },
+}
+
+impl PatchOperation {
+    /// The categories `--only` and `--exclude` pick the operation by - all of them when every
+    /// one is incidental, so a note whose dates are the only change is still known by them.
+    fn deciding_categories(&self) -> Vec<&String> {
+        let standalone: Vec<&String> = self
+            .categories
+            .iter()
+            .filter(|category| !self.incidental.contains(category))
+            .collect();
+        if standalone.is_empty() {
+            self.categories.iter().collect()
+        } else {
+            standalone
+        }
+    }
+
+    /// Whether the file is still as it was when the patch was made.
+    fn is_current(&self) -> bool {
+        match &self.change {
+            PatchChange::Create { .. } => !self.path.exists(),
+            PatchChange::Delete { hash } | PatchChange::Write { hash, .. } => fs::read(&self.path)
+                .is_ok_and(|contents| sha256_cache::hash_contents(&contents) == *hash),
+        }
+    }
+
+    /// The file dates a write sets, parsed before anything is touched.
+    fn file_dates(&self) -> Result<Option<FileDates>, PatchError> {
+        let PatchChange::Write {
+            date_created,
+            date_modified,
+            ..
+        } = &self.change
+        else {
+            return Ok(None);
+        };
+        Ok(Some((
+            date_created.as_deref().map(parse_date).transpose()?,
+            parse_date(date_modified)?,
+        )))
+    }
+
+    /// Writes the new contents of a create or a write to a temp file beside the note, making
+    /// the folders a new note goes in - recorded in `created_folders`, to remove on undo. A
+    /// write's temp file starts as a copy of the note, so it keeps the note's permissions.
+    fn stage(&self, created_folders: &mut Vec<PathBuf>) -> io::Result<Option<PathBuf>> {
+        let path = &self.path;
+        let content = match &self.change {
+            PatchChange::Delete { .. } => return Ok(None),
+            PatchChange::Create { content } => {
+                if let Some(parent) = path.parent() {
+                    create_folders(parent, created_folders)?;
+                }
+                content
+            },
+            PatchChange::Write { content, .. } => content,
+        };
+
+        let temp_path = support::atomic_temp_path(path);
+        if matches!(self.change, PatchChange::Write { .. }) {
+            fs::copy(path, &temp_path)?;
+        }
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        Ok(Some(temp_path))
+    }
+
+    /// Moves the staged file into place, or the deleted file aside, recording each step in
+    /// `applied` as soon as it is taken so a failure after it can undo it.
+    fn commit(
+        &self,
+        staged: Option<&Path>,
+        file_dates: Option<FileDates>,
+        applied: &mut Vec<Applied>,
+    ) -> io::Result<()> {
+        let path = &self.path;
+        match (&self.change, staged) {
+            (PatchChange::Delete { .. }, _) => {
+                let aside = aside_path(path);
+                fs::rename(path, &aside)?;
+                applied.push(Applied::MovedAside {
+                    path: path.clone(),
+                    aside,
+                });
+            },
+            (PatchChange::Create { .. }, Some(staged)) => {
+                if path.exists() {
+                    return Err(io::Error::from(ErrorKind::AlreadyExists));
+                }
+                fs::rename(staged, path)?;
+                applied.push(Applied::Created(path.clone()));
+            },
+            (PatchChange::Write { .. }, Some(staged)) => {
+                let aside = aside_path(path);
+                // A rename would split a hard linked note from its other links, so it is
+                // copied aside and written in place instead.
+                if fs::metadata(path).is_ok_and(|metadata| support::has_other_links(&metadata)) {
+                    fs::copy(path, &aside)?;
+                    applied.push(Applied::CopiedAside {
+                        path: path.clone(),
+                        aside,
+                    });
+                    fs::copy(staged, path)?;
+                    fs::remove_file(staged)?;
+                } else {
+                    fs::rename(path, &aside)?;
+                    applied.push(Applied::MovedAside {
+                        path: path.clone(),
+                        aside,
+                    });
+                    fs::rename(staged, path)?;
+                }
+                if let Some((date_created, date_modified)) = file_dates {
+                    support::set_file_dates(path, date_created, date_modified)
+                        .map_err(|error| io::Error::other(error.to_string()))?;
+                }
+            },
+            (PatchChange::Create { .. } | PatchChange::Write { .. }, None) => {
+                return Err(io::Error::from(ErrorKind::NotFound));
+            },
+        }
+        Ok(())
+    }
+}
+
+/// The created and modified dates a write sets on its note.
+type FileDates = (Option<DateTime<Utc>>, DateTime<Utc>);
+
+/// One step `Patch::apply` took, kept until every operation is made so a failure can undo it.
+enum Applied {
+    /// A new note - removed on undo.
+    Created(PathBuf),
+    /// A replaced or deleted file, renamed to `aside` - renamed back on undo.
+    MovedAside { path: PathBuf, aside: PathBuf },
+    /// A hard linked note written in place, its old contents copied to `aside` - copied back
+    /// on undo.
+    CopiedAside { path: PathBuf, aside: PathBuf },
+}
+
+impl Applied {
+    fn undo(&self) -> io::Result<()> {
+        match self {
+            Self::Created(path) => fs::remove_file(path),
+            Self::MovedAside { path, aside } => fs::rename(aside, path),
+            Self::CopiedAside { path, aside } => {
+                fs::copy(aside, path)?;
+                fs::remove_file(aside)
+            },
+        }
+    }
+
+    /// Drops the copy kept for undo, once every operation is made.
+    fn finish(&self) -> io::Result<()> {
+        match self {
+            Self::Created(_) => Ok(()),
+            Self::MovedAside { aside, .. } | Self::CopiedAside { aside, .. } => {
+                fs::remove_file(aside)
+            },
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            Self::Created(path)
+            | Self::MovedAside { path, .. }
+            | Self::CopiedAside { path, .. } => path,
+        }
+    }
+}
+
+/// Hidden sibling of `path` a file is moved to while a patch is applied:
+/// `notes/Recipe.md` goes to `notes/.Recipe.md.ok-aside`.
+fn aside_path(path: &Path) -> PathBuf {
+    let mut file_name = OsString::from(ATOMIC_WRITE_TEMP_PREFIX);
+    file_name.push(path.file_name().unwrap_or_default());
+    file_name.push(PATCH_ASIDE_SUFFIX);
+    path.with_file_name(file_name)
+}
+
+/// Creates `folder` and the folders above it that don't exist yet, recording each one made in
+/// `created_folders` from the top down.
+fn create_folders(folder: &Path, created_folders: &mut Vec<PathBuf>) -> io::Result<()> {
+    let missing: Vec<&Path> = folder
+        .ancestors()
+        .take_while(|ancestor| !ancestor.as_os_str().is_empty() && !ancestor.exists())
+        .collect();
+    for missing_folder in missing.into_iter().rev() {
+        fs::create_dir(missing_folder)?;
+        created_folders.push(missing_folder.to_path_buf());
+    }
+    Ok(())
+}
+
+/// Undoes `applied` last step first and removes the temp files and folders staging made, after
+/// the operation on `path` failed with `error`.
+fn roll_back(
+    path: &Path,
+    error: io::Error,
+    staged: &[Option<PathBuf>],
+    applied: &[Applied],
+    created_folders: &[PathBuf],
+) -> PatchError {
+    // Best effort - a temp file already moved into place is gone.
+    for temp_path in staged.iter().flatten() {
+        let _ = fs::remove_file(temp_path);
+    }
+    let not_undone: Vec<PathBuf> = applied
+        .iter()
+        .rev()
+        .filter(|step| step.undo().is_err())
+        .map(|step| step.path().to_path_buf())
+        .collect();
+    // A folder something else was put in meanwhile isn't empty, and stays.
+    for folder in created_folders.iter().rev() {
+        let _ = fs::remove_dir(folder);
+    }
+
+    if not_undone.is_empty() {
+        PatchError::RolledBack {
+            path: path.to_path_buf(),
+            error,
+        }
+    } else {
+        PatchError::PartlyApplied {
+            path: path.to_path_buf(),
+            error,
+            not_undone,
+        }
+    }
+}
+
+/// One `--only` or `--exclude` value - a category from `PATCH_CATEGORIES`, or the part before
+/// its `:` to name the whole group, otherwise a path glob relative to the vault.
+#[derive(Debug)]
+enum PatchSelector {
+    Category(String),
+    Path(Regex),
+}
+
+impl PatchSelector {
+    fn parse(value: &str) -> Self {
+        let group = format!("{value}{PATCH_CATEGORY_SEPARATOR}");
+        if PATCH_CATEGORIES
+            .iter()
+            .any(|category| *category == value || category.starts_with(&group))
+        {
+            return Self::Category(value.to_string());
+        }
+        Self::Path(glob_regex(value))
+    }
+
+    fn matches_path(&self, relative_path: &str) -> bool {
+        matches!(self, Self::Path(regex) if regex.is_match(relative_path))
+    }
+
+    fn matches_categories(&self, operation: &PatchOperation) -> bool {
+        operation
+            .deciding_categories()
+            .iter()
+            .any(|category| self.selects_category(category))
+    }
+
+    fn selects_category(&self, category: &str) -> bool {
+        match self {
+            Self::Category(selected) => {
+                let group = format!("{selected}{PATCH_CATEGORY_SEPARATOR}");
+                category == selected || category.starts_with(&group)
+            },
+            Self::Path(_) => false,
+        }
+    }
+
+    const fn is_category(&self) -> bool { matches!(self, Self::Category(_)) }
+}
+
+/// Which operations of a patch to apply. An operation is kept when it matches one of the
+/// `--only` categories and one of the `--only` paths - either kind counts as matched when none
+/// were given - and no `--exclude` value.
+#[derive(Debug, Default)]
+pub(crate) struct PatchFilter {
+    only:    Vec<PatchSelector>,
+    exclude: Vec<PatchSelector>,
+}
+
+impl PatchFilter {
+    fn selects_path(&self, relative_path: &str) -> bool {
+        let mut only_paths = self
+            .only
+            .iter()
+            .filter(|selector| !selector.is_category())
+            .peekable();
+        (only_paths.peek().is_none()
+            || only_paths.any(|selector| selector.matches_path(relative_path)))
+            && !self
+                .exclude
+                .iter()
+                .any(|selector| selector.matches_path(relative_path))
+    }
+
+    fn selects_categories(&self, operation: &PatchOperation) -> bool {
+        let mut only_categories = self
+            .only
+            .iter()
+            .filter(|selector| selector.is_category())
+            .peekable();
+        (only_categories.peek().is_none()
+            || only_categories.any(|selector| selector.matches_categories(operation)))
+            && !self
+                .exclude
+                .iter()
+                .any(|selector| selector.matches_categories(operation))
+    }
+
+    /// Whether the `--only` or the `--exclude` categories name some of the operation's
+    /// categories but not all - a rewritten note can only be applied whole.
+    fn splits(&self, operation: &PatchOperation) -> bool {
+        let categories = operation.deciding_categories();
+        [&self.only, &self.exclude].into_iter().any(|selectors| {
+            let selected = categories
+                .iter()
+                .filter(|category| {
+                    selectors
+                        .iter()
+                        .any(|selector| selector.selects_category(category))
+                })
+                .count();
+            selected > 0 && selected < categories.len()
+        })
+    }
+}
+
+/// The changes a dry run would have made, written for review and applied later with
+/// `ok apply <patch_file>`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct Patch {
+    /// The vault the paths are in, for matching `--only` and `--exclude` paths.
+    pub obsidian_path: PathBuf,
+    /// Where the run that made the patch keeps its lock, so applying it takes the same lock.
+    pub output_folder: PathBuf,
+    pub operations:    Vec<PatchOperation>,
+}
+
+impl Patch {
+    pub(crate) fn read(path: &Path) -> Result<Self, PatchError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub(crate) fn write(&self, path: &Path) -> Result<(), PatchError> {
+        support::write_atomically(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Makes every operation in order, or none. If any file changed since the patch was made,
+    /// the patch is refused before anything is touched. Otherwise every new note and rewrite is
+    /// written to a temp file first, then the operations are made one by one - the files they
+    /// replace or delete moved aside until the last is made - and a failure part way undoes the
+    /// ones already made.
+    pub(crate) fn apply(&self) -> Result<usize, PatchError> {
+        let _run_lock = RunLock::acquire(&self.output_folder)?;
+
+        let stale: Vec<PathBuf> = self
+            .operations
+            .iter()
+            .filter(|operation| !operation.is_current())
+            .map(|operation| operation.path.clone())
+            .collect();
+        if !stale.is_empty() {
+            return Err(PatchError::Stale(stale));
+        }
+
+        let file_dates = self
+            .operations
+            .iter()
+            .map(PatchOperation::file_dates)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut created_folders = Vec::new();
+        let mut staged = Vec::with_capacity(self.operations.len());
+        for operation in &self.operations {
+            match operation.stage(&mut created_folders) {
+                Ok(temp_path) => staged.push(temp_path),
+                Err(error) => {
+                    return Err(roll_back(
+                        &operation.path,
+                        error,
+                        &staged,
+                        &[],
+                        &created_folders,
+                    ));
+                },
+            }
+        }
+
+        let mut applied = Vec::new();
+        for ((operation, temp_path), file_dates) in
+            self.operations.iter().zip(&staged).zip(file_dates)
+        {
+            if let Err(error) = operation.commit(temp_path.as_deref(), file_dates, &mut applied) {
+                return Err(roll_back(
+                    &operation.path,
+                    error,
+                    &staged,
+                    &applied,
+                    &created_folders,
+                ));
+            }
+        }
+
+        // Every change is made - a copy left aside is only untidy.
+        for step in &applied {
+            let _ = step.finish();
+        }
+        Ok(self.operations.len())
+    }
+
+    /// The patch with only the operations the filter keeps. Refused when the filter would take
+    /// part of a rewritten note, or keep a change without one it needs - deleting a duplicate
+    /// image without moving the links to it.
+    pub(crate) fn filtered(&self, filter: &PatchFilter) -> Result<Self, PatchError> {
+        let mut partial = Vec::new();
+        let mut operations: Vec<PatchOperation> = Vec::new();
+        for operation in &self.operations {
+            let relative_path = operation
+                .path
+                .strip_prefix(&self.obsidian_path)
+                .unwrap_or(&operation.path)
+                .to_string_lossy();
+            if !filter.selects_path(&relative_path) {
+                continue;
+            }
+            if filter.splits(operation) {
+                partial.push(operation.path.clone());
+            } else if filter.selects_categories(operation) {
+                operations.push(operation.clone());
+            }
+        }
+        if !partial.is_empty() {
+            return Err(PatchError::Partial(partial));
+        }
+
+        let dependent: Vec<PathBuf> = operations
+            .iter()
+            .filter(|operation| {
+                operation.requires.iter().any(|required| {
+                    self.operations.iter().any(|other| other.path == *required)
+                        && !operations.iter().any(|kept| kept.path == *required)
+                })
+            })
+            .map(|operation| operation.path.clone())
+            .collect();
+        if !dependent.is_empty() {
+            return Err(PatchError::Dependent(dependent));
+        }
+
+        Ok(Self {
+            operations,
+            ..self.clone()
+        })
+    }
+}
+
+pub(crate) fn from_args(args: &[String]) -> Result<(PathBuf, PatchFilter), PatchError> {
+    let [patch_file, options @ ..] = args else {
+        return Err(PatchError::Usage);
+    };
+
+    let mut filter = PatchFilter::default();
+    for option in options.chunks(2) {
+        match option {
+            [flag, value] if flag == ONLY_FLAG => filter.only.push(PatchSelector::parse(value)),
+            [flag, value] if flag == EXCLUDE_FLAG => {
+                filter.exclude.push(PatchSelector::parse(value));
+            },
+            _ => return Err(PatchError::Usage),
+        }
+    }
+    Ok((PathBuf::from(patch_file), filter))
+}
+
+/// `**` matches across folders, `*` and `?` within one, and a glob without either also
+/// matches everything under it, so `journal` selects the whole folder.
+fn glob_regex(glob: &str) -> Regex {
+    let mut pattern = String::from("^");
+    let mut characters = glob.trim_matches('/').chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '*' if characters.peek() == Some(&'*') => {
+                characters.next();
+                pattern.push_str(".*");
+            },
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(&character.to_string())),
+        }
+    }
+    pattern.push_str("(/.*)?$");
+    support::compile_regex(&pattern)
+}
+
+pub(crate) fn format_date(date: DateTime<Utc>) -> String { date.to_rfc3339() }
+
+fn parse_date(date: &str) -> Result<DateTime<Utc>, PatchError> {
+    DateTime::parse_from_rfc3339(date)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|_| PatchError::InvalidDate(date.to_string()))
+}
@@ -1,13 +1,24 @@
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum Phrase {
+    Acronym(usize),
+    Admonition(usize),
+    Alias(usize),
+    Character(usize),
+    ConflictCopy(usize),
+    EncryptedBlock(usize),
     File(usize),
     Has(usize),
     Image(usize),
     Issue(usize),
     Is(usize),
+    ListItem(usize),
     Match(usize),
+    Pair(usize),
+    Property(usize),
     Reason(usize),
     Reference(usize),
+    Replacement(usize),
+    Section(usize),
     Target(usize),
     Time(usize),
     Wikilink(usize),
@@ -17,6 +28,18 @@ pub(crate) enum Phrase {
 impl Phrase {
     pub(crate) const fn pluralize(&self) -> &'static str {
         match self {
+            Self::Acronym(1) => "acronym",
+            Self::Acronym(_) => "acronyms",
+            Self::Admonition(1) => "admonition",
+            Self::Admonition(_) => "admonitions",
+            Self::Alias(1) => "alias",
+            Self::Alias(_) => "aliases",
+            Self::Character(1) => "character",
+            Self::Character(_) => "characters",
+            Self::ConflictCopy(1) => "conflict copy",
+            Self::ConflictCopy(_) => "conflict copies",
+            Self::EncryptedBlock(1) => "encrypted block",
+            Self::EncryptedBlock(_) => "encrypted blocks",
             Self::File(1) => "file",
             Self::File(_) => "files",
             Self::Has(1) => "has a",
@@ -27,12 +50,22 @@ impl Phrase {
             Self::Issue(_) => "issues",
             Self::Is(1) => "is",
             Self::Is(_) => "are",
+            Self::ListItem(1) => "list item",
+            Self::ListItem(_) => "list items",
             Self::Match(1) => "match",
             Self::Match(_) => "matches",
+            Self::Pair(1) => "pair",
+            Self::Pair(_) => "pairs",
+            Self::Property(1) => "property",
+            Self::Property(_) => "properties",
             Self::Reason(1) => "reason",
             Self::Reason(_) => "reasons",
             Self::Reference(1) => "reference",
             Self::Reference(_) => "references",
+            Self::Replacement(1) => "replacement",
+            Self::Replacement(_) => "replacements",
+            Self::Section(1) => "section",
+            Self::Section(_) => "sections",
             Self::Target(1) => "target",
             Self::Target(_) => "targets",
             Self::Time(1) => "time",
@@ -46,14 +79,25 @@ impl Phrase {
 
     pub(crate) const fn value(&self) -> usize {
         match self {
-            Self::File(value)
+            Self::Acronym(value)
+            | Self::Admonition(value)
+            | Self::Alias(value)
+            | Self::Character(value)
+            | Self::ConflictCopy(value)
+            | Self::EncryptedBlock(value)
+            | Self::File(value)
             | Self::Has(value)
             | Self::Image(value)
             | Self::Issue(value)
             | Self::Is(value)
+            | Self::ListItem(value)
             | Self::Match(value)
+            | Self::Pair(value)
+            | Self::Property(value)
             | Self::Reason(value)
             | Self::Reference(value)
+            | Self::Replacement(value)
+            | Self::Section(value)
             | Self::Target(value)
             | Self::Time(value)
             | Self::Wikilink(value)
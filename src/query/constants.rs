@@ -0,0 +1,104 @@
+// dataview
+pub(super) const DATAVIEW_AND: &str = "AND";
+pub(super) const DATAVIEW_AS: &str = "AS";
+pub(super) const DATAVIEW_ASC: &str = "ASC";
+pub(super) const DATAVIEW_CLOSING_PARENTHESIS: &str = ")";
+pub(super) const DATAVIEW_COMMA: &str = ",";
+/// Comparisons, longest first so `<=` isn't read as `<`.
+pub(super) const DATAVIEW_COMPARISONS: [&str; 6] = ["!=", "<=", ">=", "=", "<", ">"];
+pub(super) const DATAVIEW_DATE: &str = "date";
+pub(super) const DATAVIEW_DESC: &str = "DESC";
+pub(super) const DATAVIEW_DUR: &str = "dur";
+/// `dur()` units, the `SQLite` date modifier unit each becomes, and how many of those it is.
+pub(super) const DATAVIEW_DURATION_UNITS: [(&str, &str, i64); 8] = [
+    ("day", "days", 1),
+    ("days", "days", 1),
+    ("week", "days", 7),
+    ("weeks", "days", 7),
+    ("month", "months", 1),
+    ("months", "months", 1),
+    ("year", "years", 1),
+    ("years", "years", 1),
+];
+pub(super) const DATAVIEW_END_OF_QUERY: &str = "the end of the query";
+pub(super) const DATAVIEW_FIELD_ALIASES: &str = "file.aliases";
+pub(super) const DATAVIEW_FIELD_CTIME: &str = "file.ctime";
+pub(super) const DATAVIEW_FIELD_INLINKS: &str = "file.inlinks";
+pub(super) const DATAVIEW_FIELD_MTIME: &str = "file.mtime";
+pub(super) const DATAVIEW_FIELD_NAME: &str = "file.name";
+pub(super) const DATAVIEW_FIELD_OUTLINKS: &str = "file.outlinks";
+pub(super) const DATAVIEW_FIELD_PATH: &str = "file.path";
+pub(super) const DATAVIEW_FIELD_TAGS: &str = "file.tags";
+pub(super) const DATAVIEW_FILE_HEADER: &str = "File";
+pub(super) const DATAVIEW_FROM: &str = "FROM";
+/// A query starting with one of these is read as Dataview rather than as filters.
+pub(super) const DATAVIEW_LEADING_KEYWORDS: [&str; 3] = ["FROM", "LIST", "TABLE"];
+pub(super) const DATAVIEW_LENGTH: &str = "length";
+pub(super) const DATAVIEW_LIMIT: &str = "LIMIT";
+pub(super) const DATAVIEW_LIST: &str = "LIST";
+/// Dataview shows an empty value as a dash.
+pub(super) const DATAVIEW_MINUS: &str = "-";
+pub(super) const DATAVIEW_NULL: &str = "-";
+pub(super) const DATAVIEW_OPENING_PARENTHESIS: &str = "(";
+pub(super) const DATAVIEW_OR: &str = "OR";
+pub(super) const DATAVIEW_PLUS: &str = "+";
+pub(super) const DATAVIEW_SORT: &str = "SORT";
+pub(super) const DATAVIEW_SQL_ALIASES: &str =
+    "(SELECT group_concat(alias, ', ') FROM aliases WHERE aliases.path = notes.path)";
+pub(super) const DATAVIEW_SQL_ALIASES_LENGTH: &str =
+    "(SELECT COUNT(*) FROM aliases WHERE aliases.path = notes.path)";
+pub(super) const DATAVIEW_SQL_CTIME: &str = "file_created";
+pub(super) const DATAVIEW_SQL_FOLDER: &str = "path LIKE ?";
+/// Links whose target names the note - `[[Note]]`, `[[Note#Heading]]` or `[[folder/Note]]`.
+pub(super) const DATAVIEW_SQL_INLINKS: &str = "(SELECT group_concat('[[' || sources.name || ']]', \
+                                               ', ') FROM links JOIN notes AS sources ON \
+                                               sources.path = links.path WHERE \
+                                               links.note = notes.name COLLATE NOCASE)";
+pub(super) const DATAVIEW_SQL_INLINKS_LENGTH: &str =
+    "(SELECT COUNT(*) FROM links WHERE links.note = notes.name COLLATE NOCASE)";
+pub(super) const DATAVIEW_SQL_MTIME: &str = "file_modified";
+pub(super) const DATAVIEW_SQL_NAME: &str = "name";
+pub(super) const DATAVIEW_SQL_NO_OFFSET: &str = "+0 days";
+pub(super) const DATAVIEW_SQL_OUTLINKS: &str = "(SELECT group_concat('[[' || target || ']]', ', \
+                                                ') FROM links WHERE links.path = notes.path)";
+pub(super) const DATAVIEW_SQL_OUTLINKS_LENGTH: &str =
+    "(SELECT COUNT(*) FROM links WHERE links.path = notes.path)";
+pub(super) const DATAVIEW_SQL_PARAMETER: &str = "?";
+pub(super) const DATAVIEW_SQL_PATH: &str = "path";
+pub(super) const DATAVIEW_SQL_TAGS: &str =
+    "(SELECT group_concat('#' || tag, ', ') FROM tags WHERE tags.path = notes.path)";
+pub(super) const DATAVIEW_SQL_TAGS_LENGTH: &str =
+    "(SELECT COUNT(*) FROM tags WHERE tags.path = notes.path)";
+/// Today's date, UTC, moved by the bound modifier - compares with the index's `file_created`
+/// and `file_modified` as text.
+pub(super) const DATAVIEW_SQL_TODAY: &str = "date('now', ?)";
+pub(super) const DATAVIEW_SQL_WILDCARD: &str = "/%";
+pub(super) const DATAVIEW_TABLE: &str = "TABLE";
+pub(super) const DATAVIEW_TODAY: &str = "today";
+pub(super) const DATAVIEW_WHERE: &str = "WHERE";
+
+// filters
+pub(super) const QUERY_AND: &str = "AND";
+pub(super) const QUERY_FILTER_SEPARATOR: char = ':';
+pub(super) const QUERY_LINKS_TO: &str = "links-to";
+pub(super) const QUERY_MARKDOWN_FLAG: &str = "--markdown";
+pub(super) const QUERY_MISSING: &str = "missing";
+pub(super) const QUERY_MISSING_ALIASES: &str = "aliases";
+pub(super) const QUERY_MISSING_DATE_CREATED: &str = "date_created";
+pub(super) const QUERY_MISSING_DATE_MODIFIED: &str = "date_modified";
+pub(super) const QUERY_MISSING_TAGS: &str = "tags";
+pub(super) const QUERY_QUOTE: char = '"';
+pub(super) const QUERY_SELECT_NOTES: &str = "SELECT name, path FROM notes WHERE";
+pub(super) const QUERY_SQL_AND: &str = " AND ";
+pub(super) const QUERY_SQL_LINKS_TO: &str =
+    "path IN (SELECT path FROM links WHERE note = ? COLLATE NOCASE)";
+pub(super) const QUERY_SQL_MISSING_ALIASES: &str = "path NOT IN (SELECT path FROM aliases)";
+pub(super) const QUERY_SQL_MISSING_DATE_CREATED: &str = "COALESCE(date_created, '') = ''";
+pub(super) const QUERY_SQL_MISSING_DATE_MODIFIED: &str = "COALESCE(date_modified, '') = ''";
+pub(super) const QUERY_SQL_MISSING_TAGS: &str = "path NOT IN (SELECT path FROM tags)";
+pub(super) const QUERY_SQL_ORDER: &str = " ORDER BY path";
+pub(super) const QUERY_SQL_TAG: &str =
+    "path IN (SELECT path FROM tags WHERE tag = ? COLLATE NOCASE)";
+pub(super) const QUERY_TAG: &str = "tag";
+pub(super) const QUERY_USAGE: &str =
+    "usage: obsidian_knife query <obsidian_folder/config_file.md> <query> [--markdown]";
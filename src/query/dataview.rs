@@ -0,0 +1,704 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Write;
+
+use rusqlite::Connection;
+use rusqlite::params_from_iter;
+use rusqlite::types::Value;
+
+use super::QueryError;
+use super::constants::DATAVIEW_AND;
+use super::constants::DATAVIEW_AS;
+use super::constants::DATAVIEW_ASC;
+use super::constants::DATAVIEW_CLOSING_PARENTHESIS;
+use super::constants::DATAVIEW_COMMA;
+use super::constants::DATAVIEW_COMPARISONS;
+use super::constants::DATAVIEW_DATE;
+use super::constants::DATAVIEW_DESC;
+use super::constants::DATAVIEW_DUR;
+use super::constants::DATAVIEW_DURATION_UNITS;
+use super::constants::DATAVIEW_END_OF_QUERY;
+use super::constants::DATAVIEW_FIELD_ALIASES;
+use super::constants::DATAVIEW_FIELD_CTIME;
+use super::constants::DATAVIEW_FIELD_INLINKS;
+use super::constants::DATAVIEW_FIELD_MTIME;
+use super::constants::DATAVIEW_FIELD_NAME;
+use super::constants::DATAVIEW_FIELD_OUTLINKS;
+use super::constants::DATAVIEW_FIELD_PATH;
+use super::constants::DATAVIEW_FIELD_TAGS;
+use super::constants::DATAVIEW_FILE_HEADER;
+use super::constants::DATAVIEW_FROM;
+use super::constants::DATAVIEW_LEADING_KEYWORDS;
+use super::constants::DATAVIEW_LENGTH;
+use super::constants::DATAVIEW_LIMIT;
+use super::constants::DATAVIEW_LIST;
+use super::constants::DATAVIEW_MINUS;
+use super::constants::DATAVIEW_NULL;
+use super::constants::DATAVIEW_OPENING_PARENTHESIS;
+use super::constants::DATAVIEW_OR;
+use super::constants::DATAVIEW_PLUS;
+use super::constants::DATAVIEW_SORT;
+use super::constants::DATAVIEW_SQL_ALIASES;
+use super::constants::DATAVIEW_SQL_ALIASES_LENGTH;
+use super::constants::DATAVIEW_SQL_CTIME;
+use super::constants::DATAVIEW_SQL_FOLDER;
+use super::constants::DATAVIEW_SQL_INLINKS;
+use super::constants::DATAVIEW_SQL_INLINKS_LENGTH;
+use super::constants::DATAVIEW_SQL_MTIME;
+use super::constants::DATAVIEW_SQL_NAME;
+use super::constants::DATAVIEW_SQL_NO_OFFSET;
+use super::constants::DATAVIEW_SQL_OUTLINKS;
+use super::constants::DATAVIEW_SQL_OUTLINKS_LENGTH;
+use super::constants::DATAVIEW_SQL_PARAMETER;
+use super::constants::DATAVIEW_SQL_PATH;
+use super::constants::DATAVIEW_SQL_TAGS;
+use super::constants::DATAVIEW_SQL_TAGS_LENGTH;
+use super::constants::DATAVIEW_SQL_TODAY;
+use super::constants::DATAVIEW_SQL_WILDCARD;
+use super::constants::DATAVIEW_TABLE;
+use super::constants::DATAVIEW_TODAY;
+use super::constants::DATAVIEW_WHERE;
+use super::constants::QUERY_QUOTE;
+use super::constants::QUERY_SQL_LINKS_TO;
+use super::constants::QUERY_SQL_TAG;
+use crate::constants::CLOSING_WIKILINK;
+use crate::constants::ESCAPED_PIPE;
+use crate::constants::FORWARD_SLASH;
+use crate::constants::MARKDOWN_TABLE_CELL_SEPARATOR;
+use crate::constants::MARKDOWN_TABLE_SEPARATOR;
+use crate::constants::OPENING_WIKILINK;
+use crate::constants::PIPE;
+use crate::constants::TAG_PREFIX;
+use crate::output_file_writer::OutputFileWriter;
+use crate::wikilink::ToWikilink;
+
+/// Whether `query` reads as Dataview - `TABLE`, `LIST` or `FROM` first - rather than filters.
+pub(super) fn is_dataview(query: &str) -> bool {
+    query.split_whitespace().next().is_some_and(|first| {
+        DATAVIEW_LEADING_KEYWORDS
+            .iter()
+            .any(|keyword| first.eq_ignore_ascii_case(keyword))
+    })
+}
+
+/// Runs a Dataview query against the vault index and renders it the way Dataview does - a
+/// markdown table headed `File (N)` for `TABLE`, or a list of links for `LIST`. A query with
+/// neither is a table with no columns.
+pub(super) fn run(connection: &Connection, query: &str) -> Result<Vec<String>, QueryError> {
+    let compiled = parse(query)?;
+    let rows = query_rows(connection, &compiled)?;
+
+    let Some(headers) = compiled.headers else {
+        return Ok(rows
+            .iter()
+            .map(|row| format!("- {}", row[0].to_wikilink()))
+            .collect());
+    };
+
+    let mut lines = Vec::with_capacity(rows.len() + 2);
+    let mut header_cells = vec![format!("{DATAVIEW_FILE_HEADER} ({})", rows.len())];
+    header_cells.extend(headers);
+    lines.push(OutputFileWriter::markdown_table_row(
+        &header_cells.join(MARKDOWN_TABLE_CELL_SEPARATOR),
+    ));
+    lines.push(OutputFileWriter::markdown_table_row(
+        &vec![MARKDOWN_TABLE_SEPARATOR; header_cells.len()].join(MARKDOWN_TABLE_CELL_SEPARATOR),
+    ));
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                if index == 0 {
+                    cell.to_wikilink()
+                } else {
+                    cell.replace(PIPE, ESCAPED_PIPE)
+                }
+            })
+            .collect();
+        lines.push(OutputFileWriter::markdown_table_row(
+            &cells.join(MARKDOWN_TABLE_CELL_SEPARATOR),
+        ));
+    }
+    Ok(lines)
+}
+
+/// Runs a saved report's `query` - `FROM`, `WHERE`, `SORT` and `LIMIT` clauses - as a Dataview
+/// `TABLE` of `columns`. Returns the column headers, and a row per note with its name first.
+pub(crate) fn saved_report_rows(
+    connection: &Connection,
+    query: &str,
+    columns: &[String],
+) -> Result<(Vec<String>, Vec<Vec<String>>), QueryError> {
+    let compiled = parse(&saved_report_query(query, columns))?;
+    let rows = query_rows(connection, &compiled)?;
+    Ok((compiled.headers.unwrap_or_default(), rows))
+}
+
+/// Checks a saved report's query and columns parse, so a bad one fails at config validation
+/// rather than mid-run.
+pub(crate) fn validate_saved_report(query: &str, columns: &[String]) -> Result<(), QueryError> {
+    parse(&saved_report_query(query, columns)).map(|_| ())
+}
+
+fn saved_report_query(query: &str, columns: &[String]) -> String {
+    format!(
+        "{DATAVIEW_TABLE} {} {query}",
+        columns.join(&format!("{DATAVIEW_COMMA} "))
+    )
+}
+
+fn query_rows(
+    connection: &Connection,
+    compiled: &CompiledQuery,
+) -> Result<Vec<Vec<String>>, QueryError> {
+    let mut statement = connection.prepare(&compiled.sql)?;
+    let column_count = statement.column_count();
+    let rows = statement
+        .query_map(params_from_iter(&compiled.values), |row| {
+            (0..column_count)
+                .map(|index| row.get::<_, Value>(index).map(display_value))
+                .collect::<Result<Vec<String>, _>>()
+        })?
+        .collect::<Result<Vec<Vec<String>>, _>>()?;
+    Ok(rows)
+}
+
+fn display_value(value: Value) -> String {
+    match value {
+        Value::Null | Value::Blob(_) => DATAVIEW_NULL.to_string(),
+        Value::Integer(integer) => integer.to_string(),
+        Value::Real(real) => real.to_string(),
+        Value::Text(text) if text.is_empty() => DATAVIEW_NULL.to_string(),
+        Value::Text(text) => text,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Link(String),
+    Tag(String),
+    Number(i64),
+    Symbol(&'static str),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "{word}"),
+            Self::Text(text) => write!(f, "{QUERY_QUOTE}{text}{QUERY_QUOTE}"),
+            Self::Link(target) => write!(f, "{}", target.to_wikilink()),
+            Self::Tag(tag) => write!(f, "{TAG_PREFIX}{tag}"),
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+fn symbols() -> impl Iterator<Item = &'static str> {
+    DATAVIEW_COMPARISONS.into_iter().chain([
+        DATAVIEW_OPENING_PARENTHESIS,
+        DATAVIEW_CLOSING_PARENTHESIS,
+        DATAVIEW_COMMA,
+    ])
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut rest = query.trim_start();
+    while !rest.is_empty() {
+        if let Some(symbol) = symbols().find(|symbol| rest.starts_with(symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else if let Some(link) = rest.strip_prefix(OPENING_WIKILINK) {
+            let (target, after) =
+                link.split_once(CLOSING_WIKILINK)
+                    .ok_or_else(|| QueryError::DataviewSyntax {
+                        expected: CLOSING_WIKILINK,
+                        found:    DATAVIEW_END_OF_QUERY.to_string(),
+                    })?;
+            tokens.push(Token::Link(target.to_string()));
+            rest = after;
+        } else if let Some(quoted) = rest.strip_prefix(QUERY_QUOTE) {
+            let (text, after) = quoted
+                .split_once(QUERY_QUOTE)
+                .ok_or(QueryError::UnclosedQuote)?;
+            tokens.push(Token::Text(text.to_string()));
+            rest = after;
+        } else {
+            let end = rest
+                .find(|character: char| {
+                    character.is_whitespace()
+                        || character == QUERY_QUOTE
+                        || symbols().any(|symbol| symbol.starts_with(character))
+                })
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            tokens.push(word_token(word));
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+fn word_token(word: &str) -> Token {
+    word.strip_prefix(TAG_PREFIX).map_or_else(
+        || {
+            word.parse()
+                .map_or_else(|_| Token::Word(word.to_string()), Token::Number)
+        },
+        |tag| Token::Tag(tag.to_string()),
+    )
+}
+
+/// A note field Dataview knows as `file.*`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Aliases,
+    Created,
+    Inlinks,
+    Modified,
+    Name,
+    Outlinks,
+    Path,
+    Tags,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            DATAVIEW_FIELD_ALIASES => Some(Self::Aliases),
+            DATAVIEW_FIELD_CTIME => Some(Self::Created),
+            DATAVIEW_FIELD_INLINKS => Some(Self::Inlinks),
+            DATAVIEW_FIELD_MTIME => Some(Self::Modified),
+            DATAVIEW_FIELD_NAME => Some(Self::Name),
+            DATAVIEW_FIELD_OUTLINKS => Some(Self::Outlinks),
+            DATAVIEW_FIELD_PATH => Some(Self::Path),
+            DATAVIEW_FIELD_TAGS => Some(Self::Tags),
+            _ => None,
+        }
+    }
+
+    /// The field's value on a `notes` row - lists are joined into one string.
+    const fn sql(self) -> &'static str {
+        match self {
+            Self::Aliases => DATAVIEW_SQL_ALIASES,
+            Self::Created => DATAVIEW_SQL_CTIME,
+            Self::Inlinks => DATAVIEW_SQL_INLINKS,
+            Self::Modified => DATAVIEW_SQL_MTIME,
+            Self::Name => DATAVIEW_SQL_NAME,
+            Self::Outlinks => DATAVIEW_SQL_OUTLINKS,
+            Self::Path => DATAVIEW_SQL_PATH,
+            Self::Tags => DATAVIEW_SQL_TAGS,
+        }
+    }
+
+    /// `length()` of the field - the item count of a list, the character count otherwise.
+    fn length_sql(self) -> String {
+        match self {
+            Self::Aliases => DATAVIEW_SQL_ALIASES_LENGTH.to_string(),
+            Self::Inlinks => DATAVIEW_SQL_INLINKS_LENGTH.to_string(),
+            Self::Outlinks => DATAVIEW_SQL_OUTLINKS_LENGTH.to_string(),
+            Self::Tags => DATAVIEW_SQL_TAGS_LENGTH.to_string(),
+            Self::Created | Self::Modified | Self::Name | Self::Path => {
+                format!("{DATAVIEW_LENGTH}({})", self.sql())
+            },
+        }
+    }
+}
+
+/// A Dataview query translated to SQL over the vault index. `headers` is `None` for `LIST`.
+struct CompiledQuery {
+    sql:     String,
+    values:  Vec<Value>,
+    headers: Option<Vec<String>>,
+}
+
+struct Parser {
+    tokens:   Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> { self.tokens.get(self.position) }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let matched =
+            matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword));
+        if matched {
+            self.position += 1;
+        }
+        matched
+    }
+
+    fn symbol(&mut self, symbol: &str) -> bool {
+        let matched = matches!(self.peek(), Some(Token::Symbol(found)) if *found == symbol);
+        if matched {
+            self.position += 1;
+        }
+        matched
+    }
+
+    fn at_clause(&self) -> bool {
+        match self.peek() {
+            None => true,
+            Some(Token::Word(word)) => {
+                [DATAVIEW_FROM, DATAVIEW_WHERE, DATAVIEW_SORT, DATAVIEW_LIMIT]
+                    .iter()
+                    .any(|keyword| word.eq_ignore_ascii_case(keyword))
+            },
+            Some(_) => false,
+        }
+    }
+
+    fn expected(&self, expected: &'static str) -> QueryError {
+        QueryError::DataviewSyntax {
+            expected,
+            found: self
+                .peek()
+                .map_or_else(|| DATAVIEW_END_OF_QUERY.to_string(), ToString::to_string),
+        }
+    }
+
+    /// A field, or `length(field)`, as SQL.
+    fn expression(&mut self) -> Result<String, QueryError> {
+        if self.keyword(DATAVIEW_LENGTH) {
+            if !self.symbol(DATAVIEW_OPENING_PARENTHESIS) {
+                return Err(self.expected(DATAVIEW_OPENING_PARENTHESIS));
+            }
+            let field = self.field()?;
+            if !self.symbol(DATAVIEW_CLOSING_PARENTHESIS) {
+                return Err(self.expected(DATAVIEW_CLOSING_PARENTHESIS));
+            }
+            return Ok(field.length_sql());
+        }
+        Ok(self.field()?.sql().to_string())
+    }
+
+    fn field(&mut self) -> Result<Field, QueryError> {
+        if let Some(Token::Word(name)) = self.peek()
+            && let Some(field) = Field::parse(name)
+        {
+            self.position += 1;
+            return Ok(field);
+        }
+        Err(self.expected("a field such as file.name, file.mtime or file.inlinks"))
+    }
+
+    /// `TABLE` columns: expressions, each optionally `AS "header"`, separated by commas.
+    fn columns(&mut self, select: &mut String) -> Result<Vec<String>, QueryError> {
+        let mut headers = Vec::new();
+        while !self.at_clause() {
+            let start = self.position;
+            let sql = self.expression()?;
+            let mut header: Vec<String> = self.tokens[start..self.position]
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            if self.keyword(DATAVIEW_AS) {
+                match self.next() {
+                    Some(Token::Text(text) | Token::Word(text)) => header = vec![text],
+                    _ => return Err(self.expected("a column name after AS")),
+                }
+            }
+            let _ = write!(select, ", {sql}");
+            headers.push(header.concat());
+            if !self.symbol(DATAVIEW_COMMA) {
+                break;
+            }
+        }
+        Ok(headers)
+    }
+
+    /// `FROM #tag`, `FROM "folder"` or `FROM [[note]]` - the notes linking to the note.
+    fn source(&mut self, values: &mut Vec<Value>) -> Result<&'static str, QueryError> {
+        match self.next() {
+            Some(Token::Tag(tag)) => {
+                values.push(Value::Text(tag));
+                Ok(QUERY_SQL_TAG)
+            },
+            Some(Token::Text(folder)) => {
+                values.push(Value::Text(format!(
+                    "{}{DATAVIEW_SQL_WILDCARD}",
+                    folder.trim_end_matches(FORWARD_SLASH)
+                )));
+                Ok(DATAVIEW_SQL_FOLDER)
+            },
+            Some(Token::Link(target)) => {
+                values.push(Value::Text(target));
+                Ok(QUERY_SQL_LINKS_TO)
+            },
+            _ => {
+                self.position -= 1;
+                Err(self.expected("#tag, \"folder\" or [[note]] after FROM"))
+            },
+        }
+    }
+
+    /// `expression comparison value`, joined by `AND` or `OR`.
+    fn conditions(&mut self, values: &mut Vec<Value>) -> Result<String, QueryError> {
+        let mut sql = String::new();
+        loop {
+            let expression = self.expression()?;
+            let Some(comparison) = DATAVIEW_COMPARISONS
+                .into_iter()
+                .find(|comparison| self.symbol(comparison))
+            else {
+                return Err(self.expected("a comparison such as = or >"));
+            };
+            let value = self.value(values)?;
+            let _ = write!(sql, "{expression} {comparison} {value}");
+
+            if self.keyword(DATAVIEW_AND) {
+                let _ = write!(sql, " {DATAVIEW_AND} ");
+            } else if self.keyword(DATAVIEW_OR) {
+                let _ = write!(sql, " {DATAVIEW_OR} ");
+            } else {
+                return Ok(sql);
+            }
+        }
+    }
+
+    /// A number, `"text"`, or `date(today)` less or plus a `dur(14 days)`, as SQL.
+    fn value(&mut self, values: &mut Vec<Value>) -> Result<&'static str, QueryError> {
+        match self.next() {
+            Some(Token::Number(number)) => {
+                values.push(Value::Integer(number));
+                Ok(DATAVIEW_SQL_PARAMETER)
+            },
+            Some(Token::Text(text)) => {
+                values.push(Value::Text(text));
+                Ok(DATAVIEW_SQL_PARAMETER)
+            },
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(DATAVIEW_DATE) => {
+                if !(self.symbol(DATAVIEW_OPENING_PARENTHESIS)
+                    && self.keyword(DATAVIEW_TODAY)
+                    && self.symbol(DATAVIEW_CLOSING_PARENTHESIS))
+                {
+                    return Err(self.expected("date(today)"));
+                }
+                let sign = if self.keyword(DATAVIEW_MINUS) {
+                    DATAVIEW_MINUS
+                } else if self.keyword(DATAVIEW_PLUS) {
+                    DATAVIEW_PLUS
+                } else {
+                    values.push(Value::Text(DATAVIEW_SQL_NO_OFFSET.to_string()));
+                    return Ok(DATAVIEW_SQL_TODAY);
+                };
+                let offset = self.duration()?;
+                values.push(Value::Text(format!("{sign}{offset}")));
+                Ok(DATAVIEW_SQL_TODAY)
+            },
+            _ => {
+                self.position -= 1;
+                Err(self.expected("a number, \"text\" or date(today)"))
+            },
+        }
+    }
+
+    /// `dur(14 days)` as an `SQLite` date modifier such as `14 days`.
+    fn duration(&mut self) -> Result<String, QueryError> {
+        const EXPECTED: &str = "a duration such as dur(14 days)";
+        if !(self.keyword(DATAVIEW_DUR) && self.symbol(DATAVIEW_OPENING_PARENTHESIS)) {
+            return Err(self.expected(EXPECTED));
+        }
+        let (Some(Token::Number(count)), Some(Token::Word(unit))) = (self.next(), self.next())
+        else {
+            return Err(self.expected(EXPECTED));
+        };
+        let Some((_, sql_unit, multiplier)) = DATAVIEW_DURATION_UNITS
+            .iter()
+            .find(|(name, _, _)| unit.eq_ignore_ascii_case(name))
+        else {
+            return Err(self.expected(EXPECTED));
+        };
+        if !self.symbol(DATAVIEW_CLOSING_PARENTHESIS) {
+            return Err(self.expected(DATAVIEW_CLOSING_PARENTHESIS));
+        }
+        Ok(format!("{} {sql_unit}", count * multiplier))
+    }
+
+    /// `SORT expression [ASC|DESC]`, separated by commas.
+    fn sort(&mut self) -> Result<String, QueryError> {
+        let mut sql = String::new();
+        loop {
+            let expression = self.expression()?;
+            let direction = if self.keyword(DATAVIEW_DESC) {
+                DATAVIEW_DESC
+            } else {
+                self.keyword(DATAVIEW_ASC);
+                DATAVIEW_ASC
+            };
+            let _ = write!(sql, "{expression} {direction}, ");
+            if !self.symbol(DATAVIEW_COMMA) {
+                return Ok(sql);
+            }
+        }
+    }
+}
+
+fn parse(query: &str) -> Result<CompiledQuery, QueryError> {
+    let mut parser = Parser {
+        tokens:   tokenize(query)?,
+        position: 0,
+    };
+    let mut values = Vec::new();
+    let mut sql = format!("SELECT {DATAVIEW_SQL_NAME}");
+
+    let headers = if parser.keyword(DATAVIEW_LIST) {
+        None
+    } else if parser.keyword(DATAVIEW_TABLE) {
+        Some(parser.columns(&mut sql)?)
+    } else {
+        Some(Vec::new())
+    };
+    sql.push_str(" FROM notes WHERE 1");
+
+    if parser.keyword(DATAVIEW_FROM) {
+        let source = parser.source(&mut values)?;
+        let _ = write!(sql, " {DATAVIEW_AND} {source}");
+    }
+    if parser.keyword(DATAVIEW_WHERE) {
+        let conditions = parser.conditions(&mut values)?;
+        let _ = write!(sql, " {DATAVIEW_AND} ({conditions})");
+    }
+    sql.push_str(" ORDER BY ");
+    if parser.keyword(DATAVIEW_SORT) {
+        sql.push_str(&parser.sort()?);
+    }
+    sql.push_str(DATAVIEW_SQL_PATH);
+    if parser.keyword(DATAVIEW_LIMIT) {
+        let Some(Token::Number(limit)) = parser.next() else {
+            parser.position -= 1;
+            return Err(parser.expected("a number after LIMIT"));
+        };
+        values.push(Value::Integer(limit));
+        sql.push_str(" LIMIT ?");
+    }
+    if parser.peek().is_some() {
+        return Err(parser.expected(DATAVIEW_END_OF_QUERY));
+    }
+
+    Ok(CompiledQuery {
+        sql,
+        values,
+        headers,
+    })
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use rusqlite::Connection;
+    use tempfile::TempDir;
+
+    use super::is_dataview;
+    use super::run;
+    use crate::constants::VAULT_INDEX_FILE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::query::QueryError;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_dataview_queries_render_like_dataview() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("projects")).unwrap();
+        TestFileBuilder::new()
+            .with_title("Launch".to_string())
+            .with_custom_frontmatter("tags:\n  - project".to_string())
+            .with_content("See [[Roadmap]]".to_string())
+            .create(&temp_dir, "projects/Launch.md");
+        TestFileBuilder::new()
+            .with_title("Roadmap".to_string())
+            .with_content("#project".to_string())
+            .create(&temp_dir, "Roadmap.md");
+        TestFileBuilder::new()
+            .with_title("Inbox".to_string())
+            .create(&temp_dir, "Inbox.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.vault_index(true);
+            })
+            .unwrap();
+        ObsidianRepository::new(&validated_config)
+            .unwrap()
+            .write_vault_index(&validated_config)
+            .unwrap();
+        let connection =
+            Connection::open(validated_config.state_folder().join(VAULT_INDEX_FILE)).unwrap();
+
+        assert!(is_dataview("from #project"));
+        assert!(!is_dataview("tag:project"));
+
+        assert_eq!(
+            run(
+                &connection,
+                "FROM #project WHERE length(file.inlinks) = 0 SORT file.mtime"
+            )
+            .unwrap(),
+            vec![
+                "| File (1) |".to_string(),
+                "| --- |".to_string(),
+                "| [[Launch]] |".to_string(),
+            ]
+        );
+        assert_eq!(
+            run(
+                &connection,
+                r#"TABLE length(file.outlinks) AS "Links", file.inlinks FROM #project SORT file.name DESC"#
+            )
+            .unwrap(),
+            vec![
+                "| File (2) | Links | file.inlinks |".to_string(),
+                "| --- | --- | --- |".to_string(),
+                "| [[Roadmap]] | 0 | [[Launch]] |".to_string(),
+                "| [[Launch]] | 1 | - |".to_string(),
+            ]
+        );
+        assert_eq!(
+            run(&connection, r#"LIST FROM "projects""#).unwrap(),
+            vec!["- [[Launch]]".to_string()]
+        );
+        assert_eq!(
+            run(&connection, "LIST FROM [[Roadmap]] LIMIT 5").unwrap(),
+            vec!["- [[Launch]]".to_string()]
+        );
+        assert_eq!(
+            run(
+                &connection,
+                "LIST WHERE file.mtime > date(today) - dur(1 week) AND file.ctime < date(today) + dur(1 day) SORT file.name"
+            )
+            .unwrap()
+            .len(),
+            3
+        );
+        assert!(matches!(
+            run(
+                &connection,
+                "LIST WHERE file.mtime < date(today) - dur(2 fortnights)"
+            ),
+            Err(QueryError::DataviewSyntax { .. })
+        ));
+        assert!(matches!(
+            run(&connection, "TABLE file.size"),
+            Err(QueryError::DataviewSyntax { .. })
+        ));
+    }
+}
@@ -0,0 +1,295 @@
+mod constants;
+mod dataview;
+
+use std::mem;
+use std::path::Path;
+use std::path::PathBuf;
+
+use rusqlite::Connection;
+use rusqlite::OpenFlags;
+use rusqlite::params_from_iter;
+use thiserror::Error;
+
+use self::constants::QUERY_AND;
+use self::constants::QUERY_FILTER_SEPARATOR;
+use self::constants::QUERY_LINKS_TO;
+use self::constants::QUERY_MARKDOWN_FLAG;
+use self::constants::QUERY_MISSING;
+use self::constants::QUERY_MISSING_ALIASES;
+use self::constants::QUERY_MISSING_DATE_CREATED;
+use self::constants::QUERY_MISSING_DATE_MODIFIED;
+use self::constants::QUERY_MISSING_TAGS;
+use self::constants::QUERY_QUOTE;
+use self::constants::QUERY_SELECT_NOTES;
+use self::constants::QUERY_SQL_AND;
+use self::constants::QUERY_SQL_LINKS_TO;
+use self::constants::QUERY_SQL_MISSING_ALIASES;
+use self::constants::QUERY_SQL_MISSING_DATE_CREATED;
+use self::constants::QUERY_SQL_MISSING_DATE_MODIFIED;
+use self::constants::QUERY_SQL_MISSING_TAGS;
+use self::constants::QUERY_SQL_ORDER;
+use self::constants::QUERY_SQL_TAG;
+use self::constants::QUERY_TAG;
+use self::constants::QUERY_USAGE;
+pub(crate) use self::dataview::saved_report_rows;
+pub(crate) use self::dataview::validate_saved_report;
+use crate::constants::TAG_PREFIX;
+use crate::wikilink::ToWikilink;
+
+#[derive(Debug, Error)]
+pub(crate) enum QueryError {
+    #[error("{QUERY_USAGE}")]
+    Usage,
+    #[error("unclosed quote in query")]
+    UnclosedQuote,
+    #[error("unknown filter {0:?} - use tag:, links-to: or missing:, joined with AND")]
+    UnknownFilter(String),
+    #[error("missing: takes date_created, date_modified, aliases or tags, got {0:?}")]
+    UnknownMissingField(String),
+    #[error("dataview query expected {expected}, found {found}")]
+    DataviewSyntax {
+        expected: &'static str,
+        found:    String,
+    },
+    #[error("no vault index at {} - set vault_index: true and run ok first", .0.display())]
+    NoIndex(PathBuf),
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum QueryFormat {
+    Paths,
+    MarkdownList,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MissingField {
+    Aliases,
+    DateCreated,
+    DateModified,
+    Tags,
+}
+
+/// One term of a query. A note matches when it matches every term.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Filter {
+    LinksTo(String),
+    Missing(MissingField),
+    Tag(String),
+}
+
+impl Filter {
+    fn parse(term: &str) -> Result<Self, QueryError> {
+        let unknown = || QueryError::UnknownFilter(term.to_string());
+        let (key, value) = term
+            .split_once(QUERY_FILTER_SEPARATOR)
+            .ok_or_else(unknown)?;
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(unknown());
+        }
+
+        match key {
+            QUERY_TAG => Ok(Self::Tag(value.trim_start_matches(TAG_PREFIX).to_string())),
+            QUERY_LINKS_TO => Ok(Self::LinksTo(value.to_string())),
+            QUERY_MISSING => match value {
+                QUERY_MISSING_ALIASES => Ok(Self::Missing(MissingField::Aliases)),
+                QUERY_MISSING_DATE_CREATED => Ok(Self::Missing(MissingField::DateCreated)),
+                QUERY_MISSING_DATE_MODIFIED => Ok(Self::Missing(MissingField::DateModified)),
+                QUERY_MISSING_TAGS => Ok(Self::Missing(MissingField::Tags)),
+                _ => Err(QueryError::UnknownMissingField(value.to_string())),
+            },
+            _ => Err(unknown()),
+        }
+    }
+
+    /// The SQL condition on `notes`, and the value it binds, if any.
+    fn condition(&self) -> (&'static str, Option<&str>) {
+        match self {
+            Self::LinksTo(target) => (QUERY_SQL_LINKS_TO, Some(target)),
+            Self::Missing(MissingField::Aliases) => (QUERY_SQL_MISSING_ALIASES, None),
+            Self::Missing(MissingField::DateCreated) => (QUERY_SQL_MISSING_DATE_CREATED, None),
+            Self::Missing(MissingField::DateModified) => (QUERY_SQL_MISSING_DATE_MODIFIED, None),
+            Self::Missing(MissingField::Tags) => (QUERY_SQL_MISSING_TAGS, None),
+            Self::Tag(tag) => (QUERY_SQL_TAG, Some(tag)),
+        }
+    }
+}
+
+/// Reads `<config_file.md> <query> [--markdown]`.
+pub(crate) fn from_args(args: &[String]) -> Result<(PathBuf, String, QueryFormat), QueryError> {
+    match args {
+        [config, query] => Ok((PathBuf::from(config), query.clone(), QueryFormat::Paths)),
+        [config, query, flag] if flag == QUERY_MARKDOWN_FLAG => Ok((
+            PathBuf::from(config),
+            query.clone(),
+            QueryFormat::MarkdownList,
+        )),
+        _ => Err(QueryError::Usage),
+    }
+}
+
+/// Splits a query into terms on whitespace, keeping quoted text - `links-to:"Some Note"` -
+/// together. `AND` between terms is optional.
+fn parse(query: &str) -> Result<Vec<Filter>, QueryError> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut quoted = false;
+    for character in query.chars() {
+        match character {
+            QUERY_QUOTE => quoted = !quoted,
+            _ if character.is_whitespace() && !quoted => terms.push(mem::take(&mut term)),
+            _ => term.push(character),
+        }
+    }
+    if quoted {
+        return Err(QueryError::UnclosedQuote);
+    }
+    terms.push(term);
+
+    let filters: Vec<Filter> = terms
+        .iter()
+        .filter(|term| !term.is_empty() && *term != QUERY_AND)
+        .map(|term| Filter::parse(term))
+        .collect::<Result<_, _>>()?;
+    if filters.is_empty() {
+        return Err(QueryError::Usage);
+    }
+    Ok(filters)
+}
+
+/// Runs `query` against the vault index, returning the matching notes one per line - as
+/// vault-relative paths, or as a markdown list of links. A Dataview query is rendered the way
+/// Dataview renders it, whatever the format.
+pub(crate) fn run(
+    index_path: &Path,
+    query: &str,
+    format: QueryFormat,
+) -> Result<Vec<String>, QueryError> {
+    if !index_path.exists() {
+        return Err(QueryError::NoIndex(index_path.to_path_buf()));
+    }
+    let connection = Connection::open_with_flags(index_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    if dataview::is_dataview(query) {
+        return dataview::run(&connection, query);
+    }
+
+    let filters = parse(query)?;
+
+    let (conditions, values): (Vec<&str>, Vec<Option<&str>>) =
+        filters.iter().map(Filter::condition).unzip();
+    let sql = format!(
+        "{QUERY_SELECT_NOTES} {}{QUERY_SQL_ORDER}",
+        conditions.join(QUERY_SQL_AND)
+    );
+
+    let mut statement = connection.prepare(&sql)?;
+    let rows = statement.query_map(params_from_iter(values.into_iter().flatten()), |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    rows.map(|row| {
+        let (name, path) = row?;
+        Ok(match format {
+            QueryFormat::Paths => path,
+            QueryFormat::MarkdownList => format!("- {}", name.to_wikilink()),
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::Filter;
+    use super::MissingField;
+    use super::QueryError;
+    use super::QueryFormat;
+    use super::parse;
+    use super::run;
+    use crate::constants::VAULT_INDEX_FILE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            parse(r#"tag:#project AND missing:date_created links-to:"Some Note""#).unwrap(),
+            vec![
+                Filter::Tag("project".to_string()),
+                Filter::Missing(MissingField::DateCreated),
+                Filter::LinksTo("Some Note".to_string()),
+            ]
+        );
+        assert!(matches!(
+            parse("links-to:\"Some Note"),
+            Err(QueryError::UnclosedQuote)
+        ));
+        assert!(matches!(
+            parse("status:done"),
+            Err(QueryError::UnknownFilter(_))
+        ));
+        assert!(matches!(
+            parse("missing:status"),
+            Err(QueryError::UnknownMissingField(_))
+        ));
+        assert!(matches!(parse(" AND "), Err(QueryError::Usage)));
+    }
+
+    #[test]
+    fn test_query_runs_against_the_vault_index() {
+        let temp_dir = TempDir::new().unwrap();
+        TestFileBuilder::new()
+            .with_title("Launch".to_string())
+            .with_custom_frontmatter("tags:\n  - project".to_string())
+            .with_content("Kickoff with [[Some Note]]".to_string())
+            .create(&temp_dir, "Launch.md");
+        TestFileBuilder::new()
+            .with_title("Roadmap".to_string())
+            .with_content("#project".to_string())
+            .create(&temp_dir, "Roadmap.md");
+        TestFileBuilder::new()
+            .with_title("Some Note".to_string())
+            .create(&temp_dir, "Some Note.md");
+
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.vault_index(true);
+            })
+            .unwrap();
+        ObsidianRepository::new(&validated_config)
+            .unwrap()
+            .write_vault_index(&validated_config)
+            .unwrap();
+        let index_path = validated_config.state_folder().join(VAULT_INDEX_FILE);
+
+        assert_eq!(
+            run(&index_path, "tag:#project", QueryFormat::Paths).unwrap(),
+            vec!["Launch.md".to_string(), "Roadmap.md".to_string()]
+        );
+        assert_eq!(
+            run(
+                &index_path,
+                r#"tag:project AND links-to:"some note""#,
+                QueryFormat::MarkdownList
+            )
+            .unwrap(),
+            vec!["- [[Launch]]".to_string()]
+        );
+        assert!(matches!(
+            run(
+                &temp_dir.path().join("missing.sqlite"),
+                "tag:project",
+                QueryFormat::Paths
+            ),
+            Err(QueryError::NoIndex(_))
+        ));
+    }
+}
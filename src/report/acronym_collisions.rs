@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_ACRONYM;
+use super::constants::TABLE_HEADER_NOTES;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::ACRONYM_COLLISIONS;
+use crate::constants::ACRONYM_COLLISIONS_DESCRIPTION;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::AcronymCollision;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct AcronymCollisionsTable;
+
+impl ReportDefinition for AcronymCollisionsTable {
+    type Item = AcronymCollision;
+
+    fn headers(&self) -> Vec<&str> { vec![TABLE_HEADER_ACRONYM, TABLE_HEADER_NOTES] }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![ColumnAlignment::Left, ColumnAlignment::Left]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|collision| {
+                vec![
+                    collision.acronym.clone(),
+                    collision
+                        .paths
+                        .iter()
+                        .map(|path| {
+                            path.file_stem()
+                                .and_then(OsStr::to_str)
+                                .unwrap_or_default()
+                                .to_wikilink()
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(ACRONYM_COLLISIONS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Acronym(items.len()))
+            .text_with_newline("")
+            .no_space(ACRONYM_COLLISIONS_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_acronym_collisions_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.acronym_collisions.clone());
+        report_writer.write(&AcronymCollisionsTable, output_file_writer)
+    }
+}
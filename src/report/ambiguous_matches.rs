@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::error::Error;
@@ -6,6 +7,7 @@ use std::ffi::OsStr;
 use std::fmt::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use anyhow::Result as AnyhowResult;
 use anyhow::anyhow;
@@ -27,14 +29,19 @@ use crate::constants::IN;
 use crate::constants::LEVEL1;
 use crate::constants::LEVEL2;
 use crate::constants::LEVEL3;
+use crate::constants::LINE;
+use crate::constants::MATCH_CONTEXT_CALLOUT;
+use crate::constants::MATCH_CONTEXT_LINE_PREFIX;
 use crate::constants::MATCHES;
 use crate::constants::MATCHES_AMBIGUOUS;
 use crate::constants::MOST_RECENT_UNIQUE_LINES;
+use crate::constants::NEWLINE;
 use crate::constants::OCCURRENCES;
 use crate::constants::OPENING_WIKILINK;
 use crate::constants::REFERENCES_TO;
 use crate::constants::SHOWING_THE;
 use crate::constants::TEXT;
+use crate::constants::VIA_ALIAS;
 use crate::constants::YOU_HAVE_TO_FIX_THESE_YOURSELF;
 use crate::description_builder::DescriptionBuilder;
 use crate::markdown_file::BackPopulateMatch;
@@ -42,13 +49,19 @@ use crate::obsidian_repository::ObsidianRepository;
 use crate::output_file_writer::ColumnAlignment;
 use crate::output_file_writer::OutputFileWriter;
 use crate::phrase::Phrase;
+use crate::support::format_relative_path;
 use crate::validated_config::ValidatedConfig;
 use crate::wikilink::ToWikilink;
 
+/// A match's note and line number.
+type LineKey = (Arc<str>, usize);
+
 struct AmbiguousMatchesTable {
     display_text:   String,
     targets:        HashSet<String>,
     sorted_targets: Vec<String>,
+    /// Targets the text matches through an alias rather than the note's name.
+    alias_targets:  HashSet<String>,
 }
 
 impl ReportDefinition for AmbiguousMatchesTable {
@@ -73,7 +86,7 @@ impl ReportDefinition for AmbiguousMatchesTable {
         _: Option<&ValidatedConfig>,
     ) -> AnyhowResult<Vec<Vec<String>>> {
         // `line_map` consolidates `BackPopulateMatch` values by path and line.
-        let mut line_map: HashMap<(String, usize), (String, Vec<usize>)> = HashMap::new();
+        let mut line_map: HashMap<LineKey, (Arc<str>, Vec<usize>)> = HashMap::new();
 
         // line_map groups BackPopulateMatch positions by relative path and line number.
         for match_info in items {
@@ -87,7 +100,7 @@ impl ReportDefinition for AmbiguousMatchesTable {
 
         let mut rows = Vec::new();
         for ((file_path, line_number), (line_text, positions)) in line_map {
-            let file_path = Path::new(&file_path);
+            let file_path = Path::new(&*file_path);
             let file_stem = file_path
                 .file_stem()
                 .and_then(OsStr::to_str)
@@ -142,16 +155,21 @@ impl ReportDefinition for AmbiguousMatchesTable {
 
         // `sorted_targets` form the target list before the source-line tables.
         for target in &self.sorted_targets {
+            let via_alias = if self.alias_targets.contains(target) {
+                format!(" {VIA_ALIAS}")
+            } else {
+                String::new()
+            };
             let _ = writeln!(
                 result,
-                "- \\[\\[{}|{}]]",
+                "- \\[\\[{}|{}]]{via_alias}",
                 target.to_wikilink(),
                 self.display_text
             );
         }
 
         // DescriptionBuilder summarizes the source file count for this display text.
-        let unique_files: HashSet<String> = items.iter().map(|m| m.relative_path.clone()).collect();
+        let unique_files: HashSet<&str> = items.iter().map(|m| &*m.relative_path).collect();
 
         let stats = DescriptionBuilder::new()
             .pluralize_with_count(Phrase::Time(items.len()))
@@ -175,6 +193,10 @@ impl ReportDefinition for AmbiguousMatchesTable {
     }
 
     fn level(&self) -> &'static str { LEVEL2 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
 }
 
 struct TargetLinesTable {
@@ -281,6 +303,10 @@ impl ReportDefinition for TargetLinesTable {
     }
 
     fn level(&self) -> &'static str { LEVEL3 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
 }
 
 /// Collapses rows sharing the same trimmed `TEXT_COLUMN_INDEX` cell (carried-forward daily
@@ -301,6 +327,7 @@ fn sample_recent_unique_rows(rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
 impl ObsidianRepository {
     pub(super) fn write_ambiguous_matches_report(
         &self,
+        validated_config: &ValidatedConfig,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         output_file_writer.writeln(LEVEL1, MATCHES_AMBIGUOUS)?;
@@ -320,19 +347,31 @@ impl ObsidianRepository {
             }
         }
 
-        // targets_by_text maps found text to candidate Wikilink targets.
+        // targets_by_text maps found text to candidate Wikilink targets, and alias_targets_by_text
+        // to those the text only reaches through an alias.
         let mut targets_by_text: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut alias_targets_by_text: HashMap<String, HashSet<String>> = HashMap::new();
         for wikilink in &self.wikilinks_sorted {
             if let Some(matches) = matches_by_text.get(&wikilink.display_text.to_lowercase()) {
                 let first_match = matches
                     .first()
                     .ok_or_else(|| anyhow!(AMBIGUOUS_MATCH_GROUP_EMPTY))?;
                 targets_by_text
-                    .entry(first_match.found_text.clone())
+                    .entry(first_match.found_text.to_string())
                     .or_default()
                     .insert(wikilink.target.clone());
+                if wikilink.is_alias() {
+                    alias_targets_by_text
+                        .entry(first_match.found_text.to_string())
+                        .or_default()
+                        .insert(wikilink.target.clone());
+                }
             }
         }
+        // A target also linked by its own name isn't reached only through an alias.
+        for (display_text, alias_targets) in &mut alias_targets_by_text {
+            alias_targets.retain(|target| !target.eq_ignore_ascii_case(display_text));
+        }
 
         // sorted_keys gives report sections deterministic order.
         let mut sorted_keys: Vec<_> = matches_by_text.keys().cloned().collect();
@@ -349,7 +388,7 @@ impl ObsidianRepository {
                 .found_text;
             let default_targets = HashSet::new();
             let targets = targets_by_text
-                .get(display_text)
+                .get(&**display_text)
                 .unwrap_or(&default_targets)
                 .clone();
 
@@ -358,14 +397,25 @@ impl ObsidianRepository {
             sorted_targets.sort();
 
             let ambiguous_matches_table = AmbiguousMatchesTable {
-                display_text: display_text.clone(),
+                display_text: display_text.to_string(),
                 targets,
                 sorted_targets: sorted_targets.clone(),
+                alias_targets: alias_targets_by_text
+                    .get(&**display_text)
+                    .cloned()
+                    .unwrap_or_default(),
             };
 
             let report_writer = ReportWriter::new(matches.clone());
             report_writer.write(&ambiguous_matches_table, output_file_writer)?;
 
+            if let Some(context_lines) = validated_config.ambiguous_match_context_lines() {
+                output_file_writer.writeln(
+                    "",
+                    &self.match_contexts(matches, context_lines, validated_config.obsidian_path()),
+                )?;
+            }
+
             // `TargetLinesTable` renders source lines for each candidate target.
             for target in &sorted_targets {
                 let lines = self.collect_target_lines(target);
@@ -380,6 +430,67 @@ impl ObsidianRepository {
         Ok(())
     }
 
+    /// One collapsed callout per matched line, holding the `context_lines` lines either side of
+    /// it with the matches highlighted, so each match can be judged without opening its note.
+    fn match_contexts(
+        &self,
+        matches: &[BackPopulateMatch],
+        context_lines: usize,
+        obsidian_path: &Path,
+    ) -> String {
+        let mut positions_by_line: BTreeMap<LineKey, Vec<usize>> = BTreeMap::new();
+        for match_info in matches {
+            positions_by_line
+                .entry((match_info.relative_path.clone(), match_info.line_number))
+                .or_default()
+                .push(match_info.position);
+        }
+        let match_length = matches
+            .first()
+            .map_or(0, |match_info| match_info.found_text.len());
+
+        let mut result = String::new();
+        for ((relative_path, line_number), positions) in positions_by_line {
+            let Some(markdown_file) = self.markdown_files.iter().find(|markdown_file| {
+                format_relative_path(&markdown_file.path, obsidian_path) == *relative_path
+            }) else {
+                continue;
+            };
+            let Some(line_index) = markdown_file.body_line_index(line_number) else {
+                continue;
+            };
+            let lines: Vec<&str> = markdown_file.content.lines().collect();
+            let first = line_index.saturating_sub(context_lines);
+            let last = line_index
+                .saturating_add(context_lines)
+                .min(lines.len().saturating_sub(1));
+
+            let file_stem = Path::new(&*relative_path)
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default();
+            let _ = writeln!(
+                result,
+                "{MATCH_CONTEXT_CALLOUT} {} {LINE} {line_number}",
+                file_stem.to_wikilink()
+            );
+            for (index, line) in lines.iter().enumerate().take(last + 1).skip(first) {
+                let text = if index == line_index {
+                    support::highlight_matches(line, &positions, match_length)
+                } else {
+                    (*line).to_string()
+                };
+                let _ = writeln!(
+                    result,
+                    "{MATCH_CONTEXT_LINE_PREFIX}`{}` {text}",
+                    markdown_file.frontmatter_line_count + index + 1
+                );
+            }
+            result.push(NEWLINE);
+        }
+        result
+    }
+
     fn collect_target_lines(&self, target: &str) -> Vec<TargetLine> {
         let target_lower = target.to_lowercase();
 
@@ -418,7 +529,11 @@ impl ObsidianRepository {
 mod tests {
     use std::path::PathBuf;
 
+    use tempfile::TempDir;
+
     use super::*;
+    use crate::test_support as test_utils;
+    use crate::test_support::TestFileBuilder;
 
     fn target_line(file: &str, number: usize, text: &str) -> TargetLine {
         TargetLine {
@@ -493,4 +608,58 @@ mod tests {
             "uncapped tables have no sample note: {description}"
         );
     }
+
+    #[test]
+    fn test_match_contexts_show_surrounding_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_date = test_utils::eastern_midnight(2024, 1, 15);
+        for name in ["Apple", "Banana"] {
+            TestFileBuilder::new()
+                .with_aliases(vec!["fruit".to_string()])
+                .with_matching_dates(test_date)
+                .with_file_system_dates(test_date, test_date)
+                .create(&temp_dir, &format!("{name}.md"));
+        }
+        TestFileBuilder::new()
+            .with_content("one\ntwo\nI ate fruit today\nthree\nfour\n".to_string())
+            .with_matching_dates(test_date)
+            .with_file_system_dates(test_date, test_date)
+            .create(&temp_dir, "Story.md");
+
+        let validated_config = test_utils::get_test_validated_config_result(&temp_dir, |builder| {
+            builder.ambiguous_match_context_lines(Some(1));
+        })
+        .unwrap();
+        let repository = ObsidianRepository::new(&validated_config).unwrap();
+        let matches: Vec<BackPopulateMatch> = repository
+            .markdown_files
+            .iter()
+            .flat_map(|file| file.back_populate_matches.ambiguous.clone())
+            .collect();
+        assert_eq!(matches.len(), 1, "fruit names two notes");
+
+        let contexts = repository.match_contexts(&matches, 1, validated_config.obsidian_path());
+        let line_number = matches[0].line_number;
+        assert!(contexts.starts_with(&format!(
+            "{MATCH_CONTEXT_CALLOUT} {} {LINE} {line_number}",
+            "Story".to_wikilink()
+        )));
+        let quoted: Vec<&str> = contexts
+            .lines()
+            .skip(1)
+            .filter(|line| line.starts_with(MATCH_CONTEXT_LINE_PREFIX))
+            .collect();
+        assert_eq!(
+            quoted.len(),
+            3,
+            "one line either side of the match: {contexts}"
+        );
+        assert!(quoted[0].ends_with("two"));
+        assert!(
+            !quoted[1].ends_with("I ate fruit today"),
+            "the match is highlighted: {contexts}"
+        );
+        assert!(quoted[1].contains(&format!("`{line_number}`")));
+        assert!(quoted[2].ends_with("three"));
+    }
 }
@@ -1,26 +1,36 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::error::Error;
 use std::ffi::OsStr;
 use std::path::Path;
+use std::sync::Arc;
 
 use anyhow::Result as AnyhowResult;
 use anyhow::anyhow;
 
 use super::constants::BACK_POPULATE_MATCH_GROUP_EMPTY;
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::FOUND_TEXT_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
 use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_FOUND_TEXT;
 use super::constants::TABLE_HEADER_LINE;
 use super::support;
 use super::writer::ReportDefinition;
 use super::writer::ReportWriter;
+use crate::config::BackPopulateGrouping;
 use crate::constants::BACK_POPULATE;
 use crate::constants::COLON;
+use crate::constants::ESCAPED_PIPE;
 use crate::constants::FOUND;
 use crate::constants::IN;
 use crate::constants::LEVEL2;
 use crate::constants::LEVEL3;
+use crate::constants::LINKS_TO;
 use crate::constants::MATCHES;
 use crate::constants::OCCURRENCES;
+use crate::constants::PIPE;
 use crate::constants::SOURCE_TEXT;
 use crate::constants::TEXT;
 use crate::constants::WIKILINKS;
@@ -29,11 +39,13 @@ use crate::constants::WILL_REPLACE_WITH;
 use crate::description_builder::DescriptionBuilder;
 use crate::markdown_file::BackPopulateMatch;
 use crate::markdown_file::MatchContext;
+use crate::markdown_files::MarkdownFiles;
 use crate::obsidian_repository::ObsidianRepository;
 use crate::output_file_writer::ColumnAlignment;
 use crate::output_file_writer::OutputFileWriter;
 use crate::phrase::Phrase;
 use crate::validated_config::ValidatedConfig;
+use crate::wikilink;
 use crate::wikilink::ToWikilink;
 
 struct BackPopulateTable {
@@ -72,37 +84,91 @@ impl ReportDefinition for BackPopulateTable {
         items: &[Self::Item],
         _: Option<&ValidatedConfig>,
     ) -> AnyhowResult<Vec<Vec<String>>> {
-        let consolidated = consolidate_matches(items);
-        let mut table_rows = Vec::new();
+        Ok(consolidated_rows(items, self.display_text.len()))
+    }
 
-        for entry in consolidated {
-            let file_path = Path::new(&entry.file_path);
-            let file_stem = file_path.file_stem().and_then(OsStr::to_str).unwrap_or("");
-
-            for match_line in entry.lines {
-                let highlighted_line = support::highlight_matches(
-                    &match_line.text,
-                    &match_line.positions,
-                    self.display_text.len(),
-                );
-
-                let replacement = if entry.match_context == MatchContext::MarkdownTable {
-                    entry.replacement.clone()
-                } else {
-                    support::escape_pipe(&entry.replacement)
-                };
-
-                table_rows.push(vec![
-                    file_stem.to_wikilink(),
-                    match_line.number.to_string(),
-                    support::escape_pipe(&highlighted_line),
-                    match_line.positions.len().to_string(),
-                    replacement.clone(),
-                    support::escape_brackets(&replacement),
-                ]);
+    fn title(&self) -> Option<String> {
+        let stats = DescriptionBuilder::new()
+            .pluralize_with_count(Phrase::Time(self.total_occurrences))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(self.file_count))
+            .build();
+
+        let title = DescriptionBuilder::new()
+            .text(FOUND)
+            .no_space(COLON)
+            .quoted_text(&self.display_text)
+            .parenthetical_text(&stats)
+            .build();
+
+        Some(title)
+    }
+
+    fn description(&self, _: &[Self::Item]) -> String { String::new() }
+
+    fn level(&self) -> &'static str { LEVEL3 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+/// The matches that will link to one note, with the text each one matched.
+struct TargetNoteTable {
+    target:            String,
+    total_occurrences: usize,
+    file_count:        usize,
+}
+
+impl ReportDefinition for TargetNoteTable {
+    type Item = BackPopulateMatch;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_FOUND_TEXT,
+            TEXT,
+            OCCURRENCES,
+            WILL_REPLACE_WITH,
+            SOURCE_TEXT,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Center,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let mut table_rows = Vec::new();
+        for group_matches in group_by_found_text(items).into_values() {
+            let found_text = group_matches
+                .first()
+                .ok_or_else(|| anyhow!(BACK_POPULATE_MATCH_GROUP_EMPTY))?
+                .found_text
+                .clone();
+            for mut row in consolidated_rows(&group_matches, found_text.len()) {
+                row.insert(FOUND_TEXT_COLUMN_INDEX, support::escape_pipe(&found_text));
+                table_rows.push(row);
             }
         }
-
+        table_rows.sort_by(|a, b| {
+            a[FILE_COLUMN_INDEX]
+                .to_lowercase()
+                .cmp(&b[FILE_COLUMN_INDEX].to_lowercase())
+        });
         Ok(table_rows)
     }
 
@@ -114,9 +180,9 @@ impl ReportDefinition for BackPopulateTable {
             .build();
 
         let title = DescriptionBuilder::new()
-            .text(FOUND)
+            .text(LINKS_TO)
             .no_space(COLON)
-            .quoted_text(&self.display_text)
+            .text(&self.target.to_wikilink())
             .parenthetical_text(&stats)
             .build();
 
@@ -126,29 +192,35 @@ impl ReportDefinition for BackPopulateTable {
     fn description(&self, _: &[Self::Item]) -> String { String::new() }
 
     fn level(&self) -> &'static str { LEVEL3 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
 }
 
 #[derive(Debug, Clone)]
 struct ConsolidatedMatch {
-    file_path:     String,
+    file_path:     Arc<str>,
     lines:         Vec<MatchLine>,
-    replacement:   String,
+    replacement:   Arc<str>,
     match_context: MatchContext,
 }
 
 #[derive(Debug, Clone)]
 struct MatchLine {
     number:    usize,
-    text:      String,
+    text:      Arc<str>,
     positions: Vec<usize>,
 }
 
 impl ObsidianRepository {
     pub(super) fn write_back_populate_report(
         &self,
+        validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let matches = self.markdown_files.files_to_persist().unambiguous_matches();
+        let matches = files_to_persist.unambiguous_matches();
 
         output_file_writer.writeln(LEVEL2, MATCHES)?;
         let header_message = DescriptionBuilder::new()
@@ -158,8 +230,7 @@ impl ObsidianRepository {
             .build();
         output_file_writer.writeln("", &header_message)?;
 
-        let unique_files: HashSet<String> =
-            matches.iter().map(|m| m.relative_path.clone()).collect();
+        let unique_files: HashSet<&str> = matches.iter().map(|m| &*m.relative_path).collect();
 
         let header_message = DescriptionBuilder::new()
             .pluralize_with_count(Phrase::Match(matches.len()))
@@ -170,50 +241,146 @@ impl ObsidianRepository {
 
         output_file_writer.writeln("", &header_message)?;
 
-        // `matches_by_text` groups `BackPopulateMatch` values by lowercase display text.
-        let mut matches_by_text: HashMap<String, Vec<BackPopulateMatch>> = HashMap::new();
-        for match_info in matches {
-            let key = match_info.found_text.to_lowercase();
-            matches_by_text
-                .entry(key)
-                .or_default()
-                .push(match_info.clone());
+        match validated_config.back_populate_grouping() {
+            BackPopulateGrouping::FoundText => {
+                write_tables_by_found_text(&matches, output_file_writer)
+            },
+            BackPopulateGrouping::TargetNote => {
+                write_tables_by_target_note(&matches, output_file_writer)
+            },
         }
+    }
+}
 
-        // sorted_keys gives BackPopulateTable sections deterministic order.
-        let mut sorted_keys: Vec<String> = matches_by_text.keys().cloned().collect();
-        sorted_keys.sort();
+fn write_tables_by_found_text(
+    matches: &[BackPopulateMatch],
+    output_file_writer: &OutputFileWriter,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // BackPopulateTable writes one section per grouped display text.
+    for (_, group_matches) in group_by_found_text(matches) {
+        let display_text = &group_matches
+            .first()
+            .ok_or_else(|| anyhow!(BACK_POPULATE_MATCH_GROUP_EMPTY))?
+            .found_text;
+
+        let back_populate_table = BackPopulateTable {
+            display_text:      display_text.to_string(),
+            total_occurrences: group_matches.len(),
+            file_count:        file_count(&group_matches),
+        };
+
+        let report_writer = ReportWriter::new(group_matches.clone());
+        report_writer.write(&back_populate_table, output_file_writer)?;
+    }
 
-        // BackPopulateTable writes one section per grouped display text.
-        for key in sorted_keys {
-            let group_matches = &matches_by_text[&key];
-            let display_text = &group_matches
-                .first()
-                .ok_or_else(|| anyhow!(BACK_POPULATE_MATCH_GROUP_EMPTY))?
-                .found_text;
-            let total_occurrences = group_matches.len();
-            let file_paths: HashSet<String> = group_matches
-                .iter()
-                .map(|m| m.relative_path.clone())
-                .collect();
-
-            let back_populate_table = BackPopulateTable {
-                display_text: display_text.clone(),
-                total_occurrences,
-                file_count: file_paths.len(),
+    Ok(())
+}
+
+/// One section per note the matches will link to, so every text that links a note can be
+/// reviewed together when deciding whether it belongs in `do_not_back_populate`.
+fn write_tables_by_target_note(
+    matches: &[BackPopulateMatch],
+    output_file_writer: &OutputFileWriter,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    for (target, group_matches) in group_by_target_note(matches) {
+        let target_note_table = TargetNoteTable {
+            target,
+            total_occurrences: group_matches.len(),
+            file_count: file_count(&group_matches),
+        };
+
+        let report_writer = ReportWriter::new(group_matches);
+        report_writer.write(&target_note_table, output_file_writer)?;
+    }
+
+    Ok(())
+}
+
+/// Matches grouped by the note they link to, in lowercase target order - each group is
+/// written with the case of its first match.
+fn group_by_target_note(matches: &[BackPopulateMatch]) -> Vec<(String, Vec<BackPopulateMatch>)> {
+    let mut matches_by_target: BTreeMap<String, (String, Vec<BackPopulateMatch>)> = BTreeMap::new();
+    for match_info in matches {
+        let target = replacement_target(&match_info.replacement);
+        matches_by_target
+            .entry(target.to_lowercase())
+            .or_insert_with(|| (target, Vec::new()))
+            .1
+            .push(match_info.clone());
+    }
+    matches_by_target.into_values().collect()
+}
+
+/// Matches keyed by lowercase found text, in key order - each group is written with the case
+/// of its first match.
+fn group_by_found_text(matches: &[BackPopulateMatch]) -> BTreeMap<String, Vec<BackPopulateMatch>> {
+    let mut matches_by_text: BTreeMap<String, Vec<BackPopulateMatch>> = BTreeMap::new();
+    for match_info in matches {
+        matches_by_text
+            .entry(match_info.found_text.to_lowercase())
+            .or_default()
+            .push(match_info.clone());
+    }
+    matches_by_text
+}
+
+fn file_count(matches: &[BackPopulateMatch]) -> usize {
+    matches
+        .iter()
+        .map(|m| &*m.relative_path)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// The note a replacement links to - table replacements carry an escaped pipe.
+fn replacement_target(replacement: &str) -> String {
+    wikilink::extract_wikilinks(&replacement.replace(ESCAPED_PIPE, &PIPE.to_string()))
+        .valid
+        .into_iter()
+        .next()
+        .map_or_else(
+            || replacement.to_string(),
+            |spanned_wikilink| spanned_wikilink.wikilink.target,
+        )
+}
+
+fn consolidated_rows(matches: &[BackPopulateMatch], display_text_len: usize) -> Vec<Vec<String>> {
+    let mut table_rows = Vec::new();
+
+    for entry in consolidate_matches(matches) {
+        let file_path = Path::new(&*entry.file_path);
+        let file_stem = file_path.file_stem().and_then(OsStr::to_str).unwrap_or("");
+
+        for match_line in entry.lines {
+            let highlighted_line = support::highlight_matches(
+                &match_line.text,
+                &match_line.positions,
+                display_text_len,
+            );
+
+            let replacement = if entry.match_context == MatchContext::MarkdownTable {
+                entry.replacement.to_string()
+            } else {
+                support::escape_pipe(&entry.replacement)
             };
 
-            let report_writer = ReportWriter::new(group_matches.clone());
-            report_writer.write(&back_populate_table, output_file_writer)?;
+            table_rows.push(vec![
+                file_stem.to_wikilink(),
+                match_line.number.to_string(),
+                support::escape_pipe(&highlighted_line),
+                match_line.positions.len().to_string(),
+                replacement.clone(),
+                support::escape_brackets(&replacement),
+            ]);
         }
-
-        Ok(())
     }
+
+    table_rows
 }
 
 fn consolidate_matches(matches: &[BackPopulateMatch]) -> Vec<ConsolidatedMatch> {
-    let mut line_map: HashMap<(String, usize), MatchLine> = HashMap::new();
-    let mut file_info: HashMap<String, (String, MatchContext)> = HashMap::new();
+    let mut line_map: HashMap<(Arc<str>, usize), MatchLine> = HashMap::new();
+    let mut file_info: HashMap<Arc<str>, (Arc<str>, MatchContext)> = HashMap::new();
 
     for match_info in matches {
         let key = (match_info.relative_path.clone(), match_info.line_number);
@@ -228,7 +395,7 @@ fn consolidate_matches(matches: &[BackPopulateMatch]) -> Vec<ConsolidatedMatch>
         file_info.insert(
             match_info.relative_path.clone(),
             (
-                match_info.replacement.clone(),
+                Arc::clone(&match_info.replacement),
                 match_info.match_context.clone(),
             ),
         );
@@ -253,11 +420,11 @@ fn consolidate_matches(matches: &[BackPopulateMatch]) -> Vec<ConsolidatedMatch>
     }
 
     result.sort_by(|a, b| {
-        let file_a = Path::new(&a.file_path)
+        let file_a = Path::new(&*a.file_path)
             .file_stem()
             .and_then(OsStr::to_str)
             .unwrap_or("");
-        let file_b = Path::new(&b.file_path)
+        let file_b = Path::new(&*b.file_path)
             .file_stem()
             .and_then(OsStr::to_str)
             .unwrap_or("");
@@ -266,3 +433,88 @@ fn consolidate_matches(matches: &[BackPopulateMatch]) -> Vec<ConsolidatedMatch>
 
     result
 }
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use super::*;
+
+    fn back_populate_match(
+        relative_path: &str,
+        found_text: &str,
+        replacement: &str,
+    ) -> BackPopulateMatch {
+        let line_text = format!("went to {found_text} last spring");
+        BackPopulateMatch {
+            found_text:    found_text.into(),
+            match_context: MatchContext::Plaintext,
+            line_number:   3,
+            line_text:     line_text.into(),
+            position:      "went to ".len(),
+            relative_path: relative_path.into(),
+            replacement:   replacement.into(),
+        }
+    }
+
+    #[test]
+    fn test_back_populate_grouped_by_target_note() {
+        let matches = vec![
+            back_populate_match("travel/Trip.md", "Tokyo", "[[Tokyo]]"),
+            back_populate_match("History.md", "Edo", "[[Tokyo|Edo]]"),
+            back_populate_match("travel/Trip.md", "Kyoto", "[[Kyoto]]"),
+        ];
+
+        let groups = group_by_target_note(&matches);
+        let summary: Vec<(String, Vec<&str>)> = groups
+            .iter()
+            .map(|(target, group_matches)| {
+                (
+                    target.clone(),
+                    group_matches.iter().map(|m| &*m.found_text).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Kyoto".to_string(), vec!["Kyoto"]),
+                ("Tokyo".to_string(), vec!["Tokyo", "Edo"]),
+            ],
+            "one table per target note, with every text that links it"
+        );
+
+        let (target, tokyo_matches) = &groups[1];
+        let table = TargetNoteTable {
+            target:            target.clone(),
+            total_occurrences: tokyo_matches.len(),
+            file_count:        file_count(tokyo_matches),
+        };
+        assert_eq!(
+            table.title().unwrap(),
+            "links to: [[Tokyo]] (2 times in 2 files)"
+        );
+
+        let rows = table.build_rows(tokyo_matches, None).unwrap();
+        let files_and_texts: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|row| {
+                (
+                    row[FILE_COLUMN_INDEX].as_str(),
+                    row[FOUND_TEXT_COLUMN_INDEX].as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            files_and_texts,
+            vec![("[[History]]", "Edo"), ("[[Trip]]", "Tokyo")],
+            "each source keeps its own found text, sorted by file"
+        );
+        assert!(
+            rows[0].contains(&"[[Tokyo\\|Edo]]".to_string()),
+            "the alias link is shown as it will be written"
+        );
+    }
+}
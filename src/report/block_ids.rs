@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_BLOCK_ID;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_ISSUE;
+use super::constants::TABLE_HEADER_LINE;
+use super::constants::TABLE_HEADER_LINKS;
+use super::constants::TABLE_HEADER_REFERENCE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::BLOCK_ID_ISSUES;
+use crate::constants::BLOCK_ID_ISSUES_DESCRIPTION;
+use crate::constants::BLOCK_IDS;
+use crate::constants::BLOCK_IDS_DESCRIPTION;
+use crate::constants::BLOCK_REFERENCE_PREFIX;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::BlockIdIssue;
+use crate::obsidian_repository::NoteBlockId;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct BlockIdIssuesTable;
+
+impl ReportDefinition for BlockIdIssuesTable {
+    type Item = BlockIdIssue;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_REFERENCE,
+            TABLE_HEADER_ISSUE,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|issue| {
+                vec![
+                    file_stem_wikilink(&issue.path),
+                    issue.line_number.to_string(),
+                    support::escape_pipe(&support::escape_brackets(&issue.text)),
+                    issue.kind.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(BLOCK_ID_ISSUES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(items.len()))
+            .text_with_newline("")
+            .no_space(BLOCK_ID_ISSUES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+struct BlockIdsTable;
+
+impl ReportDefinition for BlockIdsTable {
+    type Item = NoteBlockId;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_BLOCK_ID,
+            TABLE_HEADER_LINKS,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|block_id| {
+                vec![
+                    file_stem_wikilink(&block_id.path),
+                    block_id.line_number.to_string(),
+                    format!("{BLOCK_REFERENCE_PREFIX}{}", block_id.id),
+                    block_id.references.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(BLOCK_IDS.to_string()) }
+
+    fn description(&self, _: &[Self::Item]) -> String { BLOCK_IDS_DESCRIPTION.to_string() }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+fn file_stem_wikilink(path: &Path) -> String {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_wikilink()
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_block_id_reports(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut issues = self.block_id_issues.clone();
+        issues.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+        ReportWriter::new(issues).write(&BlockIdIssuesTable, output_file_writer)?;
+
+        let mut block_ids = self.block_ids.clone();
+        block_ids.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+        ReportWriter::new(block_ids).write(&BlockIdsTable, output_file_writer)
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::constants::TABLE_HEADER_LINE_TEXT;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::ADMONITION;
+use crate::constants::CALLOUT;
+use crate::constants::CALLOUT_ISSUES;
+use crate::constants::CALLOUT_ISSUES_DESCRIPTION;
+use crate::constants::CONVERTED_ADMONITIONS;
+use crate::constants::CONVERTED_ADMONITIONS_DESCRIPTION;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::REASON;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::CalloutIssue;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct CalloutIssuesTable;
+
+impl ReportDefinition for CalloutIssuesTable {
+    type Item = (PathBuf, CalloutIssue);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            REASON,
+            TABLE_HEADER_LINE_TEXT,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|(path, issue)| {
+                vec![
+                    note_link(path),
+                    issue.line_number.to_string(),
+                    issue.kind.to_string(),
+                    escape_line(&issue.line_text),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(CALLOUT_ISSUES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&PathBuf> = items.iter().map(|(path, _)| path).collect();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(CALLOUT_ISSUES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+/// One row per converted block: the admonition fence and the callout header replacing it.
+struct ConvertedAdmonitionsTable;
+
+impl ReportDefinition for ConvertedAdmonitionsTable {
+    type Item = (PathBuf, usize, String, String);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            ADMONITION,
+            CALLOUT,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|(path, line_number, admonition, callout_header)| {
+                vec![
+                    note_link(path),
+                    line_number.to_string(),
+                    escape_line(admonition),
+                    escape_line(callout_header),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(CONVERTED_ADMONITIONS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&PathBuf> = items.iter().map(|(path, ..)| path).collect();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Admonition(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(CONVERTED_ADMONITIONS_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+fn note_link(path: &Path) -> String {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_wikilink()
+}
+
+fn escape_line(text: &str) -> String { support::escape_pipe(&support::escape_brackets(text)) }
+
+impl ObsidianRepository {
+    pub(super) fn write_callout_reports(
+        &self,
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let issues_writer = ReportWriter::new(self.collect_callout_issues());
+        issues_writer.write(&CalloutIssuesTable, output_file_writer)?;
+
+        let mut conversions: Vec<(PathBuf, usize, String, String)> = files_to_persist
+            .iter()
+            .flat_map(|file| {
+                file.admonition_conversions.iter().map(|conversion| {
+                    (
+                        file.path.clone(),
+                        conversion.line_number,
+                        conversion.admonition.clone(),
+                        conversion.callout_header.clone(),
+                    )
+                })
+            })
+            .collect();
+        conversions.sort();
+
+        let conversions_writer = ReportWriter::new(conversions);
+        conversions_writer.write(&ConvertedAdmonitionsTable, output_file_writer)
+    }
+}
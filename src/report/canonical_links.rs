@@ -25,6 +25,7 @@ use crate::constants::SOURCE_TEXT;
 use crate::constants::WILL_REPLACE_WITH;
 use crate::description_builder::DescriptionBuilder;
 use crate::markdown_file::CanonicalLinkMatch;
+use crate::markdown_files::MarkdownFiles;
 use crate::obsidian_repository::ObsidianRepository;
 use crate::output_file_writer::ColumnAlignment;
 use crate::output_file_writer::OutputFileWriter;
@@ -126,16 +127,18 @@ impl ReportDefinition for CanonicalLinksTable {
     }
 
     fn level(&self) -> &'static str { LEVEL2 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
 }
 
 impl ObsidianRepository {
     pub(super) fn write_canonical_links_report(
-        &self,
+        files_to_persist: &MarkdownFiles,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let matches: Vec<CanonicalLinkMatch> = self
-            .markdown_files
-            .files_to_persist()
+        let matches: Vec<CanonicalLinkMatch> = files_to_persist
             .iter()
             .flat_map(|file| file.canonical_link_matches.clone())
             .collect();
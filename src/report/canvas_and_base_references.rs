@@ -0,0 +1,90 @@
+use std::error::Error;
+
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::constants::CANVAS_AND_BASE_REFERENCES_REPORT_CONFIG_REQUIRED;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_REFERENCE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::CANVAS_AND_BASE_REFERENCES;
+use crate::constants::CANVAS_AND_BASE_REFERENCES_DESCRIPTION;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::REASON;
+use crate::constants::TYPE;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::StructuredFileIssue;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+
+struct CanvasAndBaseReferencesTable;
+
+impl ReportDefinition for CanvasAndBaseReferencesTable {
+    type Item = StructuredFileIssue;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![TABLE_HEADER_FILE_NAME, TYPE, TABLE_HEADER_REFERENCE, REASON]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        validated_config: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let obsidian_path = validated_config
+            .ok_or_else(|| anyhow!(CANVAS_AND_BASE_REFERENCES_REPORT_CONFIG_REQUIRED))?
+            .obsidian_path();
+
+        Ok(items
+            .iter()
+            .map(|issue| {
+                vec![
+                    support::format_wikilink(&issue.path, obsidian_path),
+                    issue.kind.to_string(),
+                    support::escape_pipe(&support::escape_brackets(&issue.reference)),
+                    issue.reason.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(CANVAS_AND_BASE_REFERENCES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Reference(items.len()))
+            .text_with_newline("")
+            .no_space(CANVAS_AND_BASE_REFERENCES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_canvas_and_base_references_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report = ReportWriter::new(self.structured_file_issues.clone())
+            .with_validated_config(validated_config);
+        report.write(&CanvasAndBaseReferencesTable, output_file_writer)
+    }
+}
@@ -0,0 +1,78 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_SKIPPED_CHANGES;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::CHANGED_ON_DISK_NOTES;
+use crate::constants::CHANGED_ON_DISK_NOTES_DESCRIPTION;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ChangedOnDiskNote;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct ChangedOnDiskNotesTable;
+
+impl ReportDefinition for ChangedOnDiskNotesTable {
+    type Item = ChangedOnDiskNote;
+
+    fn headers(&self) -> Vec<&str> { vec![TABLE_HEADER_FILE_NAME, TABLE_HEADER_SKIPPED_CHANGES] }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![ColumnAlignment::Left, ColumnAlignment::Left]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|changed_on_disk_note| {
+                vec![
+                    changed_on_disk_note
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    support::escape_pipe(&changed_on_disk_note.changes.join(", ")),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(CHANGED_ON_DISK_NOTES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .text_with_newline("")
+            .no_space(CHANGED_ON_DISK_NOTES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_changed_on_disk_notes_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ReportWriter::new(self.changed_on_disk_notes.clone())
+            .write(&ChangedOnDiskNotesTable, output_file_writer)
+    }
+}
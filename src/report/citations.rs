@@ -0,0 +1,104 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_CITATION_KEY;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_ISSUE;
+use super::constants::TABLE_HEADER_LINE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::CITATION_ISSUES;
+use crate::constants::CITATION_ISSUES_DESCRIPTION;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::CitationIssue;
+use crate::obsidian_repository::CitationIssueKind;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct CitationsTable;
+
+impl ReportDefinition for CitationsTable {
+    type Item = CitationIssue;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_CITATION_KEY,
+            TABLE_HEADER_ISSUE,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|issue| {
+                // The bibliography isn't a note, so it is named rather than linked.
+                let file = match issue.kind {
+                    CitationIssueKind::UnknownKey => issue
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    CitationIssueKind::UnusedEntry => issue
+                        .path
+                        .file_name()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                };
+                vec![
+                    file,
+                    issue.line_number.to_string(),
+                    support::escape_pipe(&issue.key),
+                    issue.kind.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(CITATION_ISSUES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(items.len()))
+            .text_with_newline("")
+            .no_space(CITATION_ISSUES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_citations_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ReportWriter::new(self.citation_issues.clone()).write(&CitationsTable, output_file_writer)
+    }
+}
@@ -0,0 +1,112 @@
+use std::error::Error;
+
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::constants::CONFLICT_COPIES_REPORT_CONFIG_REQUIRED;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::config::ConflictResolution;
+use crate::constants::ACTION;
+use crate::constants::BODY;
+use crate::constants::CONFLICT_COPIES;
+use crate::constants::CONFLICT_COPIES_DESCRIPTION;
+use crate::constants::CONFLICT_COPIES_FIX_DISABLED;
+use crate::constants::CONFLICT_COPY;
+use crate::constants::DIFFERS;
+use crate::constants::FOUND;
+use crate::constants::IDENTICAL;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ConflictCopy;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+
+struct ConflictCopiesTable {
+    resolution: Option<ConflictResolution>,
+}
+
+impl ReportDefinition for ConflictCopiesTable {
+    type Item = ConflictCopy;
+
+    fn headers(&self) -> Vec<&str> { vec![TABLE_HEADER_FILE_NAME, CONFLICT_COPY, BODY, ACTION] }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        validated_config: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let obsidian_path = validated_config
+            .ok_or_else(|| anyhow!(CONFLICT_COPIES_REPORT_CONFIG_REQUIRED))?
+            .obsidian_path();
+
+        Ok(items
+            .iter()
+            .map(|conflict_copy| {
+                vec![
+                    support::format_wikilink(&conflict_copy.original, obsidian_path),
+                    support::format_wikilink(&conflict_copy.copy, obsidian_path),
+                    if conflict_copy.identical {
+                        IDENTICAL
+                    } else {
+                        DIFFERS
+                    }
+                    .to_string(),
+                    conflict_copy.action.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(CONFLICT_COPIES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let description = DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::ConflictCopy(items.len()))
+            .text_with_newline("")
+            .no_space(CONFLICT_COPIES_DESCRIPTION);
+
+        if self.resolution.is_some() {
+            description.build()
+        } else {
+            description
+                .text_with_newline("")
+                .no_space(CONFLICT_COPIES_FIX_DISABLED)
+                .build()
+        }
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_conflict_copies_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report =
+            ReportWriter::new(self.conflict_copies.clone()).with_validated_config(validated_config);
+        report.write(
+            &ConflictCopiesTable {
+                resolution: validated_config.resolve_conflict_copies(),
+            },
+            output_file_writer,
+        )
+    }
+}
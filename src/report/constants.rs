@@ -1,3 +1,12 @@
+// deep links
+/// Obsidian's own `open` URI can't go to a line, so deep links need the Advanced URI plugin.
+pub(super) const DEEP_LINK_PREFIX: &str = "obsidian://advanced-uri?vault=";
+pub(super) const DEEP_LINK_FILENAME_PARAMETER: &str = "&filename=";
+pub(super) const DEEP_LINK_LINE_PARAMETER: &str = "&line=";
+pub(super) const DEEP_LINK_TEXT: &str = "open";
+/// Characters a URI query value carries as is - everything else is percent-encoded.
+pub(super) const URI_UNRESERVED_PUNCTUATION: [char; 4] = ['-', '.', '_', '~'];
+
 // highlighting
 pub(super) const HIGHLIGHT_CLOSE_TAG: &str = "</span>";
 pub(super) const HIGHLIGHT_EXTRA_TAG_CAPACITY_MULTIPLIER: usize = 2;
@@ -8,9 +17,17 @@ pub(super) const AMBIGUOUS_MATCH_GROUP_EMPTY: &str =
     "ambiguous match group must contain at least one match";
 pub(super) const BACK_POPULATE_MATCH_GROUP_EMPTY: &str =
     "back-populate match group must contain at least one match";
+pub(super) const CANVAS_AND_BASE_REFERENCES_REPORT_CONFIG_REQUIRED: &str =
+    "ValidatedConfig required for canvas-and-base-references report";
+pub(super) const CONFLICT_COPIES_REPORT_CONFIG_REQUIRED: &str =
+    "ValidatedConfig required for conflict-copies report";
 pub(super) const DUPLICATE_IMAGES_REPORT_CONFIG_REQUIRED: &str =
     "ValidatedConfig required for duplicate-images report";
 pub(super) const FILES_TO_BE_UPDATED: &str = "files to be updated";
+pub(super) const HEAVIEST_NOTES_REPORT_CONFIG_REQUIRED: &str =
+    "ValidatedConfig required for heaviest-notes report";
+pub(super) const HIDDEN_FOLDERS_REPORT_CONFIG_REQUIRED: &str =
+    "ValidatedConfig required for hidden-folders report";
 pub(super) const INCOMPATIBLE_IMAGES_REPORT_CONFIG_REQUIRED: &str =
     "ValidatedConfig required for incompatible-images report";
 pub(super) const INCOMPATIBLE_IMAGES_REPORT_INVARIANT: &str =
@@ -19,22 +36,85 @@ pub(super) const INVALID_UTF8_BOUNDARY_DETECTED: &str =
     "Invalid UTF-8 boundary detected at position";
 pub(super) const MISSING_REFERENCES_REPORT_CONFIG_REQUIRED: &str =
     "ValidatedConfig required for missing-references report";
+pub(super) const UNSAFE_PATHS_REPORT_CONFIG_REQUIRED: &str =
+    "ValidatedConfig required for unsafe-paths report";
+
+// percentages
+pub(super) const PERCENT_MULTIPLIER: f64 = 100.0;
 
 // sampling
+/// Notes shown in the heaviest notes table.
+pub(super) const HEAVIEST_NOTES_SHOWN: usize = 20;
 /// Cap on rows shown per "references to" table in the ambiguous-matches report.
 pub(super) const TARGET_REFERENCE_SAMPLE_MAX: usize = 10;
 
+// sizes
+pub(super) const BYTES_PER_KILOBYTE: u64 = 1024;
+pub(super) const SIZE_UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+// sparkline
+pub(super) const SPARKLINE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+pub(super) const VAULT_HEALTH_MAX_SCORE: usize = 100;
+/// Days of vault health history shown in the trend table.
+pub(super) const VAULT_HEALTH_TREND_ENTRIES: usize = 14;
+
 // table columns
 pub(super) const FILE_COLUMN_INDEX: usize = 0;
+pub(super) const FOUND_TEXT_COLUMN_INDEX: usize = 2;
 pub(super) const IMAGE_PATH_COLUMN_INDEX: usize = 1;
 pub(super) const LINE_NUMBER_COLUMN_INDEX: usize = 1;
+pub(super) const RARE_WORD_LINE_COLUMN_INDEX: usize = 2;
 pub(super) const TEXT_COLUMN_INDEX: usize = 2;
 pub(super) const UNPARSABLE_LINE_NUMBER_SORT_KEY: usize = 0;
 
 // table headers
+pub(super) const TABLE_HEADER_ACRONYM: &str = "acronym";
+pub(super) const TABLE_HEADER_ALIAS: &str = "alias";
+pub(super) const TABLE_HEADER_APPLIED: &str = "applied";
+pub(super) const TABLE_HEADER_BLOCK_ID: &str = "block id";
+pub(super) const TABLE_HEADER_CHARACTER: &str = "character";
+pub(super) const TABLE_HEADER_CITATION_KEY: &str = "citation key";
+pub(super) const TABLE_HEADER_COUNT: &str = "count";
+pub(super) const TABLE_HEADER_DATE_CREATED: &str = "date_created";
+pub(super) const TABLE_HEADER_DATE_MODIFIED: &str = "date_modified";
+pub(super) const TABLE_HEADER_DAYS_OVERDUE: &str = "days overdue";
+pub(super) const TABLE_HEADER_DETECTOR: &str = "detector";
+pub(super) const TABLE_HEADER_DUE: &str = "due";
 pub(super) const TABLE_HEADER_ERROR_MESSAGE: &str = "error message";
+pub(super) const TABLE_HEADER_EXTENSION: &str = "extension";
+pub(super) const TABLE_HEADER_FILE_CREATED: &str = "file created";
 pub(super) const TABLE_HEADER_FILE_NAME: &str = "file name";
+pub(super) const TABLE_HEADER_FIX: &str = "fix";
+pub(super) const TABLE_HEADER_FOLDER: &str = "folder";
+pub(super) const TABLE_HEADER_FOUND_IN: &str = "found in";
+pub(super) const TABLE_HEADER_HELD: &str = "held";
+pub(super) const TABLE_HEADER_FOUND_TEXT: &str = "found text";
 pub(super) const TABLE_HEADER_INVALID_REASON: &str = "invalid reason";
+pub(super) const TABLE_HEADER_ISSUE: &str = "issue";
+pub(super) const TABLE_HEADER_ISSUES: &str = "issues";
 pub(super) const TABLE_HEADER_LINE: &str = "line";
 pub(super) const TABLE_HEADER_LINE_TEXT: &str = "line text";
+pub(super) const TABLE_HEADER_LINES: &str = "lines";
+pub(super) const TABLE_HEADER_LINKS: &str = "links";
+pub(super) const TABLE_HEADER_MATCHES: &str = "matches";
+pub(super) const TABLE_HEADER_MINUTES_AGO: &str = "minutes ago";
+pub(super) const TABLE_HEADER_NOTES: &str = "notes";
+pub(super) const TABLE_HEADER_OPEN: &str = "open";
+pub(super) const TABLE_HEADER_OWNER: &str = "owner";
+pub(super) const TABLE_HEADER_PATTERN: &str = "pattern";
+pub(super) const TABLE_HEADER_PREVIEW: &str = "preview";
+pub(super) const TABLE_HEADER_PROPERTY: &str = "property";
+pub(super) const TABLE_HEADER_REFERENCE: &str = "reference";
+pub(super) const TABLE_HEADER_RULE: &str = "rule";
+pub(super) const TABLE_HEADER_SKIPPED: &str = "skipped";
+pub(super) const TABLE_HEADER_SKIPPED_CHANGES: &str = "skipped changes";
 pub(super) const TABLE_HEADER_SOURCE_TEXT: &str = "source text";
+pub(super) const TABLE_HEADER_TOTAL_SIZE: &str = "total size";
+pub(super) const TABLE_HEADER_VALUE: &str = "value";
+pub(super) const TABLE_HEADER_VARIANT: &str = "variant";
+pub(super) const TABLE_HEADER_WITHHELD: &str = "withheld";
+
+// tasks
+/// Obsidian writes a checked task as `- [x]`; `- [X]` is read as checked too.
+pub(super) const TASK_DONE_PREFIXES: [&str; 2] = ["- [x] ", "- [X] "];
+pub(super) const TASK_OPEN_PREFIX: &str = "- [ ] ";
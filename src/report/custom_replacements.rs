@@ -0,0 +1,45 @@
+use std::error::Error;
+
+use super::match_type_table::MatchTypeTable;
+use super::match_type_table::found_in_files;
+use super::match_type_table::group_by_match_type;
+use super::writer::ReportWriter;
+use crate::constants::CUSTOM_REPLACEMENTS;
+use crate::constants::CUSTOM_REPLACEMENTS_DESCRIPTION;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::CustomReplacementMatch;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::OutputFileWriter;
+
+impl ObsidianRepository {
+    /// Writes a summary of every custom replacement, then a `MatchTypeTable` per source - a new
+    /// replacement source shows up with its own table and count without a change here.
+    pub(super) fn write_custom_replacements_report(
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let matches: Vec<CustomReplacementMatch> = files_to_persist
+            .iter()
+            .flat_map(|file| file.custom_replacement_matches.clone())
+            .collect();
+        if matches.is_empty() {
+            return Ok(());
+        }
+
+        output_file_writer.writeln(LEVEL1, CUSTOM_REPLACEMENTS)?;
+        let description = DescriptionBuilder::new()
+            .no_space(&found_in_files(&matches))
+            .text_with_newline("")
+            .no_space(CUSTOM_REPLACEMENTS_DESCRIPTION)
+            .build();
+        output_file_writer.writeln("", &description)?;
+
+        for (match_type, matches) in group_by_match_type(matches) {
+            ReportWriter::new(matches)
+                .write(&MatchTypeTable::new(match_type), output_file_writer)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use std::error::Error;
+
+use super::match_type_table::MatchTypeTable;
+use super::match_type_table::group_by_match_type;
+use super::writer::ReportWriter;
+use crate::markdown_file::DateMentionMatch;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::OutputFileWriter;
+
+impl ObsidianRepository {
+    pub(super) fn write_date_mentions_report(
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let matches: Vec<DateMentionMatch> = files_to_persist
+            .iter()
+            .flat_map(|file| file.date_mention_matches.clone())
+            .collect();
+        for (match_type, matches) in group_by_match_type(matches) {
+            ReportWriter::new(matches)
+                .write(&MatchTypeTable::new(match_type), output_file_writer)?;
+        }
+        Ok(())
+    }
+}
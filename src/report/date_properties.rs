@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_FIX;
+use super::constants::TABLE_HEADER_ISSUE;
+use super::constants::TABLE_HEADER_PROPERTY;
+use super::constants::TABLE_HEADER_VALUE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::DATE_PROPERTY_ISSUES;
+use crate::constants::DATE_PROPERTY_ISSUES_DESCRIPTION;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::DatePropertyValidation;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct DatePropertiesTable;
+
+impl ReportDefinition for DatePropertiesTable {
+    type Item = (PathBuf, DatePropertyValidation);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_PROPERTY,
+            TABLE_HEADER_VALUE,
+            TABLE_HEADER_ISSUE,
+            TABLE_HEADER_FIX,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|(path, validation)| {
+                vec![
+                    path.file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    validation.property.clone(),
+                    support::escape_pipe(&support::escape_brackets(&validation.frontmatter)),
+                    validation.issue.to_string(),
+                    validation.fixed.clone().unwrap_or_default(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(DATE_PROPERTY_ISSUES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&PathBuf> = items.iter().map(|(path, _)| path).collect();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(DATE_PROPERTY_ISSUES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_date_properties_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let validations: Vec<(PathBuf, DatePropertyValidation)> = self
+            .markdown_files
+            .iter()
+            .flat_map(|markdown_file| {
+                markdown_file
+                    .date_property_validations
+                    .iter()
+                    .map(|validation| (markdown_file.path.clone(), validation.clone()))
+            })
+            .collect();
+
+        let report_writer = ReportWriter::new(validations);
+        report_writer.write(&DatePropertiesTable, output_file_writer)
+    }
+}
@@ -34,4 +34,8 @@ pub(super) trait ReportDefinition<C = ()> {
 
     /// markdown level
     fn level(&self) -> &'static str;
+
+    /// The file name and line columns a deep link to the offending line is built from, when
+    /// `report_deep_links` is on. Reports without a line column keep the default.
+    fn deep_link_columns(&self) -> Option<(usize, usize)> { None }
 }
@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use serde_yaml::Value;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_PROPERTY;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::config::DuplicateKeyResolution;
+use crate::constants::DUPLICATE_FRONTMATTER_KEYS;
+use crate::constants::DUPLICATE_FRONTMATTER_KEYS_DESCRIPTION;
+use crate::constants::DUPLICATE_FRONTMATTER_KEYS_FIX_DISABLED;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::KEPT;
+use crate::constants::LEVEL1;
+use crate::constants::LINES;
+use crate::constants::VALUES;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::DuplicateFrontmatterKey;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct DuplicateFrontmatterKeysTable {
+    resolution: Option<DuplicateKeyResolution>,
+}
+
+impl ReportDefinition for DuplicateFrontmatterKeysTable {
+    type Item = (PathBuf, DuplicateFrontmatterKey);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_PROPERTY,
+            LINES,
+            VALUES,
+            KEPT,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|(path, duplicate)| {
+                // Without a fix the YAML parser keeps the last occurrence.
+                let kept = self.resolution.map_or_else(
+                    || duplicate.values.last().cloned().unwrap_or(Value::Null),
+                    |resolution| duplicate.resolved_value(resolution),
+                );
+
+                vec![
+                    path.file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    duplicate.key.clone(),
+                    duplicate
+                        .line_numbers
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    duplicate
+                        .values
+                        .iter()
+                        .map(support::format_yaml_value)
+                        .collect::<Vec<_>>()
+                        .join("<br>"),
+                    support::format_yaml_value(&kept),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(DUPLICATE_FRONTMATTER_KEYS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&PathBuf> = items.iter().map(|(path, _)| path).collect();
+
+        let description = DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Property(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(DUPLICATE_FRONTMATTER_KEYS_DESCRIPTION);
+
+        if self.resolution.is_some() {
+            description.build()
+        } else {
+            description
+                .text_with_newline("")
+                .no_space(DUPLICATE_FRONTMATTER_KEYS_FIX_DISABLED)
+                .build()
+        }
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_duplicate_frontmatter_keys_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let duplicates: Vec<(PathBuf, DuplicateFrontmatterKey)> = self
+            .markdown_files
+            .iter()
+            .flat_map(|markdown_file| {
+                markdown_file
+                    .duplicate_frontmatter_keys
+                    .iter()
+                    .map(|duplicate| (markdown_file.path.clone(), duplicate.clone()))
+            })
+            .collect();
+
+        let report_writer = ReportWriter::new(duplicates);
+        report_writer.write(
+            &DuplicateFrontmatterKeysTable {
+                resolution: validated_config.fix_duplicate_frontmatter_keys(),
+            },
+            output_file_writer,
+        )
+    }
+}
@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::constants::TABLE_HEADER_LINES;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::ENCRYPTED_BLOCKS;
+use crate::constants::ENCRYPTED_BLOCKS_DESCRIPTION;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::EncryptedNoteBlock;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct EncryptedBlocksTable;
+
+impl ReportDefinition for EncryptedBlocksTable {
+    type Item = EncryptedNoteBlock;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_LINES,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|encrypted| {
+                vec![
+                    encrypted
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    encrypted.block.start_line.to_string(),
+                    (encrypted.block.end_line - encrypted.block.start_line + 1).to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(ENCRYPTED_BLOCKS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::EncryptedBlock(items.len()))
+            .text_with_newline("")
+            .no_space(ENCRYPTED_BLOCKS_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_encrypted_blocks_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ReportWriter::new(self.encrypted_blocks.clone())
+            .write(&EncryptedBlocksTable, output_file_writer)
+    }
+}
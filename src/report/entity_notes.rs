@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_FIX;
+use super::constants::TABLE_HEADER_FOLDER;
+use super::constants::TABLE_HEADER_ISSUE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::ENTITY_NOTES;
+use crate::constants::ENTITY_NOTES_DESCRIPTION;
+use crate::constants::ENTITY_NOTES_FIX_DISABLED;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::EntityNoteIssue;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct EntityNotesTable {
+    fix_enabled: bool,
+}
+
+impl ReportDefinition for EntityNotesTable {
+    type Item = EntityNoteIssue;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_FOLDER,
+            TABLE_HEADER_ISSUE,
+            TABLE_HEADER_FIX,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|issue| {
+                vec![
+                    issue
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    issue.folder.display().to_string(),
+                    support::escape_pipe(&issue.kind.to_string()),
+                    issue.fix.clone().unwrap_or_default(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(ENTITY_NOTES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let description = DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(items.len()))
+            .text_with_newline("")
+            .no_space(ENTITY_NOTES_DESCRIPTION);
+
+        if self.fix_enabled {
+            description.build()
+        } else {
+            description
+                .text_with_newline("")
+                .no_space(ENTITY_NOTES_FIX_DISABLED)
+                .build()
+        }
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_entity_notes_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.entity_note_issues.clone());
+        report_writer.write(
+            &EntityNotesTable {
+                fix_enabled: validated_config.fix_entity_notes(),
+            },
+            output_file_writer,
+        )
+    }
+}
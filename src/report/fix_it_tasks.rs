@@ -0,0 +1,322 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use super::constants::DEEP_LINK_TEXT;
+use super::constants::TASK_DONE_PREFIXES;
+use super::constants::TASK_OPEN_PREFIX;
+use super::support;
+use crate::constants::AMBIGUOUS_MATCH_TASK;
+use crate::constants::CHANGE_TYPE_TASK;
+use crate::constants::COLON;
+use crate::constants::COMMA;
+use crate::constants::CONFLICT_COPIES;
+use crate::constants::CONFLICT_COPY_TASK;
+use crate::constants::DASH;
+use crate::constants::FIRST_IN;
+use crate::constants::FIX_FRONTMATTER_TASK;
+use crate::constants::FIX_IT_TASKS;
+use crate::constants::FIX_IT_TASKS_DESCRIPTION;
+use crate::constants::FIX_IT_TASKS_FILE;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::LEVEL2;
+use crate::constants::MATCHES_AMBIGUOUS;
+use crate::constants::MOST_COMMON_TYPE;
+use crate::constants::NEWLINE;
+use crate::constants::NOTHING_TO_FIX;
+use crate::constants::OF;
+use crate::constants::PROPERTY_ERRORS;
+use crate::constants::TIE;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::BackPopulateMatch;
+use crate::obsidian_repository::ConflictCopyAction;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+/// Frontmatter issues have no line of their own, so their deep links open the note's first.
+const FIRST_LINE: usize = 1;
+
+/// One issue to resolve by hand. `text` names it the same way from run to run, so a task
+/// checked off in the last note is still checked in the next; `deep_link` moves with its line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FixItTask {
+    text:      String,
+    deep_link: String,
+}
+
+impl FixItTask {
+    /// `note` is the wikilink the task is about, and `line` the line its deep link opens.
+    fn new(text: String, note: &str, line: usize, deep_link_vault: Option<&str>) -> Self {
+        let deep_link = deep_link_vault
+            .map(|vault| support::deep_link(vault, note, &line.to_string()))
+            .unwrap_or_default();
+        Self { text, deep_link }
+    }
+
+    fn to_line(&self, done: &HashSet<String>) -> String {
+        let prefix = if done.contains(&self.text) {
+            TASK_DONE_PREFIXES[0]
+        } else {
+            TASK_OPEN_PREFIX
+        };
+        if self.deep_link.is_empty() {
+            format!("{prefix}{}", self.text)
+        } else {
+            format!("{prefix}{} {}", self.text, self.deep_link)
+        }
+    }
+}
+
+/// The text of every task checked off in `note`, without its deep link.
+fn done_tasks(note: &str) -> HashSet<String> {
+    let deep_link_start = format!(" [{DEEP_LINK_TEXT}](");
+    note.lines()
+        .filter_map(|line| {
+            TASK_DONE_PREFIXES
+                .iter()
+                .find_map(|prefix| line.strip_prefix(prefix))
+        })
+        .map(|task| {
+            task.split_once(&deep_link_start)
+                .map_or(task, |(text, _)| text)
+                .to_string()
+        })
+        .collect()
+}
+
+fn note_wikilink(path: &Path) -> String {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_wikilink()
+}
+
+impl ObsidianRepository {
+    /// Writes a task list note beside the report with one task per issue ok leaves for you:
+    /// each ambiguous match group, each note whose frontmatter doesn't parse or holds a
+    /// property type that isn't converted, and each conflict copy that isn't removed. Deep
+    /// links are added when `report_deep_links` is set.
+    pub(super) fn write_fix_it_tasks(
+        &self,
+        validated_config: &ValidatedConfig,
+        deep_link_vault: Option<&str>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let note_path = validated_config.output_folder().join(FIX_IT_TASKS_FILE);
+        let done = fs::read_to_string(&note_path)
+            .map(|note| done_tasks(&note))
+            .unwrap_or_default();
+
+        let sections = [
+            (
+                MATCHES_AMBIGUOUS,
+                self.ambiguous_match_tasks(deep_link_vault),
+            ),
+            (
+                PROPERTY_ERRORS,
+                self.property_error_tasks(validated_config, deep_link_vault),
+            ),
+            (CONFLICT_COPIES, self.conflict_copy_tasks(deep_link_vault)),
+        ];
+
+        let mut note =
+            format!("{LEVEL1} {FIX_IT_TASKS}{NEWLINE}{FIX_IT_TASKS_DESCRIPTION}{NEWLINE}");
+        if sections.iter().all(|(_, tasks)| tasks.is_empty()) {
+            let _ = write!(note, "{NEWLINE}{NOTHING_TO_FIX}{NEWLINE}");
+        }
+        for (title, tasks) in sections.iter().filter(|(_, tasks)| !tasks.is_empty()) {
+            let _ = write!(note, "{NEWLINE}{LEVEL2} {title}{NEWLINE}");
+            for task in tasks {
+                note.push_str(&task.to_line(&done));
+                note.push(NEWLINE);
+            }
+        }
+
+        fs::write(note_path, note)?;
+        Ok(())
+    }
+
+    /// One task per ambiguous text, grouped as the ambiguous matches report groups them.
+    fn ambiguous_match_tasks(&self, deep_link_vault: Option<&str>) -> Vec<FixItTask> {
+        let mut matches_by_text: BTreeMap<String, Vec<&BackPopulateMatch>> = BTreeMap::new();
+        for markdown_file in &self.markdown_files {
+            for match_info in &markdown_file.back_populate_matches.ambiguous {
+                matches_by_text
+                    .entry(match_info.found_text.to_lowercase())
+                    .or_default()
+                    .push(match_info);
+            }
+        }
+
+        matches_by_text
+            .values()
+            .filter_map(|matches| {
+                let first = matches.first()?;
+                let note = note_wikilink(Path::new(&*first.relative_path));
+                let text = DescriptionBuilder::new()
+                    .text(AMBIGUOUS_MATCH_TASK)
+                    .quoted_text(&first.found_text)
+                    .text(DASH)
+                    .pluralize_with_count(Phrase::Match(matches.len()))
+                    .no_space(COMMA)
+                    .text(FIRST_IN)
+                    .text(&note)
+                    .build();
+                Some(FixItTask::new(
+                    text,
+                    &note,
+                    first.line_number,
+                    deep_link_vault,
+                ))
+            })
+            .collect()
+    }
+
+    /// Frontmatter that doesn't parse, then property values of an odd type that
+    /// `coerce_property_types` won't convert.
+    fn property_error_tasks(
+        &self,
+        validated_config: &ValidatedConfig,
+        deep_link_vault: Option<&str>,
+    ) -> Vec<FixItTask> {
+        let frontmatter_tasks = self.markdown_files.iter().filter_map(|markdown_file| {
+            let error = markdown_file.frontmatter_error.as_ref()?;
+            let note = note_wikilink(&markdown_file.path);
+            let error = error.to_string();
+            let text = DescriptionBuilder::new()
+                .text(FIX_FRONTMATTER_TASK)
+                .text(&note)
+                .text(DASH)
+                .text(error.lines().next().unwrap_or_default())
+                .build();
+            Some(FixItTask::new(text, &note, FIRST_LINE, deep_link_vault))
+        });
+
+        let mut mismatches: Vec<_> = self
+            .property_type_mismatches
+            .iter()
+            .filter(|mismatch| {
+                !(validated_config.coerce_property_types() && mismatch.coerced.is_some())
+            })
+            .collect();
+        mismatches.sort_by(|a, b| a.property.cmp(&b.property));
+        let property_type_tasks = mismatches.into_iter().map(|mismatch| {
+            let note = note_wikilink(&mismatch.file_path);
+            let majority = mismatch
+                .majority
+                .map_or_else(|| TIE.to_string(), |majority| majority.to_string());
+            let text = DescriptionBuilder::new()
+                .text(CHANGE_TYPE_TASK)
+                .text(&format!("`{}`", mismatch.property))
+                .text(IN)
+                .text(&note)
+                .no_space(COLON)
+                .text(&mismatch.property_type.to_string())
+                .no_space(COMMA)
+                .text(MOST_COMMON_TYPE)
+                .text(&majority)
+                .build();
+            FixItTask::new(text, &note, FIRST_LINE, deep_link_vault)
+        });
+
+        frontmatter_tasks.chain(property_type_tasks).collect()
+    }
+
+    /// Conflict copies that `resolve_conflict_copies` leaves in place.
+    fn conflict_copy_tasks(&self, deep_link_vault: Option<&str>) -> Vec<FixItTask> {
+        self.conflict_copies
+            .iter()
+            .filter(|conflict_copy| {
+                matches!(
+                    conflict_copy.action,
+                    ConflictCopyAction::Keep | ConflictCopyAction::Linked
+                )
+            })
+            .map(|conflict_copy| {
+                let copy = note_wikilink(&conflict_copy.copy);
+                let text = DescriptionBuilder::new()
+                    .text(CONFLICT_COPY_TASK)
+                    .text(&copy)
+                    .text(OF)
+                    .text(&note_wikilink(&conflict_copy.original))
+                    .build();
+                FixItTask::new(text, &copy, FIRST_LINE, deep_link_vault)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::constants::FIX_IT_TASKS_FILE;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_fix_it_tasks_keep_checked_tasks_checked() {
+        let temp_dir = TempDir::new().unwrap();
+        for (file_name, content) in [
+            ("Plan.md", "first idea"),
+            ("Plan (conflicted copy 2024-05-02).md", "second idea"),
+        ] {
+            TestFileBuilder::new()
+                .with_content(content.to_string())
+                .create(&temp_dir, file_name);
+        }
+        fs::write(
+            temp_dir.path().join("Broken.md"),
+            "---\ntitle: [unclosed\n---\nbody",
+        )
+        .unwrap();
+
+        let validated_config = test_support::get_test_validated_config(&temp_dir, None);
+        fs::create_dir_all(validated_config.output_folder()).unwrap();
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        let note_path = validated_config.output_folder().join(FIX_IT_TASKS_FILE);
+
+        obsidian_repository
+            .write_fix_it_tasks(&validated_config, Some("vault"))
+            .unwrap();
+        let note = fs::read_to_string(&note_path).unwrap();
+        let conflict_task = "- [ ] resolve the conflict copy [[Plan (conflicted copy 2024-05-02)]] \
+             of [[Plan]] [open](obsidian://advanced-uri?vault=vault\
+             &filename=Plan%20%28conflicted%20copy%202024-05-02%29&line=1)";
+        assert!(note.contains(conflict_task), "{note}");
+        assert!(
+            note.contains("- [ ] fix the frontmatter of [[Broken]] - "),
+            "{note}"
+        );
+
+        fs::write(
+            &note_path,
+            note.replace(conflict_task, &conflict_task.replace("- [ ]", "- [x]")),
+        )
+        .unwrap();
+        obsidian_repository
+            .write_fix_it_tasks(&validated_config, None)
+            .unwrap();
+        let note = fs::read_to_string(&note_path).unwrap();
+        assert!(
+            note.contains(
+                "- [x] resolve the conflict copy [[Plan (conflicted copy 2024-05-02)]] of [[Plan]]\n"
+            ),
+            "checked off in the last note: {note}"
+        );
+        assert!(note.contains("- [ ] fix the frontmatter of [[Broken]] - "));
+    }
+}
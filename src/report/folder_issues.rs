@@ -0,0 +1,301 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Component;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILES_TO_BE_UPDATED;
+use super::constants::TABLE_HEADER_FOLDER;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::BROKEN_LINKS;
+use crate::constants::DATE_ISSUES;
+use crate::constants::FRONTMATTER_ERRORS;
+use crate::constants::FRONTMATTER_ISSUES;
+use crate::constants::HASH;
+use crate::constants::IMAGE_ISSUES;
+use crate::constants::IMAGES;
+use crate::constants::ISSUES_BY_FOLDER;
+use crate::constants::ISSUES_BY_FOLDER_DESCRIPTION;
+use crate::constants::LEVEL1;
+use crate::constants::UNRESOLVED_LINKS;
+use crate::constants::VAULT_ROOT;
+use crate::image_file::ImageFileState;
+use crate::markdown_file::ImageLinkState;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::support::VecEnumFilter;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+/// The issues found in one top-level folder, counted the way the section each column links to
+/// lists them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct FolderIssues {
+    folder:             String,
+    /// Wikilinks to notes that don't exist - the unresolved links section.
+    broken_links:       usize,
+    /// `date_created`, `date_modified` and `date_properties` values that are missing or wrong.
+    date_issues:        usize,
+    /// Links to missing images in notes, and unreferenced, duplicate or incompatible images.
+    image_issues:       usize,
+    frontmatter_errors: usize,
+}
+
+impl FolderIssues {
+    const fn total(&self) -> usize {
+        self.broken_links + self.date_issues + self.image_issues + self.frontmatter_errors
+    }
+}
+
+struct FolderIssuesTable;
+
+impl ReportDefinition for FolderIssuesTable {
+    type Item = FolderIssues;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FOLDER,
+            BROKEN_LINKS,
+            DATE_ISSUES,
+            IMAGE_ISSUES,
+            FRONTMATTER_ERRORS,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let mut items = items.to_vec();
+        items.sort_by(|a, b| {
+            Reverse(a.total())
+                .cmp(&Reverse(b.total()))
+                .then_with(|| a.folder.cmp(&b.folder))
+        });
+
+        Ok(items
+            .iter()
+            .map(|folder_issues| {
+                vec![
+                    support::escape_pipe(&folder_issues.folder),
+                    section_link(folder_issues.broken_links, UNRESOLVED_LINKS),
+                    section_link(folder_issues.date_issues, FILES_TO_BE_UPDATED),
+                    section_link(folder_issues.image_issues, IMAGES),
+                    section_link(folder_issues.frontmatter_errors, FRONTMATTER_ISSUES),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(ISSUES_BY_FOLDER.to_string()) }
+
+    fn description(&self, _: &[Self::Item]) -> String { ISSUES_BY_FOLDER_DESCRIPTION.to_string() }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+/// `count` as a link to the `section` heading of the report - a zero has nothing to link to.
+fn section_link(count: usize, section: &str) -> String {
+    if count == 0 {
+        return count.to_string();
+    }
+    support::escape_pipe(&format!("{HASH}{section}").to_aliased_wikilink(&count.to_string()))
+}
+
+/// The first folder under `obsidian_path` that `path` is in, or `VAULT_ROOT` for a file at the
+/// top of the vault.
+fn top_level_folder(path: &Path, obsidian_path: &Path) -> String {
+    let relative_path = path.strip_prefix(obsidian_path).unwrap_or(path);
+    let mut components = relative_path.components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(folder)), Some(_)) => folder.to_string_lossy().into_owned(),
+        _ => VAULT_ROOT.to_string(),
+    }
+}
+
+/// The counts for the top-level folder `path` is in, added at zero the first time it's seen.
+fn folder_entry<'a>(
+    folders: &'a mut BTreeMap<String, FolderIssues>,
+    obsidian_path: &Path,
+    path: &Path,
+) -> &'a mut FolderIssues {
+    let folder = top_level_folder(path, obsidian_path);
+    folders
+        .entry(folder.clone())
+        .or_insert_with(|| FolderIssues {
+            folder,
+            ..FolderIssues::default()
+        })
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_folder_issues_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer =
+            ReportWriter::new(self.collect_folder_issues(validated_config, files_to_persist));
+        report_writer.write(&FolderIssuesTable, output_file_writer)
+    }
+
+    /// One `FolderIssues` per top-level folder with at least one issue. Links to missing
+    /// images are counted in `files_to_persist`, as the images section lists them.
+    fn collect_folder_issues(
+        &self,
+        validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
+    ) -> Vec<FolderIssues> {
+        let obsidian_path = validated_config.obsidian_path();
+        let mut folders: BTreeMap<String, FolderIssues> = BTreeMap::new();
+
+        for unresolved_link in self.collect_unresolved_links(validated_config.date_link_format()) {
+            folder_entry(&mut folders, obsidian_path, &unresolved_link.file_path).broken_links += 1;
+        }
+
+        for markdown_file in &self.markdown_files {
+            let date_issues = usize::from(markdown_file.created_date_validation.issue.is_some())
+                + usize::from(markdown_file.modified_date_validation.issue.is_some())
+                + markdown_file.date_property_validations.len();
+            if date_issues > 0 {
+                folder_entry(&mut folders, obsidian_path, &markdown_file.path).date_issues +=
+                    date_issues;
+            }
+            if markdown_file.frontmatter_error.is_some() {
+                folder_entry(&mut folders, obsidian_path, &markdown_file.path)
+                    .frontmatter_errors += 1;
+            }
+        }
+
+        for markdown_file in files_to_persist {
+            let missing_images = markdown_file
+                .image_links
+                .filter_by_variant(ImageLinkState::Missing)
+                .len();
+            if missing_images > 0 {
+                folder_entry(&mut folders, obsidian_path, &markdown_file.path).image_issues +=
+                    missing_images;
+            }
+        }
+
+        for image in &self.image_files.images {
+            if matches!(
+                image.state,
+                ImageFileState::Unreferenced
+                    | ImageFileState::Duplicate { .. }
+                    | ImageFileState::Incompatible { .. }
+            ) {
+                folder_entry(&mut folders, obsidian_path, &image.path).image_issues += 1;
+            }
+        }
+
+        folders.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::fixture_vault::FixtureVault;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_top_level_folder() {
+        let vault = Path::new("/vault");
+
+        assert_eq!(
+            top_level_folder(Path::new("/vault/projects/2026/plan.md"), vault),
+            "projects"
+        );
+        assert_eq!(
+            top_level_folder(Path::new("/vault/inbox.md"), vault),
+            VAULT_ROOT
+        );
+    }
+
+    #[test]
+    fn test_folder_rows_sort_by_total_and_link_counts() {
+        let items = vec![
+            FolderIssues {
+                folder: "archive".to_string(),
+                frontmatter_errors: 1,
+                ..FolderIssues::default()
+            },
+            FolderIssues {
+                folder: "projects".to_string(),
+                broken_links: 2,
+                image_issues: 1,
+                ..FolderIssues::default()
+            },
+        ];
+
+        let rows = FolderIssuesTable.build_rows(&items, None).unwrap();
+
+        assert_eq!(rows[0][0], "projects", "most issues first");
+        assert_eq!(rows[0][1], r"[[#unresolved links\|2]]");
+        assert_eq!(rows[0][2], "0", "nothing to link to");
+        assert_eq!(rows[0][3], r"[[#images\|1]]");
+        assert_eq!(rows[1][4], r"[[#frontmatter issues\|1]]");
+    }
+
+    #[test]
+    fn test_folder_issues_report_counts_by_top_level_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("projects").join("2026")).unwrap();
+        fs::write(
+            temp_dir
+                .path()
+                .join("projects")
+                .join("2026")
+                .join("plan.md"),
+            "see [[Missing Note]] and [[Other Missing Note]]",
+        )
+        .unwrap();
+        TestFileBuilder::new()
+            .with_content("see [[Missing Note]]")
+            .create(&temp_dir, "inbox.md");
+
+        let fixture_vault = FixtureVault::load(temp_dir.path(), "apply_changes: false").unwrap();
+        let report = fixture_vault.report().unwrap();
+
+        let matrix = report
+            .split(&format!("{LEVEL1} {ISSUES_BY_FOLDER}"))
+            .nth(1)
+            .unwrap();
+        assert!(
+            matrix.contains(r"| projects | [[#unresolved links\|2]] |"),
+            "{report}"
+        );
+        assert!(
+            matrix.contains(r"| (vault root) | [[#unresolved links\|1]] |"),
+            "{report}"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_FIX;
+use super::constants::TABLE_HEADER_PROPERTY;
+use super::constants::TABLE_HEADER_VALUE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::FRONTMATTER_LINK_ISSUES;
+use crate::constants::FRONTMATTER_LINK_ISSUES_DESCRIPTION;
+use crate::constants::FRONTMATTER_LINK_ISSUES_FIX_DISABLED;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::REASON;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::FrontmatterLinkIssue;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct FrontmatterLinksTable {
+    fixes_enabled: bool,
+}
+
+impl ReportDefinition for FrontmatterLinksTable {
+    type Item = FrontmatterLinkIssue;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_PROPERTY,
+            TABLE_HEADER_VALUE,
+            REASON,
+            TABLE_HEADER_FIX,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|issue| {
+                vec![
+                    issue
+                        .file_path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    issue.property.clone(),
+                    support::escape_pipe(&support::escape_brackets(&issue.value)),
+                    issue.kind.to_string(),
+                    issue
+                        .fix
+                        .as_deref()
+                        .map(support::escape_pipe)
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(FRONTMATTER_LINK_ISSUES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&PathBuf> = items.iter().map(|issue| &issue.file_path).collect();
+
+        let description = DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(FRONTMATTER_LINK_ISSUES_DESCRIPTION);
+
+        if self.fixes_enabled {
+            description.build()
+        } else {
+            description
+                .text_with_newline("")
+                .no_space(FRONTMATTER_LINK_ISSUES_FIX_DISABLED)
+                .build()
+        }
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_frontmatter_links_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.frontmatter_link_issues.clone());
+        report_writer.write(
+            &FrontmatterLinksTable {
+                fixes_enabled: validated_config.fix_frontmatter_link_values()
+                    || validated_config.create_missing_link_targets(),
+            },
+            output_file_writer,
+        )
+    }
+}
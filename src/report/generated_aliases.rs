@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_ALIAS;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_VARIANT;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::GENERATED_ALIASES;
+use crate::constants::GENERATED_ALIASES_DESCRIPTION;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::GeneratedAlias;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct GeneratedAliasesTable;
+
+impl ReportDefinition for GeneratedAliasesTable {
+    type Item = GeneratedAlias;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_ALIAS,
+            TABLE_HEADER_VARIANT,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|generated| {
+                vec![
+                    generated
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    generated.alias.clone(),
+                    generated.variant.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(GENERATED_ALIASES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Alias(items.len()))
+            .text_with_newline("")
+            .no_space(GENERATED_ALIASES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_generated_aliases_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.generated_aliases.clone());
+        report_writer.write(&GeneratedAliasesTable, output_file_writer)
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::match_type_table::MatchTypeTable;
+use super::match_type_table::group_by_match_type;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::BEFORE;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::STALE_HEADING_LINKS;
+use crate::constants::STALE_HEADING_LINKS_DESCRIPTION;
+use crate::constants::SUGGESTION;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::HeadingLinkRepairMatch;
+use crate::markdown_file::StaleHeadingLink;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct StaleHeadingLinksTable;
+
+impl ReportDefinition for StaleHeadingLinksTable {
+    type Item = StaleHeadingLink;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            BEFORE,
+            SUGGESTION,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|stale| {
+                vec![
+                    file_stem_wikilink(&stale.relative_path),
+                    stale.line_number.to_string(),
+                    support::escape_pipe(&support::escape_brackets(&stale.found_text)),
+                    support::escape_pipe(&stale.candidates.join(", ")),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(STALE_HEADING_LINKS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&String> = items.iter().map(|m| &m.relative_path).collect();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Wikilink(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(STALE_HEADING_LINKS_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+fn file_stem_wikilink(relative_path: &str) -> String {
+    Path::new(relative_path)
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_wikilink()
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_heading_link_reports(
+        &self,
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repairs: Vec<HeadingLinkRepairMatch> = files_to_persist
+            .iter()
+            .flat_map(|file| file.heading_link_repair_matches.clone())
+            .collect();
+        for (match_type, repairs) in group_by_match_type(repairs) {
+            ReportWriter::new(repairs)
+                .write(&MatchTypeTable::new(match_type), output_file_writer)?;
+        }
+
+        let mut stale = self.stale_heading_links.clone();
+        stale.sort_by(|a, b| {
+            a.relative_path
+                .to_lowercase()
+                .cmp(&b.relative_path.to_lowercase())
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+        let stale_writer = ReportWriter::new(stale);
+        stale_writer.write(&StaleHeadingLinksTable, output_file_writer)
+    }
+}
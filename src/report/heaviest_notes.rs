@@ -0,0 +1,124 @@
+use std::cmp::Reverse;
+use std::error::Error;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::constants::HEAVIEST_NOTES_REPORT_CONFIG_REQUIRED;
+use super::constants::HEAVIEST_NOTES_SHOWN;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_TOTAL_SIZE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::ATTACHMENTS;
+use crate::constants::HEAVIEST;
+use crate::constants::HEAVIEST_NOTES;
+use crate::constants::HEAVIEST_NOTES_DESCRIPTION;
+use crate::constants::LEVEL1;
+use crate::constants::OF;
+use crate::constants::SHOWING_THE;
+use crate::constants::WITH_ATTACHMENTS;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+
+#[derive(Clone)]
+struct NoteWeight {
+    path:             PathBuf,
+    attachment_count: usize,
+    attachment_size:  u64,
+}
+
+struct HeaviestNotesTable {
+    notes_with_attachments: usize,
+}
+
+impl ReportDefinition for HeaviestNotesTable {
+    type Item = NoteWeight;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![TABLE_HEADER_FILE_NAME, ATTACHMENTS, TABLE_HEADER_TOTAL_SIZE]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        validated_config: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let obsidian_path = validated_config
+            .ok_or_else(|| anyhow!(HEAVIEST_NOTES_REPORT_CONFIG_REQUIRED))?
+            .obsidian_path();
+
+        Ok(items
+            .iter()
+            .map(|note_weight| {
+                vec![
+                    support::format_wikilink(&note_weight.path, obsidian_path),
+                    note_weight.attachment_count.to_string(),
+                    support::format_size(note_weight.attachment_size),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(HEAVIEST_NOTES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(SHOWING_THE)
+            .number(items.len())
+            .text(HEAVIEST)
+            .text(OF)
+            .pluralize_with_count(Phrase::File(self.notes_with_attachments))
+            .text(WITH_ATTACHMENTS)
+            .text_with_newline("")
+            .no_space(HEAVIEST_NOTES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_heaviest_notes_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut note_weights: Vec<NoteWeight> = self
+            .markdown_files
+            .iter()
+            .map(|markdown_file| NoteWeight {
+                path:             markdown_file.path.clone(),
+                attachment_count: markdown_file.image_links.attachment_count(),
+                attachment_size:  markdown_file.image_links.attachment_size(),
+            })
+            .filter(|note_weight| note_weight.attachment_count > 0)
+            .collect();
+        note_weights.sort_by_key(|note_weight| Reverse(note_weight.attachment_size));
+
+        let notes_with_attachments = note_weights.len();
+        note_weights.truncate(HEAVIEST_NOTES_SHOWN);
+
+        let report = ReportWriter::new(note_weights).with_validated_config(validated_config);
+        report.write(
+            &HeaviestNotesTable {
+                notes_with_attachments,
+            },
+            output_file_writer,
+        )
+    }
+}
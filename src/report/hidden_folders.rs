@@ -0,0 +1,79 @@
+use std::error::Error;
+
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::constants::HIDDEN_FOLDERS_REPORT_CONFIG_REQUIRED;
+use super::constants::TABLE_HEADER_COUNT;
+use super::constants::TABLE_HEADER_TOTAL_SIZE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::HIDDEN_FOLDERS;
+use crate::constants::HIDDEN_FOLDERS_DESCRIPTION;
+use crate::constants::LEVEL1;
+use crate::constants::PATH;
+use crate::constants::TYPE;
+use crate::obsidian_repository::HiddenFolderItem;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::support::format_relative_path;
+use crate::validated_config::ValidatedConfig;
+
+struct HiddenFoldersTable;
+
+impl ReportDefinition for HiddenFoldersTable {
+    type Item = HiddenFolderItem;
+
+    fn headers(&self) -> Vec<&str> { vec![PATH, TYPE, TABLE_HEADER_COUNT, TABLE_HEADER_TOTAL_SIZE] }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        validated_config: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let obsidian_path = validated_config
+            .ok_or_else(|| anyhow!(HIDDEN_FOLDERS_REPORT_CONFIG_REQUIRED))?
+            .obsidian_path();
+
+        Ok(items
+            .iter()
+            .map(|item| {
+                vec![
+                    support::escape_pipe(&format_relative_path(&item.path, obsidian_path)),
+                    item.kind.to_string(),
+                    item.count.to_string(),
+                    support::format_size(item.size),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(HIDDEN_FOLDERS.to_string()) }
+
+    fn description(&self, _: &[Self::Item]) -> String { HIDDEN_FOLDERS_DESCRIPTION.to_string() }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_hidden_folders_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report = ReportWriter::new(self.hidden_folder_items.clone())
+            .with_validated_config(validated_config);
+        report.write(&HiddenFoldersTable, output_file_writer)
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use chrono::NaiveDate;
+
+use super::constants::TABLE_HEADER_DATE_CREATED;
+use super::constants::TABLE_HEADER_DATE_MODIFIED;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_FIX;
+use super::constants::TABLE_HEADER_ISSUE;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::config::ImpossibleDatePolicy;
+use crate::constants::FORMAT_DATE;
+use crate::constants::FOUND;
+use crate::constants::IMPOSSIBLE_DATES;
+use crate::constants::IMPOSSIBLE_DATES_DESCRIPTION;
+use crate::constants::IMPOSSIBLE_DATES_FIX_DISABLED;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::DateInconsistency;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct ImpossibleDatesTable {
+    policy: Option<ImpossibleDatePolicy>,
+}
+
+fn format_date(date: Option<NaiveDate>) -> String {
+    date.map(|date| date.format(FORMAT_DATE).to_string())
+        .unwrap_or_default()
+}
+
+impl ReportDefinition for ImpossibleDatesTable {
+    type Item = (PathBuf, DateInconsistency);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_ISSUE,
+            TABLE_HEADER_DATE_CREATED,
+            TABLE_HEADER_DATE_MODIFIED,
+            TABLE_HEADER_FIX,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|(path, inconsistency)| {
+                vec![
+                    path.file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    inconsistency.issue.to_string(),
+                    format_date(inconsistency.date_created),
+                    format_date(inconsistency.date_modified),
+                    format_date(inconsistency.fixed),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(IMPOSSIBLE_DATES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&PathBuf> = items.iter().map(|(path, _)| path).collect();
+
+        let description = DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(IMPOSSIBLE_DATES_DESCRIPTION);
+
+        if self.policy.is_some() {
+            description.build()
+        } else {
+            description
+                .text_with_newline("")
+                .no_space(IMPOSSIBLE_DATES_FIX_DISABLED)
+                .build()
+        }
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_impossible_dates_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let inconsistencies: Vec<(PathBuf, DateInconsistency)> = self
+            .markdown_files
+            .iter()
+            .flat_map(|markdown_file| {
+                markdown_file
+                    .date_inconsistencies
+                    .iter()
+                    .map(|inconsistency| (markdown_file.path.clone(), inconsistency.clone()))
+            })
+            .collect();
+
+        let report_writer = ReportWriter::new(inconsistencies);
+        report_writer.write(
+            &ImpossibleDatesTable {
+                policy: validated_config.fix_impossible_dates(),
+            },
+            output_file_writer,
+        )
+    }
+}
@@ -172,6 +172,7 @@ impl ObsidianRepository {
     pub(super) fn write_incompatible_image_report(
         &self,
         validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let incompatible_images = self
@@ -181,15 +182,14 @@ impl ObsidianRepository {
         if !incompatible_images.is_empty() {
             // IncompatibleImagesReport borrows files_to_persist for reference lookup.
             let incompatible_images_report = IncompatibleImagesReport {
-                markdown_files: &self.markdown_files.files_to_persist(),
+                markdown_files: files_to_persist,
             };
 
             // `would_have_rows` mirrors `IncompatibleImagesReport::build_rows` filtering.
             let would_have_rows = incompatible_images.iter().any(|image| {
                 image.references.is_empty()
                     || image.references.iter().any(|reference_path| {
-                        self.markdown_files
-                            .files_to_persist()
+                        files_to_persist
                             .iter()
                             .any(|f| f.path == Path::new(reference_path))
                     })
@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_ERROR_MESSAGE;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_SKIPPED_CHANGES;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::INTEGRITY_FAILURES;
+use crate::constants::INTEGRITY_FAILURES_DESCRIPTION;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::IntegrityFailure;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct IntegrityFailuresTable;
+
+impl ReportDefinition for IntegrityFailuresTable {
+    type Item = IntegrityFailure;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_ERROR_MESSAGE,
+            TABLE_HEADER_SKIPPED_CHANGES,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|failure| {
+                vec![
+                    failure
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    support::escape_pipe(&support::escape_brackets(&failure.issue.to_string())),
+                    support::escape_pipe(&failure.changes.join(", ")),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(INTEGRITY_FAILURES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .text_with_newline("")
+            .no_space(INTEGRITY_FAILURES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_integrity_failures_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ReportWriter::new(self.integrity_failures.clone())
+            .write(&IntegrityFailuresTable, output_file_writer)
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::error::Error;
 use std::ffi::OsStr;
@@ -6,6 +7,8 @@ use std::path::PathBuf;
 use anyhow::Result as AnyhowResult;
 use itertools::Itertools;
 
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
 use super::constants::TABLE_HEADER_FILE_NAME;
 use super::constants::TABLE_HEADER_INVALID_REASON;
 use super::constants::TABLE_HEADER_LINE;
@@ -19,6 +22,7 @@ use crate::constants::IN;
 use crate::constants::INVALID;
 use crate::constants::INVALID_WIKILINKS;
 use crate::constants::LEVEL2;
+use crate::constants::SUPPRESSED_INVALID_WIKILINKS;
 use crate::constants::YOU_HAVE_TO_FIX_THESE_YOURSELF;
 use crate::description_builder::DescriptionBuilder;
 use crate::obsidian_repository::ObsidianRepository;
@@ -27,10 +31,11 @@ use crate::output_file_writer::OutputFileWriter;
 use crate::phrase::Phrase;
 use crate::validated_config::ValidatedConfig;
 use crate::wikilink::InvalidWikilink;
-use crate::wikilink::InvalidWikilinkReason;
 use crate::wikilink::ToWikilink;
 
-pub(super) struct InvalidWikilinksTable;
+pub(super) struct InvalidWikilinksTable {
+    suppressed_summary: Option<String>,
+}
 
 impl ReportDefinition for InvalidWikilinksTable {
     type Item = (PathBuf, InvalidWikilink);
@@ -83,7 +88,7 @@ impl ReportDefinition for InvalidWikilinksTable {
     fn description(&self, items: &[Self::Item]) -> String {
         let unique_files = items.iter().map(|(p, _)| p).collect::<HashSet<_>>().len();
 
-        DescriptionBuilder::new()
+        let description = DescriptionBuilder::new()
             .text(FOUND)
             .number(items.len())
             .text(INVALID)
@@ -91,23 +96,57 @@ impl ReportDefinition for InvalidWikilinksTable {
             .text(IN)
             .pluralize_with_count(Phrase::File(unique_files))
             .text_with_newline("")
-            .no_space(YOU_HAVE_TO_FIX_THESE_YOURSELF)
-            .build()
+            .no_space(YOU_HAVE_TO_FIX_THESE_YOURSELF);
+
+        match &self.suppressed_summary {
+            Some(summary) => description.text_with_newline("").no_space(summary).build(),
+            None => description.build(),
+        }
     }
 
     fn level(&self) -> &'static str { LEVEL2 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
 }
 
 impl ObsidianRepository {
+    /// Invalid wikilinks whose reason is in `ignore_invalid_wikilink_reasons` are left out of
+    /// the table and only counted in a summary line, so it's still visible how many there are.
     pub(super) fn write_invalid_wikilinks_report(
         &self,
+        validated_config: &ValidatedConfig,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let report_writer = ReportWriter::new(self.collect_invalid_wikilinks());
-        report_writer.write(&InvalidWikilinksTable, output_file_writer)
+        let (invalid_wikilinks, suppressed_counts) =
+            self.collect_invalid_wikilinks(validated_config);
+        let suppressed_summary = format_suppressed_summary(&suppressed_counts);
+
+        if invalid_wikilinks.is_empty() {
+            if let Some(summary) = suppressed_summary {
+                output_file_writer.writeln(LEVEL2, INVALID_WIKILINKS)?;
+                output_file_writer.writeln("", &summary)?;
+            }
+            return Ok(());
+        }
+
+        let report_writer = ReportWriter::new(invalid_wikilinks);
+        report_writer.write(
+            &InvalidWikilinksTable { suppressed_summary },
+            output_file_writer,
+        )
     }
 
-    fn collect_invalid_wikilinks(&self) -> Vec<(PathBuf, InvalidWikilink)> {
+    fn collect_invalid_wikilinks(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> (
+        Vec<(PathBuf, InvalidWikilink)>,
+        BTreeMap<&'static str, usize>,
+    ) {
+        let mut suppressed_counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+
         let invalid_wikilinks: Vec<(PathBuf, InvalidWikilink)> = self
             .markdown_files
             .iter()
@@ -116,16 +155,19 @@ impl ObsidianRepository {
                     .wikilinks
                     .invalid
                     .iter()
-                    .filter(|wikilink| {
-                        !matches!(
-                            wikilink.reason,
-                            InvalidWikilinkReason::EmailAddress
-                                | InvalidWikilinkReason::Tag
-                                | InvalidWikilinkReason::RawHttpLink
-                        )
-                    })
+                    .filter(|wikilink| !markdown_file.is_repaired_wikilink(wikilink))
                     .map(move |wikilink| (markdown_file.path.clone(), (*wikilink).clone()))
             })
+            .filter(|(_, wikilink)| {
+                if validated_config.is_ignored_invalid_wikilink_reason(wikilink.reason) {
+                    *suppressed_counts
+                        .entry(wikilink.reason.config_name())
+                        .or_default() += 1;
+                    false
+                } else {
+                    true
+                }
+            })
             .collect::<Vec<_>>()
             .into_iter()
             .sorted_by(|a, b| {
@@ -136,6 +178,18 @@ impl ObsidianRepository {
                     .then(a.1.line_number.cmp(&b.1.line_number))
             })
             .collect();
-        invalid_wikilinks
+        (invalid_wikilinks, suppressed_counts)
+    }
+}
+
+fn format_suppressed_summary(suppressed_counts: &BTreeMap<&'static str, usize>) -> Option<String> {
+    if suppressed_counts.is_empty() {
+        return None;
     }
+
+    let counts = suppressed_counts
+        .iter()
+        .map(|(reason, count)| format!("{reason} {count}"))
+        .join(", ");
+    Some(format!("{SUPPRESSED_INVALID_WIKILINKS} {counts}"))
 }
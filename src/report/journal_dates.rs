@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use chrono::NaiveDate;
+
+use super::constants::TABLE_HEADER_DATE_CREATED;
+use super::constants::TABLE_HEADER_FILE_CREATED;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_FIX;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FORMAT_DATE;
+use crate::constants::FOUND;
+use crate::constants::JOURNAL_DATE_MISMATCHES;
+use crate::constants::JOURNAL_DATE_MISMATCHES_DESCRIPTION;
+use crate::constants::LEVEL1;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::JournalDateMismatch;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct JournalDatesTable;
+
+fn format_date(date: NaiveDate) -> String { date.format(FORMAT_DATE).to_string() }
+
+impl ReportDefinition for JournalDatesTable {
+    type Item = (PathBuf, JournalDateMismatch);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_DATE_CREATED,
+            TABLE_HEADER_FILE_CREATED,
+            TABLE_HEADER_FIX,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|(path, mismatch)| {
+                vec![
+                    path.file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    mismatch.date_created.map(format_date).unwrap_or_default(),
+                    format_date(mismatch.file_system),
+                    format_date(mismatch.date),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(JOURNAL_DATE_MISMATCHES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .text_with_newline("")
+            .no_space(JOURNAL_DATE_MISMATCHES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_journal_dates_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mismatches: Vec<(PathBuf, JournalDateMismatch)> = self
+            .markdown_files
+            .iter()
+            .filter_map(|markdown_file| {
+                markdown_file
+                    .journal_date_mismatch
+                    .clone()
+                    .map(|mismatch| (markdown_file.path.clone(), mismatch))
+            })
+            .collect();
+
+        let report_writer = ReportWriter::new(mismatches);
+        report_writer.write(&JournalDatesTable, output_file_writer)
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_ISSUE;
+use super::constants::TABLE_HEADER_LINE;
+use super::constants::TABLE_HEADER_LINES;
+use super::constants::TABLE_HEADER_NOTES;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::LEVEL2;
+use crate::constants::MARKDOWN_LINT;
+use crate::constants::MARKDOWN_LINT_DESCRIPTION;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::LintKind;
+use crate::obsidian_repository::MarkdownLintIssue;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+/// How many lines have one kind of issue, and in how many notes.
+#[derive(Clone)]
+struct LintKindCount {
+    kind:  LintKind,
+    lines: usize,
+    notes: usize,
+}
+
+struct MarkdownLintSummaryTable {
+    issue_count: usize,
+    file_count:  usize,
+}
+
+impl ReportDefinition for MarkdownLintSummaryTable {
+    type Item = LintKindCount;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![TABLE_HEADER_ISSUE, TABLE_HEADER_LINES, TABLE_HEADER_NOTES]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|count| {
+                vec![
+                    count.kind.to_string(),
+                    count.lines.to_string(),
+                    count.notes.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(MARKDOWN_LINT.to_string()) }
+
+    fn description(&self, _: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(self.issue_count))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(self.file_count))
+            .text_with_newline("")
+            .no_space(MARKDOWN_LINT_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+struct MarkdownLintTable;
+
+impl ReportDefinition for MarkdownLintTable {
+    type Item = MarkdownLintIssue;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_ISSUE,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|issue| {
+                vec![
+                    issue
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    issue.line_number.to_string(),
+                    issue.kind.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn description(&self, _: &[Self::Item]) -> String { String::new() }
+
+    fn level(&self) -> &'static str { LEVEL2 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_markdown_lint_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut issues = self.markdown_lint_issues.clone();
+        issues.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then_with(|| a.line_number.cmp(&b.line_number))
+                .then_with(|| a.kind.cmp(&b.kind))
+        });
+
+        let mut by_kind: BTreeMap<LintKind, (usize, HashSet<&PathBuf>)> = BTreeMap::new();
+        for issue in &issues {
+            let (lines, notes) = by_kind.entry(issue.kind).or_default();
+            *lines += 1;
+            notes.insert(&issue.path);
+        }
+        let counts: Vec<LintKindCount> = by_kind
+            .into_iter()
+            .map(|(kind, (lines, notes))| LintKindCount {
+                kind,
+                lines,
+                notes: notes.len(),
+            })
+            .collect();
+
+        let summary = MarkdownLintSummaryTable {
+            issue_count: issues.len(),
+            file_count:  issues
+                .iter()
+                .map(|issue| &issue.path)
+                .collect::<HashSet<_>>()
+                .len(),
+        };
+        let summary_writer = ReportWriter::new(counts);
+        summary_writer.write(&summary, output_file_writer)?;
+
+        let issues_writer = ReportWriter::new(issues);
+        issues_writer.write(&MarkdownLintTable, output_file_writer)
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::support;
+use super::writer::ReportDefinition;
+use crate::constants::AFTER;
+use crate::constants::BEFORE;
+use crate::constants::DATE_MENTIONS;
+use crate::constants::DATE_MENTIONS_DESCRIPTION;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::LEVEL2;
+use crate::constants::REPAIRED_HEADING_LINKS;
+use crate::constants::REPAIRED_HEADING_LINKS_DESCRIPTION;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::CustomReplacementMatch;
+use crate::markdown_file::DateMentionMatch;
+use crate::markdown_file::HeadingLinkRepairMatch;
+use crate::markdown_file::MatchType;
+use crate::markdown_file::ReplaceableContent;
+use crate::output_file_writer::ColumnAlignment;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+/// A replacement reported as a before and after row of its `MatchType`'s table.
+pub(super) trait ReportedReplacement: ReplaceableContent {
+    fn relative_path(&self) -> &str;
+}
+
+impl ReportedReplacement for CustomReplacementMatch {
+    fn relative_path(&self) -> &str { &self.relative_path }
+}
+
+impl ReportedReplacement for DateMentionMatch {
+    fn relative_path(&self) -> &str { &self.relative_path }
+}
+
+impl ReportedReplacement for HeadingLinkRepairMatch {
+    fn relative_path(&self) -> &str { &self.relative_path }
+}
+
+/// The title and explanation of a built-in `MatchType`'s table. A `Custom` type has neither -
+/// its table is titled by its source's name under the custom replacements heading.
+const fn built_in_section(match_type: &MatchType) -> Option<(&'static str, &'static str)> {
+    match match_type {
+        MatchType::DateMention => Some((DATE_MENTIONS, DATE_MENTIONS_DESCRIPTION)),
+        MatchType::HeadingLinkRepair => {
+            Some((REPAIRED_HEADING_LINKS, REPAIRED_HEADING_LINKS_DESCRIPTION))
+        },
+        _ => None,
+    }
+}
+
+/// The replacements of one `MatchType` - built-in types get their own section, and each
+/// replacement source gets its own table.
+pub(super) struct MatchTypeTable<T> {
+    match_type: MatchType,
+    items:      PhantomData<T>,
+}
+
+impl<T> MatchTypeTable<T> {
+    pub(super) const fn new(match_type: MatchType) -> Self {
+        Self {
+            match_type,
+            items: PhantomData,
+        }
+    }
+}
+
+impl<T: ReportedReplacement> ReportDefinition for MatchTypeTable<T> {
+    type Item = T;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![TABLE_HEADER_FILE_NAME, TABLE_HEADER_LINE, BEFORE, AFTER]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|replacement| {
+                vec![
+                    Path::new(replacement.relative_path())
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    replacement.line_number().to_string(),
+                    escape_text(&replacement.matched_text()),
+                    escape_text(&replacement.get_replacement()),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> {
+        Some(built_in_section(&self.match_type).map_or_else(
+            || self.match_type.to_string(),
+            |(title, _)| title.to_string(),
+        ))
+    }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        match built_in_section(&self.match_type) {
+            Some((_, description)) => DescriptionBuilder::new()
+                .no_space(&found_in_files(items))
+                .text_with_newline("")
+                .no_space(description)
+                .build(),
+            None => found_in_files(items),
+        }
+    }
+
+    fn level(&self) -> &'static str {
+        if built_in_section(&self.match_type).is_some() {
+            LEVEL1
+        } else {
+            LEVEL2
+        }
+    }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+/// "found 3 replacements in 2 files" for `items`.
+pub(super) fn found_in_files<T: ReportedReplacement>(items: &[T]) -> String {
+    let unique_files: HashSet<&str> = items
+        .iter()
+        .map(ReportedReplacement::relative_path)
+        .collect();
+
+    DescriptionBuilder::new()
+        .text(FOUND)
+        .pluralize_with_count(Phrase::Replacement(items.len()))
+        .text(IN)
+        .pluralize_with_count(Phrase::File(unique_files.len()))
+        .build()
+}
+
+/// `matches` ordered by file, line and position, then split by `MatchType` ordered by its
+/// name, each group keeping that order.
+pub(super) fn group_by_match_type<T: ReportedReplacement>(
+    mut matches: Vec<T>,
+) -> Vec<(MatchType, Vec<T>)> {
+    matches.sort_by(|a, b| {
+        a.relative_path()
+            .to_lowercase()
+            .cmp(&b.relative_path().to_lowercase())
+            .then_with(|| a.line_number().cmp(&b.line_number()))
+            .then_with(|| a.position().cmp(&b.position()))
+    });
+
+    let mut by_match_type: BTreeMap<String, (MatchType, Vec<T>)> = BTreeMap::new();
+    for replacement in matches {
+        let match_type = replacement.match_type();
+        by_match_type
+            .entry(match_type.to_string())
+            .or_insert_with(|| (match_type, Vec::new()))
+            .1
+            .push(replacement);
+    }
+    by_match_type.into_values().collect()
+}
+
+fn escape_text(text: &str) -> String { support::escape_pipe(&support::escape_brackets(text)) }
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use super::*;
+
+    fn custom_match(
+        source: &str,
+        relative_path: &str,
+        line_number: usize,
+    ) -> CustomReplacementMatch {
+        CustomReplacementMatch {
+            found_text: "https://example.com".to_string(),
+            line_number,
+            position: 0,
+            relative_path: relative_path.to_string(),
+            replacement: "<https://example.com>".to_string(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_replacements_grouped_by_match_type() {
+        let groups = group_by_match_type(vec![
+            custom_match("urls", "b.md", 1),
+            custom_match("dashes", "a.md", 2),
+            custom_match("urls", "a.md", 3),
+        ]);
+
+        let summary: Vec<(String, Vec<usize>)> = groups
+            .iter()
+            .map(|(match_type, matches)| {
+                (
+                    match_type.to_string(),
+                    matches.iter().map(|m| m.line_number).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("dashes".to_string(), vec![2]),
+                ("urls".to_string(), vec![3, 1]),
+            ],
+            "one table per source, each ordered by file then line"
+        );
+    }
+
+    #[test]
+    fn test_built_in_match_types_get_their_own_section() {
+        let date_mentions = MatchTypeTable::<DateMentionMatch>::new(MatchType::DateMention);
+        assert_eq!(date_mentions.title().unwrap(), DATE_MENTIONS);
+        assert_eq!(date_mentions.level(), LEVEL1);
+
+        let custom =
+            MatchTypeTable::<CustomReplacementMatch>::new(MatchType::Custom("urls".to_string()));
+        assert_eq!(custom.title().unwrap(), "urls");
+        assert_eq!(custom.level(), LEVEL2);
+    }
+}
@@ -20,6 +20,7 @@ use crate::constants::REFERENCE_REMOVED;
 use crate::constants::REFERENCE_WILL_BE_REMOVED;
 use crate::description_builder::DescriptionBuilder;
 use crate::markdown_file::ImageLinkState;
+use crate::markdown_files::MarkdownFiles;
 use crate::obsidian_repository::ObsidianRepository;
 use crate::output_file_writer::ColumnAlignment;
 use crate::output_file_writer::OutputFileWriter;
@@ -101,13 +102,11 @@ impl ReportDefinition for MissingReferencesTable {
 
 impl ObsidianRepository {
     pub(super) fn write_missing_references_report(
-        &self,
         validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let missing_refs: Vec<(PathBuf, String, usize, usize)> = self
-            .markdown_files
-            .files_to_persist()
+        let missing_refs: Vec<(PathBuf, String, usize, usize)> = files_to_persist
             .iter()
             .flat_map(|file| {
                 let missing_links = file.image_links.filter_by_variant(ImageLinkState::Missing);
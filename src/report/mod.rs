@@ -1,19 +1,65 @@
+mod acronym_collisions;
 mod add_frontmatter;
 mod ambiguous_matches;
 mod back_populate;
+mod block_ids;
+mod callouts;
 mod canonical_links;
+mod canvas_and_base_references;
+mod changed_on_disk_notes;
+mod citations;
+mod conflict_copies;
 mod constants;
+mod custom_replacements;
+mod date_mentions;
+mod date_properties;
 mod definition;
+mod duplicate_frontmatter_keys;
 mod duplicate_images;
+mod encrypted_blocks;
+mod entity_notes;
+mod fix_it_tasks;
+mod folder_issues;
+mod foreign_owned_notes;
 mod frontmatter_issues;
+mod frontmatter_links;
+mod generated_aliases;
+mod heading_links;
+mod heaviest_notes;
+mod hidden_folders;
+mod impossible_dates;
 mod incompatible_image;
+mod integrity_failures;
 mod invalid_wikilink;
+mod journal_dates;
+mod markdown_lint;
+mod match_type_table;
 mod missing_references;
+mod normalized_lists;
+mod normalized_punctuation;
 mod orchestration;
+mod other_files;
+mod periodic_notes;
 mod persist_reasons;
 mod phantom_links;
+mod pii;
+mod possible_secrets;
+mod property_types;
+mod rare_words;
+mod recently_modified_notes;
+mod regex_replacements;
+mod replacement_conflicts;
+mod reviews;
+mod saved_reports;
+mod self_links;
+mod similar_titles;
+mod split_notes;
 mod support;
 mod unreferenced_images;
 mod unresolved_links;
+mod unsafe_paths;
+mod vault_health;
+mod wikilink_repairs;
+mod withheld_content;
 
 mod writer;
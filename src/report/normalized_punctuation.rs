@@ -0,0 +1,123 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_CHARACTER;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::NORMALIZED_PUNCTUATION;
+use crate::constants::NORMALIZED_PUNCTUATION_DESCRIPTION;
+use crate::constants::OCCURRENCES;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::PunctuationNormalization;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct NormalizedPunctuationTable;
+
+impl ReportDefinition for NormalizedPunctuationTable {
+    type Item = (PathBuf, PunctuationNormalization);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_CHARACTER,
+            OCCURRENCES,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|(path, normalization)| {
+                vec![
+                    path.file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    normalization.line_number.to_string(),
+                    normalization.kind.to_string(),
+                    normalization.count.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(NORMALIZED_PUNCTUATION.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&PathBuf> = items.iter().map(|(path, _)| path).collect();
+        let character_count = items
+            .iter()
+            .map(|(_, normalization)| normalization.count)
+            .sum();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Character(character_count))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(NORMALIZED_PUNCTUATION_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_normalized_punctuation_report(
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut items: Vec<(PathBuf, PunctuationNormalization)> = files_to_persist
+            .iter()
+            .flat_map(|file| {
+                file.punctuation_normalizations
+                    .iter()
+                    .map(|normalization| (file.path.clone(), normalization.clone()))
+            })
+            .collect();
+        items.sort_by(|(a_path, a), (b_path, b)| {
+            a_path
+                .cmp(b_path)
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+
+        let report_writer = ReportWriter::new(items);
+        report_writer.write(&NormalizedPunctuationTable, output_file_writer)
+    }
+}
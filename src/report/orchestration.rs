@@ -2,10 +2,12 @@ use std::error::Error;
 
 use chrono::Local;
 use chrono::Utc;
+use rayon::prelude::*;
 
 use crate::constants::BACK_POPULATE;
 use crate::constants::BACK_POPULATE_FILE_FILTER_PREFIX;
 use crate::constants::BACK_POPULATE_FILE_FILTER_SUFFIX;
+use crate::constants::DOUBLE_QUOTE;
 use crate::constants::FORMAT_TIME_STAMP;
 use crate::constants::IMAGES;
 use crate::constants::IN_CHANGESET;
@@ -13,10 +15,17 @@ use crate::constants::LEVEL1;
 use crate::constants::MODE_APPLY_CHANGES;
 use crate::constants::MODE_APPLY_CHANGES_OFF;
 use crate::constants::OF;
+use crate::constants::REPORT_TAG;
+use crate::constants::TEMPLATE_FOLDERS_IGNORED;
 use crate::constants::YAML_APPLY_CHANGES;
+use crate::constants::YAML_CSSCLASSES;
+use crate::constants::YAML_DATE_CREATED;
+use crate::constants::YAML_DATE_MODIFIED;
 use crate::constants::YAML_FALSE;
 use crate::constants::YAML_FILE_LIMIT;
 use crate::constants::YAML_NONE;
+use crate::constants::YAML_RUN_ID;
+use crate::constants::YAML_TAGS;
 use crate::constants::YAML_TIMESTAMP_LOCAL;
 use crate::constants::YAML_TIMESTAMP_UTC;
 use crate::constants::YAML_TRUE;
@@ -25,15 +34,19 @@ use crate::image_file::ImageFileState;
 use crate::markdown_file::ImageLinkState;
 use crate::markdown_file::MarkdownFile;
 use crate::markdown_file::PersistReason;
+use crate::markdown_files::MarkdownFiles;
 use crate::obsidian_repository::ObsidianRepository;
 use crate::output_file_writer::OutputFileWriter;
 use crate::phrase::Phrase;
 use crate::support::VecEnumFilter;
 use crate::validated_config::ChangeMode;
 use crate::validated_config::ValidatedConfig;
-use crate::wikilink::InvalidWikilinkReason;
 use crate::wikilink::ToWikilink;
 
+/// One section of the report, written to its own buffer.
+type ReportSection<'a> =
+    Box<dyn Fn(&OutputFileWriter) -> Result<(), Box<dyn Error + Send + Sync>> + Send + Sync + 'a>;
+
 impl ObsidianRepository {
     pub fn write_reports(
         &self,
@@ -46,26 +59,105 @@ impl ObsidianRepository {
         &self,
         validated_config: &ValidatedConfig,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let output_file_writer = OutputFileWriter::new(validated_config.output_folder())?;
-
-        self.write_execution_start(validated_config, &output_file_writer)?;
-        self.write_frontmatter_issues_report(&output_file_writer)?;
-
-        self.write_image_reports(validated_config, &output_file_writer)?;
-        self.write_ambiguous_matches_reports(&output_file_writer)?;
-        self.write_unresolved_links_report(&output_file_writer)?;
-        self.write_back_populate_reports(validated_config, &output_file_writer)?;
-
-        // This report is slightly duplicative because image reference updates and back-populate
-        // updates already have dedicated reports. It still captures date changes clearly, so it
-        // remains useful as an audit trail.
-        self.write_persist_reasons_report(validated_config, &output_file_writer)?;
+        let deep_link_vault = validated_config
+            .report_deep_links()
+            .then(|| validated_config.obsidian_path().file_name())
+            .flatten()
+            .map(|vault| vault.to_string_lossy().to_string());
+        let output_file_writer = OutputFileWriter::new(validated_config.output_folder())?
+            .with_deep_link_vault(deep_link_vault)
+            .with_row_limit(validated_config.report_row_limit());
+
+        let files_to_persist = self.markdown_files.files_to_persist();
+        let files_to_persist = &files_to_persist;
+        let sections: Vec<ReportSection<'_>> = vec![
+            Box::new(|writer| {
+                self.write_execution_start(validated_config, files_to_persist, writer)
+            }),
+            Box::new(|writer| self.write_vault_health_report(writer)),
+            Box::new(|writer| {
+                self.write_folder_issues_report(validated_config, files_to_persist, writer)
+            }),
+            Box::new(|writer| self.write_saved_reports(validated_config, writer)),
+            Box::new(|writer| self.write_frontmatter_issues_report(writer)),
+            Box::new(|writer| {
+                self.write_duplicate_frontmatter_keys_report(validated_config, writer)
+            }),
+            Box::new(|writer| self.write_impossible_dates_report(validated_config, writer)),
+            Box::new(|writer| self.write_journal_dates_report(writer)),
+            Box::new(|writer| self.write_date_properties_report(writer)),
+            Box::new(|writer| self.write_needs_review_report(validated_config, writer)),
+            Box::new(|writer| self.write_property_types_report(validated_config, writer)),
+            Box::new(|writer| self.write_frontmatter_links_report(validated_config, writer)),
+            Box::new(|writer| self.write_image_reports(validated_config, files_to_persist, writer)),
+            Box::new(|writer| {
+                self.write_canvas_and_base_references_report(validated_config, writer)
+            }),
+            Box::new(|writer| self.write_heaviest_notes_report(validated_config, writer)),
+            Box::new(|writer| self.write_other_files_report(writer)),
+            Box::new(|writer| self.write_hidden_folders_report(validated_config, writer)),
+            Box::new(|writer| self.write_ambiguous_matches_reports(validated_config, writer)),
+            Box::new(|writer| self.write_unresolved_links_report(validated_config, writer)),
+            Box::new(|writer| self.write_heading_link_reports(files_to_persist, writer)),
+            Box::new(|writer| self.write_encrypted_blocks_report(writer)),
+            Box::new(|writer| self.write_possible_secrets_report(writer)),
+            Box::new(|writer| self.write_pii_report(writer)),
+            Box::new(|writer| self.write_rare_words_report(validated_config, writer)),
+            Box::new(|writer| self.write_similar_titles_report(validated_config, writer)),
+            Box::new(|writer| self.write_conflict_copies_report(validated_config, writer)),
+            Box::new(|writer| self.write_generated_aliases_report(writer)),
+            Box::new(|writer| self.write_acronym_collisions_report(writer)),
+            Box::new(|writer| {
+                self.write_back_populate_reports(validated_config, files_to_persist, writer)
+            }),
+            Box::new(|writer| Self::write_date_mentions_report(files_to_persist, writer)),
+            Box::new(|writer| Self::write_regex_replacements_report(files_to_persist, writer)),
+            Box::new(|writer| Self::write_custom_replacements_report(files_to_persist, writer)),
+            Box::new(|writer| self.write_replacement_conflicts_report(writer)),
+            Box::new(|writer| Self::write_normalized_punctuation_report(files_to_persist, writer)),
+            Box::new(|writer| Self::write_normalized_lists_report(files_to_persist, writer)),
+            Box::new(|writer| self.write_callout_reports(files_to_persist, writer)),
+            Box::new(|writer| self.write_split_notes_report(writer)),
+            Box::new(|writer| self.write_periodic_notes_report(writer)),
+            Box::new(|writer| self.write_entity_notes_report(validated_config, writer)),
+            Box::new(|writer| self.write_citations_report(writer)),
+            Box::new(|writer| self.write_block_id_reports(writer)),
+            Box::new(|writer| self.write_markdown_lint_report(writer)),
+            Box::new(|writer| self.write_unsafe_paths_report(validated_config, writer)),
+            Box::new(|writer| self.write_foreign_owned_notes_report(writer)),
+            Box::new(|writer| self.write_recently_modified_notes_report(writer)),
+            Box::new(|writer| self.write_changed_on_disk_notes_report(writer)),
+            Box::new(|writer| self.write_integrity_failures_report(writer)),
+            Box::new(|writer| self.write_withheld_content_report(writer)),
+            // This report is slightly duplicative because image reference updates and
+            // back-populate updates already have dedicated reports. It still captures date
+            // changes clearly, so it remains useful as an audit trail.
+            Box::new(|writer| {
+                self.write_persist_reasons_report(validated_config, files_to_persist, writer)
+            }),
+        ];
+
+        // Sections only read the repository, so they are written side by side and appended in
+        // the order above.
+        let written = sections
+            .par_iter()
+            .map(|section| {
+                let writer = output_file_writer.buffer();
+                section(&writer).map(|()| writer)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        for section in written {
+            output_file_writer.append(section)?;
+        }
 
+        self.write_fix_it_tasks(validated_config, output_file_writer.deep_link_vault())?;
+        output_file_writer.finish()?;
         Ok(())
     }
 
     fn write_ambiguous_matches_reports(
         &self,
+        validated_config: &ValidatedConfig,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let has_ambiguous_matches = self
@@ -74,7 +166,7 @@ impl ObsidianRepository {
             .any(MarkdownFile::has_ambiguous_matches);
 
         if has_ambiguous_matches {
-            self.write_ambiguous_matches_report(output_file_writer)?;
+            self.write_ambiguous_matches_report(validated_config, output_file_writer)?;
         }
 
         Ok(())
@@ -83,23 +175,19 @@ impl ObsidianRepository {
     fn write_back_populate_reports(
         &self,
         validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let has_back_populate_entries = self
-            .markdown_files
-            .files_to_persist()
+        let has_back_populate_entries = files_to_persist
             .iter()
             .any(MarkdownFile::has_unambiguous_matches);
 
+        // Ignored reasons still count here - the report writes a summary line for them.
         let has_invalid_wikilinks = self.markdown_files.iter().any(|file| {
-            file.wikilinks.invalid.iter().any(|wikilink| {
-                !matches!(
-                    wikilink.reason,
-                    InvalidWikilinkReason::EmailAddress
-                        | InvalidWikilinkReason::Tag
-                        | InvalidWikilinkReason::RawHttpLink
-                )
-            })
+            file.wikilinks
+                .invalid
+                .iter()
+                .any(|wikilink| !file.is_repaired_wikilink(wikilink))
         });
 
         let has_frontmatter_created = self.markdown_files.iter().any(|file| {
@@ -108,23 +196,30 @@ impl ObsidianRepository {
                 .any(|r| matches!(r, PersistReason::FrontmatterCreated))
         });
 
-        let has_canonical_links = self
-            .markdown_files
-            .files_to_persist()
+        let has_canonical_links = files_to_persist
             .iter()
             .any(MarkdownFile::has_canonical_link_matches);
 
-        let has_phantom_links = self
-            .markdown_files
-            .files_to_persist()
+        let has_phantom_links = files_to_persist
             .iter()
             .any(MarkdownFile::has_phantom_link_matches);
 
+        let has_self_links = self
+            .markdown_files
+            .iter()
+            .any(MarkdownFile::has_self_link_matches);
+
+        let has_wikilink_repairs = files_to_persist
+            .iter()
+            .any(MarkdownFile::has_wikilink_repair_matches);
+
         if has_back_populate_entries
             || has_invalid_wikilinks
             || has_frontmatter_created
             || has_canonical_links
             || has_phantom_links
+            || has_self_links
+            || has_wikilink_repairs
         {
             write_back_populate_report_header(validated_config, output_file_writer)?;
 
@@ -133,19 +228,35 @@ impl ObsidianRepository {
             }
 
             if has_invalid_wikilinks {
-                self.write_invalid_wikilinks_report(output_file_writer)?;
+                self.write_invalid_wikilinks_report(validated_config, output_file_writer)?;
+            }
+
+            if has_wikilink_repairs {
+                Self::write_wikilink_repairs_report(files_to_persist, output_file_writer)?;
             }
 
             if has_canonical_links {
-                self.write_canonical_links_report(output_file_writer)?;
+                Self::write_canonical_links_report(files_to_persist, output_file_writer)?;
             }
 
             if has_phantom_links {
-                self.write_phantom_links_report(output_file_writer)?;
+                Self::write_phantom_links_report(files_to_persist, output_file_writer)?;
+            }
+
+            if has_self_links {
+                self.write_self_links_report(
+                    validated_config,
+                    files_to_persist,
+                    output_file_writer,
+                )?;
             }
 
             if has_back_populate_entries {
-                self.write_back_populate_report(output_file_writer)?;
+                self.write_back_populate_report(
+                    validated_config,
+                    files_to_persist,
+                    output_file_writer,
+                )?;
             }
         }
 
@@ -155,6 +266,7 @@ impl ObsidianRepository {
     fn write_image_reports(
         &self,
         validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let has_report_entries = self.image_files.images.iter().any(|image| {
@@ -164,7 +276,7 @@ impl ObsidianRepository {
                     | ImageFileState::Duplicate { .. }
                     | ImageFileState::Incompatible { .. }
             )
-        }) || self.markdown_files.files_to_persist().iter().any(|file| {
+        }) || files_to_persist.iter().any(|file| {
             !file
                 .image_links
                 .filter_by_variant(ImageLinkState::Missing)
@@ -174,8 +286,16 @@ impl ObsidianRepository {
         if has_report_entries {
             output_file_writer.writeln(LEVEL1, IMAGES)?;
 
-            self.write_missing_references_report(validated_config, output_file_writer)?;
-            self.write_incompatible_image_report(validated_config, output_file_writer)?;
+            Self::write_missing_references_report(
+                validated_config,
+                files_to_persist,
+                output_file_writer,
+            )?;
+            self.write_incompatible_image_report(
+                validated_config,
+                files_to_persist,
+                output_file_writer,
+            )?;
             self.write_unreferenced_images_report(validated_config, output_file_writer)?;
             self.write_duplicate_images_report(validated_config, output_file_writer)?;
         }
@@ -186,6 +306,7 @@ impl ObsidianRepository {
     fn write_execution_start(
         &self,
         validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let timestamp_utc = Utc::now().format(FORMAT_TIME_STAMP);
@@ -201,7 +322,25 @@ impl ObsidianRepository {
             ChangeMode::DryRun => YAML_FALSE,
         };
 
+        // Tagged and dated like the vault's own notes, so the report indexes cleanly and
+        // Dataview can query it.
+        let report_tag = format!("[{REPORT_TAG}]");
+        let date_link = format!(
+            "{DOUBLE_QUOTE}{}{DOUBLE_QUOTE}",
+            validated_config.today_link()
+        );
+
         let properties = DescriptionBuilder::new()
+            .no_space(YAML_TAGS)
+            .text_with_newline(&report_tag)
+            .no_space(YAML_CSSCLASSES)
+            .text_with_newline(&report_tag)
+            .no_space(YAML_DATE_CREATED)
+            .text_with_newline(&date_link)
+            .no_space(YAML_DATE_MODIFIED)
+            .text_with_newline(&date_link)
+            .no_space(YAML_RUN_ID)
+            .text_with_newline(&self.run_id)
             .no_space(YAML_TIMESTAMP_UTC)
             .text_with_newline(&timestamp_utc.to_string())
             .no_space(YAML_TIMESTAMP_LOCAL)
@@ -215,7 +354,7 @@ impl ObsidianRepository {
         output_file_writer.write_properties(&properties)?;
 
         let total_files_to_persist = self.markdown_files.total_files_to_persist();
-        let files_to_persist = self.markdown_files.files_to_persist().len();
+        let files_to_persist = files_to_persist.len();
 
         let message = validated_config.file_limit().map_or_else(
             || {
@@ -241,6 +380,18 @@ impl ObsidianRepository {
             ChangeMode::DryRun => output_file_writer.writeln("", MODE_APPLY_CHANGES_OFF)?,
         }
 
+        let template_folders = validated_config.template_folders();
+        if !template_folders.is_empty() {
+            let folders: Vec<String> = template_folders
+                .iter()
+                .map(|folder| format!("`{}`", folder.display()))
+                .collect();
+            output_file_writer.writeln(
+                "",
+                &format!("{TEMPLATE_FOLDERS_IGNORED} {}", folders.join(", ")),
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,102 @@
+use std::error::Error;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_EXTENSION;
+use super::constants::TABLE_HEADER_ISSUES;
+use super::constants::TABLE_HEADER_TOTAL_SIZE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FILES;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::NO_EXTENSION;
+use crate::constants::OTHER_FILES;
+use crate::constants::OTHER_FILES_DESCRIPTION;
+use crate::constants::UNEXPECTED_FILE_TYPE;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::OtherFileType;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+
+struct OtherFilesTable;
+
+impl ReportDefinition for OtherFilesTable {
+    type Item = OtherFileType;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_EXTENSION,
+            FILES,
+            TABLE_HEADER_TOTAL_SIZE,
+            TABLE_HEADER_ISSUES,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|other_file_type| {
+                vec![
+                    if other_file_type.extension.is_empty() {
+                        NO_EXTENSION.to_string()
+                    } else {
+                        support::escape_pipe(&other_file_type.extension)
+                    },
+                    other_file_type.count.to_string(),
+                    support::format_size(other_file_type.total_size),
+                    if other_file_type.unexpected {
+                        UNEXPECTED_FILE_TYPE.to_string()
+                    } else {
+                        String::new()
+                    },
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(OTHER_FILES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let file_count = items
+            .iter()
+            .map(|other_file_type| other_file_type.count)
+            .sum();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(file_count))
+            .text_with_newline("")
+            .no_space(OTHER_FILES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_other_files_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.other_file_types.clone());
+        report_writer.write(&OtherFilesTable, output_file_writer)
+    }
+}
@@ -0,0 +1,87 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::ACTION;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::PERIODIC_NOTES;
+use crate::constants::PERIODIC_NOTES_DESCRIPTION;
+use crate::constants::PERIODIC_PARENT;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::PeriodicNoteChange;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct PeriodicNotesTable;
+
+impl ReportDefinition for PeriodicNotesTable {
+    type Item = PeriodicNoteChange;
+
+    fn headers(&self) -> Vec<&str> { vec![TABLE_HEADER_FILE_NAME, PERIODIC_PARENT, ACTION] }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|change| {
+                vec![
+                    change
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    change
+                        .parent
+                        .as_deref()
+                        .map(ToWikilink::to_wikilink)
+                        .unwrap_or_default(),
+                    change.action.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(PERIODIC_NOTES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .text_with_newline("")
+            .no_space(PERIODIC_NOTES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_periodic_notes_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.periodic_note_changes.clone());
+        report_writer.write(&PeriodicNotesTable, output_file_writer)
+    }
+}
@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::error::Error;
 use std::ffi::OsStr;
 use std::path::PathBuf;
@@ -6,9 +8,12 @@ use std::path::PathBuf;
 use anyhow::Result as AnyhowResult;
 
 use super::constants::FILES_TO_BE_UPDATED;
+use super::constants::TABLE_HEADER_HELD;
+use super::constants::TABLE_HEADER_NOTES;
 use super::support;
 use super::writer::ReportDefinition;
 use super::writer::ReportWriter;
+use crate::config::PersistReasonKind;
 use crate::constants::AFTER;
 use crate::constants::BEFORE;
 use crate::constants::CLOSING_WIKILINK;
@@ -22,14 +27,17 @@ use crate::constants::LEVEL2;
 use crate::constants::MARKDOWN_SUFFIX;
 use crate::constants::OPENING_WIKILINK;
 use crate::constants::PATH;
+use crate::constants::PERSIST_REASON_COUNTS_DESCRIPTION;
 use crate::constants::PIPE;
 use crate::constants::REASON;
 use crate::constants::REPORT_CHUNK_SIZE;
 use crate::constants::UPDATE;
 use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::DatePropertyValidation;
 use crate::markdown_file::DateValidation;
 use crate::markdown_file::MarkdownFile;
 use crate::markdown_file::PersistReason;
+use crate::markdown_files::MarkdownFiles;
 use crate::obsidian_repository::ObsidianRepository;
 use crate::output_file_writer::ColumnAlignment;
 use crate::output_file_writer::OutputFileWriter;
@@ -62,45 +70,7 @@ impl ReportDefinition for PersistReasonsTable {
         Ok(items
             .iter()
             .map(|item| {
-                let (before, after, reason_info) = match &item.reason {
-                    PersistReason::DateCreatedUpdated { reason } => {
-                        let (before, after) =
-                            item.created_date_validation.clone().unwrap_or_default();
-                        (before, after, reason.to_string())
-                    },
-                    PersistReason::DateModifiedUpdated { reason } => {
-                        let (before, after) =
-                            item.modified_date_validation.clone().unwrap_or_default();
-                        (before, after, reason.to_string())
-                    },
-                    PersistReason::DateCreatedFixApplied => {
-                        let (before, after) = item.date_created_fix.clone().unwrap_or_default();
-                        (before, after, String::new())
-                    },
-                    PersistReason::BackPopulated => (
-                        String::new(),
-                        String::new(),
-                        format!("{} {INSTANCES}", item.back_populate_count),
-                    ),
-                    PersistReason::ImageReferencesModified => (
-                        String::new(),
-                        String::new(),
-                        format!("{} {INSTANCES}", item.image_reference_count),
-                    ),
-                    PersistReason::FrontmatterCreated => {
-                        (String::new(), String::new(), String::new())
-                    },
-                    PersistReason::LinksCanonicalized => (
-                        String::new(),
-                        String::new(),
-                        format!("{} {INSTANCES}", item.canonical_link_count),
-                    ),
-                    PersistReason::PhantomLinksResolved => (
-                        String::new(),
-                        String::new(),
-                        format!("{} {INSTANCES}", item.phantom_link_count),
-                    ),
-                };
+                let (before, after, reason_info) = item.change();
 
                 vec![
                     item.wikilink.clone(),
@@ -127,38 +97,212 @@ impl ReportDefinition for PersistReasonsTable {
     fn level(&self) -> &'static str { LEVEL2 }
 }
 
+struct PersistReasonCountsTable;
+
+impl ReportDefinition for PersistReasonCountsTable {
+    type Item = PersistReasonCount;
+
+    fn headers(&self) -> Vec<&str> { vec![REASON, TABLE_HEADER_NOTES, TABLE_HEADER_HELD] }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|count| {
+                vec![
+                    count.reason.clone(),
+                    count.notes.to_string(),
+                    count.held.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { None }
+
+    fn description(&self, _: &[Self::Item]) -> String {
+        PERSIST_REASON_COUNTS_DESCRIPTION.to_string()
+    }
+
+    fn level(&self) -> &'static str { LEVEL2 }
+}
+
+/// How many notes will be written for a reason, and how many had it held.
+#[derive(Clone)]
+struct PersistReasonCount {
+    reason: String,
+    notes:  usize,
+    held:   usize,
+}
+
 #[derive(Clone)]
 pub(super) struct PersistReasonData {
-    back_populate_count:      usize,
-    canonical_link_count:     usize,
-    date_created_fix:         Option<(String, String)>,
+    back_populate_count:       usize,
+    canonical_link_count:      usize,
+    date_created_fix:          Option<(String, String)>,
+    date_mention_count:        usize,
+    date_property_validations: Vec<DatePropertyValidation>,
     // `created_date_validation` stores before and after date strings.
-    created_date_validation:  Option<(String, String)>,
-    modified_date_validation: Option<(String, String)>,
+    created_date_validation:   Option<(String, String)>,
+    modified_date_validation:  Option<(String, String)>,
     // `full_path` orders rows before report chunking.
-    full_path:                PathBuf,
-    image_reference_count:    usize,
-    parent_path:              String,
-    phantom_link_count:       usize,
-    reason:                   PersistReason,
-    wikilink:                 String,
+    full_path:                 PathBuf,
+    heading_link_repair_count: usize,
+    image_reference_count:     usize,
+    parent_path:               String,
+    phantom_link_count:        usize,
+    reason:                    PersistReason,
+    regex_replacement_count:   usize,
+    self_link_count:           usize,
+    wikilink:                  String,
+    wikilink_repair_count:     usize,
+}
+
+impl PersistReasonData {
+    /// The before and after values and the detail shown for this row's reason.
+    fn change(&self) -> (String, String, String) {
+        match &self.reason {
+            PersistReason::DateCreatedUpdated { reason } => {
+                let (before, after) = self.created_date_validation.clone().unwrap_or_default();
+                (before, after, reason.to_string())
+            },
+            PersistReason::DateModifiedUpdated { reason } => {
+                let (before, after) = self.modified_date_validation.clone().unwrap_or_default();
+                (before, after, reason.to_string())
+            },
+            PersistReason::DatePropertyUpdated { property } => self.date_property_change(property),
+            PersistReason::DateCreatedFixApplied => {
+                let (before, after) = self.date_created_fix.clone().unwrap_or_default();
+                (before, after, String::new())
+            },
+            PersistReason::BackPopulated => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.back_populate_count),
+            ),
+            PersistReason::DateMentionsLinked => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.date_mention_count),
+            ),
+            PersistReason::HeadingLinksRepaired => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.heading_link_repair_count),
+            ),
+            PersistReason::ImageReferencesModified => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.image_reference_count),
+            ),
+            PersistReason::EntityAliasAdded { alias } => {
+                (String::new(), String::new(), alias.clone())
+            },
+            PersistReason::FrontmatterCreated => (String::new(), String::new(), String::new()),
+            PersistReason::AdmonitionsConverted { count }
+            | PersistReason::AliasesGenerated { count }
+            | PersistReason::BlockIdsAdded { count }
+            | PersistReason::ConflictCopyMerged { count }
+            | PersistReason::DuplicateFrontmatterKeysFixed { count }
+            | PersistReason::EntityIndexLinksAdded { count }
+            | PersistReason::FlashcardIdsAdded { count }
+            | PersistReason::FrontmatterLinksCanonicalized { count }
+            | PersistReason::FrontmatterLinkValuesFixed { count }
+            | PersistReason::HeadingLinksRetargeted { count }
+            | PersistReason::ImpossibleDatesFixed { count }
+            | PersistReason::ListsNormalized { count }
+            | PersistReason::PropertyTypesCoerced { count }
+            | PersistReason::PunctuationNormalized { count }
+            | PersistReason::SectionsSplit { count } => {
+                (String::new(), String::new(), format!("{count} {INSTANCES}"))
+            },
+            PersistReason::CustomReplacementsApplied { source, count } => (
+                String::new(),
+                String::new(),
+                format!("{source}: {count} {INSTANCES}"),
+            ),
+            PersistReason::LinksCanonicalized => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.canonical_link_count),
+            ),
+            PersistReason::JournalDateAligned { date: due }
+            | PersistReason::NextReviewSet { due } => (
+                String::new(),
+                String::new(),
+                due.format(FORMAT_DATE).to_string(),
+            ),
+            PersistReason::PeriodicNoteLinked { parent } => {
+                (String::new(), String::new(), parent.clone())
+            },
+            PersistReason::PhantomLinksResolved => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.phantom_link_count),
+            ),
+            PersistReason::RegexReplacementsApplied => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.regex_replacement_count),
+            ),
+            PersistReason::SelfLinksCleaned => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.self_link_count),
+            ),
+            PersistReason::TableOfContentsUpdated { action } => {
+                (String::new(), String::new(), action.to_string())
+            },
+            PersistReason::WikilinksRepaired => (
+                String::new(),
+                String::new(),
+                format!("{} {INSTANCES}", self.wikilink_repair_count),
+            ),
+        }
+    }
+
+    /// The value a `date_properties` property had, the wikilink it becomes and what was wrong.
+    fn date_property_change(&self, property: &str) -> (String, String, String) {
+        self.date_property_validations
+            .iter()
+            .find(|validation| validation.property == property)
+            .map(|validation| {
+                (
+                    validation.frontmatter.clone(),
+                    validation.fixed.clone().unwrap_or_default(),
+                    format!("{property}: {}", validation.issue),
+                )
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl ObsidianRepository {
     pub(super) fn write_persist_reasons_report(
         &self,
         validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let mut persist_data: Vec<PersistReasonData> = self
-            .markdown_files
-            .files_to_persist()
+        let mut persist_data: Vec<PersistReasonData> = files_to_persist
             .iter()
             .filter(|file| !file.persist_reasons.is_empty())
             .flat_map(|file| Self::build_persist_data_for_file(file, validated_config))
             .collect();
+        let reason_counts = self.count_persist_reasons(files_to_persist);
 
-        if persist_data.is_empty() {
+        if persist_data.is_empty() && reason_counts.is_empty() {
             return Ok(());
         }
 
@@ -177,6 +321,7 @@ impl ObsidianRepository {
 
         output_file_writer.writeln(LEVEL1, FILES_TO_BE_UPDATED)?;
         output_file_writer.writeln("", "")?;
+        ReportWriter::new(reason_counts).write(&PersistReasonCountsTable, output_file_writer)?;
 
         for chunk in persist_data.chunks(REPORT_CHUNK_SIZE) {
             let persist_reasons_table = PersistReasonsTable;
@@ -187,6 +332,39 @@ impl ObsidianRepository {
         Ok(())
     }
 
+    /// One row per reason, counting each note once however often the reason appears in it.
+    fn count_persist_reasons(&self, files_to_persist: &[MarkdownFile]) -> Vec<PersistReasonCount> {
+        let mut counts: BTreeMap<PersistReasonKind, PersistReasonCount> = BTreeMap::new();
+        let mut count = |reasons: &[PersistReason], held: bool| {
+            let mut seen = BTreeSet::new();
+            for reason in reasons {
+                if !seen.insert(reason.kind()) {
+                    continue;
+                }
+                let count = counts
+                    .entry(reason.kind())
+                    .or_insert_with(|| PersistReasonCount {
+                        reason: reason.to_string(),
+                        notes:  0,
+                        held:   0,
+                    });
+                if held {
+                    count.held += 1;
+                } else {
+                    count.notes += 1;
+                }
+            }
+        };
+
+        for markdown_file in files_to_persist {
+            count(&markdown_file.persist_reasons, false);
+        }
+        for markdown_file in &self.markdown_files {
+            count(&markdown_file.held_persist_reasons, true);
+        }
+        counts.into_values().collect()
+    }
+
     fn build_persist_data_for_file(
         markdown_file: &MarkdownFile,
         validated_config: &ValidatedConfig,
@@ -223,29 +401,37 @@ impl ObsidianRepository {
 
         let back_populate_count = markdown_file.back_populate_matches.unambiguous.len();
         let canonical_link_count = markdown_file.canonical_link_matches.len();
+        let date_mention_count = markdown_file.date_mention_matches.len();
+        let heading_link_repair_count = markdown_file.heading_link_repair_matches.len();
         let phantom_link_count = markdown_file.phantom_link_matches.len();
+        let regex_replacement_count = markdown_file.regex_replacement_matches.len();
+        let self_link_count = markdown_file.self_link_matches.len();
+        let wikilink_repair_count = markdown_file.wikilink_repair_matches.len();
         let image_reference_count = markdown_file
             .persist_reasons
             .iter()
             .filter(|&r| matches!(r, PersistReason::ImageReferencesModified))
             .count();
 
+        let date_link_format = validated_config.date_link_format();
         let created_date_validation = Some(Self::format_date_validation(
             &markdown_file.created_date_validation,
+            date_link_format,
         ));
         let modified_date_validation = Some(Self::format_date_validation(
             &markdown_file.modified_date_validation,
+            date_link_format,
         ));
         let date_created_fix = Some({
             let formatted_date = markdown_file
                 .created_date_validation
                 .operational_file_system_date()
-                .format(FORMAT_DATE);
+                .format(date_link_format);
             let fixed_formatted = markdown_file
                 .date_created_fix_validation
                 .fixed
                 .map(|d| {
-                    let formatted = d.format(FORMAT_DATE);
+                    let formatted = d.format(date_link_format);
                     format!("{OPENING_WIKILINK}{formatted}{CLOSING_WIKILINK}")
                 })
                 .unwrap_or_default();
@@ -264,20 +450,29 @@ impl ObsidianRepository {
                 reason: reason.clone(),
                 back_populate_count,
                 canonical_link_count,
+                date_mention_count,
+                heading_link_repair_count,
                 image_reference_count,
                 parent_path: parent_path.clone(),
                 phantom_link_count,
+                regex_replacement_count,
+                self_link_count,
+                wikilink_repair_count,
                 created_date_validation: created_date_validation.clone(),
                 modified_date_validation: modified_date_validation.clone(),
                 date_created_fix: date_created_fix.clone(),
+                date_property_validations: markdown_file.date_property_validations.clone(),
             })
             .collect()
     }
 
-    fn format_date_validation(date_validation: &DateValidation) -> (String, String) {
+    fn format_date_validation(
+        date_validation: &DateValidation,
+        date_link_format: &str,
+    ) -> (String, String) {
         let formatted_date = date_validation
             .operational_file_system_date()
-            .format(FORMAT_DATE);
+            .format(date_link_format);
         (
             date_validation.frontmatter.clone().unwrap_or_default(),
             format!("{OPENING_WIKILINK}{formatted_date}{CLOSING_WIKILINK}"),
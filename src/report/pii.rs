@@ -0,0 +1,88 @@
+use std::error::Error;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_DETECTOR;
+use super::constants::TABLE_HEADER_FOLDER;
+use super::constants::TABLE_HEADER_MATCHES;
+use super::constants::TABLE_HEADER_NOTES;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::PERSONAL_DATA;
+use crate::constants::PERSONAL_DATA_DESCRIPTION;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::PiiFolderSummary;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+
+struct PersonalDataTable;
+
+impl ReportDefinition for PersonalDataTable {
+    type Item = PiiFolderSummary;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FOLDER,
+            TABLE_HEADER_DETECTOR,
+            TABLE_HEADER_MATCHES,
+            TABLE_HEADER_NOTES,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|summary| {
+                vec![
+                    support::escape_pipe(&summary.folder),
+                    support::escape_pipe(&summary.detector),
+                    summary.matches.to_string(),
+                    summary.notes.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(PERSONAL_DATA.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Match(
+                items.iter().map(|summary| summary.matches).sum(),
+            ))
+            .text_with_newline("")
+            .no_space(PERSONAL_DATA_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_pii_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ReportWriter::new(self.pii_summaries.clone()).write(&PersonalDataTable, output_file_writer)
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_PROPERTY;
+use super::constants::TABLE_HEADER_VALUE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::CANNOT_CONVERT;
+use crate::constants::CONVERTS_TO;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::MOST_COMMON_TYPE;
+use crate::constants::PROPERTY_TYPES;
+use crate::constants::PROPERTY_TYPES_DESCRIPTION;
+use crate::constants::PROPERTY_TYPES_FIX_DISABLED;
+use crate::constants::TIE;
+use crate::constants::TYPE;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::PropertyTypeMismatch;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct PropertyTypesTable {
+    coerce_property_types: bool,
+}
+
+impl ReportDefinition for PropertyTypesTable {
+    type Item = PropertyTypeMismatch;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_PROPERTY,
+            MOST_COMMON_TYPE,
+            TABLE_HEADER_FILE_NAME,
+            TYPE,
+            TABLE_HEADER_VALUE,
+            CONVERTS_TO,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|mismatch| {
+                vec![
+                    mismatch.property.clone(),
+                    mismatch
+                        .majority
+                        .map_or_else(|| TIE.to_string(), |majority| majority.to_string()),
+                    mismatch
+                        .file_path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    mismatch.property_type.to_string(),
+                    support::format_yaml_value(&mismatch.value),
+                    mismatch
+                        .coerced
+                        .as_ref()
+                        .map_or_else(|| CANNOT_CONVERT.to_string(), support::format_yaml_value),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(PROPERTY_TYPES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_properties: HashSet<&String> =
+            items.iter().map(|mismatch| &mismatch.property).collect();
+        let unique_files: HashSet<&PathBuf> =
+            items.iter().map(|mismatch| &mismatch.file_path).collect();
+
+        let description = DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Property(unique_properties.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(PROPERTY_TYPES_DESCRIPTION);
+
+        if self.coerce_property_types {
+            description.build()
+        } else {
+            description
+                .text_with_newline("")
+                .no_space(PROPERTY_TYPES_FIX_DISABLED)
+                .build()
+        }
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_property_types_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // Grouped by property, so every note holding an odd type of one property is together.
+        let mut mismatches = self.property_type_mismatches.clone();
+        mismatches.sort_by(|a, b| a.property.cmp(&b.property));
+
+        let report_writer = ReportWriter::new(mismatches);
+        report_writer.write(
+            &PropertyTypesTable {
+                coerce_property_types: validated_config.coerce_property_types(),
+            },
+            output_file_writer,
+        )
+    }
+}
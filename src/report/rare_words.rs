@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::RARE_WORD_LINE_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_FOUND_IN;
+use super::constants::TABLE_HEADER_LINE;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::COMMON_WORD;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::RARE_WORDS;
+use crate::constants::RARE_WORDS_DESCRIPTION;
+use crate::constants::TEXT;
+use crate::constants::WORD;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::RareWord;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct RareWordsTable;
+
+impl ReportDefinition for RareWordsTable {
+    type Item = RareWord;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_FOUND_IN,
+            TABLE_HEADER_LINE,
+            WORD,
+            COMMON_WORD,
+            TEXT,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|rare_word| {
+                vec![
+                    rare_word
+                        .file_path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    rare_word.source.to_string(),
+                    rare_word
+                        .line_number
+                        .map(|line_number| line_number.to_string())
+                        .unwrap_or_default(),
+                    rare_word.word.clone(),
+                    rare_word.common_word.clone(),
+                    support::escape_pipe(&support::escape_brackets(&rare_word.text)),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(RARE_WORDS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&PathBuf> =
+            items.iter().map(|rare_word| &rare_word.file_path).collect();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(RARE_WORDS_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, RARE_WORD_LINE_COLUMN_INDEX))
+    }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_rare_words_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.collect_rare_words(validated_config)?);
+        report_writer.write(&RareWordsTable, output_file_writer)
+    }
+}
@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_MINUTES_AGO;
+use super::constants::TABLE_HEADER_SKIPPED_CHANGES;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::RECENTLY_MODIFIED_NOTES;
+use crate::constants::RECENTLY_MODIFIED_NOTES_DESCRIPTION;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::RecentlyModifiedNote;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct RecentlyModifiedNotesTable;
+
+impl ReportDefinition for RecentlyModifiedNotesTable {
+    type Item = RecentlyModifiedNote;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_MINUTES_AGO,
+            TABLE_HEADER_SKIPPED_CHANGES,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|recently_modified_note| {
+                vec![
+                    recently_modified_note
+                        .path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    recently_modified_note.minutes_ago.to_string(),
+                    support::escape_pipe(&recently_modified_note.changes.join(", ")),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(RECENTLY_MODIFIED_NOTES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .text_with_newline("")
+            .no_space(RECENTLY_MODIFIED_NOTES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_recently_modified_notes_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ReportWriter::new(self.recently_modified_notes.clone())
+            .write(&RecentlyModifiedNotesTable, output_file_writer)
+    }
+}
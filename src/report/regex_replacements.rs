@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::constants::TABLE_HEADER_PATTERN;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::AFTER;
+use crate::constants::BEFORE;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL1;
+use crate::constants::REGEX_REPLACEMENTS;
+use crate::constants::REGEX_REPLACEMENTS_DESCRIPTION;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::RegexReplacementMatch;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct RegexReplacementsTable;
+
+impl ReportDefinition for RegexReplacementsTable {
+    type Item = RegexReplacementMatch;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_PATTERN,
+            BEFORE,
+            AFTER,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|replacement_match| {
+                vec![
+                    Path::new(&replacement_match.relative_path)
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    replacement_match.line_number.to_string(),
+                    support::escape_pipe(&format!("`{}`", replacement_match.pattern)),
+                    escape_text(&replacement_match.found_text),
+                    escape_text(&replacement_match.replacement),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(REGEX_REPLACEMENTS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&String> = items.iter().map(|m| &m.relative_path).collect();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Replacement(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(REGEX_REPLACEMENTS_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+fn escape_text(text: &str) -> String { support::escape_pipe(&support::escape_brackets(text)) }
+
+impl ObsidianRepository {
+    pub(super) fn write_regex_replacements_report(
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut matches: Vec<RegexReplacementMatch> = files_to_persist
+            .iter()
+            .flat_map(|file| file.regex_replacement_matches.clone())
+            .collect();
+        matches.sort_by(|a, b| {
+            a.relative_path
+                .to_lowercase()
+                .cmp(&b.relative_path.to_lowercase())
+                .then_with(|| a.line_number.cmp(&b.line_number))
+                .then_with(|| a.position.cmp(&b.position))
+        });
+
+        let report_writer = ReportWriter::new(matches);
+        report_writer.write(&RegexReplacementsTable, output_file_writer)
+    }
+}
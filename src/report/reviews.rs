@@ -0,0 +1,139 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_DATE_MODIFIED;
+use super::constants::TABLE_HEADER_DAYS_OVERDUE;
+use super::constants::TABLE_HEADER_DUE;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_RULE;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::config::ReviewRule;
+use crate::constants::ALL_NOTES;
+use crate::constants::FORMAT_DATE;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::NEEDS_REVIEW;
+use crate::constants::NEEDS_REVIEW_DESCRIPTION;
+use crate::constants::NEEDS_REVIEW_NEXT_REVIEW_DISABLED;
+use crate::constants::TAG_PREFIX;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::OverdueReview;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct NeedsReviewTable {
+    set_next_review: bool,
+}
+
+/// The notes a rule covers: its folder, its tag, both, or every note.
+fn rule_scope(rule: &ReviewRule) -> String {
+    let scope: Vec<String> = [
+        rule.folder
+            .as_ref()
+            .map(|folder| folder.display().to_string()),
+        rule.tag
+            .as_deref()
+            .map(|tag| format!("{TAG_PREFIX}{}", tag.trim().trim_start_matches(TAG_PREFIX))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if scope.is_empty() {
+        ALL_NOTES.to_string()
+    } else {
+        scope.join(" ")
+    }
+}
+
+impl ReportDefinition for NeedsReviewTable {
+    type Item = OverdueReview;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_DATE_MODIFIED,
+            TABLE_HEADER_DUE,
+            TABLE_HEADER_DAYS_OVERDUE,
+            TABLE_HEADER_RULE,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|review| {
+                vec![
+                    review
+                        .file_path
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    review.date_modified.format(FORMAT_DATE).to_string(),
+                    review.due.format(FORMAT_DATE).to_string(),
+                    review.days_overdue.to_string(),
+                    format!("{} ({} days)", rule_scope(&review.rule), review.rule.days),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(NEEDS_REVIEW.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let description = DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .text_with_newline("")
+            .no_space(NEEDS_REVIEW_DESCRIPTION);
+
+        if self.set_next_review {
+            description.build()
+        } else {
+            description
+                .text_with_newline("")
+                .no_space(NEEDS_REVIEW_NEXT_REVIEW_DISABLED)
+                .build()
+        }
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_needs_review_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.overdue_reviews.clone());
+        report_writer.write(
+            &NeedsReviewTable {
+                set_next_review: validated_config.set_next_review(),
+            },
+            output_file_writer,
+        )
+    }
+}
@@ -0,0 +1,104 @@
+use std::error::Error;
+use std::iter;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::MATCHING;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::query;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct SavedReportTable<'a> {
+    name:    &'a str,
+    query:   &'a str,
+    columns: Vec<String>,
+}
+
+impl ReportDefinition for SavedReportTable<'_> {
+    type Item = Vec<String>;
+
+    fn headers(&self) -> Vec<&str> {
+        iter::once(TABLE_HEADER_FILE_NAME)
+            .chain(self.columns.iter().map(String::as_str))
+            .collect()
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![ColumnAlignment::Left; self.columns.len() + 1]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(index, cell)| {
+                        if index == 0 {
+                            cell.to_wikilink()
+                        } else {
+                            support::escape_pipe(cell)
+                        }
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(self.name.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .text(MATCHING)
+            .quoted_text(self.query)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    /// Renders each of the config's `saved_reports` from an in-memory index of the vault as this
+    /// run sees it. Reports without matches are left out like any other empty report.
+    pub(super) fn write_saved_reports(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if validated_config.saved_reports().is_empty() {
+            return Ok(());
+        }
+
+        let connection = self.in_memory_vault_index(validated_config.obsidian_path())?;
+        for saved_report in validated_config.saved_reports() {
+            let (columns, rows) =
+                query::saved_report_rows(&connection, &saved_report.query, &saved_report.columns)?;
+            ReportWriter::new(rows).write(
+                &SavedReportTable {
+                    name: &saved_report.name,
+                    query: &saved_report.query,
+                    columns,
+                },
+                output_file_writer,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::constants::UNPARSABLE_LINE_NUMBER_SORT_KEY;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL2;
+use crate::constants::REASON;
+use crate::constants::SELF_LINKS;
+use crate::constants::SELF_LINKS_DESCRIPTION;
+use crate::constants::SELF_LINKS_FIX_DISABLED;
+use crate::constants::SOURCE_TEXT;
+use crate::constants::WIKILINKS;
+use crate::constants::WILL_REPLACE_WITH;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::MarkdownFile;
+use crate::markdown_file::SelfLinkMatch;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct SelfLinksTable {
+    fix_self_links: bool,
+}
+
+impl ReportDefinition for SelfLinksTable {
+    type Item = SelfLinkMatch;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            WIKILINKS,
+            REASON,
+            WILL_REPLACE_WITH,
+            SOURCE_TEXT,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let mut rows: Vec<Vec<String>> = items
+            .iter()
+            .map(|self_link_match| {
+                let file_stem = Path::new(&self_link_match.relative_path)
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or_default();
+
+                let replacement = support::escape_pipe(&self_link_match.replacement);
+
+                vec![
+                    file_stem.to_wikilink(),
+                    self_link_match.line_number.to_string(),
+                    support::escape_pipe(&support::escape_brackets(&self_link_match.found_text)),
+                    self_link_match.kind.to_string(),
+                    replacement.clone(),
+                    support::escape_brackets(&replacement),
+                ]
+            })
+            .collect();
+
+        rows.sort_by(|a, b| {
+            let file_cmp = a[FILE_COLUMN_INDEX]
+                .to_lowercase()
+                .cmp(&b[FILE_COLUMN_INDEX].to_lowercase());
+            if file_cmp == Ordering::Equal {
+                a[LINE_NUMBER_COLUMN_INDEX]
+                    .parse::<usize>()
+                    .unwrap_or(UNPARSABLE_LINE_NUMBER_SORT_KEY)
+                    .cmp(
+                        &b[LINE_NUMBER_COLUMN_INDEX]
+                            .parse::<usize>()
+                            .unwrap_or(UNPARSABLE_LINE_NUMBER_SORT_KEY),
+                    )
+            } else {
+                file_cmp
+            }
+        });
+
+        Ok(rows)
+    }
+
+    fn title(&self) -> Option<String> { Some(SELF_LINKS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&String> = items.iter().map(|m| &m.relative_path).collect();
+
+        let description = DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Wikilink(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(SELF_LINKS_DESCRIPTION);
+
+        if self.fix_self_links {
+            description.build()
+        } else {
+            description
+                .text_with_newline("")
+                .no_space(SELF_LINKS_FIX_DISABLED)
+                .build()
+        }
+    }
+
+    fn level(&self) -> &'static str { LEVEL2 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+impl ObsidianRepository {
+    /// With `fix_self_links` on, only files in the changeset are listed, like the other
+    /// link-rewrite reports; otherwise every file is listed because nothing will be persisted.
+    pub(super) fn write_self_links_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let fix_self_links = validated_config.fix_self_links();
+
+        let matches: Vec<SelfLinkMatch> = if fix_self_links {
+            files_to_persist
+                .iter()
+                .flat_map(|file| file.self_link_matches.clone())
+                .collect()
+        } else {
+            self.markdown_files
+                .iter()
+                .filter(|file| MarkdownFile::has_self_link_matches(file))
+                .flat_map(|file| file.self_link_matches.clone())
+                .collect()
+        };
+
+        let report_writer = ReportWriter::new(matches);
+        report_writer.write(&SelfLinksTable { fix_self_links }, output_file_writer)
+    }
+}
@@ -0,0 +1,108 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::PERCENT_MULTIPLIER;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::MERGE_INTO;
+use crate::constants::SIMILAR_TITLES;
+use crate::constants::SIMILAR_TITLES_DESCRIPTION;
+use crate::constants::SIMILARITY;
+use crate::constants::TEXT;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::SimilarTitlePair;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct SimilarTitlesTable;
+
+impl ReportDefinition for SimilarTitlesTable {
+    type Item = SimilarTitlePair;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TEXT,
+            TABLE_HEADER_FILE_NAME,
+            TEXT,
+            SIMILARITY,
+            MERGE_INTO,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|pair| {
+                vec![
+                    note_link(&pair.first),
+                    support::escape_pipe(&pair.first_name),
+                    note_link(&pair.second),
+                    support::escape_pipe(&pair.second_name),
+                    format!("{:.0}%", pair.similarity * PERCENT_MULTIPLIER),
+                    pair.merge_into
+                        .as_deref()
+                        .map(note_link)
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(SIMILAR_TITLES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Pair(items.len()))
+            .text_with_newline("")
+            .no_space(SIMILAR_TITLES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+fn note_link(path: &Path) -> String {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_wikilink()
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_similar_titles_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.collect_similar_titles(validated_config));
+        report_writer.write(&SimilarTitlesTable, output_file_writer)
+    }
+}
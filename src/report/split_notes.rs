@@ -0,0 +1,88 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::HEADING;
+use crate::constants::LEVEL1;
+use crate::constants::NEW_NOTE;
+use crate::constants::SECTION_NOTE_NAME_TAKEN;
+use crate::constants::SPLIT_NOTES;
+use crate::constants::SPLIT_NOTES_DESCRIPTION;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::SectionSplit;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct SplitNotesTable;
+
+impl ReportDefinition for SplitNotesTable {
+    type Item = SectionSplit;
+
+    fn headers(&self) -> Vec<&str> { vec![TABLE_HEADER_FILE_NAME, HEADING, NEW_NOTE] }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|split| {
+                vec![
+                    split
+                        .source
+                        .file_stem()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default()
+                        .to_wikilink(),
+                    support::escape_pipe(&split.heading),
+                    split.note_name().map_or_else(
+                        || SECTION_NOTE_NAME_TAKEN.to_string(),
+                        ToWikilink::to_wikilink,
+                    ),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(SPLIT_NOTES.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Section(items.len()))
+            .text_with_newline("")
+            .no_space(SPLIT_NOTES_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_split_notes_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report_writer = ReportWriter::new(self.section_splits.clone());
+        report_writer.write(&SplitNotesTable, output_file_writer)
+    }
+}
@@ -1,19 +1,31 @@
+use std::fmt::Write;
 use std::path::Path;
 
+use serde_yaml::Value;
+
+use super::constants::BYTES_PER_KILOBYTE;
+use super::constants::DEEP_LINK_FILENAME_PARAMETER;
+use super::constants::DEEP_LINK_LINE_PARAMETER;
+use super::constants::DEEP_LINK_PREFIX;
+use super::constants::DEEP_LINK_TEXT;
 use super::constants::HIGHLIGHT_CLOSE_TAG;
 use super::constants::HIGHLIGHT_EXTRA_TAG_CAPACITY_MULTIPLIER;
 use super::constants::HIGHLIGHT_OPEN_TAG;
 use super::constants::INVALID_UTF8_BOUNDARY_DETECTED;
+use super::constants::SIZE_UNITS;
+use super::constants::URI_UNRESERVED_PUNCTUATION;
 use crate::constants::BACKSLASH;
 use crate::constants::BACKSLASH_PARITY_DIVISOR;
 use crate::constants::CLOSING_BRACKET;
 use crate::constants::CLOSING_WIKILINK;
 use crate::constants::ESCAPED_BRACKET_CLOSE;
 use crate::constants::ESCAPED_BRACKET_OPEN;
+use crate::constants::ESCAPED_PIPE;
 use crate::constants::ESCAPED_PIPE_CAPACITY_MULTIPLIER;
 use crate::constants::OPENING_BRACKET;
 use crate::constants::OPENING_WIKILINK;
 use crate::constants::PIPE;
+use crate::wikilink;
 
 // `escape_pipe` escapes unescaped Markdown table pipes.
 pub(super) fn escape_pipe(text: &str) -> String {
@@ -52,6 +64,53 @@ pub(super) fn escape_brackets(text: &str) -> String {
         .replace(CLOSING_BRACKET, ESCAPED_BRACKET_CLOSE)
 }
 
+/// A `[open](obsidian://advanced-uri?...)` link to `line_cell` of the note `file_cell` links
+/// to, or nothing when either cell doesn't hold one. A cell listing several lines links to the
+/// first.
+pub(super) fn deep_link(vault: &str, file_cell: &str, line_cell: &str) -> String {
+    let Some(target) =
+        wikilink::extract_wikilinks(&file_cell.replace(ESCAPED_PIPE, &PIPE.to_string()))
+            .valid
+            .into_iter()
+            .next()
+            .map(|spanned_wikilink| spanned_wikilink.wikilink.target)
+    else {
+        return String::new();
+    };
+    let Some(line) = line_cell
+        .split(|ch: char| !ch.is_ascii_digit())
+        .find_map(|digits| digits.parse::<usize>().ok())
+    else {
+        return String::new();
+    };
+
+    format!(
+        "[{DEEP_LINK_TEXT}]({DEEP_LINK_PREFIX}{}{DEEP_LINK_FILENAME_PARAMETER}{}{DEEP_LINK_LINE_PARAMETER}{line})",
+        percent_encode(vault),
+        percent_encode(&target)
+    )
+}
+
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        let ch = char::from(byte);
+        if ch.is_ascii_alphanumeric() || URI_UNRESERVED_PUNCTUATION.contains(&ch) {
+            encoded.push(ch);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// Frontmatter values are shown as JSON so a list and a string with commas in it read
+/// differently.
+pub(super) fn format_yaml_value(value: &Value) -> String {
+    let json = serde_json::to_string(value).unwrap_or_default();
+    escape_pipe(&escape_brackets(&json))
+}
+
 pub(super) fn format_wikilink(path: &Path, obsidian_path: &Path) -> String {
     let relative_path = path.strip_prefix(obsidian_path).unwrap_or(path);
     let display_name = path.file_stem().unwrap_or_default().to_string_lossy();
@@ -60,6 +119,22 @@ pub(super) fn format_wikilink(path: &Path, obsidian_path: &Path) -> String {
     format!("{OPENING_WIKILINK}{path_display}\\{PIPE}{display_name}{CLOSING_WIKILINK}")
 }
 
+/// `size` bytes in the largest unit it reaches, to one decimal: `812 B`, `1.4 KB`, `3.0 MB`.
+pub(super) fn format_size(size: u64) -> String {
+    let mut unit_index = 0;
+    let mut unit_size = 1;
+    while unit_index + 1 < SIZE_UNITS.len() && size >= unit_size * BYTES_PER_KILOBYTE {
+        unit_index += 1;
+        unit_size *= BYTES_PER_KILOBYTE;
+    }
+
+    if unit_index == 0 {
+        return format!("{size} {}", SIZE_UNITS[0]);
+    }
+    let tenths = size * 10 / unit_size;
+    format!("{}.{} {}", tenths / 10, tenths % 10, SIZE_UNITS[unit_index])
+}
+
 pub(super) fn highlight_matches(text: &str, positions: &[usize], match_length: usize) -> String {
     let mut result = String::with_capacity(text.len() * HIGHLIGHT_EXTRA_TAG_CAPACITY_MULTIPLIER);
     let mut last_end = 0;
@@ -85,3 +160,22 @@ pub(super) fn highlight_matches(text: &str, positions: &[usize], match_length: u
     result.push_str(&text[last_end..]);
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::deep_link;
+
+    #[test]
+    fn test_deep_link() {
+        assert_eq!(
+            deep_link("my notes", "[[Trip Plan]]", "12"),
+            "[open](obsidian://advanced-uri?vault=my%20notes&filename=Trip%20Plan&line=12)"
+        );
+        assert_eq!(
+            deep_link("notes", r"[[travel/Trip\|Trip]]", "3, 7"),
+            "[open](obsidian://advanced-uri?vault=notes&filename=travel%2FTrip&line=3)"
+        );
+        assert_eq!(deep_link("notes", "[[Trip]]", ""), "");
+        assert_eq!(deep_link("notes", "Trip", "4"), "");
+    }
+}
@@ -101,9 +101,10 @@ fn group_row((target, occurrences, files): (String, usize, HashSet<&PathBuf>)) -
 impl ObsidianRepository {
     pub(super) fn write_unresolved_links_report(
         &self,
+        validated_config: &ValidatedConfig,
         output_file_writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let unresolved_links = self.collect_unresolved_links();
+        let unresolved_links = self.collect_unresolved_links(validated_config.date_link_format());
         if unresolved_links.is_empty() {
             return Ok(());
         }
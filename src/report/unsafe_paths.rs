@@ -0,0 +1,110 @@
+use std::error::Error;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+use anyhow::anyhow;
+
+use super::constants::TABLE_HEADER_FIX;
+use super::constants::TABLE_HEADER_ISSUES;
+use super::constants::UNSAFE_PATHS_REPORT_CONFIG_REQUIRED;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::LENGTH;
+use crate::constants::LEVEL1;
+use crate::constants::PATH;
+use crate::constants::SUGGESTION;
+use crate::constants::UNSAFE_PATHS;
+use crate::constants::UNSAFE_PATHS_DESCRIPTION;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::PathIssue;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+
+struct UnsafePathsTable;
+
+impl ReportDefinition for UnsafePathsTable {
+    type Item = PathIssue;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            PATH,
+            LENGTH,
+            TABLE_HEADER_ISSUES,
+            SUGGESTION,
+            TABLE_HEADER_FIX,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        validated_config: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let obsidian_path = validated_config
+            .ok_or_else(|| anyhow!(UNSAFE_PATHS_REPORT_CONFIG_REQUIRED))?
+            .obsidian_path();
+
+        Ok(items
+            .iter()
+            .map(|issue| {
+                vec![
+                    relative_path(&issue.path, obsidian_path),
+                    issue.length.to_string(),
+                    issue
+                        .kinds
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    relative_path(&issue.suggested, obsidian_path),
+                    issue.fix.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(UNSAFE_PATHS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .text_with_newline("")
+            .no_space(UNSAFE_PATHS_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+fn relative_path(path: &Path, obsidian_path: &Path) -> String {
+    let relative_path = path.strip_prefix(obsidian_path).unwrap_or(path);
+    support::escape_pipe(&support::escape_brackets(&relative_path.to_string_lossy()))
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_unsafe_paths_report(
+        &self,
+        validated_config: &ValidatedConfig,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report =
+            ReportWriter::new(self.path_issues.clone()).with_validated_config(validated_config);
+        report.write(&UnsafePathsTable, output_file_writer)
+    }
+}
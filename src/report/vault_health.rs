@@ -0,0 +1,126 @@
+use std::error::Error;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::SPARKLINE_BARS;
+use super::constants::VAULT_HEALTH_MAX_SCORE;
+use super::constants::VAULT_HEALTH_TREND_ENTRIES;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::BROKEN_LINKS;
+use crate::constants::DATE;
+use crate::constants::DATE_MISMATCHES;
+use crate::constants::IMAGES;
+use crate::constants::LEVEL1;
+use crate::constants::MISSING_FRONTMATTER;
+use crate::constants::NOTES;
+use crate::constants::ORPHAN_IMAGES;
+use crate::constants::SCORE;
+use crate::constants::TREND;
+use crate::constants::VAULT_HEALTH;
+use crate::constants::VAULT_HEALTH_DESCRIPTION;
+use crate::constants::VAULT_HEALTH_SCORE;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::VaultHealthEntry;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::validated_config::ValidatedConfig;
+
+struct VaultHealthTable;
+
+impl ReportDefinition for VaultHealthTable {
+    type Item = VaultHealthEntry;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            DATE,
+            SCORE,
+            BROKEN_LINKS,
+            MISSING_FRONTMATTER,
+            ORPHAN_IMAGES,
+            DATE_MISMATCHES,
+            NOTES,
+            IMAGES,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+            ColumnAlignment::Right,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry.date.clone(),
+                    entry.score.to_string(),
+                    entry.broken_links.to_string(),
+                    entry.missing_frontmatter.to_string(),
+                    entry.orphan_images.to_string(),
+                    entry.date_mismatches.to_string(),
+                    entry.notes.to_string(),
+                    entry.images.to_string(),
+                ]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(VAULT_HEALTH.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let score = items.last().map_or(0, |entry| entry.score);
+
+        DescriptionBuilder::new()
+            .text(VAULT_HEALTH_SCORE)
+            .number(score)
+            .text(TREND)
+            .text(&sparkline(items))
+            .text_with_newline("")
+            .no_space(VAULT_HEALTH_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+/// One bar per entry, oldest first, from the lowest bar at 0 to the highest at a full score.
+fn sparkline(entries: &[VaultHealthEntry]) -> String {
+    let top_bar = SPARKLINE_BARS.len() - 1;
+    entries
+        .iter()
+        .map(|entry| {
+            SPARKLINE_BARS
+                [entry.score.min(VAULT_HEALTH_MAX_SCORE) * top_bar / VAULT_HEALTH_MAX_SCORE]
+        })
+        .collect()
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_vault_health_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let recent = self
+            .vault_health_history
+            .len()
+            .saturating_sub(VAULT_HEALTH_TREND_ENTRIES);
+
+        let report_writer = ReportWriter::new(self.vault_health_history[recent..].to_vec());
+        report_writer.write(&VaultHealthTable, output_file_writer)
+    }
+}
@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::FILE_COLUMN_INDEX;
+use super::constants::LINE_NUMBER_COLUMN_INDEX;
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::constants::UNPARSABLE_LINE_NUMBER_SORT_KEY;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::AFTER;
+use crate::constants::BEFORE;
+use crate::constants::FOUND;
+use crate::constants::IN;
+use crate::constants::LEVEL2;
+use crate::constants::REASON;
+use crate::constants::REPAIRED_WIKILINKS;
+use crate::constants::REPAIRED_WIKILINKS_DESCRIPTION;
+use crate::constants::SOURCE_TEXT;
+use crate::description_builder::DescriptionBuilder;
+use crate::markdown_file::WikilinkRepairMatch;
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct WikilinkRepairsTable;
+
+impl ReportDefinition for WikilinkRepairsTable {
+    type Item = WikilinkRepairMatch;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            BEFORE,
+            AFTER,
+            REASON,
+            SOURCE_TEXT,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        let mut rows: Vec<Vec<String>> = items
+            .iter()
+            .map(|repair_match| {
+                let file_stem = Path::new(&repair_match.relative_path)
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or_default();
+
+                let replacement = support::escape_pipe(&repair_match.replacement);
+
+                vec![
+                    file_stem.to_wikilink(),
+                    repair_match.line_number.to_string(),
+                    support::escape_pipe(&support::escape_brackets(&repair_match.found_text)),
+                    replacement.clone(),
+                    repair_match.kind.to_string(),
+                    support::escape_brackets(&replacement),
+                ]
+            })
+            .collect();
+
+        rows.sort_by(|a, b| {
+            let file_cmp = a[FILE_COLUMN_INDEX]
+                .to_lowercase()
+                .cmp(&b[FILE_COLUMN_INDEX].to_lowercase());
+            if file_cmp == Ordering::Equal {
+                a[LINE_NUMBER_COLUMN_INDEX]
+                    .parse::<usize>()
+                    .unwrap_or(UNPARSABLE_LINE_NUMBER_SORT_KEY)
+                    .cmp(
+                        &b[LINE_NUMBER_COLUMN_INDEX]
+                            .parse::<usize>()
+                            .unwrap_or(UNPARSABLE_LINE_NUMBER_SORT_KEY),
+                    )
+            } else {
+                file_cmp
+            }
+        });
+
+        Ok(rows)
+    }
+
+    fn title(&self) -> Option<String> { Some(REPAIRED_WIKILINKS.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&String> = items.iter().map(|m| &m.relative_path).collect();
+
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Wikilink(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text_with_newline("")
+            .no_space(REPAIRED_WIKILINKS_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL2 }
+
+    fn deep_link_columns(&self) -> Option<(usize, usize)> {
+        Some((FILE_COLUMN_INDEX, LINE_NUMBER_COLUMN_INDEX))
+    }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_wikilink_repairs_report(
+        files_to_persist: &MarkdownFiles,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let matches: Vec<WikilinkRepairMatch> = files_to_persist
+            .iter()
+            .flat_map(|file| file.wikilink_repair_matches.clone())
+            .collect();
+
+        let report_writer = ReportWriter::new(matches);
+        report_writer.write(&WikilinkRepairsTable, output_file_writer)
+    }
+}
@@ -0,0 +1,105 @@
+use std::error::Error;
+use std::ffi::OsStr;
+
+use anyhow::Result as AnyhowResult;
+
+use super::constants::TABLE_HEADER_FILE_NAME;
+use super::constants::TABLE_HEADER_LINE;
+use super::constants::TABLE_HEADER_WITHHELD;
+use super::support;
+use super::writer::ReportDefinition;
+use super::writer::ReportWriter;
+use crate::constants::FOUND;
+use crate::constants::LEVEL1;
+use crate::constants::TAG_PREFIX;
+use crate::constants::WITHHELD_FROM_EXPORT;
+use crate::constants::WITHHELD_FROM_EXPORT_DESCRIPTION;
+use crate::constants::WITHHELD_LINE_MATCHING;
+use crate::constants::WITHHELD_NOTE_TAGGED;
+use crate::description_builder::DescriptionBuilder;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::obsidian_repository::Withheld;
+use crate::obsidian_repository::WithheldContent;
+use crate::output_file_writer::ColumnAlignment;
+use crate::output_file_writer::OutputFileWriter;
+use crate::phrase::Phrase;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+
+struct WithheldContentTable;
+
+impl ReportDefinition for WithheldContentTable {
+    type Item = WithheldContent;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            TABLE_HEADER_FILE_NAME,
+            TABLE_HEADER_LINE,
+            TABLE_HEADER_WITHHELD,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        _: Option<&ValidatedConfig>,
+    ) -> AnyhowResult<Vec<Vec<String>>> {
+        Ok(items
+            .iter()
+            .map(|withheld_content| {
+                let file = withheld_content
+                    .path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or_default()
+                    .to_wikilink();
+                // The withheld text itself stays out of the report too - only the pattern shows.
+                let (line, withheld) = match &withheld_content.withheld {
+                    Withheld::Note { tag } => (
+                        String::new(),
+                        format!("{WITHHELD_NOTE_TAGGED} {TAG_PREFIX}{tag}"),
+                    ),
+                    Withheld::Line {
+                        line_number,
+                        pattern,
+                    } => (
+                        line_number.to_string(),
+                        support::escape_pipe(&format!("{WITHHELD_LINE_MATCHING} `{pattern}`")),
+                    ),
+                };
+                vec![file, line, withheld]
+            })
+            .collect())
+    }
+
+    fn title(&self) -> Option<String> { Some(WITHHELD_FROM_EXPORT.to_string()) }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Issue(items.len()))
+            .text_with_newline("")
+            .no_space(WITHHELD_FROM_EXPORT_DESCRIPTION)
+            .build()
+    }
+
+    fn level(&self) -> &'static str { LEVEL1 }
+}
+
+impl ObsidianRepository {
+    pub(super) fn write_withheld_content_report(
+        &self,
+        output_file_writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ReportWriter::new(self.withheld_content.clone())
+            .write(&WithheldContentTable, output_file_writer)
+    }
+}
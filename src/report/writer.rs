@@ -1,6 +1,9 @@
 use std::error::Error;
 
+use super::constants::TABLE_HEADER_OPEN;
 pub(super) use super::definition::ReportDefinition;
+use super::support;
+use crate::output_file_writer::ColumnAlignment;
 use crate::output_file_writer::OutputFileWriter;
 use crate::validated_config::ValidatedConfig;
 
@@ -57,9 +60,24 @@ impl<'a, T: Clone> ReportWriter<'a, T> {
             return Ok(());
         }
 
-        let headers = report.headers();
-        let alignments = report.alignments();
-        let rows = report.build_rows(&self.items, self.validated_config)?;
+        let mut headers = report.headers();
+        let mut alignments = report.alignments();
+        let mut rows = report.build_rows(&self.items, self.validated_config)?;
+
+        if let Some(vault) = output_file_writer.deep_link_vault()
+            && let Some((file_column, line_column)) = report.deep_link_columns()
+        {
+            headers.push(TABLE_HEADER_OPEN);
+            alignments.push(ColumnAlignment::Center);
+            for row in &mut rows {
+                let deep_link = support::deep_link(
+                    vault,
+                    row.get(file_column).map_or("", String::as_str),
+                    row.get(line_column).map_or("", String::as_str),
+                );
+                row.push(deep_link);
+            }
+        }
 
         output_file_writer.write_markdown_table(&headers, &rows, Some(&alignments))?;
 
@@ -3,11 +3,33 @@ use std::env::args;
 use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::fs;
+use std::io;
+use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 
+use chrono::Duration as ChronoDuration;
+use chrono::Local;
+
+#[cfg(feature = "bench")]
+use crate::benchmarks;
 use crate::config::Config;
-use crate::config::ConfiguredChanges;
+use crate::config_init;
+use crate::constants::APPLY_ARGS_START;
+use crate::constants::APPLY_COMMAND;
+use crate::constants::BACKED_UP;
+#[cfg(feature = "bench")]
+use crate::constants::BENCH_ARGS_START;
+#[cfg(feature = "bench")]
+use crate::constants::BENCH_COMMAND;
+use crate::constants::CACHE_FILE;
+use crate::constants::CACHE_FOLDER;
 use crate::constants::CONFIG_ARG_INDEX;
+use crate::constants::DAEMON_ARG_COUNT;
+use crate::constants::DAEMON_ARGS_START;
+use crate::constants::DAEMON_FLAG;
+use crate::constants::DAEMON_NEXT_RUN;
 use crate::constants::DEFAULT_TIMEZONE;
 #[cfg(debug_assertions)]
 use crate::constants::DEV;
@@ -16,17 +38,56 @@ use crate::constants::ERROR_OCCURRED;
 use crate::constants::ERROR_SOURCE;
 use crate::constants::ERROR_TYPE;
 use crate::constants::EXPECTED_ARG_COUNT;
+use crate::constants::EXPLAIN_CONFIG_FLAG;
+use crate::constants::FLAG_PREFIX;
+use crate::constants::FORMAT_DATE;
+use crate::constants::FORMAT_TIME_STAMP;
+use crate::constants::GENERATE_VAULT_ARGS_START;
+use crate::constants::GENERATE_VAULT_COMMAND;
+use crate::constants::GENERATED_VAULT_SUMMARY;
+use crate::constants::IDEMPOTENT;
+use crate::constants::INIT_ARGS_START;
+use crate::constants::INIT_COMMAND;
+use crate::constants::INIT_WROTE_CONFIG;
+use crate::constants::INTERVAL_FLAG;
+use crate::constants::NOT_IDEMPOTENT;
+use crate::constants::NOTIFICATION_NOT_SENT;
 use crate::constants::OBSIDIAN_KNIFE;
+use crate::constants::PATCH_APPLIED;
+use crate::constants::PROFILE_FLAG;
+use crate::constants::QUERY_ARGS_START;
+use crate::constants::QUERY_COMMAND;
+use crate::constants::REBUILD_CACHE_FLAG;
 #[cfg(not(debug_assertions))]
 use crate::constants::RELEASE;
+use crate::constants::SANDBOX_APPLIED;
+use crate::constants::SANDBOX_FLAG;
+use crate::constants::SANDBOX_PREFIX;
+use crate::constants::SKIPPED_CHANGED_ON_DISK;
 use crate::constants::TOTAL_TIME;
 use crate::constants::USAGE;
+use crate::constants::VAULT_HEALTH_HISTORY_FILE;
+use crate::constants::VAULT_INDEX_FILE;
+use crate::constants::VERIFY_IDEMPOTENT_FLAG;
+use crate::daemon;
+use crate::explain_config;
 use crate::frontmatter::FrontMatter;
+use crate::idempotency;
 use crate::markdown_file::MarkdownFile;
+use crate::markdown_file::ReplacementSource;
 use crate::obsidian_repository::ObsidianRepository;
+use crate::patch;
+use crate::patch::Patch;
+use crate::query;
+use crate::run_lock::RunLock;
+use crate::sandbox::Sandbox;
+use crate::sha256_cache::Sha256Cache;
 use crate::support;
 use crate::timer::Timer;
 use crate::validated_config::ChangeMode;
+use crate::validated_config::ValidatedConfig;
+use crate::vault_generator;
+use crate::vault_generator::VaultSpec;
 use crate::yaml_frontmatter::YamlFrontMatter;
 use crate::yaml_frontmatter::YamlFrontMatterError;
 
@@ -46,7 +107,22 @@ impl Display for MainError {
 
 impl Error for MainError {}
 
-pub(crate) fn run() -> Result<(), Box<dyn Error + Send + Sync>> {
+/// Runs the `ok` command line with the process's arguments.
+///
+/// # Errors
+///
+/// Returns the error that stopped the run, after printing it.
+pub fn run() -> Result<(), Box<dyn Error + Send + Sync>> { run_with_replacement_sources(&[]) }
+
+/// Like `run`, with the replacements `replacement_sources` find applied along with the
+/// built-in ones wherever the run analyzes the vault.
+///
+/// # Errors
+///
+/// Returns the error that stopped the run, after printing it.
+pub fn run_with_replacement_sources(
+    replacement_sources: &[&dyn ReplacementSource],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
     let _timer = Timer::new(TOTAL_TIME);
 
     #[cfg(debug_assertions)]
@@ -58,43 +134,294 @@ pub(crate) fn run() -> Result<(), Box<dyn Error + Send + Sync>> {
         env!("CARGO_PKG_VERSION")
     );
 
+    let args: Vec<String> = args().collect();
+    if args
+        .get(CONFIG_ARG_INDEX)
+        .is_some_and(|command| command == GENERATE_VAULT_COMMAND)
+    {
+        return generate_vault(args.get(GENERATE_VAULT_ARGS_START..).unwrap_or_default())
+            .or_else(handle_error);
+    }
+
+    if args
+        .get(CONFIG_ARG_INDEX)
+        .is_some_and(|command| command == INIT_COMMAND)
+    {
+        return init_config(args.get(INIT_ARGS_START..).unwrap_or_default()).or_else(handle_error);
+    }
+
+    if args
+        .get(CONFIG_ARG_INDEX)
+        .is_some_and(|command| command == QUERY_COMMAND)
+    {
+        return run_query(args.get(QUERY_ARGS_START..).unwrap_or_default()).or_else(handle_error);
+    }
+
+    if args
+        .get(CONFIG_ARG_INDEX)
+        .is_some_and(|command| command == APPLY_COMMAND)
+    {
+        return apply_patch(args.get(APPLY_ARGS_START..).unwrap_or_default()).or_else(handle_error);
+    }
+
+    #[cfg(feature = "bench")]
+    if args
+        .get(CONFIG_ARG_INDEX)
+        .is_some_and(|command| command == BENCH_COMMAND)
+    {
+        return benchmarks::run(args.get(BENCH_ARGS_START..).unwrap_or_default())
+            .or_else(handle_error);
+    }
+
+    let profile = requested_profile()?;
+    let profile = profile.as_deref();
+    if let [_, config_path, flag] = args_without_global_flags().as_slice() {
+        if flag == EXPLAIN_CONFIG_FLAG {
+            return explain_config(Path::new(config_path), profile).or_else(handle_error);
+        }
+        if flag == SANDBOX_FLAG {
+            return run_in_sandbox(Path::new(config_path), profile, replacement_sources)
+                .or_else(handle_error);
+        }
+        if flag == VERIFY_IDEMPOTENT_FLAG {
+            return verify_idempotent(Path::new(config_path), profile, replacement_sources)
+                .or_else(handle_error);
+        }
+    }
+
     let config_path = get_config_file()?;
+    let rebuild_cache = rebuild_cache_requested();
 
-    process_obsidian_repository(config_path).or_else(handle_error)
+    get_daemon_interval()?
+        .map_or_else(
+            || {
+                process_obsidian_repository(
+                    &config_path,
+                    profile,
+                    replacement_sources,
+                    None,
+                    rebuild_cache,
+                )
+            },
+            |interval| {
+                run_daemon(
+                    &config_path,
+                    profile,
+                    replacement_sources,
+                    interval,
+                    rebuild_cache,
+                )
+            },
+        )
+        .or_else(handle_error)
 }
 
-fn process_obsidian_repository(config_path: PathBuf) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let path = support::expand_tilde(config_path);
+// `run_daemon` runs the config every `interval` until the process is stopped. It holds the
+// run lock the whole time, so no other run can touch the vault in between. A requested cache
+// rebuild happens on the first run only.
+fn run_daemon(
+    config_path: &Path,
+    profile: Option<&str>,
+    replacement_sources: &[&dyn ReplacementSource],
+    interval: Duration,
+    rebuild_cache: bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (_, _, validated_config) = load_config(config_path, profile)?;
+    let run_lock = RunLock::acquire(validated_config.output_folder())?;
+    let mut rebuild_cache = rebuild_cache;
+
+    loop {
+        run_lock.refresh()?;
+        // A failed run was already reported - the next one may succeed, so the daemon carries on.
+        let _ = process_obsidian_repository(
+            config_path,
+            profile,
+            replacement_sources,
+            Some(&run_lock),
+            rebuild_cache,
+        )
+        .or_else(handle_error);
+        rebuild_cache = false;
+
+        let next_run = Local::now() + ChronoDuration::from_std(interval)?;
+        println!("{DAEMON_NEXT_RUN} {}", next_run.format(FORMAT_TIME_STAMP));
+        daemon::wait(&run_lock, interval)?;
+    }
+}
+
+// `process_obsidian_repository` runs the config once, with `profile` merged over it when given.
+// A daemon passes in the lock it holds; otherwise the run takes the lock itself.
+// `rebuild_cache` discards the image hash cache first.
+fn process_obsidian_repository(
+    config_path: &Path,
+    profile: Option<&str>,
+    replacement_sources: &[&dyn ReplacementSource],
+    held_lock: Option<&RunLock>,
+    rebuild_cache: bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (mut markdown_file, config, validated_config) = load_config(config_path, profile)?;
+    // Held until the run returns, so a second run can't interleave its changes with this one.
+    let _run_lock = held_lock
+        .is_none()
+        .then(|| RunLock::acquire(validated_config.output_folder()))
+        .transpose()?;
+
+    relocate_legacy_state(&validated_config)?;
+    if rebuild_cache {
+        Sha256Cache::remove(&validated_config.state_folder())?;
+    }
 
-    let mut markdown_file = MarkdownFile::new(path, DEFAULT_TIMEZONE)?;
-    let mut config = if let Some(front_matter) = &markdown_file.front_matter {
-        Config::try_from(front_matter)?
+    let mut obsidian_repository =
+        ObsidianRepository::with_replacement_sources(&validated_config, replacement_sources)?;
+    let apply = matches!(config.change_mode(), ChangeMode::Apply);
+    let mut skipped = if apply {
+        obsidian_repository.guard_changed_on_disk_notes()
     } else {
-        return Err(markdown_file
-            .frontmatter_error
-            .unwrap_or(YamlFrontMatterError::Missing)
-            .into());
+        Vec::new()
     };
+    obsidian_repository.write_reports(&validated_config)?;
 
-    let validated_config = config.validate()?;
+    if apply {
+        skipped.extend(apply_changes(&obsidian_repository, &validated_config)?);
+        // A daemon runs unattended, so apply_changes stays on for its next run.
+        if held_lock.is_none() {
+            reset_change_mode(&mut markdown_file, &config, profile)?;
+        }
+    } else {
+        obsidian_repository.write_patch(&validated_config)?;
+    }
+    write_outputs(&obsidian_repository, &validated_config)?;
+
+    // The run's work is done by now, so a failed notification is reported rather than failing it.
+    if let Err(error) = obsidian_repository.notify(&validated_config, &skipped) {
+        eprintln!("{NOTIFICATION_NOT_SENT} {error}");
+    }
+
+    Ok(())
+}
+
+// `run_in_sandbox` applies the config to a copy of the vault and says where the copy is, so
+// the changes can be inspected first. The vault, its state and the config file are left alone,
+// and no notification is sent.
+fn run_in_sandbox(
+    config_path: &Path,
+    profile: Option<&str>,
+    replacement_sources: &[&dyn ReplacementSource],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (_, config, _) = load_config(config_path, profile)?;
+    let sandbox = Sandbox::create(&config.expanded_obsidian_path()?, SANDBOX_PREFIX)?;
+    let validated_config = sandbox.config(&config)?.validate()?;
+    // Nothing else runs against the copy - the lock is taken for the output folder it creates.
+    let _run_lock = RunLock::acquire(validated_config.output_folder())?;
 
-    let obsidian_repository = ObsidianRepository::new(&validated_config)?;
+    let mut obsidian_repository =
+        ObsidianRepository::with_replacement_sources(&validated_config, replacement_sources)?;
+    obsidian_repository.guard_changed_on_disk_notes();
     obsidian_repository.write_reports(&validated_config)?;
+    apply_changes(&obsidian_repository, &validated_config)?;
+    write_outputs(&obsidian_repository, &validated_config)?;
+
+    println!("{SANDBOX_APPLIED} {}", sandbox.keep().display());
+    Ok(())
+}
+
+// `apply_changes` backs up what the run is about to change when configured to, writes the
+// changes and records them in the audit note and changelog. The notes that changed on disk
+// were set aside by `guard_changed_on_disk_notes` before the report - it returns the ones
+// saved again since, which `persist` still leaves unwritten.
+fn apply_changes(
+    obsidian_repository: &ObsidianRepository,
+    validated_config: &ValidatedConfig,
+) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+    let run_id = &obsidian_repository.run_id;
+    let backed_up = obsidian_repository.back_up_before_apply(validated_config, run_id)?;
+    if backed_up > 0 {
+        println!("{BACKED_UP} {backed_up} files for run {run_id}");
+    }
+
+    let skipped = obsidian_repository.persist()?;
+    for skipped_path in &skipped {
+        eprintln!("{SKIPPED_CHANGED_ON_DISK} {}", skipped_path.display());
+    }
+    obsidian_repository.append_audit_note(validated_config, &skipped)?;
+    obsidian_repository.write_changelog(validated_config, &skipped)?;
+    Ok(skipped)
+}
+
+// `write_outputs` writes the files a run produces besides its report.
+fn write_outputs(
+    obsidian_repository: &ObsidianRepository,
+    validated_config: &ValidatedConfig,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    obsidian_repository.write_vault_index(validated_config)?;
+    obsidian_repository.write_exports()?;
+    obsidian_repository.write_flashcards(validated_config)
+}
 
-    if matches!(config.change_mode(), ChangeMode::Apply) {
-        obsidian_repository.persist()?;
-        reset_change_mode(&mut markdown_file, &mut config)?;
+// `relocate_legacy_state` moves what earlier runs kept in the vault - the cache folder and the
+// vault index in the output folder - into the state folder, so none of it is rebuilt from
+// scratch after the move.
+fn relocate_legacy_state(validated_config: &ValidatedConfig) -> io::Result<()> {
+    let state_folder = validated_config.state_folder();
+    let legacy_folder = validated_config.obsidian_path().join(CACHE_FOLDER);
+    if state_folder == legacy_folder {
+        return Ok(());
     }
 
+    for file_name in [CACHE_FILE, VAULT_HEALTH_HISTORY_FILE] {
+        support::move_file(
+            &legacy_folder.join(file_name),
+            &state_folder.join(file_name),
+        )?;
+    }
+    support::move_file(
+        &validated_config.output_folder().join(VAULT_INDEX_FILE),
+        &state_folder.join(VAULT_INDEX_FILE),
+    )?;
+    // Best effort - the folder stays if something else was put in it.
+    let _ = fs::remove_dir(&legacy_folder);
     Ok(())
 }
 
+// `load_config` reads the config file, merges `profile` over it when given and validates the
+// result.
+fn load_config(
+    config_path: &Path,
+    profile: Option<&str>,
+) -> Result<(MarkdownFile, Config, ValidatedConfig), Box<dyn Error + Send + Sync>> {
+    let path = support::expand_tilde(config_path);
+
+    let markdown_file = MarkdownFile::new(path, DEFAULT_TIMEZONE, FORMAT_DATE, None, &[])?;
+    let config = if let Some(front_matter) = &markdown_file.front_matter {
+        let config = Config::try_from(front_matter)?;
+        match profile {
+            Some(profile) => config.with_profile(profile)?,
+            None => config,
+        }
+    } else {
+        return Err(markdown_file
+            .frontmatter_error
+            .unwrap_or(YamlFrontMatterError::Missing)
+            .into());
+    };
+
+    let validated_config = config.validate()?;
+    Ok((markdown_file, config, validated_config))
+}
+
+// `reset_change_mode` turns `apply_changes` off in the config file. It starts from the file's
+// own settings rather than `config`, which may have a profile merged in.
 fn reset_change_mode(
     markdown_file: &mut MarkdownFile,
-    config: &mut Config,
+    config: &Config,
+    profile: Option<&str>,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
-    config.configured_changes = ConfiguredChanges::DryRun;
-    let yaml = config.to_yaml_str()?;
+    let mut file_config = match &markdown_file.front_matter {
+        Some(front_matter) => Config::try_from(front_matter)?,
+        None => config.clone(),
+    };
+    file_config.turn_off_apply_changes(profile);
+    let yaml = file_config.to_yaml_str()?;
     let updated_front_matter = FrontMatter::from_yaml_str(&yaml)?;
     markdown_file.front_matter = Some(updated_front_matter);
 
@@ -110,6 +437,86 @@ fn reset_change_mode(
     Ok(())
 }
 
+// `generate_vault` writes a synthetic vault for benchmarking and reports what it wrote.
+fn generate_vault(args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (folder, spec) = VaultSpec::from_args(args)?;
+    let generated = vault_generator::generate_vault(&folder, &spec)?;
+    println!(
+        "{GENERATED_VAULT_SUMMARY} {} notes, {} wikilinks, {} mentions, {} images ({} \
+         duplicates) in {}",
+        generated.notes,
+        generated.wikilinks,
+        generated.mentions,
+        generated.images,
+        generated.duplicate_images,
+        folder.display()
+    );
+    Ok(())
+}
+
+// `init_config` writes a starter config for an existing vault.
+fn init_config(args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (obsidian_path, config_path) = config_init::from_args(args)?;
+    config_init::write_starter_config(&obsidian_path, &config_path)?;
+    println!("{INIT_WROTE_CONFIG} {}", config_path.display());
+    Ok(())
+}
+
+// `apply_patch` makes the changes a dry run wrote to a patch file, or none if any file they
+// touch has changed since.
+fn apply_patch(args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (patch_path, filter) = patch::from_args(args)?;
+    let applied = Patch::read(&patch_path)?.filtered(&filter)?.apply()?;
+    println!(
+        "{PATCH_APPLIED} {applied} changes from {}",
+        patch_path.display()
+    );
+    Ok(())
+}
+
+// `run_query` prints the notes in the config's vault index that match the query.
+fn run_query(args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (config_path, query, format) = query::from_args(args)?;
+    let (_, _, validated_config) = load_config(&config_path, None)?;
+    let index_path = validated_config.state_folder().join(VAULT_INDEX_FILE);
+    for line in query::run(&index_path, &query, format)? {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+// `verify_idempotent` applies the config twice to a copy of the vault and fails, listing what
+// changed, when the second run still has something to do.
+fn verify_idempotent(
+    config_path: &Path,
+    profile: Option<&str>,
+    replacement_sources: &[&dyn ReplacementSource],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (_, config, _) = load_config(config_path, profile)?;
+    let remaining = idempotency::verify(&config, replacement_sources)?;
+    if remaining.is_empty() {
+        println!("{IDEMPOTENT}");
+        return Ok(());
+    }
+
+    let categories: Vec<String> = remaining
+        .iter()
+        .map(|category| format!("{} ({} files)", category.category, category.files))
+        .collect();
+    Err(format!("{NOT_IDEMPOTENT} {}", categories.join(", ")).into())
+}
+
+// `explain_config` prints every config key with its type, default and the value this config
+// gives it.
+fn explain_config(
+    config_path: &Path,
+    profile: Option<&str>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (_, config, validated_config) = load_config(config_path, profile)?;
+    print!("{}", explain_config::explain(&config, &validated_config)?);
+    Ok(())
+}
+
 // `handle_error` prints the source chain before returning the original error.
 fn handle_error(e: Box<dyn Error + Send + Sync>) -> Result<(), Box<dyn Error + Send + Sync>> {
     eprintln!("{ERROR_OCCURRED}");
@@ -126,15 +533,65 @@ fn handle_error(e: Box<dyn Error + Send + Sync>) -> Result<(), Box<dyn Error + S
 
 // `get_config_file` reads the config path argument.
 fn get_config_file() -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
-    let args: Vec<String> = args().collect();
+    let args = args_without_global_flags();
 
-    if args.len() != EXPECTED_ARG_COUNT {
+    if args.len() != EXPECTED_ARG_COUNT && args.len() != EXPECTED_ARG_COUNT + DAEMON_ARG_COUNT {
         return Err(Box::new(MainError::Usage(USAGE.into())));
     }
 
     Ok(PathBuf::from(&args[CONFIG_ARG_INDEX]))
 }
 
+// `get_daemon_interval` reads `--daemon --interval <interval>` after the config path.
+fn get_daemon_interval() -> Result<Option<Duration>, Box<dyn Error + Send + Sync>> {
+    let args = args_without_global_flags();
+
+    match args.get(DAEMON_ARGS_START..) {
+        None | Some([]) => Ok(None),
+        Some([daemon_flag, interval_flag, interval])
+            if daemon_flag == DAEMON_FLAG && interval_flag == INTERVAL_FLAG =>
+        {
+            Ok(Some(daemon::parse_interval(interval)?))
+        },
+        Some(_) => Err(Box::new(MainError::Usage(USAGE.into()))),
+    }
+}
+
+// `rebuild_cache_requested` reads `--rebuild-cache`, which may appear anywhere after the config
+// path.
+fn rebuild_cache_requested() -> bool {
+    args()
+        .skip(CONFIG_ARG_INDEX + 1)
+        .any(|arg| arg == REBUILD_CACHE_FLAG)
+}
+
+// `requested_profile` reads `--profile <name>`, which may appear anywhere after the config path.
+fn requested_profile() -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+    let mut args = args().skip(CONFIG_ARG_INDEX + 1);
+    if !args.any(|arg| arg == PROFILE_FLAG) {
+        return Ok(None);
+    }
+    args.next()
+        .filter(|name| !name.starts_with(FLAG_PREFIX))
+        .map(Some)
+        .ok_or_else(|| Box::new(MainError::Usage(USAGE.into())) as Box<dyn Error + Send + Sync>)
+}
+
+// `args_without_global_flags` is the arguments with `--rebuild-cache` and `--profile <name>`
+// left out, so the others parse the same with or without them.
+fn args_without_global_flags() -> Vec<String> {
+    let mut args_left = Vec::new();
+    let mut args = args();
+    while let Some(arg) = args.next() {
+        if arg == PROFILE_FLAG {
+            args.next();
+        } else if arg != REBUILD_CACHE_FLAG {
+            args_left.push(arg);
+        }
+    }
+    args_left
+}
+
 #[cfg(test)]
 #[allow(clippy::panic, reason = "tests should panic on unexpected values")]
 mod tests {
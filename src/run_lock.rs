@@ -0,0 +1,392 @@
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::process::Command;
+use std::process::id;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use thiserror::Error;
+
+use crate::constants::ATOMIC_WRITE_TEMP_SUFFIX;
+use crate::constants::RUN_LOCK_FILE;
+use crate::constants::RUN_LOCK_INCOMPLETE_GRACE_SECONDS;
+use crate::constants::RUN_LOCK_PID_PREFIX;
+#[cfg(unix)]
+use crate::constants::RUN_LOCK_PROCESS_CHECK_ARG;
+#[cfg(unix)]
+use crate::constants::RUN_LOCK_PROCESS_CHECK_EXECUTABLE;
+use crate::constants::RUN_LOCK_STALE_AFTER_HOURS;
+use crate::constants::RUN_LOCK_STALE_SUFFIX;
+use crate::constants::RUN_LOCK_STARTED_PREFIX;
+use crate::constants::STALE_RUN_LOCK_REMOVED;
+use crate::constants::UNKNOWN;
+
+#[derive(Debug, Error)]
+pub(crate) enum RunLockError {
+    #[error(
+        "another obsidian knife run (pid {pid}, started {started}) is using this vault - wait for \
+         it to finish, or delete {} if no run is active",
+        path.display()
+    )]
+    Held {
+        path:    PathBuf,
+        pid:     String,
+        started: String,
+    },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Lock file in the output folder that keeps two runs from changing the vault at once. It is
+/// removed when the `RunLock` is dropped; a lock whose process is gone, or that is older than
+/// `RUN_LOCK_STALE_AFTER_HOURS`, was left by a crash and is replaced.
+#[derive(Debug)]
+pub(crate) struct RunLock {
+    path: PathBuf,
+}
+
+impl RunLock {
+    pub(crate) fn acquire(output_folder: &Path) -> Result<Self, RunLockError> {
+        fs::create_dir_all(output_folder)?;
+        Self::acquire_path(&output_folder.join(RUN_LOCK_FILE))
+    }
+
+    fn acquire_path(path: &Path) -> Result<Self, RunLockError> {
+        loop {
+            match Self::create(path) {
+                Err(RunLockError::Io(error)) if error.kind() == ErrorKind::AlreadyExists => {},
+                result => return result,
+            }
+            let holder = match LockHolder::read(path) {
+                // The other run finished in between.
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                holder => holder?,
+            };
+            if !holder.is_stale() {
+                return Err(holder.held(path));
+            }
+
+            return Self::take_over(path, &holder);
+        }
+    }
+
+    /// Replaces `stale`, the lock read from `path`. Another run may have seen it too and
+    /// already replaced it, so it is renamed aside - only one rename can take a file - and
+    /// checked to still be `stale` before it is removed. A lock that isn't is put back.
+    fn take_over(path: &Path, stale: &LockHolder) -> Result<Self, RunLockError> {
+        let aside_path = unique_sibling_path(path, RUN_LOCK_STALE_SUFFIX);
+        match fs::rename(path, &aside_path) {
+            // Another run took it aside first.
+            Err(error) if error.kind() == ErrorKind::NotFound => return Self::acquire_path(path),
+            result => result?,
+        }
+
+        let aside = LockHolder::read(&aside_path)?;
+        if aside != *stale {
+            put_back(&aside_path, path)?;
+            return Err(aside.held(path));
+        }
+
+        println!("{STALE_RUN_LOCK_REMOVED} {}", path.display());
+        fs::remove_file(&aside_path)?;
+        Self::acquire_path(path)
+    }
+
+    /// Restamps the lock with the current time. A daemon holds its lock across runs, so it
+    /// refreshes it to keep other runs from taking it for one left by a crash.
+    pub(crate) fn refresh(&self) -> Result<(), RunLockError> {
+        // Renamed over the lock, so a run checking it never reads it half written.
+        let temp_path = unique_sibling_path(&self.path, ATOMIC_WRITE_TEMP_SUFFIX);
+        write_holder(File::create(&temp_path)?)?;
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+
+    fn create(path: &Path) -> Result<Self, RunLockError> {
+        // The holder is written to a temp file and linked into place, so the lock never exists
+        // without it. `hard_link` fails if the lock exists, so only one run can win.
+        let temp_path = unique_sibling_path(path, ATOMIC_WRITE_TEMP_SUFFIX);
+        write_holder(File::create(&temp_path)?)?;
+        let linked = fs::hard_link(&temp_path, path);
+        let _ = fs::remove_file(&temp_path);
+        match linked {
+            Ok(()) => {},
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => return Err(error.into()),
+            // A file system without hard links gets the lock created in place - `create_new`
+            // still lets only one run win.
+            Err(_) => {
+                write_holder(OpenOptions::new().write(true).create_new(true).open(path)?)?;
+            },
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+/// A file beside the lock that only this call uses, so two runs - or two threads of one -
+/// never share one.
+fn unique_sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(
+        ".{}.{}{suffix}",
+        id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(file_name)
+}
+
+/// Returns a live lock taken aside by mistake. If a run created a lock in the meantime the
+/// lock is already held, so the aside copy is only removed.
+fn put_back(aside_path: &Path, path: &Path) -> io::Result<()> {
+    match fs::hard_link(aside_path, path) {
+        Ok(()) => {},
+        Err(error) if error.kind() == ErrorKind::AlreadyExists => {},
+        Err(_) if !path.exists() => return fs::rename(aside_path, path),
+        Err(error) => return Err(error),
+    }
+    fs::remove_file(aside_path)
+}
+
+fn write_holder(mut file: File) -> Result<(), RunLockError> {
+    writeln!(file, "{RUN_LOCK_PID_PREFIX}{}", id())?;
+    writeln!(file, "{RUN_LOCK_STARTED_PREFIX}{}", Utc::now().to_rfc3339())?;
+    Ok(())
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        // A run that took this lock for a stale one holds it now, so only a lock that still
+        // names this process is removed. Nothing useful can be done if that fails - the next
+        // run will see a stale lock.
+        let pid = id().to_string();
+        let is_ours = LockHolder::read(&self.path)
+            .is_ok_and(|holder| holder.pid.as_deref() == Some(pid.as_str()));
+        if is_ours {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// What an existing lock file says about the run holding it.
+#[derive(Debug, PartialEq, Eq)]
+struct LockHolder {
+    pid:      Option<String>,
+    started:  Option<DateTime<Utc>>,
+    modified: Option<DateTime<Utc>>,
+}
+
+impl LockHolder {
+    fn read(path: &Path) -> io::Result<Self> {
+        let modified = fs::metadata(path)?
+            .modified()
+            .ok()
+            .map(DateTime::<Utc>::from);
+        let contents = fs::read_to_string(path)?;
+        let value = |prefix: &str| {
+            contents
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix))
+                .map(str::trim)
+        };
+
+        Ok(Self {
+            pid: value(RUN_LOCK_PID_PREFIX).map(str::to_string),
+            started: value(RUN_LOCK_STARTED_PREFIX)
+                .and_then(|started| DateTime::parse_from_rfc3339(started).ok())
+                .map(|started| started.with_timezone(&Utc)),
+            modified,
+        })
+    }
+
+    fn held(self, path: &Path) -> RunLockError {
+        RunLockError::Held {
+            path:    path.to_path_buf(),
+            pid:     self.pid.unwrap_or_else(|| UNKNOWN.to_string()),
+            started: self
+                .started
+                .map_or_else(|| UNKNOWN.to_string(), |started| started.to_rfc3339()),
+        }
+    }
+
+    /// An unreadable lock is stale too - a run always writes both lines - once it is older
+    /// than `RUN_LOCK_INCOMPLETE_GRACE_SECONDS`. A newer one may be a run's lock still being
+    /// written, on a file system where it can't be linked into place whole.
+    fn is_stale(&self) -> bool {
+        let (Some(pid), Some(started)) = (&self.pid, self.started) else {
+            return self.modified.is_none_or(|modified| {
+                Utc::now() - modified > Duration::seconds(RUN_LOCK_INCOMPLETE_GRACE_SECONDS)
+            });
+        };
+        Utc::now() - started > Duration::hours(RUN_LOCK_STALE_AFTER_HOURS)
+            || !is_process_running(pid)
+    }
+}
+
+#[cfg(unix)]
+fn is_process_running(pid: &str) -> bool {
+    Command::new(RUN_LOCK_PROCESS_CHECK_EXECUTABLE)
+        .arg(RUN_LOCK_PROCESS_CHECK_ARG)
+        .arg(pid)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+// Without a portable process check, only the age of the lock marks it stale.
+#[cfg(not(unix))]
+const fn is_process_running(_: &str) -> bool { true }
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::panic,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+    use std::process::id;
+    use std::time::Duration as StdDuration;
+    use std::time::SystemTime;
+
+    use chrono::Duration;
+    use chrono::Utc;
+    use filetime::FileTime;
+    use tempfile::TempDir;
+
+    use super::LockHolder;
+    use super::RunLock;
+    use super::RunLockError;
+    use crate::constants::RUN_LOCK_FILE;
+    use crate::constants::UNKNOWN;
+
+    #[test]
+    fn test_second_run_is_refused_until_the_first_finishes() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join(RUN_LOCK_FILE);
+
+        let run_lock = RunLock::acquire(temp_dir.path()).unwrap();
+        assert!(lock_path.exists());
+
+        match RunLock::acquire(temp_dir.path()) {
+            Err(RunLockError::Held { pid, .. }) => assert_eq!(pid, id().to_string()),
+            other => panic!("expected the lock to be held, got {other:?}"),
+        }
+
+        drop(run_lock);
+        assert!(!lock_path.exists());
+        assert!(RunLock::acquire(temp_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_stale_lock_is_replaced() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join(RUN_LOCK_FILE);
+
+        let old_start = Utc::now() - Duration::days(2);
+        fs::write(
+            &lock_path,
+            format!("pid: {}\nstarted: {}\n", id(), old_start.to_rfc3339()),
+        )
+        .unwrap();
+        assert!(RunLock::acquire(temp_dir.path()).is_ok(), "old lock");
+
+        fs::write(&lock_path, "garbage").unwrap();
+        let an_hour_ago =
+            FileTime::from_system_time(SystemTime::now() - StdDuration::from_hours(1));
+        filetime::set_file_mtime(&lock_path, an_hour_ago).unwrap();
+        assert!(RunLock::acquire(temp_dir.path()).is_ok(), "unreadable lock");
+    }
+
+    #[test]
+    fn test_empty_lock_is_held_while_new() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join(RUN_LOCK_FILE);
+
+        // What another run's lock looks like between creating it and writing its holder.
+        fs::write(&lock_path, "").unwrap();
+        match RunLock::acquire(temp_dir.path()) {
+            Err(RunLockError::Held { pid, .. }) => assert_eq!(pid, UNKNOWN),
+            other => panic!("expected the new empty lock to be held, got {other:?}"),
+        }
+        assert!(lock_path.exists());
+
+        let an_hour_ago =
+            FileTime::from_system_time(SystemTime::now() - StdDuration::from_hours(1));
+        filetime::set_file_mtime(&lock_path, an_hour_ago).unwrap();
+        assert!(RunLock::acquire(temp_dir.path()).is_ok(), "old empty lock");
+    }
+
+    #[test]
+    fn test_refresh_keeps_the_lock_whole() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join(RUN_LOCK_FILE);
+
+        let run_lock = RunLock::acquire(temp_dir.path()).unwrap();
+        run_lock.refresh().unwrap();
+        let contents = fs::read_to_string(&lock_path).unwrap();
+        assert!(contents.contains(&id().to_string()));
+        assert_eq!(
+            fs::read_dir(temp_dir.path()).unwrap().count(),
+            1,
+            "no temp file left"
+        );
+    }
+
+    #[test]
+    fn test_two_takeovers_of_one_stale_lock_let_one_win() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join(RUN_LOCK_FILE);
+
+        let old_start = Utc::now() - Duration::days(2);
+        fs::write(
+            &lock_path,
+            format!("pid: {}\nstarted: {}\n", id(), old_start.to_rfc3339()),
+        )
+        .unwrap();
+        // Both runs read the lock before either replaces it.
+        let seen_by_first = LockHolder::read(&lock_path).unwrap();
+        let seen_by_second = LockHolder::read(&lock_path).unwrap();
+        assert!(seen_by_first.is_stale());
+
+        let first = RunLock::take_over(&lock_path, &seen_by_first);
+        let second = RunLock::take_over(&lock_path, &seen_by_second);
+        assert!(first.is_ok(), "first takeover");
+        assert!(
+            matches!(second, Err(RunLockError::Held { .. })),
+            "second takeover: {second:?}"
+        );
+
+        let holder = LockHolder::read(&lock_path).unwrap();
+        assert!(!holder.is_stale(), "the first run's lock was put back");
+        assert_eq!(
+            fs::read_dir(temp_dir.path()).unwrap().count(),
+            1,
+            "nothing left aside"
+        );
+    }
+
+    #[test]
+    fn test_drop_leaves_a_lock_another_run_took_over() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join(RUN_LOCK_FILE);
+
+        let run_lock = RunLock::acquire(temp_dir.path()).unwrap();
+        let other_lock = format!("pid: {}\nstarted: {}\n", id() + 1, Utc::now().to_rfc3339());
+        fs::write(&lock_path, &other_lock).unwrap();
+
+        drop(run_lock);
+        assert_eq!(fs::read_to_string(&lock_path).unwrap(), other_lock);
+    }
+}
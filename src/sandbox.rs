@@ -0,0 +1,178 @@
+//! A copy of a vault in the system temp folder, for runs that must leave the vault alone:
+//! `--sandbox` and `--verify-idempotent`.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+
+use chrono::Local;
+
+use crate::config;
+use crate::config::Config;
+use crate::config::ConfiguredChanges;
+use crate::constants::DEFAULT_EXPORT_FOLDER;
+use crate::constants::DEFAULT_OUTPUT_FOLDER;
+use crate::constants::SANDBOX_STATE_FOLDER;
+use crate::constants::SANDBOX_TIME_FORMAT;
+use crate::constants::SANDBOX_VAULT_FOLDER;
+use crate::support;
+use crate::validated_config::ValidationError;
+
+/// The copy lives in `<temp>/<prefix><time>-<pid>/vault`, with the run's state folder beside it
+/// so the real one isn't touched either. The copy is removed when this is dropped unless it was
+/// kept.
+pub(crate) struct Sandbox {
+    root: PathBuf,
+    keep: bool,
+}
+
+impl Sandbox {
+    pub(crate) fn create(obsidian_path: &Path, prefix: &str) -> io::Result<Self> {
+        let root = env::temp_dir().join(format!(
+            "{prefix}{}-{}",
+            Local::now().format(SANDBOX_TIME_FORMAT),
+            process::id()
+        ));
+        if root.exists() {
+            fs::remove_dir_all(&root)?;
+        }
+        // Built before copying, so a failed copy is cleaned up on the way out.
+        let sandbox = Self { root, keep: false };
+        support::copy_folder(obsidian_path, &sandbox.vault())?;
+        Ok(sandbox)
+    }
+
+    pub(crate) fn vault(&self) -> PathBuf { self.root.join(SANDBOX_VAULT_FOLDER) }
+
+    fn state(&self) -> PathBuf { self.root.join(SANDBOX_STATE_FOLDER) }
+
+    /// `config` pointed at the copy, with `apply_changes` on. An output folder, changelog or
+    /// export folder the config places outside the vault moves into the copy, so the run writes
+    /// nowhere else.
+    pub(crate) fn config(&self, config: &Config) -> Result<Config, ValidationError> {
+        let obsidian_path = config.expanded_obsidian_path()?;
+        let vault = self.vault();
+        let in_copy = |path: &str| -> Result<PathBuf, ValidationError> {
+            let resolved = obsidian_path.join(config::expand_path(path)?);
+            Ok(resolved.strip_prefix(&obsidian_path).map_or_else(
+                |_| {
+                    resolved
+                        .file_name()
+                        .map_or_else(|| vault.clone(), |file_name| vault.join(file_name))
+                },
+                |relative| vault.join(relative),
+            ))
+        };
+
+        let mut sandbox_config = config.clone();
+        sandbox_config.obsidian_path = vault.to_string_lossy().into_owned();
+        sandbox_config.configured_changes = ConfiguredChanges::Apply;
+        sandbox_config.state_folder = Some(self.state().to_string_lossy().into_owned());
+        sandbox_config.output_folder = Some(
+            in_copy(
+                config
+                    .output_folder
+                    .as_deref()
+                    .unwrap_or(DEFAULT_OUTPUT_FOLDER),
+            )?
+            .to_string_lossy()
+            .into_owned(),
+        );
+        sandbox_config.changelog = config
+            .changelog
+            .as_deref()
+            .map(|changelog| in_copy(changelog).map(|path| path.to_string_lossy().into_owned()))
+            .transpose()?;
+        // The export folder is relative to the output folder, so only an absolute one escapes.
+        if let Some(folder) = sandbox_config
+            .export
+            .as_mut()
+            .and_then(|export| export.folder.as_mut())
+            && config::expand_path(&*folder)?.is_absolute()
+        {
+            *folder = config::expand_path(&*folder)?.file_name().map_or_else(
+                || DEFAULT_EXPORT_FOLDER.to_string(),
+                |file_name| file_name.to_string_lossy().into_owned(),
+            );
+        }
+        Ok(sandbox_config)
+    }
+
+    /// Leaves the copy in place after this is dropped and returns the copied vault.
+    pub(crate) fn keep(mut self) -> PathBuf {
+        self.keep = true;
+        self.vault()
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        if !self.keep {
+            // Best effort - a copy left behind sits in the temp folder, where the system clears it.
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use serde_yaml::from_str;
+    use tempfile::TempDir;
+
+    use super::Sandbox;
+    use crate::config::Config;
+    use crate::validated_config::ChangeMode;
+
+    #[test]
+    fn test_sandbox_config_writes_only_inside_the_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path().join("vault");
+        fs::create_dir_all(vault.join("notes")).unwrap();
+        fs::write(vault.join("notes").join("a.md"), "# A\n").unwrap();
+        fs::write(vault.join("photo.png"), b"image").unwrap();
+
+        let outside = temp_dir.path().join("reports");
+        let config: Config = from_str(&format!(
+            "obsidian_path: {}\napply_changes: false\noutput_folder: {}\nchangelog: log.md",
+            vault.display(),
+            outside.display()
+        ))
+        .unwrap();
+
+        let sandbox = Sandbox::create(&vault, "test_sandbox_").unwrap();
+        let copy = sandbox.vault();
+        assert_eq!(
+            fs::read_to_string(copy.join("notes").join("a.md")).unwrap(),
+            "# A\n"
+        );
+        assert_eq!(fs::read(copy.join("photo.png")).unwrap(), b"image");
+
+        let validated_config = sandbox.config(&config).unwrap().validate().unwrap();
+        assert_eq!(validated_config.change_mode(), ChangeMode::Apply);
+        assert_eq!(validated_config.obsidian_path(), copy);
+        assert_eq!(validated_config.output_folder(), copy.join("reports"));
+        assert_eq!(
+            validated_config.changelog(),
+            Some(copy.join("log.md").as_path())
+        );
+        assert!(
+            validated_config
+                .state_folder()
+                .starts_with(copy.parent().unwrap())
+        );
+
+        let kept = sandbox.keep();
+        assert!(kept.exists(), "a kept sandbox outlives its handle");
+        fs::remove_dir_all(kept.parent().unwrap()).unwrap();
+        assert!(vault.join("photo.png").exists());
+    }
+}
@@ -4,7 +4,8 @@ use std::error::Error;
 use std::fmt::Write;
 use std::fs;
 use std::fs::File;
-use std::io::BufReader;
+use std::io;
+use std::io::ErrorKind;
 use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
@@ -12,20 +13,25 @@ use std::time::SystemTime;
 
 use serde::Deserialize;
 use serde::Serialize;
-use serde_json::from_reader;
-use serde_json::to_writer;
+use serde_json::from_slice;
+use serde_json::to_vec;
 use sha2::Digest;
 use sha2::Sha256;
 
+use crate::constants::CACHE_FILE;
+use crate::constants::CACHE_VERSION;
 use crate::constants::HEX_DIGITS_PER_BYTE;
 use crate::constants::SHA256_BUFFER_SIZE;
 use crate::image_file::ImageHash;
+use crate::support;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum CacheFileStatus {
     Read,
     Created,
     Corrupted,
+    /// Written with another `CACHE_VERSION`.
+    Outdated,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -42,10 +48,26 @@ pub(crate) struct CachedImageInfo {
     pub time_stamp: SystemTime,
 }
 
+/// The cache file on disk - its entries under the `CACHE_VERSION` that wrote them.
+#[derive(Serialize, Deserialize)]
+struct CacheFile<E> {
+    version: u32,
+    entries: E,
+}
+
+/// Just the version of a cache file. A cache from before versioning has none, which reads as 0.
+#[derive(Deserialize)]
+struct CacheFileHeader {
+    #[serde(default)]
+    version: u32,
+}
+
 #[derive(Debug)]
 pub(crate) struct Sha256Cache {
     entries:             HashMap<PathBuf, CachedImageInfo>,
     file_path:           PathBuf,
+    /// The file held a corrupt or outdated cache, which was discarded.
+    rebuilt:             bool,
     reads:               usize,
     pub(super) added:    usize,
     pub(super) modified: usize,
@@ -54,25 +76,19 @@ pub(crate) struct Sha256Cache {
 
 impl Sha256Cache {
     pub(crate) fn load_or_create(file_path: PathBuf) -> (Self, CacheFileStatus) {
-        let (entries, status) = if file_path.exists() {
-            File::open(&file_path).map_or_else(
-                |_| (HashMap::new(), CacheFileStatus::Created),
-                |file| {
-                    let buf_reader = BufReader::new(file);
-                    from_reader(buf_reader).map_or_else(
-                        |_| (HashMap::new(), CacheFileStatus::Corrupted),
-                        |parsed_cache| (parsed_cache, CacheFileStatus::Read),
-                    )
-                },
-            )
-        } else {
-            (HashMap::new(), CacheFileStatus::Created)
-        };
+        let (entries, status) = fs::read(&file_path).map_or_else(
+            |_| (HashMap::new(), CacheFileStatus::Created),
+            |contents| read_entries(&contents),
+        );
 
         (
             Self {
                 entries,
                 file_path,
+                rebuilt: matches!(
+                    status,
+                    CacheFileStatus::Corrupted | CacheFileStatus::Outdated
+                ),
                 reads: 0,
                 added: 0,
                 modified: 0,
@@ -82,6 +98,14 @@ impl Sha256Cache {
         )
     }
 
+    /// Removes the cache file under `state_folder`, so the next scan hashes from scratch.
+    pub(crate) fn remove(state_folder: &Path) -> io::Result<()> {
+        match fs::remove_file(cache_file_path(state_folder)) {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+
     pub(crate) fn get_or_update(
         &mut self,
         path: &Path,
@@ -131,16 +155,23 @@ impl Sha256Cache {
         }
     }
 
+    /// A cache rebuilt from a corrupt or outdated file counts as changed, so saving replaces
+    /// that file even when nothing was hashed.
     pub(crate) const fn has_changes(&self) -> bool {
-        self.added > 0 || self.modified > 0 || self.deleted > 0
+        self.rebuilt || self.added > 0 || self.modified > 0 || self.deleted > 0
     }
 
-    pub(crate) fn save(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+    /// Writes the cache, first dropping entries for files that no longer exist.
+    pub(crate) fn save(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.entries.retain(|path, _| path.exists());
         if let Some(parent) = self.file_path.parent() {
             fs::create_dir_all(parent)?;
         }
-        let file = File::create(&self.file_path)?;
-        to_writer(file, &self.entries)?;
+        let cache_file = CacheFile {
+            version: CACHE_VERSION,
+            entries: &self.entries,
+        };
+        support::write_atomically(&self.file_path, to_vec(&cache_file)?)?;
         Ok(())
     }
 
@@ -157,11 +188,96 @@ impl Sha256Cache {
             sha256_hasher.update(&buffer[..bytes_read]);
         }
 
-        let hash = sha256_hasher.finalize();
-        let mut hex = String::with_capacity(hash.len() * HEX_DIGITS_PER_BYTE);
-        for byte in hash {
-            let _ = write!(hex, "{byte:0HEX_DIGITS_PER_BYTE$x}");
-        }
-        Ok(hex)
+        Ok(to_hex(&sha256_hasher.finalize()))
+    }
+}
+
+/// Where the cache is kept in a vault's `state_folder`.
+pub(crate) fn cache_file_path(state_folder: &Path) -> PathBuf { state_folder.join(CACHE_FILE) }
+
+/// Reads the entries of a cache file. A file written with another `CACHE_VERSION` - or before
+/// the cache had one - is `Outdated`, and one that can't be parsed is `Corrupted`; either way
+/// the cache starts empty.
+fn read_entries(contents: &[u8]) -> (HashMap<PathBuf, CachedImageInfo>, CacheFileStatus) {
+    let Ok(header) = from_slice::<CacheFileHeader>(contents) else {
+        return (HashMap::new(), CacheFileStatus::Corrupted);
+    };
+    if header.version != CACHE_VERSION {
+        return (HashMap::new(), CacheFileStatus::Outdated);
+    }
+    from_slice::<CacheFile<HashMap<PathBuf, CachedImageInfo>>>(contents).map_or_else(
+        |_| (HashMap::new(), CacheFileStatus::Corrupted),
+        |cache_file| (cache_file.entries, CacheFileStatus::Read),
+    )
+}
+
+/// Hex SHA-256 of `contents`, in the same form the cache stores for images.
+pub(crate) fn hash_contents(contents: &[u8]) -> String { to_hex(&Sha256::digest(contents)) }
+
+fn to_hex(hash: &[u8]) -> String {
+    let mut hex = String::with_capacity(hash.len() * HEX_DIGITS_PER_BYTE);
+    for byte in hash {
+        let _ = write!(hex, "{byte:0HEX_DIGITS_PER_BYTE$x}");
+    }
+    hex
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::CacheFileStatus;
+    use super::Sha256Cache;
+
+    #[test]
+    fn test_corrupt_and_outdated_caches_are_rebuilt() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let image_path = temp_dir.path().join("image.png");
+        fs::write(&image_path, [1_u8, 2, 3]).unwrap();
+
+        fs::write(&cache_path, "{ not json").unwrap();
+        let (mut cache, status) = Sha256Cache::load_or_create(cache_path.clone());
+        assert_eq!(status, CacheFileStatus::Corrupted);
+        assert!(cache.has_changes());
+        cache.get_or_update(&image_path).unwrap();
+        cache.save().unwrap();
+
+        let (cache, status) = Sha256Cache::load_or_create(cache_path.clone());
+        assert_eq!(status, CacheFileStatus::Read);
+        assert!(!cache.has_changes());
+        assert!(cache.entries.contains_key(&image_path));
+
+        // A cache written before versioning was a bare map of entries.
+        fs::write(&cache_path, "{}").unwrap();
+        let (cache, status) = Sha256Cache::load_or_create(cache_path);
+        assert_eq!(status, CacheFileStatus::Outdated);
+        assert!(cache.has_changes());
+    }
+
+    #[test]
+    fn test_save_prunes_missing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let kept = temp_dir.path().join("kept.png");
+        let removed = temp_dir.path().join("removed.png");
+        fs::write(&kept, [1_u8]).unwrap();
+        fs::write(&removed, [2_u8]).unwrap();
+
+        let (mut cache, _) = Sha256Cache::load_or_create(cache_path.clone());
+        cache.get_or_update(&kept).unwrap();
+        cache.get_or_update(&removed).unwrap();
+        fs::remove_file(&removed).unwrap();
+        cache.save().unwrap();
+
+        let (cache, _) = Sha256Cache::load_or_create(cache_path);
+        assert!(cache.entries.contains_key(&kept));
+        assert!(!cache.entries.contains_key(&removed));
     }
 }
@@ -1,9 +1,16 @@
+use std::env::var;
 use std::env::var_os;
 use std::error::Error;
 use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::fs;
+use std::fs::Metadata;
+use std::fs::OpenOptions;
 use std::io;
 use std::io::ErrorKind;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
 use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
@@ -22,16 +29,30 @@ use filetime::set_file_times;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 
+use super::patterns::ENVIRONMENT_VARIABLE_REGEX;
+use crate::constants::ATOMIC_WRITE_TEMP_PREFIX;
+use crate::constants::ATOMIC_WRITE_TEMP_SUFFIX;
+use crate::constants::BASE_EXTENSION;
+use crate::constants::BASE_FILE_COLLECTION_LOCK_POISONED;
+use crate::constants::CACHE_FOLDER;
+use crate::constants::CANVAS_EXTENSION;
+use crate::constants::CANVAS_FILE_COLLECTION_LOCK_POISONED;
 use crate::constants::DS_STORE;
 use crate::constants::ERROR_NOT_FOUND;
 use crate::constants::ERROR_READING;
 #[cfg(target_os = "macos")]
 use crate::constants::FAILED_TO_SET_CREATION_DATE_WITH_SETFILE;
+use crate::constants::HIDDEN_FILE_PREFIX;
 use crate::constants::HOME_ENVIRONMENT_VARIABLE;
 use crate::constants::IMAGE_EXTENSIONS;
 use crate::constants::IMAGE_FILE_COLLECTION_LOCK_POISONED;
+#[cfg(windows)]
+use crate::constants::LOCAL_APP_DATA_ENVIRONMENT_VARIABLE;
+#[cfg(target_os = "macos")]
+use crate::constants::MACOS_CACHE_FOLDER;
 use crate::constants::MARKDOWN_EXTENSION;
 use crate::constants::MARKDOWN_FILE_COLLECTION_LOCK_POISONED;
+use crate::constants::OTHER_FILE_COLLECTION_LOCK_POISONED;
 #[cfg(target_os = "macos")]
 use crate::constants::SET_FILE_CREATED_DATE_FLAG;
 #[cfg(target_os = "macos")]
@@ -40,11 +61,21 @@ use crate::constants::SET_FILE_CREATED_DATE_FORMAT;
 use crate::constants::SET_FILE_EXECUTABLE;
 use crate::constants::TILDE;
 use crate::constants::TILDE_SLASH;
+#[cfg(not(any(target_os = "macos", windows)))]
+use crate::constants::XDG_CACHE_HOME_ENVIRONMENT_VARIABLE;
+#[cfg(not(any(target_os = "macos", windows)))]
+use crate::constants::XDG_DEFAULT_CACHE_FOLDER;
 use crate::validated_config::ValidatedConfig;
 
 pub struct RepositoryFiles {
+    /// Obsidian bases - `.base` files.
+    pub bases:    Vec<PathBuf>,
+    /// Obsidian canvases - `.canvas` files.
+    pub canvases: Vec<PathBuf>,
     pub images:   Vec<PathBuf>,
     pub markdown: Vec<PathBuf>,
+    /// Every other file outside hidden folders - attachments ok doesn't process.
+    pub other:    Vec<PathBuf>,
 }
 
 pub fn read_contents_from_file(path: &Path) -> Result<String, Box<dyn Error + Send + Sync>> {
@@ -64,6 +95,129 @@ pub fn read_contents_from_file(path: &Path) -> Result<String, Box<dyn Error + Se
     Ok(contents)
 }
 
+/// Hidden sibling of `path` that `write_atomically` writes before renaming:
+/// `notes/Recipe.md` goes through `notes/.Recipe.md.ok-tmp`. The temp file is in the same
+/// folder so the rename never crosses filesystems.
+pub(crate) fn atomic_temp_path(path: &Path) -> PathBuf {
+    let mut file_name = OsString::from(ATOMIC_WRITE_TEMP_PREFIX);
+    file_name.push(path.file_name().unwrap_or_default());
+    file_name.push(ATOMIC_WRITE_TEMP_SUFFIX);
+    path.with_file_name(file_name)
+}
+
+/// Writes `contents` to a temp file next to `path`, syncs it and renames it over `path`, so a
+/// crash leaves either the old file or the new one - never a partial write. The temp file
+/// starts as a copy of `path`, so the replacement keeps its permissions and, on macOS, its
+/// extended attributes. A file hard linked elsewhere is written in place instead - a rename
+/// would split it from its other links.
+pub(crate) fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let original = fs::metadata(path).ok();
+    if original.as_ref().is_some_and(has_other_links) {
+        return fs::write(path, contents);
+    }
+
+    let temp_path = atomic_temp_path(path);
+    let result = original
+        .map_or(Ok(0), |_| fs::copy(path, &temp_path))
+        .and_then(|_| {
+            OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&temp_path)
+        })
+        .and_then(|mut file| {
+            file.write_all(contents.as_ref())?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_path, path));
+
+    if result.is_err() {
+        // Best effort - the original error is the one worth reporting.
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Whether the file is also hard linked from somewhere else.
+#[cfg(unix)]
+pub(crate) fn has_other_links(metadata: &Metadata) -> bool { metadata.nlink() > 1 }
+
+#[cfg(not(unix))]
+pub(crate) const fn has_other_links(_: &Metadata) -> bool { false }
+
+/// Moves `from` to `to`, copying and removing when a rename can't cross filesystems. Nothing
+/// moves when `from` doesn't exist or `to` already does.
+pub(crate) fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if !from.is_file() || to.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+/// Copies every file under `from` to the same place under `to`, keeping modification times so
+/// date checks see the copy as they would the original. Images are hard linked instead when the
+/// filesystem allows - ok only ever deletes an image, which leaves the original alone - while
+/// everything else is copied, since ok rewrites notes and reports in place. Symlinks are left
+/// out.
+pub(crate) fn copy_folder(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        if file_type.is_dir() {
+            copy_folder(&source, &target)?;
+        } else if file_type.is_file() {
+            let is_image = source
+                .extension()
+                .and_then(OsStr::to_str)
+                .is_some_and(|extension| {
+                    IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+                });
+            if is_image && fs::hard_link(&source, &target).is_ok() {
+                continue;
+            }
+            fs::copy(&source, &target)?;
+            let metadata = entry.metadata()?;
+            set_file_mtime(&target, FileTime::from_last_modification_time(&metadata))?;
+        }
+    }
+    Ok(())
+}
+
+/// The user's cache folder: `~/Library/Caches`.
+#[cfg(target_os = "macos")]
+pub(crate) fn platform_cache_folder() -> Option<PathBuf> {
+    var_os(HOME_ENVIRONMENT_VARIABLE).map(|home| PathBuf::from(home).join(MACOS_CACHE_FOLDER))
+}
+
+/// The user's cache folder: `%LOCALAPPDATA%`.
+#[cfg(windows)]
+pub(crate) fn platform_cache_folder() -> Option<PathBuf> {
+    var_os(LOCAL_APP_DATA_ENVIRONMENT_VARIABLE).map(PathBuf::from)
+}
+
+/// The user's cache folder: `$XDG_CACHE_HOME`, or `~/.cache` when that isn't set.
+#[cfg(not(any(target_os = "macos", windows)))]
+pub(crate) fn platform_cache_folder() -> Option<PathBuf> {
+    var_os(XDG_CACHE_HOME_ENVIRONMENT_VARIABLE)
+        .filter(|folder| !folder.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            var_os(HOME_ENVIRONMENT_VARIABLE)
+                .map(|home| PathBuf::from(home).join(XDG_DEFAULT_CACHE_FOLDER))
+        })
+}
+
 // `expand_tilde` replaces a leading `~/` with the user's home directory.
 pub fn expand_tilde<P: AsRef<Path>>(path: P) -> PathBuf {
     let path = path.as_ref();
@@ -90,6 +244,40 @@ pub fn expand_tilde<P: AsRef<Path>>(path: P) -> PathBuf {
     path.to_path_buf()
 }
 
+/// `path` with every `$NAME` or `${NAME}` replaced by that environment variable and a leading
+/// `~` by the home directory. The error names the variable that isn't set.
+pub(crate) fn expand_path<P: AsRef<Path>>(path: P) -> Result<PathBuf, String> {
+    let path = path.as_ref();
+    let path = match path.to_str() {
+        Some(path_str) => {
+            let mut expanded = String::with_capacity(path_str.len());
+            let mut last_end = 0;
+            for captures in ENVIRONMENT_VARIABLE_REGEX.captures_iter(path_str) {
+                let (Some(whole), Some(name)) =
+                    (captures.get(0), captures.get(1).or_else(|| captures.get(2)))
+                else {
+                    continue;
+                };
+                let value = var(name.as_str()).map_err(|_| name.as_str().to_string())?;
+                expanded.push_str(&path_str[last_end..whole.start()]);
+                expanded.push_str(&value);
+                last_end = whole.end();
+            }
+            expanded.push_str(&path_str[last_end..]);
+            PathBuf::from(expanded)
+        },
+        // Variables are only looked for in valid UTF-8.
+        None => path.to_path_buf(),
+    };
+
+    if matches!(path.components().next(), Some(Component::Normal(first)) if first == TILDE)
+        && var_os(HOME_ENVIRONMENT_VARIABLE).is_none()
+    {
+        return Err(HOME_ENVIRONMENT_VARIABLE.to_string());
+    }
+    Ok(expand_tilde(path))
+}
+
 pub(crate) fn format_relative_path(path: &Path, base_path: &Path) -> String {
     path.strip_prefix(base_path)
         .unwrap_or(path)
@@ -97,6 +285,88 @@ pub(crate) fn format_relative_path(path: &Path, base_path: &Path) -> String {
         .into_owned()
 }
 
+fn push_path(
+    files: &Mutex<Vec<PathBuf>>,
+    path: &Path,
+    lock_poisoned: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    files
+        .lock()
+        .map_err(|error| format!("{lock_poisoned}: {error}"))?
+        .push(path.to_path_buf());
+    Ok(())
+}
+
+fn into_sorted(
+    files: Mutex<Vec<PathBuf>>,
+    lock_poisoned: &str,
+) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+    let mut files = files
+        .into_inner()
+        .map_err(|error| format!("{lock_poisoned}: {error}"))?;
+    // The parallel walk finds files in a different order each run; sorting keeps runs
+    // reproducible.
+    files.sort_unstable();
+    Ok(files)
+}
+
+/// The files of each kind `collect_repository_files` has found so far.
+#[derive(Default)]
+struct FileCollections {
+    bases:    Mutex<Vec<PathBuf>>,
+    canvases: Mutex<Vec<PathBuf>>,
+    images:   Mutex<Vec<PathBuf>>,
+    markdown: Mutex<Vec<PathBuf>>,
+    other:    Mutex<Vec<PathBuf>>,
+}
+
+impl FileCollections {
+    fn push(&self, path: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let extension = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+
+        if extension == MARKDOWN_EXTENSION {
+            push_path(&self.markdown, path, MARKDOWN_FILE_COLLECTION_LOCK_POISONED)
+        } else if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+            push_path(&self.images, path, IMAGE_FILE_COLLECTION_LOCK_POISONED)
+        } else if extension == BASE_EXTENSION {
+            push_path(&self.bases, path, BASE_FILE_COLLECTION_LOCK_POISONED)
+        } else if extension == CANVAS_EXTENSION {
+            push_path(&self.canvases, path, CANVAS_FILE_COLLECTION_LOCK_POISONED)
+        } else {
+            push_path(&self.other, path, OTHER_FILE_COLLECTION_LOCK_POISONED)
+        }
+    }
+
+    fn into_repository_files(
+        self,
+        obsidian_path: &Path,
+    ) -> Result<RepositoryFiles, Box<dyn Error + Send + Sync>> {
+        let mut other = into_sorted(self.other, OTHER_FILE_COLLECTION_LOCK_POISONED)?;
+        // `.ok`, `.trash` and `.git` hold ok's and other tools' own files, not the user's.
+        other.retain(|path| !is_hidden(path.strip_prefix(obsidian_path).unwrap_or(path)));
+
+        Ok(RepositoryFiles {
+            bases: into_sorted(self.bases, BASE_FILE_COLLECTION_LOCK_POISONED)?,
+            canvases: into_sorted(self.canvases, CANVAS_FILE_COLLECTION_LOCK_POISONED)?,
+            images: into_sorted(self.images, IMAGE_FILE_COLLECTION_LOCK_POISONED)?,
+            markdown: into_sorted(self.markdown, MARKDOWN_FILE_COLLECTION_LOCK_POISONED)?,
+            other,
+        })
+    }
+}
+
+/// A path with a file or folder whose name starts with a dot.
+fn is_hidden(relative_path: &Path) -> bool {
+    relative_path.components().any(|component| {
+        matches!(component, Component::Normal(name)
+            if name.to_str().is_some_and(|name| name.starts_with(HIDDEN_FILE_PREFIX)))
+    })
+}
+
 // `rayon` via `.into_par_iter()` keeps `collect_repository_files` at about 4ms
 // instead of the 12ms measured with `walkdir`.
 pub fn collect_repository_files(
@@ -112,8 +382,7 @@ pub fn collect_repository_files(
     fn visit_dirs(
         dirs: Vec<PathBuf>,
         ignore_folders: &[PathBuf],
-        markdown_files: &Mutex<Vec<PathBuf>>,
-        image_files: &Mutex<Vec<PathBuf>>,
+        file_collections: &FileCollections,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         dirs.into_par_iter().try_for_each(|dir| {
             if is_ignored(&dir, ignore_folders) {
@@ -130,58 +399,50 @@ pub fn collect_repository_files(
                     continue;
                 }
 
-                if let Some(ext) = path
-                    .extension()
-                    .and_then(OsStr::to_str)
-                    .map(str::to_lowercase)
-                {
-                    if ext == MARKDOWN_EXTENSION {
-                        markdown_files
-                            .lock()
-                            .map_err(|error| {
-                                format!("{MARKDOWN_FILE_COLLECTION_LOCK_POISONED}: {error}")
-                            })?
-                            .push(path.clone());
-                    } else if IMAGE_EXTENSIONS.contains(&ext.as_str()) {
-                        image_files
-                            .lock()
-                            .map_err(|error| {
-                                format!("{IMAGE_FILE_COLLECTION_LOCK_POISONED}: {error}")
-                            })?
-                            .push(path.clone());
-                    }
-                }
-
                 if path.is_dir() {
                     subdirs.push(path);
+                } else {
+                    file_collections.push(&path)?;
                 }
             }
 
             if !subdirs.is_empty() {
-                visit_dirs(subdirs, ignore_folders, markdown_files, image_files)?;
+                visit_dirs(subdirs, ignore_folders, file_collections)?;
             }
             Ok(())
         })
     }
 
-    let markdown_files = Mutex::new(Vec::new());
-    let image_files = Mutex::new(Vec::new());
-
+    let ignore_folders = with_own_folders(validated_config, ignore_folders);
+    let file_collections = FileCollections::default();
     visit_dirs(
         vec![validated_config.obsidian_path().to_path_buf()],
-        ignore_folders,
-        &markdown_files,
-        &image_files,
+        &ignore_folders,
+        &file_collections,
     )?;
 
-    Ok(RepositoryFiles {
-        markdown: markdown_files
-            .into_inner()
-            .map_err(|error| format!("{MARKDOWN_FILE_COLLECTION_LOCK_POISONED}: {error}"))?,
-        images:   image_files
-            .into_inner()
-            .map_err(|error| format!("{IMAGE_FILE_COLLECTION_LOCK_POISONED}: {error}"))?,
-    })
+    file_collections.into_repository_files(validated_config.obsidian_path())
+}
+
+/// `ignore_folders` plus the folders ok writes to: the output folder, the state folder and the
+/// in-vault cache folder earlier versions kept state in. Reports and caches from earlier runs
+/// are never analyzed as notes, images or other files, whatever `ignore_folders` holds.
+fn with_own_folders(
+    validated_config: &ValidatedConfig,
+    ignore_folders: &[PathBuf],
+) -> Vec<PathBuf> {
+    let obsidian_path = validated_config.obsidian_path();
+    let mut folders = ignore_folders.to_vec();
+    for own_folder in [
+        obsidian_path.join(validated_config.output_folder()),
+        validated_config.state_folder(),
+        obsidian_path.join(CACHE_FOLDER),
+    ] {
+        if !folders.contains(&own_folder) {
+            folders.push(own_folder);
+        }
+    }
+    folders
 }
 
 #[cfg(target_os = "macos")]
@@ -232,6 +493,113 @@ pub fn set_file_dates(
     Ok(())
 }
 
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod write_atomically_tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn test_write_atomically_replaces_contents_and_leaves_no_temp_file() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("note.md");
+        fs::write(&path, "old").unwrap();
+
+        write_atomically(&path, "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(
+            atomic_temp_path(&path),
+            temp_dir.path().join(".note.md.ok-tmp")
+        );
+        assert!(!atomic_temp_path(&path).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomically_keeps_permissions_and_hard_links() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("note.md");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_atomically(&path, "new").unwrap();
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+
+        let linked = temp_dir.path().join("linked.md");
+        fs::hard_link(&path, &linked).unwrap();
+        write_atomically(&path, "newer").unwrap();
+        assert_eq!(fs::read_to_string(&linked).unwrap(), "newer");
+    }
+
+    #[test]
+    fn test_write_atomically_failure_leaves_original() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("note.md");
+        fs::write(&path, "old").unwrap();
+        // A directory where the temp file should go makes the write fail.
+        fs::create_dir(atomic_temp_path(&path)).unwrap();
+
+        assert!(write_atomically(&path, "new").is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod collect_repository_files_tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::constants::CACHE_FILE;
+    use crate::constants::OUTPUT_MARKDOWN_FILE;
+    use crate::test_support;
+    use crate::test_support::TestFileBuilder;
+
+    #[test]
+    fn test_own_output_and_cache_are_not_collected() {
+        let temp_dir = TempDir::new().unwrap();
+        let note = TestFileBuilder::new()
+            .with_content("a note")
+            .create(&temp_dir, "note.md");
+        let output_folder = temp_dir.path().join("output");
+        fs::create_dir_all(&output_folder).unwrap();
+        fs::write(output_folder.join(OUTPUT_MARKDOWN_FILE), "# back populate").unwrap();
+        fs::write(output_folder.join("backup.tar"), "").unwrap();
+        let cache_folder = temp_dir.path().join(CACHE_FOLDER);
+        fs::create_dir_all(&cache_folder).unwrap();
+        fs::write(cache_folder.join(CACHE_FILE), "{}").unwrap();
+        fs::write(cache_folder.join("cached.png"), [0_u8; 4]).unwrap();
+
+        // Setting ignore_folders after the output folder leaves the output folder out of it.
+        let validated_config =
+            test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.ignore_folders(Some(Vec::new()));
+            })
+            .unwrap();
+        let ignore_folders = validated_config.ignore_folders().unwrap_or(&[]);
+        assert!(!ignore_folders.contains(&output_folder));
+
+        let repository_files = collect_repository_files(&validated_config, ignore_folders).unwrap();
+
+        assert_eq!(repository_files.markdown, vec![note]);
+        assert!(repository_files.images.is_empty());
+        assert!(repository_files.other.is_empty());
+    }
+}
+
 #[cfg(test)]
 mod expand_tilde_tests {
     use std::ffi::OsStr;
@@ -279,6 +647,21 @@ mod expand_tilde_tests {
             );
         }
     }
+
+    #[test]
+    fn test_expand_path_environment_variables() {
+        if let Some(home) = var_os("HOME") {
+            let expected = PathBuf::from(home).join("notes");
+            assert_eq!(expand_path("$HOME/notes"), Ok(expected.clone()));
+            assert_eq!(expand_path("${HOME}/notes"), Ok(expected.clone()));
+            assert_eq!(expand_path("~/notes"), Ok(expected));
+        }
+        assert_eq!(expand_path("/cost/$5"), Ok(PathBuf::from("/cost/$5")));
+        assert_eq!(
+            expand_path("/vaults/$OK_UNSET_TEST_VARIABLE/brain"),
+            Err("OK_UNSET_TEST_VARIABLE".to_string())
+        );
+    }
 }
 
 #[cfg(test)]
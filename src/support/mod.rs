@@ -2,16 +2,38 @@ mod filesystem;
 mod patterns;
 mod vec_enum_filter;
 
+pub use filesystem::RepositoryFiles;
+pub(crate) use filesystem::atomic_temp_path;
 pub use filesystem::collect_repository_files;
+pub(crate) use filesystem::copy_folder;
+pub(crate) use filesystem::expand_path;
 pub use filesystem::expand_tilde;
 pub(crate) use filesystem::format_relative_path;
+pub(crate) use filesystem::has_other_links;
+pub(crate) use filesystem::move_file;
+pub(crate) use filesystem::platform_cache_folder;
 pub use filesystem::read_contents_from_file;
 pub use filesystem::set_file_dates;
+pub(crate) use filesystem::write_atomically;
+pub use patterns::BIBTEX_ENTRY_REGEX;
+pub use patterns::BLOCK_ID_REGEX;
+pub use patterns::CALLOUT_HEADER_REGEX;
+pub use patterns::CITATION_GROUP_REGEX;
+pub use patterns::CITATION_KEY_REGEX;
+pub use patterns::DATE_MENTION_REGEX;
 pub use patterns::EMAIL_REGEX;
+pub use patterns::FLASHCARD_ID_REGEX;
+pub use patterns::HIGH_ENTROPY_TOKEN_REGEX;
 pub use patterns::IMAGE_REGEX;
+pub use patterns::LIST_ITEM_REGEX;
 pub use patterns::MARKDOWN_REGEX;
+pub use patterns::NATIONAL_ID_REGEX;
+pub use patterns::NOTE_EMBED_REGEX;
+pub use patterns::PHONE_NUMBER_REGEX;
 pub use patterns::RAW_HTTP_REGEX;
+pub use patterns::SECRET_DETECTOR_REGEXES;
 pub use patterns::TAG_REGEX;
+pub use patterns::TEMPLATE_VARIABLE_REGEX;
 pub use patterns::build_case_insensitive_word_finder;
 pub(crate) use patterns::compile_regex;
 pub(crate) use vec_enum_filter::EnumFilter;
@@ -5,20 +5,66 @@ use std::sync::LazyLock;
 use regex::Regex;
 use regex::escape;
 
+use crate::constants::BIBTEX_ENTRY_PATTERN;
+use crate::constants::BLOCK_ID_PATTERN;
+use crate::constants::CALLOUT_HEADER_PATTERN;
 use crate::constants::CASE_INSENSITIVE_WORD_PATTERN_PREFIX;
 use crate::constants::CASE_INSENSITIVE_WORD_PATTERN_SUFFIX;
+use crate::constants::CITATION_GROUP_PATTERN;
+use crate::constants::CITATION_KEY_PATTERN;
+use crate::constants::DATE_MENTION_PATTERN;
 use crate::constants::EMAIL_PATTERN;
+use crate::constants::ENVIRONMENT_VARIABLE_PATTERN;
+use crate::constants::FLASHCARD_ID_PATTERN;
+use crate::constants::HIGH_ENTROPY_TOKEN_PATTERN;
 use crate::constants::IMAGE_EXTENSIONS;
 use crate::constants::IMAGE_EXTENSIONS_SEPARATOR;
 use crate::constants::INVALID_REGEX_EXIT_CODE;
 use crate::constants::INVALID_REGEX_PATTERN;
+use crate::constants::LIST_ITEM_PATTERN;
 use crate::constants::MARKDOWN_LINK_PATTERN;
+use crate::constants::NATIONAL_ID_PATTERN;
+use crate::constants::NOTE_EMBED_PATTERN;
+use crate::constants::PHONE_NUMBER_PATTERN;
 use crate::constants::RAW_HTTP_PATTERN;
+use crate::constants::SECRET_DETECTOR_PATTERNS;
 use crate::constants::TAG_PATTERN;
+use crate::constants::TEMPLATE_VARIABLE_PATTERN;
 
 pub static MARKDOWN_REGEX: LazyLock<Regex> = LazyLock::new(|| compile_regex(MARKDOWN_LINK_PATTERN));
+pub static CITATION_GROUP_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(CITATION_GROUP_PATTERN));
+pub static CITATION_KEY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(CITATION_KEY_PATTERN));
+pub static DATE_MENTION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(DATE_MENTION_PATTERN));
 pub static EMAIL_REGEX: LazyLock<Regex> = LazyLock::new(|| compile_regex(EMAIL_PATTERN));
+pub static ENVIRONMENT_VARIABLE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(ENVIRONMENT_VARIABLE_PATTERN));
+pub static NATIONAL_ID_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(NATIONAL_ID_PATTERN));
+pub static PHONE_NUMBER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(PHONE_NUMBER_PATTERN));
+pub static HIGH_ENTROPY_TOKEN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(HIGH_ENTROPY_TOKEN_PATTERN));
+pub static SECRET_DETECTOR_REGEXES: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    SECRET_DETECTOR_PATTERNS
+        .iter()
+        .map(|&(name, pattern)| (name, compile_regex(pattern)))
+        .collect()
+});
 pub static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| compile_regex(TAG_PATTERN));
+pub static TEMPLATE_VARIABLE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(TEMPLATE_VARIABLE_PATTERN));
+pub static BIBTEX_ENTRY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(BIBTEX_ENTRY_PATTERN));
+pub static BLOCK_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| compile_regex(BLOCK_ID_PATTERN));
+pub static CALLOUT_HEADER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(CALLOUT_HEADER_PATTERN));
+pub static FLASHCARD_ID_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| compile_regex(FLASHCARD_ID_PATTERN));
+pub static LIST_ITEM_REGEX: LazyLock<Regex> = LazyLock::new(|| compile_regex(LIST_ITEM_PATTERN));
+pub static NOTE_EMBED_REGEX: LazyLock<Regex> = LazyLock::new(|| compile_regex(NOTE_EMBED_PATTERN));
 pub static RAW_HTTP_REGEX: LazyLock<Regex> = LazyLock::new(|| compile_regex(RAW_HTTP_PATTERN));
 static IMAGE_EXTENSIONS_PATTERN: LazyLock<String> =
     LazyLock::new(|| IMAGE_EXTENSIONS.join(IMAGE_EXTENSIONS_SEPARATOR));
@@ -0,0 +1,113 @@
+//! Proptest generators for vault content with known wikilink placements.
+//!
+//! The replacement engine's property tests use them; the `test-fixtures` feature exports them
+//! from the library, for property tests of a custom `ReplacementSource`.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use proptest::prelude::*;
+
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::SPACE;
+use crate::wikilink::ToWikilink;
+
+/// Names of the notes a generated vault may hold. Two contain a space, so mentions exercise
+/// multi-word back-populate matches.
+pub const NOTE_NAMES: [&str; 5] = ["Alpha", "Beta Note", "Gamma", "Delta Ray", "Echo"];
+
+const FILLER_WORDS: [&str; 6] = ["the", "and", "about", "with", "then", "notes"];
+
+/// One word or phrase of a generated line.
+#[derive(Clone, Debug)]
+pub enum Token {
+    Filler(&'static str),
+    /// Plain text naming a note, in its own case or lowercased - back populate links it.
+    Mention(String),
+    /// A wikilink to a note, written as `[[Name]]`.
+    Link(&'static str),
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match self {
+            Self::Filler(word) => (*word).to_string(),
+            Self::Mention(mention) => mention.clone(),
+            Self::Link(target) => target.to_wikilink(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneratedNote {
+    pub name:  &'static str,
+    pub body:  String,
+    /// Targets of the wikilinks written into `body`, in order.
+    pub links: Vec<&'static str>,
+}
+
+pub fn token() -> impl Strategy<Value = Token> {
+    prop_oneof![
+        3 => prop::sample::select(FILLER_WORDS.to_vec()).prop_map(Token::Filler),
+        1 => prop::sample::select(NOTE_NAMES.to_vec()).prop_flat_map(|name| {
+            prop_oneof![Just(name.to_string()), Just(name.to_lowercase())]
+                .prop_map(Token::Mention)
+        }),
+        1 => prop::sample::select(NOTE_NAMES.to_vec()).prop_map(Token::Link),
+    ]
+}
+
+/// A body of one to five lines of one to eight tokens, with the wikilink targets it holds.
+pub fn note_body() -> impl Strategy<Value = (String, Vec<&'static str>)> {
+    prop::collection::vec(prop::collection::vec(token(), 1..8), 1..5).prop_map(|lines| {
+        let links = lines
+            .iter()
+            .flatten()
+            .filter_map(|token| match token {
+                Token::Link(target) => Some(*target),
+                Token::Filler(_) | Token::Mention(_) => None,
+            })
+            .collect();
+        let body = lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(Token::text)
+                    .collect::<Vec<_>>()
+                    .join(&SPACE.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        (body, links)
+    })
+}
+
+/// Two to five notes with distinct names from `NOTE_NAMES`, each with a generated body.
+pub fn vault() -> impl Strategy<Value = Vec<GeneratedNote>> {
+    prop::sample::subsequence(NOTE_NAMES.to_vec(), 2..=NOTE_NAMES.len()).prop_flat_map(|names| {
+        let bodies = prop::collection::vec(note_body(), names.len());
+        (Just(names), bodies).prop_map(|(names, bodies)| {
+            names
+                .into_iter()
+                .zip(bodies)
+                .map(|(name, (body, links))| GeneratedNote { name, body, links })
+                .collect()
+        })
+    })
+}
+
+/// Writes each note to `dir` as `<name>.md` with a title property.
+///
+/// # Errors
+///
+/// Returns the error of the first note that can't be written.
+pub fn write_vault(notes: &[GeneratedNote], dir: &Path) -> io::Result<()> {
+    for note in notes {
+        fs::write(
+            dir.join(format!("{}{MARKDOWN_SUFFIX}", note.name)),
+            format!("---\ntitle: {}\n---\n{}", note.name, note.body),
+        )?;
+    }
+    Ok(())
+}
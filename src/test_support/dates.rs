@@ -32,7 +32,7 @@ pub fn parse_datetime(s: &str) -> DateTime<Utc> {
 }
 
 pub fn get_test_markdown_file(path: PathBuf) -> MarkdownFile {
-    MarkdownFile::new(path, DEFAULT_TIMEZONE).unwrap()
+    MarkdownFile::new(path, DEFAULT_TIMEZONE, FORMAT_DATE, None, &[]).unwrap()
 }
 
 pub fn frontmatter_date_wikilink(date: DateTime<Utc>) -> String {
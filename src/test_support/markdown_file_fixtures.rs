@@ -52,8 +52,14 @@ pub fn create_test_environment(
         )
         .create(&temp_dir, "test.md");
 
-    let markdown_file =
-        MarkdownFile::new(file_path, validated_config.operational_timezone()).unwrap();
+    let markdown_file = MarkdownFile::new(
+        file_path,
+        validated_config.operational_timezone(),
+        validated_config.date_link_format(),
+        None,
+        &[],
+    )
+    .unwrap();
     obsidian_repository.markdown_files.push(markdown_file);
 
     if let Some(wikilinks) = wikilinks {
@@ -1,19 +1,55 @@
+use std::fmt::Write;
 use std::path::Path;
 use std::path::PathBuf;
 
+use chrono::NaiveDate;
+use chrono::Utc;
 use chrono_tz::Tz;
+use chrono_tz::UTC;
 use derive_builder::Builder;
 use derive_builder::UninitializedFieldError;
 use regex::Regex;
 use thiserror::Error;
 
+use crate::app_settings::AttachmentFolder;
+use crate::app_settings::LinkFormat;
+use crate::config::AuditTrail;
+use crate::config::BackPopulateGrouping;
+use crate::config::ConflictResolution;
+use crate::config::DuplicateKeyResolution;
+use crate::config::EntityFolderRule;
+use crate::config::ExportProfile;
+use crate::config::FlashcardRule;
+use crate::config::ImpossibleDatePolicy;
+use crate::config::LinkAliasStyle;
+use crate::config::PersistReasonKind;
+use crate::config::ReplacementScope;
+use crate::config::ReviewRule;
+use crate::config::SavedReport;
+use crate::constants::CACHE_FOLDER;
 use crate::constants::CLOSING_WIKILINK;
+use crate::constants::DATE_LINK_FORMAT_CHECK_DATE;
+use crate::constants::DATE_LINK_FORMAT_FORBIDDEN;
+use crate::constants::DEFAULT_FUTURE_DATE_TOLERANCE_DAYS;
 use crate::constants::DEFAULT_TIMEZONE;
+use crate::constants::FORMAT_DATE;
 use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::MAX_SIMILAR_TITLE_THRESHOLD;
 use crate::constants::MIN_FILE_LIMIT;
+use crate::constants::MIN_LINT_MAX_LINE_LENGTH;
+use crate::constants::MIN_REPORT_ROW_LIMIT;
+use crate::constants::MIN_REVIEW_AFTER_DAYS;
+use crate::constants::MIN_SIMILAR_TITLE_THRESHOLD;
+use crate::constants::MIN_SKIP_RECENTLY_MODIFIED_MINUTES;
+use crate::constants::MIN_TABLE_OF_CONTENTS_HEADINGS;
+use crate::constants::NOTIFY_SCHEMES;
 use crate::constants::OBSIDIAN_FOLDER;
 use crate::constants::OPENING_WIKILINK;
+use crate::constants::RESERVED_DATE_PROPERTIES;
+use crate::note_template::NoteTemplate;
 use crate::support;
+use crate::wikilink::DEFAULT_IGNORED_INVALID_WIKILINK_REASONS;
+use crate::wikilink::InvalidWikilinkReason;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub(crate) enum ChangeMode {
@@ -24,20 +60,62 @@ pub(crate) enum ChangeMode {
 
 #[derive(Error, Debug)]
 pub(crate) enum ValidationError {
+    #[error("Bibliography file not found: {}", .0.display())]
+    BibliographyNotFound(PathBuf),
     #[error("Empty back populate file filter")]
     EmptyBackPopulateFileFilter,
+    #[error("Empty backup_before_apply - use archive or a backup command")]
+    EmptyBackupBeforeApply,
     #[error("Empty output folder")]
     EmptyOutputFolder,
+    #[error("Empty periodic note link property")]
+    EmptyPeriodicNoteLinkProperty,
     #[error("Back populate file count must be >= 1")]
     InvalidFileLimit,
+    #[error("Lint max line length must be >= 1: {0}")]
+    InvalidLintMaxLineLength(usize),
+    #[error("Report row limit must be >= 1: {0}")]
+    InvalidReportRowLimit(usize),
+    #[error("Invalid PII pattern '{0}': {1}")]
+    InvalidPiiPattern(String, String),
+    #[error("Invalid redaction pattern '{0}': {1}")]
+    InvalidRedactionPattern(String, String),
+    #[error("Invalid replacement pattern '{0}': {1}")]
+    InvalidReplacementPattern(String, String),
+    #[error("Block id placeholder '{0}' must be a single word")]
+    InvalidBlockIdPlaceholder(String),
+    #[error("Date link format {0} must write a date it can read back, without [ ] | # or ^")]
+    InvalidDateLinkFormat(String),
+    #[error("Date property {0} is always checked - remove it from date_properties")]
+    ReservedDateProperty(String),
     #[error("Invalid timezone: {0}")]
     InvalidTimezone(String),
+    #[error("Notify must be an http(s) webhook URL or a mailto: address: {0}")]
+    InvalidNotify(String),
+    #[error("Review after days must be >= {MIN_REVIEW_AFTER_DAYS}: {0}")]
+    InvalidReviewAfter(u32),
+    #[error("Invalid saved report '{0}': {1}")]
+    InvalidSavedReport(String, String),
+    #[error("Invalid secret pattern '{0}': {1}")]
+    InvalidSecretPattern(String, String),
     #[error("Obsidian path does not exist: {0}")]
     InvalidObsidianPath(String),
+    #[error("Similar title threshold must be between 1 and 100: {0}")]
+    InvalidSimilarTitleThreshold(u8),
+    #[error("Skip recently modified minutes must be >= {MIN_SKIP_RECENTLY_MODIFIED_MINUTES}: {0}")]
+    InvalidSkipRecentlyModifiedMinutes(u32),
+    #[error("Table of contents minimum heading count must be >= 2: {0}")]
+    InvalidTableOfContentsMinHeadings(usize),
+    #[error("Match decisions file not found: {}", .0.display())]
+    MatchDecisionsNotFound(PathBuf),
     #[error("Missing obsidian path")]
     MissingObsidianPath,
     #[error("Field not initialized: {0}")]
     UninitializedField(String),
+    #[error("Can't expand path '{0}' - environment variable {1} is not set")]
+    UnexpandablePath(String, String),
+    #[error("Unknown profile '{0}' - the config's profiles are: {1}")]
+    UnknownProfile(String, String),
 }
 
 impl From<UninitializedFieldError> for ValidationError {
@@ -46,7 +124,46 @@ impl From<UninitializedFieldError> for ValidationError {
     }
 }
 
+/// Whether `format` writes a date that reads back as the same date and can sit in a wikilink.
+/// An unknown specifier fails to write.
+fn date_link_round_trips(format: &str) -> bool {
+    let (year, month, day) = DATE_LINK_FORMAT_CHECK_DATE;
+    let Some(date) = NaiveDate::from_ymd_opt(year, month, day) else {
+        return false;
+    };
+    let mut written = String::new();
+    write!(written, "{}", date.format(format)).is_ok()
+        && !written.contains(DATE_LINK_FORMAT_FORBIDDEN)
+        && NaiveDate::parse_from_str(&written, format) == Ok(date)
+}
+
+/// A `pii_patterns` entry with its pattern compiled.
+#[derive(Clone, Debug)]
+pub(crate) struct PiiPatternRule {
+    pub name:  String,
+    pub regex: Regex,
+}
+
+/// `export.redact` with its patterns compiled and its tag normalized.
+#[derive(Clone, Debug)]
+pub(crate) struct RedactionRule {
+    pub regexes:     Vec<Regex>,
+    pub private_tag: String,
+}
+
+/// A `replacements` rule with its pattern compiled.
+#[derive(Clone, Debug)]
+pub(crate) struct RegexReplacementRule {
+    pub regex:       Regex,
+    pub replacement: String,
+    pub scope:       ReplacementScope,
+}
+
 #[derive(Debug, Builder)]
+#[allow(
+    clippy::struct_excessive_bools,
+    reason = "each bool is an independent config switch"
+)]
 #[builder(
     pattern = "mutable",
     build_fn(
@@ -56,21 +173,168 @@ impl From<UninitializedFieldError> for ValidationError {
 )]
 pub(crate) struct ValidatedConfig {
     #[builder(default)]
-    change_mode:                  ChangeMode,
+    change_mode:                      ChangeMode,
+    #[builder(default)]
+    ambiguous_match_context_lines:    Option<usize>,
+    /// Obsidian's "Default location for new attachments".
+    #[builder(default)]
+    attachment_folder:                AttachmentFolder,
+    #[builder(default)]
+    audit_trail:                      Option<AuditTrail>,
+    #[builder(default)]
+    back_populate_file_filter:        Option<String>,
+    #[builder(default)]
+    back_populate_grouping:           BackPopulateGrouping,
+    #[builder(default)]
+    backup_before_apply:              Option<String>,
+    #[builder(default)]
+    bibliography:                     Option<PathBuf>,
+    #[builder(default)]
+    block_id_placeholder:             Option<String>,
     #[builder(default)]
-    back_populate_file_filter:    Option<String>,
+    changelog:                        Option<PathBuf>,
+    #[builder(default)]
+    coerce_property_types:            bool,
+    #[builder(default)]
+    convert_admonitions:              bool,
+    #[builder(default)]
+    create_missing_link_targets:      bool,
+    #[builder(default = "FORMAT_DATE.to_string()")]
+    date_link_format:                 String,
+    #[builder(default)]
+    date_properties:                  Vec<String>,
+    #[builder(default)]
+    detect_pii:                       bool,
     #[builder(setter(strip_option), default)]
-    do_not_back_populate_regexes: Option<Vec<Regex>>,
+    do_not_back_populate_regexes:     Option<Vec<Regex>>,
+    #[builder(default)]
+    entity_folders:                   Vec<EntityFolderRule>,
+    #[builder(default = "true")]
+    exclude_math:                     bool,
+    #[builder(default)]
+    export:                           Option<ExportProfile>,
+    #[builder(default)]
+    export_redaction:                 Option<RedactionRule>,
+    #[builder(default)]
+    file_limit:                       Option<usize>,
+    #[builder(default)]
+    fix_duplicate_frontmatter_keys:   Option<DuplicateKeyResolution>,
+    #[builder(default)]
+    fix_entity_notes:                 bool,
+    #[builder(default)]
+    fix_frontmatter_link_values:      bool,
+    #[builder(default)]
+    fix_impossible_dates:             Option<ImpossibleDatePolicy>,
+    #[builder(default)]
+    fix_self_links:                   bool,
+    #[builder(default)]
+    flashcards:                       Option<FlashcardRule>,
+    #[builder(default)]
+    frontmatter_link_properties:      Vec<String>,
+    #[builder(default)]
+    frontmatter_only:                 bool,
+    #[builder(default = "DEFAULT_FUTURE_DATE_TOLERANCE_DAYS")]
+    future_date_tolerance_days:       u32,
     #[builder(default)]
-    file_limit:                   Option<usize>,
+    generate_aliases:                 bool,
+    #[builder(default)]
+    hash_all_images:                  bool,
+    #[builder(default)]
+    hold_persist_reasons:             Vec<PersistReasonKind>,
     #[builder(setter(custom), default)]
-    ignore_folders:               Option<Vec<PathBuf>>,
+    ignore_folders:                   Option<Vec<PathBuf>>,
+    #[builder(default = "DEFAULT_IGNORED_INVALID_WIKILINK_REASONS.to_vec()")]
+    ignored_invalid_wikilink_reasons: Vec<InvalidWikilinkReason>,
+    #[builder(default)]
+    journal_folders:                  Vec<PathBuf>,
+    #[builder(default)]
+    link_acronyms:                    bool,
+    #[builder(default)]
+    link_alias_style:                 LinkAliasStyle,
+    #[builder(default)]
+    link_date_mentions:               bool,
+    /// Obsidian's "New link format" and "Use [[Wikilinks]]" settings.
+    #[builder(default)]
+    link_format:                      LinkFormat,
+    #[builder(default)]
+    link_target_template:             Option<NoteTemplate>,
+    #[builder(default)]
+    lint_markdown:                    bool,
+    #[builder(default)]
+    lint_max_line_length:             Option<usize>,
+    #[builder(default)]
+    list_block_ids:                   bool,
+    #[builder(default)]
+    match_decisions:                  Option<PathBuf>,
+    #[builder(default)]
+    normalize_link_aliases:           bool,
+    #[builder(default)]
+    normalize_lists:                  bool,
+    #[builder(default)]
+    normalize_punctuation:            bool,
+    #[builder(default)]
+    notify:                           Option<String>,
     #[builder(setter(into))]
-    obsidian_path:                PathBuf,
+    obsidian_path:                    PathBuf,
+    #[builder(default)]
+    only_modify_owner:                Option<String>,
     #[builder(default = "DEFAULT_TIMEZONE.to_string()")]
-    operational_timezone:         String,
+    operational_timezone:             String,
     #[builder(setter(custom))]
-    output_folder:                PathBuf,
+    output_folder:                    PathBuf,
+    #[builder(default)]
+    periodic_note_link_property:      Option<String>,
+    #[builder(default)]
+    periodic_note_template:           Option<NoteTemplate>,
+    #[builder(default)]
+    pii_patterns:                     Vec<PiiPatternRule>,
+    #[builder(default)]
+    purge_trash_after_days:           Option<u32>,
+    #[builder(default)]
+    rename_unsafe_paths:              bool,
+    #[builder(default)]
+    repair_heading_links:             bool,
+    #[builder(default)]
+    repair_invalid_wikilinks:         bool,
+    #[builder(default)]
+    report_deep_links:                bool,
+    #[builder(default)]
+    report_rare_words:                bool,
+    #[builder(default)]
+    report_row_limit:                 Option<usize>,
+    #[builder(default)]
+    replacement_rules:                Vec<RegexReplacementRule>,
+    #[builder(default)]
+    resolve_conflict_copies:          Option<ConflictResolution>,
+    #[builder(default)]
+    review_after:                     Vec<ReviewRule>,
+    #[builder(default)]
+    saved_reports:                    Vec<SavedReport>,
+    #[builder(default)]
+    scan_secrets:                     bool,
+    #[builder(default)]
+    secret_patterns:                  Vec<Regex>,
+    #[builder(default)]
+    set_next_review:                  bool,
+    #[builder(default)]
+    similar_title_threshold:          Option<u8>,
+    #[builder(default)]
+    skip_recently_modified_minutes:   Option<u32>,
+    #[builder(default)]
+    split_notes:                      Vec<String>,
+    #[builder(default)]
+    split_note_template:              Option<NoteTemplate>,
+    #[builder(setter(strip_option), default)]
+    state_folder:                     Option<PathBuf>,
+    #[builder(default)]
+    table_of_contents_min_headings:   Option<usize>,
+    /// Template folders found in the plugins' settings - also in `ignore_folders`.
+    #[builder(setter(custom), default)]
+    template_folders:                 Vec<PathBuf>,
+    #[builder(default)]
+    vault_index:                      bool,
+    #[builder(default)]
+    write_patch:                      bool,
 }
 
 impl ValidatedConfigBuilder {
@@ -94,6 +358,109 @@ impl ValidatedConfigBuilder {
             return Err(ValidationError::InvalidFileLimit);
         }
 
+        // `lint_max_line_length` must meet `MIN_LINT_MAX_LINE_LENGTH`.
+        if let Some(Some(length)) = self.lint_max_line_length
+            && length < MIN_LINT_MAX_LINE_LENGTH
+        {
+            return Err(ValidationError::InvalidLintMaxLineLength(length));
+        }
+
+        // `report_row_limit` must meet `MIN_REPORT_ROW_LIMIT`.
+        if let Some(Some(limit)) = self.report_row_limit
+            && limit < MIN_REPORT_ROW_LIMIT
+        {
+            return Err(ValidationError::InvalidReportRowLimit(limit));
+        }
+
+        // `similar_title_threshold` is a percentage.
+        if let Some(Some(threshold)) = self.similar_title_threshold
+            && !(MIN_SIMILAR_TITLE_THRESHOLD..=MAX_SIMILAR_TITLE_THRESHOLD).contains(&threshold)
+        {
+            return Err(ValidationError::InvalidSimilarTitleThreshold(threshold));
+        }
+
+        // `skip_recently_modified_minutes` must meet `MIN_SKIP_RECENTLY_MODIFIED_MINUTES`.
+        if let Some(Some(minutes)) = self.skip_recently_modified_minutes
+            && minutes < MIN_SKIP_RECENTLY_MODIFIED_MINUTES
+        {
+            return Err(ValidationError::InvalidSkipRecentlyModifiedMinutes(minutes));
+        }
+
+        // `review_after` days must meet `MIN_REVIEW_AFTER_DAYS`.
+        if let Some(rule) = self
+            .review_after
+            .iter()
+            .flatten()
+            .find(|rule| rule.days < MIN_REVIEW_AFTER_DAYS)
+        {
+            return Err(ValidationError::InvalidReviewAfter(rule.days));
+        }
+
+        // `table_of_contents_min_headings` must meet `MIN_TABLE_OF_CONTENTS_HEADINGS`.
+        if let Some(Some(count)) = self.table_of_contents_min_headings
+            && count < MIN_TABLE_OF_CONTENTS_HEADINGS
+        {
+            return Err(ValidationError::InvalidTableOfContentsMinHeadings(count));
+        }
+
+        // `bibliography` must name a file.
+        if let Some(Some(bibliography)) = &self.bibliography
+            && !bibliography.is_file()
+        {
+            return Err(ValidationError::BibliographyNotFound(bibliography.clone()));
+        }
+
+        // `match_decisions` must name a file.
+        if let Some(Some(match_decisions)) = &self.match_decisions
+            && !match_decisions.is_file()
+        {
+            return Err(ValidationError::MatchDecisionsNotFound(
+                match_decisions.clone(),
+            ));
+        }
+
+        self.validate_text_settings()?;
+
+        // `date_link_format` must read back the date it writes, inside a wikilink.
+        if let Some(format) = &self.date_link_format
+            && !date_link_round_trips(format)
+        {
+            return Err(ValidationError::InvalidDateLinkFormat(format.clone()));
+        }
+
+        // `date_properties` can't name the dates ok already checks.
+        if let Some(property) = self
+            .date_properties
+            .iter()
+            .flatten()
+            .find(|property| RESERVED_DATE_PROPERTIES.contains(&property.as_str()))
+        {
+            return Err(ValidationError::ReservedDateProperty(property.clone()));
+        }
+
+        // `notify` must be a webhook URL or a `mailto:` address.
+        if let Some(Some(notify)) = &self.notify
+            && !NOTIFY_SCHEMES
+                .iter()
+                .any(|scheme| notify.starts_with(scheme) && notify.len() > scheme.len())
+        {
+            return Err(ValidationError::InvalidNotify(notify.clone()));
+        }
+
+        // `timezone` must parse as a `Tz`.
+        let timezone = self
+            .operational_timezone
+            .clone()
+            .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
+        if timezone.parse::<Tz>().is_err() {
+            return Err(ValidationError::InvalidTimezone(timezone));
+        }
+
+        Ok(())
+    }
+
+    /// Settings written as free text must hold something usable.
+    fn validate_text_settings(&self) -> Result<(), ValidationError> {
         // `back_populate_file_filter` must not be blank.
         if let Some(Some(filter)) = &self.back_populate_file_filter
             && filter.trim().is_empty()
@@ -109,13 +476,27 @@ impl ValidatedConfigBuilder {
             }
         }
 
-        // `timezone` must parse as a `Tz`.
-        let timezone = self
-            .operational_timezone
-            .clone()
-            .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
-        if timezone.parse::<Tz>().is_err() {
-            return Err(ValidationError::InvalidTimezone(timezone));
+        // `backup_before_apply` must not be blank.
+        if let Some(Some(backup)) = &self.backup_before_apply
+            && backup.trim().is_empty()
+        {
+            return Err(ValidationError::EmptyBackupBeforeApply);
+        }
+
+        // `periodic_note_link_property` must not be blank.
+        if let Some(Some(property)) = &self.periodic_note_link_property
+            && property.trim().is_empty()
+        {
+            return Err(ValidationError::EmptyPeriodicNoteLinkProperty);
+        }
+
+        // `block_id_placeholder` must be one token at the end of a line.
+        if let Some(Some(placeholder)) = &self.block_id_placeholder
+            && (placeholder.is_empty() || placeholder.contains(char::is_whitespace))
+        {
+            return Err(ValidationError::InvalidBlockIdPlaceholder(
+                placeholder.clone(),
+            ));
         }
 
         Ok(())
@@ -187,6 +568,20 @@ impl ValidatedConfigBuilder {
         self
     }
 
+    /// Template folders are ignored too, so their placeholders survive.
+    pub(crate) fn template_folders(&mut self, template_folders: Vec<PathBuf>) -> &mut Self {
+        let mut folders = self.get_or_create_ignore_folders();
+        for folder in self.resolve_paths(template_folders.clone()) {
+            if !folders.contains(&folder) {
+                folders.push(folder);
+            }
+        }
+
+        self.ignore_folders = Some(Some(self.resolve_paths(folders)));
+        self.template_folders = Some(template_folders);
+        self
+    }
+
     pub(crate) fn ignore_folders(&mut self, folders: Option<Vec<PathBuf>>) -> &mut Self {
         let mut folders = folders.unwrap_or_default();
         let obsidian_folder = PathBuf::from(OBSIDIAN_FOLDER);
@@ -201,10 +596,186 @@ impl ValidatedConfigBuilder {
 }
 
 impl ValidatedConfig {
+    pub(crate) const fn ambiguous_match_context_lines(&self) -> Option<usize> {
+        self.ambiguous_match_context_lines
+    }
+
+    pub(crate) const fn attachment_folder(&self) -> &AttachmentFolder { &self.attachment_folder }
+
+    pub(crate) const fn audit_trail(&self) -> Option<AuditTrail> { self.audit_trail }
+
     pub(crate) const fn change_mode(&self) -> ChangeMode { self.change_mode }
 
+    pub(crate) const fn coerce_property_types(&self) -> bool { self.coerce_property_types }
+
+    pub(crate) const fn convert_admonitions(&self) -> bool { self.convert_admonitions }
+
+    pub(crate) const fn create_missing_link_targets(&self) -> bool {
+        self.create_missing_link_targets
+    }
+
+    pub(crate) fn date_link_format(&self) -> &str { &self.date_link_format }
+
+    pub(crate) const fn date_properties(&self) -> &[String] { self.date_properties.as_slice() }
+
+    pub(crate) const fn detect_pii(&self) -> bool { self.detect_pii }
+
+    pub(crate) fn entity_folders(&self) -> &[EntityFolderRule] { &self.entity_folders }
+
+    pub(crate) const fn exclude_math(&self) -> bool { self.exclude_math }
+
+    pub(crate) const fn export(&self) -> Option<&ExportProfile> { self.export.as_ref() }
+
+    pub(crate) const fn export_redaction(&self) -> Option<&RedactionRule> {
+        self.export_redaction.as_ref()
+    }
+
     pub(crate) const fn file_limit(&self) -> Option<usize> { self.file_limit }
 
+    pub(crate) const fn fix_duplicate_frontmatter_keys(&self) -> Option<DuplicateKeyResolution> {
+        self.fix_duplicate_frontmatter_keys
+    }
+
+    pub(crate) const fn fix_entity_notes(&self) -> bool { self.fix_entity_notes }
+
+    pub(crate) const fn fix_frontmatter_link_values(&self) -> bool {
+        self.fix_frontmatter_link_values
+    }
+
+    pub(crate) const fn fix_impossible_dates(&self) -> Option<ImpossibleDatePolicy> {
+        self.fix_impossible_dates
+    }
+
+    pub(crate) const fn fix_self_links(&self) -> bool { self.fix_self_links }
+
+    pub(crate) const fn flashcards(&self) -> Option<&FlashcardRule> { self.flashcards.as_ref() }
+
+    pub(crate) const fn frontmatter_link_properties(&self) -> &[String] {
+        self.frontmatter_link_properties.as_slice()
+    }
+
+    pub(crate) const fn frontmatter_only(&self) -> bool { self.frontmatter_only }
+
+    pub(crate) const fn future_date_tolerance_days(&self) -> u32 { self.future_date_tolerance_days }
+
+    pub(crate) const fn generate_aliases(&self) -> bool { self.generate_aliases }
+
+    pub(crate) const fn hash_all_images(&self) -> bool { self.hash_all_images }
+
+    pub(crate) const fn hold_persist_reasons(&self) -> &[PersistReasonKind] {
+        self.hold_persist_reasons.as_slice()
+    }
+
+    pub(crate) const fn link_acronyms(&self) -> bool { self.link_acronyms }
+
+    pub(crate) const fn link_alias_style(&self) -> LinkAliasStyle { self.link_alias_style }
+
+    pub(crate) const fn link_date_mentions(&self) -> bool { self.link_date_mentions }
+
+    pub(crate) const fn link_format(&self) -> LinkFormat { self.link_format }
+
+    pub(crate) const fn link_target_template(&self) -> Option<&NoteTemplate> {
+        self.link_target_template.as_ref()
+    }
+
+    pub(crate) const fn lint_markdown(&self) -> bool { self.lint_markdown }
+
+    pub(crate) const fn lint_max_line_length(&self) -> Option<usize> { self.lint_max_line_length }
+
+    pub(crate) const fn list_block_ids(&self) -> bool { self.list_block_ids }
+
+    pub(crate) const fn normalize_link_aliases(&self) -> bool { self.normalize_link_aliases }
+
+    pub(crate) const fn normalize_lists(&self) -> bool { self.normalize_lists }
+
+    pub(crate) const fn normalize_punctuation(&self) -> bool { self.normalize_punctuation }
+
+    pub(crate) fn notify(&self) -> Option<&str> { self.notify.as_deref() }
+
+    pub(crate) fn periodic_note_link_property(&self) -> Option<&str> {
+        self.periodic_note_link_property.as_deref()
+    }
+
+    pub(crate) const fn periodic_note_template(&self) -> Option<&NoteTemplate> {
+        self.periodic_note_template.as_ref()
+    }
+
+    pub(crate) fn pii_patterns(&self) -> &[PiiPatternRule] { &self.pii_patterns }
+
+    pub(crate) const fn purge_trash_after_days(&self) -> Option<u32> { self.purge_trash_after_days }
+
+    pub(crate) const fn rename_unsafe_paths(&self) -> bool { self.rename_unsafe_paths }
+
+    pub(crate) const fn repair_heading_links(&self) -> bool { self.repair_heading_links }
+
+    pub(crate) const fn repair_invalid_wikilinks(&self) -> bool { self.repair_invalid_wikilinks }
+
+    pub(crate) const fn report_deep_links(&self) -> bool { self.report_deep_links }
+
+    pub(crate) const fn report_rare_words(&self) -> bool { self.report_rare_words }
+
+    pub(crate) const fn report_row_limit(&self) -> Option<usize> { self.report_row_limit }
+
+    pub(crate) const fn replacement_rules(&self) -> &[RegexReplacementRule] {
+        self.replacement_rules.as_slice()
+    }
+
+    pub(crate) const fn resolve_conflict_copies(&self) -> Option<ConflictResolution> {
+        self.resolve_conflict_copies
+    }
+
+    pub(crate) const fn review_after(&self) -> &[ReviewRule] { self.review_after.as_slice() }
+
+    pub(crate) fn saved_reports(&self) -> &[SavedReport] { &self.saved_reports }
+
+    pub(crate) const fn scan_secrets(&self) -> bool { self.scan_secrets }
+
+    pub(crate) fn secret_patterns(&self) -> &[Regex] { &self.secret_patterns }
+
+    pub(crate) const fn set_next_review(&self) -> bool { self.set_next_review }
+
+    pub(crate) const fn similar_title_threshold(&self) -> Option<u8> {
+        self.similar_title_threshold
+    }
+
+    pub(crate) const fn skip_recently_modified_minutes(&self) -> Option<u32> {
+        self.skip_recently_modified_minutes
+    }
+
+    pub(crate) fn split_notes(&self) -> &[String] { &self.split_notes }
+
+    /// Where ok keeps its own state - the image hash cache, the vault health history and the
+    /// vault index. A config that doesn't set it gets a folder of its own under the platform cache
+    /// folder from `Config::validate`; `CACHE_FOLDER` inside the vault is only used when the
+    /// platform has no cache folder, or for a `ValidatedConfig` built without one.
+    pub(crate) fn state_folder(&self) -> PathBuf {
+        self.state_folder
+            .clone()
+            .unwrap_or_else(|| self.obsidian_path.join(CACHE_FOLDER))
+    }
+
+    pub(crate) const fn split_note_template(&self) -> Option<&NoteTemplate> {
+        self.split_note_template.as_ref()
+    }
+
+    pub(crate) const fn table_of_contents_min_headings(&self) -> Option<usize> {
+        self.table_of_contents_min_headings
+    }
+
+    pub(crate) fn template_folders(&self) -> &[PathBuf] { &self.template_folders }
+
+    pub(crate) const fn vault_index(&self) -> bool { self.vault_index }
+
+    pub(crate) const fn write_patch(&self) -> bool { self.write_patch }
+
+    pub(crate) fn ignored_invalid_wikilink_reasons(&self) -> &[InvalidWikilinkReason] {
+        &self.ignored_invalid_wikilink_reasons
+    }
+
+    pub(crate) fn is_ignored_invalid_wikilink_reason(&self, reason: InvalidWikilinkReason) -> bool {
+        self.ignored_invalid_wikilink_reasons.contains(&reason)
+    }
+
     pub(crate) fn back_populate_file_filter(&self) -> Option<String> {
         self.back_populate_file_filter.as_ref().map(|filter| {
             // Wikilink filters use the inner target text before suffix handling.
@@ -223,17 +794,58 @@ impl ValidatedConfig {
             }
         })
     }
+
+    pub(crate) const fn back_populate_grouping(&self) -> BackPopulateGrouping {
+        self.back_populate_grouping
+    }
+
+    pub(crate) fn backup_before_apply(&self) -> Option<&str> { self.backup_before_apply.as_deref() }
+
+    pub(crate) fn bibliography(&self) -> Option<&Path> { self.bibliography.as_deref() }
+
+    pub(crate) fn block_id_placeholder(&self) -> Option<&str> {
+        self.block_id_placeholder.as_deref()
+    }
+
+    pub(crate) fn changelog(&self) -> Option<&Path> { self.changelog.as_deref() }
+
     pub(crate) fn do_not_back_populate_regexes(&self) -> Option<&[Regex]> {
         self.do_not_back_populate_regexes.as_deref()
     }
 
     pub(crate) fn ignore_folders(&self) -> Option<&[PathBuf]> { self.ignore_folders.as_deref() }
 
+    pub(crate) const fn journal_folders(&self) -> &[PathBuf] { self.journal_folders.as_slice() }
+
+    pub(crate) fn match_decisions(&self) -> Option<&Path> { self.match_decisions.as_deref() }
+
     pub(crate) fn obsidian_path(&self) -> &Path { &self.obsidian_path }
 
+    pub(crate) fn only_modify_owner(&self) -> Option<&str> { self.only_modify_owner.as_deref() }
+
     pub(crate) fn operational_timezone(&self) -> &str { &self.operational_timezone }
 
     pub(crate) fn output_folder(&self) -> &Path { &self.output_folder }
+
+    /// Today's date in the operational timezone, as `FORMAT_DATE`.
+    pub(crate) fn today(&self) -> String {
+        let timezone: Tz = self.operational_timezone.parse().unwrap_or(UTC);
+        Utc::now()
+            .with_timezone(&timezone)
+            .format(FORMAT_DATE)
+            .to_string()
+    }
+
+    /// Today's date in the operational timezone as a wikilink in `date_link_format` - the way
+    /// ok writes dates into notes.
+    pub(crate) fn today_link(&self) -> String {
+        let timezone: Tz = self.operational_timezone.parse().unwrap_or(UTC);
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+        format!(
+            "{OPENING_WIKILINK}{}{CLOSING_WIKILINK}",
+            today.format(&self.date_link_format)
+        )
+    }
 }
 
 #[cfg(test)]
@@ -363,6 +975,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_similar_title_threshold_validation() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for (threshold, valid) in [(0, false), (1, true), (90, true), (100, true), (101, false)] {
+            let result = test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.similar_title_threshold(Some(threshold));
+            });
+            assert_eq!(result.is_ok(), valid, "threshold {threshold}");
+        }
+    }
+
+    #[test]
+    fn test_table_of_contents_min_headings_validation() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for (count, valid) in [(0, false), (1, false), (2, true), (10, true)] {
+            let result = test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.table_of_contents_min_headings(Some(count));
+            });
+            assert_eq!(result.is_ok(), valid, "count {count}");
+        }
+    }
+
+    #[test]
+    fn test_block_id_placeholder_validation() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for (placeholder, valid) in [("^new", true), ("", false), ("new id", false)] {
+            let result = test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.block_id_placeholder(Some(placeholder.to_string()));
+            });
+            assert_eq!(result.is_ok(), valid, "placeholder {placeholder:?}");
+        }
+    }
+
+    #[test]
+    fn test_date_link_format_validation() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for (format, valid) in [
+            ("%Y-%m-%d", true),
+            ("%Y-%m-%d %a", true),
+            ("%d.%m.%Y", true),
+            ("%d.%m", false),
+            ("%Y|%m|%d", false),
+            ("%Q", false),
+        ] {
+            let result = test_support::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.date_link_format(format.to_string());
+            });
+            assert_eq!(result.is_ok(), valid, "format {format}");
+        }
+    }
+
     #[test]
     fn test_default_timezone() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,277 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::constants::DEFAULT_GENERATED_IMAGE_DUPLICATION;
+use crate::constants::DEFAULT_GENERATED_LINK_DENSITY;
+use crate::constants::DEFAULT_GENERATED_NOTE_COUNT;
+use crate::constants::DEFAULT_GENERATED_SEED;
+use crate::constants::GENERATE_VAULT_USAGE;
+use crate::constants::GENERATED_FILLER_WORDS;
+use crate::constants::GENERATED_IMAGE_PREFIX;
+use crate::constants::GENERATED_IMAGES_PER_NOTE_DIVISOR;
+use crate::constants::GENERATED_LINES_PER_NOTE;
+use crate::constants::GENERATED_NOTE_PREFIX;
+use crate::constants::GENERATED_WIKILINK_SHARE;
+use crate::constants::GENERATED_WORDS_PER_LINE;
+use crate::constants::IMAGE_DUPLICATION_FLAG;
+use crate::constants::LINK_DENSITY_FLAG;
+use crate::constants::MARKDOWN_SUFFIX;
+use crate::constants::NOTES_FLAG;
+use crate::constants::PNG_EXTENSION;
+use crate::constants::PNG_SIGNATURE;
+use crate::constants::SEED_FLAG;
+use crate::wikilink::ToWikilink;
+
+#[derive(Debug, Error)]
+pub(crate) enum VaultGeneratorError {
+    #[error("{GENERATE_VAULT_USAGE}")]
+    Usage,
+    #[error("{flag} expects {expected}, got {value:?}")]
+    InvalidValue {
+        flag:     String,
+        expected: &'static str,
+        value:    String,
+    },
+    #[error("{} is not empty - generate the vault into a new folder", .0.display())]
+    FolderNotEmpty(PathBuf),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// The shape of a generated vault. The same spec and seed always write the same files, so
+/// timings taken against one can be reproduced.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct VaultSpec {
+    pub note_count:        usize,
+    /// Fraction of body words that name another note - half as wikilinks, half as plain
+    /// mentions for back populate to link.
+    pub link_density:      f64,
+    /// Fraction of images whose bytes copy an earlier image.
+    pub image_duplication: f64,
+    pub seed:              u64,
+}
+
+impl Default for VaultSpec {
+    fn default() -> Self {
+        Self {
+            note_count:        DEFAULT_GENERATED_NOTE_COUNT,
+            link_density:      DEFAULT_GENERATED_LINK_DENSITY,
+            image_duplication: DEFAULT_GENERATED_IMAGE_DUPLICATION,
+            seed:              DEFAULT_GENERATED_SEED,
+        }
+    }
+}
+
+impl VaultSpec {
+    /// Reads `<folder> [--notes N] [--link-density F] [--image-duplication F] [--seed N]`.
+    pub(crate) fn from_args(args: &[String]) -> Result<(PathBuf, Self), VaultGeneratorError> {
+        let (folder, flags) = args.split_first().ok_or(VaultGeneratorError::Usage)?;
+        let mut spec = Self::default();
+
+        for pair in flags.chunks(2) {
+            let [flag, value] = pair else {
+                return Err(VaultGeneratorError::Usage);
+            };
+            match flag.as_str() {
+                NOTES_FLAG => spec.note_count = parse_value(flag, value, "a note count")?,
+                LINK_DENSITY_FLAG => spec.link_density = parse_fraction(flag, value)?,
+                IMAGE_DUPLICATION_FLAG => spec.image_duplication = parse_fraction(flag, value)?,
+                SEED_FLAG => spec.seed = parse_value(flag, value, "a number")?,
+                _ => return Err(VaultGeneratorError::Usage),
+            }
+        }
+
+        Ok((PathBuf::from(folder), spec))
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(
+    flag: &str,
+    value: &str,
+    expected: &'static str,
+) -> Result<T, VaultGeneratorError> {
+    value
+        .parse()
+        .map_err(|_| VaultGeneratorError::InvalidValue {
+            flag: flag.to_string(),
+            expected,
+            value: value.to_string(),
+        })
+}
+
+fn parse_fraction(flag: &str, value: &str) -> Result<f64, VaultGeneratorError> {
+    const EXPECTED: &str = "a fraction between 0 and 1";
+    let fraction: f64 = parse_value(flag, value, EXPECTED)?;
+    if (0.0..=1.0).contains(&fraction) {
+        Ok(fraction)
+    } else {
+        Err(VaultGeneratorError::InvalidValue {
+            flag:     flag.to_string(),
+            expected: EXPECTED,
+            value:    value.to_string(),
+        })
+    }
+}
+
+/// What `generate_vault` wrote.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct GeneratedVault {
+    pub notes:            usize,
+    pub wikilinks:        usize,
+    pub mentions:         usize,
+    pub images:           usize,
+    pub duplicate_images: usize,
+}
+
+/// `SplitMix64` - small, seedable and good enough to scatter links and duplicates.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut mixed = self.0;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        mixed ^ (mixed >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        let bound = u64::try_from(bound).unwrap_or(u64::MAX).max(1);
+        usize::try_from(self.next_u64() % bound).unwrap_or_default()
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        let high_bits = u32::try_from(self.next_u64() >> 32).unwrap_or(u32::MAX);
+        f64::from(high_bits) / f64::from(u32::MAX) < probability
+    }
+}
+
+/// Writes a synthetic vault described by `spec` into `folder`, which must be empty or not yet
+/// exist so a vault is never generated over real notes.
+pub(crate) fn generate_vault(
+    folder: &Path,
+    spec: &VaultSpec,
+) -> Result<GeneratedVault, VaultGeneratorError> {
+    if folder.exists() && fs::read_dir(folder)?.next().is_some() {
+        return Err(VaultGeneratorError::FolderNotEmpty(folder.to_path_buf()));
+    }
+    fs::create_dir_all(folder)?;
+
+    let mut random = SplitMix64(spec.seed);
+    let mut generated = GeneratedVault::default();
+
+    let image_count = spec.note_count / GENERATED_IMAGES_PER_NOTE_DIVISOR;
+    let mut image_bytes: Vec<Vec<u8>> = Vec::with_capacity(image_count);
+    for image in 0..image_count {
+        let bytes = if image > 0 && random.chance(spec.image_duplication) {
+            generated.duplicate_images += 1;
+            image_bytes[random.below(image)].clone()
+        } else {
+            let mut bytes = PNG_SIGNATURE.to_vec();
+            bytes.extend_from_slice(&random.next_u64().to_le_bytes());
+            bytes
+        };
+        fs::write(folder.join(image_name(image)), &bytes)?;
+        image_bytes.push(bytes);
+    }
+    generated.images = image_count;
+
+    for note in 0..spec.note_count {
+        let mut body = Vec::with_capacity(GENERATED_LINES_PER_NOTE);
+        if note < image_count {
+            body.push(format!("!{}", image_name(note).to_wikilink()));
+        }
+        for _ in 0..GENERATED_LINES_PER_NOTE {
+            let words: Vec<String> = (0..GENERATED_WORDS_PER_LINE)
+                .map(|_| {
+                    if !random.chance(spec.link_density) {
+                        return GENERATED_FILLER_WORDS[random.below(GENERATED_FILLER_WORDS.len())]
+                            .to_string();
+                    }
+                    let target = note_name(random.below(spec.note_count));
+                    if random.chance(GENERATED_WIKILINK_SHARE) {
+                        generated.wikilinks += 1;
+                        target.to_wikilink()
+                    } else {
+                        generated.mentions += 1;
+                        target.to_lowercase()
+                    }
+                })
+                .collect();
+            body.push(words.join(" "));
+        }
+
+        let name = note_name(note);
+        fs::write(
+            folder.join(format!("{name}{MARKDOWN_SUFFIX}")),
+            format!("---\ntitle: {name}\n---\n{}\n", body.join("\n")),
+        )?;
+        generated.notes += 1;
+    }
+
+    Ok(generated)
+}
+
+fn note_name(note: usize) -> String { format!("{GENERATED_NOTE_PREFIX}{note}") }
+
+fn image_name(image: usize) -> String { format!("{GENERATED_IMAGE_PREFIX}{image}.{PNG_EXTENSION}") }
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    reason = "tests should panic on unexpected values"
+)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::GeneratedVault;
+    use super::VaultSpec;
+    use super::generate_vault;
+    use crate::obsidian_repository::ObsidianRepository;
+    use crate::test_support;
+
+    #[test]
+    fn test_generated_vault_is_reproducible_and_scanned() {
+        let args: Vec<String> = ["vault", "--notes", "40", "--image-duplication", "0.5"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let (_, spec) = VaultSpec::from_args(&args).unwrap();
+        assert_eq!(spec.note_count, 40);
+
+        let temp_dir = TempDir::new().unwrap();
+        let generated = generate_vault(temp_dir.path(), &spec).unwrap();
+        assert_eq!(generated.notes, 40);
+        assert_eq!(generated.images, 4);
+        assert!(generated.wikilinks + generated.mentions > 0);
+        assert!(
+            generate_vault(temp_dir.path(), &spec).is_err(),
+            "a vault is never generated over existing files"
+        );
+
+        let again = TempDir::new().unwrap();
+        let regenerated: GeneratedVault = generate_vault(again.path(), &spec).unwrap();
+        assert_eq!(
+            generated, regenerated,
+            "the same seed writes the same vault"
+        );
+
+        let validated_config = test_support::get_test_validated_config(&temp_dir, None);
+        let obsidian_repository = ObsidianRepository::new(&validated_config).unwrap();
+        assert_eq!(obsidian_repository.markdown_files.len(), 40);
+        assert_eq!(obsidian_repository.image_files.len(), 4);
+
+        assert!(
+            VaultSpec::from_args(&[
+                "vault".to_string(),
+                "--link-density".to_string(),
+                "2".to_string()
+            ])
+            .is_err()
+        );
+    }
+}
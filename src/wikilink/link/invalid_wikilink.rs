@@ -2,6 +2,9 @@ use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use serde::Deserialize;
+use serde::Serialize;
+
 use crate::wikilink::constants::INVALID_WIKILINK_DOUBLE_ALIAS;
 use crate::wikilink::constants::INVALID_WIKILINK_EMAIL_ADDRESS;
 use crate::wikilink::constants::INVALID_WIKILINK_EMPTY;
@@ -15,7 +18,8 @@ use crate::wikilink::constants::INVALID_WIKILINK_UNMATCHED_MARKDOWN_LINK_OPENING
 use crate::wikilink::constants::INVALID_WIKILINK_UNMATCHED_OPENING;
 use crate::wikilink::constants::INVALID_WIKILINK_UNMATCHED_SINGLE;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum InvalidWikilinkReason {
     DoubleAlias,                  // e.g. [[A|B|C]]
     Empty,                        // [[]] or [[|]]
@@ -30,6 +34,33 @@ pub enum InvalidWikilinkReason {
     UnmatchedSingle,              // ] without [ or [ without ]
 }
 
+/// Reasons left out of the invalid wikilinks report unless `ignore_invalid_wikilink_reasons`
+/// says otherwise - these are intentional text, not broken links.
+pub const DEFAULT_IGNORED_INVALID_WIKILINK_REASONS: [InvalidWikilinkReason; 3] = [
+    InvalidWikilinkReason::EmailAddress,
+    InvalidWikilinkReason::RawHttpLink,
+    InvalidWikilinkReason::Tag,
+];
+
+impl InvalidWikilinkReason {
+    /// The name used for this reason in `ignore_invalid_wikilink_reasons`.
+    pub const fn config_name(self) -> &'static str {
+        match self {
+            Self::DoubleAlias => "double_alias",
+            Self::Empty => "empty",
+            Self::EmailAddress => "email_address",
+            Self::NestedOpening => "nested_opening",
+            Self::RawHttpLink => "raw_http_link",
+            Self::Tag => "tag",
+            Self::UnclosedInlineCode => "unclosed_inline_code",
+            Self::UnmatchedClosing => "unmatched_closing",
+            Self::UnmatchedMarkdownLinkOpening => "unmatched_markdown_link_opening",
+            Self::UnmatchedOpening => "unmatched_opening",
+            Self::UnmatchedSingle => "unmatched_single",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InvalidWikilink {
     pub content:     String, // The actual problematic wikilink text
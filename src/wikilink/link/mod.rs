@@ -2,6 +2,7 @@ mod invalid_wikilink;
 mod to_wikilink;
 mod wikilink;
 
+pub use invalid_wikilink::DEFAULT_IGNORED_INVALID_WIKILINK_REASONS;
 pub use invalid_wikilink::InvalidWikilink;
 pub use invalid_wikilink::InvalidWikilinkReason;
 pub use to_wikilink::ToWikilink;
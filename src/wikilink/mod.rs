@@ -2,6 +2,7 @@ mod constants;
 mod link;
 mod parser;
 
+pub use link::DEFAULT_IGNORED_INVALID_WIKILINK_REASONS;
 pub use link::InvalidWikilink;
 pub use link::InvalidWikilinkReason;
 pub use link::ToWikilink;
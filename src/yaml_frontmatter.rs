@@ -107,6 +107,11 @@ macro_rules! yaml_frontmatter_struct {
         }
 
         impl $name {
+            /// Each declared field's name and its type as written.
+            #[allow(dead_code, reason = "only the config lists its fields")]
+            pub const FIELDS: &'static [(&'static str, &'static str)] =
+                &[$((stringify!($field_name), stringify!($field_ty))),*];
+
             pub fn new() -> Self {
                 Self {
                     $(